@@ -2,9 +2,14 @@ use action_items_core::{ActionItemsCorePlugin, LauncherEvent};
 // Complete ECS service ecosystem - ALL SERVICES ACTIVE
 use action_items_ecs_bluetooth::BluetoothPlugin;
 use action_items_ecs_clipboard::ClipboardPlugin;
+use action_items_ecs_clipboard_history::ClipboardHistoryPlugin;
+use action_items_ecs_snippets::SnippetsPlugin;
+use action_items_ecs_quicklinks::QuicklinksPlugin;
+use action_items_ecs_script_commands::ScriptCommandsPlugin;
+use action_items_ecs_calculator::CalculatorPlugin;
 use action_items_ecs_compression::CompressionPlugin;
 use action_items_ecs_permissions::{PermissionPlugin, PermissionWizardPlugin, PermissionType};
-use action_items_ecs_search::{SearchPlugin, SearchUIPlugin};
+use action_items_ecs_search::{FrecencyPlugin, SearchPlugin, SearchUIPlugin};
 use action_items_ecs_settings::{SettingsPlugin, SettingsUIPlugin};
 use action_items_ecs_preferences::{PreferencesPlugin, PreferencesUIPlugin};
 use action_items_ecs_search_aggregator::SearchAggregatorPlugin;
@@ -174,13 +179,19 @@ pub fn configure_app() -> App {
         UserSettingsPlugin,      // User settings with database backend ✅
         EcsCachePlugin,          // Cache service for performance ✅
         SearchPlugin,            // Core search logic
+        FrecencyPlugin::default(), // Launch history ranking persisted in SurrealDB
         SearchUIPlugin::default(), // Search UI components
         SearchAggregatorPlugin, // Search coordination across plugins ✅
+        CalculatorPlugin,       // Inline calculator and unit conversions
     ))
     // ECS Service ecosystem - Core services
     .add_plugins((
         BluetoothPlugin,              // Cross-platform Bluetooth operations ✅
         ClipboardPlugin,              // ECS clipboard service ✅
+        ClipboardHistoryPlugin::default(), // Clipboard history persisted in SurrealDB
+        SnippetsPlugin,               // Snippets with placeholder expansion
+        QuicklinksPlugin,             // Quicklinks opening URL and file templates
+        ScriptCommandsPlugin::default(), // Script commands from local script directories
         CompressionPlugin::default(), // Compression service for data optimization ✅
         PermissionPlugin,             // ECS permissions service ✅
         PermissionWizardPlugin::default()
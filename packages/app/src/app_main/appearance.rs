@@ -0,0 +1,45 @@
+//! Appearance settings applied to the running launcher
+//!
+//! Only text size is applied so far. There is a single color palette, so the dark and light
+//! theme names and `follow_system_appearance` take effect once named themes exist; the window
+//! mode is likewise not applied yet.
+
+use action_items_ecs_surrealdb::LiveAction;
+use action_items_ecs_ui::theme::Theme;
+use action_items_ecs_ui::theme::typography::TypographyTheme;
+use action_items_ecs_user_settings::AppearanceSettingsChanged;
+use bevy::prelude::*;
+use tracing::warn;
+
+/// Scale the theme's font sizes when `appearance_settings` changes
+///
+/// Text spawned afterwards uses the new sizes.
+pub fn apply_appearance_settings_system(
+    mut changes: EventReader<AppearanceSettingsChanged>,
+    theme: Option<ResMut<Theme>>,
+) {
+    let Some(change) = changes.read().last() else {
+        return;
+    };
+    let Some(mut theme) = theme else {
+        return;
+    };
+
+    let factor = match change.action {
+        LiveAction::Delete => 1.0,
+        _ => text_size_factor(&change.record.text_size),
+    };
+    theme.typography = TypographyTheme::scaled(factor);
+}
+
+fn text_size_factor(text_size: &str) -> f32 {
+    match text_size {
+        "small" => 0.875,
+        "medium" => 1.0,
+        "large" => 1.125,
+        other => {
+            warn!("Unknown text size '{other}', using medium");
+            1.0
+        },
+    }
+}
@@ -4,6 +4,7 @@
 //! decomposed into focused modules for better maintainability.
 
 pub mod app_config;
+pub mod appearance;
 pub mod events;
 pub mod hotkey_setup;
 pub mod systems;
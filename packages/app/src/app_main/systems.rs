@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use super::appearance::apply_appearance_settings_system;
 use super::events::bridge_preferences_to_settings;
 use super::hotkey_setup::setup_global_hotkey_callback;
 use super::window_config::configure_non_activating_panel;
@@ -14,8 +15,8 @@ use crate::events::{
     real_hotkey_capture_system, update_current_query_from_events,
 };
 use crate::hotkeys::{
-    handle_hotkey_registration_system, handle_launcher_hotkey_press_system,
-    register_launcher_hotkey_system,
+    apply_launcher_hotkey_settings_system, handle_hotkey_registration_system,
+    handle_launcher_hotkey_press_system, register_launcher_hotkey_system,
 };
 use crate::input::{
     // Focus system imports
@@ -52,8 +53,10 @@ use crate::input::{
 };
 // Preferences UI now handled by ecs-preferences service
 use action_items_ecs_permissions::{PermissionChanged, PermissionStatus, PermissionType};
-use action_items_core::search::setup_search_index;
-use crate::search::{handle_text_input_to_search, log_search_events, update_search_ui};
+use action_items_core::search::{persist_search_index_system, setup_search_index};
+use crate::search::{
+    handle_search_query_changed, handle_text_input_to_search, log_search_events, update_search_ui,
+};
 // UI setup now handled by ECS UI service
 // UI systems will be integrated when needed - removing unused imports
 use crate::window::{
@@ -195,6 +198,7 @@ pub fn add_update_systems(app: &mut App) {
             (
                 action_items_ui::systems::search_input_system,
                 action_items_ui::systems::results_visibility_system,
+                handle_search_query_changed, // Search bar edits to CurrentQuery
             )
                 .chain(),
             // Simplified search integration - ECS search aggregator handles coordination
@@ -286,6 +290,18 @@ pub fn add_update_systems(app: &mut App) {
         ),
     );
 
+    // Settings changed in the database, including by other devices through cloud sync
+    app.add_systems(
+        Update,
+        (
+            apply_launcher_hotkey_settings_system,
+            apply_appearance_settings_system,
+        ),
+    );
+
+    // Persist incremental SearchIndex changes to the on-disk journal
+    app.add_systems(Update, persist_search_index_system);
+
     // ECS Storage systems now handled by ecs-filesystem service plugin
 
     // Advanced UI systems - gradient, responsive, search, and setup systems
@@ -3,16 +3,29 @@
 //! Systems for integrating with the ECS hotkey service instead of manual hotkey management.
 
 use action_items_core::LauncherEvent;
+use action_items_ecs_surrealdb::LiveAction;
+use action_items_ecs_user_settings::{HotkeySetting, HotkeySettingsChanged};
 use bevy::prelude::*;
 use ecs_hotkey::{
-    HotkeyBinding, HotkeyPressed, HotkeyRegisterCompleted, HotkeyRegisterRequested,
+    HotkeyBinding, HotkeyDefinition, HotkeyPressed, HotkeyRegisterCompleted,
+    HotkeyRegisterRequested, HotkeyRegistry, HotkeyUnregisterRequested,
     get_default_hotkey_combinations,
 };
-use tracing::{error, info};
+use global_hotkey::hotkey::HotKey;
+use tracing::{error, info, warn};
 
 use crate::app_main::AppState;
 use crate::window::activation::{ActivationReason, WindowActivationEvent};
 
+/// Action of the binding that toggles the launcher
+const LAUNCHER_ACTION: &str = "launcher_toggle";
+
+/// Requester of the launcher's hotkey registrations
+const LAUNCHER_REQUESTER: &str = "action_items_launcher";
+
+/// `hotkey_id` of the launcher's record in `hotkey_settings`
+const LAUNCHER_HOTKEY_ID: &str = "launcher";
+
 /// Startup system to register launcher hotkey using ECS service
 pub fn register_launcher_hotkey_system(mut hotkey_events: EventWriter<HotkeyRegisterRequested>) {
     info!("Registering launcher hotkey via ECS hotkey service");
@@ -21,8 +34,8 @@ pub fn register_launcher_hotkey_system(mut hotkey_events: EventWriter<HotkeyRegi
     let preferred_combinations = get_default_hotkey_combinations();
 
     if let Some(primary_hotkey) = preferred_combinations.first() {
-        let binding = HotkeyBinding::new(primary_hotkey.clone(), "launcher_toggle")
-            .with_requester("action_items_launcher");
+        let binding = HotkeyBinding::new(primary_hotkey.clone(), LAUNCHER_ACTION)
+            .with_requester(LAUNCHER_REQUESTER);
 
         hotkey_events.write(HotkeyRegisterRequested {
             binding,
@@ -42,7 +55,7 @@ pub fn handle_hotkey_registration_system(
     mut registration_events: EventReader<HotkeyRegisterCompleted>,
 ) {
     for event in registration_events.read() {
-        if event.binding.action == "launcher_toggle" {
+        if event.binding.action == LAUNCHER_ACTION {
             if event.success {
                 info!(
                     "✅ Launcher hotkey registered successfully: {}",
@@ -81,7 +94,7 @@ pub fn handle_launcher_hotkey_press_system(
     mut activation_events: EventWriter<WindowActivationEvent>,
 ) {
     for event in hotkey_events.read() {
-        if event.binding.action == "launcher_toggle" {
+        if event.binding.action == LAUNCHER_ACTION {
             info!(
                 "Launcher hotkey pressed: {}",
                 event.binding.definition.description
@@ -102,3 +115,73 @@ pub fn handle_launcher_hotkey_press_system(
         }
     }
 }
+
+/// Rebind the launcher hotkey when its record in `hotkey_settings` changes
+///
+/// A disabled record unregisters the hotkey, and deleting the record restores the default.
+pub fn apply_launcher_hotkey_settings_system(
+    mut changes: EventReader<HotkeySettingsChanged>,
+    registry: Res<HotkeyRegistry>,
+    mut register_events: EventWriter<HotkeyRegisterRequested>,
+    mut unregister_events: EventWriter<HotkeyUnregisterRequested>,
+) {
+    // Only the latest change to the launcher's record matters
+    let Some(change) = changes
+        .read()
+        .filter(|change| change.record.hotkey_id == LAUNCHER_HOTKEY_ID)
+        .last()
+    else {
+        return;
+    };
+
+    let definition = match change.action {
+        LiveAction::Delete => get_default_hotkey_combinations().into_iter().next(),
+        _ if !change.record.enabled => None,
+        _ => match hotkey_definition(&change.record) {
+            Ok(definition) => Some(definition),
+            Err(e) => {
+                warn!("Ignoring launcher hotkey setting: {e}");
+                return;
+            },
+        },
+    };
+
+    let current: Vec<&HotkeyBinding> = registry
+        .registered_hotkeys
+        .values()
+        .filter(|binding| binding.action == LAUNCHER_ACTION)
+        .collect();
+    if let Some(definition) = &definition
+        && current.iter().any(|binding| {
+            binding.definition.modifiers == definition.modifiers
+                && binding.definition.code == definition.code
+        })
+    {
+        return;
+    }
+
+    for binding in current {
+        unregister_events.write(HotkeyUnregisterRequested {
+            hotkey_id: binding.id.clone(),
+            requester: LAUNCHER_REQUESTER.to_string(),
+        });
+    }
+    if let Some(definition) = definition {
+        info!("Rebinding launcher hotkey to {}", definition.description);
+        register_events.write(HotkeyRegisterRequested {
+            binding: HotkeyBinding::new(definition, LAUNCHER_ACTION)
+                .with_requester(LAUNCHER_REQUESTER),
+        });
+    }
+}
+
+/// Hotkey of a settings record, such as modifiers `["cmd"]` with key code `space`
+fn hotkey_definition(setting: &HotkeySetting) -> Result<HotkeyDefinition, String> {
+    let mut parts: Vec<&str> = setting.modifiers.iter().map(String::as_str).collect();
+    parts.push(&setting.key_code);
+    let text = parts.join("+");
+    let hotkey: HotKey = text
+        .parse()
+        .map_err(|e| format!("'{text}' is not a valid hotkey: {e}"))?;
+    Ok(HotkeyDefinition::new(hotkey.mods, hotkey.key))
+}
@@ -27,6 +27,21 @@ pub fn handle_text_input_to_search(
     }
 }
 
+/// Forward search bar edits to CurrentQuery
+///
+/// Changing the query makes the aggregator cancel searches still running for the old one.
+#[inline]
+pub fn handle_search_query_changed(
+    mut query_changed_events: EventReader<action_items_ui::SearchQueryChanged>,
+    mut current_query: ResMut<CurrentQuery>,
+) {
+    if let Some(event) = query_changed_events.read().last()
+        && current_query.0 != event.query
+    {
+        current_query.0 = event.query.clone();
+    }
+}
+
 /// Direct UI update system using ECS search aggregator results
 #[inline]
 pub fn update_search_ui(
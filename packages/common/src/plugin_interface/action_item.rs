@@ -49,7 +49,7 @@ pub struct ItemBadge {
     pub tooltip: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActionType {
     OpenUrl(String),
     OpenFile(PathBuf),
@@ -35,3 +35,16 @@ pub struct PluginPermissions {
     pub contacts: bool,
     pub calendar: bool,
 }
+
+/// Execution limits a WASM plugin asks for; unset fields take the launcher's defaults
+///
+/// The launcher clamps every value to its own caps, so a manifest can only lower them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ResourceLimits {
+    /// Fuel available to each call into the plugin
+    pub fuel: Option<u64>,
+    /// Maximum linear memory, in 64 KiB WebAssembly pages
+    pub memory_pages: Option<u32>,
+    /// Wall-clock budget for each call, in milliseconds
+    pub timeout_ms: Option<u64>,
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::capabilities::{PluginCapabilities, PluginPermissions};
+use super::capabilities::{PluginCapabilities, PluginPermissions, ResourceLimits};
 use super::commands::{ActionDefinition, CommandDefinition};
 use super::config::{ConfigurationField, PreferenceField};
 
@@ -27,6 +27,8 @@ pub struct PluginManifest {
     // Capabilities and permissions
     pub capabilities: PluginCapabilities,
     pub permissions: PluginPermissions,
+    #[serde(default)]
+    pub resource_limits: ResourceLimits,
 
     // Configuration
     pub configuration: Vec<ConfigurationField>,
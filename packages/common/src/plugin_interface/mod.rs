@@ -9,7 +9,7 @@ pub mod manifest;
 // Re-export action item types for convenience
 pub use action_item::{ActionItem, ActionType, Icon, ItemAction, ItemBadge, Shortcut};
 // Re-export supporting types for convenience
-pub use capabilities::{PluginCapabilities, PluginPermissions};
+pub use capabilities::{PluginCapabilities, PluginPermissions, ResourceLimits};
 pub use commands::{
     ActionDefinition, ArgumentDefinition, ArgumentType, CommandDefinition, CommandMode,
 };
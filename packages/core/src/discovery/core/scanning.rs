@@ -29,12 +29,12 @@ pub fn scan_directory_for_plugin_wrappers(
         let path = entry.path();
 
         if path.is_file() && is_plugin_file(&path) {
-            if let Ok(wrapper) = create_plugin_wrapper_from_file(&path) {
+            if let Ok(wrapper) = create_plugin_wrapper_from_file(&path, config) {
                 wrappers.push(wrapper);
             }
         } else if path.is_dir() && path.join("Cargo.toml").exists() {
             // This is a Rust plugin project - build and create wrapper
-            if let Ok(wrapper) = create_plugin_wrapper_from_rust_project(&path) {
+            if let Ok(wrapper) = create_plugin_wrapper_from_rust_project(&path, config) {
                 wrappers.push(wrapper);
             }
         } else if path.is_dir() && config.recursive_scan {
@@ -1,5 +1,8 @@
 use std::path::{Path, PathBuf};
 
+use action_items_common::plugin_interface::PluginManifest;
+use action_items_native::ipc::SupervisorConfig;
+
 use crate::plugins::extism::wrapper::ExtismPluginWrapper;
 use crate::plugins::native::wrapper::NativePluginWrapper;
 use crate::raycast::wrapper::RaycastPluginWrapper;
@@ -43,14 +46,29 @@ impl DiscoveredPlugin {
             Self::Deno(wrapper) => &wrapper.metadata().id,
         }
     }
+
+    /// Get the plugin manifest; Raycast extensions describe themselves with their package instead
+    pub fn manifest(&self) -> Option<&PluginManifest> {
+        match self {
+            Self::Native(wrapper) => Some(&wrapper.metadata().manifest),
+            Self::Extism(wrapper) => Some(&wrapper.metadata().manifest),
+            Self::Raycast(_) => None,
+            Self::Deno(wrapper) => Some(&wrapper.metadata().manifest),
+        }
+    }
 }
 
+/// Environment variable that, when set, enables [`DiscoveryConfig::native_isolation`]
+pub const ISOLATE_NATIVE_PLUGINS_VAR: &str = "ACTION_ITEMS_ISOLATE_NATIVE_PLUGINS";
+
 /// Plugin discovery configuration
 pub struct DiscoveryConfig {
     pub plugin_directories: Vec<PathBuf>,
     pub auto_discover: bool,
     pub recursive_scan: bool,
     pub max_depth: usize,
+    /// Run native plugins in supervised host processes instead of the launcher's own
+    pub native_isolation: Option<SupervisorConfig>,
 }
 
 impl Default for DiscoveryConfig {
@@ -67,6 +85,8 @@ impl Default for DiscoveryConfig {
         if let Some(data_dir) = dirs::data_local_dir() {
             dirs.push(data_dir.join("Action Items/plugins"));
         }
+        // Plugins installed from signed packages
+        dirs.push(crate::plugins::PackageInstaller::default_install_dir());
 
         // System directories
         #[cfg(target_os = "linux")]
@@ -100,6 +120,8 @@ impl Default for DiscoveryConfig {
             auto_discover: true,
             recursive_scan: true,
             max_depth: 3,
+            native_isolation: std::env::var_os(ISOLATE_NATIVE_PLUGINS_VAR)
+                .map(|_| SupervisorConfig::default()),
         }
     }
 }
@@ -1,14 +1,16 @@
 use std::path::Path;
 
 use action_items_common::plugin_interface::PluginManifest;
+use action_items_native::ipc::IsolatedPlugin;
 use log::info;
 
 use super::build_management::find_or_build_plugin_library;
-use super::types::DiscoveredPlugin;
+use super::types::{DiscoveredPlugin, DiscoveryConfig};
 use crate::discovery::adapter::FFIToNativeAdapter;
 use crate::discovery::loader::{DynamicPlugin, is_native_plugin_file};
 use crate::plugins::extism::ExtismPluginAdapter;
 use crate::plugins::extism::wrapper::ExtismPluginWrapper;
+use crate::plugins::native::isolation::host_exit_sender;
 use crate::plugins::native::wrapper::NativePluginWrapper;
 use crate::{Error, Result};
 
@@ -44,11 +46,15 @@ pub fn create_minimal_manifest(plugin_name: &str, description: &str) -> PluginMa
         max_launcher_version: None,
         update_url: None,
         changelog_url: None,
+        resource_limits: Default::default(),
     }
 }
 
 /// Creates a plugin wrapper from a file (WASM or native dynamic library)
-pub fn create_plugin_wrapper_from_file(path: &Path) -> Result<DiscoveredPlugin> {
+pub fn create_plugin_wrapper_from_file(
+    path: &Path,
+    config: &DiscoveryConfig,
+) -> Result<DiscoveredPlugin> {
     let plugin_name = path
         .file_stem()
         .and_then(|s| s.to_str())
@@ -100,9 +106,23 @@ pub fn create_plugin_wrapper_from_file(path: &Path) -> Result<DiscoveredPlugin>
         Ok(DiscoveredPlugin::Extism(wrapper))
     } else if is_native_plugin_file(path) {
         // Load native plugin dynamically and create NativePluginWrapper
-        let dynamic_plugin = DynamicPlugin::load_from_path(path)?;
-        let plugin = dynamic_plugin.into_plugin();
-        let adapter = FFIToNativeAdapter::new(plugin);
+        let adapter = match &config.native_isolation {
+            Some(supervisor) => {
+                let plugin = IsolatedPlugin::spawn(
+                    path,
+                    supervisor.clone(),
+                    Some(host_exit_sender()),
+                )
+                .map_err(|e| Error::PluginLoadError(e.to_string()))?;
+                FFIToNativeAdapter::isolated(plugin)
+            },
+            None => {
+                let dynamic_plugin = DynamicPlugin::load_from_path(path)?;
+                let plugin = dynamic_plugin.into_plugin();
+                FFIToNativeAdapter::new(plugin)
+                    .map_err(|e| Error::PluginLoadError(e.to_string()))?
+            },
+        };
         let wrapper = NativePluginWrapper::new(Box::new(adapter))?;
         Ok(DiscoveredPlugin::Native(wrapper))
     } else {
@@ -114,12 +134,15 @@ pub fn create_plugin_wrapper_from_file(path: &Path) -> Result<DiscoveredPlugin>
 }
 
 /// Creates a plugin wrapper from a Rust plugin project directory
-pub fn create_plugin_wrapper_from_rust_project(dir: &Path) -> Result<DiscoveredPlugin> {
+pub fn create_plugin_wrapper_from_rust_project(
+    dir: &Path,
+    config: &DiscoveryConfig,
+) -> Result<DiscoveredPlugin> {
     info!("Found Rust plugin project at {}", dir.display());
 
     // Build the plugin and get the library path
     let lib_path = find_or_build_plugin_library(dir)?;
 
     // Create wrapper from the built library
-    create_plugin_wrapper_from_file(&lib_path)
+    create_plugin_wrapper_from_file(&lib_path, config)
 }
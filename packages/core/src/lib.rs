@@ -96,6 +96,43 @@ impl Plugin for ActionItemsCorePlugin {
         app.add_event::<LauncherEvent>();
         app.add_event::<ConfigEvent>();
 
+        // Audit trail of plugin host calls denied by their manifest permissions
+        app.add_event::<plugins::PermissionDenied>();
+        app.add_systems(Update, plugins::permissions::forward_permission_denials_system);
+
+        // Deaths of out-of-process native plugin hosts
+        app.add_event::<action_items_native::PluginErrorEvent>();
+        app.add_systems(Update, plugins::native::isolation::forward_plugin_host_exits_system);
+
+        // WASM plugins that ran out of fuel, memory or time are degraded
+        app.add_systems(Update, plugins::extism::limits::handle_wasm_limit_trips_system);
+
+        // Plugin load outcomes, command and hotkey registration, and dev-mode hot reload
+        app.add_event::<plugins::PluginLoaded>()
+            .add_event::<plugins::PluginLoadFailed>()
+            .init_resource::<plugins::PluginCommandRegistrations>()
+            .add_systems(Startup, plugins::hot_reload::start_plugin_hot_reload_system)
+            .add_systems(
+                Update,
+                (
+                    plugins::hot_reload::queue_plugin_reloads_system,
+                    plugins::hot_reload::finish_plugin_reloads_system,
+                    plugins::handle_plugin_loading_tasks,
+                    plugins::hot_reload::register_loaded_plugin_commands_system,
+                )
+                    .chain(),
+            );
+
+        // Services behind the Extism host functions that answer through plugin callbacks
+        app.add_systems(
+            Update,
+            (
+                plugins::extism::host_services::process_extism_host_requests_system,
+                plugins::extism::host_services::deliver_extism_host_responses_system,
+            )
+                .chain(),
+        );
+
         let mut service_count = 2; // Base services (ServiceBridge + SearchAggregator)
 
         //service_count += 1; // Cache service - temporarily disabled
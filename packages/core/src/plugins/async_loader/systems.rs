@@ -10,7 +10,7 @@ use log::{error, info};
 use super::context::{
     create_deno_plugin_context, create_plugin_context, create_raycast_plugin_context,
 };
-use super::events::PluginLoadingStarted;
+use super::events::{PluginLoadFailed, PluginLoaded, PluginLoadingStarted};
 use super::progress::PluginLoadingProgress;
 use super::tasks::{LoadingPlugin, PluginLoadingTask};
 use crate::discovery::{DiscoveredPlugin, discover_plugin_wrappers};
@@ -31,8 +31,6 @@ pub fn start_async_plugin_loading(
 
     info!("Starting async plugin loading...");
 
-    let thread_pool = AsyncComputeTaskPool::get();
-
     // Discover plugins synchronously first
     let discovered_plugins = discover_plugin_wrappers();
     let plugin_count = discovered_plugins.len();
@@ -54,37 +52,46 @@ pub fn start_async_plugin_loading(
 
     // Create loading tasks for each discovered plugin
     for plugin in discovered_plugins {
-        let entity = commands.spawn_empty().id();
-        let plugin_info = LoadingPlugin {
-            path: match &plugin {
-                DiscoveredPlugin::Native(w) => PathBuf::from(format!("native:{}", w.metadata().id)),
-                DiscoveredPlugin::Extism(w) => PathBuf::from(format!("extism:{}", w.metadata().id)),
-                DiscoveredPlugin::Raycast(w) => w.metadata().path.clone(),
-                DiscoveredPlugin::Deno(w) => PathBuf::from(format!("deno:{}", w.metadata().id)),
-            },
-            plugin_type: match &plugin {
-                DiscoveredPlugin::Native(_) => "native".to_string(),
-                DiscoveredPlugin::Extism(_) => "extism".to_string(),
-                DiscoveredPlugin::Raycast(_) => "raycast".to_string(),
-                DiscoveredPlugin::Deno(_) => "deno".to_string(),
-            },
-            plugin_data: plugin.clone(),
-        };
-
-        // Create async loading task for this plugin
-        let plugin_clone = plugin.clone();
-        let entity_id = entity;
-        let app_directories_clone = (*app_directories).clone();
-        let loading_task = thread_pool.spawn(async move {
-            create_plugin_loading_task(plugin_clone, entity_id, app_directories_clone).await
-        });
-
-        commands
-            .entity(entity)
-            .insert((plugin_info, PluginLoadingTask(loading_task)));
+        spawn_plugin_loading(&mut commands, plugin, (*app_directories).clone());
     }
 }
 
+/// Spawn the entity that initializes a plugin and registers it with the service bridge
+pub fn spawn_plugin_loading(
+    commands: &mut Commands,
+    plugin: DiscoveredPlugin,
+    app_directories: crate::config::AppDirectories,
+) {
+    let thread_pool = AsyncComputeTaskPool::get();
+    let entity = commands.spawn_empty().id();
+    let plugin_info = LoadingPlugin {
+        path: match &plugin {
+            DiscoveredPlugin::Native(w) => PathBuf::from(format!("native:{}", w.metadata().id)),
+            DiscoveredPlugin::Extism(w) => PathBuf::from(format!("extism:{}", w.metadata().id)),
+            DiscoveredPlugin::Raycast(w) => w.metadata().path.clone(),
+            DiscoveredPlugin::Deno(w) => PathBuf::from(format!("deno:{}", w.metadata().id)),
+        },
+        plugin_type: match &plugin {
+            DiscoveredPlugin::Native(_) => "native".to_string(),
+            DiscoveredPlugin::Extism(_) => "extism".to_string(),
+            DiscoveredPlugin::Raycast(_) => "raycast".to_string(),
+            DiscoveredPlugin::Deno(_) => "deno".to_string(),
+        },
+        plugin_data: plugin.clone(),
+    };
+
+    // Create async loading task for this plugin
+    let plugin_clone = plugin.clone();
+    let entity_id = entity;
+    let loading_task = thread_pool.spawn(async move {
+        create_plugin_loading_task(plugin_clone, entity_id, app_directories).await
+    });
+
+    commands
+        .entity(entity)
+        .insert((plugin_info, PluginLoadingTask(loading_task)));
+}
+
 /// Creates the async plugin loading task logic
 async fn create_plugin_loading_task(
     plugin_clone: DiscoveredPlugin,
@@ -212,6 +219,9 @@ fn handle_initialization_result(
     plugin_clone: DiscoveredPlugin,
     entity_id: Entity,
 ) {
+    // The task has finished either way and must not be polled again
+    world.entity_mut(entity_id).remove::<PluginLoadingTask>();
+
     match initialization_result {
         Ok(_) => {
             // Plugin initialized successfully - register with service bridge
@@ -221,14 +231,10 @@ fn handle_initialization_result(
                         info!("Successfully registered plugin with service bridge");
 
                         // Add service bridge registration to entity
-                        world
-                            .entity_mut(entity_id)
-                            .insert(registration)
-                            .remove::<PluginLoadingTask>();
+                        world.entity_mut(entity_id).insert(registration);
                     },
                     Err(e) => {
                         error!("Failed to register plugin with service bridge: {}", e);
-                        world.entity_mut(entity_id).remove::<PluginLoadingTask>();
                     },
                 }
             }
@@ -237,6 +243,11 @@ fn handle_initialization_result(
             if let Some(mut progress) = world.get_resource_mut::<PluginLoadingProgress>() {
                 progress.mark_loaded();
             }
+
+            world.send_event(PluginLoaded {
+                plugin_id: plugin_clone.id().to_string(),
+                plugin_name: plugin_clone.name().to_string(),
+            });
         },
         Err(error_msg) => {
             error!("Failed to initialize plugin: {}", error_msg);
@@ -246,8 +257,14 @@ fn handle_initialization_result(
                 progress.mark_failed();
             }
 
-            // Remove failed plugin entity
-            world.entity_mut(entity_id).remove::<PluginLoadingTask>();
+            let path = world
+                .get::<LoadingPlugin>(entity_id)
+                .map(|plugin| plugin.path.clone())
+                .unwrap_or_default();
+            world.send_event(PluginLoadFailed {
+                path,
+                error: error_msg,
+            });
         },
     }
 }
@@ -60,6 +60,7 @@ impl PluginBuilder {
                 max_launcher_version: None,
                 update_url: None,
                 changelog_url: None,
+                resource_limits: Default::default(),
             },
             search_handler: None,
             action_handler: None,
@@ -88,32 +89,4 @@ impl PluginBuilder {
             service_bridge,
         })
     }
-
-    /// Build the plugin and return an FFI-safe pointer (for use in extern functions)
-    #[allow(dead_code)]
-    pub fn build_ffi(
-        self,
-    ) -> Result<crate::plugins::interface::ffi::LauncherPluginFFI, crate::Error> {
-        let service_bridge = self
-            .service_bridge
-            .clone()
-            .unwrap_or_else(|| Arc::new(ServiceBridge::new()));
-
-        // Create a new BuiltPlugin instance for FFI conversion
-        let ffi_plugin_instance = BuiltPlugin {
-            manifest: self.manifest.clone(),
-            search_handler: self.search_handler.clone(),
-            action_handler: self.action_handler.clone(),
-            refresh_handler: self.refresh_handler.clone(),
-            service_bridge,
-        };
-
-        // Create FFI-safe wrapper using the helper function
-        let ffi_plugin = crate::plugins::interface::ffi::ffi_helpers::plugin_to_ffi(Box::new(
-            ffi_plugin_instance,
-        )
-            as Box<dyn crate::plugins::interface::ffi::LauncherPlugin>);
-
-        Ok(ffi_plugin)
-    }
 }
@@ -17,6 +17,8 @@ impl ExtismPluginAdapter {
         task_pool: &AsyncComputeTaskPool,
     ) -> Task<Result<Option<Value>, Error>> {
         let plugin_arc = Arc::clone(&self.plugin);
+        let plugin_id = self.manifest.id.clone();
+        let limits = self.limits;
 
         task_pool.spawn(async move {
             #[derive(Serialize)]
@@ -40,15 +42,19 @@ impl ExtismPluginAdapter {
 
             let mut plugin_guard = plugin_arc.lock();
 
-            let response_json =
-                match plugin_guard.call::<String, String>("plugin_execute_action", request_json) {
-                    Ok(response) => response,
-                    Err(e) => {
-                        return Err(Error::PluginError(format!(
-                            "ExtismAdapter: Failed to call plugin_execute_action: {e}"
-                        )));
-                    },
-                };
+            let response_json = match limits.call::<String, String>(
+                &mut plugin_guard,
+                &plugin_id,
+                "plugin_execute_action",
+                request_json,
+            ) {
+                Ok(response) => response,
+                Err(e) => {
+                    return Err(Error::PluginError(format!(
+                        "ExtismAdapter: Failed to call plugin_execute_action: {e}"
+                    )));
+                },
+            };
 
             if response_json.is_empty() || response_json.to_lowercase() == "null" {
                 Ok(None)
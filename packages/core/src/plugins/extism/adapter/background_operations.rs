@@ -14,6 +14,8 @@ impl ExtismPluginAdapter {
         task_pool: &AsyncComputeTaskPool,
     ) -> Task<Result<(), Error>> {
         let plugin_arc = Arc::clone(&self.plugin);
+        let plugin_id = self.manifest.id.clone();
+        let limits = self.limits;
 
         task_pool.spawn(async move {
             let mut plugin_guard = plugin_arc.lock();
@@ -25,8 +27,13 @@ impl ExtismPluginAdapter {
                     ))
                 })?;
 
-                plugin_guard
-                    .call::<String, ()>("plugin_background_refresh", context_json)
+                limits
+                    .call::<String, ()>(
+                        &mut plugin_guard,
+                        &plugin_id,
+                        "plugin_background_refresh",
+                        context_json,
+                    )
                     .map_err(|e| {
                         Error::PluginError(format!(
                             "ExtismAdapter: Failed to call plugin_background_refresh: {e}"
@@ -17,6 +17,8 @@ impl ExtismPluginAdapter {
         task_pool: &AsyncComputeTaskPool,
     ) -> Task<Result<Option<Value>, Error>> {
         let plugin_arc = Arc::clone(&self.plugin);
+        let plugin_id = self.manifest.id.clone();
+        let limits = self.limits;
 
         task_pool.spawn(async move {
             #[derive(Serialize)]
@@ -40,8 +42,13 @@ impl ExtismPluginAdapter {
 
             let mut plugin_guard = plugin_arc.lock();
 
-            let response_json = plugin_guard
-                .call::<String, String>("plugin_execute_command", request_json)
+            let response_json = limits
+                .call::<String, String>(
+                    &mut plugin_guard,
+                    &plugin_id,
+                    "plugin_execute_command",
+                    request_json,
+                )
                 .map_err(|e| {
                     Error::PluginError(format!(
                         "ExtismAdapter: Failed to call plugin_execute_command: {e}"
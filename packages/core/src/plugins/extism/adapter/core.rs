@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use extism::{Plugin, PluginBuilder};
+use extism::Plugin;
+use serde_json::Value;
 
+use crate::plugins::extism::limits::WasmLimits;
 use crate::plugins::extism::manifest::create_manifest_from_data;
 use crate::plugins::interface::{PluginContext, PluginManifest};
 
@@ -10,6 +13,7 @@ pub struct ExtismPluginAdapter {
     pub(super) plugin: Arc<parking_lot::Mutex<Plugin>>,
     pub(super) manifest: PluginManifest,
     pub(super) context: Arc<parking_lot::Mutex<Option<PluginContext>>>,
+    pub(super) limits: WasmLimits,
 }
 
 impl ExtismPluginAdapter {
@@ -19,17 +23,30 @@ impl ExtismPluginAdapter {
         plugin_data: Vec<u8>,
         host_functions: Vec<extism::Function>,
     ) -> crate::Result<Self> {
-        let extism_manifest_data = create_manifest_from_data(plugin_data);
-        let plugin = PluginBuilder::new(extism_manifest_data)
-            .with_wasi(true)
-            .with_functions(host_functions)
-            .build()
+        let limits = WasmLimits::from_requested(&manifest.resource_limits);
+        let plugin = limits
+            .build_plugin(create_manifest_from_data(plugin_data), host_functions)
             .map_err(|e| crate::Error::Extism(e.to_string()))?;
 
         Ok(Self {
             plugin: Arc::new(parking_lot::Mutex::new(plugin)),
             manifest,
             context: Arc::new(parking_lot::Mutex::new(None)),
+            limits,
         })
     }
+
+    /// Manifest preference defaults overlaid with the values the plugin was initialized with
+    pub fn preferences(&self) -> HashMap<String, Value> {
+        let mut preferences: HashMap<String, Value> = self
+            .manifest
+            .preferences
+            .iter()
+            .map(|field| (field.key.clone(), field.default.clone()))
+            .collect();
+        if let Some(context) = self.context.lock().as_ref() {
+            preferences.extend(context.preferences.clone());
+        }
+        preferences
+    }
 }
@@ -4,7 +4,6 @@ use super::core::ExtismPluginAdapter;
 
 impl ExtismPluginAdapter {
     /// Call a function in the plugin (used by callback system)
-    #[allow(dead_code)]
     pub fn call_plugin_function(
         &self,
         function_name: &str,
@@ -20,7 +19,12 @@ impl ExtismPluginAdapter {
         }
 
         let payload_json = serde_json::to_string(payload)?;
-        match plugin.call::<String, ()>(function_name, payload_json) {
+        match self.limits.call::<String, ()>(
+            &mut plugin,
+            &self.manifest.id,
+            function_name,
+            payload_json,
+        ) {
             Ok(_) => {},
             Err(e) => return Err(crate::Error::Extism(e.to_string())),
         };
@@ -14,6 +14,8 @@ impl ExtismPluginAdapter {
     ) -> Task<Result<(), Error>> {
         let plugin_arc = Arc::clone(&self.plugin);
         let context_arc = Arc::clone(&self.context);
+        let plugin_id = self.manifest.id.clone();
+        let limits = self.limits;
 
         task_pool.spawn(async move {
             let context_json = serde_json::to_string(&context).map_err(|e| {
@@ -24,8 +26,13 @@ impl ExtismPluginAdapter {
 
             let mut plugin_guard = plugin_arc.lock();
 
-            plugin_guard
-                .call::<String, ()>("plugin_initialize", context_json)
+            limits
+                .call::<String, ()>(
+                    &mut plugin_guard,
+                    &plugin_id,
+                    "plugin_initialize",
+                    context_json,
+                )
                 .map_err(|e| {
                     Error::PluginError(format!(
                         "ExtismAdapter: Failed to call plugin_initialize: {e}"
@@ -16,6 +16,8 @@ impl ExtismPluginAdapter {
         task_pool: &AsyncComputeTaskPool,
     ) -> Task<Result<Vec<ActionItem>, Error>> {
         let plugin_arc = Arc::clone(&self.plugin);
+        let plugin_id = self.manifest.id.clone();
+        let limits = self.limits;
 
         task_pool.spawn(async move {
             #[derive(Serialize)]
@@ -34,8 +36,13 @@ impl ExtismPluginAdapter {
 
             let mut plugin_guard = plugin_arc.lock();
 
-            let response_json = plugin_guard
-                .call::<String, String>("plugin_search", request_json)
+            let response_json = limits
+                .call::<String, String>(
+                    &mut plugin_guard,
+                    &plugin_id,
+                    "plugin_search",
+                    request_json,
+                )
                 .map_err(|e| {
                     Error::PluginError(format!("ExtismAdapter: Failed to call plugin_search: {e}"))
                 })?;
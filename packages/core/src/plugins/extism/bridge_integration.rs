@@ -7,6 +7,8 @@ use action_items_native::{
 };
 
 use crate::plugins::extism::host_functions::ExtismHostUserData;
+use crate::plugins::extism::host_services::host_service_senders;
+use crate::plugins::permissions::PermissionPolicy;
 
 /// Create plugin context with service bridge integration
 pub fn create_plugin_context_with_bridge(
@@ -61,15 +63,24 @@ pub fn create_host_user_data(
     let (http_sender, _http_receiver) = crossbeam_channel::unbounded();
     let (storage_read_sender, _storage_read_receiver) = crossbeam_channel::unbounded();
     let (storage_write_sender, _storage_write_receiver) = crossbeam_channel::unbounded();
+    let services = host_service_senders();
 
     ExtismHostUserData {
         plugin_id: manifest.id.clone(),
+        permissions: PermissionPolicy::new(manifest.id.clone(), manifest.permissions.clone()),
         storage_read_sender,
         storage_write_sender,
         clipboard_read_sender,
         clipboard_write_sender,
         notification_sender,
         http_sender,
+        file_read_sender: services.file_read,
+        file_write_sender: services.file_write,
+        shell_sender: services.shell,
+        preferences_sender: services.preferences,
+        toast_sender: services.toast,
+        open_url_sender: services.open_url,
+        search_ingest_sender: services.search_ingest,
         cache_service: CacheService::new(1000),
     }
 }
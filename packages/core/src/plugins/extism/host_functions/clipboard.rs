@@ -2,6 +2,7 @@ use action_items_native::{ClipboardReadRequest, ClipboardWriteRequest};
 use extism::{Function, UserData, Val, ValType};
 
 use super::core::ExtismHostUserData;
+use crate::plugins::permissions::HostAccess;
 
 /// Create async clipboard read host function using modern event-driven architecture
 pub fn create_clipboard_read_async(user_data_param: ExtismHostUserData) -> Function {
@@ -42,6 +43,7 @@ pub fn create_clipboard_read_async(user_data_param: ExtismHostUserData) -> Funct
                 .lock()
                 .map_err(|_| extism::Error::msg("Mutex poisoned in clipboard_read_async"))?;
             let host_data_mut = &mut *guard;
+            host_data_mut.permissions.check(HostAccess::ReadClipboard)?;
 
             let request = ClipboardReadRequest {
                 plugin_id: host_data_mut.plugin_id.clone(),
@@ -108,6 +110,7 @@ pub fn create_clipboard_write_async(user_data_param: ExtismHostUserData) -> Func
                 .lock()
                 .map_err(|_| extism::Error::msg("Mutex poisoned in clipboard_write_async"))?;
             let host_data_mut = &mut *guard;
+            host_data_mut.permissions.check(HostAccess::WriteClipboard)?;
 
             let request = ClipboardWriteRequest {
                 plugin_id: host_data_mut.plugin_id.clone(),
@@ -1,22 +1,34 @@
 use action_items_native::{
-    ClipboardReadRequest, ClipboardWriteRequest, HttpRequest, NotificationRequest,
-    StorageReadRequest, StorageWriteRequest,
+    ClipboardReadRequest, ClipboardWriteRequest, FileReadRequest, FileWriteRequest, HttpRequest,
+    NotificationRequest, OpenUrlRequest, PreferencesReadRequest, SearchIngestRequest,
+    ShellExecuteRequest, StorageReadRequest, StorageWriteRequest, ToastRequest,
 };
 use crossbeam_channel::Sender as CrossbeamSender;
-use extism::Function;
+use extism::{CurrentPlugin, Function, Val};
+use serde::de::DeserializeOwned;
 
 use crate::plugins::interface::CacheService;
+use crate::plugins::permissions::PermissionPolicy;
 
 /// User data passed to host functions using modern event-driven architecture
 #[derive(Clone)]
 pub struct ExtismHostUserData {
     pub plugin_id: String,
+    /// Manifest permissions checked before any call leaves the plugin
+    pub permissions: PermissionPolicy,
     pub storage_read_sender: CrossbeamSender<StorageReadRequest>,
     pub storage_write_sender: CrossbeamSender<StorageWriteRequest>,
     pub clipboard_read_sender: CrossbeamSender<ClipboardReadRequest>,
     pub clipboard_write_sender: CrossbeamSender<ClipboardWriteRequest>,
     pub notification_sender: CrossbeamSender<NotificationRequest>,
     pub http_sender: CrossbeamSender<HttpRequest>,
+    pub file_read_sender: CrossbeamSender<FileReadRequest>,
+    pub file_write_sender: CrossbeamSender<FileWriteRequest>,
+    pub shell_sender: CrossbeamSender<ShellExecuteRequest>,
+    pub preferences_sender: CrossbeamSender<PreferencesReadRequest>,
+    pub toast_sender: CrossbeamSender<ToastRequest>,
+    pub open_url_sender: CrossbeamSender<OpenUrlRequest>,
+    pub search_ingest_sender: CrossbeamSender<SearchIngestRequest>,
     pub cache_service: CacheService,
 }
 
@@ -30,8 +42,66 @@ pub fn create_host_functions(user_data_param: ExtismHostUserData) -> Vec<Functio
         super::clipboard::create_clipboard_write_async(user_data_param.clone()),
         super::notification::create_notification_show_async(user_data_param.clone()),
         super::http::create_http_request_async(user_data_param.clone()),
+        super::filesystem::create_fs_read_async(user_data_param.clone()),
+        super::filesystem::create_fs_write_async(user_data_param.clone()),
+        super::shell::create_shell_execute_async(user_data_param.clone()),
+        super::preferences::create_preferences_get_async(user_data_param.clone()),
+        super::ui::create_toast_show_async(user_data_param.clone()),
+        super::ui::create_hud_show_async(user_data_param.clone()),
+        super::ui::create_open_url_async(user_data_param.clone()),
+        super::search::create_search_ingest_async(user_data_param.clone()),
         super::cache::create_cache_get_async(user_data_param.clone()),
         super::cache::create_cache_set_async(user_data_param.clone()),
         super::cache::create_cache_delete_async(user_data_param),
     ]
 }
+
+/// Arguments of a host function answering through a plugin callback
+pub(super) struct AsyncCall {
+    /// JSON payload describing the request
+    pub payload: String,
+    pub request_id: String,
+    /// Plugin export called with the result
+    pub callback: String,
+}
+
+impl AsyncCall {
+    /// Read the `(payload_ptr, request_id_ptr, callback_fn_ptr)` arguments from plugin memory
+    pub(super) fn read(
+        plugin: &mut CurrentPlugin,
+        inputs: &[Val],
+        function: &str,
+    ) -> Result<Self, extism::Error> {
+        let mut strings = ["payload_ptr", "request_id_ptr", "callback_fn_ptr"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let ptr = inputs.get(index).and_then(Val::i64).ok_or_else(|| {
+                    extism::Error::msg(format!(
+                        "Host function '{function}' expected I64 for {name}"
+                    ))
+                })? as u64;
+                let handle = plugin.memory_handle(ptr).ok_or_else(|| {
+                    extism::Error::msg(format!("Invalid memory handle for {name}"))
+                })?;
+                Ok(plugin.memory_str(handle)?.to_string())
+            })
+            .collect::<Result<Vec<_>, extism::Error>>()?
+            .into_iter();
+
+        Ok(Self {
+            payload: strings.next().unwrap_or_default(),
+            request_id: strings.next().unwrap_or_default(),
+            callback: strings.next().unwrap_or_default(),
+        })
+    }
+
+    /// Parse the payload as `T`
+    pub(super) fn parse<T: DeserializeOwned>(&self, function: &str) -> Result<T, extism::Error> {
+        serde_json::from_str(&self.payload).map_err(|e| {
+            extism::Error::msg(format!(
+                "Invalid payload for host function '{function}': {e}"
+            ))
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use action_items_native::{FileReadRequest, FileWriteRequest};
+use extism::{Function, UserData, Val, ValType};
+use serde::Deserialize;
+
+use super::core::{AsyncCall, ExtismHostUserData};
+
+#[derive(Deserialize)]
+struct FileReadPayload {
+    path: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct FileWritePayload {
+    path: PathBuf,
+    contents: String,
+}
+
+/// Create async file read host function, limited to the manifest's `read_files`
+pub fn create_fs_read_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "fs_read_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "fs_read_async")?;
+            let payload: FileReadPayload = call.parse("fs_read_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in fs_read_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in fs_read_async"))?;
+            let path = guard.permissions.check_read(&payload.path)?;
+
+            guard
+                .file_read_sender
+                .send(FileReadRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    path,
+                })
+                .map_err(|e| {
+                    extism::Error::msg(format!("Failed to send file read request: {e}"))
+                })?;
+
+            Ok(())
+        },
+    )
+}
+
+/// Create async file write host function, limited to the manifest's `write_files`
+pub fn create_fs_write_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "fs_write_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "fs_write_async")?;
+            let payload: FileWritePayload = call.parse("fs_write_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in fs_write_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in fs_write_async"))?;
+            let path = guard.permissions.check_write(&payload.path)?;
+
+            guard
+                .file_write_sender
+                .send(FileWriteRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    path,
+                    contents: payload.contents,
+                })
+                .map_err(|e| {
+                    extism::Error::msg(format!("Failed to send file write request: {e}"))
+                })?;
+
+            Ok(())
+        },
+    )
+}
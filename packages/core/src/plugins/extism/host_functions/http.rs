@@ -1,7 +1,8 @@
+use action_items_native::HttpRequest;
 use extism::{Function, UserData, Val, ValType};
 
 use super::core::ExtismHostUserData;
-// Note: HttpRequest will be used when HTTP host functions are implemented
+use crate::plugins::permissions::HostAccess;
 
 /// Create async HTTP request host function
 pub fn create_http_request_async(user_data_param: ExtismHostUserData) -> Function {
@@ -52,11 +53,13 @@ pub fn create_http_request_async(user_data_param: ExtismHostUserData) -> Functio
                 .map_err(|_| extism::Error::msg("Mutex poisoned in http_request_async"))?;
             let host_data_mut = &mut *guard;
 
+            let request: HttpRequest = serde_json::from_str(&request_str)
+                .map_err(|e| extism::Error::msg(format!("Failed to parse HTTP request: {e}")))?;
+            host_data_mut.permissions.check(HostAccess::Network { url: &request.url })?;
+
             host_data_mut
                 .http_sender
-                .send(serde_json::from_str(&request_str).map_err(|e| {
-                    extism::Error::msg(format!("Failed to parse HTTP request: {e}"))
-                })?)
+                .send(request)
                 .map_err(|e| extism::Error::msg(format!("Failed to send HTTP command: {e}")))?;
 
             Ok(())
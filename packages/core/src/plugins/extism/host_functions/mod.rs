@@ -5,9 +5,14 @@
 pub mod cache;
 pub mod clipboard;
 pub mod core;
+pub mod filesystem;
 pub mod http;
 pub mod notification;
+pub mod preferences;
+pub mod search;
+pub mod shell;
 pub mod storage;
+pub mod ui;
 
 // Re-export core types and factory function
 pub use core::{ExtismHostUserData, create_host_functions};
@@ -2,6 +2,7 @@ use action_items_native::NotificationRequest;
 use extism::{Function, UserData, Val, ValType};
 
 use super::core::ExtismHostUserData;
+use crate::plugins::permissions::HostAccess;
 
 /// Create async notification show host function using modern event-driven architecture
 pub fn create_notification_show_async(user_data_param: ExtismHostUserData) -> Function {
@@ -51,6 +52,7 @@ pub fn create_notification_show_async(user_data_param: ExtismHostUserData) -> Fu
                 .lock()
                 .map_err(|_| extism::Error::msg("Mutex poisoned in notification_show_async"))?;
             let host_data_mut = &mut *guard;
+            host_data_mut.permissions.check(HostAccess::Notification)?;
 
             // Parse notification JSON to extract title, body, and icon
             let notification_data: serde_json::Value = serde_json::from_str(&notification_str)
@@ -0,0 +1,51 @@
+use action_items_native::PreferencesReadRequest;
+use extism::{Function, UserData, Val, ValType};
+use serde::Deserialize;
+
+use super::core::{AsyncCall, ExtismHostUserData};
+
+#[derive(Deserialize)]
+struct PreferencesPayload {
+    #[serde(default)]
+    key: Option<String>,
+}
+
+/// Create async preferences read host function
+///
+/// Plugins only ever see their own preferences, so no permission is needed.
+pub fn create_preferences_get_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "preferences_get_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "preferences_get_async")?;
+            let payload: PreferencesPayload = call.parse("preferences_get_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in preferences_get_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in preferences_get_async"))?;
+
+            guard
+                .preferences_sender
+                .send(PreferencesReadRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    key: payload.key,
+                })
+                .map_err(|e| {
+                    extism::Error::msg(format!("Failed to send preferences request: {e}"))
+                })?;
+
+            Ok(())
+        },
+    )
+}
@@ -0,0 +1,50 @@
+use action_items_native::{ActionItem, SearchIngestRequest};
+use extism::{Function, UserData, Val, ValType};
+use serde::Deserialize;
+
+use super::core::{AsyncCall, ExtismHostUserData};
+
+#[derive(Deserialize)]
+struct SearchIngestPayload {
+    items: Vec<ActionItem>,
+}
+
+/// Create async search index ingest host function
+///
+/// Ingested items are namespaced by plugin id, so no permission is needed.
+pub fn create_search_ingest_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "search_ingest_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "search_ingest_async")?;
+            let payload: SearchIngestPayload = call.parse("search_ingest_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in search_ingest_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in search_ingest_async"))?;
+
+            guard
+                .search_ingest_sender
+                .send(SearchIngestRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    items: payload.items,
+                })
+                .map_err(|e| {
+                    extism::Error::msg(format!("Failed to send search ingest request: {e}"))
+                })?;
+
+            Ok(())
+        },
+    )
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use action_items_native::ShellExecuteRequest;
+use extism::{Function, UserData, Val, ValType};
+use serde::Deserialize;
+
+use super::core::{AsyncCall, ExtismHostUserData};
+use crate::plugins::permissions::{HostAccess, PermissionPolicy};
+
+#[derive(Deserialize)]
+struct ShellPayload {
+    program: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+}
+
+/// Create async command execution host function, limited to the manifest's `execute_commands`
+///
+/// The program runs directly rather than through a shell, so arguments are never reparsed. It
+/// sees only the environment variables granted by `environment_variables`.
+pub fn create_shell_execute_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "shell_execute_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "shell_execute_async")?;
+            let payload: ShellPayload = call.parse("shell_execute_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in shell_execute_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in shell_execute_async"))?;
+            guard
+                .permissions
+                .check(HostAccess::ExecuteCommand(&payload.program))?;
+            // The program can read the directory it runs in
+            let cwd = payload
+                .cwd
+                .map(|cwd| guard.permissions.check_read(&cwd))
+                .transpose()?;
+
+            guard
+                .shell_sender
+                .send(ShellExecuteRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    program: payload.program,
+                    args: payload.args,
+                    cwd,
+                    env: granted_environment(&guard.permissions),
+                })
+                .map_err(|e| extism::Error::msg(format!("Failed to send shell request: {e}")))?;
+
+            Ok(())
+        },
+    )
+}
+
+/// The launcher's environment variables the plugin was granted
+fn granted_environment(permissions: &PermissionPolicy) -> Vec<(String, String)> {
+    std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .filter(|(name, _)| permissions.environment_granted(name))
+        .collect()
+}
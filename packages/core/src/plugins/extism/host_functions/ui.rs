@@ -0,0 +1,144 @@
+use action_items_native::{OpenUrlRequest, ToastRequest, ToastStyle};
+use extism::{Function, UserData, Val, ValType};
+use serde::Deserialize;
+
+use super::core::{AsyncCall, ExtismHostUserData};
+use crate::plugins::permissions::HostAccess;
+
+#[derive(Deserialize)]
+struct ToastPayload {
+    title: String,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    style: ToastStyle,
+}
+
+#[derive(Deserialize)]
+struct HudPayload {
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct OpenUrlPayload {
+    url: String,
+}
+
+/// Create async toast host function, gated like system notifications
+pub fn create_toast_show_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "toast_show_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "toast_show_async")?;
+            let payload: ToastPayload = call.parse("toast_show_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in toast_show_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in toast_show_async"))?;
+            guard.permissions.check(HostAccess::Notification)?;
+
+            guard
+                .toast_sender
+                .send(ToastRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    style: payload.style,
+                    title: payload.title,
+                    message: payload.message,
+                })
+                .map_err(|e| extism::Error::msg(format!("Failed to send toast request: {e}")))?;
+
+            Ok(())
+        },
+    )
+}
+
+/// Create async HUD host function, gated like system notifications
+pub fn create_hud_show_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "hud_show_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "hud_show_async")?;
+            let payload: HudPayload = call.parse("hud_show_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in hud_show_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in hud_show_async"))?;
+            guard.permissions.check(HostAccess::Notification)?;
+
+            guard
+                .toast_sender
+                .send(ToastRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    style: ToastStyle::Hud,
+                    title: payload.title,
+                    message: None,
+                })
+                .map_err(|e| extism::Error::msg(format!("Failed to send HUD request: {e}")))?;
+
+            Ok(())
+        },
+    )
+}
+
+/// Create async open URL host function
+///
+/// Only http and https URLs open, and only for hosts in the manifest's `network_hosts`.
+pub fn create_open_url_async(user_data_param: ExtismHostUserData) -> Function {
+    Function::new(
+        "open_url_async",
+        [ValType::I64, ValType::I64, ValType::I64],
+        [],
+        UserData::new(user_data_param),
+        |plugin: &mut extism::CurrentPlugin,
+         inputs: &[Val],
+         _outputs: &mut [Val],
+         user_data: UserData<ExtismHostUserData>| {
+            let call = AsyncCall::read(plugin, inputs, "open_url_async")?;
+            let payload: OpenUrlPayload = call.parse("open_url_async")?;
+
+            let arc_mutex_t = user_data
+                .get()
+                .map_err(|_| extism::Error::msg("UserData has no data in open_url_async"))?;
+            let guard = arc_mutex_t
+                .lock()
+                .map_err(|_| extism::Error::msg("Mutex poisoned in open_url_async"))?;
+            guard
+                .permissions
+                .check(HostAccess::Network { url: &payload.url })?;
+
+            guard
+                .open_url_sender
+                .send(OpenUrlRequest {
+                    plugin_id: guard.plugin_id.clone(),
+                    request_id: call.request_id,
+                    callback: call.callback,
+                    url: payload.url,
+                })
+                .map_err(|e| extism::Error::msg(format!("Failed to send open URL request: {e}")))?;
+
+            Ok(())
+        },
+    )
+}
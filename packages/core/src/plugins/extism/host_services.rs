@@ -0,0 +1,481 @@
+//! Launcher services behind the Extism host functions that answer through plugin callbacks
+//!
+//! Host functions queue requests on process-wide channels. [`process_extism_host_requests_system`]
+//! serves them, running file and command work off the main thread, and
+//! [`deliver_extism_host_responses_system`] calls the plugin's callback export with
+//! `{"request_id": ..., "result": ...}`. The result of a failed call is `{"error": ...}`.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use action_items_native::{
+    FileReadRequest, FileWriteRequest, HostCallResponse, OpenUrlRequest, PreferencesReadRequest,
+    SearchIngestRequest, ShellExecuteRequest, ToastRequest, ToastStyle,
+};
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+use crossbeam_channel::{Receiver, Sender};
+use serde_json::{Value, json};
+
+use crate::plugins::extism::wrapper::ExtismPluginComponent;
+use crate::runtime::deno::notifications::NotificationOptions;
+use crate::runtime::deno::ops::{notification_manager, open_command};
+use crate::search::{SearchIndex, SearchItem, SearchItemType};
+
+/// Wall-clock limit of a plugin command, after which it is killed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Commands one plugin may have running at once
+const MAX_COMMANDS_PER_PLUGIN: usize = 4;
+
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Bytes kept from each of a command's stdout and stderr; the rest is discarded
+const MAX_COMMAND_OUTPUT: usize = 1024 * 1024;
+
+/// How long reader threads may keep draining pipes once a command has exited or been killed
+///
+/// Only a process that left the command's process group can hold the pipes open that long.
+const COMMAND_OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+type Channel<T> = (Sender<T>, Receiver<T>);
+
+struct HostChannels {
+    file_read: Channel<FileReadRequest>,
+    file_write: Channel<FileWriteRequest>,
+    shell: Channel<ShellExecuteRequest>,
+    preferences: Channel<PreferencesReadRequest>,
+    toast: Channel<ToastRequest>,
+    open_url: Channel<OpenUrlRequest>,
+    search_ingest: Channel<SearchIngestRequest>,
+    responses: Channel<HostCallResponse>,
+}
+
+static HOST_CHANNELS: OnceLock<HostChannels> = OnceLock::new();
+
+/// Commands currently running, per plugin id
+static RUNNING_COMMANDS: OnceLock<Mutex<HashMap<String, usize>>> = OnceLock::new();
+
+fn host_channels() -> &'static HostChannels {
+    HOST_CHANNELS.get_or_init(|| HostChannels {
+        file_read: crossbeam_channel::unbounded(),
+        file_write: crossbeam_channel::unbounded(),
+        shell: crossbeam_channel::unbounded(),
+        preferences: crossbeam_channel::unbounded(),
+        toast: crossbeam_channel::unbounded(),
+        open_url: crossbeam_channel::unbounded(),
+        search_ingest: crossbeam_channel::unbounded(),
+        responses: crossbeam_channel::unbounded(),
+    })
+}
+
+/// Request senders copied into every plugin's [`ExtismHostUserData`]
+///
+/// [`ExtismHostUserData`]: crate::plugins::extism::ExtismHostUserData
+pub struct HostServiceSenders {
+    pub file_read: Sender<FileReadRequest>,
+    pub file_write: Sender<FileWriteRequest>,
+    pub shell: Sender<ShellExecuteRequest>,
+    pub preferences: Sender<PreferencesReadRequest>,
+    pub toast: Sender<ToastRequest>,
+    pub open_url: Sender<OpenUrlRequest>,
+    pub search_ingest: Sender<SearchIngestRequest>,
+}
+
+pub fn host_service_senders() -> HostServiceSenders {
+    let channels = host_channels();
+    HostServiceSenders {
+        file_read: channels.file_read.0.clone(),
+        file_write: channels.file_write.0.clone(),
+        shell: channels.shell.0.clone(),
+        preferences: channels.preferences.0.clone(),
+        toast: channels.toast.0.clone(),
+        open_url: channels.open_url.0.clone(),
+        search_ingest: channels.search_ingest.0.clone(),
+    }
+}
+
+fn respond(plugin_id: String, request_id: String, callback: String, result: Result<Value, String>) {
+    let _ = host_channels().responses.0.send(HostCallResponse {
+        plugin_id,
+        request_id,
+        callback,
+        result,
+    });
+}
+
+/// Serve queued host function requests
+pub fn process_extism_host_requests_system(
+    mut search_index: Option<ResMut<SearchIndex>>,
+    plugins: Query<&ExtismPluginComponent>,
+) {
+    let channels = host_channels();
+    let io = IoTaskPool::get();
+
+    for request in channels.file_read.1.try_iter() {
+        io.spawn(async move {
+            let result = std::fs::read_to_string(&request.path)
+                .map(Value::String)
+                .map_err(|e| format!("Failed to read {}: {e}", request.path.display()));
+            respond(
+                request.plugin_id,
+                request.request_id,
+                request.callback,
+                result,
+            );
+        })
+        .detach();
+    }
+
+    for request in channels.file_write.1.try_iter() {
+        io.spawn(async move {
+            let result = std::fs::write(&request.path, &request.contents)
+                .map(|()| Value::Null)
+                .map_err(|e| format!("Failed to write {}: {e}", request.path.display()));
+            respond(
+                request.plugin_id,
+                request.request_id,
+                request.callback,
+                result,
+            );
+        })
+        .detach();
+    }
+
+    for request in channels.shell.1.try_iter() {
+        let Some(slot) = CommandSlot::acquire(&request.plugin_id) else {
+            let error = format!(
+                "Plugin already runs {MAX_COMMANDS_PER_PLUGIN} commands; wait for one to finish"
+            );
+            respond(
+                request.plugin_id,
+                request.request_id,
+                request.callback,
+                Err(error),
+            );
+            continue;
+        };
+        // Commands may run for a long time, so they get a thread rather than a pool slot
+        let spawned = std::thread::Builder::new()
+            .name("extism-shell".to_string())
+            .spawn(move || {
+                let result = run_command(&request);
+                drop(slot);
+                respond(
+                    request.plugin_id,
+                    request.request_id,
+                    request.callback,
+                    result,
+                );
+            });
+        if let Err(e) = spawned {
+            error!("Failed to start command thread: {}", e);
+        }
+    }
+
+    for request in channels.preferences.1.try_iter() {
+        let result =
+            plugin_preferences(&plugins, &request.plugin_id).and_then(|preferences| match &request
+                .key
+            {
+                Some(key) => preferences
+                    .get(key)
+                    .cloned()
+                    .ok_or_else(|| format!("Unknown preference '{key}'")),
+                None => Ok(Value::Object(preferences.into_iter().collect())),
+            });
+        respond(
+            request.plugin_id,
+            request.request_id,
+            request.callback,
+            result,
+        );
+    }
+
+    for request in channels.toast.1.try_iter() {
+        io.spawn(async move {
+            // Shown through the same notification backend as Deno toasts and HUDs
+            let message = request.message.as_deref().unwrap_or_default();
+            let options = match request.style {
+                ToastStyle::Hud => NotificationOptions {
+                    title: "Action Items",
+                    message: &request.title,
+                    sound: false,
+                    urgent: true,
+                    ..Default::default()
+                },
+                ToastStyle::Failure => NotificationOptions {
+                    title: &request.title,
+                    message,
+                    urgent: true,
+                    ..Default::default()
+                },
+                ToastStyle::Success | ToastStyle::Animated => NotificationOptions {
+                    title: &request.title,
+                    message,
+                    ..Default::default()
+                },
+            };
+            let result = notification_manager()
+                .show_notification(options)
+                .map(|id| json!(id.as_u64()))
+                .map_err(|e| format!("Failed to show toast: {e}"));
+            respond(
+                request.plugin_id,
+                request.request_id,
+                request.callback,
+                result,
+            );
+        })
+        .detach();
+    }
+
+    for request in channels.open_url.1.try_iter() {
+        let result = open_url(&request.url)
+            .map(|()| Value::Null)
+            .map_err(|e| format!("Failed to open {}: {e}", request.url));
+        respond(
+            request.plugin_id,
+            request.request_id,
+            request.callback,
+            result,
+        );
+    }
+
+    for request in channels.search_ingest.1.try_iter() {
+        let result = match search_index.as_deref_mut() {
+            Some(index) => {
+                let count = request.items.len();
+                for item in request.items {
+                    let mut search_item = SearchItem::new(
+                        format!("extism:{}:{}", request.plugin_id, item.id),
+                        item.title,
+                        item.subtitle.or(item.description).unwrap_or_default(),
+                        SearchItemType::ActionItem,
+                    )
+                    .with_keywords(item.tags);
+                    search_item.score = item.score;
+                    index.add_item(search_item);
+                }
+                Ok(json!(count))
+            },
+            None => Err("Search index is not available".to_string()),
+        };
+        respond(
+            request.plugin_id,
+            request.request_id,
+            request.callback,
+            result,
+        );
+    }
+}
+
+/// Call each plugin's callback export with the result of its host call
+pub fn deliver_extism_host_responses_system(plugins: Query<&ExtismPluginComponent>) {
+    for response in host_channels().responses.1.try_iter() {
+        if response.callback.is_empty() {
+            continue;
+        }
+        let Some(component) = plugins
+            .iter()
+            .find(|plugin| plugin.id == response.plugin_id)
+        else {
+            warn!(
+                "Dropping host call response for unloaded plugin '{}'",
+                response.plugin_id
+            );
+            continue;
+        };
+
+        let result = response
+            .result
+            .unwrap_or_else(|error| json!({ "error": error }));
+        let payload = json!({ "request_id": response.request_id, "result": result });
+        if let Err(e) = component
+            .plugin
+            .read()
+            .call_plugin_function(&response.callback, &payload)
+        {
+            warn!(
+                "Failed to deliver host call response to plugin '{}': {}",
+                response.plugin_id, e
+            );
+        }
+    }
+}
+
+fn plugin_preferences(
+    plugins: &Query<&ExtismPluginComponent>,
+    plugin_id: &str,
+) -> Result<HashMap<String, Value>, String> {
+    plugins
+        .iter()
+        .find(|plugin| plugin.id == plugin_id)
+        .map(|plugin| plugin.plugin.read().preferences())
+        .ok_or_else(|| format!("Plugin '{plugin_id}' is not loaded"))
+}
+
+/// One of a plugin's [`MAX_COMMANDS_PER_PLUGIN`] command slots, released on drop
+struct CommandSlot {
+    plugin_id: String,
+}
+
+impl CommandSlot {
+    fn acquire(plugin_id: &str) -> Option<Self> {
+        let mut running = RUNNING_COMMANDS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let count = running.entry(plugin_id.to_string()).or_default();
+        if *count >= MAX_COMMANDS_PER_PLUGIN {
+            return None;
+        }
+        *count += 1;
+        Some(Self {
+            plugin_id: plugin_id.to_string(),
+        })
+    }
+}
+
+impl Drop for CommandSlot {
+    fn drop(&mut self) {
+        let mut running = RUNNING_COMMANDS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(count) = running.get_mut(&self.plugin_id) {
+            *count -= 1;
+            if *count == 0 {
+                running.remove(&self.plugin_id);
+            }
+        }
+    }
+}
+
+/// Run a plugin command with only its granted environment, killing it after
+/// [`COMMAND_TIMEOUT`]
+///
+/// On Unix the command gets its own process group, which is killed once the command exits or
+/// times out, so nothing it left in the background can keep running or hold its pipes open.
+fn run_command(request: &ShellExecuteRequest) -> Result<Value, String> {
+    let mut command = Command::new(&request.program);
+    command
+        .args(&request.args)
+        .env_clear()
+        .envs(request.env.iter().cloned())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = &request.cwd {
+        command.current_dir(cwd);
+    }
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {e}", request.program))?;
+    let stdout = Capture::start(child.stdout.take());
+    let stderr = Capture::start(child.stderr.take());
+
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(COMMAND_POLL_INTERVAL),
+            Ok(None) => {
+                kill_command(&mut child);
+                return Err(format!(
+                    "{} timed out after {COMMAND_TIMEOUT:?}",
+                    request.program
+                ));
+            },
+            Err(e) => {
+                kill_command(&mut child);
+                return Err(format!("Failed to wait for {}: {e}", request.program));
+            },
+        }
+    };
+    kill_command(&mut child);
+
+    let stdout = stdout.finish();
+    let stderr = stderr.finish();
+    Ok(json!({
+        "status": status.code(),
+        "stdout": String::from_utf8_lossy(&stdout.bytes),
+        "stderr": String::from_utf8_lossy(&stderr.bytes),
+        "truncated": stdout.truncated || stderr.truncated,
+    }))
+}
+
+/// Kill a command and whatever is left of its process group
+fn kill_command(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: signals the process group created for this child in `run_command`; its id stays
+    // reserved while any member is alive, so it cannot name another group
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Bytes read from one pipe so far
+#[derive(Debug, Default)]
+struct Captured {
+    bytes: Vec<u8>,
+    truncated: bool,
+}
+
+/// A child's pipe drained on a thread, so a full pipe never blocks the child, keeping at most
+/// [`MAX_COMMAND_OUTPUT`] bytes
+struct Capture {
+    captured: Arc<Mutex<Captured>>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl Capture {
+    fn start<R: Read + Send + 'static>(pipe: Option<R>) -> Self {
+        let captured = Arc::new(Mutex::new(Captured::default()));
+        let reader = pipe.map(|mut pipe| {
+            let captured = Arc::clone(&captured);
+            std::thread::spawn(move || {
+                let mut buffer = [0u8; 8192];
+                // Keep draining past the limit so the command never blocks on a full pipe
+                while let Ok(read) = pipe.read(&mut buffer) {
+                    if read == 0 {
+                        break;
+                    }
+                    let mut captured = captured.lock().unwrap_or_else(PoisonError::into_inner);
+                    let room = MAX_COMMAND_OUTPUT.saturating_sub(captured.bytes.len());
+                    captured.bytes.extend_from_slice(&buffer[..read.min(room)]);
+                    captured.truncated |= read > room;
+                }
+            })
+        });
+        Self { captured, reader }
+    }
+
+    /// Take what was read once the pipe closes, or after [`COMMAND_OUTPUT_GRACE`]
+    ///
+    /// A reader still running then exits whenever the pipe finally closes.
+    fn finish(mut self) -> Captured {
+        if let Some(reader) = self.reader.take() {
+            let deadline = Instant::now() + COMMAND_OUTPUT_GRACE;
+            while Instant::now() < deadline && !reader.is_finished() {
+                std::thread::sleep(COMMAND_POLL_INTERVAL);
+            }
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+        std::mem::take(&mut *self.captured.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+fn open_url(url: &str) -> std::io::Result<()> {
+    open_command(url, None).spawn().map(drop)
+}
@@ -0,0 +1,202 @@
+//! Fuel, memory and wall-clock limits for WASM plugin calls
+//!
+//! A manifest's [`ResourceLimits`] pick the limits for its plugin, clamped to the launcher's
+//! caps. When a call trips one, the plugin is reported through [`handle_wasm_limit_trips_system`],
+//! which raises a [`PluginErrorEvent`] and marks the plugin [`PluginStatus::Degraded`].
+//!
+//! Memory growth past the limit fails inside the plugin, like any allocation failure, so only
+//! fuel and wall-clock limits are reported as trips.
+
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use action_items_common::plugin_interface::ResourceLimits;
+use action_items_native::PluginErrorEvent;
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+use ecs_service_bridge::components::{PluginComponent, PluginStatus};
+use ecs_service_bridge::resources::PluginRegistryResource;
+use extism::{Manifest, Plugin, PluginBuilder};
+use wasmtime::Trap;
+
+/// Fuel per call when the manifest does not ask for an amount
+pub const DEFAULT_FUEL: u64 = 1_000_000_000;
+/// Most fuel a manifest can ask for per call
+pub const MAX_FUEL: u64 = 10_000_000_000;
+/// Linear memory when the manifest does not ask for an amount (16 MiB)
+pub const DEFAULT_MEMORY_PAGES: u32 = 256;
+/// Most linear memory a manifest can ask for (64 MiB)
+pub const MAX_MEMORY_PAGES: u32 = 1024;
+/// Wall-clock budget per call when the manifest does not ask for one
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest wall-clock budget a manifest can ask for
+pub const MAX_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Limits a plugin actually runs under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    pub fuel: u64,
+    pub memory_pages: u32,
+    pub timeout: Duration,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL,
+            memory_pages: DEFAULT_MEMORY_PAGES,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl WasmLimits {
+    /// Resolve the limits a manifest asks for against the launcher's defaults and caps
+    pub fn from_requested(requested: &ResourceLimits) -> Self {
+        Self {
+            fuel: requested.fuel.unwrap_or(DEFAULT_FUEL).min(MAX_FUEL),
+            memory_pages: requested
+                .memory_pages
+                .unwrap_or(DEFAULT_MEMORY_PAGES)
+                .min(MAX_MEMORY_PAGES),
+            timeout: requested
+                .timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_TIMEOUT)
+                .min(MAX_TIMEOUT),
+        }
+    }
+
+    /// Apply the memory and wall-clock limits, which Extism takes on the manifest
+    pub fn apply_to_manifest(&self, manifest: Manifest) -> Manifest {
+        manifest
+            .with_memory_max(self.memory_pages)
+            .with_timeout(self.timeout)
+    }
+
+    /// Build a plugin from an Extism manifest with all three limits in place
+    pub fn build_plugin(
+        &self,
+        manifest: Manifest,
+        host_functions: Vec<extism::Function>,
+    ) -> Result<Plugin, extism::Error> {
+        PluginBuilder::new(self.apply_to_manifest(manifest))
+            .with_wasi(true)
+            .with_functions(host_functions)
+            .with_fuel_limit(self.fuel)
+            .build()
+    }
+
+    /// Work out which limit, if any, made a call fail
+    ///
+    /// Fuel exhaustion and the epoch interruption behind Extism's timeout and cancellation are
+    /// wasmtime traps in the error chain. A call failing any other way after its whole budget
+    /// is counted as a timeout as well.
+    pub fn classify(&self, error: &extism::Error, elapsed: Duration) -> Option<LimitKind> {
+        let trap = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<Trap>());
+        match trap {
+            Some(Trap::OutOfFuel) => Some(LimitKind::Fuel),
+            Some(Trap::Interrupt) => Some(LimitKind::WallClock),
+            _ => (elapsed >= self.timeout).then_some(LimitKind::WallClock),
+        }
+    }
+
+    /// Call a plugin export, reporting the failure if it tripped one of these limits
+    pub fn call<'a, 'b, I, O>(
+        &self,
+        plugin: &'b mut Plugin,
+        plugin_id: &str,
+        function: &str,
+        input: I,
+    ) -> Result<O, extism::Error>
+    where
+        I: extism::ToBytes<'a>,
+        O: extism::FromBytes<'b>,
+    {
+        let started = Instant::now();
+        let result = plugin.call(function, input);
+        if let Err(error) = &result
+            && let Some(kind) = self.classify(error, started.elapsed())
+        {
+            report_limit_trip(LimitTrip {
+                plugin_id: plugin_id.to_string(),
+                kind,
+                message: error.to_string(),
+            });
+        }
+        result
+    }
+}
+
+/// Which limit a call ran into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    Fuel,
+    WallClock,
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fuel => f.write_str("fuel"),
+            Self::WallClock => f.write_str("wall-clock"),
+        }
+    }
+}
+
+/// A call that ran into one of its plugin's limits
+#[derive(Debug, Clone)]
+pub struct LimitTrip {
+    pub plugin_id: String,
+    pub kind: LimitKind,
+    pub message: String,
+}
+
+static LIMIT_TRIPS: OnceLock<(Sender<LimitTrip>, Receiver<LimitTrip>)> = OnceLock::new();
+
+fn limit_trips() -> &'static (Sender<LimitTrip>, Receiver<LimitTrip>) {
+    LIMIT_TRIPS.get_or_init(crossbeam_channel::unbounded)
+}
+
+/// Queue a limit trip; calls run on task pools, so it reaches the ECS world on the next frame
+pub fn report_limit_trip(trip: LimitTrip) {
+    warn!(
+        "Plugin '{}' exceeded its {} limit: {}",
+        trip.plugin_id, trip.kind, trip.message
+    );
+    let _ = limit_trips().0.send(trip);
+}
+
+/// Degrade plugins that tripped a limit and report them as [`PluginErrorEvent`]s
+pub fn handle_wasm_limit_trips_system(
+    mut errors: EventWriter<PluginErrorEvent>,
+    mut plugins: Query<&mut PluginComponent>,
+    mut registry: Option<ResMut<PluginRegistryResource>>,
+) {
+    for trip in limit_trips().1.try_iter() {
+        let reason = format!("Exceeded {} limit: {}", trip.kind, trip.message);
+
+        if let Some(info) = registry
+            .as_deref_mut()
+            .and_then(|registry| registry.plugins.get_mut(&trip.plugin_id))
+        {
+            info.status = PluginStatus::Degraded(reason.clone());
+        }
+        if let Some(mut plugin) = plugins
+            .iter_mut()
+            .find(|plugin| plugin.plugin_id == trip.plugin_id)
+        {
+            plugin.status = PluginStatus::Degraded(reason.clone());
+        }
+
+        errors.write(PluginErrorEvent {
+            plugin_id: trip.plugin_id,
+            error: reason,
+            // The plugin stays loaded; a reload with the same limits is allowed to try again
+            recoverable: true,
+        });
+    }
+}
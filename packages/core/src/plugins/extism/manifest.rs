@@ -1,8 +1,9 @@
 use std::path::Path;
 
-use extism::{Manifest, Plugin, PluginBuilder, Wasm};
+use extism::{Manifest, Plugin, Wasm};
 use log::debug;
 
+use crate::plugins::extism::limits::WasmLimits;
 use crate::plugins::interface::PluginManifest;
 use crate::plugins::interface::extism::REQUIRED_EXPORTS;
 
@@ -12,10 +13,9 @@ pub fn load_manifest_from_file(path: &Path) -> Result<(Plugin, PluginManifest),
     let url = Wasm::file(path);
     let manifest_data = Manifest::new([url]);
 
-    // Create plugin with host functions
-    let mut plugin = PluginBuilder::new(manifest_data)
-        .with_wasi(true)
-        .build()
+    // The plugin's own limits are unknown until its manifest is read, so the defaults apply
+    let mut plugin = WasmLimits::default()
+        .build_plugin(manifest_data, Vec::new())
         .map_err(|e| crate::Error::Extism(e.to_string()))?;
 
     // Get plugin manifest
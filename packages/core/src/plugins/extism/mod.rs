@@ -6,6 +6,8 @@
 pub mod adapter;
 pub mod bridge_integration;
 pub mod host_functions;
+pub mod host_services;
+pub mod limits;
 pub mod manifest;
 pub mod runtime;
 pub mod wrapper;
@@ -17,6 +19,7 @@ pub use adapter::ExtismPluginAdapter;
 pub use adapter::ExtismPluginAdapter as ExtismPlugin;
 pub use bridge_integration::{create_host_user_data, create_plugin_context_with_bridge};
 pub use host_functions::{ExtismHostUserData, create_host_functions};
+pub use limits::{LimitKind, WasmLimits};
 pub use manifest::{create_manifest_from_data, load_manifest_from_file, validate_plugin_exports};
 pub use runtime::{ExtismPluginRuntime, ExtismPluginRuntime as ExtismPluginLoader};
 pub use wrapper::{ExtismPluginComponent, ExtismPluginWrapper};
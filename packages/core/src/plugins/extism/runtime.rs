@@ -7,6 +7,7 @@ use extism::Plugin;
 use log::{debug, trace};
 use serde::Serialize;
 
+use crate::plugins::extism::limits::WasmLimits;
 use crate::plugins::interface::{CommandResult, PluginContext, PluginManifest};
 
 /// Core Extism plugin runtime operations
@@ -14,24 +15,32 @@ pub struct ExtismPluginRuntime {
     plugin: Plugin,
     manifest: PluginManifest,
     id: String,
+    limits: WasmLimits,
 }
 
 impl ExtismPluginRuntime {
     /// Create new runtime from plugin and manifest
     pub fn new(plugin: Plugin, manifest: PluginManifest) -> Self {
         let id = manifest.id.clone();
+        let limits = WasmLimits::from_requested(&manifest.resource_limits);
         Self {
             plugin,
             manifest,
             id,
+            limits,
         }
     }
 
     /// Initialize plugin with context
     pub fn initialize(&mut self, context: &PluginContext) -> Result<(), crate::Error> {
         let context_json = serde_json::to_string(context)?;
-        self.plugin
-            .call::<String, ()>("plugin_initialize", context_json)
+        self.limits
+            .call::<String, ()>(
+                &mut self.plugin,
+                &self.id,
+                "plugin_initialize",
+                context_json,
+            )
             .map_err(|e| crate::Error::Extism(e.to_string()))?;
         debug!("Plugin {} initialized successfully", self.id);
         Ok(())
@@ -58,8 +67,8 @@ impl ExtismPluginRuntime {
         trace!("Executing search for plugin {}: {}", self.id, query);
 
         let response_json = self
-            .plugin
-            .call::<String, String>("plugin_search", request_json)
+            .limits
+            .call::<String, String>(&mut self.plugin, &self.id, "plugin_search", request_json)
             .map_err(|e| crate::Error::Extism(e.to_string()))?;
         let results: Vec<ActionItem> = serde_json::from_str(&response_json)?;
 
@@ -95,8 +104,13 @@ impl ExtismPluginRuntime {
         trace!("Executing command {} for plugin {}", command_id, self.id);
 
         let response_json = self
-            .plugin
-            .call::<String, String>("plugin_execute_command", request_json)
+            .limits
+            .call::<String, String>(
+                &mut self.plugin,
+                &self.id,
+                "plugin_execute_command",
+                request_json,
+            )
             .map_err(|e| crate::Error::Extism(e.to_string()))?;
         let result: CommandResult = serde_json::from_str(&response_json)?;
 
@@ -130,8 +144,13 @@ impl ExtismPluginRuntime {
         let request_json = serde_json::to_string(&request)?;
         trace!("Executing action {} for plugin {}", action_id, self.id);
 
-        self.plugin
-            .call::<String, ()>("plugin_execute_action", request_json)
+        self.limits
+            .call::<String, ()>(
+                &mut self.plugin,
+                &self.id,
+                "plugin_execute_action",
+                request_json,
+            )
             .map_err(|e| crate::Error::Extism(e.to_string()))?;
 
         debug!(
@@ -147,8 +166,13 @@ impl ExtismPluginRuntime {
             let context_json = serde_json::to_string(context)?;
             trace!("Executing background refresh for plugin {}", self.id);
 
-            self.plugin
-                .call::<String, ()>("plugin_background_refresh", context_json)
+            self.limits
+                .call::<String, ()>(
+                    &mut self.plugin,
+                    &self.id,
+                    "plugin_background_refresh",
+                    context_json,
+                )
                 .map_err(|e| crate::Error::Extism(e.to_string()))?;
 
             debug!("Plugin {} background refresh completed", self.id);
@@ -189,7 +213,12 @@ impl ExtismPluginRuntime {
         let functions =
             crate::plugins::extism::host_functions::create_host_functions(host_user_data);
 
-        let plugin = Plugin::new(&plugin_data, functions, true)
+        let limits = WasmLimits::from_requested(&manifest.resource_limits);
+        let plugin = limits
+            .build_plugin(
+                crate::plugins::extism::create_manifest_from_data(plugin_data),
+                functions,
+            )
             .map_err(|e| crate::Error::Extism(e.to_string()))?;
 
         Ok(Self::new(plugin, manifest))
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use ecs_hotkey::{
+    HotkeyBinding, HotkeyDefinition, HotkeyId, HotkeyRegisterRequested, HotkeyUnregisterRequested,
+};
+use global_hotkey::hotkey::HotKey;
+
+use crate::plugins::async_loader::{LoadingPlugin, PluginLoaded};
+use crate::search::{SearchIndex, SearchItem, SearchItemType};
+
+/// Search items and hotkeys registered on behalf of each loaded plugin
+#[derive(Resource, Default)]
+pub struct PluginCommandRegistrations {
+    registered: HashMap<String, RegisteredCommands>,
+}
+
+#[derive(Default)]
+struct RegisteredCommands {
+    search_items: Vec<String>,
+    hotkeys: Vec<HotkeyId>,
+}
+
+/// Register the commands and hotkeys of every plugin that finished loading
+///
+/// Runs for plugins loaded at startup as well as reloaded ones; anything a previous instance of
+/// the plugin registered is replaced.
+pub fn register_loaded_plugin_commands_system(
+    mut commands: Commands,
+    mut loaded: EventReader<PluginLoaded>,
+    plugins: Query<&LoadingPlugin>,
+    mut registrations: ResMut<PluginCommandRegistrations>,
+    mut search_index: Option<ResMut<SearchIndex>>,
+) {
+    for event in loaded.read() {
+        let Some(manifest) = plugins
+            .iter()
+            .find(|loading| loading.plugin_data.id() == event.plugin_id)
+            .and_then(|loading| loading.plugin_data.manifest())
+        else {
+            continue;
+        };
+
+        let requester = format!("plugin:{}", event.plugin_id);
+        if let Some(previous) = registrations.registered.remove(&event.plugin_id) {
+            release(
+                &mut commands,
+                search_index.as_deref_mut(),
+                &requester,
+                previous,
+            );
+        }
+
+        let mut registered = RegisteredCommands::default();
+
+        for command in &manifest.commands {
+            let item_id = format!("plugin:{}:{}", event.plugin_id, command.id);
+
+            if let Some(search_index) = search_index.as_deref_mut() {
+                let description = command
+                    .subtitle
+                    .clone()
+                    .unwrap_or_else(|| command.description.clone());
+                search_index.add_item(
+                    SearchItem::new(
+                        item_id.clone(),
+                        command.title.clone(),
+                        description,
+                        SearchItemType::Command,
+                    )
+                    .with_keywords(command.keywords.clone()),
+                );
+                registered.search_items.push(item_id.clone());
+            }
+
+            let Some(hotkey) = &command.hotkey else {
+                continue;
+            };
+            match hotkey.parse::<HotKey>() {
+                Ok(hotkey) => {
+                    let binding =
+                        HotkeyBinding::new(HotkeyDefinition::new(hotkey.mods, hotkey.key), item_id)
+                            .with_requester(requester.clone());
+                    registered.hotkeys.push(binding.id.clone());
+                    commands.send_event(HotkeyRegisterRequested { binding });
+                },
+                Err(e) => warn!(
+                    "Plugin '{}' command '{}' has an invalid hotkey '{}': {}",
+                    event.plugin_id, command.id, hotkey, e
+                ),
+            }
+        }
+
+        registrations
+            .registered
+            .insert(event.plugin_id.clone(), registered);
+    }
+}
+
+/// Remove a plugin's command search items and release its hotkeys
+pub(super) fn unregister_plugin_commands(world: &mut World, plugin_id: &str) {
+    let Some(registered) = world
+        .get_resource_mut::<PluginCommandRegistrations>()
+        .and_then(|mut registrations| registrations.registered.remove(plugin_id))
+    else {
+        return;
+    };
+
+    let requester = format!("plugin:{plugin_id}");
+    if let Some(mut search_index) = world.get_resource_mut::<SearchIndex>() {
+        for item in &registered.search_items {
+            search_index.remove_item(item);
+        }
+    }
+    if world.contains_resource::<Events<HotkeyUnregisterRequested>>() {
+        for hotkey_id in registered.hotkeys {
+            world.send_event(HotkeyUnregisterRequested {
+                hotkey_id,
+                requester: requester.clone(),
+            });
+        }
+    }
+}
+
+fn release(
+    commands: &mut Commands,
+    search_index: Option<&mut SearchIndex>,
+    requester: &str,
+    previous: RegisteredCommands,
+) {
+    if let Some(search_index) = search_index {
+        for item in &previous.search_items {
+            search_index.remove_item(item);
+        }
+    }
+    for hotkey_id in previous.hotkeys {
+        commands.send_event(HotkeyUnregisterRequested {
+            hotkey_id,
+            requester: requester.to_string(),
+        });
+    }
+}
@@ -0,0 +1,21 @@
+//! Development mode that reloads plugins when their files change
+//!
+//! Setting [`PLUGIN_DEV_MODE_VAR`] watches the plugin directories through
+//! [`FileSystemWatcher`](ecs_filesystem::watcher::FileSystemWatcher). A change to a plugin file,
+//! or to anything in a Rust plugin project outside its `target` directory, tears the running
+//! instance down and loads the plugin again through the async loader, which reports the outcome
+//! as [`PluginLoaded`](super::PluginLoaded) or [`PluginLoadFailed`](super::PluginLoadFailed).
+//!
+//! A plugin's storage lives under its id in the data directory and is never touched by a
+//! reload, so the new instance sees everything the old one stored.
+
+pub mod commands;
+pub mod reload;
+pub mod watch;
+
+pub use commands::{PluginCommandRegistrations, register_loaded_plugin_commands_system};
+pub use reload::{PluginReloadTask, finish_plugin_reloads_system, unload_plugin};
+pub use watch::{
+    PLUGIN_DEV_MODE_VAR, PluginHotReload, RELOAD_DEBOUNCE, plugin_source,
+    queue_plugin_reloads_system, start_plugin_hot_reload_system,
+};
@@ -0,0 +1,234 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use ecs_service_bridge::events::{LifecycleEventType, PluginLifecycleEvent};
+use ecs_service_bridge::types::TimeStamp;
+
+use super::commands::unregister_plugin_commands;
+use super::watch::PluginHotReload;
+use crate::config::AppDirectories;
+use crate::discovery::DiscoveredPlugin;
+use crate::discovery::core::build_management::find_or_build_plugin_library;
+use crate::discovery::core::wrapper_creation::create_plugin_wrapper_from_file;
+use crate::discovery::{DiscoveryConfig, is_native_plugin_file};
+use crate::plugins::async_loader::{
+    LoadingPlugin, PluginLoadFailed, systems::spawn_plugin_loading,
+};
+use crate::plugins::core::PluginMetadata;
+use crate::plugins::extism::ExtismPluginComponent;
+use crate::plugins::services::PluginId;
+use crate::runtime::deno::plugin_manager::PluginManager;
+use crate::runtime::deno::types::RuntimeConfig;
+use crate::runtime::plugin_wrapper::DenoPluginComponent;
+use crate::runtime::plugin_wrapper::core::DenoPluginWrapper;
+use crate::search::SearchIndex;
+
+/// Directory under the system temp directory holding copies of reloaded native libraries
+pub(super) const SHADOW_DIR_NAME: &str = "action-items-hot-reload";
+
+/// A plugin being loaded again after its files changed
+#[derive(Component)]
+pub struct PluginReloadTask {
+    pub source: PathBuf,
+    task: Task<Result<DiscoveredPlugin, String>>,
+}
+
+impl PluginReloadTask {
+    pub(super) fn new(source: PathBuf, task: Task<Result<DiscoveredPlugin, String>>) -> Self {
+        Self { source, task }
+    }
+}
+
+/// Load a plugin from a plugin file or Rust project without initializing it
+pub(super) fn load_plugin_from_source(source: &Path) -> Result<DiscoveredPlugin, String> {
+    let config = DiscoveryConfig::default();
+    let result = if source.is_dir() {
+        find_or_build_plugin_library(source)
+            .and_then(|library| shadow_copy(&library))
+            .and_then(|library| create_plugin_wrapper_from_file(&library, &config))
+    } else if is_native_plugin_file(source) {
+        shadow_copy(source).and_then(|library| create_plugin_wrapper_from_file(&library, &config))
+    } else if is_deno_source(source) {
+        return load_deno_plugin(source);
+    } else {
+        create_plugin_wrapper_from_file(source, &config)
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Copy a native library to a path that has never been loaded
+///
+/// The dynamic loader hands back the image it already mapped for a path, so loading the rebuilt
+/// library in place would keep running the old code. The copy also leaves the original free for
+/// the next build to overwrite.
+fn shadow_copy(library: &Path) -> crate::Result<PathBuf> {
+    let dir = std::env::temp_dir().join(SHADOW_DIR_NAME);
+    std::fs::create_dir_all(&dir)?;
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let stem = library
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("plugin");
+    let mut copy = dir.join(format!("{stem}-{stamp}"));
+    if let Some(extension) = library.extension() {
+        copy.set_extension(extension);
+    }
+
+    std::fs::copy(library, &copy)?;
+    Ok(copy)
+}
+
+fn is_deno_source(source: &Path) -> bool {
+    matches!(
+        source.extension().and_then(|ext| ext.to_str()),
+        Some("js" | "ts" | "mjs")
+    )
+}
+
+fn load_deno_plugin(source: &Path) -> Result<DiscoveredPlugin, String> {
+    let manifest = PluginManager::new(RuntimeConfig::default()).load_manifest(source)?;
+    let metadata = PluginMetadata {
+        id: manifest.id.clone(),
+        name: manifest.name.clone(),
+        path: source.to_path_buf(),
+        manifest,
+        is_loaded: true,
+        last_accessed: Some(SystemTime::now()),
+        load_count: 1,
+    };
+    DenoPluginWrapper::new(PluginId::from(metadata.id.clone()), metadata)
+        .map(DiscoveredPlugin::Deno)
+        .map_err(|e| e.to_string())
+}
+
+/// Swap in plugins whose reload finished loading
+pub fn finish_plugin_reloads_system(
+    mut commands: Commands,
+    mut reloads: Query<(Entity, &mut PluginReloadTask)>,
+) {
+    for (entity, mut reload) in &mut reloads {
+        let Some(result) = block_on(future::poll_once(&mut reload.task)) else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+
+        let source = reload.source.clone();
+        commands.queue(move |world: &mut World| match result {
+            Ok(plugin) => replace_plugin(world, source, plugin),
+            Err(error) => {
+                warn!(
+                    "Failed to reload plugin from {}: {}",
+                    source.display(),
+                    error
+                );
+                world.send_event(PluginLoadFailed {
+                    path: source,
+                    error,
+                });
+            },
+        });
+    }
+}
+
+/// Tear down the instance loaded from `source` and start initializing its replacement
+fn replace_plugin(world: &mut World, source: PathBuf, plugin: DiscoveredPlugin) {
+    let plugin_id = plugin.id().to_string();
+    let previous_id = world
+        .get_resource_mut::<PluginHotReload>()
+        .and_then(|mut hot_reload| hot_reload.sources.insert(source.clone(), plugin_id.clone()));
+
+    unload_plugin(world, &plugin_id, &source);
+    if let Some(previous_id) = previous_id.filter(|previous_id| *previous_id != plugin_id) {
+        unload_plugin(world, &previous_id, &source);
+    }
+
+    if let DiscoveredPlugin::Deno(wrapper) = &plugin {
+        let metadata = wrapper.metadata();
+        world.spawn(DenoPluginComponent {
+            plugin_id: PluginId::from(metadata.id.clone()),
+            name: metadata.name.clone(),
+            version: metadata.manifest.version.clone(),
+            description: metadata.manifest.description.clone(),
+            entry_point: source,
+        });
+    }
+
+    let app_directories = world
+        .get_resource::<AppDirectories>()
+        .cloned()
+        .unwrap_or_else(AppDirectories::new);
+    spawn_plugin_loading(&mut world.commands(), plugin, app_directories);
+    world.flush();
+}
+
+/// Tear down every running instance of a plugin, leaving its storage in place
+///
+/// Deno plugins are matched by the entry point they were loaded from, everything else by id.
+pub fn unload_plugin(world: &mut World, plugin_id: &str, source: &Path) {
+    let task_pool = AsyncComputeTaskPool::get();
+    let mut entities = Vec::new();
+
+    let mut loaded = world.query::<(Entity, &LoadingPlugin)>();
+    for (entity, loading) in loaded.iter(world) {
+        if loading.plugin_data.id() != plugin_id {
+            continue;
+        }
+        match &loading.plugin_data {
+            DiscoveredPlugin::Native(wrapper) => {
+                wrapper.plugin().write().cleanup(task_pool).detach();
+            },
+            DiscoveredPlugin::Extism(wrapper) => {
+                wrapper.adapter().write().cleanup(task_pool).detach();
+            },
+            DiscoveredPlugin::Raycast(_) | DiscoveredPlugin::Deno(_) => {},
+        }
+        entities.push(entity);
+    }
+
+    let mut extism = world.query::<(Entity, &ExtismPluginComponent)>();
+    entities.extend(
+        extism
+            .iter(world)
+            .filter(|(_, plugin)| plugin.id == plugin_id)
+            .map(|(entity, _)| entity),
+    );
+
+    let mut deno_items = Vec::new();
+    let mut deno = world.query::<(Entity, &DenoPluginComponent)>();
+    for (entity, plugin) in deno.iter(world) {
+        if plugin.entry_point == source {
+            deno_items.push(format!("deno:{}", plugin.name));
+            entities.push(entity);
+        }
+    }
+
+    let was_loaded = !entities.is_empty();
+    for entity in entities {
+        world.despawn(entity);
+    }
+    if let Some(mut search_index) = world.get_resource_mut::<SearchIndex>() {
+        for item in &deno_items {
+            search_index.remove_item(item);
+        }
+    }
+    unregister_plugin_commands(world, plugin_id);
+
+    if !was_loaded {
+        return;
+    }
+    // Drops the plugin's registry entry and message channel in the service bridge
+    if world.contains_resource::<Events<PluginLifecycleEvent>>() {
+        world.send_event(PluginLifecycleEvent {
+            plugin_id: plugin_id.to_string(),
+            event_type: LifecycleEventType::Unregistered,
+            timestamp: TimeStamp::now(),
+        });
+    }
+    info!("Unloaded plugin '{}'", plugin_id);
+}
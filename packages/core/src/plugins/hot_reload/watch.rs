@@ -0,0 +1,154 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use ecs_filesystem::security::PathValidator;
+use ecs_filesystem::watcher::FileSystemWatcher;
+use ecs_filesystem::{FileOperationId, FileSystemChange, SecurityConfig, WatchConfig};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use super::reload::{PluginReloadTask, SHADOW_DIR_NAME, load_plugin_from_source, unload_plugin};
+use crate::discovery::DiscoveryConfig;
+use crate::discovery::core::detection::is_plugin_file;
+
+/// Environment variable that, when set, reloads plugins whenever their files change
+pub const PLUGIN_DEV_MODE_VAR: &str = "ACTION_ITEMS_PLUGIN_DEV_MODE";
+
+/// Quiet period after a source's last change before it is reloaded
+///
+/// Editors and builds write several files in quick succession; each burst reloads once.
+pub const RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watcher over the plugin directories; only present in dev mode
+#[derive(Resource)]
+pub struct PluginHotReload {
+    // Watching stops when the watcher is dropped
+    _watcher: FileSystemWatcher,
+    changes: UnboundedReceiver<Vec<FileSystemChange>>,
+    roots: Vec<PathBuf>,
+    /// Id of the plugin last loaded from each source, which may differ from the new one's
+    pub(super) sources: HashMap<PathBuf, String>,
+    /// Sources waiting to be reloaded, with the time of their last change
+    pending: HashMap<PathBuf, Instant>,
+}
+
+/// Start watching the plugin directories when [`PLUGIN_DEV_MODE_VAR`] is set
+pub fn start_plugin_hot_reload_system(mut commands: Commands) {
+    if std::env::var_os(PLUGIN_DEV_MODE_VAR).is_none() {
+        return;
+    }
+
+    // Notify reports canonical paths, so the roots are compared in the same form
+    let roots: Vec<PathBuf> = DiscoveryConfig::default()
+        .plugin_directories
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .collect();
+
+    // Libraries copied by earlier sessions are no longer mapped by anything
+    let _ = std::fs::remove_dir_all(std::env::temp_dir().join(SHADOW_DIR_NAME));
+
+    let (sender, changes) = tokio::sync::mpsc::unbounded_channel();
+    let validator = PathValidator::new(SecurityConfig {
+        allowed_directories: roots.iter().cloned().collect(),
+        max_path_depth: usize::MAX,
+        ..Default::default()
+    });
+    let watcher = FileSystemWatcher::new(validator, sender);
+    for root in &roots {
+        match watcher.start_watching(FileOperationId::new(), root, WatchConfig::default()) {
+            Ok(()) => info!("Reloading plugins in {} when they change", root.display()),
+            Err(e) => warn!("Failed to watch plugin directory {}: {}", root.display(), e),
+        }
+    }
+
+    commands.insert_resource(PluginHotReload {
+        _watcher: watcher,
+        changes,
+        roots,
+        sources: HashMap::new(),
+        pending: HashMap::new(),
+    });
+}
+
+/// Start reloading every plugin whose files changed and then stayed unchanged for
+/// [`RELOAD_DEBOUNCE`]
+///
+/// A source that is still reloading waits until that reload finishes, so the latest change is
+/// always loaded last.
+pub fn queue_plugin_reloads_system(
+    mut commands: Commands,
+    hot_reload: Option<ResMut<PluginHotReload>>,
+    reloads: Query<&PluginReloadTask>,
+) {
+    let Some(mut hot_reload) = hot_reload else {
+        return;
+    };
+    let hot_reload = &mut *hot_reload;
+
+    let now = Instant::now();
+    while let Ok(changes) = hot_reload.changes.try_recv() {
+        for change in &changes {
+            if let Some(source) = plugin_source(&hot_reload.roots, &change.path) {
+                hot_reload.pending.insert(source, now);
+            }
+        }
+    }
+
+    let reloading: HashSet<&Path> = reloads
+        .iter()
+        .map(|reload| reload.source.as_path())
+        .collect();
+    let due: Vec<PathBuf> = hot_reload
+        .pending
+        .iter()
+        .filter(|(source, changed_at)| {
+            now.duration_since(**changed_at) >= RELOAD_DEBOUNCE
+                && !reloading.contains(source.as_path())
+        })
+        .map(|(source, _)| source.clone())
+        .collect();
+
+    let task_pool = AsyncComputeTaskPool::get();
+    for source in due {
+        hot_reload.pending.remove(&source);
+        if !source.exists() {
+            // Removed from disk: tear the plugin down without loading anything in its place
+            if let Some(plugin_id) = hot_reload.sources.remove(&source) {
+                commands.queue(move |world: &mut World| unload_plugin(world, &plugin_id, &source));
+            }
+            continue;
+        }
+
+        info!("Reloading plugin from {}", source.display());
+        let task_source = source.clone();
+        let task = task_pool.spawn(async move { load_plugin_from_source(&task_source) });
+        commands.spawn(PluginReloadTask::new(source, task));
+    }
+}
+
+/// The plugin a changed path belongs to: a plugin file, or the Rust project containing it
+pub fn plugin_source(roots: &[PathBuf], changed: &Path) -> Option<PathBuf> {
+    let root = roots.iter().find(|root| changed.starts_with(root))?;
+
+    // Rust projects rewrite their build output on every build, including the ones reloads run
+    let relative = changed.strip_prefix(root).ok()?;
+    if relative
+        .components()
+        .any(|part| part.as_os_str() == "target")
+    {
+        return None;
+    }
+
+    let project = changed
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| *dir != root.as_path())
+        .find(|dir| dir.join("Cargo.toml").is_file());
+    match project {
+        Some(project) => Some(project.to_path_buf()),
+        None => is_plugin_file(changed).then(|| changed.to_path_buf()),
+    }
+}
@@ -69,46 +69,3 @@ pub struct ActionItem {
     pub action_id: String,
     pub metadata: Option<Value>,
 }
-
-/// FFI-safe wrapper for a trait object
-/// Trait objects in Rust are fat pointers (pointer + vtable), so we need to
-/// preserve both parts when crossing FFI boundaries
-#[repr(C)]
-pub struct LauncherPluginFFI {
-    data: *mut std::ffi::c_void,
-    vtable: *mut std::ffi::c_void,
-}
-
-/// Helper functions for converting between Box<dyn LauncherPlugin> and FFI handle
-pub mod ffi_helpers {
-    use super::*;
-
-    /// Convert a boxed plugin to an FFI-safe handle
-    pub fn plugin_to_ffi(plugin: Box<dyn LauncherPlugin>) -> LauncherPluginFFI {
-        let raw = Box::into_raw(plugin);
-        // Trait object pointers are fat pointers containing (data_ptr, vtable_ptr)
-        // We need to extract both parts
-        unsafe {
-            let (data, vtable): (*mut std::ffi::c_void, *mut std::ffi::c_void) =
-                std::mem::transmute(raw);
-            LauncherPluginFFI { data, vtable }
-        }
-    }
-
-    /// Convert an FFI handle back to a boxed plugin
-    ///
-    /// # Safety
-    /// The caller must ensure that:
-    /// - The handle was created by `plugin_to_ffi`
-    /// - The handle hasn't been freed already
-    /// - The data and vtable pointers are valid
-    pub unsafe fn ffi_to_plugin(handle: LauncherPluginFFI) -> Box<dyn LauncherPlugin> {
-        // Reconstruct the fat pointer from its parts
-        let fat_ptr: *mut dyn LauncherPlugin =
-            unsafe { std::mem::transmute((handle.data, handle.vtable)) };
-        unsafe { Box::from_raw(fat_ptr) }
-    }
-}
-
-/// The single FFI export that plugins provide
-pub type CreatePluginFn = extern "C" fn() -> LauncherPluginFFI;
@@ -33,8 +33,11 @@ pub use extism::{
     ExtismHostUserData, ExtismPlugin, ExtismPluginAdapter, ExtismPluginLoader,
     create_host_functions,
 };
+pub use hot_reload::{PluginCommandRegistrations, PluginHotReload};
 pub use interface::ActionItem as InterfaceActionItem; // Interface version with different name
 pub use native::wrapper::PluginMetadata as NativePluginMetadata;
+pub use package::{InstalledPackage, PackageError, PackageInstaller, PackageTrust, PluginPackage};
+pub use permissions::{HostAccess, PermissionDenied, PermissionPolicy};
 // native_plugin module removed - use ECS plugin components instead
 pub use services::{PluginCache, StorageDirectory};
 
@@ -45,7 +48,10 @@ pub mod builder;
 pub mod core;
 pub mod ecs_queries;
 pub mod extism;
+pub mod hot_reload;
 pub mod interface;
 pub mod native;
+pub mod package;
+pub mod permissions;
 pub mod service_bridge_integration;
 pub mod services;
@@ -0,0 +1,30 @@
+//! Reporting for native plugins running in their own host process
+
+use std::sync::OnceLock;
+
+use action_items_native::PluginErrorEvent;
+use action_items_native::ipc::HostExit;
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+
+static HOST_EXITS: OnceLock<(Sender<HostExit>, Receiver<HostExit>)> = OnceLock::new();
+
+fn host_exits() -> &'static (Sender<HostExit>, Receiver<HostExit>) {
+    HOST_EXITS.get_or_init(crossbeam_channel::unbounded)
+}
+
+/// Sender handed to every [`IsolatedPlugin`](action_items_native::ipc::IsolatedPlugin)
+pub fn host_exit_sender() -> Sender<HostExit> {
+    host_exits().0.clone()
+}
+
+/// Report plugin host deaths to the ECS world as [`PluginErrorEvent`]s
+pub fn forward_plugin_host_exits_system(mut errors: EventWriter<PluginErrorEvent>) {
+    for exit in host_exits().1.try_iter() {
+        errors.write(PluginErrorEvent {
+            plugin_id: exit.plugin_id,
+            error: format!("Plugin host stopped: {}", exit.reason),
+            recoverable: exit.recoverable,
+        });
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Component, Path};
+
+use action_items_common::plugin_interface::PluginManifest;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::PackageError;
+use super::install::RECORD_FILE;
+
+/// Archive entry holding the manifest
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+/// Archive entry holding the signature
+pub const SIGNATURE_ENTRY: &str = "signature.json";
+/// Directory in the archive holding the plugin's code and assets
+pub const FILES_DIR: &str = "files";
+
+/// A single-file plugin package: a `.tar.gz` with the manifest, the plugin's files and
+/// optionally a signature
+#[derive(Debug, Clone)]
+pub struct PluginPackage {
+    pub manifest: PluginManifest,
+    /// The manifest exactly as stored, which is what the content hash covers
+    manifest_json: Vec<u8>,
+    /// Code and assets by path relative to the plugin directory, with forward slashes
+    pub files: BTreeMap<String, Vec<u8>>,
+    pub signature: Option<PackageSignature>,
+}
+
+/// An ed25519 signature over a package's content hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageSignature {
+    /// Publisher name, for display; trust is decided by the key
+    pub publisher: String,
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+    /// Hex-encoded ed25519 signature
+    pub signature: String,
+}
+
+impl PluginPackage {
+    /// An unsigned package
+    pub fn new(
+        manifest: PluginManifest,
+        files: BTreeMap<String, Vec<u8>>,
+    ) -> Result<Self, PackageError> {
+        for path in files.keys() {
+            validate_path(path)?;
+        }
+        let manifest_json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| PackageError::Malformed(format!("Unserializable manifest: {e}")))?;
+        Ok(Self {
+            manifest,
+            manifest_json,
+            files,
+            signature: None,
+        })
+    }
+
+    /// An unsigned package of every file below `dir`
+    pub fn from_directory(manifest: PluginManifest, dir: &Path) -> Result<Self, PackageError> {
+        let mut files = BTreeMap::new();
+        collect_files(dir, dir, &mut files)?;
+        Self::new(manifest, files)
+    }
+
+    /// SHA-256 over the manifest and every file, with their paths
+    ///
+    /// Each entry contributes its archive path, a NUL byte and the SHA-256 of its contents, in
+    /// path order, so renaming, adding or removing a file changes the hash as well.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        let entries = std::iter::once((MANIFEST_ENTRY.to_string(), &self.manifest_json)).chain(
+            self.files
+                .iter()
+                .map(|(path, contents)| (format!("{FILES_DIR}/{path}"), contents)),
+        );
+        for (path, contents) in entries {
+            hasher.update(path.as_bytes());
+            hasher.update([0]);
+            hasher.update(Sha256::digest(contents));
+        }
+        hasher.finalize().into()
+    }
+
+    /// Sign the package as `publisher`, replacing any previous signature
+    pub fn sign(&mut self, publisher: &str, key: &SigningKey) {
+        let signature = key.sign(&self.content_hash());
+        self.signature = Some(PackageSignature {
+            publisher: publisher.to_string(),
+            public_key: hex::encode(key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+    }
+
+    /// Read a package file
+    pub fn read(path: &Path) -> Result<Self, PackageError> {
+        let file = std::fs::File::open(path)?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+
+        let mut manifest_json = None;
+        let mut signature = None;
+        let mut files = BTreeMap::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path()?.to_string_lossy().replace('\\', "/");
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)?;
+
+            match name.as_str() {
+                MANIFEST_ENTRY => manifest_json = Some(contents),
+                SIGNATURE_ENTRY => {
+                    signature = Some(serde_json::from_slice(&contents).map_err(|e| {
+                        PackageError::Malformed(format!("Invalid signature entry: {e}"))
+                    })?)
+                },
+                _ => {
+                    let Some(relative) = name.strip_prefix(&format!("{FILES_DIR}/")) else {
+                        return Err(PackageError::Malformed(format!(
+                            "Unexpected entry '{name}'"
+                        )));
+                    };
+                    validate_path(relative)?;
+                    files.insert(relative.to_string(), contents);
+                },
+            }
+        }
+
+        let manifest_json = manifest_json
+            .ok_or_else(|| PackageError::Malformed(format!("Missing {MANIFEST_ENTRY}")))?;
+        let manifest = serde_json::from_slice(&manifest_json)
+            .map_err(|e| PackageError::Malformed(format!("Invalid manifest: {e}")))?;
+        Ok(Self {
+            manifest,
+            manifest_json,
+            files,
+            signature,
+        })
+    }
+
+    /// Write the package file
+    pub fn write(&self, path: &Path) -> Result<(), PackageError> {
+        let file = std::fs::File::create(path)?;
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ));
+
+        let mut append = |name: &str, contents: &[u8]| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, contents)
+        };
+        append(MANIFEST_ENTRY, &self.manifest_json)?;
+        for (relative, contents) in &self.files {
+            append(&format!("{FILES_DIR}/{relative}"), contents)?;
+        }
+        if let Some(signature) = &self.signature {
+            let signature = serde_json::to_vec_pretty(signature)
+                .map_err(|e| PackageError::Malformed(format!("Unserializable signature: {e}")))?;
+            append(SIGNATURE_ENTRY, &signature)?;
+        }
+
+        builder.into_inner()?.finish()?;
+        Ok(())
+    }
+}
+
+/// Reject paths that would land outside the plugin directory once installed, or on a file the
+/// installer writes itself
+fn validate_path(path: &str) -> Result<(), PackageError> {
+    let valid = !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if !valid {
+        return Err(PackageError::Malformed(format!(
+            "File path '{path}' leaves the plugin directory"
+        )));
+    }
+    if path == MANIFEST_ENTRY || path == RECORD_FILE {
+        return Err(PackageError::Malformed(format!(
+            "File path '{path}' is reserved for the installer"
+        )));
+    }
+    Ok(())
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<String, Vec<u8>>,
+) -> Result<(), PackageError> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, std::fs::read(&path)?);
+        }
+    }
+    Ok(())
+}
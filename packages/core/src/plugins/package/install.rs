@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::format::MANIFEST_ENTRY;
+use super::{PackageError, PackageTrust, PackageVerification, PluginPackage};
+use crate::config::AppDirectories;
+
+/// Records how an installed plugin got there, beside its manifest
+///
+/// Packages may not ship a file of this name, which [`PluginPackage`] checks.
+pub const RECORD_FILE: &str = ".action-items-package.json";
+
+/// Installs verified packages into one directory per plugin
+pub struct PackageInstaller {
+    install_dir: PathBuf,
+    trust: PackageTrust,
+}
+
+/// A plugin installed from a package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledPackage {
+    pub id: String,
+    pub version: String,
+    /// Trusted publisher that signed the package, or `None` if developer mode let it in
+    pub publisher: Option<String>,
+    /// Hex-encoded content hash of the installed package
+    pub content_hash: String,
+    pub installed_at: SystemTime,
+}
+
+impl PackageInstaller {
+    pub fn new(install_dir: PathBuf, trust: PackageTrust) -> Self {
+        Self { install_dir, trust }
+    }
+
+    /// Where packages are installed in the user's data directory
+    ///
+    /// Plugin discovery scans this directory.
+    pub fn default_install_dir() -> PathBuf {
+        AppDirectories::new().data_dir().join("packages")
+    }
+
+    /// Directory an installed plugin lives in
+    pub fn plugin_path(&self, plugin_id: &str) -> Result<PathBuf, PackageError> {
+        check_plugin_id(plugin_id)?;
+        Ok(self.install_dir.join(plugin_id))
+    }
+
+    /// Verify and install a package file, replacing any installed version of the plugin
+    pub fn install(&self, package_path: &Path) -> Result<InstalledPackage, PackageError> {
+        let package = PluginPackage::read(package_path)?;
+        let publisher = match self.trust.verify(&package)? {
+            PackageVerification::Trusted { publisher } => Some(publisher),
+            PackageVerification::DeveloperMode { .. } => None,
+        };
+
+        let id = package.manifest.id.clone();
+        check_plugin_id(&id)?;
+        let installed = InstalledPackage {
+            id: id.clone(),
+            version: package.manifest.version.clone(),
+            publisher,
+            content_hash: hex::encode(package.content_hash()),
+            installed_at: SystemTime::now(),
+        };
+
+        let staging = self
+            .install_dir
+            .join(".staging")
+            .join(uuid::Uuid::new_v4().to_string());
+        let result = self.install_staged(&package, &installed, &staging);
+        let _ = fs::remove_dir_all(&staging);
+        result?;
+
+        info!(
+            plugin = %id,
+            version = %installed.version,
+            publisher = ?installed.publisher,
+            "Plugin package installed"
+        );
+        Ok(installed)
+    }
+
+    /// Remove an installed plugin
+    pub fn uninstall(&self, plugin_id: &str) -> Result<(), PackageError> {
+        let path = self.plugin_path(plugin_id)?;
+        if !path.join(RECORD_FILE).is_file() {
+            return Err(PackageError::NotInstalled(plugin_id.to_string()));
+        }
+        fs::remove_dir_all(&path)?;
+        info!(plugin = plugin_id, "Plugin package uninstalled");
+        Ok(())
+    }
+
+    /// Plugins installed from packages
+    pub fn installed(&self) -> Result<Vec<InstalledPackage>, PackageError> {
+        let mut installed = Vec::new();
+        if !self.install_dir.exists() {
+            return Ok(installed);
+        }
+        for entry in fs::read_dir(&self.install_dir)? {
+            let record = entry?.path().join(RECORD_FILE);
+            if let Ok(content) = fs::read(&record)
+                && let Ok(package) = serde_json::from_slice(&content)
+            {
+                installed.push(package);
+            }
+        }
+        installed.sort_by(|a: &InstalledPackage, b| a.id.cmp(&b.id));
+        Ok(installed)
+    }
+
+    /// Write the plugin beside its final location so replacing it is a rename
+    fn install_staged(
+        &self,
+        package: &PluginPackage,
+        installed: &InstalledPackage,
+        staging: &Path,
+    ) -> Result<(), PackageError> {
+        for (relative, contents) in &package.files {
+            let path = staging.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+        fs::create_dir_all(staging)?;
+        let manifest = serde_json::to_vec_pretty(&package.manifest)
+            .map_err(|e| PackageError::Malformed(format!("Unserializable manifest: {e}")))?;
+        fs::write(staging.join(MANIFEST_ENTRY), manifest)?;
+        let record = serde_json::to_vec_pretty(installed)
+            .map_err(|e| PackageError::Malformed(format!("Unserializable record: {e}")))?;
+        fs::write(staging.join(RECORD_FILE), record)?;
+
+        let target = self.plugin_path(&installed.id)?;
+        if target.exists() {
+            fs::remove_dir_all(&target)?;
+        }
+        fs::rename(staging, &target)?;
+        Ok(())
+    }
+}
+
+/// Reject ids that are not a single directory below the install directory
+///
+/// Ids starting with `.` would also collide with the staging directory.
+fn check_plugin_id(plugin_id: &str) -> Result<(), PackageError> {
+    if plugin_id.is_empty() || plugin_id.contains(['/', '\\']) || plugin_id.starts_with('.') {
+        return Err(PackageError::InvalidId(plugin_id.to_string()));
+    }
+    Ok(())
+}
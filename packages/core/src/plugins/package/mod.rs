@@ -0,0 +1,44 @@
+//! Signed plugin packages
+//!
+//! A package is a single `.tar.gz` holding a plugin's manifest, code and assets, plus an ed25519
+//! signature over a hash of all of them. Installing verifies the signature against the publisher
+//! keys in [`PackageTrust`] and refuses unsigned or modified packages unless developer mode is
+//! on.
+
+pub mod format;
+pub mod install;
+pub mod trust;
+
+pub use format::{PackageSignature, PluginPackage};
+pub use install::{InstalledPackage, PackageInstaller};
+pub use trust::{PackageTrust, PackageVerification, TrustedPublisher};
+
+/// Why a package could not be read, verified or installed
+#[derive(Debug, thiserror::Error)]
+pub enum PackageError {
+    #[error("Package I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed package: {0}")]
+    Malformed(String),
+    /// Not usable as a directory name below the install directory
+    #[error("Invalid plugin id '{0}'")]
+    InvalidId(String),
+    #[error("Package is not signed")]
+    Unsigned,
+    #[error("Package is signed by untrusted publisher '{0}'")]
+    UntrustedPublisher(String),
+    #[error("Package contents do not match its signature")]
+    Tampered,
+    #[error("Plugin '{0}' is not installed")]
+    NotInstalled(String),
+}
+
+impl From<PackageError> for crate::error::Error {
+    fn from(error: PackageError) -> Self {
+        match error {
+            PackageError::Io(e) => Self::IoError(e.to_string()),
+            PackageError::NotInstalled(id) => Self::PluginNotFound(id),
+            other => Self::PluginError(other.to_string()),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use super::{PackageError, PluginPackage};
+use crate::config::AppDirectories;
+
+/// Publishers whose packages install without asking, and whether anything else may install
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackageTrust {
+    pub trusted_publishers: Vec<TrustedPublisher>,
+    /// Install unsigned, untrusted or modified packages anyway, for plugin development
+    #[serde(default)]
+    pub developer_mode: bool,
+}
+
+/// A publisher key allowed to sign packages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedPublisher {
+    pub name: String,
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+}
+
+/// Why a package was accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageVerification {
+    /// Signed by a trusted publisher and unmodified since
+    Trusted { publisher: String },
+    /// Failed verification but was accepted because developer mode is on
+    DeveloperMode { reason: String },
+}
+
+impl PackageTrust {
+    /// Where package trust settings are kept in the user's config directory
+    pub fn default_path() -> PathBuf {
+        AppDirectories::new().config_dir().join("plugin-trust.json")
+    }
+
+    /// Read trust settings, trusting nobody if the file is missing
+    pub fn load(path: &Path) -> Result<Self, PackageError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| PackageError::Malformed(format!("Invalid trust settings: {e}"))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the trust settings, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), PackageError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| PackageError::Malformed(format!("Unserializable trust settings: {e}")))?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Trust packages signed with `public_key`
+    pub fn trust(&mut self, name: &str, public_key: &VerifyingKey) {
+        let public_key = hex::encode(public_key.as_bytes());
+        self.trusted_publishers
+            .retain(|publisher| publisher.public_key != public_key);
+        self.trusted_publishers.push(TrustedPublisher {
+            name: name.to_string(),
+            public_key,
+        });
+    }
+
+    /// Check a package's signature against the trusted publishers
+    ///
+    /// In developer mode, packages that fail are accepted with the reason they failed.
+    pub fn verify(&self, package: &PluginPackage) -> Result<PackageVerification, PackageError> {
+        match self.check(package) {
+            Ok(publisher) => Ok(PackageVerification::Trusted { publisher }),
+            Err(e) if self.developer_mode => {
+                warn!(
+                    "Accepting plugin package '{}' in developer mode: {}",
+                    package.manifest.id, e
+                );
+                Ok(PackageVerification::DeveloperMode {
+                    reason: e.to_string(),
+                })
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    fn check(&self, package: &PluginPackage) -> Result<String, PackageError> {
+        let signature = package.signature.as_ref().ok_or(PackageError::Unsigned)?;
+        let publisher = self
+            .trusted_publishers
+            .iter()
+            .find(|publisher| {
+                publisher
+                    .public_key
+                    .eq_ignore_ascii_case(&signature.public_key)
+            })
+            .ok_or_else(|| PackageError::UntrustedPublisher(signature.publisher.clone()))?;
+
+        let key = decode::<32>(&publisher.public_key)
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| {
+                PackageError::Malformed(format!("Invalid public key for '{}'", publisher.name))
+            })?;
+        let signature = decode::<64>(&signature.signature)
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(PackageError::Tampered)?;
+        key.verify_strict(&package.content_hash(), &signature)
+            .map_err(|_| PackageError::Tampered)?;
+        Ok(publisher.name.clone())
+    }
+}
+
+fn decode<const N: usize>(hex_string: &str) -> Option<[u8; N]> {
+    hex::decode(hex_string).ok()?.try_into().ok()
+}
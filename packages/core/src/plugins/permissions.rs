@@ -0,0 +1,356 @@
+//! Plugin permission policy
+//!
+//! Every Extism host function and Deno op that reaches outside the plugin checks the caller's
+//! manifest [`PluginPermissions`] through a [`PermissionPolicy`] first. Denied calls return a
+//! [`PermissionDenied`] to the plugin and are reported to the ECS world as an audit event by
+//! [`forward_permission_denials_system`].
+//!
+//! Storage, cache, preferences and search ingest host functions are namespaced by plugin id and
+//! need no grant.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+
+use action_items_common::plugin_interface::PluginPermissions;
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+
+/// Denials kept for the audit system before new ones are dropped
+const AUDIT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Grant that allows every host, file or command
+const WILDCARD: &str = "*";
+
+static AUDIT_CHANNEL: OnceLock<(Sender<PermissionDenied>, Receiver<PermissionDenied>)> =
+    OnceLock::new();
+
+fn audit_channel() -> &'static (Sender<PermissionDenied>, Receiver<PermissionDenied>) {
+    AUDIT_CHANNEL.get_or_init(|| crossbeam_channel::bounded(AUDIT_CHANNEL_CAPACITY))
+}
+
+/// A host call a plugin is attempting
+#[derive(Debug, Clone, Copy)]
+pub enum HostAccess<'a> {
+    ReadClipboard,
+    WriteClipboard,
+    Notification,
+    /// HTTP request to a URL
+    Network {
+        url: &'a str,
+    },
+    ReadFile(&'a Path),
+    WriteFile(&'a Path),
+    /// Command line to run; its first word is the program
+    ExecuteCommand(&'a str),
+    /// File (as a `file:` URL) or non-HTTP URL handed to the platform opener
+    ///
+    /// The opener can run what it is given, such as `.desktop` files or the application
+    /// registered for a URL scheme, so this is guarded by `execute_commands`.
+    Open(&'a str),
+    EnvironmentVariable(&'a str),
+}
+
+impl HostAccess<'_> {
+    /// Name of the manifest permission guarding this call
+    pub fn permission(&self) -> &'static str {
+        match self {
+            Self::ReadClipboard => "read_clipboard",
+            Self::WriteClipboard => "write_clipboard",
+            Self::Notification => "system_notifications",
+            Self::Network { .. } => "network_hosts",
+            Self::ReadFile(_) => "read_files",
+            Self::WriteFile(_) => "write_files",
+            Self::ExecuteCommand(_) | Self::Open(_) => "execute_commands",
+            Self::EnvironmentVariable(_) => "environment_variables",
+        }
+    }
+
+    fn resource(&self) -> Option<String> {
+        match self {
+            Self::ReadClipboard | Self::WriteClipboard | Self::Notification => None,
+            Self::Network { url } => Some(url.to_string()),
+            Self::ReadFile(path) | Self::WriteFile(path) => Some(path.display().to_string()),
+            Self::ExecuteCommand(command) | Self::Open(command) => Some(command.to_string()),
+            Self::EnvironmentVariable(name) => Some(name.to_string()),
+        }
+    }
+}
+
+/// Structured denial returned to a plugin and sent as an audit event
+#[derive(Event, Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("Plugin '{plugin_id}' denied {permission}: {reason}")]
+pub struct PermissionDenied {
+    pub plugin_id: String,
+    /// Manifest permission that would allow the call
+    pub permission: String,
+    /// Host, path, command or variable the plugin asked for
+    pub resource: Option<String>,
+    pub reason: String,
+}
+
+impl PermissionDenied {
+    /// JSON response for Deno ops, shaped like their other failures
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "success": false,
+            "error": "permission_denied",
+            "permission": self.permission,
+            "resource": self.resource,
+            "reason": self.reason,
+        })
+    }
+}
+
+/// Permission checks for one plugin
+#[derive(Debug, Clone)]
+pub struct PermissionPolicy {
+    plugin_id: String,
+    permissions: PluginPermissions,
+}
+
+impl PermissionPolicy {
+    pub fn new(plugin_id: impl Into<String>, permissions: PluginPermissions) -> Self {
+        Self {
+            plugin_id: plugin_id.into(),
+            permissions,
+        }
+    }
+
+    pub fn plugin_id(&self) -> &str {
+        &self.plugin_id
+    }
+
+    pub fn permissions(&self) -> &PluginPermissions {
+        &self.permissions
+    }
+
+    /// Whether the plugin may see the launcher's environment variable `name`
+    ///
+    /// Unlike [`check`](Self::check) this does not audit, since commands the plugin runs get
+    /// every granted variable and silently lack the rest.
+    pub fn environment_granted(&self, name: &str) -> bool {
+        self.evaluate(&HostAccess::EnvironmentVariable(name)).is_ok()
+    }
+
+    /// Check a host call, auditing it if it is denied
+    pub fn check(&self, access: HostAccess<'_>) -> Result<(), PermissionDenied> {
+        self.evaluate(&access)
+            .map_err(|reason| self.deny(&access, reason))
+    }
+
+    /// Check a file read and return the path to open, with symlinks resolved
+    ///
+    /// Open only the returned path: the one the plugin gave may lead elsewhere through links.
+    pub fn check_read(&self, path: &Path) -> Result<PathBuf, PermissionDenied> {
+        path_allowed(&self.permissions.read_files, path)
+            .map_err(|reason| self.deny(&HostAccess::ReadFile(path), reason))
+    }
+
+    /// Check a file write and return the path to write, with symlinks resolved
+    pub fn check_write(&self, path: &Path) -> Result<PathBuf, PermissionDenied> {
+        path_allowed(&self.permissions.write_files, path)
+            .map_err(|reason| self.deny(&HostAccess::WriteFile(path), reason))
+    }
+
+    fn deny(&self, access: &HostAccess<'_>, reason: String) -> PermissionDenied {
+        let denial = PermissionDenied {
+            plugin_id: self.plugin_id.clone(),
+            permission: access.permission().to_string(),
+            resource: access.resource(),
+            reason,
+        };
+        warn!("{}", denial);
+        // A full channel means nothing drains it; the call is still denied
+        let _ = audit_channel().0.try_send(denial.clone());
+        denial
+    }
+
+    fn evaluate(&self, access: &HostAccess<'_>) -> Result<(), String> {
+        let permissions = &self.permissions;
+        match *access {
+            HostAccess::ReadClipboard => granted(permissions.read_clipboard),
+            HostAccess::WriteClipboard => granted(permissions.write_clipboard),
+            HostAccess::Notification => granted(permissions.system_notifications),
+            HostAccess::Network { url } => {
+                let host = request_host(url)?;
+                allowed(&permissions.network_hosts, |pattern| {
+                    host_matches(pattern, &host)
+                })
+            },
+            HostAccess::ReadFile(path) => path_allowed(&permissions.read_files, path).map(drop),
+            HostAccess::WriteFile(path) => path_allowed(&permissions.write_files, path).map(drop),
+            HostAccess::ExecuteCommand(command) => {
+                let program = command
+                    .split_whitespace()
+                    .next()
+                    .ok_or_else(|| "empty command".to_string())?;
+                allowed(&permissions.execute_commands, |pattern| {
+                    program_matches(pattern, program)
+                })
+            },
+            HostAccess::Open(target) => allowed(&permissions.execute_commands, |pattern| {
+                open_matches(pattern, target)
+            }),
+            HostAccess::EnvironmentVariable(name) => {
+                allowed(&permissions.environment_variables, |pattern| {
+                    wildcard_match(pattern, name)
+                })
+            },
+        }
+    }
+}
+
+/// Move denials from host threads into the ECS world as [`PermissionDenied`] events
+pub fn forward_permission_denials_system(mut denials: EventWriter<PermissionDenied>) {
+    for denial in audit_channel().1.try_iter() {
+        denials.write(denial);
+    }
+}
+
+fn granted(flag: bool) -> Result<(), String> {
+    if flag {
+        Ok(())
+    } else {
+        Err("not granted in the plugin manifest".to_string())
+    }
+}
+
+fn allowed(patterns: &[String], matches: impl Fn(&str) -> bool) -> Result<(), String> {
+    if patterns.iter().any(|pattern| matches(pattern)) {
+        Ok(())
+    } else {
+        Err("not covered by any pattern in the plugin manifest".to_string())
+    }
+}
+
+/// Host, with its port if the URL names one, of an http or https URL
+fn request_host(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid URL: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("unsupported URL scheme '{}'", parsed.scheme()));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "URL has no host".to_string())?
+        .to_ascii_lowercase();
+    Ok(match parsed.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host,
+    })
+}
+
+/// Match a host pattern such as `api.github.com`, `*.example.com` or `localhost:8080`
+///
+/// Patterns without a port allow any port.
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    if pattern.contains(':') {
+        return wildcard_match(&pattern, host);
+    }
+    let name = host.split(':').next().unwrap_or(host);
+    wildcard_match(&pattern, name)
+}
+
+/// Match a command pattern against the program of a command line
+///
+/// Patterns are compared with the program exactly: a bare name such as `git` allows only `git`,
+/// looked up on the launcher's `PATH`, never a program of that name elsewhere.
+fn program_matches(pattern: &str, program: &str) -> bool {
+    pattern == WILDCARD || pattern == program
+}
+
+/// Match a command pattern against a target handed to the platform opener
+///
+/// Besides `*` and the exact target, a scheme pattern such as `mailto:` or `file:` allows every
+/// target with that scheme.
+fn open_matches(pattern: &str, target: &str) -> bool {
+    if pattern == WILDCARD || pattern == target {
+        return true;
+    }
+    match (pattern.strip_suffix(':'), target.split_once(':')) {
+        (Some(scheme), Some((target_scheme, _))) => {
+            !scheme.is_empty() && scheme.eq_ignore_ascii_case(target_scheme)
+        },
+        _ => false,
+    }
+}
+
+/// Check a path against granted directory prefixes, compared component by component
+///
+/// Returns the path with symlinks resolved.
+fn path_allowed(prefixes: &[PathBuf], path: &Path) -> Result<PathBuf, String> {
+    let path = resolve_path(path)?;
+    let covered = prefixes.iter().any(|prefix| {
+        prefix.as_os_str() == WILDCARD
+            || resolve_path(&expand_home(prefix)).is_ok_and(|prefix| path.starts_with(prefix))
+    });
+    if covered {
+        Ok(path)
+    } else {
+        Err("outside the directories granted in the plugin manifest".to_string())
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Absolute path with symlinks followed where the path exists
+///
+/// Relative paths are rejected since plugins do not share the host's working directory. So
+/// are paths with `..`: the OS applies it after following the link before it, which textual
+/// resolution cannot know.
+fn resolve_path(path: &Path) -> Result<PathBuf, String> {
+    if !path.is_absolute() {
+        return Err(format!("path '{}' is not absolute", path.display()));
+    }
+    if path
+        .components()
+        .any(|component| component == Component::ParentDir)
+    {
+        return Err(format!("path '{}' contains '..'", path.display()));
+    }
+    let resolved: PathBuf = path.components().collect();
+
+    // Follow symlinks in the longest existing ancestor so links cannot leave a granted prefix
+    let mut existing = resolved.as_path();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = std::fs::canonicalize(existing) {
+            return Ok(missing
+                .iter()
+                .rev()
+                .fold(canonical, |path, name| path.join(name)));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent;
+            },
+            _ => return Ok(resolved),
+        }
+    }
+}
+
+/// Match text against a pattern in which `*` stands for any run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match remaining.find(part) {
+            Some(at) => remaining = &remaining[at + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
@@ -427,6 +427,7 @@ mod tests {
             max_launcher_version: None,
             update_url: None,
             changelog_url: None,
+            resource_limits: Default::default(),
         };
 
         let mock_plugin = MockNativePlugin {
@@ -1,7 +1,11 @@
 //! Raycast API Adapter Generation
 //!
-//! Zero-allocation API adapter generator that creates JavaScript compatibility layer
-//! for Raycast extensions with blazing-fast performance.
+//! Generates the JavaScript that stands in for `react`, `@raycast/api` and `@raycast/utils` when
+//! a Raycast extension runs in the Deno runtime. Extensions are prebuilt CommonJS bundles, so the
+//! shim hands those modules to the bundle's `require` and renders the command's component tree
+//! with a small reconciler: hook state lives on the fiber that called the hook, effects run after
+//! each commit and clean up on re-run or unmount, and every commit is diffed against the previous
+//! one into the view updates described in [`super::reconciler`].
 
 use crate::error::Result;
 
@@ -11,160 +15,1155 @@ pub fn create_api_adapter() -> Result<String> {
 }
 
 /// Create the Raycast API adapter that maps to our plugin interface
+///
+/// Evaluated once per runtime as a classic script; it installs `globalThis.__raycast__`.
 fn create_raycast_api_adapter() -> String {
-    r#"
-// @raycast/api adapter for Action Items
-import { PluginContext } from './plugin-context.js';
+    r##"
+(() => {
+"use strict";
 
-// React-like component system
-const React = {
-    createElement(type, props, ...children) {
-        return { type, props: props || {}, children };
-    },
-    useState(initial) {
-        // Simplified state management
-        let state = initial;
-        const setState = (newState) => {
-            state = typeof newState === 'function' ? newState(state) : newState;
-            // Trigger re-render through our plugin system
-            globalThis.__actionItems__.rerender();
-        };
-        return [state, setState];
-    },
-    useEffect(effect, deps) {
-        // Simplified effect hook
-        globalThis.__actionItems__.registerEffect(effect, deps);
+const core = Deno.core;
+
+// Call an op the launcher may not provide; the API degrades to a no-op without it
+function hostCall(name, ...args) {
+    const op = core.ops[name];
+    if (typeof op !== "function") {
+        console.warn(`${name} is not available in this launcher`);
+        return undefined;
+    }
+    return op(...args);
+}
+
+if (typeof globalThis.setTimeout !== "function" && typeof core.queueUserTimer === "function") {
+    const schedule = (repeat, callback, delay, args) =>
+        core.queueUserTimer(core.getTimerDepth() + 1, repeat, Math.max(0, delay || 0), () =>
+            callback(...args),
+        );
+    globalThis.setTimeout = (callback, delay, ...args) => schedule(false, callback, delay, args);
+    globalThis.setInterval = (callback, delay, ...args) => schedule(true, callback, delay, args);
+    globalThis.clearTimeout = (id) => id != null && core.cancelTimer(id);
+    globalThis.clearInterval = globalThis.clearTimeout;
+}
+
+// ---------------------------------------------------------------------------------------------
+// Elements
+
+const ELEMENT = Symbol.for("react.element");
+const PROVIDER = Symbol.for("react.provider");
+const CONTEXT = Symbol.for("react.context");
+const Fragment = Symbol.for("react.fragment");
+const TEXT = Symbol.for("action-items.text");
+
+function applyDefaultProps(type, props) {
+    if (type && type.defaultProps) {
+        for (const name in type.defaultProps) {
+            if (props[name] === undefined) props[name] = type.defaultProps[name];
+        }
+    }
+    return props;
+}
+
+function createElement(type, config, ...children) {
+    const props = {};
+    let key = null;
+    if (config != null) {
+        for (const name in config) {
+            if (name === "key") key = config.key == null ? null : String(config.key);
+            else if (name !== "ref" && name !== "__self" && name !== "__source") props[name] = config[name];
+        }
+    }
+    if (children.length === 1) props.children = children[0];
+    else if (children.length > 1) props.children = children;
+    return { $$typeof: ELEMENT, type, key, props: applyDefaultProps(type, props) };
+}
+
+function jsx(type, config, maybeKey) {
+    const props = {};
+    let key = maybeKey === undefined ? null : String(maybeKey);
+    for (const name in config) {
+        if (name === "key") key = String(config.key);
+        else if (name !== "ref") props[name] = config[name];
     }
+    return { $$typeof: ELEMENT, type, key, props: applyDefaultProps(type, props) };
+}
+
+const isValidElement = (value) =>
+    value != null && typeof value === "object" && value.$$typeof === ELEMENT;
+
+function cloneElement(element, config, ...children) {
+    const props = { ...element.props };
+    let key = element.key;
+    if (config != null) {
+        for (const name in config) {
+            if (name === "key") key = config.key == null ? null : String(config.key);
+            else if (name !== "ref") props[name] = config[name];
+        }
+    }
+    if (children.length === 1) props.children = children[0];
+    else if (children.length > 1) props.children = children;
+    return { $$typeof: ELEMENT, type: element.type, key, props };
+}
+
+function flattenChildren(children, out = []) {
+    if (children == null || typeof children === "boolean") return out;
+    if (Array.isArray(children)) {
+        for (const child of children) flattenChildren(child, out);
+    } else {
+        out.push(children);
+    }
+    return out;
+}
+
+const Children = {
+    toArray: (children) => flattenChildren(children),
+    map: (children, fn) => (children == null ? children : flattenChildren(children).map(fn)),
+    forEach: (children, fn) => flattenChildren(children).forEach(fn),
+    count: (children) => flattenChildren(children).length,
+    only(children) {
+        if (!isValidElement(children)) throw new Error("Children.only expected a single element");
+        return children;
+    },
 };
 
-// Raycast UI components mapped to our search results
-export function List({ searchBarPlaceholder, onSearchTextChange, throttle, children }) {
-    const items = React.Children.toArray(children);
-    return {
-        type: 'list',
-        placeholder: searchBarPlaceholder || 'Search...',
-        onSearchTextChange,
-        throttle,
-        items: items.map(child => ({
-            ...child.props,
-            id: child.props.id || child.props.title?.replace(/\s+/g, '_').toLowerCase(),
-        }))
-    };
+function createContext(defaultValue) {
+    const context = { $$typeof: CONTEXT, defaultValue };
+    context.Provider = { $$typeof: PROVIDER, context };
+    context.Consumer = ({ children }) => children(useContext(context));
+    return context;
 }
 
-export function Detail({ markdown, actions }) {
-    return {
-        type: 'detail',
-        content: markdown,
-        actions: actions?.props?.children || []
+const memo = (component) => component;
+const forwardRef = (render) => (props) => render(props, null);
+
+// Components rendered by the launcher rather than by the extension
+function hostComponent(name, actions) {
+    const component = () => {
+        throw new Error(`${name} is rendered by the launcher`);
     };
+    component.$$host = name;
+    component.$$actions = actions;
+    component.displayName = name;
+    return component;
 }
 
-export const Icon = {
-    Envelope: 'envelope',
-    Key: 'key',
-    Globe: 'globe',
-    Trash: 'trash',
-    Eye: 'eye',
-    Message: 'message',
-    Terminal: 'terminal',
-    Calendar: 'calendar',
-    Clock: 'clock',
-    Document: 'document',
-    Folder: 'folder',
-    Gear: 'gear',
-    Heart: 'heart',
-    Home: 'home',
-    Lightbulb: 'lightbulb',
-    List: 'list',
-    Lock: 'lock',
-    Person: 'person',
-    Phone: 'phone',
-    Photo: 'photo',
-    Play: 'play',
-    Plus: 'plus',
-    QuestionMark: 'question-mark',
-    Search: 'search',
-    Star: 'star',
-    Tag: 'tag',
-    Video: 'video',
-    Warning: 'warning',
-    Wand: 'wand',
-    // Complete icon mapping for Raycast compatibility
-};
+const isHost = (type) => typeof type === "function" && type.$$host !== undefined;
 
-export const Color = {
-    Purple: '#8B5CF6',
-    Blue: '#3B82F6',
-    Red: '#EF4444',
-    Green: '#10B981',
-    // ... map all Raycast colors
-};
+// ---------------------------------------------------------------------------------------------
+// Fibers
 
-export function ActionPanel({ children }) {
+const MAX_RENDER_PASSES = 50;
+
+let nextFiberId = 1;
+let nextUseId = 1;
+let currentFiber = null;
+let hookIndex = 0;
+let rendering = false;
+let flushScheduled = false;
+const dirty = new Set();
+let pendingEffects = [];
+let pendingCleanups = [];
+
+// Roots of the navigation stack; the top one is on screen
+const stack = [];
+let committedView = null;
+let handlers = new Map();
+let updates = [];
+
+function createFiber(element, parent) {
     return {
-        type: 'action-panel',
-        actions: React.Children.toArray(children)
+        id: nextFiberId++,
+        type: element.type,
+        key: element.key,
+        props: element.props,
+        parent,
+        depth: parent ? parent.depth + 1 : 0,
+        hooks: [],
+        children: [],
+        slots: {},
+        unmounted: false,
     };
 }
 
-export const Action = {
-    CopyToClipboard({ title, content, icon, shortcut }) {
-        return {
-            type: 'copy-to-clipboard',
-            title,
-            content,
-            icon,
-            shortcut
+function textElement(value) {
+    return { $$typeof: ELEMENT, type: TEXT, key: null, props: { text: String(value) } };
+}
+
+// Flatten children, giving each a slot that stays put when siblings before it come and go
+function normalizeChildren(value, out, scope, index) {
+    if (value == null || typeof value === "boolean") return out;
+    if (Array.isArray(value)) {
+        value.forEach((child, i) => normalizeChildren(child, out, `${scope}${index}/`, i));
+    } else if (typeof value === "string" || typeof value === "number") {
+        out.push({ element: textElement(value), slot: `${scope}${index}` });
+    } else if (isValidElement(value)) {
+        const slot = value.key == null ? `${scope}${index}` : `${scope}$${value.key}`;
+        out.push({ element: value, slot });
+    } else {
+        throw new Error(`Objects are not valid as a child: ${JSON.stringify(value)}`);
+    }
+    return out;
+}
+
+function reconcileChildren(fiber, value) {
+    const previous = new Map(fiber.children.map((child) => [child.slot, child]));
+    fiber.children = normalizeChildren(value, [], "", 0).map(({ element, slot }) => {
+        const existing = previous.get(slot);
+        if (existing && existing.type === element.type) {
+            previous.delete(slot);
+            existing.props = element.props;
+            renderFiber(existing);
+            return existing;
+        }
+        const child = createFiber(element, fiber);
+        child.slot = slot;
+        renderFiber(child);
+        return child;
+    });
+    for (const stale of previous.values()) unmountFiber(stale);
+}
+
+// Element-valued props of host components, such as `actions`, render as subtrees of their own
+function reconcileSlots(fiber) {
+    const actions = fiber.type.$$actions || {};
+    for (const [name, value] of Object.entries(fiber.props)) {
+        if (name === "children" || name in actions || !isValidElement(value)) continue;
+        let slot = fiber.slots[name];
+        if (!slot) {
+            slot = createFiber({ type: Fragment, key: null, props: {} }, fiber);
+            fiber.slots[name] = slot;
+        }
+        slot.props = { children: value };
+        renderFiber(slot);
+    }
+    for (const [name, slot] of Object.entries(fiber.slots)) {
+        if (!isValidElement(fiber.props[name]) || name in actions) {
+            unmountFiber(slot);
+            delete fiber.slots[name];
+        }
+    }
+}
+
+function renderFiber(fiber) {
+    dirty.delete(fiber);
+    const type = fiber.type;
+    if (type === TEXT) return;
+    if (isHost(type)) {
+        reconcileSlots(fiber);
+        reconcileChildren(fiber, fiber.props.children);
+    } else if (typeof type === "function") {
+        const previous = currentFiber;
+        currentFiber = fiber;
+        hookIndex = 0;
+        let output;
+        try {
+            output = type(fiber.props);
+        } finally {
+            currentFiber = previous;
+        }
+        reconcileChildren(fiber, output);
+    } else if (
+        type === Fragment ||
+        (type && (type.$$typeof === PROVIDER || type.$$typeof === CONTEXT))
+    ) {
+        reconcileChildren(fiber, fiber.props.children);
+    } else {
+        throw new Error(`Unsupported element type: ${String(type)}`);
+    }
+}
+
+function unmountFiber(fiber) {
+    fiber.unmounted = true;
+    dirty.delete(fiber);
+    for (const hook of fiber.hooks) {
+        if (typeof hook.cleanup === "function") pendingCleanups.push(hook.cleanup);
+        hook.cleanup = undefined;
+    }
+    fiber.children.forEach(unmountFiber);
+    Object.values(fiber.slots).forEach(unmountFiber);
+}
+
+function scheduleFlush() {
+    if (flushScheduled) return;
+    flushScheduled = true;
+    Promise.resolve().then(flush);
+}
+
+function scheduleUpdate(fiber) {
+    if (fiber.unmounted) return;
+    dirty.add(fiber);
+    if (!rendering) scheduleFlush();
+}
+
+function flush() {
+    flushScheduled = false;
+    try {
+        let passes = 0;
+        while (dirty.size > 0) {
+            if (++passes > MAX_RENDER_PASSES) {
+                dirty.clear();
+                throw new Error("Too many re-renders; a component updates its state on every render");
+            }
+            const fibers = [...dirty].sort((a, b) => a.depth - b.depth);
+            rendering = true;
+            try {
+                for (const fiber of fibers) {
+                    if (dirty.has(fiber) && !fiber.unmounted) renderFiber(fiber);
+                    dirty.delete(fiber);
+                }
+            } finally {
+                rendering = false;
+            }
+        }
+        commit();
+    } catch (error) {
+        reportError(error);
+    }
+}
+
+function commit() {
+    const nextHandlers = new Map();
+    const top = stack[stack.length - 1];
+    const view = top ? collectHostNodes(top, nextHandlers, [])[0] || null : null;
+    handlers = nextHandlers;
+    diffRoot(committedView, view, updates);
+    committedView = view;
+
+    const cleanups = pendingCleanups;
+    const effects = pendingEffects;
+    pendingCleanups = [];
+    pendingEffects = [];
+    for (const cleanup of cleanups) guard(cleanup);
+    for (const effect of effects) guard(effect);
+}
+
+function guard(fn) {
+    try {
+        fn();
+    } catch (error) {
+        reportError(error);
+    }
+}
+
+function reportError(error) {
+    const message = error && error.stack ? String(error.stack) : String(error);
+    console.error(message);
+    updates.push({ op: "error", message });
+}
+
+// ---------------------------------------------------------------------------------------------
+// Host nodes and diffing
+
+function collectHostNodes(fiber, registry, out) {
+    if (fiber.type === TEXT) {
+        out.push({ type: "#text", key: null, props: { text: fiber.props.text }, children: [] });
+    } else if (isHost(fiber.type)) {
+        out.push(hostNode(fiber, registry));
+    } else {
+        for (const child of fiber.children) collectHostNodes(child, registry, out);
+    }
+    return out;
+}
+
+function hostNode(fiber, registry) {
+    const actions = fiber.type.$$actions || {};
+    const props = {};
+    for (const [name, value] of Object.entries(fiber.props)) {
+        if (name === "children" || value === undefined) continue;
+        const id = `${fiber.id}.${name}`;
+        if (name in actions) {
+            registry.set(id, actions[name](fiber.props));
+            props[name] = { $handler: id };
+        } else if (fiber.slots[name]) {
+            props[name] = collectHostNodes(fiber.slots[name], registry, [])[0] || null;
+        } else {
+            props[name] = serializeValue(value, registry, id);
+        }
+    }
+    const children = [];
+    for (const child of fiber.children) collectHostNodes(child, registry, children);
+    return { type: fiber.type.$$host, key: fiber.key, props, children };
+}
+
+// Turn a prop into JSON; callbacks become handlers the launcher can dispatch by id
+function serializeValue(value, registry, id) {
+    if (typeof value === "function") {
+        registry.set(id, value);
+        return { $handler: id };
+    }
+    if (value === null || typeof value !== "object") {
+        return typeof value === "bigint" || typeof value === "symbol" ? String(value) : value;
+    }
+    if (value instanceof Date) return value.toISOString();
+    if (isValidElement(value)) return null;
+    if (Array.isArray(value)) return value.map((item, i) => serializeValue(item, registry, `${id}.${i}`));
+    const object = {};
+    for (const [name, item] of Object.entries(value)) {
+        if (item !== undefined) object[name] = serializeValue(item, registry, `${id}.${name}`);
+    }
+    return object;
+}
+
+function diffRoot(previous, next, out) {
+    if (!previous || !next || previous.type !== next.type || previous.key !== next.key) {
+        if (previous !== next) out.push({ op: "replace", path: [], node: next });
+        return;
+    }
+    diffNode(previous, next, [], out);
+}
+
+function diffNode(previous, next, path, out) {
+    if (previous.type !== next.type || previous.key !== next.key) {
+        out.push({ op: "replace", path, node: next });
+        return;
+    }
+    if (JSON.stringify(previous.props) !== JSON.stringify(next.props)) {
+        out.push({ op: "set_props", path, props: next.props });
+    }
+    const before = previous.children;
+    const after = next.children;
+    const common = Math.min(before.length, after.length);
+    for (let i = 0; i < common; i++) diffNode(before[i], after[i], [...path, i], out);
+    for (let i = before.length - 1; i >= after.length; i--) out.push({ op: "remove", path: [...path, i] });
+    for (let i = common; i < after.length; i++) out.push({ op: "insert", path: [...path, i], node: after[i] });
+}
+
+// ---------------------------------------------------------------------------------------------
+// Hooks
+
+function nextHook(create) {
+    if (!currentFiber) throw new Error("Hooks can only be called inside the body of a function component");
+    const hooks = currentFiber.hooks;
+    if (hookIndex >= hooks.length) hooks.push(create());
+    return hooks[hookIndex++];
+}
+
+function depsChanged(previous, next) {
+    if (previous === undefined || next === undefined || previous.length !== next.length) return true;
+    return next.some((dep, i) => !Object.is(dep, previous[i]));
+}
+
+function useReducer(reducer, initialArg, init) {
+    const fiber = currentFiber;
+    const hook = nextHook(() => ({ state: init ? init(initialArg) : initialArg }));
+    hook.reducer = reducer;
+    if (!hook.dispatch) {
+        hook.dispatch = (action) => {
+            const next = hook.reducer(hook.state, action);
+            if (Object.is(next, hook.state)) return;
+            hook.state = next;
+            scheduleUpdate(fiber);
         };
+    }
+    return [hook.state, hook.dispatch];
+}
+
+const basicStateReducer = (state, action) => (typeof action === "function" ? action(state) : action);
+
+function useState(initialState) {
+    return useReducer(basicStateReducer, initialState, (value) =>
+        typeof value === "function" ? value() : value,
+    );
+}
+
+function useEffect(effect, deps) {
+    const fiber = currentFiber;
+    const hook = nextHook(() => ({ deps: undefined, cleanup: undefined }));
+    if (!depsChanged(hook.deps, deps) && hook.ran) return;
+    hook.ran = true;
+    hook.deps = deps;
+    pendingEffects.push(() => {
+        if (fiber.unmounted) return;
+        if (typeof hook.cleanup === "function") hook.cleanup();
+        const cleanup = effect();
+        hook.cleanup = typeof cleanup === "function" ? cleanup : undefined;
+    });
+}
+
+function useMemo(factory, deps) {
+    const hook = nextHook(() => ({ deps: undefined, value: undefined, computed: false }));
+    if (!hook.computed || depsChanged(hook.deps, deps)) {
+        hook.value = factory();
+        hook.deps = deps;
+        hook.computed = true;
+    }
+    return hook.value;
+}
+
+const useCallback = (callback, deps) => useMemo(() => callback, deps);
+const useRef = (initialValue) => nextHook(() => ({ current: initialValue }));
+const useId = () => nextHook(() => ({ id: `:r${nextUseId++}:` })).id;
+
+function useContext(context) {
+    for (let fiber = currentFiber && currentFiber.parent; fiber; fiber = fiber.parent) {
+        const type = fiber.type;
+        if (type === context || (type && type.$$typeof === PROVIDER && type.context === context)) {
+            return fiber.props.value;
+        }
+    }
+    return context.defaultValue;
+}
+
+function useSyncExternalStore(subscribe, getSnapshot) {
+    const value = getSnapshot();
+    const [, forceRender] = useReducer((count) => count + 1, 0);
+    useEffect(() => subscribe(() => forceRender()), [subscribe]);
+    return value;
+}
+
+const React = {
+    Children,
+    Fragment,
+    StrictMode: Fragment,
+    Suspense: Fragment,
+    cloneElement,
+    createContext,
+    createElement,
+    forwardRef,
+    isValidElement,
+    memo,
+    useCallback,
+    useContext,
+    useDebugValue: () => {},
+    useDeferredValue: (value) => value,
+    useEffect,
+    useId,
+    useImperativeHandle: () => {},
+    useInsertionEffect: useEffect,
+    useLayoutEffect: useEffect,
+    useMemo,
+    useReducer,
+    useRef,
+    useState,
+    useSyncExternalStore,
+    useTransition: () => [false, (callback) => callback()],
+    version: "18.2.0",
+};
+React.default = React;
+
+const jsxRuntime = { Fragment, jsx, jsxs: jsx, jsxDEV: jsx };
+
+// ---------------------------------------------------------------------------------------------
+// Navigation
+
+function push(element) {
+    const root = createFiber({ type: Fragment, key: null, props: { children: element } }, null);
+    stack.push(root);
+    scheduleUpdate(root);
+}
+
+function pop() {
+    if (stack.length <= 1) return;
+    unmountFiber(stack.pop());
+    scheduleFlush();
+}
+
+function popToRootView() {
+    while (stack.length > 1) unmountFiber(stack.pop());
+    scheduleFlush();
+}
+
+const navigation = { push, pop };
+const useNavigation = () => navigation;
+
+// ---------------------------------------------------------------------------------------------
+// @raycast/api
+
+const kebabCase = (name) => name.replace(/([a-z0-9])([A-Z])/g, "$1-$2").toLowerCase();
+
+const Icon = new Proxy({}, { get: (_, name) => (typeof name === "string" ? kebabCase(name) : undefined) });
+
+const Color = new Proxy(
+    {
+        Blue: "#3B82F6",
+        Green: "#10B981",
+        Magenta: "#D946EF",
+        Orange: "#F97316",
+        Purple: "#8B5CF6",
+        Red: "#EF4444",
+        Yellow: "#EAB308",
+        PrimaryText: "primary-text",
+        SecondaryText: "secondary-text",
     },
-    OpenInBrowser({ title, url, icon }) {
-        return {
-            type: 'open-in-browser',
-            title,
-            url,
-            icon
-        };
+    { get: (colors, name) => (typeof name === "string" ? colors[name] ?? kebabCase(name) : undefined) },
+);
+
+const Image = { Mask: { Circle: "circle", RoundedRectangle: "rounded-rectangle" } };
+
+const Keyboard = {
+    Shortcut: {
+        Common: new Proxy({}, { get: (_, name) => (typeof name === "string" ? { common: name } : undefined) }),
     },
-    Push({ title, target, icon }) {
-        return {
-            type: 'push-view',
-            title,
-            target,
-            icon
-        };
+};
+
+const List = hostComponent("List");
+List.Item = hostComponent("List.Item");
+List.Item.Detail = hostComponent("List.Item.Detail");
+List.Item.Detail.Metadata = hostComponent("List.Item.Detail.Metadata");
+List.Item.Detail.Metadata.Label = hostComponent("List.Item.Detail.Metadata.Label");
+List.Item.Detail.Metadata.Link = hostComponent("List.Item.Detail.Metadata.Link");
+List.Item.Detail.Metadata.Separator = hostComponent("List.Item.Detail.Metadata.Separator");
+List.Item.Detail.Metadata.TagList = hostComponent("List.Item.Detail.Metadata.TagList");
+List.Item.Detail.Metadata.TagList.Item = hostComponent("List.Item.Detail.Metadata.TagList.Item");
+List.Section = hostComponent("List.Section");
+List.EmptyView = hostComponent("List.EmptyView");
+List.Dropdown = hostComponent("List.Dropdown");
+List.Dropdown.Item = hostComponent("List.Dropdown.Item");
+List.Dropdown.Section = hostComponent("List.Dropdown.Section");
+
+const Grid = hostComponent("Grid");
+Grid.Item = hostComponent("Grid.Item");
+Grid.Section = hostComponent("Grid.Section");
+Grid.EmptyView = hostComponent("Grid.EmptyView");
+Grid.Dropdown = hostComponent("Grid.Dropdown");
+Grid.Dropdown.Item = hostComponent("Grid.Dropdown.Item");
+Grid.Dropdown.Section = hostComponent("Grid.Dropdown.Section");
+Grid.Inset = { Small: "small", Medium: "medium", Large: "large" };
+Grid.ItemSize = { Small: "small", Medium: "medium", Large: "large" };
+Grid.Fit = { Contain: "contain", Fill: "fill" };
+
+const Detail = hostComponent("Detail");
+Detail.Metadata = hostComponent("Detail.Metadata");
+Detail.Metadata.Label = hostComponent("Detail.Metadata.Label");
+Detail.Metadata.Link = hostComponent("Detail.Metadata.Link");
+Detail.Metadata.Separator = hostComponent("Detail.Metadata.Separator");
+Detail.Metadata.TagList = hostComponent("Detail.Metadata.TagList");
+Detail.Metadata.TagList.Item = hostComponent("Detail.Metadata.TagList.Item");
+
+const Form = hostComponent("Form");
+Form.TextField = hostComponent("Form.TextField");
+Form.PasswordField = hostComponent("Form.PasswordField");
+Form.TextArea = hostComponent("Form.TextArea");
+Form.Checkbox = hostComponent("Form.Checkbox");
+Form.DatePicker = hostComponent("Form.DatePicker");
+Form.DatePicker.Type = { Date: "date", DateTime: "date-time" };
+Form.Dropdown = hostComponent("Form.Dropdown");
+Form.Dropdown.Item = hostComponent("Form.Dropdown.Item");
+Form.Dropdown.Section = hostComponent("Form.Dropdown.Section");
+Form.TagPicker = hostComponent("Form.TagPicker");
+Form.TagPicker.Item = hostComponent("Form.TagPicker.Item");
+Form.FilePicker = hostComponent("Form.FilePicker");
+Form.Separator = hostComponent("Form.Separator");
+Form.Description = hostComponent("Form.Description");
+Form.LinkAccessory = hostComponent("Form.LinkAccessory");
+
+const MenuBarExtra = hostComponent("MenuBarExtra");
+MenuBarExtra.Item = hostComponent("MenuBarExtra.Item");
+MenuBarExtra.Section = hostComponent("MenuBarExtra.Section");
+MenuBarExtra.Submenu = hostComponent("MenuBarExtra.Submenu");
+
+const ActionPanel = hostComponent("ActionPanel");
+ActionPanel.Section = hostComponent("ActionPanel.Section");
+ActionPanel.Submenu = hostComponent("ActionPanel.Submenu");
+
+const Action = hostComponent("Action");
+Action.Style = { Regular: "regular", Destructive: "destructive" };
+Action.CopyToClipboard = hostComponent("Action.CopyToClipboard");
+Action.Paste = hostComponent("Action.Paste");
+Action.OpenInBrowser = hostComponent("Action.OpenInBrowser");
+Action.Open = hostComponent("Action.Open");
+Action.OpenWith = hostComponent("Action.OpenWith");
+Action.ShowInFinder = hostComponent("Action.ShowInFinder");
+Action.Trash = hostComponent("Action.Trash");
+Action.SubmitForm = hostComponent("Action.SubmitForm");
+Action.CreateQuicklink = hostComponent("Action.CreateQuicklink");
+Action.CreateSnippet = hostComponent("Action.CreateSnippet");
+Action.ToggleQuickLook = hostComponent("Action.ToggleQuickLook");
+Action.PickDate = hostComponent("Action.PickDate");
+// Running a push action shows its target, which is only rendered once it is on the stack
+Action.Push = hostComponent("Action.Push", {
+    target: (props) => () => {
+        push(props.target);
+        if (typeof props.onPush === "function") props.onPush();
+    },
+});
+
+class Toast {
+    static Style = { Success: "success", Failure: "failure", Animated: "animated" };
+
+    constructor(options) {
+        Object.assign(this, { style: Toast.Style.Success }, options);
+    }
+
+    async show() {
+        const message = this.message == null ? null : String(this.message);
+        hostCall("op_show_styled_toast", this.style, String(this.title), message);
     }
+
+    async hide() {}
+}
+
+async function showToast(options, title, message) {
+    const toast = new Toast(typeof options === "string" ? { style: options, title, message } : options);
+    await toast.show();
+    return toast;
+}
+
+async function showHUD(title) {
+    hostCall("op_show_hud", String(title));
+}
+
+// Content is a string, a number or `{ text }`, `{ html }` or `{ file }`
+function clipboardText(content) {
+    if (content !== null && typeof content === "object") {
+        return String(content.text ?? content.html ?? content.file ?? "");
+    }
+    return String(content);
+}
+
+const Clipboard = {
+    async copy(content) {
+        hostCall("op_set_clipboard", clipboardText(content));
+    },
+    async paste(content) {
+        await hostCall("op_paste_clipboard", clipboardText(content));
+    },
+    async readText() {
+        return hostCall("op_get_clipboard") ?? undefined;
+    },
+    async read() {
+        return { text: await Clipboard.readText() };
+    },
+    async clear() {
+        await Clipboard.copy("");
+    },
 };
 
-// Environment and utilities
-export const environment = {
-    assetsPath: '/tmp/raycast-assets',
-    supportPath: '/tmp/raycast-support',
-    commandName: globalThis.__actionItems__.commandName,
-    extensionName: globalThis.__actionItems__.extensionName,
+// Values are kept by the host per extension, so they survive relaunches
+const LocalStorage = {
+    async getItem(key) {
+        return (await hostCall("op_local_storage_get", String(key))) ?? undefined;
+    },
+    async setItem(key, value) {
+        await hostCall("op_local_storage_set", String(key), value);
+    },
+    async removeItem(key) {
+        await hostCall("op_local_storage_remove", String(key));
+    },
+    async allItems() {
+        return (await hostCall("op_local_storage_all")) ?? {};
+    },
+    async clear() {
+        await hostCall("op_local_storage_clear");
+    },
 };
 
-export async function showHUD(message) {
-    await globalThis.__actionItems__.showHUD(message);
+// Cache methods are synchronous, so each namespace is read from the host once and written back
+// whole after every change. Instances sharing a namespace share entries and subscribers.
+const cacheNamespaces = new Map();
+
+function cacheNamespace(name) {
+    if (!cacheNamespaces.has(name)) {
+        const stored = hostCall("op_cache_load", name) ?? {};
+        cacheNamespaces.set(name, { entries: new Map(Object.entries(stored)), listeners: new Set() });
+    }
+    return cacheNamespaces.get(name);
 }
 
-export async function popToRoot() {
-    await globalThis.__actionItems__.popToRoot();
+class Cache {
+    constructor(options = {}) {
+        this.namespace = options.namespace ?? "";
+        this.shared = cacheNamespace(this.namespace);
+    }
+
+    get(key) {
+        return this.shared.entries.get(key);
+    }
+
+    has(key) {
+        return this.shared.entries.has(key);
+    }
+
+    set(key, data) {
+        this.shared.entries.set(key, data);
+        this.store();
+        this.notify(key, data);
+    }
+
+    remove(key) {
+        const removed = this.shared.entries.delete(key);
+        if (removed) this.store();
+        this.notify(key, undefined);
+        return removed;
+    }
+
+    clear(options = { notifySubscribers: true }) {
+        const keys = [...this.shared.entries.keys()];
+        this.shared.entries.clear();
+        this.store();
+        if (options.notifySubscribers) keys.forEach((key) => this.notify(key, undefined));
+    }
+
+    get isEmpty() {
+        return this.shared.entries.size === 0;
+    }
+
+    subscribe(listener) {
+        this.shared.listeners.add(listener);
+        return () => this.shared.listeners.delete(listener);
+    }
+
+    store() {
+        hostCall("op_cache_store", this.namespace, Object.fromEntries(this.shared.entries));
+    }
+
+    notify(key, data) {
+        this.shared.listeners.forEach((listener) => listener(key, data));
+    }
 }
 
-// Export React for JSX
-export { React };
-export default {
-    List,
+const launch = { commandName: "", extensionName: "", preferences: {} };
+
+const environment = {
+    appearance: "dark",
+    isDevelopment: false,
+    launchType: "userInitiated",
+    raycastVersion: "1.80.0",
+    textSize: "medium",
+    get assetsPath() {
+        return launch.assetsPath;
+    },
+    get commandMode() {
+        return launch.mode;
+    },
+    get commandName() {
+        return launch.commandName;
+    },
+    get extensionName() {
+        return launch.extensionName;
+    },
+    get supportPath() {
+        return launch.supportPath;
+    },
+    canAccess: () => true,
+};
+
+const LaunchType = { UserInitiated: "userInitiated", Background: "background" };
+const Alert = { ActionStyle: { Default: "default", Cancel: "cancel", Destructive: "destructive" } };
+const PopToRootType = { Default: "default", Immediate: "immediate", Suspended: "suspended" };
+
+const api = {
+    Action,
+    ActionPanel,
+    Alert,
+    Cache,
+    Clipboard,
+    Color,
     Detail,
+    Form,
+    Grid,
     Icon,
-    Color,
-    ActionPanel,
-    Action,
+    Image,
+    Keyboard,
+    LaunchType,
+    List,
+    LocalStorage,
+    MenuBarExtra,
+    PopToRootType,
+    Toast,
     environment,
     showHUD,
-    popToRoot,
+    showToast,
+    useNavigation,
+    closeMainWindow: async () => hostCall("op_close_main_window"),
+    confirmAlert: async (options) => Boolean(await hostCall("op_confirm_alert", JSON.stringify(options))),
+    getPreferenceValues: () => ({ ...launch.preferences }),
+    getSelectedText: async () => {
+        throw new Error("Reading the selected text is not supported");
+    },
+    launchCommand: async (options) => hostCall("op_launch_command", JSON.stringify(options)),
+    open: async (target, application) => {
+        const app = typeof application === "string" ? application : application?.path;
+        hostCall("op_open", String(target), app ?? "");
+    },
+    popToRoot: async () => popToRootView(),
+    showInFinder: async (path) => hostCall("op_show_in_finder", String(path)),
+    trash: async (paths) => hostCall("op_trash", JSON.stringify([].concat(paths))),
+};
+
+// ---------------------------------------------------------------------------------------------
+// @raycast/utils
+
+async function showFailureToast(error, options = {}) {
+    return showToast({
+        style: Toast.Style.Failure,
+        title: options.title ?? "Something went wrong",
+        message: error instanceof Error ? error.message : String(error),
+        primaryAction: options.primaryAction,
+    });
+}
+
+function usePromise(fn, args = [], options = {}) {
+    const execute = options.execute !== false;
+    const [state, setState] = useState({ data: options.initialData, error: undefined, isLoading: execute });
+    const latest = useRef();
+    latest.current = { fn, args, options };
+    const calls = useRef(0);
+
+    const revalidate = useCallback(() => {
+        const { fn, args, options } = latest.current;
+        const call = ++calls.current;
+        if (options.onWillExecute) options.onWillExecute(args);
+        setState((previous) => ({ ...previous, isLoading: true }));
+        return Promise.resolve()
+            .then(() => fn(...args))
+            .then(
+                (data) => {
+                    if (call !== calls.current) return data;
+                    setState({ data, error: undefined, isLoading: false });
+                    if (options.onData) options.onData(data);
+                    return data;
+                },
+                (error) => {
+                    if (call !== calls.current) return undefined;
+                    setState((previous) => ({ ...previous, error, isLoading: false }));
+                    if (options.onError) options.onError(error);
+                    else showFailureToast(error);
+                    return undefined;
+                },
+            );
+    }, []);
+
+    const mutate = useCallback(async (update, mutateOptions = {}) => {
+        let previous;
+        if (mutateOptions.optimisticUpdate) {
+            setState((current) => {
+                previous = current.data;
+                return { ...current, data: mutateOptions.optimisticUpdate(current.data) };
+            });
+        }
+        try {
+            const result = await update;
+            if (mutateOptions.shouldRevalidateAfter !== false) await revalidate();
+            return result;
+        } catch (error) {
+            if (mutateOptions.optimisticUpdate && mutateOptions.rollbackOnError !== false) {
+                setState((current) => ({ ...current, data: previous }));
+            }
+            throw error;
+        }
+    }, []);
+
+    useEffect(() => {
+        if (execute) revalidate();
+        else calls.current++;
+    }, [execute, ...args]);
+
+    return { ...state, revalidate, mutate, pagination: undefined };
+}
+
+// Cached promise results and state outlive the command, like in Raycast
+let utilsCache;
+
+function cachedValue(key, fallback) {
+    utilsCache ??= new Cache({ namespace: "raycast-utils" });
+    return utilsCache.has(key) ? JSON.parse(utilsCache.get(key)) : fallback;
+}
+
+function cacheValue(key, value) {
+    utilsCache ??= new Cache({ namespace: "raycast-utils" });
+    if (value === undefined) utilsCache.remove(key);
+    else utilsCache.set(key, JSON.stringify(value));
+}
+
+function useCachedPromise(fn, args = [], options = {}) {
+    const key = `${String(fn)}:${JSON.stringify(args)}`;
+    const cached = cachedValue(key, options.initialData);
+    return usePromise(fn, args, {
+        ...options,
+        initialData: cached,
+        onData(data) {
+            cacheValue(key, data);
+            if (options.onData) options.onData(data);
+        },
+    });
+}
+
+async function parseFetchResponse(response) {
+    if (!response.ok) throw new Error(`${response.status} ${response.statusText}`);
+    const contentType = response.headers.get("content-type") || "";
+    return contentType.includes("json") ? response.json() : response.text();
+}
+
+function useFetch(url, options = {}) {
+    const { parseResponse, mapResult, initialData, execute, keepPreviousData, onData, onError, onWillExecute, ...init } =
+        options;
+    const fetcher = async (target) => {
+        if (typeof globalThis.fetch !== "function") throw new Error("fetch is not available in this runtime");
+        const response = await globalThis.fetch(target, init);
+        const parsed = await (parseResponse ? parseResponse(response) : parseFetchResponse(response));
+        return mapResult ? mapResult(parsed).data : parsed;
+    };
+    return useCachedPromise(fetcher, [url], { initialData, execute, onData, onError, onWillExecute });
+}
+
+
+function useCachedState(key, initialState, config = {}) {
+    const cacheKey = config.cacheNamespace ? `${config.cacheNamespace}-${key}` : key;
+    const [state, setState] = useState(() => cachedValue(cacheKey, initialState));
+    const setCachedState = useCallback(
+        (value) =>
+            setState((previous) => {
+                const next = typeof value === "function" ? value(previous) : value;
+                cacheValue(cacheKey, next);
+                return next;
+            }),
+        [cacheKey],
+    );
+    return [state, setCachedState];
+}
+
+function useLocalStorage(key, initialValue) {
+    const { data, isLoading, mutate } = usePromise(
+        async (storageKey) => {
+            const stored = await LocalStorage.getItem(storageKey);
+            return stored === undefined ? initialValue : JSON.parse(stored);
+        },
+        [key],
+    );
+    const setValue = (value) => mutate(LocalStorage.setItem(key, JSON.stringify(value)));
+    const removeValue = () => mutate(LocalStorage.removeItem(key));
+    return { value: data, setValue, removeValue, isLoading };
+}
+
+const FormValidation = { Required: "required" };
+
+function useForm({ onSubmit, initialValues = {}, validation = {} }) {
+    const [values, setValues] = useState(initialValues);
+    const [errors, setErrors] = useState({});
+    const latest = useRef();
+    latest.current = { values, onSubmit, validation };
+
+    const validate = (rule, value) => {
+        if (typeof rule === "function") return rule(value) || undefined;
+        const empty = value === undefined || value === "" || (Array.isArray(value) && value.length === 0);
+        return rule === FormValidation.Required && empty ? "The item is required" : undefined;
+    };
+
+    const handleSubmit = useCallback(async (submitted) => {
+        const { values, onSubmit, validation } = latest.current;
+        const merged = { ...values, ...submitted };
+        const found = {};
+        for (const [name, rule] of Object.entries(validation)) {
+            const error = validate(rule, merged[name]);
+            if (error) found[name] = error;
+        }
+        if (Object.keys(found).length > 0) {
+            setErrors(found);
+            return false;
+        }
+        return onSubmit(merged);
+    }, []);
+
+    const setValue = (name, value) => setValues((current) => ({ ...current, [name]: value }));
+    const itemProps = new Proxy(
+        {},
+        {
+            get: (_, name) =>
+                typeof name !== "string"
+                    ? undefined
+                    : {
+                          id: name,
+                          value: values[name],
+                          error: errors[name],
+                          onChange(value) {
+                              setValue(name, value);
+                              if (errors[name]) setErrors((current) => ({ ...current, [name]: undefined }));
+                          },
+                      },
+        },
+    );
+
+    return {
+        handleSubmit,
+        itemProps,
+        values,
+        setValue,
+        setValidationError: (name, error) => setErrors((current) => ({ ...current, [name]: error })),
+        reset: (next = initialValues) => setValues(next),
+        focus: () => {},
+    };
+}
+
+function getFavicon(url, options = {}) {
+    const host = String(url).replace(/^[a-z][a-z0-9+.-]*:\/\//i, "").split(/[/?#]/)[0];
+    return {
+        source: `https://www.google.com/s2/favicons?sz=${options.size ?? 64}&domain=${host}`,
+        fallback: options.fallback ?? Icon.Link,
+        mask: options.mask,
+    };
+}
+
+const utils = {
+    FormValidation,
+    getFavicon,
+    runAppleScript: async () => {
+        throw new Error("AppleScript is not supported");
+    },
+    showFailureToast,
+    useCachedPromise,
+    useCachedState,
+    useFetch,
+    useForm,
+    useFrecencySorting: (data) => ({ data: data ?? [], visitItem: async () => {}, resetRanking: async () => {} }),
+    useLocalStorage,
+    usePromise,
+};
+
+// ---------------------------------------------------------------------------------------------
+// Entry points used by the launcher
+
+const modules = {
+    react: React,
+    "react/jsx-runtime": jsxRuntime,
+    "react/jsx-dev-runtime": jsxRuntime,
+    "@raycast/api": api,
+    "@raycast/utils": utils,
+};
+
+function requireModule(name) {
+    const module = modules[name];
+    if (!module) throw new Error(`Cannot find module '${name}'`);
+    return module;
+}
+
+globalThis.__raycast__ = {
+    // Evaluate a command bundle and start it; view commands render, others run to completion
+    launch(factory, options) {
+        Object.assign(launch, options);
+        const module = { exports: {} };
+        factory(requireModule, module, module.exports);
+        const command = module.exports.default ?? module.exports;
+        if (typeof command !== "function") throw new Error("The command bundle has no default export");
+
+        const props = { arguments: options.arguments ?? {}, launchContext: options.launchContext };
+        if (options.mode === "view") {
+            push(createElement(command, props));
+            return undefined;
+        }
+        return Promise.resolve()
+            .then(() => command(props))
+            .catch(reportError);
+    },
+
+    // Invoke a callback from the committed view by its handler id
+    dispatch(id, args) {
+        const handler = handlers.get(id);
+        if (!handler) throw new Error(`No handler '${id}' in the current view`);
+        return Promise.resolve()
+            .then(() => handler(...args))
+            .catch(reportError);
+    },
+
+    pop,
+
+    // View updates since the last call, as JSON
+    takeUpdates() {
+        const taken = updates;
+        updates = [];
+        return JSON.stringify(taken);
+    },
 };
-"#
+})();
+"##
     .to_string()
 }
@@ -10,6 +10,7 @@ use action_items_common::plugin_interface::{CommandDefinition, CommandMode, Plug
 
 use crate::error::{Error, Result};
 use crate::plugins::interface::{PluginCapabilities, PluginManifest, PluginPermissions};
+use crate::plugins::permissions::PermissionPolicy;
 use crate::raycast::loader::RaycastExtension;
 
 /// Adapter to run Raycast extensions as WASM plugins using Deno
@@ -66,22 +67,7 @@ impl RaycastAdapter {
                 context_menu: false,
                 quick_actions: true,
             },
-            permissions: PluginPermissions {
-                read_clipboard: true,
-                write_clipboard: true,
-                read_files: vec![PathBuf::from("*")], // Raycast extensions can read any file
-                write_files: vec![],                  // Limited write access
-                execute_commands: vec!["*".to_string()], // Can execute any command
-                network_hosts: vec!["*".to_string()], // Can access any host
-                environment_variables: vec![],
-                system_notifications: true,
-                accessibility: false,
-                camera: false,
-                microphone: false,
-                location: false,
-                contacts: false,
-                calendar: false,
-            },
+            permissions: raycast_permissions(),
             configuration: super::configuration::map_raycast_preferences(extension),
             preferences: vec![], // Will be managed by the standard plugin configuration system
             commands: extension
@@ -109,6 +95,7 @@ impl RaycastAdapter {
             max_launcher_version: None,
             update_url: None,
             changelog_url: None,
+            resource_limits: Default::default(),
         }
     }
 
@@ -121,8 +108,10 @@ impl RaycastAdapter {
         // 1. Initialize Deno Runtime with existing infrastructure
         let config = RuntimeConfig::default();
         let channels = RuntimeChannels::default();
+        let policy = PermissionPolicy::new(extension.id.clone(), raycast_permissions());
         let mut runtime = DenoRuntime::new(config, channels)
-            .map_err(|e| Error::SystemError(format!("Runtime initialization failed: {}", e)))?;
+            .map_err(|e| Error::SystemError(format!("Runtime initialization failed: {}", e)))?
+            .with_permissions(policy);
 
         // 2. Load extension source code
         let source_path = extension.path.join("src").join("index.ts");
@@ -182,3 +171,23 @@ impl RaycastAdapter {
             .map_err(|e| Error::SystemError(format!("WASM module serialization failed: {}", e)))
     }
 }
+
+/// Permissions granted to Raycast extensions, which declare none of their own
+pub fn raycast_permissions() -> PluginPermissions {
+    PluginPermissions {
+        read_clipboard: true,
+        write_clipboard: true,
+        read_files: vec![PathBuf::from("*")], // Raycast extensions can read any file
+        write_files: vec![],                  // Limited write access
+        execute_commands: vec!["*".to_string()], // Can execute any command
+        network_hosts: vec!["*".to_string()], // Can access any host
+        environment_variables: vec![],
+        system_notifications: true,
+        accessibility: false,
+        camera: false,
+        microphone: false,
+        location: false,
+        contacts: false,
+        calendar: false,
+    }
+}
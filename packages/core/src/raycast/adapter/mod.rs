@@ -10,7 +10,8 @@ pub use conversion::*;
 pub use host_functions::{
     HostFunction, HostFunctionRegistry, get_host_function_registry as create_host_functions,
 };
-pub use implementation::RaycastAdapter;
+pub use implementation::{RaycastAdapter, raycast_permissions};
+pub use reconciler::*;
 
 // Import the modular implementation
 mod api_shim;
@@ -18,3 +19,4 @@ mod configuration;
 mod conversion;
 mod host_functions;
 mod implementation;
+mod reconciler;
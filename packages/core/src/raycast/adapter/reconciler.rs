@@ -0,0 +1,275 @@
+//! Launcher views rendered by Raycast commands
+//!
+//! The API shim renders a command's React tree in the Deno runtime and diffs each commit against
+//! the previous one. The differences arrive here as [`RaycastViewUpdate`]s, which the launcher
+//! applies to its copy of the view with [`apply_view_update`]. Callbacks in props are sent as
+//! `{"$handler": id}` and run with [`RaycastCommandSession::dispatch`].
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use action_items_ecs_preferences::ExtensionPreferences;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::api_shim::create_api_adapter;
+use super::implementation::raycast_permissions;
+use crate::config::AppDirectories;
+use crate::error::{Error, Result};
+use crate::plugins::permissions::PermissionPolicy;
+use crate::raycast::loader::{RaycastCommand, RaycastExtension};
+use crate::runtime::deno::{DenoRuntime, RuntimeChannels, RuntimeConfig};
+
+/// How long a launch or dispatch waits for the command's promises and effects to settle
+pub const SETTLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A node of a rendered view, such as `List`, `List.Item` or `Action.Push`
+///
+/// Element-valued props such as `actions` or `detail` hold a node of their own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaycastViewNode {
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub key: Option<String>,
+    pub props: Map<String, Value>,
+    pub children: Vec<RaycastViewNode>,
+}
+
+impl RaycastViewNode {
+    /// Look up a prop by name
+    pub fn prop(&self, name: &str) -> Option<&Value> {
+        self.props.get(name)
+    }
+
+    /// Handler id of a callback prop
+    pub fn handler(&self, name: &str) -> Option<&str> {
+        self.prop(name)?.get("$handler")?.as_str()
+    }
+
+    /// Node held by an element-valued prop
+    pub fn slot(&self, name: &str) -> Option<RaycastViewNode> {
+        serde_json::from_value(self.prop(name)?.clone()).ok()
+    }
+
+    /// Child at `path`, where each entry indexes into the children of the previous node
+    pub fn at(&self, path: &[usize]) -> Option<&RaycastViewNode> {
+        path.iter()
+            .try_fold(self, |node, &index| node.children.get(index))
+    }
+
+    fn at_mut(&mut self, path: &[usize]) -> Option<&mut RaycastViewNode> {
+        path.iter()
+            .try_fold(self, |node, &index| node.children.get_mut(index))
+    }
+}
+
+/// A change between two commits of a command's view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RaycastViewUpdate {
+    /// Swap the node at `path` for another; at the root, `None` clears the view
+    Replace {
+        path: Vec<usize>,
+        node: Option<RaycastViewNode>,
+    },
+    /// Insert a node so that it ends up at `path`
+    Insert {
+        path: Vec<usize>,
+        node: RaycastViewNode,
+    },
+    /// Remove the node at `path`
+    Remove { path: Vec<usize> },
+    /// Replace the props of the node at `path`, keeping its children
+    SetProps {
+        path: Vec<usize>,
+        props: Map<String, Value>,
+    },
+    /// Rendering or a callback threw; the view is left as it was
+    Error { message: String },
+}
+
+/// Apply an update to the launcher's copy of a command's view
+pub fn apply_view_update(
+    view: &mut Option<RaycastViewNode>,
+    update: &RaycastViewUpdate,
+) -> Result<()> {
+    let missing = |path: &[usize]| Error::PluginError(format!("No view node at {path:?}"));
+    match update {
+        RaycastViewUpdate::Replace { path, node } => match path.split_last() {
+            None => *view = node.clone(),
+            Some((&index, parent)) => {
+                let parent = view
+                    .as_mut()
+                    .and_then(|root| root.at_mut(parent))
+                    .ok_or_else(|| missing(path))?;
+                let slot = parent
+                    .children
+                    .get_mut(index)
+                    .ok_or_else(|| missing(path))?;
+                match node {
+                    Some(node) => *slot = node.clone(),
+                    None => {
+                        parent.children.remove(index);
+                    },
+                }
+            },
+        },
+        RaycastViewUpdate::Insert { path, node } => {
+            let (&index, parent) = path.split_last().ok_or_else(|| missing(path))?;
+            let parent = view
+                .as_mut()
+                .and_then(|root| root.at_mut(parent))
+                .filter(|parent| index <= parent.children.len())
+                .ok_or_else(|| missing(path))?;
+            parent.children.insert(index, node.clone());
+        },
+        RaycastViewUpdate::Remove { path } => {
+            let (&index, parent) = path.split_last().ok_or_else(|| missing(path))?;
+            let parent = view
+                .as_mut()
+                .and_then(|root| root.at_mut(parent))
+                .filter(|parent| index < parent.children.len())
+                .ok_or_else(|| missing(path))?;
+            parent.children.remove(index);
+        },
+        RaycastViewUpdate::SetProps { path, props } => {
+            let node = view
+                .as_mut()
+                .and_then(|root| root.at_mut(path))
+                .ok_or_else(|| missing(path))?;
+            node.props = props.clone();
+        },
+        RaycastViewUpdate::Error { .. } => {},
+    }
+    Ok(())
+}
+
+/// What a command is launched with
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RaycastLaunch {
+    pub command_name: String,
+    pub extension_name: String,
+    /// `view`, `no-view` or `menu-bar`, as declared in the extension's package.json
+    pub mode: String,
+    pub arguments: Map<String, Value>,
+    pub preferences: Map<String, Value>,
+    pub assets_path: PathBuf,
+    pub support_path: PathBuf,
+}
+
+impl RaycastLaunch {
+    /// Launch a command of an installed extension without arguments
+    ///
+    /// Preferences are the extension's declared defaults with the values the user set on top.
+    pub fn new(extension: &RaycastExtension, command: &RaycastCommand) -> Self {
+        let stored = ExtensionPreferences::load(&ExtensionPreferences::default_path());
+        Self {
+            command_name: command.name.clone(),
+            extension_name: extension.name.clone(),
+            mode: command.mode.clone(),
+            arguments: Map::new(),
+            preferences: extension.preference_values(command, &stored.values(&extension.id)),
+            assets_path: extension.path.join("assets"),
+            support_path: AppDirectories::new().plugin_data().join(&extension.id),
+        }
+    }
+}
+
+/// A running Raycast command and the launcher's copy of its view
+///
+/// The Deno runtime is not `Send`, so a session stays on the thread that created it.
+pub struct RaycastCommandSession {
+    runtime: DenoRuntime,
+    view: Option<RaycastViewNode>,
+}
+
+impl RaycastCommandSession {
+    /// Create a runtime with the API shim installed
+    pub fn new(plugin_id: &str) -> Result<Self> {
+        let mut runtime = DenoRuntime::new(RuntimeConfig::default(), RuntimeChannels::default())
+            .map_err(|e| Error::PluginError(format!("Failed to create runtime: {e}")))?
+            .with_permissions(PermissionPolicy::new(plugin_id, raycast_permissions()));
+        runtime
+            .evaluate("raycast_api_shim", &create_api_adapter()?)
+            .map_err(Error::PluginError)?;
+
+        Ok(Self {
+            runtime,
+            view: None,
+        })
+    }
+
+    /// Evaluate a command's CommonJS bundle and start it
+    pub async fn launch(
+        &mut self,
+        bundle: &str,
+        launch: &RaycastLaunch,
+    ) -> Result<Vec<RaycastViewUpdate>> {
+        let options =
+            serde_json::to_string(launch).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let script = format!(
+            "globalThis.__raycast__.launch(function (require, module, exports) {{\n{bundle}\n}}, \
+             {options});"
+        );
+        self.runtime
+            .evaluate(&format!("raycast_{}", launch.command_name), &script)
+            .map_err(Error::PluginError)?;
+        self.settle().await
+    }
+
+    /// Run a callback from the current view, such as an action's `onAction`
+    pub async fn dispatch(
+        &mut self,
+        handler: &str,
+        args: &[Value],
+    ) -> Result<Vec<RaycastViewUpdate>> {
+        let handler =
+            serde_json::to_string(handler).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let args =
+            serde_json::to_string(args).map_err(|e| Error::SerializationError(e.to_string()))?;
+        self.runtime
+            .evaluate(
+                "raycast_dispatch",
+                &format!("globalThis.__raycast__.dispatch({handler}, {args});"),
+            )
+            .map_err(Error::PluginError)?;
+        self.settle().await
+    }
+
+    /// Go back to the previous view, as the launcher's escape key does
+    pub async fn pop(&mut self) -> Result<Vec<RaycastViewUpdate>> {
+        self.runtime
+            .evaluate("raycast_pop", "globalThis.__raycast__.pop();")
+            .map_err(Error::PluginError)?;
+        self.settle().await
+    }
+
+    /// The view as of the last update
+    pub fn view(&self) -> Option<&RaycastViewNode> {
+        self.view.as_ref()
+    }
+
+    /// Let promises and effects run, then collect the updates they caused
+    ///
+    /// Commands that keep timers running never go idle; they are cut off after
+    /// [`SETTLE_TIMEOUT`] and their later updates arrive with the next call.
+    async fn settle(&mut self) -> Result<Vec<RaycastViewUpdate>> {
+        if let Ok(result) =
+            tokio::time::timeout(SETTLE_TIMEOUT, self.runtime.run_event_loop()).await
+        {
+            result.map_err(Error::PluginError)?;
+        }
+
+        let taken = self
+            .runtime
+            .evaluate("raycast_updates", "globalThis.__raycast__.takeUpdates()")
+            .map_err(Error::PluginError)?;
+        let updates: Vec<RaycastViewUpdate> = serde_json::from_str(taken.as_str().unwrap_or("[]"))
+            .map_err(|e| Error::SerializationError(format!("Invalid view update: {e}")))?;
+        for update in &updates {
+            apply_view_update(&mut self.view, update)?;
+        }
+        Ok(updates)
+    }
+}
@@ -0,0 +1,254 @@
+//! Compiling Raycast command sources into bundles the API shim can launch
+//!
+//! Each command's entry module and everything it imports from the extension or its
+//! `node_modules` is transpiled to CommonJS with `deno_ast` and wrapped into a single script.
+//! React and the `@raycast/*` packages are left to the shim, which provides them at runtime.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use deno_ast::{
+    EmitOptions, JsxAutomaticOptions, JsxRuntime, MediaType, ModuleKind, ModuleSpecifier,
+    ParseParams, SourceMapOption, TranspileModuleOptions, TranspileOptions,
+};
+use regex::Regex;
+
+use super::extension::{RaycastCommand, RaycastExtension};
+use crate::error::{Error, Result};
+
+/// Modules provided by the API shim instead of the extension
+pub const HOST_MODULES: &[&str] = &[
+    "react",
+    "react/jsx-runtime",
+    "react/jsx-dev-runtime",
+    "@raycast/api",
+    "@raycast/utils",
+];
+
+/// Extensions tried, in order, when an import leaves them out
+const SOURCE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mjs", "cjs", "json"];
+
+static REQUIRE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\brequire\(\s*["']([^"']+)["']\s*\)"#).expect("require pattern is valid")
+});
+
+/// Path of a command's entry module, `src/<command>.tsx` or one of the other source extensions
+///
+/// `None` for a command whose name is not valid, see [`check_command_name`].
+pub fn command_entry(extension_dir: &Path, command: &RaycastCommand) -> Option<PathBuf> {
+    check_command_name(&command.name).ok()?;
+    resolve_file(&extension_dir.join("src").join(&command.name))
+}
+
+/// Check that a command name from `package.json` is a plain file name
+///
+/// Names become `src/<command>` and `dist/<command>.js`, so only letters, digits, `-` and `_`
+/// are allowed.
+pub fn check_command_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(Error::PluginError(format!(
+            "Invalid Raycast command name '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Compile every command of an extension, keyed by command name
+pub fn compile_extension(extension: &RaycastExtension) -> Result<BTreeMap<String, String>> {
+    extension
+        .commands
+        .iter()
+        .map(|command| {
+            check_command_name(&command.name)?;
+            let entry = command_entry(&extension.path, command).ok_or_else(|| {
+                Error::PluginError(format!(
+                    "Extension '{}' has no source for command '{}'",
+                    extension.name, command.name
+                ))
+            })?;
+            Ok((command.name.clone(), bundle(&extension.path, &entry)?))
+        })
+        .collect()
+}
+
+/// Bundle an entry module and the modules it imports into one CommonJS script
+///
+/// Only files inside `root` are bundled; imports that leave it are left to the shim.
+pub fn bundle(root: &Path, entry: &Path) -> Result<String> {
+    let canonical = |path: &Path| {
+        path.canonicalize()
+            .map_err(|e| Error::IoError(format!("Failed to resolve {}: {e}", path.display())))
+    };
+    let root = &canonical(root)?;
+    let entry = &canonical(entry)?;
+
+    let mut modules: BTreeMap<String, (String, BTreeMap<String, String>)> = BTreeMap::new();
+    let mut queue = VecDeque::from([entry.to_path_buf()]);
+
+    while let Some(path) = queue.pop_front() {
+        let id = module_id(root, &path);
+        if modules.contains_key(&id) {
+            continue;
+        }
+
+        let code = compile_module(&path)?;
+        let mut dependencies = BTreeMap::new();
+        for captures in REQUIRE.captures_iter(&code) {
+            let specifier = &captures[1];
+            if dependencies.contains_key(specifier) {
+                continue;
+            }
+            if let Some(dependency) = resolve_import(root, &path, specifier)
+                .filter(|dependency| dependency.starts_with(root))
+            {
+                dependencies.insert(specifier.to_string(), module_id(root, &dependency));
+                queue.push_back(dependency);
+            }
+        }
+        modules.insert(id, (code, dependencies));
+    }
+
+    let mut script = String::from("const __modules = {\n");
+    for (id, (code, dependencies)) in &modules {
+        let id = serde_json::to_string(id).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let dependencies = serde_json::to_string(dependencies)
+            .map_err(|e| Error::SerializationError(e.to_string()))?;
+        script.push_str(&format!(
+            "{id}: [function (require, module, exports) {{\n{code}\n}}, {dependencies}],\n"
+        ));
+    }
+    let entry = serde_json::to_string(&module_id(root, entry))
+        .map_err(|e| Error::SerializationError(e.to_string()))?;
+    script.push_str(&format!(
+        r#"}};
+const __loaded = {{}};
+function __load(id) {{
+    if (__loaded[id]) return __loaded[id].exports;
+    const [factory, dependencies] = __modules[id];
+    const module = (__loaded[id] = {{ exports: {{}} }});
+    const load = (specifier) =>
+        specifier in dependencies ? __load(dependencies[specifier]) : require(specifier);
+    factory(load, module, module.exports);
+    return module.exports;
+}}
+module.exports = __load({entry});
+"#
+    ));
+    Ok(script)
+}
+
+/// Transpile one module to CommonJS
+fn compile_module(path: &Path) -> Result<String> {
+    let source = fs::read_to_string(path)
+        .map_err(|e| Error::IoError(format!("Failed to read {}: {e}", path.display())))?;
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        return Ok(format!("module.exports = {source};"));
+    }
+
+    let specifier = ModuleSpecifier::from_file_path(path)
+        .map_err(|_| Error::PluginError(format!("Invalid module path {}", path.display())))?;
+    let parsed = deno_ast::parse_module(ParseParams {
+        specifier,
+        text: source.into(),
+        media_type: MediaType::from_path(path),
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+    })
+    .map_err(|e| Error::PluginError(format!("Failed to parse {}: {e}", path.display())))?;
+
+    let transpiled = parsed
+        .transpile(
+            &TranspileOptions {
+                jsx: Some(JsxRuntime::Automatic(JsxAutomaticOptions {
+                    development: false,
+                    import_source: Some("react".to_string()),
+                })),
+                ..Default::default()
+            },
+            &TranspileModuleOptions {
+                module_kind: Some(ModuleKind::Cjs),
+            },
+            &EmitOptions {
+                source_map: SourceMapOption::None,
+                ..Default::default()
+            },
+        )
+        .map_err(|e| Error::PluginError(format!("Failed to compile {}: {e}", path.display())))?;
+    Ok(transpiled.into_source().text)
+}
+
+/// File an import refers to, or `None` for modules the shim provides or that do not exist
+fn resolve_import(root: &Path, importer: &Path, specifier: &str) -> Option<PathBuf> {
+    if specifier.starts_with("./") || specifier.starts_with("../") {
+        return resolve_file(&importer.parent()?.join(specifier));
+    }
+    if HOST_MODULES.contains(&specifier) {
+        return None;
+    }
+
+    // Bare imports come from the nearest node_modules inside the extension
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let mut parts = specifier.splitn(segments + 1, '/');
+    let package = parts.by_ref().take(segments).collect::<Vec<_>>().join("/");
+    let subpath = parts.next();
+    importer
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .map(|dir| dir.join("node_modules").join(&package))
+        .find(|dir| dir.is_dir())
+        .and_then(|dir| match subpath {
+            Some(subpath) => resolve_file(&dir.join(subpath)),
+            None => {
+                let main = fs::read_to_string(dir.join("package.json"))
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+                    .and_then(|package| package.get("main")?.as_str().map(str::to_string))
+                    .unwrap_or_else(|| "index.js".to_string());
+                resolve_file(&dir.join(main))
+            },
+        })
+}
+
+/// A source file at `path`, trying source extensions and `index` files like Node does
+///
+/// The path is canonical, so a module imported through different relative paths is bundled once.
+fn resolve_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return path.canonicalize().ok();
+    }
+    let with_extension = |base: &Path| {
+        SOURCE_EXTENSIONS.iter().find_map(|extension| {
+            let mut candidate = base.as_os_str().to_owned();
+            candidate.push(".");
+            candidate.push(extension);
+            let candidate = PathBuf::from(candidate);
+            candidate
+                .canonicalize()
+                .ok()
+                .filter(|candidate| candidate.is_file())
+        })
+    };
+    with_extension(path).or_else(|| with_extension(&path.join("index")))
+}
+
+/// Stable module id: the path relative to the extension, with forward slashes
+fn module_id(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
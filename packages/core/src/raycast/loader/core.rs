@@ -1,18 +1,20 @@
 //! Main Raycast loader implementation
+//!
+//! Extensions are installed one at a time from a local directory, a `.tar.gz` or a local mirror
+//! of the Raycast extensions repository. Installing compiles each command into
+//! `dist/<command>.js` below the installed copy, so nothing is fetched or built at launch.
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::time::SystemTime;
 
 use tracing::info;
 
+use super::compile::compile_extension;
 use super::extension::RaycastExtension;
-use super::state::RaycastSyncState;
+use super::state::{InstalledRaycastExtension, RaycastExtensionSource, RaycastSyncState};
 use crate::error::{Error, Result};
 
-const RAYCAST_EXTENSIONS_REPO: &str = "https://github.com/raycast/extensions.git";
-
 pub struct RaycastLoader {
     base_path: PathBuf,
     extensions_path: PathBuf,
@@ -32,151 +34,163 @@ impl RaycastLoader {
         }
     }
 
-    /// Initialize the Raycast extensions directory and clone if needed
+    /// Create the directory installed extensions live in
     pub fn initialize(&self) -> Result<()> {
-        // Create base directory
-        fs::create_dir_all(&self.base_path)
-            .map_err(|e| Error::IoError(format!("Failed to create Raycast directory: {e}")))?;
+        fs::create_dir_all(&self.extensions_path)
+            .map_err(|e| Error::IoError(format!("Failed to create Raycast directory: {e}")))
+    }
 
-        // Check if we need to clone or update
-        if !self.extensions_path.exists() {
-            self.clone_extensions()?;
-        } else if self.should_update()? {
-            self.update_extensions()?;
-        }
+    /// Installed extensions and the configured mirror
+    ///
+    /// A missing or unreadable state file counts as nothing installed.
+    pub fn sync_state(&self) -> RaycastSyncState {
+        RaycastSyncState::load_from_file(&self.state_file).unwrap_or_default()
+    }
 
-        Ok(())
+    /// Set or clear the local mirror that [`RaycastExtensionSource::Mirror`] installs from
+    pub fn set_mirror(&self, mirror: Option<&Path>) -> Result<()> {
+        self.initialize()?;
+        let mut state = self.sync_state();
+        state.mirror = mirror.map(Path::to_path_buf);
+        state.save_to_file(&self.state_file)
     }
 
-    /// Check if we should update based on the 24-hour interval
-    fn should_update(&self) -> Result<bool> {
-        if let Ok(state) = RaycastSyncState::load_from_file(&self.state_file) {
-            Ok(state.should_update())
-        } else {
-            // No state file, should update
-            Ok(true)
-        }
+    /// Install an extension, replacing any installed version of it
+    ///
+    /// The extension is compiled before the installed copy is touched, so a failed install
+    /// leaves the previous version in place.
+    pub fn install(&self, source: RaycastExtensionSource) -> Result<RaycastExtension> {
+        self.initialize()?;
+        let staging = self
+            .base_path
+            .join("staging")
+            .join(uuid::Uuid::new_v4().to_string());
+        let result = self.install_staged(source, &staging);
+        let _ = fs::remove_dir_all(&staging);
+        result
     }
 
-    /// Clone the Raycast extensions repository
-    fn clone_extensions(&self) -> Result<()> {
-        info!(
-            repository = RAYCAST_EXTENSIONS_REPO,
-            target_path = %self.extensions_path.display(),
-            "Cloning Raycast extensions repository"
-        );
+    /// Install an extension again from where it was installed from
+    pub fn upgrade(&self, extension_id: &str) -> Result<RaycastExtension> {
+        let source = self
+            .sync_state()
+            .extensions
+            .get(extension_id)
+            .map(|installed| installed.source.clone())
+            .ok_or_else(|| Error::PluginNotFound(extension_id.to_string()))?;
+        self.install(source)
+    }
 
-        let output = Command::new("git")
-            .args([
-                "clone",
-                "--depth",
-                "1", // Shallow clone for speed
-                "--single-branch",
-                "--branch",
-                "main",
-                RAYCAST_EXTENSIONS_REPO,
-                self.extensions_path.to_str().ok_or_else(|| {
-                    Error::SystemError("Invalid UTF-8 in extensions path".to_string())
-                })?,
-            ])
-            .output()
-            .map_err(|e| Error::SystemError(format!("Failed to run git clone: {e}")))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(Error::SystemError(format!("Git clone failed: {stderr}")));
+    /// Remove an installed extension
+    pub fn uninstall(&self, extension_id: &str) -> Result<()> {
+        check_extension_name(extension_id)?;
+        let mut state = self.sync_state();
+        let path = self.get_extension_path(extension_id);
+        if state.extensions.remove(extension_id).is_none() && !path.exists() {
+            return Err(Error::PluginNotFound(extension_id.to_string()));
         }
 
-        self.save_sync_state()?;
+        if path.exists() {
+            fs::remove_dir_all(&path)
+                .map_err(|e| Error::IoError(format!("Failed to remove {extension_id}: {e}")))?;
+        }
+        state.last_sync = SystemTime::now();
+        state.save_to_file(&self.state_file)?;
+        info!(extension = extension_id, "Raycast extension uninstalled");
         Ok(())
     }
 
-    /// Update the Raycast extensions repository
-    fn update_extensions(&self) -> Result<()> {
-        info!(
-            repository = RAYCAST_EXTENSIONS_REPO,
-            extensions_path = %self.extensions_path.display(),
-            "Updating Raycast extensions"
-        );
+    fn install_staged(
+        &self,
+        source: RaycastExtensionSource,
+        staging: &Path,
+    ) -> Result<RaycastExtension> {
+        let mut state = self.sync_state();
+        let source_dir = match &source {
+            RaycastExtensionSource::Directory { path } => path.clone(),
+            RaycastExtensionSource::Tarball { path } => {
+                unpack_tarball(path, &staging.join("unpacked"))?
+            },
+            RaycastExtensionSource::Mirror { name } => {
+                check_extension_name(name)?;
+                let mirror = state.mirror.as_ref().ok_or_else(|| {
+                    Error::ConfigurationError("No Raycast extensions mirror is set".to_string())
+                })?;
+                mirror.join("extensions").join(name)
+            },
+        };
 
-        // Git pull
-        let output = Command::new("git")
-            .args(["pull", "--rebase"])
-            .current_dir(&self.extensions_path)
-            .output()
-            .map_err(|e| Error::SystemError(format!("Failed to run git pull: {e}")))?;
+        let extension = RaycastExtension::from_path(&source_dir)?;
+        let id = extension.name.clone();
+        check_extension_name(&id)?;
+        let bundles = compile_extension(&extension)?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(Error::SystemError(format!("Git pull failed: {stderr}")));
+        // Assemble the installed copy beside the final location so replacing it is a rename
+        let assembled = staging.join(&id);
+        let write_error = |e: std::io::Error| {
+            Error::IoError(format!("Failed to install Raycast extension {id}: {e}"))
+        };
+        fs::create_dir_all(assembled.join("dist")).map_err(write_error)?;
+        fs::copy(
+            source_dir.join("package.json"),
+            assembled.join("package.json"),
+        )
+        .map_err(write_error)?;
+        if source_dir.join("assets").is_dir() {
+            copy_dir(&source_dir.join("assets"), &assembled.join("assets")).map_err(write_error)?;
         }
-
-        self.save_sync_state()?;
-        Ok(())
-    }
-
-    /// Get the current git commit hash
-    fn get_current_commit(&self) -> Result<String> {
-        let output = Command::new("git")
-            .args(["rev-parse", "HEAD"])
-            .current_dir(&self.extensions_path)
-            .output()
-            .map_err(|e| Error::SystemError(format!("Failed to get git commit: {e}")))?;
-
-        if !output.status.success() {
-            return Err(Error::SystemError(
-                "Failed to get current commit".to_string(),
-            ));
+        for (command, bundle) in &bundles {
+            fs::write(assembled.join("dist").join(format!("{command}.js")), bundle)
+                .map_err(write_error)?;
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-    }
-
-    /// Count the number of extensions
-    fn count_extensions(&self) -> Result<usize> {
-        let mut count = 0;
-        let extensions_dir = self.extensions_path.join("extensions");
-
-        if extensions_dir.exists() {
-            for entry in (fs::read_dir(&extensions_dir)
-                .map_err(|e| Error::IoError(format!("Failed to read extensions directory: {e}")))?)
-            .flatten()
-            {
-                if entry.path().join("package.json").exists() {
-                    count += 1;
-                }
-            }
+        let target = self.get_extension_path(&id);
+        if target.exists() {
+            fs::remove_dir_all(&target).map_err(write_error)?;
         }
-
-        Ok(count)
-    }
-
-    /// Save the sync state
-    fn save_sync_state(&self) -> Result<()> {
-        let state = RaycastSyncState {
-            last_sync: SystemTime::now(),
-            last_commit: self.get_current_commit()?,
-            extension_count: self.count_extensions()?,
+        fs::rename(&assembled, &target).map_err(write_error)?;
+
+        let now = SystemTime::now();
+        let installed = InstalledRaycastExtension {
+            version: package_version(&target),
+            source,
+            installed_at: now,
+            commands: bundles.into_keys().collect(),
         };
+        info!(
+            extension = %id,
+            version = ?installed.version,
+            commands = installed.commands.len(),
+            "Raycast extension installed"
+        );
+        state.extensions.insert(id, installed);
+        state.last_sync = now;
+        state.save_to_file(&self.state_file)?;
 
-        state.save_to_file(&self.state_file)
+        RaycastExtension::from_path(&target)
     }
 
     /// Get the path to a specific extension
     pub fn get_extension_path(&self, extension_id: &str) -> PathBuf {
-        self.extensions_path.join("extensions").join(extension_id)
+        self.extensions_path.join(extension_id)
+    }
+
+    /// Compiled script of an installed command
+    pub fn command_bundle_path(&self, extension_id: &str, command: &str) -> PathBuf {
+        self.get_extension_path(extension_id)
+            .join("dist")
+            .join(format!("{command}.js"))
     }
 
-    /// List all available Raycast extensions
+    /// List all installed Raycast extensions
     pub fn list_extensions(&self) -> Result<Vec<RaycastExtension>> {
         let mut extensions = Vec::new();
-        let extensions_dir = self.extensions_path.join("extensions");
 
-        if !extensions_dir.exists() {
+        if !self.extensions_path.exists() {
             return Ok(extensions);
         }
 
-        for entry in fs::read_dir(&extensions_dir)
+        for entry in fs::read_dir(&self.extensions_path)
             .map_err(|e| Error::IoError(format!("Failed to read extensions directory: {e}")))?
         {
             let entry = entry
@@ -193,3 +207,62 @@ impl RaycastLoader {
         Ok(extensions)
     }
 }
+
+/// Reject extension names that are not a single directory below the extensions directory
+fn check_extension_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(Error::PluginError(format!(
+            "Invalid Raycast extension name '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Unpack a `.tar.gz` and find the extension directory in it
+///
+/// `npm pack` puts everything below `package/`; other archives have the package.json at the top
+/// or below a single directory.
+fn unpack_tarball(tarball: &Path, into: &Path) -> Result<PathBuf> {
+    let file = fs::File::open(tarball)
+        .map_err(|e| Error::IoError(format!("Failed to open {}: {e}", tarball.display())))?;
+    tar::Archive::new(flate2::read::GzDecoder::new(file))
+        .unpack(into)
+        .map_err(|e| Error::IoError(format!("Failed to unpack {}: {e}", tarball.display())))?;
+
+    if into.join("package.json").is_file() {
+        return Ok(into.to_path_buf());
+    }
+    let directories: Vec<PathBuf> = fs::read_dir(into)
+        .map_err(|e| Error::IoError(format!("Failed to read {}: {e}", into.display())))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    match directories.as_slice() {
+        [directory] if directory.join("package.json").is_file() => Ok(directory.clone()),
+        _ => Err(Error::PluginError(format!(
+            "{} does not contain a Raycast extension",
+            tarball.display()
+        ))),
+    }
+}
+
+fn package_version(extension_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(extension_dir.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    Some(package.get("version")?.as_str()?.to_string())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
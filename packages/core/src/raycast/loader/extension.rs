@@ -4,6 +4,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::error::{Error, Result};
 
@@ -18,6 +19,9 @@ pub struct RaycastExtension {
     pub icon: Option<String>,
     pub path: PathBuf,
     pub commands: Vec<RaycastCommand>,
+    /// Preferences shared by all commands
+    #[serde(default)]
+    pub preferences: Vec<RaycastPreference>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +30,21 @@ pub struct RaycastCommand {
     pub title: String,
     pub description: Option<String>,
     pub mode: String,
+    #[serde(default)]
+    pub preferences: Vec<RaycastPreference>,
+}
+
+/// A preference declared in an extension's package.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaycastPreference {
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// `textfield`, `password`, `checkbox`, `dropdown`, `appPicker`, `file` or `directory`
+    #[serde(rename = "type")]
+    pub preference_type: String,
+    pub required: bool,
+    pub default: Option<Value>,
 }
 
 impl RaycastExtension {
@@ -35,7 +54,7 @@ impl RaycastExtension {
         let content = fs::read_to_string(&package_json_path)
             .map_err(|e| Error::IoError(format!("Failed to read package.json: {e}")))?;
 
-        let package: serde_json::Value = serde_json::from_str(&content)
+        let package: Value = serde_json::from_str(&content)
             .map_err(|e| Error::SerializationError(format!("Failed to parse package.json: {e}")))?;
 
         let id = path
@@ -62,6 +81,7 @@ impl RaycastExtension {
                                 .and_then(|m| m.as_str())
                                 .unwrap_or("view")
                                 .to_string(),
+                            preferences: parse_preferences(cmd),
                         })
                     })
                     .collect()
@@ -105,6 +125,66 @@ impl RaycastExtension {
                 .map(|s| s.to_string()),
             path: path.to_path_buf(),
             commands,
+            preferences: parse_preferences(&package),
         })
     }
+
+    /// Preference values a command runs with
+    ///
+    /// Declared defaults are overridden by `stored`, the values the user set. Checkboxes without
+    /// a default are off, like in Raycast; other preferences without a value are left out.
+    pub fn preference_values(
+        &self,
+        command: &RaycastCommand,
+        stored: &Map<String, Value>,
+    ) -> Map<String, Value> {
+        let mut values = Map::new();
+        for preference in self.preferences.iter().chain(&command.preferences) {
+            let value = stored.get(&preference.name).cloned().or_else(|| {
+                preference.default.clone().or_else(|| {
+                    (preference.preference_type == "checkbox").then_some(Value::Bool(false))
+                })
+            });
+            if let Some(value) = value {
+                values.insert(preference.name.clone(), value);
+            }
+        }
+        values
+    }
+}
+
+fn parse_preferences(declaration: &Value) -> Vec<RaycastPreference> {
+    declaration
+        .get("preferences")
+        .and_then(|p| p.as_array())
+        .map(|prefs| {
+            prefs
+                .iter()
+                .filter_map(|pref| {
+                    Some(RaycastPreference {
+                        name: pref.get("name")?.as_str()?.to_string(),
+                        title: pref
+                            .get("title")
+                            .and_then(|t| t.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        description: pref
+                            .get("description")
+                            .and_then(|d| d.as_str())
+                            .map(|s| s.to_string()),
+                        preference_type: pref
+                            .get("type")
+                            .and_then(|t| t.as_str())
+                            .unwrap_or("textfield")
+                            .to_string(),
+                        required: pref
+                            .get("required")
+                            .and_then(|r| r.as_bool())
+                            .unwrap_or(false),
+                        default: pref.get("default").cloned(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
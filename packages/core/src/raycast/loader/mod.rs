@@ -1,5 +1,6 @@
 //! Raycast loader module for syncing and managing Raycast extensions
 
+pub mod compile;
 pub mod core;
 pub mod extension;
 pub mod state;
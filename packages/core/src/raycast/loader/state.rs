@@ -1,31 +1,61 @@
 //! Raycast sync state management
 
+use std::collections::BTreeMap;
 use std::fs;
-use std::time::{Duration, SystemTime};
+use std::path::PathBuf;
+use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use crate::error::{Error, Result};
 
-const UPDATE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60); // 24 hours
-
-#[derive(Debug, Serialize, Deserialize)]
+/// Installed Raycast extensions and where they came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RaycastSyncState {
+    /// When an extension was last installed, upgraded or removed
     pub last_sync: SystemTime,
-    pub last_commit: String,
-    pub extension_count: usize,
+    /// Local copy of the Raycast extensions repository that extensions can be installed from
+    pub mirror: Option<PathBuf>,
+    /// Installed extensions by id
+    pub extensions: BTreeMap<String, InstalledRaycastExtension>,
 }
 
-impl RaycastSyncState {
-    /// Check if we should update based on the 24-hour interval
-    pub fn should_update(&self) -> bool {
-        let elapsed = SystemTime::now()
-            .duration_since(self.last_sync)
-            .unwrap_or(Duration::MAX);
-        elapsed > UPDATE_INTERVAL
+impl Default for RaycastSyncState {
+    fn default() -> Self {
+        Self {
+            last_sync: SystemTime::UNIX_EPOCH,
+            mirror: None,
+            extensions: BTreeMap::new(),
+        }
     }
+}
+
+/// An installed extension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledRaycastExtension {
+    /// `version` from the extension's package.json, if it declares one
+    pub version: Option<String>,
+    /// Where the extension was installed from; upgrades install from here again
+    pub source: RaycastExtensionSource,
+    pub installed_at: SystemTime,
+    /// Commands compiled at install time
+    pub commands: Vec<String>,
+}
+
+/// Where an extension is installed from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RaycastExtensionSource {
+    /// An extension directory with a package.json and its sources
+    Directory { path: PathBuf },
+    /// A `.tar.gz` of an extension directory, as produced by `npm pack`
+    Tarball { path: PathBuf },
+    /// An extension in the configured mirror, by directory name
+    Mirror { name: String },
+}
 
+impl RaycastSyncState {
     /// Save the sync state to file
     pub fn save_to_file(&self, state_file: &std::path::Path) -> Result<()> {
         let json = serde_json::to_string_pretty(self).map_err(|e| {
@@ -36,9 +66,8 @@ impl RaycastSyncState {
             .map_err(|e| Error::IoError(format!("Failed to write sync state: {e}")))?;
 
         info!(
-            extension_count = self.extension_count,
-            last_commit = %self.last_commit,
-            "Raycast extensions sync completed"
+            extension_count = self.extensions.len(),
+            "Raycast extensions sync state saved"
         );
         Ok(())
     }
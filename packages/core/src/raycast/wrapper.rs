@@ -6,8 +6,8 @@ use log::{debug, info};
 
 use crate::discovery::core::types::MetadataProvider;
 use crate::error::Result;
+use crate::raycast::adapter::{RaycastCommandSession, RaycastLaunch};
 use crate::raycast::loader::RaycastExtension;
-use crate::runtime::deno::{DenoRuntime, RuntimeChannels, RuntimeConfig};
 use crate::search::{SearchIndex, SearchItem, SearchItemType};
 
 /// Bevy Plugin wrapper around Raycast Extension
@@ -165,59 +165,50 @@ pub struct RaycastPluginComponent {
 
 impl RaycastPluginComponent {
     /// Execute a command on this Raycast plugin
+    ///
+    /// Runs the bundle compiled when the extension was installed and reports the view it
+    /// rendered, if any.
     pub fn execute_command(&self, command: &str, _args: &[String]) -> Result<String> {
-        // Find the command in the extension
-        if let Some(_cmd) = self.extension.commands.iter().find(|c| c.name == command) {
-            // Create Deno runtime for execution
-            let config = RuntimeConfig::default();
-            let channels = RuntimeChannels::default();
-
-            let mut runtime = DenoRuntime::new(config, channels).map_err(|e| {
-                crate::Error::PluginError(format!("Failed to create runtime: {}", e))
-            })?;
-
-            // Load extension source files
-            let source_path = self.path.join("src").join("index.ts");
-            let source_code = if source_path.exists() {
-                fs::read_to_string(&source_path)
-                    .map_err(|e| crate::Error::IoError(format!("Failed to read source: {}", e)))?
-            } else {
-                // Extension source files don't exist - this is an error
-                return Err(crate::Error::PluginError(format!(
-                    "Extension '{}' source files not found. Expected '{}'",
-                    self.name,
-                    source_path.display()
-                )));
-            };
-
-            // Note: This is a synchronous interface for compatibility
-            // In a full Bevy implementation, this would use AsyncComputeTaskPool and
-            // component-based execution For now, we use the Deno runtime's blocking
-            // execution capability
-            let plugin_id = format!("raycast_{}", self.name);
-
-            // Create a simple async runtime for this execution
-            // This maintains synchronous interface compatibility while using proper async execution
-            // internally
-            let rt = tokio::runtime::Runtime::new().map_err(|e| {
-                crate::Error::PluginError(format!("Failed to create runtime: {}", e))
-            })?;
-
-            rt.block_on(async { runtime.execute_plugin(&plugin_id, &source_code).await })
-                .map_err(|e| {
-                    crate::Error::PluginError(format!("Plugin execution failed: {}", e))
-                })?;
-
-            Ok(format!(
-                "Successfully executed '{}' on Raycast plugin '{}'",
+        let Some(cmd) = self.extension.commands.iter().find(|c| c.name == command) else {
+            return Err(crate::Error::PluginError(format!(
+                "Command '{}' not found in plugin '{}'",
                 command, self.name
+            )));
+        };
+
+        let bundle_path = self.path.join("dist").join(format!("{command}.js"));
+        let bundle = fs::read_to_string(&bundle_path).map_err(|e| {
+            crate::Error::PluginError(format!(
+                "Extension '{}' has no compiled bundle at '{}': {}",
+                self.name,
+                bundle_path.display(),
+                e
             ))
-        } else {
-            Err(crate::Error::PluginError(format!(
-                "Command '{}' not found in plugin '{}'",
+        })?;
+
+        // The Deno runtime is not Send, so the command runs to completion on this thread
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| crate::Error::PluginError(format!("Failed to create runtime: {}", e)))?;
+        let launch = RaycastLaunch::new(&self.extension, cmd);
+        let plugin_id = format!("raycast:{}", self.extension.id);
+        let view = rt.block_on(async {
+            let mut session = RaycastCommandSession::new(&plugin_id)?;
+            session.launch(&bundle, &launch).await?;
+            Ok::<_, crate::Error>(session.view().map(|view| view.node_type.clone()))
+        })?;
+
+        Ok(match view {
+            Some(view) => format!(
+                "Executed '{}' on Raycast plugin '{}', rendering a {}",
+                command, self.name, view
+            ),
+            None => format!(
+                "Successfully executed '{}' on Raycast plugin '{}'",
                 command, self.name
-            )))
-        }
+            ),
+        })
     }
 }
 
@@ -1,14 +1,28 @@
 //! Deno runtime operations
 //!
-//! Real Deno ops with actual system integration for notifications, clipboard, and HUD.
+//! Real Deno ops with actual system integration for notifications, clipboard, HUD, opening
+//! URLs, and per-plugin storage and caches.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
 use std::sync::{Arc, OnceLock};
 
 use action_items_common::plugin_interface::ActionItem;
-use deno_core::op2;
+use action_items_ecs_clipboard::ArboardManager;
+use bevy::tasks::{AsyncComputeTaskPool, TaskPool};
+use deno_core::{OpState, op2};
+use deno_error::JsErrorBox;
+use serde_json::{Map, Value};
 use tokio::sync::{Mutex, mpsc};
 use tracing::debug;
 
+use crate::config::AppDirectories;
+use crate::plugins::permissions::{HostAccess, PermissionDenied, PermissionPolicy};
+use crate::plugins::services::StorageService;
 use crate::runtime::deno::notifications::{NotificationManager, NotificationOptions};
 use crate::runtime::plugin_wrapper::request_handling::{
     ActionItemAction, ActionItemRequest, ActionItemResponse, ActionItemUpdates, RequestHandler,
@@ -18,6 +32,9 @@ use crate::runtime::plugin_wrapper::request_handling::{
 /// Global notification manager instance
 static NOTIFICATION_MANAGER: OnceLock<NotificationManager> = OnceLock::new();
 
+/// LocalStorage of each plugin, opened on first use
+static PLUGIN_STORAGE: OnceLock<Mutex<HashMap<String, StorageService>>> = OnceLock::new();
+
 /// Global ActionItem request handler
 static ACTION_ITEM_HANDLER: OnceLock<Arc<Mutex<RequestHandler>>> = OnceLock::new();
 
@@ -36,11 +53,12 @@ pub fn initialize_action_item_handler(
 async fn action_item_handler() -> Result<Arc<Mutex<RequestHandler>>, &'static str> {
     ACTION_ITEM_HANDLER
         .get()
-        .ok_or("ActionItem handler not initialized").cloned()
+        .ok_or("ActionItem handler not initialized")
+        .cloned()
 }
 
 /// Get notification manager with database integration
-fn notification_manager() -> &'static NotificationManager {
+pub(crate) fn notification_manager() -> &'static NotificationManager {
     NOTIFICATION_MANAGER.get_or_init(|| {
         // Create notification manager without persistence for ops use
         match NotificationManager::new_without_persistence() {
@@ -56,25 +74,59 @@ fn notification_manager() -> &'static NotificationManager {
     })
 }
 
+/// Check an op against the runtime's permission policy
+///
+/// Denials are thrown to JavaScript as `PermissionDenied` errors carrying the denial as JSON.
+/// Runtimes without a policy deny every checked op.
+fn check_permission(state: &OpState, access: HostAccess<'_>) -> Result<(), JsErrorBox> {
+    check_with_policy(state, access, |policy| policy.check(access))
+}
+
+/// Check a file read like [`check_permission`], returning the resolved path to open
+fn check_read(state: &OpState, path: &Path) -> Result<PathBuf, JsErrorBox> {
+    check_with_policy(state, HostAccess::ReadFile(path), |policy| {
+        policy.check_read(path)
+    })
+}
+
+fn check_with_policy<T>(
+    state: &OpState,
+    access: HostAccess<'_>,
+    check: impl FnOnce(&PermissionPolicy) -> Result<T, PermissionDenied>,
+) -> Result<T, JsErrorBox> {
+    let result = match state.try_borrow::<PermissionPolicy>() {
+        Some(policy) => check(policy),
+        None => Err(PermissionDenied {
+            plugin_id: "unknown".to_string(),
+            permission: access.permission().to_string(),
+            resource: None,
+            reason: "no permission policy installed for this runtime".to_string(),
+        }),
+    };
+    result.map_err(|denial| JsErrorBox::new("PermissionDenied", denial.to_json().to_string()))
+}
+
 /// Raycast API: Show toast notification
 /// Real toast notification using UserNotifications framework
 #[op2]
 #[string]
-pub fn op_show_toast(#[string] message: String) -> String {
-    match notification_manager().show_toast(&message) {
+pub fn op_show_toast(state: &mut OpState, #[string] message: String) -> Result<String, JsErrorBox> {
+    check_permission(state, HostAccess::Notification)?;
+    Ok(match notification_manager().show_toast(&message) {
         Ok(id) => format!("toast_shown_id_{}", id.as_u64()),
         Err(e) => {
             tracing::error!("Toast failed: {}", e);
             "toast_error".to_string()
         },
-    }
+    })
 }
 
 /// Raycast API: Show HUD notification
 /// Real HUD notification using UserNotifications framework
 #[op2]
 #[string]
-pub fn op_show_hud(#[string] message: String) -> String {
+pub fn op_show_hud(state: &mut OpState, #[string] message: String) -> Result<String, JsErrorBox> {
+    check_permission(state, HostAccess::Notification)?;
     let options = NotificationOptions {
         title: "Action Items",
         message: &message,
@@ -83,21 +135,22 @@ pub fn op_show_hud(#[string] message: String) -> String {
         ..Default::default()
     };
 
-    match notification_manager().show_notification(options) {
+    Ok(match notification_manager().show_notification(options) {
         Ok(id) => format!("hud_shown_id_{}", id.as_u64()),
         Err(e) => {
             tracing::error!("HUD failed: {}", e);
             "hud_error".to_string()
         },
-    }
+    })
 }
 
 /// Raycast API: Get clipboard contents
 /// Real clipboard access with system integration
 #[op2]
 #[string]
-pub fn op_get_clipboard() -> String {
-    match action_items_ecs_clipboard::Clipboard::new() {
+pub fn op_get_clipboard(state: &mut OpState) -> Result<String, JsErrorBox> {
+    check_permission(state, HostAccess::ReadClipboard)?;
+    Ok(match action_items_ecs_clipboard::Clipboard::new() {
         Ok(mut clipboard) => match clipboard.get_text() {
             Ok(text) => {
                 debug!(
@@ -116,7 +169,281 @@ pub fn op_get_clipboard() -> String {
             debug!(source = "raycast", "Failed to initialize clipboard: {}", e);
             String::new()
         },
+    })
+}
+
+/// Raycast API: Show a toast with a style and an optional message
+#[op2]
+#[string]
+pub fn op_show_styled_toast(
+    state: &mut OpState,
+    #[string] style: String,
+    #[string] title: String,
+    #[serde] message: Option<String>,
+) -> Result<String, JsErrorBox> {
+    check_permission(state, HostAccess::Notification)?;
+    let options = NotificationOptions {
+        title: &title,
+        message: message.as_deref().unwrap_or_default(),
+        urgent: style == "failure",
+        ..Default::default()
+    };
+
+    Ok(match notification_manager().show_notification(options) {
+        Ok(id) => format!("toast_shown_id_{}", id.as_u64()),
+        Err(e) => {
+            tracing::error!("Toast failed: {}", e);
+            "toast_error".to_string()
+        },
+    })
+}
+
+/// Raycast API: Copy text to the clipboard
+#[op2]
+pub fn op_set_clipboard(state: &mut OpState, #[string] text: String) -> Result<(), JsErrorBox> {
+    check_permission(state, HostAccess::WriteClipboard)?;
+    action_items_ecs_clipboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| JsErrorBox::generic(format!("Failed to copy to the clipboard: {e}")))
+}
+
+/// Raycast API: Paste text into the application that was focused before the launcher
+#[op2(async)]
+pub async fn op_paste_clipboard(
+    state: Rc<RefCell<OpState>>,
+    #[string] text: String,
+) -> Result<(), JsErrorBox> {
+    check_permission(&state.borrow(), HostAccess::WriteClipboard)?;
+    AsyncComputeTaskPool::get_or_init(TaskPool::default);
+    ArboardManager::set_text(text)
+        .await
+        .map_err(|e| JsErrorBox::generic(format!("Failed to copy to the clipboard: {e}")))?;
+    ArboardManager::paste(0)
+        .await
+        .map_err(|e| JsErrorBox::generic(format!("Failed to paste: {e}")))
+}
+
+/// Raycast API: Open a URL or file, optionally with a given application
+///
+/// Web URLs need a `network_hosts` grant. The opener may run files and hands other URLs to
+/// the application registered for their scheme, so those need an `execute_commands` grant for
+/// the target or its scheme (`file:`, `mailto:`, `slack:`, ...), and files a `read_files`
+/// grant as well. An explicit application also needs `execute_commands`.
+#[op2]
+pub fn op_open(
+    state: &mut OpState,
+    #[string] target: String,
+    #[string] application: String,
+) -> Result<(), JsErrorBox> {
+    let application = Some(application.as_str()).filter(|app| !app.is_empty());
+    let opened: OsString = match open_target_path(&target) {
+        Some(path) => {
+            let path = check_read(state, &path)?;
+            let url = reqwest::Url::from_file_path(&path).map_err(|()| {
+                JsErrorBox::generic(format!("Cannot open relative path {}", path.display()))
+            })?;
+            check_permission(state, HostAccess::Open(url.as_str()))?;
+            path.into()
+        },
+        None if is_web_url(&target) => {
+            check_permission(state, HostAccess::Network { url: &target })?;
+            target.as_str().into()
+        },
+        None => {
+            check_permission(state, HostAccess::Open(&target))?;
+            target.as_str().into()
+        },
+    };
+    if let Some(application) = application {
+        check_permission(state, HostAccess::ExecuteCommand(application))?;
+    }
+    open_command(&opened, application)
+        .spawn()
+        .map(drop)
+        .map_err(|e| JsErrorBox::generic(format!("Failed to open {target}: {e}")))
+}
+
+fn is_web_url(target: &str) -> bool {
+    reqwest::Url::parse(target).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// The file an `open` target names, or `None` if it is a URL
+///
+/// `file:` URLs name files, and single-letter schemes are Windows drive letters.
+fn open_target_path(target: &str) -> Option<PathBuf> {
+    match reqwest::Url::parse(target) {
+        Ok(url) if url.scheme() == "file" => {
+            Some(url.to_file_path().unwrap_or_else(|()| PathBuf::from(target)))
+        },
+        Ok(url) if url.scheme().len() > 1 => None,
+        _ => Some(PathBuf::from(target)),
+    }
+}
+
+/// Command that opens a URL or file the way the platform's file manager would
+pub(crate) fn open_command(target: impl AsRef<OsStr>, application: Option<&str>) -> Command {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        if let Some(application) = application {
+            command.args(["-a", application]);
+        }
+        command
+    };
+    #[cfg(target_os = "windows")]
+    let mut command = match application {
+        Some(application) => Command::new(application),
+        None => {
+            // Unlike `cmd /C start`, this does not reparse `&` in query strings
+            let mut command = Command::new("rundll32");
+            command.arg("url.dll,FileProtocolHandler");
+            command
+        },
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new(application.unwrap_or("xdg-open"));
+
+    command.arg(target);
+    command
+}
+
+/// Raycast API: Read a value from the plugin's LocalStorage
+#[op2(async)]
+#[serde]
+pub async fn op_local_storage_get(
+    state: Rc<RefCell<OpState>>,
+    #[string] key: String,
+) -> Result<Option<Value>, JsErrorBox> {
+    let storage = plugin_storage(&state).await?;
+    storage.get(&key).await.map_err(JsErrorBox::generic)
+}
+
+/// Raycast API: Store a value in the plugin's LocalStorage
+#[op2(async)]
+pub async fn op_local_storage_set(
+    state: Rc<RefCell<OpState>>,
+    #[string] key: String,
+    #[serde] value: Value,
+) -> Result<(), JsErrorBox> {
+    let storage = plugin_storage(&state).await?;
+    storage.set(&key, value).await.map_err(JsErrorBox::generic)
+}
+
+/// Raycast API: Remove a value from the plugin's LocalStorage
+#[op2(async)]
+pub async fn op_local_storage_remove(
+    state: Rc<RefCell<OpState>>,
+    #[string] key: String,
+) -> Result<(), JsErrorBox> {
+    let storage = plugin_storage(&state).await?;
+    storage
+        .delete(&key)
+        .await
+        .map(drop)
+        .map_err(JsErrorBox::generic)
+}
+
+/// Raycast API: Every value in the plugin's LocalStorage
+#[op2(async)]
+#[serde]
+pub async fn op_local_storage_all(
+    state: Rc<RefCell<OpState>>,
+) -> Result<Map<String, Value>, JsErrorBox> {
+    let storage = plugin_storage(&state).await?;
+    let mut items = Map::new();
+    for key in storage.keys().await.map_err(JsErrorBox::generic)? {
+        if let Some(value) = storage.get(&key).await.map_err(JsErrorBox::generic)? {
+            items.insert(key, value);
+        }
+    }
+    Ok(items)
+}
+
+/// Raycast API: Remove every value from the plugin's LocalStorage
+#[op2(async)]
+pub async fn op_local_storage_clear(state: Rc<RefCell<OpState>>) -> Result<(), JsErrorBox> {
+    let storage = plugin_storage(&state).await?;
+    storage.clear().await.map_err(JsErrorBox::generic)
+}
+
+/// Raycast API: Read a Cache namespace
+///
+/// Caches are read whole when a `Cache` is created, since its methods are synchronous.
+#[op2]
+#[serde]
+pub fn op_cache_load(
+    state: &mut OpState,
+    #[string] namespace: String,
+) -> Result<Map<String, Value>, JsErrorBox> {
+    let path = cache_file(state, &namespace)?;
+    Ok(std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default())
+}
+
+/// Raycast API: Write a Cache namespace back after it changed
+#[op2]
+pub fn op_cache_store(
+    state: &mut OpState,
+    #[string] namespace: String,
+    #[serde] entries: Map<String, Value>,
+) -> Result<(), JsErrorBox> {
+    let path = cache_file(state, &namespace)?;
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_vec(&entries)?)
+    };
+    write().map_err(|e| JsErrorBox::generic(format!("Failed to write cache: {e}")))
+}
+
+/// Id of the plugin a runtime was created for, from its permission policy
+fn policy_plugin_id(state: &OpState) -> Result<String, JsErrorBox> {
+    state
+        .try_borrow::<PermissionPolicy>()
+        .map(|policy| policy.plugin_id().to_string())
+        .ok_or_else(|| JsErrorBox::generic("No plugin is associated with this runtime"))
+}
+
+/// Storage service of the runtime's plugin
+///
+/// Services are shared by every runtime of a plugin so that concurrent commands do not
+/// overwrite each other's storage file.
+async fn plugin_storage(state: &Rc<RefCell<OpState>>) -> Result<StorageService, JsErrorBox> {
+    let plugin_id = policy_plugin_id(&state.borrow())?;
+    let mut services = PLUGIN_STORAGE.get_or_init(Default::default).lock().await;
+    if let Some(storage) = services.get(&plugin_id) {
+        return Ok(storage.clone());
     }
+
+    let storage = StorageService::new(AppDirectories::new().plugin_data(), plugin_id.clone())
+        .await
+        .map_err(|e| JsErrorBox::generic(format!("Failed to open plugin storage: {e}")))?;
+    services.insert(plugin_id, storage.clone());
+    Ok(storage)
+}
+
+/// File a Cache namespace is kept in, below the plugin's cache directory
+fn cache_file(state: &OpState, namespace: &str) -> Result<PathBuf, JsErrorBox> {
+    let plugin_id = policy_plugin_id(state)?;
+    // Namespaces come from the plugin, so they must not be able to leave its directory
+    let name: String = namespace
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let name = if name.is_empty() { "default" } else { &name };
+    Ok(AppDirectories::new()
+        .plugin_cache()
+        .join(plugin_id)
+        .join(format!("{name}.json")))
 }
 
 /// Log from JavaScript runtime
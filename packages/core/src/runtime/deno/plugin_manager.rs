@@ -283,6 +283,7 @@ impl PluginManager {
             max_launcher_version: None,
             update_url: None,
             changelog_url: None,
+            resource_limits: Default::default(),
         })
     }
 
@@ -375,6 +376,7 @@ impl PluginManager {
             max_launcher_version: None,
             update_url: None,
             changelog_url: None,
+            resource_limits: Default::default(),
         })
     }
 
@@ -4,9 +4,11 @@
 //! sandboxing.
 
 use deno_core::{
-    Extension, ExtensionFileSource, FastStaticString, JsRuntime, RuntimeOptions, ascii_str,
+    Extension, ExtensionFileSource, FastStaticString, JsRuntime, PollEventLoopOptions,
+    RuntimeOptions, ascii_str,
 };
 
+use crate::plugins::permissions::PermissionPolicy;
 use crate::runtime::deno::types::*;
 
 /// Core Deno runtime implementation
@@ -40,6 +42,14 @@ impl DenoRuntime {
         })
     }
 
+    /// Install the permission policy checked by ops that reach outside the runtime
+    ///
+    /// Without a policy those ops deny every call.
+    pub fn with_permissions(mut self, policy: PermissionPolicy) -> Self {
+        self.runtime.op_state().borrow_mut().put(policy);
+        self
+    }
+
     /// Execute plugin code with security sandboxing
     /// Zero-allocation code execution with blazing-fast error handling
     pub async fn execute_plugin(
@@ -87,8 +97,37 @@ impl DenoRuntime {
         let start_time = std::time::Instant::now();
         let script_name = format!("plugin_{}", plugin_id);
 
+        let result = self.evaluate(&script_name, code);
+
+        let duration = start_time.elapsed();
+        
+        if result.is_ok() {
+            tracing::info!(
+                "Plugin {} executed successfully in {:?}",
+                plugin_id,
+                duration
+            );
+
+            // Log performance metrics
+            if duration.as_millis() > 1000 {
+                tracing::warn!(
+                    "Plugin {} execution took {}ms (>1000ms threshold)",
+                    plugin_id,
+                    duration.as_millis()
+                );
+            }
+        }
+
+        result
+    }
+
+    /// Evaluate a script without the checks applied to plugin code
+    ///
+    /// For code the launcher trusts, such as API shims and the calls that drive them. Promises
+    /// the script starts only settle once [`Self::run_event_loop`] runs.
+    pub fn evaluate(&mut self, name: &str, code: &str) -> Result<serde_json::Value, String> {
         // Execute script synchronously (execute_script is not async)
-        let result = match self.runtime.execute_script(script_name, code.to_string()) {
+        match self.runtime.execute_script(name.to_string(), code.to_string()) {
             Ok(global_value) => {
                 // Use scope! macro to create V8 scope (replaces handle_scope() in deno_core 0.362.0)
                 deno_core::scope!(scope, &mut self.runtime);
@@ -110,28 +149,15 @@ impl DenoRuntime {
                 }
             },
             Err(e) => Err(format!("Plugin execution failed: {}", e)),
-        };
-
-        let duration = start_time.elapsed();
-        
-        if result.is_ok() {
-            tracing::info!(
-                "Plugin {} executed successfully in {:?}",
-                plugin_id,
-                duration
-            );
-
-            // Log performance metrics
-            if duration.as_millis() > 1000 {
-                tracing::warn!(
-                    "Plugin {} execution took {}ms (>1000ms threshold)",
-                    plugin_id,
-                    duration.as_millis()
-                );
-            }
         }
+    }
 
-        result
+    /// Run pending promises, ops and timers until there is nothing left to do
+    pub async fn run_event_loop(&mut self) -> Result<(), String> {
+        self.runtime
+            .run_event_loop(PollEventLoopOptions::default())
+            .await
+            .map_err(|e| e.to_string())
     }
 }
 
@@ -151,6 +177,9 @@ globalThis.showHUD = function(message) {
 };
 
 globalThis.Clipboard = {
+    copy: function(text) {
+        return Deno.core.ops.op_set_clipboard(String(text));
+    },
     readText: function() {
         return Deno.core.ops.op_get_clipboard();
     }
@@ -188,6 +217,17 @@ console.log("Raycast API initialized");
                 ops::op_show_toast(),
                 ops::op_show_hud(),
                 ops::op_get_clipboard(),
+                ops::op_show_styled_toast(),
+                ops::op_set_clipboard(),
+                ops::op_paste_clipboard(),
+                ops::op_open(),
+                ops::op_local_storage_get(),
+                ops::op_local_storage_set(),
+                ops::op_local_storage_remove(),
+                ops::op_local_storage_all(),
+                ops::op_local_storage_clear(),
+                ops::op_cache_load(),
+                ops::op_cache_store(),
                 ops::op_log(),
                 ops::op_action_item_create(),
                 ops::op_action_item_search(),
@@ -1,31 +1,89 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 
+use action_items_common::directories::AppDirectories;
 use bevy::prelude::*;
 
 use crate::error::Result;
+use crate::search::inverted_index::{IndexStore, InvertedIndex, JournalOp};
 use crate::search::item::{SearchItem, SearchItemType};
 
+/// Subdirectory of the application data dir holding the persisted index
+const INDEX_DIR_NAME: &str = "search_index";
+
+/// Bonus applied when the whole query equals an item title, on top of its term scores
+const EXACT_TITLE_BONUS: f32 = 25.0;
+
 #[derive(Resource)]
 pub struct SearchIndex {
     items: HashMap<String, SearchItem>,
-    indexed_at: std::time::SystemTime,
+    postings: InvertedIndex,
+    store: Option<IndexStore>,
+    indexed_at: SystemTime,
 }
 
 impl SearchIndex {
+    /// Create an in-memory index that is not persisted
     pub fn new() -> Self {
         Self {
             items: HashMap::new(),
-            indexed_at: std::time::SystemTime::now(),
+            postings: InvertedIndex::new(),
+            store: None,
+            indexed_at: SystemTime::now(),
         }
     }
 
+    /// Open a persistent index stored in `dir`, recovering any previously indexed items
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let (store, loaded) = IndexStore::open(dir)?;
+        Ok(Self {
+            items: loaded.items,
+            postings: loaded.postings,
+            store: Some(store),
+            indexed_at: loaded.indexed_at.unwrap_or(SystemTime::UNIX_EPOCH),
+        })
+    }
+
+    /// Open the persistent index under the application data directory
+    pub fn open_default() -> Result<Self> {
+        Self::open(AppDirectories::new().data_dir().join(INDEX_DIR_NAME))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Time of the last full rebuild
+    pub fn indexed_at(&self) -> SystemTime {
+        self.indexed_at
+    }
+
+    /// Whether the last full rebuild is older than `max_age`
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.indexed_at.elapsed().map(|age| age > max_age).unwrap_or(true)
+    }
+
+    /// Whether the index is backed by on-disk storage
+    pub fn is_persistent(&self) -> bool {
+        self.store.is_some()
+    }
+
     pub fn add_item(&mut self, item: SearchItem) {
+        self.postings.insert(&item);
+        self.journal(JournalOp::Upsert { item: item.clone() });
         self.items.insert(item.id.clone(), item);
     }
 
     pub fn remove_item(&mut self, id: &str) -> Option<SearchItem> {
-        self.items.remove(id)
+        let removed = self.items.remove(id)?;
+        self.postings.remove(id);
+        self.journal(JournalOp::Remove { id: id.to_string() });
+        Some(removed)
     }
 
     pub fn search(&self, query: &str) -> Vec<SearchItem> {
@@ -33,60 +91,29 @@ impl SearchIndex {
             return Vec::new();
         }
 
-        let query_lower = query.to_lowercase();
+        let query_lower = query.trim().to_lowercase();
         let mut results: Vec<SearchItem> = self
-            .items
-            .values()
-            .filter_map(|item| {
-                let mut score = 0.0;
-
-                // Exact title match gets highest score
-                if item.title.to_lowercase() == query_lower {
-                    score += 100.0;
-                } else if item.title.to_lowercase().starts_with(&query_lower) {
-                    score += 75.0;
-                } else if item.title.to_lowercase().contains(&query_lower) {
-                    score += 50.0;
-                }
-
-                // Description match
-                if item.description.to_lowercase().contains(&query_lower) {
-                    score += 25.0;
-                }
-
-                // Keywords match
-                for keyword in &item.keywords {
-                    if keyword.to_lowercase().contains(&query_lower) {
-                        score += 30.0;
-                    }
-                }
-
-                // Path match for files
-                if let Some(path) = &item.path
-                    && let Some(filename) = path.file_name()
-                    && filename
-                        .to_string_lossy()
-                        .to_lowercase()
-                        .contains(&query_lower)
-                {
-                    score += 40.0;
-                }
-
-                if score > 0.0 {
-                    let mut result = item.clone();
-                    result.score = score;
-                    Some(result)
+            .postings
+            .query(query)
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let item = self.items.get(&id)?;
+                let mut result = item.clone();
+                result.score = if item.title.to_lowercase() == query_lower {
+                    score + EXACT_TITLE_BONUS
                 } else {
-                    None
-                }
+                    score
+                };
+                Some(result)
             })
             .collect();
 
-        // Sort by score (highest first)
+        // Sort by score (highest first), then title for a stable order between keystrokes
         results.sort_by(|a, b| {
             b.score
                 .partial_cmp(&a.score)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.title.cmp(&b.title))
         });
         results.truncate(10); // Limit to top 10 results
         results
@@ -96,8 +123,58 @@ impl SearchIndex {
         self.items.get(id)
     }
 
+    /// Iterate over every indexed item, e.g. for scorers that cannot use the postings
+    pub fn items(&self) -> impl Iterator<Item = &SearchItem> {
+        self.items.values()
+    }
+
+    /// Items worth fuzzy matching against `query`, at most `limit`
+    ///
+    /// Returns items with a title or keyword word starting with the query's first character,
+    /// so callers can score subsequence matches without scanning the whole index.
+    pub fn fuzzy_candidates(&self, query: &str, limit: usize) -> Vec<&SearchItem> {
+        let Some(initial) = query.trim().chars().next() else {
+            return Vec::new();
+        };
+        self.postings
+            .initial_candidates(initial, limit)
+            .into_iter()
+            .filter_map(|id| self.items.get(&id))
+            .collect()
+    }
+
+    /// Make journaled changes durable, compacting into a snapshot when the journal is long
+    pub fn flush(&mut self) -> Result<()> {
+        let Some(store) = self.store.as_mut() else {
+            return Ok(());
+        };
+
+        if store.needs_compaction() {
+            store.write_snapshot(self.items.values(), &self.postings, self.indexed_at)
+        } else {
+            store.flush()
+        }
+    }
+
+    /// Whether there are journaled changes not yet flushed to disk
+    pub fn has_pending_writes(&self) -> bool {
+        self.store.as_ref().is_some_and(|store| store.is_dirty())
+    }
+
+    fn journal(&mut self, op: JournalOp) {
+        if let Some(store) = self.store.as_mut()
+            && let Err(e) = store.append(&op)
+        {
+            error!("Failed to journal search index change: {}", e);
+        }
+    }
+
     pub fn rebuild_index(&mut self) -> Result<()> {
+        // Detach the store so a full rebuild is written as one snapshot instead of journaling
+        // every item individually
+        let store = self.store.take();
         self.items.clear();
+        self.postings.clear();
 
         // Try to index applications, but don't fail the whole operation
         if let Err(e) = crate::search::platforms::index_applications(self) {
@@ -112,7 +189,15 @@ impl SearchIndex {
         // Always index system commands (cannot fail)
         self.index_system_commands();
 
-        self.indexed_at = std::time::SystemTime::now();
+        self.indexed_at = SystemTime::now();
+        self.store = store;
+
+        if let Some(store) = self.store.as_mut() {
+            let items = self.items.values();
+            if let Err(e) = store.write_snapshot(items, &self.postings, self.indexed_at) {
+                error!("Failed to persist rebuilt search index: {}", e);
+            }
+        }
 
         // Log final index size
         info!("Search index rebuilt with {} items", self.items.len());
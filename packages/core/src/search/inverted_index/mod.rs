@@ -0,0 +1,13 @@
+//! Persistent inverted index backing [`SearchIndex`](crate::search::SearchIndex)
+//!
+//! Item fields are tokenized into terms with prefix and trigram lookups, and the resulting
+//! postings are persisted under the application data directory so a cold start can serve
+//! queries without re-scanning applications and files.
+
+pub mod postings;
+pub mod storage;
+pub mod tokenizer;
+
+pub use postings::{DocId, IndexedField, InvertedIndex};
+pub use storage::{COMPACTION_THRESHOLD, IndexStore, JournalOp, LoadedIndex};
+pub use tokenizer::{ngrams, tokenize, tokenize_query};
@@ -0,0 +1,341 @@
+//! In-memory inverted index over search item fields
+//!
+//! Terms are kept in a `BTreeMap` so prefix queries are a single range scan, and every term is
+//! additionally decomposed into trigrams so that infix queries only verify a small candidate set
+//! instead of scanning the whole corpus.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::tokenizer::{ngrams, tokenize, tokenize_query};
+use crate::search::item::SearchItem;
+
+/// Compact document identifier used inside posting lists
+pub type DocId = u32;
+
+/// Item field a term was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IndexedField {
+    Title = 0b0001,
+    FileName = 0b0010,
+    Keyword = 0b0100,
+    Description = 0b1000,
+}
+
+impl IndexedField {
+    const ALL: [IndexedField; 4] = [
+        IndexedField::Title,
+        IndexedField::FileName,
+        IndexedField::Keyword,
+        IndexedField::Description,
+    ];
+
+    /// Score contribution for a whole-term match in this field
+    ///
+    /// Mirrors the relative weights of the original substring scorer: title beats file name,
+    /// which beats keywords, which beat the description.
+    fn weight(self) -> f32 {
+        match self {
+            IndexedField::Title => 75.0,
+            IndexedField::FileName => 40.0,
+            IndexedField::Keyword => 30.0,
+            IndexedField::Description => 25.0,
+        }
+    }
+}
+
+/// How a query term matched an indexed term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchKind {
+    Exact,
+    Prefix,
+    Infix,
+}
+
+impl MatchKind {
+    fn multiplier(self) -> f32 {
+        match self {
+            MatchKind::Exact => 1.0,
+            MatchKind::Prefix => 0.8,
+            MatchKind::Infix => 0.6,
+        }
+    }
+}
+
+/// Bitset of [`IndexedField`]s a term occurs in for one document
+pub type FieldMask = u8;
+
+/// Inverted index mapping terms and trigrams to documents
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InvertedIndex {
+    /// term -> (document -> fields containing the term)
+    terms: BTreeMap<String, HashMap<DocId, FieldMask>>,
+    /// trigram -> terms containing it
+    ngrams: HashMap<String, HashSet<String>>,
+    /// document id -> item id, `None` for freed slots
+    docs: Vec<Option<String>>,
+    /// item id -> document id
+    doc_ids: HashMap<String, DocId>,
+    /// document id -> terms it was indexed under, for removal without a full term scan
+    doc_terms: HashMap<DocId, Vec<String>>,
+    /// Freed document slots available for reuse
+    free_docs: Vec<DocId>,
+}
+
+impl InvertedIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of indexed documents
+    pub fn len(&self) -> usize {
+        self.doc_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.doc_ids.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Index an item, replacing any previous postings for the same id
+    pub fn insert(&mut self, item: &SearchItem) {
+        self.remove(&item.id);
+
+        let doc = self.allocate_doc(&item.id);
+        let item_terms = field_terms(item);
+        let mut indexed = Vec::with_capacity(item_terms.len());
+        for (term, mask) in item_terms {
+            if !self.terms.contains_key(&term) {
+                for gram in ngrams(&term) {
+                    self.ngrams.entry(gram).or_default().insert(term.clone());
+                }
+            }
+            *self.terms.entry(term.clone()).or_default().entry(doc).or_insert(0) |= mask;
+            indexed.push(term);
+        }
+        self.doc_terms.insert(doc, indexed);
+    }
+
+    /// Drop all postings for an item id
+    ///
+    /// Postings are located through the per-document term list rather than by re-tokenizing
+    /// the item, so removal works even when the caller no longer has the original item.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some(doc) = self.doc_ids.remove(id) else {
+            return false;
+        };
+
+        let mut emptied = Vec::new();
+        for term in self.doc_terms.remove(&doc).unwrap_or_default() {
+            if let Some(postings) = self.terms.get_mut(&term) {
+                postings.remove(&doc);
+                if postings.is_empty() {
+                    emptied.push(term);
+                }
+            }
+        }
+
+        for term in emptied {
+            self.terms.remove(&term);
+            for gram in ngrams(&term) {
+                if let Some(terms) = self.ngrams.get_mut(&gram) {
+                    terms.remove(&term);
+                    if terms.is_empty() {
+                        self.ngrams.remove(&gram);
+                    }
+                }
+            }
+        }
+
+        if let Some(slot) = self.docs.get_mut(doc as usize) {
+            *slot = None;
+        }
+        self.free_docs.push(doc);
+        true
+    }
+
+    /// Score every document matching all query terms
+    ///
+    /// Each query term must match at least one indexed term exactly, as a prefix, or (for terms
+    /// of three or more characters) as an infix. The returned scores are keyed by item id.
+    pub fn query(&self, query: &str) -> HashMap<String, f32> {
+        let query_terms = tokenize_query(query);
+        if query_terms.is_empty() {
+            return HashMap::new();
+        }
+
+        let mut totals: Option<HashMap<DocId, f32>> = None;
+        for query_term in &query_terms {
+            let term_scores = self.score_term(query_term);
+
+            totals = Some(match totals {
+                None => term_scores,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(doc, score)| term_scores.get(&doc).map(|s| (doc, score + s)))
+                    .collect(),
+            });
+
+            if totals.as_ref().is_some_and(HashMap::is_empty) {
+                return HashMap::new();
+            }
+        }
+
+        let term_count = query_terms.len() as f32;
+        totals
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(doc, score)| {
+                self.docs
+                    .get(doc as usize)
+                    .and_then(|slot| slot.clone())
+                    .map(|id| (id, score / term_count))
+            })
+            .collect()
+    }
+
+    /// Ids of documents with a title or keyword term starting with `initial`, at most `limit`
+    ///
+    /// Fuzzy subsequence and acronym matches are anchored on a word start, so this is the
+    /// candidate set for fuzzy scoring when the term lookup in [`Self::query`] finds nothing.
+    pub fn initial_candidates(&self, initial: char, limit: usize) -> Vec<String> {
+        let fields = IndexedField::Title as u8 | IndexedField::Keyword as u8;
+        let prefix: String = initial.to_lowercase().collect();
+
+        let mut seen: HashSet<DocId> = HashSet::new();
+        for (term, postings) in self.terms.range(prefix.clone()..) {
+            if !term.starts_with(&prefix) || seen.len() >= limit {
+                break;
+            }
+            seen.extend(
+                postings
+                    .iter()
+                    .filter(|(_, mask)| **mask & fields != 0)
+                    .map(|(doc, _)| *doc),
+            );
+        }
+
+        seen.into_iter()
+            .take(limit)
+            .filter_map(|doc| self.docs.get(doc as usize).and_then(|slot| slot.clone()))
+            .collect()
+    }
+
+    /// Best score per document for a single query term
+    fn score_term(&self, query_term: &str) -> HashMap<DocId, f32> {
+        let mut scores: HashMap<DocId, f32> = HashMap::new();
+
+        if let Some(postings) = self.terms.get(query_term) {
+            accumulate(&mut scores, postings, MatchKind::Exact);
+        }
+
+        for (term, postings) in self.terms.range(query_term.to_string()..) {
+            if !term.starts_with(query_term) {
+                break;
+            }
+            if term != query_term {
+                accumulate(&mut scores, postings, MatchKind::Prefix);
+            }
+        }
+
+        for term in self.infix_candidates(query_term) {
+            if term.starts_with(query_term) {
+                continue;
+            }
+            if let Some(postings) = self.terms.get(&term) {
+                accumulate(&mut scores, postings, MatchKind::Infix);
+            }
+        }
+
+        scores
+    }
+
+    /// Indexed terms containing `query_term` as a substring
+    fn infix_candidates(&self, query_term: &str) -> Vec<String> {
+        let grams = ngrams(query_term);
+        if grams.is_empty() {
+            return Vec::new();
+        }
+
+        // Intersect starting from the rarest trigram to keep the working set small
+        let mut sets: Vec<&HashSet<String>> = Vec::with_capacity(grams.len());
+        for gram in &grams {
+            match self.ngrams.get(gram) {
+                Some(set) => sets.push(set),
+                None => return Vec::new(),
+            }
+        }
+        sets.sort_by_key(|set| set.len());
+
+        let (smallest, rest) = sets.split_first().expect("non-empty trigram list");
+        smallest
+            .iter()
+            .filter(|term| rest.iter().all(|set| set.contains(*term)))
+            .filter(|term| term.contains(query_term))
+            .cloned()
+            .collect()
+    }
+
+    fn allocate_doc(&mut self, id: &str) -> DocId {
+        let doc = match self.free_docs.pop() {
+            Some(doc) => {
+                self.docs[doc as usize] = Some(id.to_string());
+                doc
+            },
+            None => {
+                self.docs.push(Some(id.to_string()));
+                (self.docs.len() - 1) as DocId
+            },
+        };
+        self.doc_ids.insert(id.to_string(), doc);
+        doc
+    }
+}
+
+fn accumulate(
+    scores: &mut HashMap<DocId, f32>,
+    postings: &HashMap<DocId, FieldMask>,
+    kind: MatchKind,
+) {
+    for (&doc, &mask) in postings {
+        let score = best_field_weight(mask) * kind.multiplier();
+        let entry = scores.entry(doc).or_insert(0.0);
+        if score > *entry {
+            *entry = score;
+        }
+    }
+}
+
+fn best_field_weight(mask: FieldMask) -> f32 {
+    IndexedField::ALL
+        .iter()
+        .filter(|field| mask & (**field as u8) != 0)
+        .map(|field| field.weight())
+        .fold(0.0, f32::max)
+}
+
+/// All terms of an item with the fields they occur in
+fn field_terms(item: &SearchItem) -> HashMap<String, FieldMask> {
+    let mut terms: HashMap<String, FieldMask> = HashMap::new();
+    let mut add = |text: &str, field: IndexedField| {
+        for term in tokenize(text) {
+            *terms.entry(term).or_insert(0) |= field as u8;
+        }
+    };
+
+    add(&item.title, IndexedField::Title);
+    add(&item.description, IndexedField::Description);
+    for keyword in &item.keywords {
+        add(keyword, IndexedField::Keyword);
+    }
+    if let Some(file_name) = item.path.as_ref().and_then(|p| p.file_name()) {
+        add(&file_name.to_string_lossy(), IndexedField::FileName);
+    }
+
+    terms
+}
@@ -0,0 +1,299 @@
+//! On-disk persistence for the search index
+//!
+//! The index is stored as a binary snapshot (items plus postings) and an append-only JSON-lines
+//! journal of incremental `add_item`/`remove_item` operations. Opening the store loads the
+//! snapshot and replays the journal; once the journal grows past [`COMPACTION_THRESHOLD`]
+//! operations a fresh snapshot is written atomically and the journal is truncated.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use super::postings::InvertedIndex;
+use crate::error::{Error, Result, SearchError, SerializationError};
+use crate::search::item::SearchItem;
+
+/// Snapshot format version, bumped whenever the serialized layout changes
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+const SNAPSHOT_FILE: &str = "index.bin";
+const JOURNAL_FILE: &str = "journal.jsonl";
+/// Journal length after which the next flush rewrites the snapshot
+pub const COMPACTION_THRESHOLD: usize = 4096;
+
+/// Serialized form of the whole index
+#[derive(Deserialize)]
+struct IndexSnapshot {
+    format_version: u32,
+    indexed_at: SystemTime,
+    items: Vec<SearchItem>,
+    postings: InvertedIndex,
+}
+
+/// Borrowed counterpart of [`IndexSnapshot`] so writing a snapshot does not clone the index
+#[derive(Serialize)]
+struct IndexSnapshotRef<'a> {
+    format_version: u32,
+    indexed_at: SystemTime,
+    items: Vec<&'a SearchItem>,
+    postings: &'a InvertedIndex,
+}
+
+/// Single incremental change recorded in the journal
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum JournalOp {
+    Upsert { item: SearchItem },
+    Remove { id: String },
+}
+
+/// State recovered from disk when opening the store
+pub struct LoadedIndex {
+    pub items: HashMap<String, SearchItem>,
+    pub postings: InvertedIndex,
+    pub indexed_at: Option<SystemTime>,
+}
+
+/// Snapshot and journal files backing a persistent [`SearchIndex`](crate::search::SearchIndex)
+pub struct IndexStore {
+    dir: PathBuf,
+    journal: BufWriter<File>,
+    journal_len: usize,
+    dirty: bool,
+}
+
+impl IndexStore {
+    /// Open (or create) the store in `dir` and recover its contents
+    pub fn open(dir: impl Into<PathBuf>) -> Result<(Self, LoadedIndex)> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|e| index_error(format!("create {dir:?}: {e}")))?;
+
+        let mut loaded = match load_snapshot(&dir.join(SNAPSHOT_FILE)) {
+            Ok(Some(snapshot)) => LoadedIndex {
+                items: snapshot.items.into_iter().map(|item| (item.id.clone(), item)).collect(),
+                postings: snapshot.postings,
+                indexed_at: Some(snapshot.indexed_at),
+            },
+            Ok(None) => LoadedIndex {
+                items: HashMap::new(),
+                postings: InvertedIndex::new(),
+                indexed_at: None,
+            },
+            Err(e) => {
+                warn!("Discarding unreadable search index snapshot: {}", e);
+                LoadedIndex {
+                    items: HashMap::new(),
+                    postings: InvertedIndex::new(),
+                    indexed_at: None,
+                }
+            },
+        };
+
+        let journal_path = dir.join(JOURNAL_FILE);
+        let (journal_len, journal_intact) = replay_journal(&journal_path, &mut loaded)?;
+
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| index_error(format!("open {journal_path:?}: {e}")))?;
+
+        debug!(
+            "Opened search index store at {:?}: {} items, {} journal ops replayed",
+            dir,
+            loaded.items.len(),
+            journal_len
+        );
+
+        let mut store = Self {
+            dir,
+            journal: BufWriter::new(journal),
+            journal_len,
+            dirty: false,
+        };
+
+        // Anything appended after a corrupt line would never be replayed, so fold the
+        // recovered state into a fresh snapshot before accepting new writes.
+        if !journal_intact {
+            let indexed_at = loaded.indexed_at.unwrap_or_else(SystemTime::now);
+            store.write_snapshot(loaded.items.values(), &loaded.postings, indexed_at)?;
+        }
+
+        Ok((store, loaded))
+    }
+
+    /// Directory holding the snapshot and journal
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether buffered journal entries are waiting to be flushed
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Whether the journal is long enough that a snapshot should be written
+    pub fn needs_compaction(&self) -> bool {
+        self.journal_len >= COMPACTION_THRESHOLD
+    }
+
+    /// Buffer a journal entry; call [`IndexStore::flush`] to make it durable
+    pub fn append(&mut self, op: &JournalOp) -> Result<()> {
+        serde_json::to_writer(&mut self.journal, op)?;
+        self.journal
+            .write_all(b"\n")
+            .map_err(|e| index_error(format!("append to journal: {e}")))?;
+        self.journal_len += 1;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Flush buffered journal entries to disk
+    pub fn flush(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.journal
+            .flush()
+            .and_then(|_| self.journal.get_ref().sync_data())
+            .map_err(|e| index_error(format!("flush journal: {e}")))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Write a full snapshot and truncate the journal
+    ///
+    /// The snapshot is written to a temporary file and renamed into place so a crash never
+    /// leaves a half-written snapshot behind; the journal is only truncated afterwards.
+    pub fn write_snapshot<'a>(
+        &mut self,
+        items: impl Iterator<Item = &'a SearchItem>,
+        postings: &InvertedIndex,
+        indexed_at: SystemTime,
+    ) -> Result<()> {
+        let snapshot = IndexSnapshotRef {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            indexed_at,
+            items: items.collect(),
+            postings,
+        };
+
+        let snapshot_path = self.dir.join(SNAPSHOT_FILE);
+        let tmp_path = self.dir.join(format!("{SNAPSHOT_FILE}.tmp"));
+        {
+            let file = File::create(&tmp_path)
+                .map_err(|e| index_error(format!("create {tmp_path:?}: {e}")))?;
+            let mut writer = BufWriter::new(file);
+            bincode::serde::encode_into_std_write(
+                &snapshot,
+                &mut writer,
+                bincode::config::standard(),
+            )
+            .map_err(binary_error)?;
+            writer
+                .into_inner()
+                .map_err(|e| index_error(format!("write snapshot: {e}")))?
+                .sync_all()
+                .map_err(|e| index_error(format!("sync snapshot: {e}")))?;
+        }
+        fs::rename(&tmp_path, &snapshot_path)
+            .map_err(|e| index_error(format!("install snapshot: {e}")))?;
+
+        // Drain the old writer first; if it flushed on drop after the truncation its stale
+        // entries would land at the start of the new journal.
+        self.journal
+            .flush()
+            .map_err(|e| index_error(format!("flush journal: {e}")))?;
+        let journal_path = self.dir.join(JOURNAL_FILE);
+        File::create(&journal_path)
+            .map_err(|e| index_error(format!("truncate {journal_path:?}: {e}")))?;
+        let journal = OpenOptions::new()
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| index_error(format!("open {journal_path:?}: {e}")))?;
+        self.journal = BufWriter::new(journal);
+        self.journal_len = 0;
+        self.dirty = false;
+
+        debug!(
+            "Wrote search index snapshot with {} items to {:?}",
+            snapshot.items.len(),
+            snapshot_path
+        );
+        Ok(())
+    }
+}
+
+fn load_snapshot(path: &Path) -> Result<Option<IndexSnapshot>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let file = File::open(path).map_err(|e| index_error(format!("open {path:?}: {e}")))?;
+    let snapshot: IndexSnapshot = bincode::serde::decode_from_std_read(
+        &mut BufReader::new(file),
+        bincode::config::standard(),
+    )
+    .map_err(binary_error)?;
+
+    if snapshot.format_version != SNAPSHOT_FORMAT_VERSION {
+        warn!(
+            "Ignoring search index snapshot with format version {} (expected {})",
+            snapshot.format_version, SNAPSHOT_FORMAT_VERSION
+        );
+        return Ok(None);
+    }
+
+    Ok(Some(snapshot))
+}
+
+/// Apply journal entries on top of the loaded snapshot
+///
+/// Returns how many entries were applied and whether the journal was read to the end. A
+/// truncated trailing line (from a crash mid-append) stops the replay instead of failing the
+/// whole open, since every earlier entry is still valid.
+fn replay_journal(path: &Path, loaded: &mut LoadedIndex) -> Result<(usize, bool)> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, true)),
+        Err(e) => return Err(index_error(format!("open {path:?}: {e}"))),
+    };
+
+    let mut applied = 0;
+    for (line_no, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| index_error(format!("read journal: {e}")))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<JournalOp>(&line) {
+            Ok(JournalOp::Upsert { item }) => {
+                loaded.postings.insert(&item);
+                loaded.items.insert(item.id.clone(), item);
+            },
+            Ok(JournalOp::Remove { id }) => {
+                loaded.postings.remove(&id);
+                loaded.items.remove(&id);
+            },
+            Err(e) => {
+                warn!("Stopping search journal replay at line {}: {}", line_no + 1, e);
+                return Ok((applied, false));
+            },
+        }
+        applied += 1;
+    }
+
+    Ok((applied, true))
+}
+
+fn index_error(message: String) -> Error {
+    Error::Search(SearchError::IndexError(message))
+}
+
+fn binary_error(error: impl std::fmt::Display) -> Error {
+    Error::Serialization(SerializationError::BinaryError(error.to_string()))
+}
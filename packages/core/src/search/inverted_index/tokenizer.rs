@@ -0,0 +1,82 @@
+//! Tokenization for the inverted search index
+//!
+//! Splits item fields into lowercase terms on non-alphanumeric boundaries and camelCase
+//! transitions, and derives the character trigrams used for infix matching.
+
+/// Length of the character n-grams used for substring lookups
+pub const NGRAM_SIZE: usize = 3;
+
+/// Split text into lowercase index terms
+///
+/// `"VisualStudio Code.app"` yields `visualstudio`, `visual`, `studio`, `code`, `app`.
+/// The whole alphanumeric run is always emitted so that acronym-free prefixes still match,
+/// followed by its camelCase parts when there is more than one.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() {
+            continue;
+        }
+
+        terms.push(word.to_lowercase());
+
+        let parts = split_camel_case(word);
+        if parts.len() > 1 {
+            terms.extend(parts.into_iter().map(|part| part.to_lowercase()));
+        }
+    }
+
+    terms.sort_unstable();
+    terms.dedup();
+    terms
+}
+
+/// Split query text into unique lowercase terms in the order they were typed
+pub fn tokenize_query(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in query.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let term = word.to_lowercase();
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Character trigrams of a term, used to answer infix queries like `code` in `vscode`
+pub fn ngrams(term: &str) -> Vec<String> {
+    let chars: Vec<char> = term.chars().collect();
+    if chars.len() < NGRAM_SIZE {
+        return Vec::new();
+    }
+
+    let mut grams: Vec<String> = chars.windows(NGRAM_SIZE).map(|w| w.iter().collect()).collect();
+    grams.sort_unstable();
+    grams.dedup();
+    grams
+}
+
+fn split_camel_case(word: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+
+    for (idx, ch) in word.char_indices() {
+        if let Some(p) = prev {
+            let lower_to_upper = p.is_lowercase() && ch.is_uppercase();
+            let alpha_to_digit = p.is_alphabetic() && ch.is_ascii_digit();
+            let digit_to_alpha = p.is_ascii_digit() && ch.is_alphabetic();
+            if (lower_to_upper || alpha_to_digit || digit_to_alpha) && idx > start {
+                parts.push(&word[start..idx]);
+                start = idx;
+            }
+        }
+        prev = Some(ch);
+    }
+
+    if start < word.len() {
+        parts.push(&word[start..]);
+    }
+    parts
+}
@@ -1,5 +1,6 @@
 pub mod distributed;
 pub mod index;
+pub mod inverted_index;
 pub mod item;
 pub mod platforms;
 pub mod systems;
@@ -16,6 +17,7 @@ pub use item::{SearchItem, SearchItemType};
 pub use systems::{
     // ECS-based search systems
     execute_action_item_ecs,
+    persist_search_index_system,
     search_system_ecs,
     setup_search_index,
 };
@@ -7,10 +7,27 @@ use log::{debug, error, info, warn};
 use crate::plugins::ecs_queries::{PluginExecutor, PluginSearcher};
 use crate::search::index::SearchIndex;
 
+/// Age after which a persisted index is rebuilt at startup to pick up new applications
+const REINDEX_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Minimum interval between journal flushes of the persistent search index
+const INDEX_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
 pub fn setup_search_index(mut commands: Commands) {
-    let mut index = SearchIndex::new();
-    if let Err(e) = index.rebuild_index() {
-        error!("Failed to build search index: {}", e);
+    let mut index = match SearchIndex::open_default() {
+        Ok(index) => index,
+        Err(e) => {
+            error!("Failed to open persistent search index, using in-memory index: {}", e);
+            SearchIndex::new()
+        },
+    };
+
+    if index.is_empty() || index.is_stale(REINDEX_INTERVAL) {
+        if let Err(e) = index.rebuild_index() {
+            error!("Failed to build search index: {}", e);
+        }
+    } else {
+        info!("Loaded {} items from persistent search index", index.len());
     }
     commands.insert_resource(index);
 
@@ -20,6 +37,28 @@ pub fn setup_search_index(mut commands: Commands) {
     commands.insert_resource(SearchPerformanceMetrics::default());
 }
 
+/// Flush journaled search index changes to disk
+///
+/// Runs every frame but only touches the disk when `add_item`/`remove_item` produced pending
+/// writes and the flush interval has elapsed, compacting into a snapshot when the journal is long.
+pub fn persist_search_index_system(
+    mut index: ResMut<SearchIndex>,
+    mut last_flush: Local<Option<Instant>>,
+) {
+    if !index.has_pending_writes() {
+        return;
+    }
+    if last_flush.is_some_and(|at| at.elapsed() < INDEX_FLUSH_INTERVAL) {
+        return;
+    }
+
+    // Flushing does not change search results, so avoid triggering change detection
+    if let Err(e) = index.bypass_change_detection().flush() {
+        error!("Failed to persist search index: {}", e);
+    }
+    *last_flush = Some(Instant::now());
+}
+
 /// Real-time search state for blazing-fast reactive search
 /// Zero-allocation state management with optimized string handling
 #[derive(Resource, Debug, Clone)]
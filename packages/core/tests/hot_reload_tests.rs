@@ -0,0 +1,53 @@
+use std::fs;
+
+use action_items_core::plugins::hot_reload::plugin_source;
+
+#[test]
+fn test_changes_map_to_their_plugin() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_path_buf();
+    let roots = vec![root.clone()];
+
+    let project = root.join("greeter");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"greeter\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        plugin_source(&roots, &project.join("src").join("lib.rs")),
+        Some(project.clone())
+    );
+    assert_eq!(
+        plugin_source(&roots, &root.join("clock.wasm")),
+        Some(root.join("clock.wasm"))
+    );
+    assert_eq!(
+        plugin_source(&roots, &root.join("notes").join("index.ts")),
+        Some(root.join("notes").join("index.ts"))
+    );
+}
+
+#[test]
+fn test_build_output_and_other_files_are_ignored() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().to_path_buf();
+    let roots = vec![root.clone()];
+
+    let project = root.join("greeter");
+    fs::create_dir_all(project.join("target")).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"greeter\"\n",
+    )
+    .unwrap();
+
+    let build_output = project.join("target").join("release").join("libgreeter.so");
+    assert_eq!(plugin_source(&roots, &build_output), None);
+    assert_eq!(plugin_source(&roots, &root.join("README.md")), None);
+
+    let outside = std::env::temp_dir().join("elsewhere.wasm");
+    assert_eq!(plugin_source(&roots, &outside), None);
+}
@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+
+use action_items_common::plugin_interface::PluginPermissions;
+use action_items_core::plugins::permissions::forward_permission_denials_system;
+use action_items_core::plugins::{HostAccess, PermissionDenied, PermissionPolicy};
+use bevy::prelude::*;
+
+fn policy(permissions: PluginPermissions) -> PermissionPolicy {
+    PermissionPolicy::new("test-plugin", permissions)
+}
+
+#[test]
+fn test_clipboard_requires_grant() {
+    let denied = policy(PluginPermissions::default());
+    let error = denied.check(HostAccess::ReadClipboard).unwrap_err();
+    assert_eq!(error.plugin_id, "test-plugin");
+    assert_eq!(error.permission, "read_clipboard");
+    assert_eq!(error.resource, None);
+
+    let granted = policy(PluginPermissions {
+        read_clipboard: true,
+        ..Default::default()
+    });
+    assert!(granted.check(HostAccess::ReadClipboard).is_ok());
+    assert!(granted.check(HostAccess::WriteClipboard).is_err());
+}
+
+#[test]
+fn test_network_host_patterns() {
+    let policy = policy(PluginPermissions {
+        network_hosts: vec![
+            "api.github.com".to_string(),
+            "*.example.com".to_string(),
+            "localhost:8080".to_string(),
+        ],
+        ..Default::default()
+    });
+    let check = |url| policy.check(HostAccess::Network { url }).is_ok();
+
+    assert!(check("https://api.github.com/repos"));
+    assert!(check("https://API.GitHub.com:443/repos"));
+    assert!(check("https://cdn.example.com/a.png"));
+    assert!(check("http://localhost:8080/health"));
+    assert!(!check("http://localhost:9000/health"));
+    assert!(!check("https://example.com/"));
+    assert!(!check("https://github.com/"));
+    assert!(!check("https://api.github.com.evil.io/"));
+    assert!(!check("file:///etc/passwd"));
+    assert!(!check("not a url"));
+
+    let any = PermissionPolicy::new(
+        "any",
+        PluginPermissions {
+            network_hosts: vec!["*".to_string()],
+            ..Default::default()
+        },
+    );
+    assert!(
+        any.check(HostAccess::Network {
+            url: "https://anything.dev"
+        })
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_file_path_prefixes() {
+    let root = PathBuf::from("/nonexistent-action-items-root");
+    let policy = policy(PluginPermissions {
+        read_files: vec![root.join("notes")],
+        write_files: vec![root.join("notes/drafts")],
+        ..Default::default()
+    });
+
+    assert!(
+        policy
+            .check(HostAccess::ReadFile(&root.join("notes/todo.md")))
+            .is_ok()
+    );
+    assert!(
+        policy
+            .check(HostAccess::ReadFile(&root.join("notes/./todo.md")))
+            .is_ok()
+    );
+    assert!(
+        policy
+            .check(HostAccess::ReadFile(&root.join("notes/a/../todo.md")))
+            .is_err()
+    );
+    assert!(
+        policy
+            .check(HostAccess::ReadFile(&root.join("notes/../secrets")))
+            .is_err()
+    );
+    assert!(
+        policy
+            .check(HostAccess::ReadFile(&root.join("notes-backup/todo.md")))
+            .is_err()
+    );
+    assert!(
+        policy
+            .check(HostAccess::ReadFile(Path::new("notes/todo.md")))
+            .is_err()
+    );
+
+    assert!(
+        policy
+            .check(HostAccess::WriteFile(&root.join("notes/drafts/a.md")))
+            .is_ok()
+    );
+    let error = policy
+        .check(HostAccess::WriteFile(&root.join("notes/todo.md")))
+        .unwrap_err();
+    assert_eq!(error.permission, "write_files");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinks_cannot_leave_granted_directories() {
+    let root = tempfile::tempdir().unwrap();
+    let granted = root.path().join("granted");
+    let secret = root.path().join("secret");
+    std::fs::create_dir_all(granted.join("inner")).unwrap();
+    std::fs::create_dir(&secret).unwrap();
+    std::fs::write(secret.join("key"), "hunter2").unwrap();
+    std::os::unix::fs::symlink(&secret, granted.join("link")).unwrap();
+    let policy = policy(PluginPermissions {
+        read_files: vec![granted.clone()],
+        ..Default::default()
+    });
+
+    // Through the link, `..` would be applied inside the secret directory's parent
+    assert!(
+        policy
+            .check_read(&granted.join("link/../secret/key"))
+            .is_err()
+    );
+    assert!(policy.check_read(&granted.join("link/key")).is_err());
+
+    let resolved = policy
+        .check_read(&granted.join("inner/./notes.md"))
+        .unwrap();
+    assert_eq!(
+        resolved,
+        granted.canonicalize().unwrap().join("inner/notes.md")
+    );
+}
+
+#[test]
+fn test_command_allow_list() {
+    let policy = policy(PluginPermissions {
+        execute_commands: vec!["git".to_string(), "/usr/local/bin/brew".to_string()],
+        ..Default::default()
+    });
+    let check = |command| policy.check(HostAccess::ExecuteCommand(command)).is_ok();
+
+    assert!(check("git status"));
+    // A bare grant never allows a program of that name at some path
+    assert!(!check("/usr/bin/git log"));
+    assert!(!check("/tmp/x/git status"));
+    assert!(check("/usr/local/bin/brew update"));
+    assert!(!check("brew update"));
+    assert!(!check("rm -rf /"));
+    assert!(!check(""));
+}
+
+#[test]
+fn test_open_grants_match_target_or_scheme() {
+    let policy = policy(PluginPermissions {
+        execute_commands: vec![
+            "mailto:".to_string(),
+            "slack://open".to_string(),
+            "file:".to_string(),
+        ],
+        ..Default::default()
+    });
+    let check = |target| policy.check(HostAccess::Open(target)).is_ok();
+
+    assert!(check("mailto:someone@example.com"));
+    assert!(check("MAILTO:someone@example.com"));
+    assert!(check("slack://open"));
+    assert!(!check("slack://channel?id=1"));
+    assert!(check("file:///home/user/notes.md"));
+    assert!(!check("vscode://file/home/user"));
+    assert!(!check("mailto"));
+
+    // Network grants never cover other schemes
+    let web_only = PermissionPolicy::new(
+        "test-plugin",
+        PluginPermissions {
+            network_hosts: vec!["*".to_string()],
+            ..Default::default()
+        },
+    );
+    let denial = web_only
+        .check(HostAccess::Open("vscode://file"))
+        .unwrap_err();
+    assert_eq!(denial.permission, "execute_commands");
+}
+
+#[test]
+fn test_environment_grants() {
+    let policy = policy(PluginPermissions {
+        environment_variables: vec!["HOME".to_string(), "LC_*".to_string()],
+        ..Default::default()
+    });
+
+    assert!(policy.environment_granted("HOME"));
+    assert!(policy.environment_granted("LC_ALL"));
+    assert!(!policy.environment_granted("AWS_SECRET_ACCESS_KEY"));
+    assert!(!policy.environment_granted("PATH"));
+}
+
+#[test]
+fn test_denial_json_and_audit_event() {
+    let policy = PermissionPolicy::new("audited-plugin", PluginPermissions::default());
+    let denial = policy
+        .check(HostAccess::Network {
+            url: "https://evil.io/upload",
+        })
+        .unwrap_err();
+
+    let json = denial.to_json();
+    assert_eq!(json["success"], false);
+    assert_eq!(json["error"], "permission_denied");
+    assert_eq!(json["permission"], "network_hosts");
+    assert_eq!(json["resource"], "https://evil.io/upload");
+
+    let mut app = App::new();
+    app.add_event::<PermissionDenied>()
+        .add_systems(Update, forward_permission_denials_system);
+    app.update();
+
+    let events = app.world().resource::<Events<PermissionDenied>>();
+    let mut reader = events.get_cursor();
+    assert!(reader.read(events).any(|event| *event == denial));
+}
@@ -0,0 +1,227 @@
+//! Signed plugin packages: building, verifying and installing
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use action_items_common::plugin_interface::PluginManifest;
+use action_items_core::plugins::package::{
+    PackageError, PackageInstaller, PackageTrust, PackageVerification, PluginPackage,
+};
+use ed25519_dalek::SigningKey;
+
+fn manifest() -> PluginManifest {
+    PluginManifest {
+        id: "hello".to_string(),
+        name: "Hello".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Says hello".to_string(),
+        author: "Example".to_string(),
+        license: "MIT".to_string(),
+        homepage: None,
+        repository: None,
+        icon: None,
+        categories: vec![],
+        keywords: vec![],
+        capabilities: Default::default(),
+        permissions: Default::default(),
+        resource_limits: Default::default(),
+        configuration: vec![],
+        preferences: vec![],
+        commands: vec![],
+        actions: vec![],
+        dependencies: HashMap::new(),
+        environment: HashMap::new(),
+        min_launcher_version: "1.0.0".to_string(),
+        max_launcher_version: None,
+        update_url: None,
+        changelog_url: None,
+    }
+}
+
+fn package() -> PluginPackage {
+    let files = BTreeMap::from([
+        ("plugin.js".to_string(), b"export default {}".to_vec()),
+        ("assets/icon.png".to_string(), b"png".to_vec()),
+    ]);
+    PluginPackage::new(manifest(), files).unwrap()
+}
+
+fn publisher_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn trusting_publisher() -> PackageTrust {
+    let mut trust = PackageTrust::default();
+    trust.trust("Example", &publisher_key().verifying_key());
+    trust
+}
+
+fn write_signed(path: &Path) {
+    let mut package = package();
+    package.sign("Example", &publisher_key());
+    package.write(path).unwrap();
+}
+
+#[test]
+fn test_package_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hello.aipkg");
+    write_signed(&path);
+
+    let read = PluginPackage::read(&path).unwrap();
+    assert_eq!(read.manifest.id, "hello");
+    assert_eq!(read.files, package().files);
+    assert_eq!(read.content_hash(), package().content_hash());
+    assert_eq!(read.signature.unwrap().publisher, "Example");
+}
+
+#[test]
+fn test_install_signed_package() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hello.aipkg");
+    write_signed(&path);
+
+    let installer = PackageInstaller::new(dir.path().join("packages"), trusting_publisher());
+    let installed = installer.install(&path).unwrap();
+    assert_eq!(installed.publisher.as_deref(), Some("Example"));
+
+    let plugin = installer.plugin_path("hello").unwrap();
+    assert!(plugin.join("manifest.json").is_file());
+    assert_eq!(
+        fs::read(plugin.join("assets").join("icon.png")).unwrap(),
+        b"png"
+    );
+    assert_eq!(installer.installed().unwrap(), [installed]);
+}
+
+#[test]
+fn test_tampered_package_is_refused() {
+    let mut package = package();
+    package.sign("Example", &publisher_key());
+    package
+        .files
+        .insert("plugin.js".to_string(), b"steal()".to_vec());
+
+    assert!(matches!(
+        trusting_publisher().verify(&package),
+        Err(PackageError::Tampered)
+    ));
+}
+
+#[test]
+fn test_unsigned_and_untrusted_packages_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let unsigned = dir.path().join("unsigned.aipkg");
+    package().write(&unsigned).unwrap();
+    let signed = dir.path().join("signed.aipkg");
+    write_signed(&signed);
+
+    let installer = PackageInstaller::new(dir.path().join("packages"), trusting_publisher());
+    assert!(matches!(
+        installer.install(&unsigned),
+        Err(PackageError::Unsigned)
+    ));
+
+    let installer = PackageInstaller::new(dir.path().join("packages"), PackageTrust::default());
+    assert!(matches!(
+        installer.install(&signed),
+        Err(PackageError::UntrustedPublisher(publisher)) if publisher == "Example"
+    ));
+    assert!(!installer.plugin_path("hello").unwrap().exists());
+}
+
+#[test]
+fn test_developer_mode_accepts_unsigned_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    let unsigned = dir.path().join("unsigned.aipkg");
+    package().write(&unsigned).unwrap();
+
+    let trust = PackageTrust {
+        developer_mode: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        trust.verify(&package()),
+        Ok(PackageVerification::DeveloperMode { .. })
+    ));
+
+    let installer = PackageInstaller::new(dir.path().join("packages"), trust);
+    let installed = installer.install(&unsigned).unwrap();
+    assert_eq!(installed.publisher, None);
+}
+
+#[test]
+fn test_uninstall() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hello.aipkg");
+    write_signed(&path);
+
+    let installer = PackageInstaller::new(dir.path().join("packages"), trusting_publisher());
+    installer.install(&path).unwrap();
+    installer.uninstall("hello").unwrap();
+
+    assert!(!installer.plugin_path("hello").unwrap().exists());
+    assert!(installer.installed().unwrap().is_empty());
+    assert!(matches!(
+        installer.uninstall("hello"),
+        Err(PackageError::NotInstalled(_))
+    ));
+}
+
+#[test]
+fn test_reserved_file_names_are_refused() {
+    for reserved in ["manifest.json", ".action-items-package.json"] {
+        let files = BTreeMap::from([(reserved.to_string(), b"{}".to_vec())]);
+        assert!(matches!(
+            PluginPackage::new(manifest(), files),
+            Err(PackageError::Malformed(_))
+        ));
+    }
+    // Only the top level is reserved
+    let files = BTreeMap::from([("assets/manifest.json".to_string(), b"{}".to_vec())]);
+    assert!(PluginPackage::new(manifest(), files).is_ok());
+}
+
+#[test]
+fn test_plugin_ids_must_stay_in_the_install_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let installer = PackageInstaller::new(dir.path().join("packages"), trusting_publisher());
+    for id in ["", ".", "..", "../packages", "a/b", ".staging"] {
+        assert!(matches!(
+            installer.plugin_path(id),
+            Err(PackageError::InvalidId(_))
+        ));
+        assert!(matches!(
+            installer.uninstall(id),
+            Err(PackageError::InvalidId(_))
+        ));
+    }
+
+    let mut package = PluginPackage::new(
+        PluginManifest {
+            id: "../escape".to_string(),
+            ..manifest()
+        },
+        BTreeMap::new(),
+    )
+    .unwrap();
+    package.sign("Example", &publisher_key());
+    let path = dir.path().join("escape.aipkg");
+    package.write(&path).unwrap();
+    assert!(matches!(
+        installer.install(&path),
+        Err(PackageError::InvalidId(_))
+    ));
+}
+
+#[test]
+fn test_trust_settings_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plugin-trust.json");
+    assert_eq!(PackageTrust::load(&path).unwrap(), PackageTrust::default());
+
+    let trust = trusting_publisher();
+    trust.save(&path).unwrap();
+    assert_eq!(PackageTrust::load(&path).unwrap(), trust);
+}
@@ -42,6 +42,7 @@ fn create_test_manifest() -> PluginManifest {
         max_launcher_version: None,
         update_url: None,
         changelog_url: None,
+        resource_limits: Default::default(),
     }
 }
 
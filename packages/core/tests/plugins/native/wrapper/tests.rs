@@ -117,6 +117,7 @@ fn test_native_plugin_wrapper_creation() -> Result<(), Box<dyn std::error::Error
         max_launcher_version: None,
         update_url: None,
         changelog_url: None,
+        resource_limits: Default::default(),
     };
 
     let mock_plugin = MockPlugin { manifest };
@@ -164,6 +165,7 @@ fn test_plugin_registration() -> Result<(), Box<dyn std::error::Error>> {
         max_launcher_version: None,
         update_url: None,
         changelog_url: None,
+        resource_limits: Default::default(),
     };
 
     let mock_plugin = MockPlugin { manifest };
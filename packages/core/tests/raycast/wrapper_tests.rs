@@ -20,7 +20,9 @@ fn create_test_extension() -> RaycastExtension {
             title: "Test Command".to_string(),
             description: Some("Test command description".to_string()),
             mode: "view".to_string(),
+            preferences: vec![],
         }],
+        preferences: vec![],
     }
 }
 
@@ -0,0 +1,121 @@
+//! Raycast API calls answered by the launcher
+
+use std::fs;
+
+use action_items_core::raycast::{RaycastCommandSession, RaycastExtension, RaycastLaunch};
+use action_items_ecs_preferences::ExtensionPreferences;
+use serde_json::json;
+
+const PACKAGE: &str = r#"{
+    "name": "weather",
+    "title": "Weather",
+    "preferences": [
+        {
+            "name": "units",
+            "title": "Units",
+            "type": "dropdown",
+            "required": false,
+            "default": "metric"
+        },
+        { "name": "apiKey", "title": "API Key", "type": "password", "required": true }
+    ],
+    "commands": [
+        {
+            "name": "forecast",
+            "title": "Forecast",
+            "mode": "view",
+            "preferences": [
+                { "name": "showWind", "title": "Show Wind", "type": "checkbox", "required": false }
+            ]
+        }
+    ]
+}"#;
+
+const FORECAST: &str = r#"
+const { jsx } = require("react/jsx-runtime");
+const { Detail, environment, getPreferenceValues } = require("@raycast/api");
+
+module.exports.default = function Command() {
+    const { units, showWind } = getPreferenceValues();
+    return jsx(Detail, {
+        markdown: `${units}, wind ${showWind ? "on" : "off"}`,
+        navigationTitle: environment.supportPath,
+    });
+};
+"#;
+
+fn weather_extension(dir: &tempfile::TempDir) -> RaycastExtension {
+    let path = dir.path().join("weather");
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join("package.json"), PACKAGE).unwrap();
+    RaycastExtension::from_path(&path).unwrap()
+}
+
+#[test]
+fn test_preferences_are_read_from_package_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let extension = weather_extension(&dir);
+
+    let names: Vec<&str> = extension
+        .preferences
+        .iter()
+        .map(|preference| preference.name.as_str())
+        .collect();
+    assert_eq!(names, ["units", "apiKey"]);
+    assert!(extension.preferences[1].required);
+
+    let show_wind = &extension.commands[0].preferences[0];
+    assert_eq!(show_wind.preference_type, "checkbox");
+    assert_eq!(show_wind.default, None);
+}
+
+#[test]
+fn test_stored_preferences_override_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let extension = weather_extension(&dir);
+    let command = &extension.commands[0];
+
+    let defaults = extension.preference_values(command, &Default::default());
+    assert_eq!(
+        serde_json::Value::Object(defaults),
+        json!({ "units": "metric", "showWind": false })
+    );
+
+    let path = dir.path().join("extension-preferences.json");
+    let mut stored = ExtensionPreferences::default();
+    stored.set(&extension.id, "units", json!("imperial"));
+    stored.set(&extension.id, "apiKey", json!("secret"));
+    stored.save(&path).unwrap();
+
+    let stored = ExtensionPreferences::load(&path);
+    let values = extension.preference_values(command, &stored.values(&extension.id));
+    assert_eq!(
+        serde_json::Value::Object(values),
+        json!({ "units": "imperial", "apiKey": "secret", "showWind": false })
+    );
+}
+
+#[tokio::test]
+async fn test_commands_see_preferences_and_environment() {
+    let dir = tempfile::tempdir().unwrap();
+    let extension = weather_extension(&dir);
+    let command = &extension.commands[0];
+
+    let launch = RaycastLaunch {
+        command_name: command.name.clone(),
+        extension_name: extension.name.clone(),
+        mode: command.mode.clone(),
+        preferences: extension.preference_values(command, &Default::default()),
+        support_path: dir.path().join("support"),
+        ..Default::default()
+    };
+    let mut session = RaycastCommandSession::new("raycast:weather").unwrap();
+    session.launch(FORECAST, &launch).await.unwrap();
+
+    let detail = session.view().unwrap();
+    assert_eq!(detail.prop("markdown"), Some(&json!("metric, wind off")));
+    assert_eq!(
+        detail.prop("navigationTitle"),
+        Some(&json!(dir.path().join("support")))
+    );
+}
@@ -32,7 +32,9 @@ mod tests {
                 title: "Test Command".to_string(),
                 description: Some("Test command".to_string()),
                 mode: "view".to_string(),
+                preferences: vec![],
             }],
+            preferences: vec![],
         };
 
         let component = RaycastPluginComponent {
@@ -50,8 +52,17 @@ mod tests {
         assert_eq!(component.commands[0], "test-command");
     }
 
-    #[tokio::test]
-    async fn test_raycast_command_execution() {
+    #[test]
+    fn test_raycast_command_execution() {
+        // Installed extensions run the bundle compiled for each command
+        let dir = tempfile::tempdir().expect("Failed to create extension directory");
+        std::fs::create_dir_all(dir.path().join("dist")).expect("Failed to create dist");
+        std::fs::write(
+            dir.path().join("dist").join("test-command.js"),
+            "module.exports.default = async function Command() {};",
+        )
+        .expect("Failed to write command bundle");
+
         // Create a test Raycast extension
         let extension = RaycastExtension {
             id: "test-extension".to_string(),
@@ -66,15 +77,17 @@ mod tests {
                 name: "test-command".to_string(),
                 title: "Test Command".to_string(),
                 description: Some("Test command".to_string()),
-                mode: "view".to_string(),
+                mode: "no-view".to_string(),
+                preferences: vec![],
             }],
+            preferences: vec![],
         };
 
         let component = RaycastPluginComponent {
             id: "test".to_string(),
             name: "Test".to_string(),
             description: "Test".to_string(),
-            path: PathBuf::from("/tmp"),
+            path: dir.path().to_path_buf(),
             commands: vec!["test-command".to_string()],
             extension,
         };
@@ -105,7 +118,9 @@ mod tests {
                 title: "Valid Command".to_string(),
                 description: Some("Valid command".to_string()),
                 mode: "view".to_string(),
+                preferences: vec![],
             }],
+            preferences: vec![],
         };
 
         let component = RaycastPluginComponent {
@@ -0,0 +1,263 @@
+//! Installing Raycast extensions from local sources
+
+use std::fs;
+use std::path::Path;
+
+use action_items_core::raycast::{
+    RaycastCommandSession, RaycastExtensionSource, RaycastLaunch, RaycastLoader,
+};
+
+const GREET: &str = r#"
+import { Detail } from "@raycast/api";
+import { greeting } from "./greeting";
+
+export default function Command() {
+    return <Detail markdown={greeting("Raycast")} />;
+}
+"#;
+
+const GREETING: &str = r#"
+export function greeting(name: string): string {
+    return `# Hello, ${name}!`;
+}
+"#;
+
+fn write_extension(dir: &Path, version: &str) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::create_dir_all(dir.join("assets")).unwrap();
+    fs::write(
+        dir.join("package.json"),
+        format!(
+            r#"{{
+                "name": "hello",
+                "title": "Hello",
+                "version": "{version}",
+                "commands": [{{ "name": "greet", "title": "Greet", "mode": "view" }}]
+            }}"#
+        ),
+    )
+    .unwrap();
+    fs::write(dir.join("src").join("greet.tsx"), GREET).unwrap();
+    fs::write(dir.join("src").join("greeting.ts"), GREETING).unwrap();
+    fs::write(dir.join("assets").join("icon.png"), b"png").unwrap();
+}
+
+fn write_tarball(source: &Path, tarball: &Path) {
+    let file = fs::File::create(tarball).unwrap();
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        file,
+        flate2::Compression::default(),
+    ));
+    builder.append_dir_all("package", source).unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+}
+
+#[test]
+fn test_install_from_directory_compiles_commands() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    write_extension(&source, "1.0.0");
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    let extension = loader
+        .install(RaycastExtensionSource::Directory {
+            path: source.clone(),
+        })
+        .unwrap();
+
+    assert_eq!(extension.id, "hello");
+    assert!(loader.command_bundle_path("hello", "greet").is_file());
+    assert!(
+        loader
+            .get_extension_path("hello")
+            .join("assets")
+            .join("icon.png")
+            .is_file()
+    );
+
+    let state = loader.sync_state();
+    let installed = &state.extensions["hello"];
+    assert_eq!(installed.version.as_deref(), Some("1.0.0"));
+    assert_eq!(installed.commands, ["greet"]);
+    assert_eq!(
+        installed.source,
+        RaycastExtensionSource::Directory { path: source }
+    );
+
+    let names: Vec<String> = loader
+        .list_extensions()
+        .unwrap()
+        .into_iter()
+        .map(|extension| extension.name)
+        .collect();
+    assert_eq!(names, ["hello"]);
+}
+
+#[tokio::test]
+async fn test_installed_bundles_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    write_extension(&source, "1.0.0");
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    let extension = loader
+        .install(RaycastExtensionSource::Directory { path: source })
+        .unwrap();
+    let bundle = fs::read_to_string(loader.command_bundle_path("hello", "greet")).unwrap();
+
+    let launch = RaycastLaunch {
+        command_name: "greet".to_string(),
+        extension_name: extension.name.clone(),
+        mode: "view".to_string(),
+        ..Default::default()
+    };
+    let mut session = RaycastCommandSession::new("raycast:hello").unwrap();
+    session.launch(&bundle, &launch).await.unwrap();
+
+    let detail = session.view().unwrap();
+    assert_eq!(detail.node_type, "Detail");
+    assert_eq!(
+        detail
+            .prop("markdown")
+            .and_then(|markdown| markdown.as_str()),
+        Some("# Hello, Raycast!")
+    );
+}
+
+#[test]
+fn test_install_from_tarball_and_upgrade() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    write_extension(&source, "1.0.0");
+    let tarball = dir.path().join("hello.tar.gz");
+    write_tarball(&source, &tarball);
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    loader
+        .install(RaycastExtensionSource::Tarball {
+            path: tarball.clone(),
+        })
+        .unwrap();
+    assert!(loader.command_bundle_path("hello", "greet").is_file());
+
+    write_extension(&source, "1.1.0");
+    write_tarball(&source, &tarball);
+    loader.upgrade("hello").unwrap();
+    assert_eq!(
+        loader.sync_state().extensions["hello"].version.as_deref(),
+        Some("1.1.0")
+    );
+}
+
+#[test]
+fn test_install_from_mirror() {
+    let dir = tempfile::tempdir().unwrap();
+    let mirror = dir.path().join("mirror");
+    write_extension(&mirror.join("extensions").join("hello"), "2.0.0");
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    let from_mirror = RaycastExtensionSource::Mirror {
+        name: "hello".to_string(),
+    };
+    assert!(loader.install(from_mirror.clone()).is_err());
+
+    loader.set_mirror(Some(&mirror)).unwrap();
+    loader.install(from_mirror).unwrap();
+    assert_eq!(
+        loader.sync_state().extensions["hello"].version.as_deref(),
+        Some("2.0.0")
+    );
+}
+
+#[test]
+fn test_failed_install_keeps_the_previous_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    write_extension(&source, "1.0.0");
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    loader
+        .install(RaycastExtensionSource::Directory {
+            path: source.clone(),
+        })
+        .unwrap();
+
+    fs::write(
+        source.join("src").join("greet.tsx"),
+        "export default function (",
+    )
+    .unwrap();
+    assert!(
+        loader
+            .install(RaycastExtensionSource::Directory { path: source })
+            .is_err()
+    );
+    assert!(loader.command_bundle_path("hello", "greet").is_file());
+    assert_eq!(
+        loader.sync_state().extensions["hello"].version.as_deref(),
+        Some("1.0.0")
+    );
+}
+
+#[test]
+fn test_uninstall() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    write_extension(&source, "1.0.0");
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    loader
+        .install(RaycastExtensionSource::Directory { path: source })
+        .unwrap();
+    loader.uninstall("hello").unwrap();
+
+    assert!(!loader.get_extension_path("hello").exists());
+    assert!(loader.sync_state().extensions.is_empty());
+    assert!(loader.uninstall("hello").is_err());
+}
+
+#[test]
+fn test_names_outside_the_extensions_directory_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let mirror = dir.path().join("mirror");
+    write_extension(&dir.path().join("outside"), "1.0.0");
+    fs::create_dir_all(mirror.join("extensions")).unwrap();
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    loader.set_mirror(Some(&mirror)).unwrap();
+    let escaping = RaycastExtensionSource::Mirror {
+        name: "../../outside".to_string(),
+    };
+    assert!(loader.install(escaping).is_err());
+    assert!(loader.sync_state().extensions.is_empty());
+
+    // Would otherwise remove the config directory's parent
+    let config = dir.path().join("config");
+    assert!(loader.uninstall("../../..").is_err());
+    assert!(loader.uninstall(".").is_err());
+    assert!(config.exists());
+}
+
+#[test]
+fn test_command_names_outside_the_extension_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("source");
+    write_extension(&source, "1.0.0");
+    // A command named after a file outside the extension would be read and compiled, and its
+    // bundle written beside that file
+    let victim = dir.path().join("victim");
+    fs::write(&victim, "export default function Command() {}").unwrap();
+    let package = fs::read_to_string(source.join("package.json"))
+        .unwrap()
+        .replace(r#""name": "greet""#, r#""name": "../../victim""#);
+    fs::write(source.join("package.json"), package).unwrap();
+
+    let loader = RaycastLoader::new(&dir.path().join("config"));
+    assert!(
+        loader
+            .install(RaycastExtensionSource::Directory { path: source })
+            .is_err()
+    );
+    assert!(!dir.path().join("victim.js").exists());
+    assert!(loader.sync_state().extensions.is_empty());
+}
@@ -0,0 +1,248 @@
+//! Sample Raycast extensions rendered end-to-end in the Deno runtime
+
+use action_items_core::raycast::{
+    RaycastCommandSession, RaycastLaunch, RaycastViewNode, RaycastViewUpdate,
+};
+
+const COUNTER: &str = r#"
+const { jsx, jsxs } = require("react/jsx-runtime");
+const { useEffect, useState } = require("react");
+const { Action, ActionPanel, List } = require("@raycast/api");
+
+function Ticker({ onCleanup }) {
+    useEffect(() => () => onCleanup(), []);
+    return jsx(List.Item, { title: "Ticker" });
+}
+
+function Command() {
+    const [count, setCount] = useState(0);
+    const [showTicker, setShowTicker] = useState(true);
+    const [cleanups, setCleanups] = useState(0);
+    return jsxs(List, {
+        searchBarPlaceholder: "Count",
+        children: [
+            jsx(List.Item, {
+                title: `Count: ${count}`,
+                actions: jsxs(ActionPanel, {
+                    children: [
+                        jsx(Action, { title: "Increment", onAction: () => setCount((c) => c + 1) }),
+                        jsx(Action, { title: "Hide Ticker", onAction: () => setShowTicker(false) }),
+                    ],
+                }),
+            }),
+            showTicker ? jsx(Ticker, { onCleanup: () => setCleanups((c) => c + 1) }) : null,
+            jsx(List.Item, { title: `Cleanups: ${cleanups}` }),
+        ],
+    });
+}
+
+module.exports = { default: Command };
+"#;
+
+const ARTICLES: &str = r#"
+const { jsx } = require("react/jsx-runtime");
+const { Action, ActionPanel, Detail, List, useNavigation } = require("@raycast/api");
+const { usePromise } = require("@raycast/utils");
+
+function Article({ id }) {
+    const { pop } = useNavigation();
+    return jsx(Detail, {
+        markdown: `# Article ${id}`,
+        actions: jsx(ActionPanel, { children: jsx(Action, { title: "Back", onAction: pop }) }),
+    });
+}
+
+function Command() {
+    const { data, isLoading } = usePromise(
+        async (count) => Array.from({ length: count }, (_, i) => `Article ${i + 1}`),
+        [2],
+    );
+    return jsx(List, {
+        isLoading,
+        children: (data ?? []).map((title, i) =>
+            jsx(
+                List.Item,
+                {
+                    title,
+                    actions: jsx(ActionPanel, {
+                        children: jsx(Action.Push, {
+                            title: "Open",
+                            target: jsx(Article, { id: i + 1 }),
+                        }),
+                    }),
+                },
+                title,
+            ),
+        ),
+    });
+}
+
+module.exports = { default: Command };
+"#;
+
+const THEMED_FORM: &str = r#"
+const React = require("react");
+const { Action, ActionPanel, Form } = require("@raycast/api");
+
+const Theme = React.createContext("light");
+
+function Field() {
+    const theme = React.useContext(Theme);
+    const [value, dispatch] = React.useReducer((state, action) => state + action, "");
+    return React.createElement(Form.TextField, {
+        id: "name",
+        title: `Name (${theme})`,
+        value,
+        onChange: (next) => dispatch(next),
+    });
+}
+
+exports.default = function Command() {
+    return React.createElement(
+        Theme.Provider,
+        { value: "dark" },
+        React.createElement(
+            Form,
+            {
+                actions: React.createElement(
+                    ActionPanel,
+                    null,
+                    React.createElement(Action.SubmitForm, { title: "Save", onSubmit: () => {} }),
+                ),
+            },
+            React.createElement(Field),
+        ),
+    );
+};
+"#;
+
+const BROKEN: &str = r#"
+const { jsx } = require("react/jsx-runtime");
+const { Detail } = require("@raycast/api");
+
+module.exports.default = function Command() {
+    throw new Error("boom");
+    return jsx(Detail, { markdown: "unreachable" });
+};
+"#;
+
+fn launch(command_name: &str) -> RaycastLaunch {
+    RaycastLaunch {
+        command_name: command_name.to_string(),
+        extension_name: "samples".to_string(),
+        mode: "view".to_string(),
+        ..Default::default()
+    }
+}
+
+fn title(node: &RaycastViewNode) -> &str {
+    node.prop("title")
+        .and_then(|title| title.as_str())
+        .unwrap_or_default()
+}
+
+fn action_handler(item: &RaycastViewNode, index: usize, prop: &str) -> String {
+    let panel = item.slot("actions").expect("item has an action panel");
+    panel.children[index]
+        .handler(prop)
+        .expect("action has a handler")
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_state_updates_are_diffed_into_the_view() {
+    let mut session = RaycastCommandSession::new("raycast:samples").unwrap();
+    let updates = session.launch(COUNTER, &launch("counter")).await.unwrap();
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(
+        &updates[0],
+        RaycastViewUpdate::Replace { path, node: Some(_) } if path.is_empty()
+    ));
+
+    let view = session.view().unwrap();
+    assert_eq!(view.node_type, "List");
+    let titles: Vec<&str> = view.children.iter().map(title).collect();
+    assert_eq!(titles, ["Count: 0", "Ticker", "Cleanups: 0"]);
+
+    let increment = action_handler(&view.children[0], 0, "onAction");
+    let updates = session.dispatch(&increment, &[]).await.unwrap();
+    assert_eq!(updates.len(), 1);
+    assert!(matches!(&updates[0], RaycastViewUpdate::SetProps { path, .. } if path == &[0]));
+    assert_eq!(title(&session.view().unwrap().children[0]), "Count: 1");
+}
+
+#[tokio::test]
+async fn test_effect_cleanup_runs_on_unmount() {
+    let mut session = RaycastCommandSession::new("raycast:samples").unwrap();
+    session.launch(COUNTER, &launch("counter")).await.unwrap();
+
+    let hide = action_handler(&session.view().unwrap().children[0], 1, "onAction");
+    session.dispatch(&hide, &[]).await.unwrap();
+
+    let titles: Vec<&str> = session.view().unwrap().children.iter().map(title).collect();
+    assert_eq!(titles, ["Count: 0", "Cleanups: 1"]);
+}
+
+#[tokio::test]
+async fn test_promises_and_navigation() {
+    let mut session = RaycastCommandSession::new("raycast:samples").unwrap();
+    session.launch(ARTICLES, &launch("articles")).await.unwrap();
+
+    let list = session.view().unwrap().clone();
+    assert_eq!(
+        list.prop("isLoading"),
+        Some(&serde_json::Value::Bool(false))
+    );
+    let titles: Vec<&str> = list.children.iter().map(title).collect();
+    assert_eq!(titles, ["Article 1", "Article 2"]);
+
+    let open = action_handler(&list.children[0], 0, "target");
+    session.dispatch(&open, &[]).await.unwrap();
+    let detail = session.view().unwrap();
+    assert_eq!(detail.node_type, "Detail");
+    assert_eq!(
+        detail
+            .prop("markdown")
+            .and_then(|markdown| markdown.as_str()),
+        Some("# Article 1")
+    );
+
+    let back = detail
+        .slot("actions")
+        .and_then(|panel| panel.children[0].handler("onAction").map(str::to_string))
+        .unwrap();
+    session.dispatch(&back, &[]).await.unwrap();
+    // The list comes back with its state instead of loading again
+    assert_eq!(session.view(), Some(&list));
+}
+
+#[tokio::test]
+async fn test_context_reducer_and_form_input() {
+    let mut session = RaycastCommandSession::new("raycast:samples").unwrap();
+    session.launch(THEMED_FORM, &launch("form")).await.unwrap();
+
+    let form = session.view().unwrap();
+    assert_eq!(form.node_type, "Form");
+    let field = &form.children[0];
+    assert_eq!(field.node_type, "Form.TextField");
+    assert_eq!(title(field), "Name (dark)");
+
+    let on_change = field.handler("onChange").unwrap().to_string();
+    session
+        .dispatch(&on_change, &[serde_json::json!("Ada")])
+        .await
+        .unwrap();
+    let field = &session.view().unwrap().children[0];
+    assert_eq!(field.prop("value"), Some(&serde_json::json!("Ada")));
+}
+
+#[tokio::test]
+async fn test_render_errors_are_reported() {
+    let mut session = RaycastCommandSession::new("raycast:samples").unwrap();
+    let updates = session.launch(BROKEN, &launch("broken")).await.unwrap();
+
+    assert!(updates.iter().any(
+        |update| matches!(update, RaycastViewUpdate::Error { message } if message.contains("boom"))
+    ));
+    assert!(session.view().is_none());
+}
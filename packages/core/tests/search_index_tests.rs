@@ -0,0 +1,104 @@
+use action_items_core::search::{SearchIndex, SearchItem, SearchItemType};
+
+fn app(id: &str, title: &str) -> SearchItem {
+    SearchItem::new(
+        id.to_string(),
+        title.to_string(),
+        format!("Application: {title}"),
+        SearchItemType::Application,
+    )
+}
+
+#[test]
+fn test_prefix_and_infix_matching() {
+    let mut index = SearchIndex::new();
+    index.add_item(app("app_vscode", "Visual Studio Code"));
+    index.add_item(app("app_xcode", "Xcode"));
+    index.add_item(app("app_safari", "Safari"));
+
+    let prefix: Vec<String> = index.search("vis stu").into_iter().map(|i| i.id).collect();
+    assert_eq!(prefix, vec!["app_vscode"]);
+
+    let infix: Vec<String> = index.search("code").into_iter().map(|i| i.id).collect();
+    assert!(infix.contains(&"app_vscode".to_string()));
+    assert!(infix.contains(&"app_xcode".to_string()));
+    assert!(!infix.contains(&"app_safari".to_string()));
+}
+
+#[test]
+fn test_exact_title_ranks_first() {
+    let mut index = SearchIndex::new();
+    index.add_item(app("app_notes", "Notes"));
+    index.add_item(app("app_notes_plus", "Notes Plus"));
+
+    let results = index.search("notes");
+    assert_eq!(results[0].id, "app_notes");
+    assert!(results[0].score > results[1].score);
+}
+
+#[test]
+fn test_remove_item_drops_postings() {
+    let mut index = SearchIndex::new();
+    index.add_item(app("app_terminal", "Terminal"));
+    assert_eq!(index.search("term").len(), 1);
+
+    index.remove_item("app_terminal");
+    assert!(index.search("term").is_empty());
+}
+
+#[test]
+fn test_persistent_index_survives_reopen() {
+    let dir = tempfile::tempdir().expect("temp dir");
+
+    {
+        let mut index = SearchIndex::open(dir.path()).expect("open index");
+        index.add_item(app("app_firefox", "Firefox"));
+        index.add_item(app("app_finder", "Finder"));
+        index.remove_item("app_finder");
+        index.flush().expect("flush index");
+    }
+
+    let reopened = SearchIndex::open(dir.path()).expect("reopen index");
+    assert_eq!(reopened.len(), 1);
+    assert_eq!(reopened.search("fire")[0].id, "app_firefox");
+    assert!(reopened.search("finder").is_empty());
+}
+
+#[test]
+fn test_compaction_leaves_empty_journal() {
+    use action_items_core::search::inverted_index::COMPACTION_THRESHOLD;
+
+    let dir = tempfile::tempdir().expect("temp dir");
+
+    {
+        let mut index = SearchIndex::open(dir.path()).expect("open index");
+        for i in 0..COMPACTION_THRESHOLD {
+            index.add_item(app(&format!("app_{i}"), &format!("App {i}")));
+        }
+        index.flush().expect("compact index");
+        index.remove_item("app_0");
+        index.flush().expect("flush index");
+    }
+
+    let journal = std::fs::read_to_string(dir.path().join("journal.jsonl")).expect("journal");
+    assert_eq!(journal.lines().count(), 1);
+
+    let reopened = SearchIndex::open(dir.path()).expect("reopen index");
+    assert_eq!(reopened.len(), COMPACTION_THRESHOLD - 1);
+    assert!(reopened.get_item("app_0").is_none());
+}
+
+#[test]
+fn test_fuzzy_candidates_share_a_word_initial() {
+    let mut index = SearchIndex::new();
+    index.add_item(app("app_vscode", "Visual Studio Code"));
+    index.add_item(app("app_safari", "Safari"));
+
+    let ids: Vec<&str> = index
+        .fuzzy_candidates("vsc", 10)
+        .into_iter()
+        .map(|item| item.id.as_str())
+        .collect();
+    assert_eq!(ids, ["app_vscode"]);
+    assert_eq!(index.fuzzy_candidates("s", 1).len(), 1);
+}
@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+use action_items_common::plugin_interface::ResourceLimits;
+use action_items_core::plugins::extism::limits::{
+    DEFAULT_FUEL, DEFAULT_MEMORY_PAGES, DEFAULT_TIMEOUT, LimitKind, LimitTrip, MAX_FUEL,
+    MAX_MEMORY_PAGES, MAX_TIMEOUT, WasmLimits, handle_wasm_limit_trips_system, report_limit_trip,
+};
+use action_items_native::PluginErrorEvent;
+use bevy::prelude::*;
+use ecs_service_bridge::components::{PluginComponent, PluginStatus, PluginType};
+use ecs_service_bridge::types::TimeStamp;
+
+#[test]
+fn test_unset_limits_use_defaults() {
+    let limits = WasmLimits::from_requested(&ResourceLimits::default());
+    assert_eq!(limits, WasmLimits::default());
+    assert_eq!(limits.fuel, DEFAULT_FUEL);
+    assert_eq!(limits.memory_pages, DEFAULT_MEMORY_PAGES);
+    assert_eq!(limits.timeout, DEFAULT_TIMEOUT);
+}
+
+#[test]
+fn test_requested_limits_are_clamped_to_caps() {
+    let lowered = WasmLimits::from_requested(&ResourceLimits {
+        fuel: Some(1_000),
+        memory_pages: Some(16),
+        timeout_ms: Some(250),
+    });
+    assert_eq!(lowered.fuel, 1_000);
+    assert_eq!(lowered.memory_pages, 16);
+    assert_eq!(lowered.timeout, Duration::from_millis(250));
+
+    let raised = WasmLimits::from_requested(&ResourceLimits {
+        fuel: Some(u64::MAX),
+        memory_pages: Some(u32::MAX),
+        timeout_ms: Some(u64::MAX),
+    });
+    assert_eq!(raised.fuel, MAX_FUEL);
+    assert_eq!(raised.memory_pages, MAX_MEMORY_PAGES);
+    assert_eq!(raised.timeout, MAX_TIMEOUT);
+}
+
+/// Module exporting `spin`, which loops forever
+///
+/// `(module (func (export "spin") (result i32) (loop (br 0)) (i32.const 0)))`
+const SPIN_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f, // type: () -> i32
+    0x03, 0x02, 0x01, 0x00, // function 0 has type 0
+    0x07, 0x08, 0x01, 0x04, b's', b'p', b'i', b'n', 0x00, 0x00, // export "spin"
+    0x0a, 0x0b, 0x01, 0x09, 0x00, 0x03, 0x40, 0x0c, 0x00, 0x0b, 0x41, 0x00, 0x0b, // code
+];
+
+fn spin(limits: WasmLimits) -> (extism::Error, Duration) {
+    let manifest = extism::Manifest::new([extism::Wasm::data(SPIN_WASM.to_vec())]);
+    let mut plugin = limits.build_plugin(manifest, Vec::new()).expect("build plugin");
+    let started = Instant::now();
+    let error = plugin
+        .call::<&str, &str>("spin", "")
+        .expect_err("spin never returns");
+    (error, started.elapsed())
+}
+
+#[test]
+fn test_classify_fuel_exhaustion() {
+    let limits = WasmLimits {
+        fuel: 100_000,
+        ..Default::default()
+    };
+    let (error, elapsed) = spin(limits);
+    assert!(elapsed < limits.timeout);
+    assert_eq!(limits.classify(&error, elapsed), Some(LimitKind::Fuel));
+}
+
+#[test]
+fn test_classify_timeout() {
+    let limits = WasmLimits {
+        fuel: MAX_FUEL,
+        timeout: Duration::from_millis(100),
+        ..Default::default()
+    };
+    let (error, elapsed) = spin(limits);
+    assert_eq!(limits.classify(&error, elapsed), Some(LimitKind::WallClock));
+}
+
+#[test]
+fn test_other_failures_are_not_limit_trips() {
+    let limits = WasmLimits::default();
+    let error = extism::Error::msg("plugin returned an error");
+
+    assert_eq!(limits.classify(&error, Duration::from_millis(1)), None);
+    assert_eq!(
+        limits.classify(&error, DEFAULT_TIMEOUT),
+        Some(LimitKind::WallClock)
+    );
+}
+
+#[test]
+fn test_limit_trip_degrades_plugin() {
+    let mut app = App::new();
+    app.add_event::<PluginErrorEvent>()
+        .add_systems(Update, handle_wasm_limit_trips_system);
+    let entity = app
+        .world_mut()
+        .spawn(PluginComponent {
+            plugin_id: "runaway".to_string(),
+            name: "Runaway".to_string(),
+            version: "1.0.0".to_string(),
+            description: String::new(),
+            status: PluginStatus::Active,
+            plugin_type: PluginType::Wasm,
+            author: None,
+            has_config: false,
+            registration_time: TimeStamp::now(),
+            last_heartbeat: None,
+        })
+        .id();
+
+    report_limit_trip(LimitTrip {
+        plugin_id: "runaway".to_string(),
+        kind: LimitKind::Fuel,
+        message: "all fuel consumed by WebAssembly".to_string(),
+    });
+    app.update();
+
+    let status = &app.world().get::<PluginComponent>(entity).unwrap().status;
+    assert!(matches!(status, PluginStatus::Degraded(reason) if reason.contains("fuel")));
+
+    let events = app.world().resource::<Events<PluginErrorEvent>>();
+    let mut reader = events.get_cursor();
+    assert!(
+        reader
+            .read(events)
+            .any(|event| event.plugin_id == "runaway" && event.recoverable)
+    );
+}
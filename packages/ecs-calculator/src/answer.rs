@@ -0,0 +1,159 @@
+//! Turning launcher queries into answers
+//!
+//! A query is either an expression (`23*1.19`), a unit conversion (`5 km in mi`) or a base
+//! conversion (`255 in hex`). Conversions are split at the last ` in `, ` to `, ` as `, `->`
+//! or `=`, so `5 in in cm` converts inches.
+
+use crate::expression::{self, Radix, format_radix};
+use crate::types::CalculatorError;
+use crate::units::{self, Dimension, Unit};
+
+/// Significant digits shown in answers
+const SIGNIFICANT_DIGITS: i32 = 12;
+/// Conversion separators; words need surrounding spaces
+const SEPARATORS: &[&str] = &[" in ", " to ", " as ", "->", "→", "="];
+
+/// What an answer was computed from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnswerKind {
+    Arithmetic,
+    Conversion(Dimension),
+    Base(Radix),
+}
+
+/// Result of a calculation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub kind: AnswerKind,
+    /// Text copied to the clipboard: the number without unit or grouping
+    pub value: String,
+    /// Text shown in the launcher, with the unit of a conversion
+    pub display: String,
+}
+
+impl Answer {
+    /// Short description of the calculation for the result row
+    pub fn description(&self) -> String {
+        match self.kind {
+            AnswerKind::Arithmetic => "Calculator".to_string(),
+            AnswerKind::Conversion(dimension) => format!("{} conversion", dimension.name()),
+            AnswerKind::Base(radix) => radix.name().to_string(),
+        }
+    }
+}
+
+/// Answer a launcher query if it is a calculation
+///
+/// Queries without digits and plain numbers are not treated as calculations, so typing `e`
+/// or `42` does not produce an answer row.
+pub fn calculate(query: &str) -> Option<Answer> {
+    let query = query.trim();
+    if !query.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if expression::parse(query).is_ok_and(|expr| expr.is_plain_number()) {
+        return None;
+    }
+    evaluate_query(query).ok()
+}
+
+/// Evaluate a query as a conversion or an expression
+pub fn evaluate_query(query: &str) -> Result<Answer, CalculatorError> {
+    let query = query.trim();
+    match split_conversion(query) {
+        Some((source, target)) => convert(source, target),
+        None => {
+            let value = expression::evaluate(query)?;
+            let value = format_number(value);
+            Ok(Answer {
+                kind: AnswerKind::Arithmetic,
+                display: value.clone(),
+                value,
+            })
+        },
+    }
+}
+
+/// Format a number with up to [`SIGNIFICANT_DIGITS`] digits and no trailing zeros
+///
+/// Very large and very small magnitudes use scientific notation.
+pub fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    if !(-7..15).contains(&magnitude) {
+        let text = format!("{:.*e}", (SIGNIFICANT_DIGITS - 1) as usize, value);
+        let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+        return format!("{}e{exponent}", trim_zeros(mantissa));
+    }
+    let decimals = (SIGNIFICANT_DIGITS - 1 - magnitude).max(0) as usize;
+    let text = format!("{value:.decimals$}");
+    match trim_zeros(&text) {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+fn trim_zeros(text: &str) -> &str {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        text
+    }
+}
+
+fn split_conversion(query: &str) -> Option<(&str, &str)> {
+    // ASCII lowercasing keeps byte offsets valid for the original query
+    let lowered = query.to_ascii_lowercase();
+    let (at, separator) = SEPARATORS
+        .iter()
+        .filter_map(|separator| lowered.rfind(separator).map(|at| (at, separator)))
+        .max_by_key(|(at, _)| *at)?;
+    Some((&query[..at], &query[at + separator.len()..]))
+}
+
+fn convert(source: &str, target: &str) -> Result<Answer, CalculatorError> {
+    let target = target.trim();
+    if let Some(radix) = Radix::from_name(target) {
+        let value = expression::evaluate(source)?;
+        let display = format_radix(value, radix)?;
+        return Ok(Answer {
+            kind: AnswerKind::Base(radix),
+            value: display.clone(),
+            display,
+        });
+    }
+
+    let to =
+        units::find_unit(target).ok_or_else(|| CalculatorError::UnknownUnit(target.to_string()))?;
+    let (quantity, from) = split_quantity(source)
+        .ok_or_else(|| CalculatorError::UnknownUnit(source.trim().to_string()))?;
+    let amount = if quantity.trim().is_empty() {
+        1.0
+    } else {
+        expression::evaluate(quantity)?
+    };
+    let value = format_number(units::convert(amount, from, to)?);
+    Ok(Answer {
+        kind: AnswerKind::Conversion(to.dimension),
+        display: format!("{value} {}", to.symbol),
+        value,
+    })
+}
+
+/// Split `5 km` or `(2 + 3)km` into the quantity expression and its unit
+///
+/// The longest matching unit wins, so multi-word units like `nautical miles` are found.
+fn split_quantity(source: &str) -> Option<(&str, &'static Unit)> {
+    let source = source.trim_end();
+    source
+        .char_indices()
+        .filter(|(at, _)| {
+            source[..*at]
+                .chars()
+                .next_back()
+                .is_none_or(|c| c.is_whitespace() || c.is_ascii_digit() || c == ')' || c == '.')
+        })
+        .find_map(|(at, _)| units::find_unit(&source[at..]).map(|unit| (&source[..at], unit)))
+}
@@ -0,0 +1,12 @@
+//! Calculator notification events
+
+use bevy::prelude::*;
+
+use crate::types::CalculatorError;
+
+/// An answer chosen in the launcher was copied to the clipboard
+#[derive(Event, Debug, Clone)]
+pub struct CalculationCopied {
+    pub value: String,
+    pub result: Result<(), CalculatorError>,
+}
@@ -0,0 +1,645 @@
+//! Arithmetic expressions
+//!
+//! Supports `+ - * / ^` (also `×`, `x`, `÷` and `**`) with the usual precedence (`^` is
+//! right-associative and binds tighter than unary minus), `mod`, parentheses, implicit
+//! multiplication (`2pi`, `3(4 + 1)`), factorials (`5!`), the constants `pi`, `e` and `tau`,
+//! functions such as `sqrt` and `log`, and `0x`, `0b` and `0o` integer literals.
+//!
+//! `%` after a number is a percentage: `50%` is `0.5`, `200 + 10%` adds ten percent of 200
+//! and `15% of 80` is `12`. Followed by another operand, `%` is the remainder operator.
+//!
+//! Expressions nest at most [`MAX_DEPTH`] levels deep, so parsing, evaluating and dropping
+//! them cannot overflow the stack whatever is typed into the launcher.
+
+use std::f64::consts;
+
+use crate::types::CalculatorError;
+
+/// Deepest nesting of operators, parentheses and function calls in an expression
+pub const MAX_DEPTH: usize = 64;
+
+/// Base of a number literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    Decimal,
+    Hexadecimal,
+    Binary,
+    Octal,
+}
+
+impl Radix {
+    /// Parse a conversion target such as `hex` or `binary`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dec" | "decimal" => Some(Radix::Decimal),
+            "hex" | "hexadecimal" => Some(Radix::Hexadecimal),
+            "bin" | "binary" => Some(Radix::Binary),
+            "oct" | "octal" => Some(Radix::Octal),
+            _ => None,
+        }
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Radix::Decimal => "",
+            Radix::Hexadecimal => "0x",
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Radix::Decimal => "Decimal",
+            Radix::Hexadecimal => "Hexadecimal",
+            Radix::Binary => "Binary",
+            Radix::Octal => "Octal",
+        }
+    }
+
+    fn base(&self) -> u32 {
+        match self {
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+    /// `15% of 80`
+    Of,
+}
+
+/// Built-in functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Sqrt,
+    Cbrt,
+    Abs,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Ln,
+    /// Base 10, or the base given as second argument
+    Log,
+    Log2,
+    Exp,
+    Floor,
+    Ceil,
+    Round,
+    Trunc,
+    Min,
+    Max,
+    Pow,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sqrt" => Function::Sqrt,
+            "cbrt" => Function::Cbrt,
+            "abs" => Function::Abs,
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            "tan" => Function::Tan,
+            "asin" | "arcsin" => Function::Asin,
+            "acos" | "arccos" => Function::Acos,
+            "atan" | "arctan" => Function::Atan,
+            "ln" => Function::Ln,
+            "log" | "log10" => Function::Log,
+            "log2" => Function::Log2,
+            "exp" => Function::Exp,
+            "floor" => Function::Floor,
+            "ceil" => Function::Ceil,
+            "round" => Function::Round,
+            "trunc" => Function::Trunc,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "pow" => Function::Pow,
+            _ => return None,
+        })
+    }
+
+    /// Accepted argument counts, as shown in errors
+    fn arity(&self) -> (usize, usize, &'static str) {
+        match self {
+            Function::Log => (1, 2, "1 or 2"),
+            Function::Pow => (2, 2, "2"),
+            Function::Min | Function::Max => (1, usize::MAX, "at least 1"),
+            _ => (1, 1, "1"),
+        }
+    }
+}
+
+/// Parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64, Radix),
+    Negate(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Percent(Box<Expr>),
+    Factorial(Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    /// Whether the expression is a single decimal number, which is not worth an answer
+    pub fn is_plain_number(&self) -> bool {
+        matches!(self, Expr::Number(_, Radix::Decimal))
+    }
+
+    /// Evaluate the expression
+    ///
+    /// Recurses once per level, which [`parse`] bounds by [`MAX_DEPTH`].
+    pub fn evaluate(&self) -> Result<f64, CalculatorError> {
+        let value = match self {
+            Expr::Number(value, _) => *value,
+            Expr::Negate(inner) => -inner.evaluate()?,
+            Expr::Percent(inner) => inner.evaluate()? / 100.0,
+            Expr::Factorial(inner) => factorial(inner.evaluate()?)?,
+            Expr::Binary(op, lhs, rhs) => {
+                let a = lhs.evaluate()?;
+                // `a + p%` and `a - p%` change `a` by p percent of itself
+                if let (BinaryOp::Add | BinaryOp::Subtract, Expr::Percent(p)) = (op, &**rhs) {
+                    let change = a * p.evaluate()? / 100.0;
+                    return finite(if *op == BinaryOp::Add {
+                        a + change
+                    } else {
+                        a - change
+                    });
+                }
+                let b = rhs.evaluate()?;
+                match op {
+                    BinaryOp::Add => a + b,
+                    BinaryOp::Subtract => a - b,
+                    BinaryOp::Multiply | BinaryOp::Of => a * b,
+                    BinaryOp::Divide if b == 0.0 => return Err(CalculatorError::DivisionByZero),
+                    BinaryOp::Divide => a / b,
+                    BinaryOp::Remainder if b == 0.0 => {
+                        return Err(CalculatorError::DivisionByZero);
+                    },
+                    BinaryOp::Remainder => a % b,
+                    BinaryOp::Power => a.powf(b),
+                }
+            },
+            Expr::Call(function, args) => {
+                let args = args
+                    .iter()
+                    .map(Expr::evaluate)
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(*function, &args)?
+            },
+        };
+        finite(value)
+    }
+}
+
+/// Parse and evaluate an expression
+pub fn evaluate(input: &str) -> Result<f64, CalculatorError> {
+    parse(input)?.evaluate()
+}
+
+/// Parse an expression, failing with [`CalculatorError::TooDeep`] past [`MAX_DEPTH`] levels
+pub fn parse(input: &str) -> Result<Expr, CalculatorError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+        nesting: 0,
+    };
+    let node = parser.expression(0)?;
+    match parser.peek() {
+        None => Ok(node.expr),
+        Some(token) => Err(CalculatorError::UnexpectedToken(token.to_string())),
+    }
+}
+
+/// Format an integer value in a base, e.g. `0xFF`
+pub fn format_radix(value: f64, radix: Radix) -> Result<String, CalculatorError> {
+    if value.fract() != 0.0 || value.abs() >= 2f64.powi(64) {
+        return Err(CalculatorError::NotAnInteger(value.to_string()));
+    }
+    let magnitude = value.abs() as u64;
+    let digits = match radix {
+        Radix::Decimal => magnitude.to_string(),
+        Radix::Hexadecimal => format!("{magnitude:X}"),
+        Radix::Binary => format!("{magnitude:b}"),
+        Radix::Octal => format!("{magnitude:o}"),
+    };
+    let sign = if value < 0.0 { "-" } else { "" };
+    Ok(format!("{sign}{}{digits}", radix.prefix()))
+}
+
+const PRECEDENCE_ADD: u8 = 1;
+const PRECEDENCE_MULTIPLY: u8 = 2;
+const PRECEDENCE_POWER: u8 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64, Radix),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    Percent,
+    Bang,
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(value, _) => write!(f, "{value}"),
+            Token::Ident(name) => write!(f, "{name}"),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Star => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::Caret => write!(f, "^"),
+            Token::Percent => write!(f, "%"),
+            Token::Bang => write!(f, "!"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, CalculatorError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            let start = i;
+            let radix = match (c, next.map(|n| n.to_ascii_lowercase())) {
+                ('0', Some('x')) => Radix::Hexadecimal,
+                ('0', Some('b')) => Radix::Binary,
+                ('0', Some('o')) => Radix::Octal,
+                _ => Radix::Decimal,
+            };
+            let value = if radix == Radix::Decimal {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                // Exponent, only when digits follow so `2e` stays `2 * e`
+                if matches!(chars.get(i), Some('e' | 'E')) {
+                    let digits_at = match chars.get(i + 1) {
+                        Some('+' | '-') => i + 2,
+                        _ => i + 1,
+                    };
+                    if chars.get(digits_at).is_some_and(|d| d.is_ascii_digit()) {
+                        i = digits_at;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..i].iter().collect();
+                text.parse::<f64>()
+                    .map_err(|_| CalculatorError::InvalidNumber(text))?
+            } else {
+                i += 2;
+                let digits_start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let digits: String = chars[digits_start..i]
+                    .iter()
+                    .filter(|d| **d != '_')
+                    .collect();
+                u128::from_str_radix(&digits, radix.base())
+                    .map_err(|_| CalculatorError::InvalidNumber(chars[start..i].iter().collect()))?
+                    as f64
+            };
+            tokens.push(Token::Number(value, radix));
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(
+                chars[start..i].iter().collect::<String>().to_lowercase(),
+            ));
+            continue;
+        }
+
+        let token = match c {
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' if next == Some('*') => {
+                i += 1;
+                Token::Caret
+            },
+            '*' | '×' | '·' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '^' => Token::Caret,
+            '%' => Token::Percent,
+            '!' => Token::Bang,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            other => return Err(CalculatorError::UnexpectedToken(other.to_string())),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// A parsed expression with the depth of its tree
+struct Node {
+    expr: Expr,
+    depth: usize,
+}
+
+impl Node {
+    fn leaf(expr: Expr) -> Self {
+        Self { expr, depth: 1 }
+    }
+
+    /// An expression one level above children at most `child_depth` deep
+    fn parent(expr: Expr, child_depth: usize) -> Result<Self, CalculatorError> {
+        let depth = child_depth + 1;
+        if depth > MAX_DEPTH {
+            return Err(CalculatorError::TooDeep);
+        }
+        Ok(Self { expr, depth })
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Active calls of [`Parser::expression`], which parentheses nest without adding nodes
+    nesting: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Result<Token, CalculatorError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(CalculatorError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), CalculatorError> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => Err(CalculatorError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    /// Precedence climbing over binary operators binding at least as tight as `min`
+    fn expression(&mut self, min: u8) -> Result<Node, CalculatorError> {
+        if self.nesting >= MAX_DEPTH {
+            return Err(CalculatorError::TooDeep);
+        }
+        self.nesting += 1;
+        let node = self.binary_chain(min);
+        self.nesting -= 1;
+        node
+    }
+
+    fn binary_chain(&mut self, min: u8) -> Result<Node, CalculatorError> {
+        let mut lhs = self.unary()?;
+        while let Some((op, precedence, consumed)) = self.binary_operator() {
+            if precedence < min {
+                break;
+            }
+            self.pos += consumed;
+            // `^` is right-associative
+            let next_min = if op == BinaryOp::Power {
+                precedence
+            } else {
+                precedence + 1
+            };
+            let rhs = self.expression(next_min)?;
+            lhs = Node::parent(
+                Expr::Binary(op, Box::new(lhs.expr), Box::new(rhs.expr)),
+                lhs.depth.max(rhs.depth),
+            )?;
+        }
+        Ok(lhs)
+    }
+
+    /// The binary operator at the cursor, with its precedence and token count
+    ///
+    /// An operand directly after another is an implicit multiplication and consumes nothing.
+    fn binary_operator(&self) -> Option<(BinaryOp, u8, usize)> {
+        Some(match self.peek()? {
+            Token::Plus => (BinaryOp::Add, PRECEDENCE_ADD, 1),
+            Token::Minus => (BinaryOp::Subtract, PRECEDENCE_ADD, 1),
+            Token::Star => (BinaryOp::Multiply, PRECEDENCE_MULTIPLY, 1),
+            Token::Slash => (BinaryOp::Divide, PRECEDENCE_MULTIPLY, 1),
+            Token::Percent => (BinaryOp::Remainder, PRECEDENCE_MULTIPLY, 1),
+            Token::Caret => (BinaryOp::Power, PRECEDENCE_POWER, 1),
+            Token::Ident(name) if name == "mod" => (BinaryOp::Remainder, PRECEDENCE_MULTIPLY, 1),
+            Token::Ident(name) if name == "of" => (BinaryOp::Of, PRECEDENCE_MULTIPLY, 1),
+            Token::Ident(name) if name == "x" => (BinaryOp::Multiply, PRECEDENCE_MULTIPLY, 1),
+            Token::Number(..) | Token::Ident(_) | Token::LParen => {
+                (BinaryOp::Multiply, PRECEDENCE_MULTIPLY, 0)
+            },
+            _ => return None,
+        })
+    }
+
+    fn unary(&mut self) -> Result<Node, CalculatorError> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.pos += 1;
+                // Binds looser than `^`, so `-2^2` is `-(2^2)`
+                let inner = self.expression(PRECEDENCE_POWER)?;
+                Node::parent(Expr::Negate(Box::new(inner.expr)), inner.depth)
+            },
+            Some(Token::Plus) => {
+                self.pos += 1;
+                self.expression(PRECEDENCE_POWER)
+            },
+            _ => {
+                let primary = self.primary()?;
+                self.postfix(primary)
+            },
+        }
+    }
+
+    fn postfix(&mut self, mut node: Node) -> Result<Node, CalculatorError> {
+        loop {
+            match self.peek() {
+                Some(Token::Bang) => {
+                    self.pos += 1;
+                    node = Node::parent(Expr::Factorial(Box::new(node.expr)), node.depth)?;
+                },
+                // A percentage unless an operand follows, which makes it the remainder operator
+                Some(Token::Percent) if !self.operand_at(1) => {
+                    self.pos += 1;
+                    node = Node::parent(Expr::Percent(Box::new(node.expr)), node.depth)?;
+                },
+                _ => return Ok(node),
+            }
+        }
+    }
+
+    fn operand_at(&self, offset: usize) -> bool {
+        match self.peek_at(offset) {
+            Some(Token::Ident(name)) => !matches!(name.as_str(), "of" | "mod" | "x"),
+            Some(Token::Number(..) | Token::LParen) => true,
+            _ => false,
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node, CalculatorError> {
+        match self.next()? {
+            Token::Number(value, radix) => Ok(Node::leaf(Expr::Number(value, radix))),
+            Token::LParen => {
+                let inner = self.expression(0)?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            },
+            Token::Ident(name)
+                if self.peek() == Some(&Token::LParen) && constant(&name).is_none() =>
+            {
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| CalculatorError::UnknownFunction(name.clone()))?;
+                self.pos += 1;
+                let mut args = Vec::new();
+                if self.peek() != Some(&Token::RParen) {
+                    args.push(self.expression(0)?);
+                    while self.peek() == Some(&Token::Comma) {
+                        self.pos += 1;
+                        args.push(self.expression(0)?);
+                    }
+                }
+                self.expect(Token::RParen)?;
+
+                let (min, max, expected) = function.arity();
+                if args.len() < min || args.len() > max {
+                    return Err(CalculatorError::WrongArgumentCount {
+                        function: name,
+                        expected: expected.to_string(),
+                    });
+                }
+                let depth = args.iter().map(|arg| arg.depth).max().unwrap_or(0);
+                let args = args.into_iter().map(|arg| arg.expr).collect();
+                Node::parent(Expr::Call(function, args), depth)
+            },
+            Token::Ident(name) => constant(&name)
+                .map(|value| Node::leaf(Expr::Number(value, Radix::Decimal)))
+                .ok_or(CalculatorError::UnknownIdentifier(name)),
+            token => Err(CalculatorError::UnexpectedToken(token.to_string())),
+        }
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" | "π" => Some(consts::PI),
+        "e" => Some(consts::E),
+        "tau" | "τ" => Some(consts::TAU),
+        _ => None,
+    }
+}
+
+fn call(function: Function, args: &[f64]) -> Result<f64, CalculatorError> {
+    let x = args[0];
+    let domain = |ok: bool, name: &str| {
+        if ok {
+            Ok(())
+        } else {
+            Err(CalculatorError::Domain(name.to_string()))
+        }
+    };
+    Ok(match function {
+        Function::Sqrt => {
+            domain(x >= 0.0, "sqrt")?;
+            x.sqrt()
+        },
+        Function::Cbrt => x.cbrt(),
+        Function::Abs => x.abs(),
+        Function::Sin => x.sin(),
+        Function::Cos => x.cos(),
+        Function::Tan => x.tan(),
+        Function::Asin => {
+            domain((-1.0..=1.0).contains(&x), "asin")?;
+            x.asin()
+        },
+        Function::Acos => {
+            domain((-1.0..=1.0).contains(&x), "acos")?;
+            x.acos()
+        },
+        Function::Atan => x.atan(),
+        Function::Ln => {
+            domain(x > 0.0, "ln")?;
+            x.ln()
+        },
+        Function::Log => {
+            let base = args.get(1).copied().unwrap_or(10.0);
+            domain(x > 0.0 && base > 0.0 && base != 1.0, "log")?;
+            x.log(base)
+        },
+        Function::Log2 => {
+            domain(x > 0.0, "log2")?;
+            x.log2()
+        },
+        Function::Exp => x.exp(),
+        Function::Floor => x.floor(),
+        Function::Ceil => x.ceil(),
+        Function::Round => x.round(),
+        Function::Trunc => x.trunc(),
+        Function::Min => args.iter().copied().fold(f64::INFINITY, f64::min),
+        Function::Max => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        Function::Pow => x.powf(args[1]),
+    })
+}
+
+fn factorial(n: f64) -> Result<f64, CalculatorError> {
+    // 171! overflows f64
+    if n < 0.0 || n.fract() != 0.0 || n > 170.0 {
+        return Err(CalculatorError::Domain("factorial".to_string()));
+    }
+    Ok((2..=n as u32).map(f64::from).product())
+}
+
+fn finite(value: f64) -> Result<f64, CalculatorError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(CalculatorError::NotFinite)
+    }
+}
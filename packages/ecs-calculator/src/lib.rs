@@ -0,0 +1,27 @@
+//! Action Items ECS Calculator
+//!
+//! Bevy ECS plugin that answers launcher queries such as `23*1.19`, `15% of 80`,
+//! `sqrt(2)^2`, `0xff + 1`, `255 in hex` or `5 km in mi` with an instant result row.
+//!
+//! - Arithmetic with operator precedence, functions, percentages and hex, binary and octal
+//!   literals
+//! - Offline conversions between units of length, mass, temperature, data size and time
+//! - Choosing the result copies its value to the clipboard
+
+pub mod answer;
+pub mod events;
+pub mod expression;
+pub mod plugin;
+pub mod provider;
+pub mod systems;
+pub mod types;
+pub mod units;
+
+pub use answer::{Answer, AnswerKind, calculate, evaluate_query, format_number};
+pub use events::CalculationCopied;
+pub use expression::{Radix, evaluate};
+pub use plugin::CalculatorPlugin;
+pub use provider::CalculatorProvider;
+pub use systems::{CALCULATOR_ACTION_PREFIX, CALCULATOR_PROVIDER_ID};
+pub use types::CalculatorError;
+pub use units::{Dimension, Unit, find_unit};
@@ -0,0 +1,25 @@
+//! Calculator plugin
+
+use action_items_core::LauncherEvent;
+use action_items_ecs_search_aggregator::SearchProviders;
+use bevy::prelude::*;
+
+use crate::events::CalculationCopied;
+use crate::systems::*;
+
+/// Plugin answering launcher queries with calculations and unit conversions
+///
+/// Answers are copied through `ClipboardResource`, so `ClipboardPlugin` must be added as well.
+pub struct CalculatorPlugin;
+
+impl Plugin for CalculatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SearchProviders>()
+            .add_event::<LauncherEvent>()
+            .add_event::<CalculationCopied>()
+            .add_systems(Startup, register_search_provider)
+            .add_systems(Update, handle_calculator_actions);
+
+        tracing::info!("CalculatorPlugin initialized");
+    }
+}
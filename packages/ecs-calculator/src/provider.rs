@@ -0,0 +1,43 @@
+//! Calculator search provider
+
+use std::time::Duration;
+
+use action_items_ecs_search_aggregator::{
+    SearchContext, SearchProvider, SearchResult, SearchResultStream,
+};
+use bevy::tasks::futures_lite::{StreamExt, stream};
+
+use crate::answer::calculate;
+use crate::systems::{CALCULATOR_ACTION_PREFIX, CALCULATOR_PROVIDER_ID};
+
+/// Answers launcher queries that are calculations or conversions
+///
+/// The answer is ranked above every other result.
+pub struct CalculatorProvider;
+
+impl SearchProvider for CalculatorProvider {
+    fn id(&self) -> &str {
+        CALCULATOR_PROVIDER_ID
+    }
+
+    fn budget(&self) -> Duration {
+        // Evaluation is synchronous; anything slower than this is a runaway expression
+        Duration::from_millis(50)
+    }
+
+    fn search(&self, query: &str, _ctx: SearchContext) -> SearchResultStream {
+        let query = query.to_string();
+        let answer = stream::once_future(async move {
+            calculate(&query).map(|answer| SearchResult {
+                title: answer.display.clone(),
+                description: format!("{} · {}", answer.description(), query.trim()),
+                action: format!("{CALCULATOR_ACTION_PREFIX}{}", answer.value),
+                icon: Some("🧮".to_string()),
+                score: 1.0,
+                plugin_id: CALCULATOR_PROVIDER_ID.to_string(),
+                title_matches: Vec::new(),
+            })
+        });
+        Box::pin(answer.filter_map(|result| result))
+    }
+}
@@ -0,0 +1,47 @@
+//! Calculator ECS systems
+
+use action_items_core::{LauncherEvent, LauncherEventType};
+use action_items_ecs_clipboard::{ClipboardData, ClipboardResource};
+use action_items_ecs_search_aggregator::SearchProviders;
+use bevy::prelude::*;
+use tracing::warn;
+
+use crate::events::CalculationCopied;
+use crate::provider::CalculatorProvider;
+
+/// Provider id used in aggregated launcher searches
+pub const CALCULATOR_PROVIDER_ID: &str = "calculator";
+/// Prefix of launcher action ids; the rest of the id is the value to copy
+pub const CALCULATOR_ACTION_PREFIX: &str = "calculator_";
+
+/// Register the calculator with the search aggregator
+pub fn register_search_provider(mut providers: ResMut<SearchProviders>) {
+    providers.register(CalculatorProvider);
+}
+
+/// Copy the value of an answer chosen in the launcher
+pub fn handle_calculator_actions(
+    mut launcher_events: EventReader<LauncherEvent>,
+    clipboard: Res<ClipboardResource>,
+    mut copied_events: EventWriter<CalculationCopied>,
+) {
+    for event in launcher_events.read() {
+        let LauncherEventType::Execute(action_id) = &event.event_type else {
+            continue;
+        };
+        let Some(value) = action_id.strip_prefix(CALCULATOR_ACTION_PREFIX) else {
+            continue;
+        };
+
+        let result = clipboard
+            .set_sync(ClipboardData::Text(value.to_string()))
+            .map_err(Into::into);
+        if let Err(e) = &result {
+            warn!("Failed to copy calculator answer {}: {}", value, e);
+        }
+        copied_events.write(CalculationCopied {
+            value: value.to_string(),
+            result,
+        });
+    }
+}
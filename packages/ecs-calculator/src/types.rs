@@ -0,0 +1,42 @@
+//! Calculator errors
+
+use serde::{Deserialize, Serialize};
+
+/// Errors from parsing, evaluating or copying a calculation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+pub enum CalculatorError {
+    #[error("Unexpected '{0}'")]
+    UnexpectedToken(String),
+    #[error("Expression ends unexpectedly")]
+    UnexpectedEnd,
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+    #[error("Unknown name: {0}")]
+    UnknownIdentifier(String),
+    #[error("Unknown function: {0}")]
+    UnknownFunction(String),
+    #[error("Expression is nested too deeply")]
+    TooDeep,
+    #[error("{function} takes {expected} argument(s)")]
+    WrongArgumentCount { function: String, expected: String },
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("{0} is undefined for this value")]
+    Domain(String),
+    #[error("Result is not a finite number")]
+    NotFinite,
+    #[error("{0} is not an integer")]
+    NotAnInteger(String),
+    #[error("Unknown unit: {0}")]
+    UnknownUnit(String),
+    #[error("Cannot convert {from} to {to}")]
+    IncompatibleUnits { from: String, to: String },
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+}
+
+impl From<action_items_ecs_clipboard::ClipboardError> for CalculatorError {
+    fn from(error: action_items_ecs_clipboard::ClipboardError) -> Self {
+        CalculatorError::Clipboard(error.to_string())
+    }
+}
@@ -0,0 +1,297 @@
+//! Offline unit conversions
+//!
+//! Units are converted through a base unit per dimension: metres, kilograms, kelvin, bytes
+//! and seconds. Symbols such as `MB` and `Mb` are matched case-sensitively first; names and
+//! lowercase aliases are matched case-insensitively after that.
+
+use crate::types::CalculatorError;
+
+/// Kind of quantity a unit measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Temperature,
+    DataSize,
+    Time,
+}
+
+impl Dimension {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dimension::Length => "Length",
+            Dimension::Mass => "Mass",
+            Dimension::Temperature => "Temperature",
+            Dimension::DataSize => "Data size",
+            Dimension::Time => "Time",
+        }
+    }
+}
+
+/// A unit and its relation to the dimension's base unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    /// Symbol shown in answers
+    pub symbol: &'static str,
+    /// Lowercase names and alternative symbols
+    pub aliases: &'static [&'static str],
+    pub dimension: Dimension,
+    /// Base units per unit
+    pub scale: f64,
+    /// Added after scaling, for temperatures
+    pub offset: f64,
+}
+
+impl Unit {
+    const fn new(
+        symbol: &'static str,
+        aliases: &'static [&'static str],
+        dimension: Dimension,
+        scale: f64,
+    ) -> Self {
+        Self {
+            symbol,
+            aliases,
+            dimension,
+            scale,
+            offset: 0.0,
+        }
+    }
+
+    pub fn to_base(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
+
+    pub fn from_base(&self, value: f64) -> f64 {
+        (value - self.offset) / self.scale
+    }
+}
+
+const KIB: f64 = 1024.0;
+const DAY: f64 = 86_400.0;
+
+pub const UNITS: &[Unit] = &[
+    // Length, in metres
+    Unit::new(
+        "nm",
+        &["nanometer", "nanometers", "nanometre", "nanometres"],
+        Dimension::Length,
+        1e-9,
+    ),
+    Unit::new(
+        "µm",
+        &["um", "micrometer", "micrometers", "micron", "microns"],
+        Dimension::Length,
+        1e-6,
+    ),
+    Unit::new(
+        "mm",
+        &["millimeter", "millimeters", "millimetre", "millimetres"],
+        Dimension::Length,
+        1e-3,
+    ),
+    Unit::new(
+        "cm",
+        &["centimeter", "centimeters", "centimetre", "centimetres"],
+        Dimension::Length,
+        1e-2,
+    ),
+    Unit::new(
+        "m",
+        &["meter", "meters", "metre", "metres"],
+        Dimension::Length,
+        1.0,
+    ),
+    Unit::new(
+        "km",
+        &["kilometer", "kilometers", "kilometre", "kilometres"],
+        Dimension::Length,
+        1e3,
+    ),
+    Unit::new("in", &["inch", "inches", "\""], Dimension::Length, 0.0254),
+    Unit::new("ft", &["foot", "feet", "'"], Dimension::Length, 0.3048),
+    Unit::new("yd", &["yard", "yards"], Dimension::Length, 0.9144),
+    Unit::new("mi", &["mile", "miles"], Dimension::Length, 1609.344),
+    Unit::new(
+        "nmi",
+        &["nautical mile", "nautical miles"],
+        Dimension::Length,
+        1852.0,
+    ),
+    // Mass, in kilograms
+    Unit::new("mg", &["milligram", "milligrams"], Dimension::Mass, 1e-6),
+    Unit::new("g", &["gram", "grams"], Dimension::Mass, 1e-3),
+    Unit::new(
+        "kg",
+        &["kilogram", "kilograms", "kilo", "kilos"],
+        Dimension::Mass,
+        1.0,
+    ),
+    Unit::new(
+        "t",
+        &["tonne", "tonnes", "metric ton", "metric tons"],
+        Dimension::Mass,
+        1e3,
+    ),
+    Unit::new(
+        "oz",
+        &["ounce", "ounces"],
+        Dimension::Mass,
+        0.028_349_523_125,
+    ),
+    Unit::new(
+        "lb",
+        &["lbs", "pound", "pounds"],
+        Dimension::Mass,
+        0.453_592_37,
+    ),
+    Unit::new("st", &["stone", "stones"], Dimension::Mass, 6.350_293_18),
+    // Temperature, in kelvin
+    Unit {
+        symbol: "°C",
+        aliases: &["c", "celsius", "degc"],
+        dimension: Dimension::Temperature,
+        scale: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        symbol: "°F",
+        aliases: &["f", "fahrenheit", "degf"],
+        dimension: Dimension::Temperature,
+        scale: 5.0 / 9.0,
+        offset: 459.67 * 5.0 / 9.0,
+    },
+    Unit::new("K", &["k", "kelvin"], Dimension::Temperature, 1.0),
+    // Data sizes, in bytes
+    Unit::new("bit", &["bits"], Dimension::DataSize, 0.125),
+    Unit::new("B", &["byte", "bytes"], Dimension::DataSize, 1.0),
+    Unit::new(
+        "kB",
+        &["kb", "kilobyte", "kilobytes"],
+        Dimension::DataSize,
+        1e3,
+    ),
+    Unit::new(
+        "MB",
+        &["mb", "megabyte", "megabytes"],
+        Dimension::DataSize,
+        1e6,
+    ),
+    Unit::new(
+        "GB",
+        &["gb", "gigabyte", "gigabytes"],
+        Dimension::DataSize,
+        1e9,
+    ),
+    Unit::new(
+        "TB",
+        &["tb", "terabyte", "terabytes"],
+        Dimension::DataSize,
+        1e12,
+    ),
+    Unit::new(
+        "PB",
+        &["pb", "petabyte", "petabytes"],
+        Dimension::DataSize,
+        1e15,
+    ),
+    Unit::new(
+        "KiB",
+        &["kib", "kibibyte", "kibibytes"],
+        Dimension::DataSize,
+        KIB,
+    ),
+    Unit::new(
+        "MiB",
+        &["mib", "mebibyte", "mebibytes"],
+        Dimension::DataSize,
+        KIB * KIB,
+    ),
+    Unit::new(
+        "GiB",
+        &["gib", "gibibyte", "gibibytes"],
+        Dimension::DataSize,
+        KIB * KIB * KIB,
+    ),
+    Unit::new(
+        "TiB",
+        &["tib", "tebibyte", "tebibytes"],
+        Dimension::DataSize,
+        KIB * KIB * KIB * KIB,
+    ),
+    Unit::new(
+        "kbit",
+        &["Kb", "kilobit", "kilobits"],
+        Dimension::DataSize,
+        1e3 / 8.0,
+    ),
+    Unit::new(
+        "Mbit",
+        &["Mb", "megabit", "megabits"],
+        Dimension::DataSize,
+        1e6 / 8.0,
+    ),
+    Unit::new(
+        "Gbit",
+        &["Gb", "gigabit", "gigabits"],
+        Dimension::DataSize,
+        1e9 / 8.0,
+    ),
+    // Time, in seconds
+    Unit::new(
+        "ms",
+        &["millisecond", "milliseconds"],
+        Dimension::Time,
+        1e-3,
+    ),
+    Unit::new(
+        "s",
+        &["sec", "secs", "second", "seconds"],
+        Dimension::Time,
+        1.0,
+    ),
+    Unit::new("min", &["mins", "minute", "minutes"], Dimension::Time, 60.0),
+    Unit::new(
+        "h",
+        &["hr", "hrs", "hour", "hours"],
+        Dimension::Time,
+        3600.0,
+    ),
+    Unit::new("d", &["day", "days"], Dimension::Time, DAY),
+    Unit::new("wk", &["week", "weeks"], Dimension::Time, 7.0 * DAY),
+    // Gregorian averages
+    Unit::new(
+        "mo",
+        &["month", "months"],
+        Dimension::Time,
+        30.436_875 * DAY,
+    ),
+    Unit::new("yr", &["year", "years"], Dimension::Time, 365.2425 * DAY),
+];
+
+/// Look up a unit by symbol, alias or name
+pub fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim();
+    let name = name.strip_prefix("degrees ").unwrap_or(name);
+    UNITS
+        .iter()
+        .find(|unit| unit.symbol == name || unit.aliases.contains(&name))
+        .or_else(|| {
+            let lowered = name.to_lowercase();
+            UNITS.iter().find(|unit| {
+                unit.symbol.to_lowercase() == lowered
+                    || unit.aliases.iter().any(|alias| *alias == lowered)
+            })
+        })
+}
+
+/// Convert a value between units of the same dimension
+pub fn convert(value: f64, from: &Unit, to: &Unit) -> Result<f64, CalculatorError> {
+    if from.dimension != to.dimension {
+        return Err(CalculatorError::IncompatibleUnits {
+            from: from.symbol.to_string(),
+            to: to.symbol.to_string(),
+        });
+    }
+    Ok(to.from_base(from.to_base(value)))
+}
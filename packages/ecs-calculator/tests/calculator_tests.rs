@@ -0,0 +1,144 @@
+use action_items_ecs_calculator::expression::{Radix, format_radix};
+use action_items_ecs_calculator::{
+    AnswerKind, CalculatorError, Dimension, calculate, evaluate, evaluate_query, find_unit,
+    format_number,
+};
+
+fn value(query: &str) -> String {
+    evaluate_query(query).unwrap().value
+}
+
+#[test]
+fn test_operator_precedence() {
+    assert_eq!(value("2 + 3 * 4"), "14");
+    assert_eq!(value("(2 + 3) * 4"), "20");
+    assert_eq!(value("2^3^2"), "512");
+    assert_eq!(value("-2^2"), "-4");
+    assert_eq!(value("2^-1"), "0.5");
+    assert_eq!(value("6 ÷ 2 × 3"), "9");
+    assert_eq!(value("7 mod 4 + 10 % 3"), "4");
+    assert_eq!(value("2pi"), value("2 * pi"));
+    assert_eq!(value("3(4 + 1)"), "15");
+}
+
+#[test]
+fn test_functions_and_percentages() {
+    assert_eq!(value("sqrt(2)^2"), "2");
+    assert_eq!(value("log(1000)"), "3");
+    assert_eq!(value("log(8, 2)"), "3");
+    assert_eq!(value("max(3, 9, 4) - min(3, 9, 4)"), "6");
+    assert_eq!(value("5!"), "120");
+    assert_eq!(value("200 + 10%"), "220");
+    assert_eq!(value("80 - 25%"), "60");
+    assert_eq!(value("15% of 80"), "12");
+    assert_eq!(value("50% * 30"), "15");
+
+    assert_eq!(evaluate("1 / 0"), Err(CalculatorError::DivisionByZero));
+    assert_eq!(
+        evaluate("sqrt(-1)"),
+        Err(CalculatorError::Domain("sqrt".to_string()))
+    );
+    assert!(matches!(
+        evaluate("pow(2)"),
+        Err(CalculatorError::WrongArgumentCount { .. })
+    ));
+    assert_eq!(
+        evaluate("foo(2)"),
+        Err(CalculatorError::UnknownFunction("foo".to_string()))
+    );
+    assert_eq!(evaluate("2 +"), Err(CalculatorError::UnexpectedEnd));
+}
+
+#[test]
+fn test_nesting_is_limited() {
+    let nested = |depth: usize| format!("{}1{}", "(-".repeat(depth), ")".repeat(depth));
+    assert_eq!(value(&nested(30)), "1");
+    assert_eq!(evaluate(&nested(10_000)), Err(CalculatorError::TooDeep));
+    assert_eq!(
+        evaluate(&format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000))),
+        Err(CalculatorError::TooDeep)
+    );
+
+    // Left-associative chains and postfix operators nest without recursing in the parser
+    assert_eq!(value(&vec!["1"; 64].join(" + ")), "64");
+    assert_eq!(evaluate(&vec!["1"; 10_000].join(" + ")), Err(CalculatorError::TooDeep));
+    assert_eq!(evaluate(&format!("3{}", "!".repeat(10_000))), Err(CalculatorError::TooDeep));
+}
+
+#[test]
+fn test_bases() {
+    assert_eq!(value("0xff + 0b1 + 0o10"), "264");
+    assert_eq!(value("255 in hex"), "0xFF");
+    assert_eq!(value("0x1F to bin"), "0b11111");
+    assert_eq!(value("-8 as oct"), "-0o10");
+    assert_eq!(value("0xff in dec"), "255");
+    assert_eq!(
+        evaluate_query("1.5 in hex"),
+        Err(CalculatorError::NotAnInteger("1.5".to_string()))
+    );
+    assert_eq!(format_radix(10.0, Radix::Binary).unwrap(), "0b1010");
+}
+
+#[test]
+fn test_unit_conversions() {
+    let answer = evaluate_query("5 km in mi").unwrap();
+    assert_eq!(answer.kind, AnswerKind::Conversion(Dimension::Length));
+    assert_eq!(answer.display, "3.10685596119 mi");
+    assert_eq!(answer.value, "3.10685596119");
+
+    assert_eq!(value("5 in in cm"), "12.7");
+    assert_eq!(value("3 nautical miles to km"), "5.556");
+    assert_eq!(value("2 lb in g"), "907.18474");
+    assert_eq!(value("100 °F in °C"), "37.7777777778");
+    assert_eq!(value("0 c to f"), "32");
+    assert_eq!(value("-40 F in K"), "233.15");
+    assert_eq!(value("1.5 GB in MiB"), "1430.51147461");
+    assert_eq!(value("1 Gb in MB"), "125");
+    assert_eq!(value("90 min in h"), "1.5");
+    assert_eq!(value("(1 + 1) days -> hours"), "48");
+    assert_eq!(value("km in m"), "1000");
+
+    assert_eq!(
+        evaluate_query("5 kg in m"),
+        Err(CalculatorError::IncompatibleUnits {
+            from: "kg".to_string(),
+            to: "m".to_string()
+        })
+    );
+    assert_eq!(
+        evaluate_query("5 km in parsecs"),
+        Err(CalculatorError::UnknownUnit("parsecs".to_string()))
+    );
+}
+
+#[test]
+fn test_unit_lookup_is_case_sensitive_first() {
+    assert_eq!(find_unit("MB").unwrap().symbol, "MB");
+    assert_eq!(find_unit("Mb").unwrap().symbol, "Mbit");
+    assert_eq!(find_unit("mb").unwrap().symbol, "MB");
+    assert_eq!(find_unit("KM").unwrap().symbol, "km");
+    assert_eq!(find_unit("degrees celsius").unwrap().symbol, "°C");
+    assert!(find_unit("furlong").is_none());
+}
+
+#[test]
+fn test_launcher_queries() {
+    assert_eq!(calculate("23*1.19").unwrap().value, "27.37");
+    assert_eq!(calculate("0.1 + 0.2").unwrap().value, "0.3");
+    // Not calculations: no digits, plain numbers and unfinished input
+    assert!(calculate("e").is_none());
+    assert!(calculate("safari").is_none());
+    assert!(calculate("42").is_none());
+    assert!(calculate("2 +").is_none());
+    assert!(calculate("5 km in").is_none());
+    assert!(calculate("iphone 15").is_none());
+}
+
+#[test]
+fn test_number_formatting() {
+    assert_eq!(format_number(1.0 / 3.0), "0.333333333333");
+    assert_eq!(format_number(-0.0), "0");
+    assert_eq!(format_number(1234567.0), "1234567");
+    assert_eq!(format_number(3e20), "3e20");
+    assert_eq!(format_number(-1.5e-9), "-1.5e-9");
+}
@@ -0,0 +1,61 @@
+//! Reading the current clipboard content and its source application
+
+use action_items_ecs_clipboard::{ArboardManager, ClipboardData, ClipboardError, ClipboardFormat};
+
+/// Read the richest format currently on the clipboard
+///
+/// Files win over images, images over HTML, and HTML over plain text, matching what the user
+/// most likely meant to copy: copying a file in a file manager also places its name on the
+/// clipboard as text, and copying rich text also places a plain-text fallback. Returns `None`
+/// for an empty clipboard.
+pub async fn read_clipboard() -> Result<Option<ClipboardData>, ClipboardError> {
+    let formats = ArboardManager::available_formats().await;
+
+    if formats.contains(&ClipboardFormat::Files) {
+        let files = ArboardManager::get_files().await?;
+        if !files.is_empty() {
+            return Ok(Some(ClipboardData::Files(files)));
+        }
+    }
+
+    if formats.contains(&ClipboardFormat::Image) {
+        return ArboardManager::get_image().await.map(Some);
+    }
+
+    let text = ArboardManager::get_text()
+        .await
+        .ok()
+        .filter(|t| !t.is_empty());
+
+    if formats.contains(&ClipboardFormat::Html) {
+        let html = ArboardManager::get_html().await?;
+        return Ok(Some(ClipboardData::Html {
+            html,
+            alt_text: text,
+        }));
+    }
+
+    Ok(text.map(ClipboardData::Text))
+}
+
+/// Identifier of the application that is frontmost, and so most likely the copy source
+///
+/// Returns the bundle identifier on macOS. Other platforms do not expose the clipboard owner
+/// reliably, so entries from them carry no source.
+pub fn frontmost_application() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSWorkspace;
+
+        let workspace = NSWorkspace::sharedWorkspace();
+        let app = workspace.frontmostApplication()?;
+        app.bundleIdentifier()
+            .or_else(|| app.localizedName())
+            .map(|name| name.to_string())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        None
+    }
+}
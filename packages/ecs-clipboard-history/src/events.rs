@@ -0,0 +1,69 @@
+//! Clipboard history request and notification events
+
+use bevy::prelude::*;
+
+use crate::privacy::IgnoreReason;
+use crate::types::ClipboardHistoryError;
+
+/// A copy was recorded in the history
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryEntryAdded {
+    pub entry_id: String,
+    /// Whether the content was already in the history and was moved to the top
+    pub deduplicated: bool,
+}
+
+/// A copy was left out of the history by the privacy filters
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryCopyIgnored {
+    pub reason: IgnoreReason,
+}
+
+/// Pin or unpin an entry
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryPinRequested {
+    pub entry_id: String,
+    pub pinned: bool,
+}
+
+/// Delete a single entry
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryRemoveRequested {
+    pub entry_id: String,
+}
+
+/// Delete the whole history
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryClearRequested {
+    /// Keep pinned entries
+    pub keep_pinned: bool,
+}
+
+/// Put a history entry back on the clipboard and paste it into the focused application
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryPasteRequested {
+    /// 1-based position in display order (pinned entries first)
+    pub position: usize,
+}
+
+impl ClipboardHistoryPasteRequested {
+    pub fn new(position: usize) -> Self {
+        Self { position }
+    }
+}
+
+/// Put a specific history entry back on the clipboard and paste it
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryPasteEntryRequested {
+    pub entry_id: String,
+}
+
+/// Result of a paste request
+///
+/// Sent once the entry is on the clipboard; the paste keystroke follows in the background.
+#[derive(Event, Debug, Clone)]
+pub struct ClipboardHistoryPasteCompleted {
+    /// Entry placed on the clipboard, if the request resolved to one
+    pub entry_id: Option<String>,
+    pub result: Result<(), ClipboardHistoryError>,
+}
@@ -0,0 +1,243 @@
+//! In-memory clipboard history with deduplication, pinning and retention
+//!
+//! The resource mirrors the `clipboard_history` table; systems write every change through to
+//! the database, so this type itself stays free of I/O and is easy to test.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use action_items_ecs_surrealdb::LoadState;
+use bevy::prelude::*;
+
+use crate::types::{ClipboardHistoryConfig, ClipboardHistoryEntry};
+
+/// Result of recording a copy
+#[derive(Debug, Clone)]
+pub struct RecordOutcome {
+    /// Entry as stored after the copy, either new or updated
+    pub entry: ClipboardHistoryEntry,
+    /// Whether the content was already in the history
+    pub deduplicated: bool,
+    /// Ids of entries evicted by retention limits
+    pub evicted: Vec<String>,
+}
+
+/// Clipboard history ordered for display
+#[derive(Resource, Debug, Default)]
+pub struct ClipboardHistory {
+    pub config: ClipboardHistoryConfig,
+    pub load_state: LoadState,
+    /// Whether sensitive entries can be encrypted and written to the database
+    pub persist_sensitive: bool,
+    /// Entries, pinned first and then most recently copied first
+    entries: Vec<ClipboardHistoryEntry>,
+}
+
+impl ClipboardHistory {
+    pub fn new(config: ClipboardHistoryConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Merge entries loaded from the database into the history
+    ///
+    /// Copies recorded before loading finished are folded into the stored entry for the same
+    /// content. Returns those entries, which still need to be written to the database.
+    pub fn load(&mut self, stored: Vec<ClipboardHistoryEntry>) -> Vec<ClipboardHistoryEntry> {
+        let mut unsaved = std::mem::take(&mut self.entries);
+        self.entries = stored;
+
+        for early in &mut unsaved {
+            if let Some(index) = self.entries.iter().position(|e| e.same_content(early)) {
+                let stored = self.entries.remove(index);
+                early.id = stored.id;
+                early.first_copied_at = early.first_copied_at.min(stored.first_copied_at);
+                early.copy_count = early.copy_count.saturating_add(stored.copy_count);
+                early.pinned |= stored.pinned;
+                if stored.source_app.is_some() {
+                    early.source_app = stored.source_app;
+                }
+            }
+        }
+
+        self.entries.extend(unsaved.iter().cloned());
+        self.sort();
+        self.load_state = LoadState::Loaded;
+        unsaved
+    }
+
+    /// Record a copy, deduplicating by content and applying retention
+    ///
+    /// Returns `None` when the content exceeds [`ClipboardHistoryConfig::max_entry_bytes`].
+    pub fn record(&mut self, entry: ClipboardHistoryEntry, now: i64) -> Option<RecordOutcome> {
+        if entry.byte_size > self.config.max_entry_bytes {
+            return None;
+        }
+
+        let existing = self.entries.iter().position(|e| e.same_content(&entry));
+        let (entry, deduplicated) = match existing {
+            Some(index) => {
+                let mut existing = self.entries.remove(index);
+                existing.last_copied_at = entry.last_copied_at;
+                existing.copy_count = existing.copy_count.saturating_add(1);
+                // Re-copies of history items through the launcher would otherwise overwrite the
+                // original source with the launcher itself
+                if existing.source_app.is_none() {
+                    existing.source_app = entry.source_app;
+                }
+                (existing, true)
+            },
+            None => (entry, false),
+        };
+
+        self.entries.insert(0, entry.clone());
+        self.sort();
+        let evicted = self.enforce_retention(now);
+
+        Some(RecordOutcome {
+            entry,
+            deduplicated,
+            evicted,
+        })
+    }
+
+    /// Drop unpinned entries beyond the count, age and size limits, returning their ids
+    ///
+    /// Oldest entries are evicted first; pinned entries never count against the limits.
+    pub fn enforce_retention(&mut self, now: i64) -> Vec<String> {
+        let max_age_ms = i64::try_from(self.config.max_age.as_millis()).unwrap_or(i64::MAX);
+        let mut kept = 0usize;
+        let mut kept_bytes = 0usize;
+        let mut evicted = Vec::new();
+
+        // Entries are newest first, so the running totals favour recent copies
+        self.entries.retain(|entry| {
+            if entry.pinned {
+                return true;
+            }
+            let too_old = now.saturating_sub(entry.last_copied_at) > max_age_ms;
+            let too_many = kept >= self.config.max_entries;
+            let too_large = kept_bytes + entry.byte_size > self.config.max_total_bytes;
+            if too_old || too_many || too_large {
+                evicted.push(entry.id.clone());
+                return false;
+            }
+            kept += 1;
+            kept_bytes += entry.byte_size;
+            true
+        });
+
+        evicted
+    }
+
+    /// Pin or unpin an entry, returning the updated entry
+    pub fn set_pinned(&mut self, id: &str, pinned: bool) -> Option<ClipboardHistoryEntry> {
+        let entry = self.entries.iter_mut().find(|e| e.id == id)?;
+        entry.pinned = pinned;
+        let entry = entry.clone();
+        self.sort();
+        Some(entry)
+    }
+
+    /// Remove a single entry
+    pub fn remove(&mut self, id: &str) -> Option<ClipboardHistoryEntry> {
+        let index = self.entries.iter().position(|e| e.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Remove all entries, optionally keeping pinned ones, returning the removed ids
+    pub fn clear(&mut self, keep_pinned: bool) -> Vec<String> {
+        let mut removed = Vec::new();
+        self.entries.retain(|entry| {
+            let keep = keep_pinned && entry.pinned;
+            if !keep {
+                removed.push(entry.id.clone());
+            }
+            keep
+        });
+        removed
+    }
+
+    /// Entry at a 1-based position in display order (pinned entries first)
+    pub fn get_position(&self, position: usize) -> Option<&ClipboardHistoryEntry> {
+        position
+            .checked_sub(1)
+            .and_then(|index| self.entries.get(index))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ClipboardHistoryEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Entries in display order: pinned first, then most recently copied
+    pub fn entries(&self) -> &[ClipboardHistoryEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Search entries whose text contains every query term, best matches first
+    ///
+    /// Matching is case-insensitive. Entries whose text starts with the query rank above
+    /// entries that merely contain it, and more recent entries break ties, so the result order
+    /// stays close to the history order users already know.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(&ClipboardHistoryEntry, f32)> {
+        let query = query.trim().to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() {
+            return self
+                .entries
+                .iter()
+                .take(limit)
+                .map(|entry| (entry, 1.0))
+                .collect();
+        }
+
+        let total = self.entries.len().max(1) as f32;
+        let mut matches: Vec<(&ClipboardHistoryEntry, f32)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(rank, entry)| {
+                let text = entry.searchable_text()?.to_lowercase();
+                if !terms.iter().all(|term| text.contains(term)) {
+                    return None;
+                }
+                let base = if text.trim_start().starts_with(&query) {
+                    0.9
+                } else {
+                    0.7
+                };
+                // Up to 0.1 for recency within the history
+                Some((entry, base + 0.1 * (1.0 - rank as f32 / total)))
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        matches.truncate(limit);
+        matches
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| b.last_copied_at.cmp(&a.last_copied_at))
+        });
+    }
+}
+
+/// Current time as Unix milliseconds
+pub fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,32 @@
+//! Action Items ECS Clipboard History
+//!
+//! Bevy ECS plugin that records every clipboard change reported by `action_items_ecs_clipboard`
+//! into a persistent history.
+//!
+//! - Text, HTML, images and file lists are stored in SurrealDB with copy timestamps and the
+//!   source application
+//! - Identical content is deduplicated and moved to the top instead of stored twice
+//! - Retention limits by entry count, age and total size; pinned entries are exempt
+//! - Privacy filters skip concealed copies and denied apps, and drop or encrypt likely secrets
+//! - History entries are returned as launcher search results
+//! - [`ClipboardHistoryPasteRequested`] puts the N-th entry back on the clipboard and pastes it
+
+pub mod capture;
+pub mod events;
+pub mod history;
+pub mod persistence;
+pub mod plugin;
+pub mod privacy;
+pub mod systems;
+pub mod types;
+
+pub use events::{
+    ClipboardHistoryClearRequested, ClipboardHistoryCopyIgnored, ClipboardHistoryEntryAdded,
+    ClipboardHistoryPasteCompleted, ClipboardHistoryPasteEntryRequested,
+    ClipboardHistoryPasteRequested, ClipboardHistoryPinRequested, ClipboardHistoryRemoveRequested,
+};
+pub use history::{ClipboardHistory, RecordOutcome};
+pub use plugin::ClipboardHistoryPlugin;
+pub use privacy::{IgnoreReason, PrivacyConfig, SecretHandling, SecretKind};
+pub use systems::{CLIPBOARD_HISTORY_PROVIDER_ID, PASTE_ACTION_PREFIX};
+pub use types::{ClipboardHistoryConfig, ClipboardHistoryEntry, ClipboardHistoryError};
@@ -0,0 +1,276 @@
+//! SurrealDB persistence for clipboard history entries
+//!
+//! Entries live in `clipboard_history`, keyed by their content hash. Content is stored as a
+//! tagged object; image pixels are base64-encoded RGBA so every `ClipboardData` variant
+//! round-trips through the same record shape. The table is schemaless so content variants can
+//! carry their own fields; the scalar fields are still typed.
+//!
+//! Content of sensitive entries is stored as an `encrypted` variant holding the other variants
+//! sealed with `ecs_tls::tls::key_encryption`, which derives its key from the
+//! `SWEETMCP_KEY_ENCRYPTION_PASSPHRASE` deployment passphrase. Without a usable passphrase,
+//! sensitive entries are kept for the session only; [`check_encryption`] tells which applies.
+
+use std::path::PathBuf;
+
+use action_items_ecs_clipboard::ClipboardData;
+use action_items_ecs_surrealdb::{DatabaseError, DatabaseService, LoadState, Persisted};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ecs_tls::tls::key_encryption::{decrypt_private_key, encrypt_private_key};
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+use crate::history::ClipboardHistory;
+use crate::privacy::SecretKind;
+use crate::types::{ClipboardHistoryEntry, ClipboardHistoryError, content_size};
+
+/// Table holding clipboard history entries
+pub const CLIPBOARD_HISTORY_TABLE: &str = "clipboard_history";
+
+/// Definition of the clipboard history table, indexed for recency and pinned ordering
+pub const CLIPBOARD_HISTORY_SCHEMA: &str = r#"
+DEFINE TABLE IF NOT EXISTS clipboard_history SCHEMALESS;
+DEFINE FIELD IF NOT EXISTS entry_id ON clipboard_history TYPE string;
+DEFINE FIELD IF NOT EXISTS content ON clipboard_history TYPE object;
+DEFINE FIELD IF NOT EXISTS source_app ON clipboard_history TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS first_copied_at ON clipboard_history TYPE int;
+DEFINE FIELD IF NOT EXISTS last_copied_at ON clipboard_history TYPE int;
+DEFINE FIELD IF NOT EXISTS copy_count ON clipboard_history TYPE int DEFAULT 1;
+DEFINE FIELD IF NOT EXISTS pinned ON clipboard_history TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS sensitive ON clipboard_history TYPE option<string>;
+DEFINE INDEX IF NOT EXISTS last_copied_idx ON clipboard_history COLUMNS last_copied_at;
+DEFINE INDEX IF NOT EXISTS pinned_idx ON clipboard_history COLUMNS pinned;
+"#;
+
+/// Persisted form of [`ClipboardData`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StoredContent {
+    Text {
+        text: String,
+    },
+    Html {
+        html: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        alt_text: Option<String>,
+    },
+    Image {
+        width: usize,
+        height: usize,
+        /// Base64-encoded RGBA pixels
+        rgba: String,
+    },
+    Files {
+        paths: Vec<String>,
+    },
+    Encrypted {
+        /// Base64 of the encrypted JSON of another variant
+        ciphertext: String,
+    },
+}
+
+impl From<&ClipboardData> for StoredContent {
+    fn from(content: &ClipboardData) -> Self {
+        match content {
+            ClipboardData::Text(text) => StoredContent::Text { text: text.clone() },
+            ClipboardData::Html { html, alt_text } => StoredContent::Html {
+                html: html.clone(),
+                alt_text: alt_text.clone(),
+            },
+            ClipboardData::Image {
+                data,
+                width,
+                height,
+            } => StoredContent::Image {
+                width: *width,
+                height: *height,
+                rgba: BASE64.encode(data),
+            },
+            ClipboardData::Files(files) => StoredContent::Files {
+                paths: files
+                    .iter()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl TryFrom<StoredContent> for ClipboardData {
+    type Error = ClipboardHistoryError;
+
+    fn try_from(content: StoredContent) -> Result<Self, Self::Error> {
+        let decode = |e: base64::DecodeError| ClipboardHistoryError::Database(e.to_string());
+        Ok(match content {
+            StoredContent::Text { text } => ClipboardData::Text(text),
+            StoredContent::Html { html, alt_text } => ClipboardData::Html { html, alt_text },
+            StoredContent::Image {
+                width,
+                height,
+                rgba,
+            } => ClipboardData::Image {
+                data: BASE64.decode(rgba).map_err(decode)?,
+                width,
+                height,
+            },
+            StoredContent::Files { paths } => {
+                ClipboardData::Files(paths.into_iter().map(PathBuf::from).collect())
+            },
+            StoredContent::Encrypted { .. } => {
+                return Err(ClipboardHistoryError::Encryption(
+                    "Encrypted content must be decrypted before decoding".to_string(),
+                ));
+            },
+        })
+    }
+}
+
+/// Database record for a history entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredEntry {
+    pub entry_id: String,
+    pub content: StoredContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_app: Option<String>,
+    pub first_copied_at: i64,
+    pub last_copied_at: i64,
+    pub copy_count: u32,
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<SecretKind>,
+}
+
+impl StoredEntry {
+    /// Build the record for an entry, encrypting sensitive content
+    async fn seal(entry: &ClipboardHistoryEntry) -> Result<Self, ClipboardHistoryError> {
+        let mut content = StoredContent::from(&entry.content);
+        if entry.sensitive.is_some() {
+            let plaintext = serde_json::to_string(&content)
+                .map_err(|e| ClipboardHistoryError::Encryption(e.to_string()))?;
+            let ciphertext = encrypt_private_key(&plaintext)
+                .await
+                .map_err(|e| ClipboardHistoryError::Encryption(e.to_string()))?;
+            content = StoredContent::Encrypted {
+                ciphertext: BASE64.encode(ciphertext),
+            };
+        }
+
+        Ok(Self {
+            entry_id: entry.id.clone(),
+            content,
+            source_app: entry.source_app.clone(),
+            first_copied_at: entry.first_copied_at,
+            last_copied_at: entry.last_copied_at,
+            copy_count: entry.copy_count,
+            pinned: entry.pinned,
+            sensitive: entry.sensitive,
+        })
+    }
+
+    /// Decode the record, decrypting sensitive content
+    async fn open(self) -> Result<ClipboardHistoryEntry, ClipboardHistoryError> {
+        let stored = match self.content {
+            StoredContent::Encrypted { ciphertext } => {
+                let encryption =
+                    |e: &dyn std::fmt::Display| ClipboardHistoryError::Encryption(e.to_string());
+                let sealed = BASE64.decode(ciphertext).map_err(|e| encryption(&e))?;
+                let plaintext = decrypt_private_key(&sealed)
+                    .await
+                    .map_err(|e| encryption(&e))?;
+                serde_json::from_slice(plaintext.as_bytes()).map_err(|e| encryption(&e))?
+            },
+            content => content,
+        };
+
+        let content = ClipboardData::try_from(stored)?;
+        Ok(ClipboardHistoryEntry {
+            id: self.entry_id,
+            byte_size: content_size(&content),
+            content,
+            source_app: self.source_app,
+            first_copied_at: self.first_copied_at,
+            last_copied_at: self.last_copied_at,
+            copy_count: self.copy_count,
+            pinned: self.pinned,
+            sensitive: self.sensitive,
+        })
+    }
+}
+
+fn record_id(entry_id: &str) -> RecordId {
+    RecordId::from((CLIPBOARD_HISTORY_TABLE, entry_id))
+}
+
+impl Persisted for ClipboardHistory {
+    const NAME: &'static str = "clipboard history";
+    const SCHEMA: &'static str = CLIPBOARD_HISTORY_SCHEMA;
+
+    fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    fn set_load_state(&mut self, state: LoadState) {
+        self.load_state = state;
+    }
+}
+
+/// Check whether sensitive entries can be encrypted for storage
+///
+/// The error explains why the configured passphrase is missing or unusable.
+pub async fn check_encryption() -> Result<(), ClipboardHistoryError> {
+    encrypt_private_key("clipboard-history")
+        .await
+        .map(|_| ())
+        .map_err(|e| ClipboardHistoryError::Encryption(e.to_string()))
+}
+
+/// Load all persisted entries, skipping records that fail to decode or decrypt
+pub async fn load_all(db: &DatabaseService) -> Result<Vec<ClipboardHistoryEntry>, DatabaseError> {
+    let stored = db.select::<StoredEntry>(CLIPBOARD_HISTORY_TABLE).await?;
+    let mut entries = Vec::with_capacity(stored.len());
+    for record in stored {
+        let entry_id = record.entry_id.clone();
+        match record.open().await {
+            Ok(entry) => entries.push(entry),
+            Err(e) => tracing::warn!("Skipping unreadable clipboard entry {}: {}", entry_id, e),
+        }
+    }
+    Ok(entries)
+}
+
+/// Upsert an entry
+///
+/// Fails with [`ClipboardHistoryError::Encryption`] for sensitive entries when no encryption
+/// passphrase is configured, leaving them unsaved. Callers skip those entries after
+/// [`check_encryption`] fails.
+pub async fn save_entry(
+    db: &DatabaseService,
+    entry: &ClipboardHistoryEntry,
+) -> Result<(), ClipboardHistoryError> {
+    let record = StoredEntry::seal(entry).await?;
+    let content = serde_json::to_string(&record)
+        .map_err(|e| ClipboardHistoryError::Database(e.to_string()))?;
+    let sql = format!("UPSERT {} CONTENT {}", record_id(&entry.id), content);
+    db.query(&sql)
+        .await?
+        .check()
+        .map_err(|e| ClipboardHistoryError::Database(e.to_string()))?;
+    Ok(())
+}
+
+/// Delete entries by id
+pub async fn delete_entries(db: &DatabaseService, ids: &[String]) -> Result<(), DatabaseError> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let sql = ids
+        .iter()
+        .map(|id| format!("DELETE {}", record_id(id)))
+        .collect::<Vec<_>>()
+        .join(";\n");
+    db.query(&sql)
+        .await?
+        .check()
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Clipboard history plugin
+
+use action_items_core::LauncherEvent;
+use action_items_ecs_clipboard::ClipboardChangeEvent;
+use action_items_ecs_search_aggregator::{
+    LocalSearchProviders, SearchRequested, SearchResultReceived,
+};
+use action_items_ecs_surrealdb::poll_persistence_tasks;
+use bevy::prelude::*;
+
+use crate::events::*;
+use crate::history::ClipboardHistory;
+use crate::systems::*;
+use crate::types::ClipboardHistoryConfig;
+
+/// Plugin recording clipboard changes into a searchable, persistent history
+///
+/// Builds on the watcher from `ClipboardPlugin`, which must be added as well. Persistence
+/// starts once `DatabasePlugin` has inserted the `DatabaseService`; without it, the history is
+/// kept in memory for the session only.
+#[derive(Default)]
+pub struct ClipboardHistoryPlugin {
+    pub config: ClipboardHistoryConfig,
+}
+
+impl ClipboardHistoryPlugin {
+    pub fn new(config: ClipboardHistoryConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for ClipboardHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClipboardHistory::new(self.config.clone()))
+            .insert_resource(RetentionTimer(Timer::new(
+                self.config.retention_interval,
+                TimerMode::Repeating,
+            )))
+            .init_resource::<LocalSearchProviders>()
+            .add_event::<ClipboardChangeEvent>()
+            .add_event::<SearchRequested>()
+            .add_event::<SearchResultReceived>()
+            .add_event::<LauncherEvent>()
+            .add_event::<ClipboardHistoryEntryAdded>()
+            .add_event::<ClipboardHistoryCopyIgnored>()
+            .add_event::<ClipboardHistoryPinRequested>()
+            .add_event::<ClipboardHistoryRemoveRequested>()
+            .add_event::<ClipboardHistoryClearRequested>()
+            .add_event::<ClipboardHistoryPasteRequested>()
+            .add_event::<ClipboardHistoryPasteEntryRequested>()
+            .add_event::<ClipboardHistoryPasteCompleted>()
+            .add_systems(Startup, register_search_provider)
+            .add_systems(
+                Update,
+                (
+                    load_clipboard_history,
+                    capture_clipboard_changes,
+                    handle_clipboard_capture_tasks,
+                    handle_history_management_requests,
+                    handle_paste_requests,
+                    search_clipboard_history,
+                    enforce_history_retention,
+                    handle_clipboard_history_tasks,
+                    poll_persistence_tasks::<ClipboardHistory>,
+                )
+                    .chain(),
+            );
+
+        tracing::info!("ClipboardHistoryPlugin initialized");
+    }
+}
@@ -0,0 +1,342 @@
+//! Privacy filters deciding which copies the history may keep
+//!
+//! Copies are filtered twice. Before the clipboard is read, copies carrying a
+//! [`ClipboardPrivacyMarker`] or coming from a denied application are ignored outright, so
+//! their content never leaves the clipboard. After reading, text is scanned for likely secrets,
+//! which are dropped or encrypted at rest depending on [`SecretHandling`].
+
+use std::collections::HashSet;
+use std::fmt;
+
+use action_items_ecs_clipboard::{ClipboardData, ClipboardPrivacyMarker};
+use serde::{Deserialize, Serialize};
+
+/// What to do with copies that look like secrets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SecretHandling {
+    /// Never record them
+    Drop,
+    /// Record them, encrypting their content in the database
+    ///
+    /// Falls back to keeping them for the current session only when no encryption passphrase
+    /// is configured, which is logged once when the history loads.
+    #[default]
+    Encrypt,
+    /// Record them like any other copy
+    Keep,
+}
+
+/// Privacy settings for clipboard history capture
+#[derive(Debug, Clone)]
+pub struct PrivacyConfig {
+    /// Ignore copies marked as concealed, typically passwords from password managers
+    pub ignore_concealed: bool,
+    /// Ignore copies marked as transient or auto-generated
+    pub ignore_transient: bool,
+    /// Bundle ids or application names whose copies are never recorded
+    pub denied_apps: HashSet<String>,
+    /// Handling of copies detected as secrets
+    pub secret_handling: SecretHandling,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            ignore_concealed: true,
+            ignore_transient: true,
+            denied_apps: [
+                "com.1password.1password",
+                "com.agilebits.onepassword7",
+                "com.bitwarden.desktop",
+                "org.keepassxc.keepassxc",
+                "com.lastpass.LastPass",
+                "com.apple.keychainaccess",
+                "com.apple.Passwords",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            secret_handling: SecretHandling::default(),
+        }
+    }
+}
+
+/// Kind of secret detected in copied text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretKind {
+    ApiKey,
+    Jwt,
+    PrivateKey,
+    CreditCard,
+}
+
+impl fmt::Display for SecretKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SecretKind::ApiKey => "API key",
+            SecretKind::Jwt => "JSON Web Token",
+            SecretKind::PrivateKey => "Private key",
+            SecretKind::CreditCard => "Credit card number",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Why a copy was not recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IgnoreReason {
+    /// The copying app marked the content
+    Marker(ClipboardPrivacyMarker),
+    /// The copying app is on the deny-list
+    DeniedApp(String),
+    /// The content looks like a secret and secrets are dropped
+    Secret(SecretKind),
+}
+
+impl fmt::Display for IgnoreReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IgnoreReason::Marker(marker) => write!(f, "clipboard marked as {marker:?}"),
+            IgnoreReason::DeniedApp(app) => write!(f, "copied from denied app {app}"),
+            IgnoreReason::Secret(kind) => write!(f, "content looks like a {kind}"),
+        }
+    }
+}
+
+impl PrivacyConfig {
+    /// Check a change before its content is read
+    pub fn check_source(
+        &self,
+        markers: &[ClipboardPrivacyMarker],
+        source_app: Option<&str>,
+    ) -> Result<(), IgnoreReason> {
+        for &marker in markers {
+            let ignored = match marker {
+                ClipboardPrivacyMarker::Concealed => self.ignore_concealed,
+                ClipboardPrivacyMarker::Transient | ClipboardPrivacyMarker::AutoGenerated => {
+                    self.ignore_transient
+                },
+            };
+            if ignored {
+                return Err(IgnoreReason::Marker(marker));
+            }
+        }
+
+        match source_app {
+            Some(app) if self.denied_apps.contains(app) => {
+                Err(IgnoreReason::DeniedApp(app.to_string()))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Check read content for secrets
+    ///
+    /// Returns the detected secret kind when the content may be kept but must be marked
+    /// sensitive.
+    pub fn check_content(
+        &self,
+        content: &ClipboardData,
+    ) -> Result<Option<SecretKind>, IgnoreReason> {
+        if self.secret_handling == SecretHandling::Keep {
+            return Ok(None);
+        }
+        match detect_secret_in(content) {
+            Some(kind) if self.secret_handling == SecretHandling::Drop => {
+                Err(IgnoreReason::Secret(kind))
+            },
+            detected => Ok(detected),
+        }
+    }
+}
+
+/// Detect a likely secret in clipboard content; only text-bearing formats are scanned
+pub fn detect_secret_in(content: &ClipboardData) -> Option<SecretKind> {
+    match content {
+        ClipboardData::Text(text) => detect_secret(text),
+        ClipboardData::Html { html, alt_text } => alt_text
+            .as_deref()
+            .and_then(detect_secret)
+            .or_else(|| detect_secret(html)),
+        ClipboardData::Image { .. } | ClipboardData::Files(_) => None,
+    }
+}
+
+/// Detect a likely secret in text
+pub fn detect_secret(text: &str) -> Option<SecretKind> {
+    if contains_private_key(text) {
+        return Some(SecretKind::PrivateKey);
+    }
+
+    let tokens = text.split(|c: char| c.is_whitespace() || "\"'`,;()[]{}<>".contains(c));
+    for token in tokens.filter(|t| !t.is_empty()) {
+        if is_jwt(token) {
+            return Some(SecretKind::Jwt);
+        }
+        if is_api_key(token) {
+            return Some(SecretKind::ApiKey);
+        }
+    }
+
+    if text.lines().any(is_secret_assignment) {
+        return Some(SecretKind::ApiKey);
+    }
+
+    if contains_card_number(text) {
+        return Some(SecretKind::CreditCard);
+    }
+
+    None
+}
+
+fn contains_private_key(text: &str) -> bool {
+    text.lines().any(|line| {
+        let line = line.trim();
+        line.starts_with("-----BEGIN ") && line.ends_with("PRIVATE KEY-----")
+    })
+}
+
+/// Three base64url segments with a JSON header, which always encodes to `eyJ`
+fn is_jwt(token: &str) -> bool {
+    let segments: Vec<&str> = token.split('.').collect();
+    segments.len() == 3
+        && segments[0].starts_with("eyJ")
+        && segments[1].len() >= 4
+        && segments.iter().all(|segment| {
+            segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// Tokens with the prefix of a well-known provider's secret key format
+fn is_api_key(token: &str) -> bool {
+    const PREFIXES: &[(&str, usize)] = &[
+        // (prefix, minimum total length)
+        ("sk-", 32),
+        ("sk_live_", 24),
+        ("sk_test_", 24),
+        ("rk_live_", 24),
+        ("ghp_", 40),
+        ("gho_", 40),
+        ("ghu_", 40),
+        ("ghs_", 40),
+        ("ghr_", 40),
+        ("github_pat_", 60),
+        ("glpat-", 26),
+        ("xoxb-", 24),
+        ("xoxp-", 24),
+        ("xoxa-", 24),
+        ("xoxr-", 24),
+        ("npm_", 40),
+        ("AIza", 39),
+        ("hf_", 37),
+    ];
+
+    let is_key_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if PREFIXES.iter().any(|(prefix, min_len)| {
+        token.starts_with(prefix) && token.len() >= *min_len && token.chars().all(is_key_char)
+    }) {
+        return true;
+    }
+
+    // AWS access key ids: AKIA or ASIA followed by 16 uppercase letters and digits
+    token.len() == 20
+        && (token.starts_with("AKIA") || token.starts_with("ASIA"))
+        && token[4..]
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// Lines like `API_KEY=...` or `"client_secret": "..."` with a non-trivial value
+fn is_secret_assignment(line: &str) -> bool {
+    const NAMES: &[&str] = &[
+        "api_key",
+        "apikey",
+        "api-key",
+        "secret",
+        "access_token",
+        "auth_token",
+        "password",
+        "passwd",
+    ];
+
+    let Some(separator) = line.find(['=', ':']) else {
+        return false;
+    };
+    let name = line[..separator]
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_lowercase();
+    let value = line[separator + 1..]
+        .trim()
+        .trim_end_matches(',')
+        .trim_matches(|c| c == '"' || c == '\'');
+
+    NAMES.iter().any(|n| name.ends_with(n))
+        && value.len() >= 8
+        && !value.contains(char::is_whitespace)
+}
+
+/// Runs of 13-19 digits, optionally grouped by single spaces or dashes, that pass the Luhn
+/// check and start like a card issuer number
+fn contains_card_number(text: &str) -> bool {
+    let chars: Vec<char> = text.chars().collect();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let boundary = start == 0 || !chars[start - 1].is_ascii_alphanumeric();
+        if !chars[start].is_ascii_digit() || !boundary {
+            start += 1;
+            continue;
+        }
+
+        let mut digits = Vec::new();
+        let mut end = start;
+        while end < chars.len() {
+            let c = chars[end];
+            if let Some(digit) = c.to_digit(10) {
+                digits.push(digit);
+            } else if !(matches!(c, ' ' | '-')
+                && chars.get(end + 1).is_some_and(char::is_ascii_digit))
+            {
+                break;
+            }
+            end += 1;
+        }
+
+        let delimited = chars.get(end).is_none_or(|c| !c.is_ascii_alphanumeric());
+        if delimited && (13..=19).contains(&digits.len()) && is_card_number(&digits) {
+            return true;
+        }
+        start = end.max(start + 1);
+    }
+
+    false
+}
+
+fn is_card_number(digits: &[u32]) -> bool {
+    let issuer = digits[0] * 10 + digits[1];
+    // Amex/Diners (3x), Visa (4x), Mastercard (51-55, 22-27), Discover/UnionPay (6x)
+    let known_issuer = matches!(issuer, 30..=39 | 40..=49 | 51..=55 | 22..=27 | 60..=69);
+    known_issuer && luhn_valid(digits)
+}
+
+/// Luhn checksum over a sequence of decimal digits
+pub fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| {
+            if i % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                digit
+            }
+        })
+        .sum();
+    !digits.is_empty() && sum % 10 == 0
+}
@@ -0,0 +1,385 @@
+//! Bevy systems for clipboard history capture, persistence and requests
+
+use action_items_core::{LauncherEvent, LauncherEventType};
+use action_items_ecs_clipboard::{
+    ArboardManager, ClipboardChangeEvent, ClipboardData, ClipboardResource,
+};
+use action_items_ecs_search_aggregator::{SearchRequested, SearchResult, SearchResultReceived};
+use action_items_ecs_surrealdb::{DatabaseService, spawn_write, start_loading};
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use tracing::{debug, info, warn};
+
+use crate::capture::{frontmost_application, read_clipboard};
+use crate::events::*;
+use crate::history::{ClipboardHistory, unix_millis};
+use crate::persistence;
+use crate::privacy::SecretHandling;
+use crate::types::{ClipboardHistoryEntry, ClipboardHistoryError};
+
+/// Search provider id of clipboard history results
+pub const CLIPBOARD_HISTORY_PROVIDER_ID: &str = "clipboard-history";
+/// Prefix of launcher action ids that paste a history entry
+pub const PASTE_ACTION_PREFIX: &str = "clipboard_history_";
+/// Cap on history entries shown for a single query
+const MAX_SEARCH_RESULTS: usize = 20;
+/// Maximum characters of entry text shown as a result title
+const PREVIEW_CHARS: usize = 80;
+
+/// Component for clipboard history paste tasks
+#[derive(Component)]
+pub struct ClipboardHistoryTask(pub Task<CommandQueue>);
+
+/// Component for reading clipboard content after a change
+#[derive(Component)]
+pub struct ClipboardCaptureTask {
+    pub source_app: Option<String>,
+    pub task: Task<Result<Option<ClipboardData>, action_items_ecs_clipboard::ClipboardError>>,
+}
+
+/// Timer for periodic retention enforcement
+#[derive(Resource)]
+pub struct RetentionTimer(pub Timer);
+
+/// Register the history as a local search provider
+pub fn register_search_provider(
+    mut providers: ResMut<action_items_ecs_search_aggregator::LocalSearchProviders>,
+) {
+    providers.register(CLIPBOARD_HISTORY_PROVIDER_ID);
+}
+
+/// Apply the schema and load persisted history once the database is available
+pub fn load_clipboard_history(
+    mut commands: Commands,
+    mut history: ResMut<ClipboardHistory>,
+    db_service: Option<Res<DatabaseService>>,
+) {
+    start_loading(
+        &mut commands,
+        history.as_mut(),
+        db_service.as_deref(),
+        |db| async move {
+            let entries = persistence::load_all(&db).await?;
+            Ok((entries, persistence::check_encryption().await))
+        },
+        |world, (entries, encryption): (Vec<ClipboardHistoryEntry>, _)| {
+            let Some(mut history) = world.get_resource_mut::<ClipboardHistory>() else {
+                return;
+            };
+            history.persist_sensitive = encryption.is_ok();
+            if let Err(e) = encryption
+                && history.config.privacy.secret_handling == SecretHandling::Encrypt
+            {
+                info!(
+                    "Clipboard entries detected as secrets are kept for this session only: {}",
+                    e
+                );
+            }
+
+            info!("Loaded {} clipboard history entries", entries.len());
+            let unsaved = history.load(entries);
+            let evicted = history.enforce_retention(unix_millis());
+            for entry in unsaved.into_iter().filter(|e| !evicted.contains(&e.id)) {
+                spawn_save(world, entry);
+            }
+            spawn_delete(world, evicted);
+        },
+    );
+}
+
+/// Read the clipboard whenever the watcher reports a change that passes the privacy filters
+pub fn capture_clipboard_changes(
+    mut commands: Commands,
+    mut events: EventReader<ClipboardChangeEvent>,
+    pending: Query<(), With<ClipboardCaptureTask>>,
+    history: Res<ClipboardHistory>,
+    mut ignored_events: EventWriter<ClipboardHistoryCopyIgnored>,
+) {
+    // Several changes in one frame collapse into a single read of the latest content
+    let Some(change) = events.read().last() else {
+        return;
+    };
+    if !pending.is_empty() {
+        return;
+    }
+
+    // Concealed or denied copies are never read, so their content stays out of memory too
+    let source_app = frontmost_application();
+    if let Err(reason) = history
+        .config
+        .privacy
+        .check_source(&change.markers, source_app.as_deref())
+    {
+        debug!("Not recording clipboard change: {}", reason);
+        ignored_events.write(ClipboardHistoryCopyIgnored { reason });
+        return;
+    }
+
+    let task = AsyncComputeTaskPool::get().spawn(read_clipboard());
+    commands.spawn(ClipboardCaptureTask { source_app, task });
+}
+
+/// Record captured content and persist the result
+pub fn handle_clipboard_capture_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut ClipboardCaptureTask)>,
+    mut history: ResMut<ClipboardHistory>,
+    mut added_events: EventWriter<ClipboardHistoryEntryAdded>,
+    mut ignored_events: EventWriter<ClipboardHistoryCopyIgnored>,
+) {
+    for (entity, mut task) in &mut tasks {
+        let Some(result) = block_on(future::poll_once(&mut task.task)) else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+
+        let content = match result {
+            Ok(Some(content)) => content,
+            Ok(None) => continue,
+            Err(e) => {
+                debug!("Could not read clipboard for history: {}", e);
+                continue;
+            },
+        };
+
+        let sensitive = match history.config.privacy.check_content(&content) {
+            Ok(sensitive) => sensitive,
+            Err(reason) => {
+                debug!("Not recording clipboard content: {}", reason);
+                ignored_events.write(ClipboardHistoryCopyIgnored { reason });
+                continue;
+            },
+        };
+
+        let now = unix_millis();
+        let mut entry = ClipboardHistoryEntry::new(content, task.source_app.take(), now);
+        if let Some(kind) = sensitive {
+            entry.mark_sensitive(kind);
+        }
+        let Some(outcome) = history.record(entry, now) else {
+            debug!("Skipping clipboard content above the history size limit");
+            continue;
+        };
+
+        added_events.write(ClipboardHistoryEntryAdded {
+            entry_id: outcome.entry.id.clone(),
+            deduplicated: outcome.deduplicated,
+        });
+        commands.queue(move |world: &mut World| {
+            spawn_save(world, outcome.entry);
+            spawn_delete(world, outcome.evicted);
+        });
+    }
+}
+
+/// Handle pin, remove and clear requests
+pub fn handle_history_management_requests(
+    mut commands: Commands,
+    mut pin_events: EventReader<ClipboardHistoryPinRequested>,
+    mut remove_events: EventReader<ClipboardHistoryRemoveRequested>,
+    mut clear_events: EventReader<ClipboardHistoryClearRequested>,
+    mut history: ResMut<ClipboardHistory>,
+) {
+    for event in pin_events.read() {
+        match history.set_pinned(&event.entry_id, event.pinned) {
+            Some(entry) => commands.queue(move |world: &mut World| spawn_save(world, entry)),
+            None => warn!("Cannot pin unknown clipboard entry {}", event.entry_id),
+        }
+    }
+
+    let mut removed: Vec<String> = remove_events
+        .read()
+        .filter_map(|event| history.remove(&event.entry_id).map(|entry| entry.id))
+        .collect();
+
+    for event in clear_events.read() {
+        removed.extend(history.clear(event.keep_pinned));
+    }
+
+    if !removed.is_empty() {
+        commands.queue(move |world: &mut World| spawn_delete(world, removed));
+    }
+}
+
+/// Put requested history entries back on the clipboard and paste them into the focused app
+pub fn handle_paste_requests(
+    mut commands: Commands,
+    mut position_events: EventReader<ClipboardHistoryPasteRequested>,
+    mut entry_events: EventReader<ClipboardHistoryPasteEntryRequested>,
+    mut launcher_events: EventReader<LauncherEvent>,
+    history: Res<ClipboardHistory>,
+    clipboard: Res<ClipboardResource>,
+    mut completed_events: EventWriter<ClipboardHistoryPasteCompleted>,
+) {
+    let by_position = position_events.read().map(|event| {
+        history
+            .get_position(event.position)
+            .ok_or(ClipboardHistoryError::NoSuchPosition(event.position))
+    });
+    let by_id = entry_events
+        .read()
+        .map(|event| event.entry_id.clone())
+        .chain(launcher_events.read().filter_map(|event| {
+            match &event.event_type {
+                LauncherEventType::Execute(action_id) => action_id
+                    .strip_prefix(PASTE_ACTION_PREFIX)
+                    .map(str::to_string),
+                _ => None,
+            }
+        }))
+        .map(|entry_id| {
+            history
+                .get(&entry_id)
+                .ok_or(ClipboardHistoryError::NoSuchEntry(entry_id))
+        });
+
+    for lookup in by_position.chain(by_id).collect::<Vec<_>>() {
+        let completed = match lookup {
+            Ok(entry) => {
+                let result = clipboard
+                    .set_sync(entry.content.clone())
+                    .map_err(ClipboardHistoryError::from);
+                if result.is_ok() {
+                    spawn_paste(&mut commands);
+                }
+                ClipboardHistoryPasteCompleted {
+                    entry_id: Some(entry.id.clone()),
+                    result,
+                }
+            },
+            Err(e) => ClipboardHistoryPasteCompleted {
+                entry_id: None,
+                result: Err(e),
+            },
+        };
+        if let Err(e) = &completed.result {
+            warn!("Clipboard history paste failed: {}", e);
+        }
+        completed_events.write(completed);
+    }
+}
+
+/// Send the paste shortcut to the focused application in the background
+fn spawn_paste(commands: &mut Commands) {
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        if let Err(e) = ArboardManager::paste(0).await {
+            warn!("Failed to paste clipboard history entry: {}", e);
+        }
+        CommandQueue::default()
+    });
+    commands.spawn(ClipboardHistoryTask(task));
+}
+
+/// Answer aggregated launcher searches from the history
+pub fn search_clipboard_history(
+    mut requests: EventReader<SearchRequested>,
+    mut results: EventWriter<SearchResultReceived>,
+    history: Res<ClipboardHistory>,
+) {
+    for request in requests.read() {
+        if !request
+            .requesting_plugins
+            .iter()
+            .any(|id| id == CLIPBOARD_HISTORY_PROVIDER_ID)
+        {
+            continue;
+        }
+
+        let started = std::time::Instant::now();
+        let matches = history
+            .search(&request.query, MAX_SEARCH_RESULTS)
+            .into_iter()
+            .map(|(entry, score)| SearchResult {
+                title: entry.preview(PREVIEW_CHARS),
+                description: describe_entry(entry),
+                action: format!("{PASTE_ACTION_PREFIX}{}", entry.id),
+                icon: Some(if entry.pinned { "📌" } else { "📋" }.to_string()),
+                score,
+                plugin_id: CLIPBOARD_HISTORY_PROVIDER_ID.to_string(),
+                title_matches: Vec::new(),
+            })
+            .collect();
+
+        results.write(SearchResultReceived {
+            search_id: request.search_id,
+            plugin_id: CLIPBOARD_HISTORY_PROVIDER_ID.to_string(),
+            results: matches,
+            execution_time_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+}
+
+/// Periodically drop entries that aged out while nothing was copied
+pub fn enforce_history_retention(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timer: ResMut<RetentionTimer>,
+    mut history: ResMut<ClipboardHistory>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let evicted = history.enforce_retention(unix_millis());
+    if !evicted.is_empty() {
+        debug!(
+            "Clipboard history retention evicted {} entries",
+            evicted.len()
+        );
+        commands.queue(move |world: &mut World| spawn_delete(world, evicted));
+    }
+}
+
+/// Poll clipboard history paste tasks
+pub fn handle_clipboard_history_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut ClipboardHistoryTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.0)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn describe_entry(entry: &ClipboardHistoryEntry) -> String {
+    let mut parts = vec![entry.format().to_string()];
+    if let Some(source) = &entry.source_app {
+        parts.push(source.clone());
+    }
+    if entry.copy_count > 1 {
+        parts.push(format!("copied {} times", entry.copy_count));
+    }
+    parts.join(" · ")
+}
+
+/// Persist an entry in the background once the history is backed by the database
+///
+/// Sensitive entries are skipped when they cannot be encrypted.
+fn spawn_save(world: &mut World, entry: ClipboardHistoryEntry) {
+    let persist_sensitive = world
+        .get_resource::<ClipboardHistory>()
+        .is_some_and(|history| history.persist_sensitive);
+    if entry.sensitive.is_some() && !persist_sensitive {
+        return;
+    }
+    spawn_write::<ClipboardHistory, _>(world, move |db| async move {
+        if let Err(e) = persistence::save_entry(&db, &entry).await {
+            warn!("Failed to persist clipboard entry {}: {}", entry.id, e);
+        }
+    });
+}
+
+/// Delete entries in the background once the history is backed by the database
+fn spawn_delete(world: &mut World, ids: Vec<String>) {
+    if ids.is_empty() {
+        return;
+    }
+    spawn_write::<ClipboardHistory, _>(world, move |db| async move {
+        if let Err(e) = persistence::delete_entries(&db, &ids).await {
+            warn!("Failed to delete {} clipboard entries: {}", ids.len(), e);
+        }
+    });
+}
@@ -0,0 +1,242 @@
+//! Clipboard history entry types, configuration and errors
+
+use std::time::Duration;
+
+use action_items_ecs_clipboard::{ClipboardData, ClipboardError, ClipboardFormat};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::privacy::{PrivacyConfig, SecretKind};
+
+/// Retention and capture limits for the clipboard history
+#[derive(Debug, Clone)]
+pub struct ClipboardHistoryConfig {
+    /// Maximum number of unpinned entries kept
+    pub max_entries: usize,
+    /// Unpinned entries last copied longer ago than this are dropped
+    pub max_age: Duration,
+    /// Maximum total content size of unpinned entries, in bytes
+    pub max_total_bytes: usize,
+    /// Copies larger than this are never recorded, in bytes
+    pub max_entry_bytes: usize,
+    /// How often retention limits are re-applied while idle
+    pub retention_interval: Duration,
+    /// Filters for concealed content, denied apps and secrets
+    pub privacy: PrivacyConfig,
+}
+
+impl Default for ClipboardHistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1000,
+            max_age: Duration::from_secs(30 * 24 * 60 * 60),
+            max_total_bytes: 512 * 1024 * 1024,
+            max_entry_bytes: 64 * 1024 * 1024,
+            retention_interval: Duration::from_secs(60 * 60),
+            privacy: PrivacyConfig::default(),
+        }
+    }
+}
+
+/// A single clipboard history entry
+///
+/// The id is derived from the content, so copying the same content again updates the existing
+/// entry instead of adding a duplicate. Sensitive entries get a random id instead, since a bare
+/// hash of a short secret such as a card number can be brute-forced from the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipboardHistoryEntry {
+    /// Content hash, also used as the database record key
+    pub id: String,
+    /// Copied content
+    pub content: ClipboardData,
+    /// Size of the content in bytes, used for size-based retention
+    pub byte_size: usize,
+    /// Bundle id or executable name of the application that owned the clipboard
+    pub source_app: Option<String>,
+    /// Unix timestamp (milliseconds) of the first copy
+    pub first_copied_at: i64,
+    /// Unix timestamp (milliseconds) of the most recent copy
+    pub last_copied_at: i64,
+    /// Number of times this content was copied
+    pub copy_count: u32,
+    /// Pinned entries are exempt from retention and listed first
+    pub pinned: bool,
+    /// Detected secret kind; sensitive content is encrypted at rest and hidden in previews
+    pub sensitive: Option<SecretKind>,
+}
+
+impl ClipboardHistoryEntry {
+    /// Create an entry for content copied at `now` (Unix milliseconds)
+    pub fn new(content: ClipboardData, source_app: Option<String>, now: i64) -> Self {
+        Self {
+            id: content_id(&content),
+            byte_size: content_size(&content),
+            content,
+            source_app,
+            first_copied_at: now,
+            last_copied_at: now,
+            copy_count: 1,
+            pinned: false,
+            sensitive: None,
+        }
+    }
+
+    /// Mark the entry as holding a secret, replacing its content-derived id
+    pub fn mark_sensitive(&mut self, kind: SecretKind) {
+        self.id = uuid::Uuid::new_v4().simple().to_string();
+        self.sensitive = Some(kind);
+    }
+
+    /// Whether both entries hold the same content
+    ///
+    /// Content-derived ids make this an id comparison, except for sensitive entries whose ids
+    /// are random.
+    pub fn same_content(&self, other: &ClipboardHistoryEntry) -> bool {
+        match (self.sensitive, other.sensitive) {
+            (None, None) => self.id == other.id,
+            (Some(_), Some(_)) => self.id == other.id || self.content == other.content,
+            _ => false,
+        }
+    }
+
+    /// Clipboard format of the entry
+    pub fn format(&self) -> ClipboardFormat {
+        match &self.content {
+            ClipboardData::Text(_) => ClipboardFormat::Text,
+            ClipboardData::Html { .. } => ClipboardFormat::Html,
+            ClipboardData::Image { .. } => ClipboardFormat::Image,
+            ClipboardData::Files(_) => ClipboardFormat::Files,
+        }
+    }
+
+    /// Single-line summary suitable for result titles; sensitive content is masked
+    pub fn preview(&self, max_chars: usize) -> String {
+        if let Some(kind) = self.sensitive {
+            return format!("•••••••• ({kind})");
+        }
+
+        let text = match &self.content {
+            ClipboardData::Text(text) => text.clone(),
+            ClipboardData::Html { html, alt_text } => {
+                alt_text.clone().unwrap_or_else(|| html.clone())
+            },
+            ClipboardData::Image { width, height, .. } => format!("Image ({width}×{height})"),
+            ClipboardData::Files(files) => files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.chars().count() <= max_chars {
+            line
+        } else {
+            let mut truncated: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+            truncated.push('…');
+            truncated
+        }
+    }
+
+    /// Text that history searches match against; images have none
+    ///
+    /// Sensitive entries only match their secret kind, never their content.
+    pub fn searchable_text(&self) -> Option<String> {
+        if let Some(kind) = self.sensitive {
+            return Some(kind.to_string());
+        }
+
+        match &self.content {
+            ClipboardData::Text(text) => Some(text.clone()),
+            ClipboardData::Html { html, alt_text } => {
+                Some(alt_text.clone().unwrap_or_else(|| html.clone()))
+            },
+            ClipboardData::Image { .. } => None,
+            ClipboardData::Files(files) => Some(
+                files
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        }
+    }
+}
+
+/// Stable content hash used for deduplication
+pub fn content_id(content: &ClipboardData) -> String {
+    let mut hasher = Sha256::new();
+    match content {
+        ClipboardData::Text(text) => {
+            hasher.update(b"text\0");
+            hasher.update(text.as_bytes());
+        },
+        ClipboardData::Html { html, alt_text } => {
+            hasher.update(b"html\0");
+            hasher.update(html.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(alt_text.as_deref().unwrap_or_default().as_bytes());
+        },
+        ClipboardData::Image {
+            data,
+            width,
+            height,
+        } => {
+            hasher.update(b"image\0");
+            hasher.update((*width as u64).to_le_bytes());
+            hasher.update((*height as u64).to_le_bytes());
+            hasher.update(data);
+        },
+        ClipboardData::Files(files) => {
+            hasher.update(b"files\0");
+            for path in files {
+                hasher.update(path.to_string_lossy().as_bytes());
+                hasher.update(b"\0");
+            }
+        },
+    }
+
+    hasher.finalize()[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Approximate in-memory size of clipboard content in bytes
+pub fn content_size(content: &ClipboardData) -> usize {
+    match content {
+        ClipboardData::Text(text) => text.len(),
+        ClipboardData::Html { html, alt_text } => {
+            html.len() + alt_text.as_ref().map_or(0, String::len)
+        },
+        ClipboardData::Image { data, .. } => data.len(),
+        ClipboardData::Files(files) => files.iter().map(|p| p.as_os_str().len()).sum(),
+    }
+}
+
+/// Errors from clipboard history operations
+#[derive(Debug, Clone, thiserror::Error, Serialize, Deserialize)]
+pub enum ClipboardHistoryError {
+    #[error("No clipboard history entry at position {0}")]
+    NoSuchPosition(usize),
+    #[error("No clipboard history entry with id {0}")]
+    NoSuchEntry(String),
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+}
+
+impl From<action_items_ecs_surrealdb::DatabaseError> for ClipboardHistoryError {
+    fn from(error: action_items_ecs_surrealdb::DatabaseError) -> Self {
+        ClipboardHistoryError::Database(error.to_string())
+    }
+}
+
+impl From<ClipboardError> for ClipboardHistoryError {
+    fn from(error: ClipboardError) -> Self {
+        ClipboardHistoryError::Clipboard(error.to_string())
+    }
+}
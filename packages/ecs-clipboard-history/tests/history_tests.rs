@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use action_items_ecs_clipboard::ClipboardData;
+use action_items_ecs_clipboard_history::{
+    ClipboardHistory, ClipboardHistoryConfig, ClipboardHistoryEntry,
+};
+
+const MINUTE: i64 = 60 * 1000;
+
+fn text(value: &str, now: i64) -> ClipboardHistoryEntry {
+    ClipboardHistoryEntry::new(ClipboardData::Text(value.to_string()), None, now)
+}
+
+fn history(max_entries: usize) -> ClipboardHistory {
+    ClipboardHistory::new(ClipboardHistoryConfig {
+        max_entries,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_identical_content_is_deduplicated() {
+    let mut history = history(10);
+    history.record(text("hello", 0), 0);
+    history.record(text("world", MINUTE), MINUTE);
+    let outcome = history
+        .record(text("hello", 2 * MINUTE), 2 * MINUTE)
+        .unwrap();
+
+    assert!(outcome.deduplicated);
+    assert_eq!(outcome.entry.copy_count, 2);
+    assert_eq!(outcome.entry.first_copied_at, 0);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get_position(1).unwrap().id, outcome.entry.id);
+}
+
+#[test]
+fn test_retention_evicts_oldest_unpinned_entries() {
+    let mut history = history(2);
+    let first = history.record(text("one", 0), 0).unwrap().entry;
+    history.set_pinned(&first.id, true);
+    let second = history.record(text("two", MINUTE), MINUTE).unwrap().entry;
+    history.record(text("three", 2 * MINUTE), 2 * MINUTE);
+    let outcome = history
+        .record(text("four", 3 * MINUTE), 3 * MINUTE)
+        .unwrap();
+
+    assert_eq!(outcome.evicted, vec![second.id]);
+    assert_eq!(history.len(), 3);
+    assert!(history.get(&first.id).is_some());
+}
+
+#[test]
+fn test_retention_by_age() {
+    let mut history = ClipboardHistory::new(ClipboardHistoryConfig {
+        max_age: Duration::from_secs(60),
+        ..Default::default()
+    });
+    let old = history.record(text("old", 0), 0).unwrap().entry;
+    history.record(text("new", 2 * MINUTE), 2 * MINUTE);
+
+    assert!(history.get(&old.id).is_none());
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn test_pinned_entries_come_first() {
+    let mut history = history(10);
+    let first = history.record(text("first", 0), 0).unwrap().entry;
+    history.record(text("second", MINUTE), MINUTE);
+    history.set_pinned(&first.id, true);
+
+    assert_eq!(history.get_position(1).unwrap().id, first.id);
+    assert!(history.get_position(0).is_none());
+    assert!(history.get_position(3).is_none());
+
+    let removed = history.clear(true);
+    assert_eq!(removed.len(), 1);
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn test_load_merges_copies_made_before_loading() {
+    let mut stored = text("shared", 0);
+    stored.copy_count = 3;
+    stored.pinned = true;
+
+    let mut history = history(10);
+    history.record(text("shared", MINUTE), MINUTE);
+    let unsaved = history.load(vec![stored]);
+
+    assert_eq!(unsaved.len(), 1);
+    assert_eq!(unsaved[0].copy_count, 4);
+    assert_eq!(unsaved[0].first_copied_at, 0);
+    assert!(unsaved[0].pinned);
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn test_search_matches_all_terms() {
+    let mut history = history(10);
+    history.record(text("cargo build --release", 0), 0);
+    history.record(text("release notes draft", MINUTE), MINUTE);
+    history.record(text("unrelated", 2 * MINUTE), 2 * MINUTE);
+
+    let results = history.search("release cargo", 10);
+    assert_eq!(results.len(), 1);
+
+    let results = history.search("release", 10);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0.preview(80), "release notes draft");
+}
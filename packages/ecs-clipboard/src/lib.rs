@@ -20,7 +20,9 @@ pub use manager::ArboardManager;
 pub use plugin::{
     ClipboardChanged, ClipboardPlugin, ClipboardRequest, ClipboardResource, ClipboardResponse,
 };
-pub use types::{ClipboardData, ClipboardError, ClipboardFormat, ClipboardChangeEvent};
+pub use types::{
+    ClipboardChangeEvent, ClipboardData, ClipboardError, ClipboardFormat, ClipboardPrivacyMarker,
+};
 
 /// Convenience function to add the clipboard system to a Bevy app
 pub fn add_clipboard(app: &mut App) {
@@ -1,11 +1,16 @@
 //! Simple arboard wrapper with async compatibility
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use arboard::{Clipboard, ImageData};
 use tracing::debug;
 
-use crate::types::{ClipboardData, ClipboardError, ClipboardFormat};
+use crate::types::{ClipboardData, ClipboardError, ClipboardFormat, ClipboardPrivacyMarker};
+
+/// Delay before sending the paste shortcut, giving the launcher window time to hide so the
+/// keystroke reaches the previously focused application
+const PASTE_FOCUS_DELAY: Duration = Duration::from_millis(150);
 
 /// Async arboard wrapper - no blocking operations
 pub struct ArboardManager;
@@ -255,4 +260,154 @@ impl ArboardManager {
             })
             .await
     }
+
+    /// Paste the clipboard into the focused application by sending the platform paste shortcut
+    ///
+    /// `move_left` presses the left arrow key that many times afterwards, placing the cursor
+    /// inside the pasted text. Sending keystrokes needs the Accessibility permission on macOS.
+    pub async fn paste(move_left: usize) -> Result<(), ClipboardError> {
+        debug!("Pasting clipboard into focused application");
+        // The focus delay runs on its own thread so it never holds a task pool worker
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        std::thread::Builder::new()
+            .name("clipboard-paste".to_string())
+            .spawn(move || {
+                std::thread::sleep(PASTE_FOCUS_DELAY);
+                let _ = sender.send(send_paste_keystroke(move_left));
+            })
+            .map_err(|e| ClipboardError::PlatformError(e.to_string()))?;
+        receiver.await.unwrap_or(Err(ClipboardError::Unknown))
+    }
+
+    /// Get the privacy markers currently on the clipboard
+    ///
+    /// Markers are advisory metadata set by the copying app, so failures to read them are
+    /// treated as no markers.
+    pub async fn privacy_markers() -> Vec<ClipboardPrivacyMarker> {
+        debug!("Getting clipboard privacy markers");
+        let task_pool = bevy::tasks::AsyncComputeTaskPool::get();
+        task_pool.spawn(async move { platform_privacy_markers() }).await
+    }
+}
+
+fn send_paste_keystroke(move_left: usize) -> Result<(), ClipboardError> {
+    use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+    let platform_error = |e: &dyn std::fmt::Display| ClipboardError::PlatformError(e.to_string());
+
+    #[cfg(target_os = "macos")]
+    let modifier = Key::Meta;
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Key::Control;
+
+    let mut enigo = Enigo::new(&Settings::default()).map_err(|e| platform_error(&e))?;
+    enigo
+        .key(modifier, Direction::Press)
+        .map_err(|e| platform_error(&e))?;
+    let pasted = enigo.key(Key::Unicode('v'), Direction::Click);
+    // Always release the modifier so it does not stay stuck after a failed click
+    let released = enigo.key(modifier, Direction::Release);
+    pasted.and(released).map_err(|e| platform_error(&e))?;
+
+    for _ in 0..move_left {
+        enigo
+            .key(Key::LeftArrow, Direction::Click)
+            .map_err(|e| platform_error(&e))?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn platform_privacy_markers() -> Vec<ClipboardPrivacyMarker> {
+    // Conventions from nspasteboard.org, plus 1Password's own type
+    use objc2::msg_send_id;
+    use objc2::rc::Retained;
+    use objc2_app_kit::NSPasteboard;
+    use objc2_foundation::{NSArray, NSString};
+
+    let types: Option<Retained<NSArray<NSString>>> = unsafe {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        msg_send_id![&pasteboard, types]
+    };
+
+    let mut markers = Vec::new();
+    for pasteboard_type in types.iter().flat_map(|types| types.iter()) {
+        let marker = match pasteboard_type.to_string().as_str() {
+            "org.nspasteboard.ConcealedType" | "com.agilebits.onepassword" => {
+                ClipboardPrivacyMarker::Concealed
+            },
+            "org.nspasteboard.TransientType" => ClipboardPrivacyMarker::Transient,
+            "org.nspasteboard.AutoGeneratedType" => ClipboardPrivacyMarker::AutoGenerated,
+            _ => continue,
+        };
+        if !markers.contains(&marker) {
+            markers.push(marker);
+        }
+    }
+    markers
+}
+
+#[cfg(target_os = "windows")]
+fn platform_privacy_markers() -> Vec<ClipboardPrivacyMarker> {
+    use windows::Win32::Foundation::HGLOBAL;
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, GetClipboardData, IsClipboardFormatAvailable, OpenClipboard,
+        RegisterClipboardFormatW,
+    };
+    use windows::Win32::System::Memory::{GlobalLock, GlobalUnlock};
+    use windows::core::w;
+
+    let mut markers = Vec::new();
+    unsafe {
+        let exclude = RegisterClipboardFormatW(w!("ExcludeClipboardContentFromMonitorProcessing"));
+        if IsClipboardFormatAvailable(exclude).is_ok() {
+            markers.push(ClipboardPrivacyMarker::Concealed);
+        }
+
+        // A DWORD of zero opts the content out of the system clipboard history
+        let history = RegisterClipboardFormatW(w!("CanIncludeInClipboardHistory"));
+        if IsClipboardFormatAvailable(history).is_ok() && OpenClipboard(None).is_ok() {
+            if let Ok(handle) = GetClipboardData(history) {
+                let global = HGLOBAL(handle.0);
+                let value = GlobalLock(global) as *const u32;
+                if !value.is_null() {
+                    if *value == 0 {
+                        markers.push(ClipboardPrivacyMarker::Transient);
+                    }
+                    let _ = GlobalUnlock(global);
+                }
+            }
+            let _ = CloseClipboard();
+        }
+    }
+    markers
+}
+
+#[cfg(target_os = "linux")]
+fn platform_privacy_markers() -> Vec<ClipboardPrivacyMarker> {
+    use std::process::Command;
+
+    // arboard does not expose selection targets, so ask the session's clipboard tool
+    let output = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Command::new("wl-paste").arg("--list-types").output()
+    } else {
+        Command::new("xclip")
+            .args(["-selection", "clipboard", "-t", "TARGETS", "-o"])
+            .output()
+    };
+
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    let targets = String::from_utf8_lossy(&output.stdout);
+    if targets.lines().any(|target| target.trim() == "x-kde-passwordManagerHint") {
+        vec![ClipboardPrivacyMarker::Concealed]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn platform_privacy_markers() -> Vec<ClipboardPrivacyMarker> {
+    Vec::new()
 }
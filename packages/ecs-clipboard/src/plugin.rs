@@ -7,7 +7,7 @@ use bevy::time::{Timer, TimerMode};
 use tracing::{error, info};
 
 use crate::manager::ArboardManager;
-use crate::types::{ClipboardData, ClipboardError, ClipboardFormat};
+use crate::types::{ClipboardData, ClipboardError, ClipboardFormat, ClipboardPrivacyMarker};
 
 /// Simple Resource for clipboard state - following Bevy ECS patterns
 #[derive(Resource)]
@@ -177,10 +177,14 @@ impl Default for ClipboardWatcher {
     }
 }
 
+/// Result of a clipboard check: the current sequence number and, when it changed, the privacy
+/// markers on the new content
+pub type ClipboardCheckResult = Result<(u64, Vec<ClipboardPrivacyMarker>), ClipboardError>;
+
 /// Component for async clipboard sequence number checking tasks
 #[derive(Component)]
 pub struct ClipboardCheckTask {
-    pub task: Task<Result<u64, ClipboardError>>,
+    pub task: Task<ClipboardCheckResult>,
     pub previous_sequence: u64,
     pub requester: Entity,
 }
@@ -447,7 +451,16 @@ fn detect_clipboard_changes(
            !existing_tasks.iter().any(|task| task.requester == entity) {
             
             let previous_sequence = watcher.last_sequence;
-            let task = AsyncComputeTaskPool::get().spawn(get_clipboard_sequence());
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                let sequence = get_clipboard_sequence().await?;
+                // Markers are read right after the change so they describe the same content
+                let markers = if sequence != previous_sequence {
+                    ArboardManager::privacy_markers().await
+                } else {
+                    Vec::new()
+                };
+                Ok((sequence, markers))
+            });
             
             commands.spawn(ClipboardCheckTask {
                 task,
@@ -468,7 +481,7 @@ fn process_clipboard_check_tasks(
     for (task_entity, mut task) in &mut tasks {
         if let Some(result) = block_on(future::poll_once(&mut task.task)) {
             match result {
-                Ok(current_sequence) => {
+                Ok((current_sequence, markers)) => {
                     // Check if sequence changed
                     if current_sequence != task.previous_sequence {
                         // Update watcher with new sequence
@@ -481,6 +494,7 @@ fn process_clipboard_check_tasks(
                                 content: None, // Lazy loading - plugins can request content
                                 timestamp: std::time::Instant::now(),
                                 watcher: task.requester,
+                                markers,
                             });
                             
                             tracing::debug!("Clipboard changed: sequence {} -> {}", 
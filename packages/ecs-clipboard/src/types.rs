@@ -146,6 +146,21 @@ impl From<arboard::ImageData<'_>> for ClipboardData {
     }
 }
 
+/// Markers apps place on the clipboard to ask managers and history tools not to record a copy
+///
+/// Detected from the `org.nspasteboard.*` pasteboard types on macOS, the
+/// `ExcludeClipboardContentFromMonitorProcessing` and `CanIncludeInClipboardHistory` formats on
+/// Windows, and the `x-kde-passwordManagerHint` target on Linux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClipboardPrivacyMarker {
+    /// Content is sensitive, typically a password copied from a password manager
+    Concealed,
+    /// Content is only on the clipboard briefly and should not be kept
+    Transient,
+    /// Content was generated by an app rather than copied by the user
+    AutoGenerated,
+}
+
 /// Event fired when clipboard content changes - enables plugin ecosystems
 #[derive(Event, Debug)]
 pub struct ClipboardChangeEvent {
@@ -153,4 +168,13 @@ pub struct ClipboardChangeEvent {
     pub content: Option<ClipboardData>,
     pub timestamp: std::time::Instant,
     pub watcher: Entity,
+    /// Privacy markers present on the clipboard when the change was detected
+    pub markers: Vec<ClipboardPrivacyMarker>,
+}
+
+impl ClipboardChangeEvent {
+    /// Whether the source app asked for this content to stay out of clipboard history
+    pub fn is_private(&self) -> bool {
+        !self.markers.is_empty()
+    }
 }
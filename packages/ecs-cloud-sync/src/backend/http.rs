@@ -0,0 +1,309 @@
+use std::sync::Arc;
+
+use action_items_ecs_fetch::{Client, HttpClientPool, Method, StatusCode, Url};
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use tokio::runtime::Handle;
+
+use super::{SyncBackend, validate_name};
+use crate::error::SyncError;
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<propfind xmlns="DAV:"><prop><resourcetype/></prop></propfind>"#;
+
+/// How an [`HttpBackend`] lists objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpListing {
+    /// `ListObjectsV2` on an S3-compatible bucket; the base URL must be the bucket root
+    S3,
+    /// `PROPFIND` on a WebDAV collection, creating collections as objects are written
+    WebDav,
+}
+
+/// Stores objects on an S3-compatible or WebDAV server with the shared HTTP client
+///
+/// Requests carry the configured `Authorization` header, such as basic credentials for WebDAV
+/// or a bearer token for an S3 gateway; AWS request signing is not supported. Requests run on
+/// the tokio runtime the backend was created on.
+pub struct HttpBackend {
+    base_url: Url,
+    client: Arc<Client>,
+    listing: HttpListing,
+    authorization: Option<String>,
+    runtime: Handle,
+}
+
+impl HttpBackend {
+    /// Must be called from within a tokio runtime
+    pub fn new(
+        base_url: Url,
+        client: Arc<Client>,
+        listing: HttpListing,
+    ) -> Result<Self, SyncError> {
+        let runtime = Handle::try_current()
+            .map_err(|_| SyncError::Backend("HTTP sync needs a tokio runtime".to_string()))?;
+        let mut base_url = base_url;
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Ok(Self {
+            base_url,
+            client,
+            listing,
+            authorization: None,
+            runtime,
+        })
+    }
+
+    /// Use a client from the app's [`HttpClientPool`]
+    pub fn from_pool(
+        base_url: Url,
+        pool: &HttpClientPool,
+        listing: HttpListing,
+    ) -> Result<Self, SyncError> {
+        Self::new(base_url, pool.get_client(), listing)
+    }
+
+    /// Send this `Authorization` header value with every request
+    pub fn with_authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = Some(authorization.into());
+        self
+    }
+
+    fn url(&self, path: &str) -> Result<Url, SyncError> {
+        let encoded: Vec<_> = path
+            .split('/')
+            .map(|part| urlencoding::encode(part))
+            .collect();
+        self.base_url
+            .join(&encoded.join("/"))
+            .map_err(|e| SyncError::Backend(format!("Invalid object URL for '{path}': {e}")))
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: Url,
+        headers: &[(&str, &str)],
+        body: Option<Vec<u8>>,
+    ) -> Result<(StatusCode, Vec<u8>), SyncError> {
+        let mut request = self.client.request(method, url);
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        self.runtime
+            .spawn(async move {
+                let response = request
+                    .send()
+                    .await
+                    .map_err(|e| SyncError::Backend(e.to_string()))?;
+                let status = response.status();
+                let body = response
+                    .bytes()
+                    .await
+                    .map_err(|e| SyncError::Backend(e.to_string()))?;
+                Ok((status, body.to_vec()))
+            })
+            .await
+            .map_err(|e| SyncError::Backend(e.to_string()))?
+    }
+
+    async fn list_s3(&self, prefix: &str) -> Result<Vec<String>, SyncError> {
+        let mut names = Vec::new();
+        let mut continuation = None;
+        loop {
+            let mut url = self.base_url.clone();
+            {
+                let mut query = url.query_pairs_mut();
+                query
+                    .append_pair("list-type", "2")
+                    .append_pair("prefix", prefix);
+                if let Some(token) = &continuation {
+                    query.append_pair("continuation-token", token);
+                }
+            }
+
+            let (status, body) = self.send(Method::GET, url, &[], None).await?;
+            check_status(status, "list")?;
+            let body = String::from_utf8_lossy(&body);
+            names.extend(element_texts(&body, "Key").into_iter().map(unescape_xml));
+
+            let truncated = element_texts(&body, "IsTruncated").first() == Some(&"true");
+            continuation = element_texts(&body, "NextContinuationToken")
+                .first()
+                .map(|token| unescape_xml(token));
+            if !truncated || continuation.is_none() {
+                break;
+            }
+        }
+        Ok(names)
+    }
+
+    async fn list_webdav(&self, prefix: &str) -> Result<Vec<String>, SyncError> {
+        let method = webdav_method(b"PROPFIND")?;
+        let start = prefix.rfind('/').map_or("", |end| &prefix[..=end]);
+        let mut pending = vec![start.to_string()];
+        let mut names = Vec::new();
+
+        while let Some(collection) = pending.pop() {
+            let url = if collection.is_empty() {
+                self.base_url.clone()
+            } else {
+                self.url(&collection)?
+            };
+            let headers = [("Depth", "1"), ("Content-Type", "application/xml")];
+            let (status, body) = self
+                .send(method.clone(), url, &headers, Some(PROPFIND_BODY.into()))
+                .await?;
+            if status == StatusCode::NOT_FOUND {
+                continue;
+            }
+            check_status(status, "list")?;
+
+            let body = String::from_utf8_lossy(&body);
+            for href in element_texts(&body, "href") {
+                let Some(relative) = self.relative_name(&unescape_xml(href)) else {
+                    continue;
+                };
+                if relative == collection {
+                    continue;
+                }
+                if relative.ends_with('/') {
+                    pending.push(relative);
+                } else {
+                    names.push(relative);
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    /// Object name for an `href` in a WebDAV response, if it lies under the base URL
+    fn relative_name(&self, href: &str) -> Option<String> {
+        let path = match Url::parse(href) {
+            Ok(url) => url.path().to_string(),
+            Err(_) => href.to_string(),
+        };
+        let relative = path.strip_prefix(self.base_url.path())?;
+        urlencoding::decode(relative)
+            .ok()
+            .map(|name| name.into_owned())
+    }
+
+    /// Create the collections above an object, for WebDAV servers that do not
+    async fn create_collections(&self, name: &str) -> Result<(), SyncError> {
+        let method = webdav_method(b"MKCOL")?;
+        let mut collection = String::new();
+        let parts: Vec<_> = name.split('/').collect();
+        for part in &parts[..parts.len().saturating_sub(1)] {
+            collection.push_str(part);
+            collection.push('/');
+            let (status, _) = self
+                .send(method.clone(), self.url(&collection)?, &[], None)
+                .await?;
+            // 405 means the collection already exists
+            if !status.is_success() && status != StatusCode::METHOD_NOT_ALLOWED {
+                check_status(status, "create collection")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SyncBackend for HttpBackend {
+    fn list<'a>(&'a self, prefix: &'a str) -> BoxFuture<'a, Result<Vec<String>, SyncError>> {
+        async move {
+            let mut names = match self.listing {
+                HttpListing::S3 => self.list_s3(prefix).await?,
+                HttpListing::WebDav => self.list_webdav(prefix).await?,
+            };
+            names.retain(|name| name.starts_with(prefix));
+            names.sort();
+            Ok(names)
+        }
+        .boxed()
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>, SyncError>> {
+        async move {
+            validate_name(name)?;
+            let (status, body) = self.send(Method::GET, self.url(name)?, &[], None).await?;
+            if status == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            check_status(status, "read")?;
+            Ok(Some(body))
+        }
+        .boxed()
+    }
+
+    fn put<'a>(&'a self, name: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), SyncError>> {
+        async move {
+            validate_name(name)?;
+            let url = self.url(name)?;
+            let (status, _) = self
+                .send(Method::PUT, url.clone(), &[], Some(data.clone()))
+                .await?;
+            // WebDAV answers 409 when the parent collection is missing
+            if status == StatusCode::CONFLICT && self.listing == HttpListing::WebDav {
+                self.create_collections(name).await?;
+                let (status, _) = self.send(Method::PUT, url, &[], Some(data)).await?;
+                return check_status(status, "write");
+            }
+            check_status(status, "write")
+        }
+        .boxed()
+    }
+}
+
+fn webdav_method(name: &[u8]) -> Result<Method, SyncError> {
+    Method::from_bytes(name).map_err(|e| SyncError::Backend(e.to_string()))
+}
+
+fn check_status(status: StatusCode, operation: &str) -> Result<(), SyncError> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(SyncError::Backend(format!(
+            "Server refused to {operation}: {status}"
+        )))
+    }
+}
+
+/// Text of every element with this local name, whatever its namespace prefix
+fn element_texts<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut texts = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..close];
+        rest = &rest[close + 1..];
+        let local = tag.rsplit(':').next().unwrap_or(tag);
+        if local == name
+            && let Some(end) = rest.find("</")
+        {
+            texts.push(rest[..end].trim());
+            rest = &rest[end..];
+        }
+    }
+    texts
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+
+use super::{SyncBackend, validate_name};
+use crate::error::SyncError;
+
+/// Stores objects as files under a directory
+///
+/// Point it at a folder another tool already syncs, such as a network share or a cloud drive,
+/// or use it to test sync between two databases on one machine.
+pub struct LocalDirectoryBackend {
+    root: PathBuf,
+}
+
+impl LocalDirectoryBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf, SyncError> {
+        validate_name(name)?;
+        Ok(name
+            .split('/')
+            .fold(self.root.clone(), |path, part| path.join(part)))
+    }
+
+    fn list_dir(&self, dir: &Path, names: &mut Vec<String>) -> Result<(), SyncError> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.list_dir(&path, names)?;
+                continue;
+            }
+            // Half-written objects from an interrupted put
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "partial")
+            {
+                continue;
+            }
+            if let Ok(relative) = path.strip_prefix(&self.root) {
+                let parts: Vec<_> = relative.iter().map(|part| part.to_string_lossy()).collect();
+                names.push(parts.join("/"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SyncBackend for LocalDirectoryBackend {
+    fn list<'a>(&'a self, prefix: &'a str) -> BoxFuture<'a, Result<Vec<String>, SyncError>> {
+        async move {
+            let mut names = Vec::new();
+            self.list_dir(&self.root, &mut names)?;
+            names.retain(|name| name.starts_with(prefix));
+            names.sort();
+            Ok(names)
+        }
+        .boxed()
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>, SyncError>> {
+        async move {
+            match fs::read(self.path(name)?) {
+                Ok(data) => Ok(Some(data)),
+                Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        }
+        .boxed()
+    }
+
+    fn put<'a>(&'a self, name: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), SyncError>> {
+        async move {
+            let path = self.path(name)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // Write beside the target so readers never see a partial object
+            let mut partial = path.clone().into_os_string();
+            partial.push(".partial");
+            fs::write(&partial, data)?;
+            fs::rename(&partial, &path)?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
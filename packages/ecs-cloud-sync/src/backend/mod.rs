@@ -0,0 +1,40 @@
+//! Storage for sealed sync objects
+//!
+//! A backend only stores opaque, already encrypted objects under `/`-separated names, so any
+//! store that can list, read and write files will do.
+
+mod http;
+mod local;
+
+pub use http::{HttpBackend, HttpListing};
+pub use local::LocalDirectoryBackend;
+
+use futures::future::BoxFuture;
+
+use crate::error::SyncError;
+
+/// Object storage the sync engine pushes to and pulls from
+pub trait SyncBackend: Send + Sync {
+    /// Names of all objects starting with `prefix`
+    fn list<'a>(&'a self, prefix: &'a str) -> BoxFuture<'a, Result<Vec<String>, SyncError>>;
+
+    /// Contents of an object, or `None` if it does not exist
+    fn get<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>, SyncError>>;
+
+    /// Create or replace an object
+    fn put<'a>(&'a self, name: &'a str, data: Vec<u8>) -> BoxFuture<'a, Result<(), SyncError>>;
+}
+
+/// Reject names that could escape the backend's root
+fn validate_name(name: &str) -> Result<(), SyncError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('/')
+        && name
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != "..");
+    if valid {
+        Ok(())
+    } else {
+        Err(SyncError::Backend(format!("Invalid object name '{name}'")))
+    }
+}
@@ -0,0 +1,225 @@
+//! Per-record change tracking and merging
+//!
+//! [`SyncState`] remembers every synced record as it was last seen, with the stamp of the write
+//! behind each field and a vector clock of the edits it has seen. Comparing a table's current
+//! records with it yields the local [`RecordChange`]s to push; merging a pulled change keeps,
+//! for every field, the value with the greatest stamp. A delete wins over the fields written
+//! before it and loses to any written after it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::clock::{Causality, Stamp, VectorClock};
+
+/// A field's value and the write that set it; `Value::Null` marks a removed field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldState {
+    pub value: Value,
+    pub stamp: Stamp,
+}
+
+/// A synced record as this machine knows it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordState {
+    pub clock: VectorClock,
+    pub fields: BTreeMap<String, FieldState>,
+    #[serde(default)]
+    pub deleted: Option<Stamp>,
+}
+
+impl RecordState {
+    /// Field values written after the last delete, or `None` if the record is deleted
+    pub fn values(&self) -> Option<Map<String, Value>> {
+        let live = |field: &&FieldState| self.deleted.as_ref().is_none_or(|d| field.stamp > *d);
+        let values: Map<String, Value> = self
+            .fields
+            .iter()
+            .filter(|(_, field)| live(field))
+            .filter(|(_, field)| !field.value.is_null())
+            .map(|(name, field)| (name.clone(), field.value.clone()))
+            .collect();
+
+        let deleted = self.deleted.is_some() && !self.fields.values().any(|f| live(&f));
+        (!deleted).then_some(values)
+    }
+}
+
+/// The fields of one record changed by one device, with the record's clock after the change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordChange {
+    pub table: String,
+    pub key: String,
+    pub clock: VectorClock,
+    pub fields: BTreeMap<String, FieldState>,
+    #[serde(default)]
+    pub deleted: Option<Stamp>,
+}
+
+/// Changes pushed by one device in one sync
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub device: String,
+    /// Position in the device's sequence of change sets, starting at 1
+    pub seq: u64,
+    pub records: Vec<RecordChange>,
+}
+
+/// How a pulled change related to what this machine had seen of the record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// The record's clock already included the change
+    AlreadySeen,
+    /// The change followed everything this machine had seen of the record
+    FastForward,
+    /// The record had been edited here concurrently; fields were merged by stamp
+    Conflict,
+}
+
+/// Everything this machine remembers between syncs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// Identifies this machine in clocks, stamps and change set names
+    pub device_id: String,
+    /// Sequence number of the next change set pushed
+    pub next_seq: u64,
+    /// Last change set pulled from each other device
+    pub pulled: BTreeMap<String, u64>,
+    /// Records by table, then key
+    pub tables: BTreeMap<String, BTreeMap<String, RecordState>>,
+    /// Pulled changes to tables that do not sync here, applied if they are turned on
+    pub deferred: BTreeMap<String, Vec<RecordChange>>,
+}
+
+impl Default for SyncState {
+    fn default() -> Self {
+        Self {
+            device_id: uuid::Uuid::new_v4().to_string(),
+            next_seq: 1,
+            pulled: BTreeMap::new(),
+            tables: BTreeMap::new(),
+            deferred: BTreeMap::new(),
+        }
+    }
+}
+
+impl SyncState {
+    /// Whether this machine has never synced
+    pub fn is_new(&self) -> bool {
+        self.pulled.is_empty() && self.tables.is_empty()
+    }
+
+    pub fn record(&self, table: &str, key: &str) -> Option<&RecordState> {
+        self.tables.get(table)?.get(key)
+    }
+
+    /// Compare a table's current records with the last ones seen and stamp what changed
+    ///
+    /// `current` maps record keys to their synced fields. Returns a change for every record
+    /// created, edited or deleted since the last call.
+    pub fn record_local(
+        &mut self,
+        table: &str,
+        current: &BTreeMap<String, Map<String, Value>>,
+        stamp: &Stamp,
+    ) -> Vec<RecordChange> {
+        let records = self.tables.entry(table.to_string()).or_default();
+        let mut changes = Vec::new();
+
+        for (key, fields) in current {
+            let record = records.entry(key.clone()).or_default();
+            let previous = record.values().unwrap_or_default();
+
+            let mut changed: BTreeMap<String, FieldState> = fields
+                .iter()
+                .filter(|(name, value)| previous.get(*name) != Some(*value))
+                .map(|(name, value)| {
+                    (
+                        name.clone(),
+                        FieldState {
+                            value: value.clone(),
+                            stamp: stamp.clone(),
+                        },
+                    )
+                })
+                .collect();
+            for name in previous.keys().filter(|name| !fields.contains_key(*name)) {
+                changed.insert(
+                    name.clone(),
+                    FieldState {
+                        value: Value::Null,
+                        stamp: stamp.clone(),
+                    },
+                );
+            }
+            if changed.is_empty() {
+                continue;
+            }
+
+            record.fields.extend(changed.clone());
+            record.clock.increment(&stamp.device);
+            changes.push(RecordChange {
+                table: table.to_string(),
+                key: key.clone(),
+                clock: record.clock.clone(),
+                fields: changed,
+                deleted: None,
+            });
+        }
+
+        for (key, record) in records.iter_mut() {
+            if current.contains_key(key) || record.values().is_none() {
+                continue;
+            }
+            record.deleted = Some(stamp.clone());
+            record.clock.increment(&stamp.device);
+            changes.push(RecordChange {
+                table: table.to_string(),
+                key: key.clone(),
+                clock: record.clock.clone(),
+                fields: BTreeMap::new(),
+                deleted: record.deleted.clone(),
+            });
+        }
+
+        changes
+    }
+
+    /// Merge a change pulled from another device
+    ///
+    /// Fields are merged by stamp whatever the clocks say, so merging is idempotent and change
+    /// sets from different devices can be merged in any order.
+    pub fn merge(&mut self, change: &RecordChange) -> MergeOutcome {
+        let record = self
+            .tables
+            .entry(change.table.clone())
+            .or_default()
+            .entry(change.key.clone())
+            .or_default();
+
+        let outcome = match record.clock.compare(&change.clock) {
+            Causality::Equal | Causality::After => MergeOutcome::AlreadySeen,
+            Causality::Before => MergeOutcome::FastForward,
+            Causality::Concurrent => MergeOutcome::Conflict,
+        };
+
+        for (name, field) in &change.fields {
+            let newer = record
+                .fields
+                .get(name)
+                .is_none_or(|local| field.stamp > local.stamp);
+            if newer {
+                record.fields.insert(name.clone(), field.clone());
+            }
+        }
+        if let Some(deleted) = &change.deleted
+            && record.deleted.as_ref().is_none_or(|local| deleted > local)
+        {
+            record.deleted = Some(deleted.clone());
+        }
+        record.clock.merge(&change.clock);
+
+        outcome
+    }
+}
@@ -0,0 +1,88 @@
+//! Vector clocks and last-writer-wins stamps
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Per-device counters of the edits a record has seen
+///
+/// Comparing two clocks tells whether one version of a record already includes the other's
+/// edits, or whether they were edited concurrently and need merging.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VectorClock(BTreeMap<String, u64>);
+
+/// How two vector clocks relate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Causality {
+    Equal,
+    /// The first clock has seen everything the second has, and more
+    After,
+    /// The second clock has seen everything the first has, and more
+    Before,
+    Concurrent,
+}
+
+impl VectorClock {
+    /// Count a new edit by `device`
+    pub fn increment(&mut self, device: &str) {
+        *self.0.entry(device.to_string()).or_default() += 1;
+    }
+
+    /// Edits by `device` this clock has seen
+    pub fn get(&self, device: &str) -> u64 {
+        self.0.get(device).copied().unwrap_or(0)
+    }
+
+    /// Take the larger counter for every device
+    pub fn merge(&mut self, other: &VectorClock) {
+        for (device, &count) in &other.0 {
+            let entry = self.0.entry(device.clone()).or_default();
+            *entry = (*entry).max(count);
+        }
+    }
+
+    pub fn compare(&self, other: &VectorClock) -> Causality {
+        let mut ahead = false;
+        let mut behind = false;
+        for device in self.0.keys().chain(other.0.keys()) {
+            match self.get(device).cmp(&other.get(device)) {
+                Ordering::Greater => ahead = true,
+                Ordering::Less => behind = true,
+                Ordering::Equal => {},
+            }
+        }
+        match (ahead, behind) {
+            (false, false) => Causality::Equal,
+            (true, false) => Causality::After,
+            (false, true) => Causality::Before,
+            (true, true) => Causality::Concurrent,
+        }
+    }
+}
+
+/// When and where a value was written; the greater stamp wins a conflict
+///
+/// Ordered by time, then by device so that every machine picks the same winner for writes in
+/// the same millisecond.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Stamp {
+    /// Milliseconds since the Unix epoch
+    pub millis: u64,
+    pub device: String,
+}
+
+impl Stamp {
+    pub fn now(device: &str) -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0);
+        Self {
+            millis,
+            device: device.to_string(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Client-side encryption of everything that leaves the machine
+//!
+//! The key is derived from the user's passphrase with Argon2id and never stored. Objects are
+//! sealed with XChaCha20-Poly1305 under a random nonce, with the object's name as associated
+//! data so a backend cannot swap one object for another. Only [`KeyParams`], which holds the
+//! salt and cost a second machine needs to derive the same key, is stored unencrypted.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::error::SyncError;
+
+/// Format version of sealed objects and key parameters
+const FORMAT_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+/// Sealed with a new key so a wrong passphrase is caught before any data is read
+const CHECK_NAME: &str = "keys.check";
+const CHECK_PLAINTEXT: &[u8] = b"action-items-sync";
+
+/// Argon2id cost used when a key is first created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfCost {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfCost {
+    /// The OWASP recommendation for Argon2id
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Everything but the passphrase needed to derive the sync key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyParams {
+    pub version: u8,
+    /// Hex-encoded Argon2 salt
+    pub salt: String,
+    pub cost: KdfCost,
+    /// Hex-encoded known plaintext sealed with the key
+    check: String,
+}
+
+/// Symmetric key for sealing sync objects
+pub struct SyncKey {
+    cipher: XChaCha20Poly1305,
+}
+
+impl SyncKey {
+    /// Derive a key from a passphrase under a new random salt
+    pub fn create(passphrase: &str, cost: KdfCost) -> Result<(Self, KeyParams), SyncError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = Self::derive(passphrase, &salt, cost)?;
+        let params = KeyParams {
+            version: FORMAT_VERSION,
+            salt: hex::encode(salt),
+            cost,
+            check: hex::encode(key.seal(CHECK_NAME, CHECK_PLAINTEXT)?),
+        };
+        Ok((key, params))
+    }
+
+    /// Derive the key described by `params`
+    ///
+    /// Fails with [`SyncError::WrongPassphrase`] if the passphrase differs from the one the
+    /// parameters were created with.
+    pub fn unlock(passphrase: &str, params: &KeyParams) -> Result<Self, SyncError> {
+        if params.version != FORMAT_VERSION {
+            return Err(SyncError::Malformed(format!(
+                "Unsupported key format version {}",
+                params.version
+            )));
+        }
+        let salt = hex::decode(&params.salt)
+            .map_err(|e| SyncError::Malformed(format!("Invalid key salt: {e}")))?;
+        let check = hex::decode(&params.check)
+            .map_err(|e| SyncError::Malformed(format!("Invalid key check: {e}")))?;
+
+        let key = Self::derive(passphrase, &salt, params.cost)?;
+        match key.open(CHECK_NAME, &check) {
+            Ok(plaintext) if plaintext == CHECK_PLAINTEXT => Ok(key),
+            _ => Err(SyncError::WrongPassphrase),
+        }
+    }
+
+    fn derive(passphrase: &str, salt: &[u8], cost: KdfCost) -> Result<Self, SyncError> {
+        let params = Params::new(cost.memory_kib, cost.iterations, cost.parallelism, Some(32))
+            .map_err(|e| SyncError::Malformed(format!("Invalid key cost: {e}")))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+            .map_err(|e| SyncError::Malformed(format!("Key derivation failed: {e}")))?;
+        let cipher = XChaCha20Poly1305::new_from_slice(key.as_ref())
+            .map_err(|e| SyncError::Malformed(e.to_string()))?;
+        Ok(Self { cipher })
+    }
+
+    /// Encrypt an object stored under `name`
+    pub fn seal(&self, name: &str, plaintext: &[u8]) -> Result<Vec<u8>, SyncError> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| SyncError::Malformed(format!("Could not encrypt {name}")))?;
+
+        let mut sealed = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+        sealed.push(FORMAT_VERSION);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt an object read from `name`
+    pub fn open(&self, name: &str, sealed: &[u8]) -> Result<Vec<u8>, SyncError> {
+        let Some((&version, rest)) = sealed.split_first() else {
+            return Err(SyncError::Tampered(name.to_string()));
+        };
+        if version != FORMAT_VERSION || rest.len() < NONCE_LEN {
+            return Err(SyncError::Tampered(name.to_string()));
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| SyncError::Tampered(name.to_string()))
+    }
+}
@@ -0,0 +1,394 @@
+//! Pushing and pulling change sets
+//!
+//! The backend holds one unencrypted object, [`KEY_PARAMS_OBJECT`], and a sealed change set per
+//! device per sync under `changes/{device}/{seq}`. A sync stamps what changed locally since the
+//! last one, pulls every change set it has not seen from the other devices, writes the records
+//! that merging changed back to SurrealDB in one transaction and pushes its own changes.
+//!
+//! A machine's first sync pulls before it looks for local changes, so joining machines adopt
+//! the synced settings instead of overwriting them with their defaults.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use action_items_ecs_surrealdb::{DatabaseError, DatabaseService};
+use action_items_ecs_user_settings::table_names::CLOUD_SYNC_SETTINGS;
+use bevy::prelude::Resource;
+use futures::FutureExt;
+use serde_json::{Map, Value as JsonValue};
+use surrealdb::Value;
+use tracing::{debug, info};
+
+use crate::backend::SyncBackend;
+use crate::changes::{ChangeSet, MergeOutcome, RecordChange, SyncState};
+use crate::clock::Stamp;
+use crate::crypto::{KdfCost, KeyParams, SyncKey};
+use crate::error::SyncError;
+use crate::selection::{SyncSettings, SyncedTable};
+
+/// Unencrypted key derivation parameters shared by every device
+pub const KEY_PARAMS_OBJECT: &str = "keys.json";
+/// Prefix of change set objects
+pub const CHANGES_PREFIX: &str = "changes/";
+
+/// Table holding this machine's [`SyncState`]
+const STATE_TABLE: &str = "sync_state";
+const STATE_SCHEMA: &str = "DEFINE TABLE IF NOT EXISTS sync_state SCHEMALESS;";
+
+/// What one sync did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Local record changes pushed
+    pub pushed: usize,
+    /// Change sets pulled from other devices
+    pub pulled: usize,
+    /// Local records written or deleted with merged changes
+    pub applied: usize,
+    /// Records edited here and elsewhere since they last synced, merged field by field
+    pub conflicts: usize,
+}
+
+/// Syncs the database through a backend with a passphrase-derived key
+#[derive(Resource, Clone)]
+pub struct SyncEngine {
+    backend: Arc<dyn SyncBackend>,
+    key: Arc<SyncKey>,
+}
+
+impl SyncEngine {
+    /// Unlock the backend's data with a passphrase
+    ///
+    /// On a backend without data yet, a key is created from the passphrase at `cost`. Fails
+    /// with [`SyncError::WrongPassphrase`] if another device set up sync with a different one.
+    pub async fn connect(
+        backend: Arc<dyn SyncBackend>,
+        passphrase: &str,
+        cost: KdfCost,
+    ) -> Result<Self, SyncError> {
+        let key = match backend.get(KEY_PARAMS_OBJECT).await? {
+            Some(data) => {
+                let params: KeyParams = serde_json::from_slice(&data)?;
+                SyncKey::unlock(passphrase, &params)?
+            },
+            None => {
+                let (key, params) = SyncKey::create(passphrase, cost)?;
+                backend
+                    .put(KEY_PARAMS_OBJECT, serde_json::to_vec_pretty(&params)?)
+                    .await?;
+                info!("Created a new cloud sync key");
+                key
+            },
+        };
+        Ok(Self {
+            backend,
+            key: Arc::new(key),
+        })
+    }
+
+    /// Push local changes and pull everyone else's
+    pub async fn sync(&self, db: &DatabaseService) -> Result<SyncReport, SyncError> {
+        let settings = SyncSettings::load(db).await?;
+        if !settings.enabled {
+            return Err(SyncError::Disabled);
+        }
+        let tables = settings.tables();
+        let mut state = load_state(db).await?;
+        let mut report = SyncReport::default();
+
+        let joining = state.is_new();
+        let mut local_changes = Vec::new();
+        if !joining {
+            local_changes = record_local_changes(db, &mut state, &tables).await?;
+        }
+
+        let touched = self.pull(&mut state, &tables, &mut report).await?;
+        report.applied = touched.len();
+        apply_records(db, &state, touched).await?;
+
+        if joining {
+            local_changes = record_local_changes(db, &mut state, &tables).await?;
+        }
+        report.pushed = local_changes.len();
+        self.push(&mut state, local_changes).await?;
+
+        save_state(db, &state).await?;
+        db.query(&format!(
+            "UPDATE {CLOUD_SYNC_SETTINGS} SET last_synced = time::now()"
+        ))
+        .await?
+        .check()
+        .map_err(query_failed)?;
+
+        info!(
+            pushed = report.pushed,
+            pulled = report.pulled,
+            applied = report.applied,
+            conflicts = report.conflicts,
+            "Cloud sync finished"
+        );
+        Ok(report)
+    }
+
+    /// Merge deferred and newly pulled changes, returning the records whose values changed
+    async fn pull(
+        &self,
+        state: &mut SyncState,
+        tables: &[SyncedTable],
+        report: &mut SyncReport,
+    ) -> Result<BTreeSet<(String, String)>, SyncError> {
+        let synced: BTreeSet<&str> = tables.iter().map(|table| table.name).collect();
+        let mut touched = BTreeSet::new();
+
+        // Changes pulled while their table was not synced here
+        let mut changes = Vec::new();
+        for table in &synced {
+            changes.extend(state.deferred.remove(*table).unwrap_or_default());
+        }
+
+        for (device, seq, name) in self.pending_change_sets(state).await? {
+            let Some(sealed) = self.backend.get(&name).await? else {
+                continue;
+            };
+            let change_set: ChangeSet = serde_json::from_slice(&self.key.open(&name, &sealed)?)?;
+            if change_set.device != device || change_set.seq != seq {
+                return Err(SyncError::Malformed(format!(
+                    "{name} holds change set {} of {}",
+                    change_set.seq, change_set.device
+                )));
+            }
+            debug!("Pulled {} with {} changes", name, change_set.records.len());
+            changes.extend(change_set.records);
+            state.pulled.insert(device, seq);
+            report.pulled += 1;
+        }
+
+        for change in changes {
+            if !synced.contains(change.table.as_str()) {
+                state
+                    .deferred
+                    .entry(change.table.clone())
+                    .or_default()
+                    .push(change);
+                continue;
+            }
+
+            let before = state
+                .record(&change.table, &change.key)
+                .and_then(|record| record.values());
+            if state.merge(&change) == MergeOutcome::Conflict {
+                report.conflicts += 1;
+            }
+            let after = state
+                .record(&change.table, &change.key)
+                .and_then(|record| record.values());
+            if before != after {
+                touched.insert((change.table, change.key));
+            }
+        }
+        Ok(touched)
+    }
+
+    /// Change sets from other devices following the last one pulled from each, in order
+    ///
+    /// A gap in a device's sequence stops at the gap until the missing object appears, so no
+    /// change set is ever skipped.
+    async fn pending_change_sets(
+        &self,
+        state: &SyncState,
+    ) -> Result<Vec<(String, u64, String)>, SyncError> {
+        let mut by_device: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+        for name in self.backend.list(CHANGES_PREFIX).await? {
+            let Some((device, seq)) = name
+                .strip_prefix(CHANGES_PREFIX)
+                .and_then(|rest| rest.split_once('/'))
+            else {
+                continue;
+            };
+            if device == state.device_id {
+                continue;
+            }
+            if let Ok(seq) = seq.parse::<u64>() {
+                by_device.entry(device.to_string()).or_default().push(seq);
+            }
+        }
+
+        let mut pending = Vec::new();
+        for (device, mut seqs) in by_device {
+            seqs.sort_unstable();
+            let mut next = state.pulled.get(&device).copied().unwrap_or(0) + 1;
+            for seq in seqs.into_iter().filter(|seq| *seq >= next) {
+                if seq != next {
+                    break;
+                }
+                pending.push((device.clone(), seq, change_set_name(&device, seq)));
+                next += 1;
+            }
+        }
+        Ok(pending)
+    }
+
+    async fn push(
+        &self,
+        state: &mut SyncState,
+        records: Vec<RecordChange>,
+    ) -> Result<(), SyncError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let change_set = ChangeSet {
+            device: state.device_id.clone(),
+            seq: state.next_seq,
+            records,
+        };
+        let name = change_set_name(&change_set.device, change_set.seq);
+        let sealed = self.key.seal(&name, &serde_json::to_vec(&change_set)?)?;
+        self.backend.put(&name, sealed).await?;
+        debug!("Pushed {} with {} changes", name, change_set.records.len());
+        state.next_seq += 1;
+        Ok(())
+    }
+}
+
+fn change_set_name(device: &str, seq: u64) -> String {
+    format!("{CHANGES_PREFIX}{device}/{seq:012}")
+}
+
+fn query_failed(error: impl std::fmt::Display) -> DatabaseError {
+    DatabaseError::QueryFailed(error.to_string())
+}
+
+fn to_value(value: impl serde::Serialize + 'static) -> Result<Value, SyncError> {
+    surrealdb::value::to_value(value).map_err(|e| SyncError::Malformed(e.to_string()))
+}
+
+async fn load_state(db: &DatabaseService) -> Result<SyncState, SyncError> {
+    db.execute_schema(STATE_SCHEMA).await?;
+    let mut response = db
+        .query(&format!("SELECT VALUE state FROM {STATE_TABLE}:local"))
+        .await?;
+    let state = response.take::<Option<String>>(0).map_err(query_failed)?;
+    match state {
+        Some(state) => Ok(serde_json::from_str(&state)?),
+        None => Ok(SyncState::default()),
+    }
+}
+
+async fn save_state(db: &DatabaseService, state: &SyncState) -> Result<(), SyncError> {
+    // Stored as a string so field values keep exactly the JSON they synced as
+    let params = HashMap::from([(
+        "state".to_string(),
+        to_value(serde_json::to_string(state)?)?,
+    )]);
+    db.query_with_params(
+        &format!("UPSERT {STATE_TABLE}:local SET state = $state"),
+        params,
+    )
+    .await?
+    .check()
+    .map_err(query_failed)?;
+    Ok(())
+}
+
+/// Stamp every change to the synced tables since the last sync
+async fn record_local_changes(
+    db: &DatabaseService,
+    state: &mut SyncState,
+    tables: &[SyncedTable],
+) -> Result<Vec<RecordChange>, SyncError> {
+    let stamp = Stamp::now(&state.device_id);
+    let mut changes = Vec::new();
+    for table in tables {
+        let current = read_table(db, table).await?;
+        changes.extend(state.record_local(table.name, &current, &stamp));
+    }
+    Ok(changes)
+}
+
+/// Synced fields of every record in a table, by record key
+async fn read_table(
+    db: &DatabaseService,
+    table: &SyncedTable,
+) -> Result<BTreeMap<String, Map<String, JsonValue>>, SyncError> {
+    let params = HashMap::from([("table".to_string(), to_value(table.name.to_string())?)]);
+    let mut response = db
+        .query_with_params(
+            "SELECT *, record::id(id) AS id FROM type::table($table)",
+            params,
+        )
+        .await?;
+    let rows = response.take::<Vec<JsonValue>>(0).map_err(query_failed)?;
+
+    let mut records = BTreeMap::new();
+    for row in rows {
+        let JsonValue::Object(mut fields) = row else {
+            continue;
+        };
+        // Only string keys survive the round trip through `type::thing`
+        let Some(JsonValue::String(key)) = fields.remove("id") else {
+            debug!("Skipping {} record without a string key", table.name);
+            continue;
+        };
+        fields
+            .retain(|name, value| !value.is_null() && !table.local_fields.contains(&name.as_str()));
+        records.insert(key, fields);
+    }
+    Ok(records)
+}
+
+/// Write merged records back to the database in one transaction
+async fn apply_records(
+    db: &DatabaseService,
+    state: &SyncState,
+    touched: BTreeSet<(String, String)>,
+) -> Result<(), SyncError> {
+    let mut statements = Vec::new();
+    for (table, key) in touched {
+        let Some(record) = state.record(&table, &key) else {
+            continue;
+        };
+        let mut params = HashMap::from([
+            ("table".to_string(), to_value(table.clone())?),
+            ("key".to_string(), to_value(key.clone())?),
+        ]);
+
+        let Some(values) = record.values() else {
+            statements.push(("DELETE type::thing($table, $key)".to_string(), params));
+            continue;
+        };
+        // Field names come from other devices, so only plain identifiers are unset by name
+        let removed: Vec<&str> = record
+            .fields
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !values.contains_key(*name))
+            .filter(|name| name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .collect();
+        let mut sql = "UPSERT type::thing($table, $key) MERGE $fields".to_string();
+        if !removed.is_empty() {
+            sql.push_str(&format!(
+                "; UPDATE type::thing($table, $key) UNSET {}",
+                removed.join(", ")
+            ));
+        }
+        params.insert("fields".to_string(), to_value(JsonValue::Object(values))?);
+        statements.push((sql, params));
+    }
+    if statements.is_empty() {
+        return Ok(());
+    }
+
+    db.with_transaction(|ctx| {
+        async move {
+            for (sql, params) in statements {
+                ctx.query_with_params(&sql, params)
+                    .await?
+                    .check()
+                    .map_err(query_failed)?;
+            }
+            Ok(())
+        }
+        .boxed()
+    })
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Cloud sync error types
+
+use action_items_ecs_surrealdb::DatabaseError;
+use thiserror::Error;
+
+/// Errors raised while syncing
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SyncError {
+    /// The `enabled` flag in `cloud_sync_settings` is off
+    #[error("Cloud sync is disabled")]
+    Disabled,
+
+    /// No sync engine has been configured with a passphrase
+    #[error("Cloud sync has not been set up")]
+    NotConfigured,
+
+    /// The passphrase does not match the key the remote data was encrypted with
+    #[error("Wrong sync passphrase")]
+    WrongPassphrase,
+
+    /// Remote data failed authentication; it was corrupted or altered
+    #[error("Sync object {0} failed to decrypt")]
+    Tampered(String),
+
+    #[error("Malformed sync data: {0}")]
+    Malformed(String),
+
+    #[error("Sync backend error: {0}")]
+    Backend(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl From<DatabaseError> for SyncError {
+    fn from(error: DatabaseError) -> Self {
+        SyncError::Database(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for SyncError {
+    fn from(error: std::io::Error) -> Self {
+        SyncError::Backend(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for SyncError {
+    fn from(error: serde_json::Error) -> Self {
+        SyncError::Malformed(error.to_string())
+    }
+}
@@ -0,0 +1,37 @@
+//! Cloud sync request and result events
+
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::backend::SyncBackend;
+use crate::crypto::KdfCost;
+use crate::engine::SyncReport;
+use crate::error::SyncError;
+
+/// Unlock a backend with the user's passphrase and start syncing through it
+///
+/// Replaces any backend set up before.
+#[derive(Event, Clone)]
+pub struct CloudSyncSetupRequested {
+    pub backend: Arc<dyn SyncBackend>,
+    pub passphrase: String,
+    /// Key derivation cost if the backend has no key yet
+    pub cost: KdfCost,
+}
+
+/// Result of a setup request
+#[derive(Event, Debug, Clone)]
+pub struct CloudSyncSetupCompleted {
+    pub result: Result<(), SyncError>,
+}
+
+/// Sync now; any number of requests made while a sync runs start one more after it
+#[derive(Event, Debug, Clone, Default)]
+pub struct CloudSyncRequested;
+
+/// Result of a sync
+#[derive(Event, Debug, Clone)]
+pub struct CloudSyncCompleted {
+    pub result: Result<SyncReport, SyncError>,
+}
@@ -0,0 +1,37 @@
+//! Action Items ECS Cloud Sync
+//!
+//! Bevy ECS plugin syncing the user settings stored by `action_items_ecs_user_settings` between
+//! machines.
+//!
+//! - Changes are tracked per record and per field and sealed with a key derived from the user's
+//!   passphrase before they leave the machine; backends only ever see ciphertext
+//! - Storage is pluggable through [`SyncBackend`]: a local directory, or an S3-compatible or
+//!   WebDAV server over the shared `ecs_fetch` client
+//! - Concurrent edits merge field by field, the latest write winning, with vector clocks
+//!   telling concurrent edits from stale ones
+//! - Only the categories enabled by the `sync_*` and `not_synced_*` flags in
+//!   `cloud_sync_settings` are pushed or applied
+
+pub mod backend;
+pub mod changes;
+pub mod clock;
+pub mod crypto;
+pub mod engine;
+pub mod error;
+pub mod events;
+pub mod plugin;
+pub mod selection;
+pub mod systems;
+
+pub use backend::{HttpBackend, HttpListing, LocalDirectoryBackend, SyncBackend};
+pub use changes::{ChangeSet, MergeOutcome, RecordChange, SyncState};
+pub use clock::{Causality, Stamp, VectorClock};
+pub use crypto::{KdfCost, KeyParams, SyncKey};
+pub use engine::{SyncEngine, SyncReport};
+pub use error::SyncError;
+pub use events::{
+    CloudSyncCompleted, CloudSyncRequested, CloudSyncSetupCompleted, CloudSyncSetupRequested,
+};
+pub use plugin::{CloudSyncConfig, CloudSyncPlugin};
+pub use selection::{SyncCategory, SyncSettings, SyncedTable};
+pub use systems::CloudSyncStatus;
@@ -0,0 +1,65 @@
+//! Cloud sync plugin
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::events::*;
+use crate::systems::*;
+
+/// Cloud sync configuration
+#[derive(Debug, Clone)]
+pub struct CloudSyncConfig {
+    /// How often to sync once set up
+    pub interval: Duration,
+}
+
+impl Default for CloudSyncConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// Plugin syncing user settings between machines through an end-to-end encrypted backend
+///
+/// Nothing syncs until a [`CloudSyncSetupRequested`] unlocks a backend. Syncs need the
+/// `DatabaseService` from `DatabasePlugin` and only run while `cloud_sync_settings.enabled` is
+/// set.
+#[derive(Default)]
+pub struct CloudSyncPlugin {
+    pub config: CloudSyncConfig,
+}
+
+impl CloudSyncPlugin {
+    pub fn new(config: CloudSyncConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for CloudSyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CloudSyncTimer(Timer::new(
+            self.config.interval,
+            TimerMode::Repeating,
+        )))
+        .init_resource::<CloudSyncStatus>()
+        .add_event::<CloudSyncSetupRequested>()
+        .add_event::<CloudSyncSetupCompleted>()
+        .add_event::<CloudSyncRequested>()
+        .add_event::<CloudSyncCompleted>()
+        .add_systems(
+            Update,
+            (
+                handle_setup_requests,
+                schedule_cloud_sync,
+                handle_sync_requests,
+                handle_cloud_sync_tasks,
+            )
+                .chain(),
+        );
+
+        tracing::info!("CloudSyncPlugin initialized");
+    }
+}
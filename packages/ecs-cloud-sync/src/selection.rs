@@ -0,0 +1,201 @@
+//! What to sync, as chosen in `cloud_sync_settings`
+//!
+//! Every `sync_*` flag opts its category in and every `not_synced_*` flag keeps its category on
+//! this machine while set, which it is by default. Categories whose data does not live in
+//! SurrealDB yet have no tables, so their flags are honored trivially.
+
+use std::collections::HashMap;
+
+use action_items_ecs_surrealdb::DatabaseService;
+use action_items_ecs_user_settings::table_names::*;
+
+use crate::error::SyncError;
+
+/// A table whose records sync, and the fields that stay local
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncedTable {
+    pub name: &'static str,
+    /// Bookkeeping fields each machine maintains itself, such as timestamps filled in by
+    /// `DEFAULT time::now()`
+    pub local_fields: &'static [&'static str],
+}
+
+const SETTINGS_TIMESTAMPS: &[&str] = &["created_at", "updated_at"];
+
+const fn settings_table(name: &'static str) -> SyncedTable {
+    SyncedTable {
+        name,
+        local_fields: SETTINGS_TIMESTAMPS,
+    }
+}
+
+const fn data_table(name: &'static str) -> SyncedTable {
+    SyncedTable {
+        name,
+        local_fields: &[],
+    }
+}
+
+// Owned by ecs-search's frecency store
+const SEARCH_HISTORY_TABLES: &[SyncedTable] =
+    &[data_table("frecency_item"), data_table("frecency_query")];
+const HOTKEY_TABLES: &[SyncedTable] = &[settings_table(HOTKEY_SETTINGS)];
+// Owned by ecs-quicklinks and ecs-snippets
+const QUICKLINK_TABLES: &[SyncedTable] = &[data_table("quicklink")];
+const SNIPPET_TABLES: &[SyncedTable] = &[data_table("snippet")];
+const EXTENSION_SETTINGS_TABLES: &[SyncedTable] = &[SyncedTable {
+    name: PLUGIN_CONFIGS,
+    local_fields: &["last_modified"],
+}];
+const THEME_TABLES: &[SyncedTable] = &[settings_table(APPEARANCE_SETTINGS)];
+const WINDOW_MANAGEMENT_TABLES: &[SyncedTable] = &[SyncedTable {
+    name: UI_STATE,
+    local_fields: &["last_updated"],
+}];
+// Owned by ecs-clipboard-history
+const CLIPBOARD_HISTORY_TABLES: &[SyncedTable] = &[data_table("clipboard_history")];
+const GENERAL_ADVANCED_TABLES: &[SyncedTable] = &[
+    settings_table(USER_PREFERENCES),
+    settings_table(ADVANCED_SETTINGS),
+    settings_table(STARTUP_SETTINGS),
+    settings_table(AI_SETTINGS),
+];
+
+/// A group of data with its own flag in `cloud_sync_settings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyncCategory {
+    SearchHistory,
+    Aliases,
+    Hotkeys,
+    Quicklinks,
+    Snippets,
+    Notes,
+    ExtensionSettings,
+    AiChats,
+    Themes,
+    WindowManagement,
+    ClipboardHistory,
+    ScriptCommands,
+    Credentials,
+    GeneralAdvanced,
+}
+
+impl SyncCategory {
+    pub const ALL: [SyncCategory; 14] = [
+        SyncCategory::SearchHistory,
+        SyncCategory::Aliases,
+        SyncCategory::Hotkeys,
+        SyncCategory::Quicklinks,
+        SyncCategory::Snippets,
+        SyncCategory::Notes,
+        SyncCategory::ExtensionSettings,
+        SyncCategory::AiChats,
+        SyncCategory::Themes,
+        SyncCategory::WindowManagement,
+        SyncCategory::ClipboardHistory,
+        SyncCategory::ScriptCommands,
+        SyncCategory::Credentials,
+        SyncCategory::GeneralAdvanced,
+    ];
+
+    /// Field of `cloud_sync_settings` controlling this category
+    pub fn flag(self) -> &'static str {
+        match self {
+            SyncCategory::SearchHistory => "sync_search_history",
+            SyncCategory::Aliases => "sync_aliases",
+            SyncCategory::Hotkeys => "sync_hotkeys",
+            SyncCategory::Quicklinks => "sync_quicklinks",
+            SyncCategory::Snippets => "sync_snippets",
+            SyncCategory::Notes => "sync_notes",
+            SyncCategory::ExtensionSettings => "sync_extensions_settings",
+            SyncCategory::AiChats => "sync_ai_chats",
+            SyncCategory::Themes => "sync_themes",
+            SyncCategory::WindowManagement => "sync_window_management",
+            SyncCategory::ClipboardHistory => "not_synced_clipboard_history",
+            SyncCategory::ScriptCommands => "not_synced_script_commands",
+            SyncCategory::Credentials => "not_synced_credentials",
+            SyncCategory::GeneralAdvanced => "not_synced_general_advanced",
+        }
+    }
+
+    /// Tables holding this category's records
+    pub fn tables(self) -> &'static [SyncedTable] {
+        match self {
+            SyncCategory::SearchHistory => SEARCH_HISTORY_TABLES,
+            SyncCategory::Hotkeys => HOTKEY_TABLES,
+            SyncCategory::Quicklinks => QUICKLINK_TABLES,
+            SyncCategory::Snippets => SNIPPET_TABLES,
+            SyncCategory::ExtensionSettings => EXTENSION_SETTINGS_TABLES,
+            SyncCategory::Themes => THEME_TABLES,
+            SyncCategory::WindowManagement => WINDOW_MANAGEMENT_TABLES,
+            SyncCategory::ClipboardHistory => CLIPBOARD_HISTORY_TABLES,
+            SyncCategory::GeneralAdvanced => GENERAL_ADVANCED_TABLES,
+            // Kept outside SurrealDB: script commands and credentials on disk and in the
+            // keychain, the rest not stored yet
+            SyncCategory::Aliases
+            | SyncCategory::Notes
+            | SyncCategory::AiChats
+            | SyncCategory::ScriptCommands
+            | SyncCategory::Credentials => &[],
+        }
+    }
+
+    /// Whether the flag names data that stays local while it is set
+    fn flag_excludes(self) -> bool {
+        self.flag().starts_with("not_synced_")
+    }
+}
+
+/// The sync flags stored in `cloud_sync_settings`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    /// Flags by field name; a missing flag has the schema default of `true`
+    pub flags: HashMap<String, bool>,
+}
+
+impl SyncSettings {
+    /// Read the flags from the first `cloud_sync_settings` record
+    ///
+    /// Without a record, sync is disabled as the schema default says.
+    pub async fn load(db: &DatabaseService) -> Result<Self, SyncError> {
+        let mut response = db
+            .query(&format!("SELECT * FROM {CLOUD_SYNC_SETTINGS} LIMIT 1"))
+            .await?;
+        let record = response
+            .take::<Option<serde_json::Value>>(0)
+            .map_err(|e| SyncError::Database(e.to_string()))?;
+        Ok(record
+            .map(|record| Self::from_record(&record))
+            .unwrap_or_default())
+    }
+
+    /// Flags from a `cloud_sync_settings` record
+    pub fn from_record(record: &serde_json::Value) -> Self {
+        let flags = record
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(field, value)| Some((field.clone(), value.as_bool()?)))
+            .collect::<HashMap<_, _>>();
+        Self {
+            enabled: flags.get("enabled").copied().unwrap_or(false),
+            flags,
+        }
+    }
+
+    /// Whether a category syncs under these flags
+    pub fn is_synced(&self, category: SyncCategory) -> bool {
+        let flag = self.flags.get(category.flag()).copied().unwrap_or(true);
+        flag != category.flag_excludes()
+    }
+
+    /// Tables of every category that syncs
+    pub fn tables(&self) -> Vec<SyncedTable> {
+        SyncCategory::ALL
+            .into_iter()
+            .filter(|category| self.is_synced(*category))
+            .flat_map(|category| category.tables().iter().copied())
+            .collect()
+    }
+}
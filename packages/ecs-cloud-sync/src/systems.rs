@@ -0,0 +1,138 @@
+//! Bevy systems running setup and sync tasks
+
+use action_items_ecs_surrealdb::DatabaseService;
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use tracing::{info, warn};
+
+use crate::engine::{SyncEngine, SyncReport};
+use crate::error::SyncError;
+use crate::events::*;
+
+/// Component for cloud sync setup and sync tasks
+#[derive(Component)]
+pub struct CloudSyncTask(pub Task<CommandQueue>);
+
+/// Timer for periodic syncs
+#[derive(Resource)]
+pub struct CloudSyncTimer(pub Timer);
+
+/// Progress and outcome of syncing
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CloudSyncStatus {
+    pub syncing: bool,
+    /// A sync was requested while one was running
+    pub pending: bool,
+    pub last_result: Option<Result<SyncReport, SyncError>>,
+}
+
+/// Unlock backends and install the resulting engine
+pub fn handle_setup_requests(
+    mut commands: Commands,
+    mut requests: EventReader<CloudSyncSetupRequested>,
+) {
+    for request in requests.read() {
+        let request = request.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let mut command_queue = CommandQueue::default();
+            let result =
+                SyncEngine::connect(request.backend, &request.passphrase, request.cost).await;
+
+            command_queue.push(move |world: &mut World| {
+                let result = result.map(|engine| {
+                    info!("Cloud sync set up");
+                    world.insert_resource(engine);
+                });
+                if let Err(e) = &result {
+                    warn!("Cloud sync setup failed: {}", e);
+                }
+                world.send_event(CloudSyncSetupCompleted { result });
+            });
+            command_queue
+        });
+        commands.spawn(CloudSyncTask(task));
+    }
+}
+
+/// Request a sync whenever the timer fires, once sync is set up
+pub fn schedule_cloud_sync(
+    time: Res<Time>,
+    mut timer: ResMut<CloudSyncTimer>,
+    engine: Option<Res<SyncEngine>>,
+    mut requests: EventWriter<CloudSyncRequested>,
+) {
+    if timer.0.tick(time.delta()).just_finished() && engine.is_some() {
+        requests.write(CloudSyncRequested);
+    }
+}
+
+/// Start a sync for pending requests unless one is running
+pub fn handle_sync_requests(
+    mut commands: Commands,
+    mut requests: EventReader<CloudSyncRequested>,
+    mut status: ResMut<CloudSyncStatus>,
+    engine: Option<Res<SyncEngine>>,
+    db_service: Option<Res<DatabaseService>>,
+    mut completed: EventWriter<CloudSyncCompleted>,
+) {
+    let requested = requests.read().count() > 0;
+    if status.syncing {
+        status.pending |= requested;
+        return;
+    }
+    if !requested && !status.pending {
+        return;
+    }
+    status.pending = false;
+
+    let Some(engine) = engine else {
+        completed.write(CloudSyncCompleted {
+            result: Err(SyncError::NotConfigured),
+        });
+        return;
+    };
+    let Some(db) = db_service else {
+        completed.write(CloudSyncCompleted {
+            result: Err(SyncError::Database(
+                "Database is not available yet".to_string(),
+            )),
+        });
+        return;
+    };
+
+    status.syncing = true;
+    let engine = (*engine).clone();
+    let db = (*db).clone();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let mut command_queue = CommandQueue::default();
+        let result = engine.sync(&db).await;
+
+        command_queue.push(move |world: &mut World| {
+            if let Err(e) = &result {
+                warn!("Cloud sync failed: {}", e);
+            }
+            if let Some(mut status) = world.get_resource_mut::<CloudSyncStatus>() {
+                status.syncing = false;
+                status.last_result = Some(result.clone());
+            }
+            world.send_event(CloudSyncCompleted { result });
+        });
+        command_queue
+    });
+    commands.spawn(CloudSyncTask(task));
+}
+
+/// Poll cloud sync tasks and apply their results
+pub fn handle_cloud_sync_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut CloudSyncTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.0)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
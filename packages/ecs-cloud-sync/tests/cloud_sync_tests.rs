@@ -0,0 +1,251 @@
+use std::sync::Arc;
+
+use action_items_ecs_cloud_sync::{
+    Causality, KdfCost, LocalDirectoryBackend, SyncBackend, SyncEngine, SyncError, SyncKey,
+    VectorClock,
+};
+use action_items_ecs_surrealdb::DatabaseService;
+use action_items_ecs_surrealdb::testing::{execute, migrated_database};
+use action_items_ecs_user_settings::USER_SETTINGS_MIGRATIONS;
+use serde_json::Value;
+use tempfile::TempDir;
+
+const PASSPHRASE: &str = "correct horse battery staple";
+
+/// Cheap enough to derive keys in tests
+const TEST_COST: KdfCost = KdfCost {
+    memory_kib: 1024,
+    iterations: 1,
+    parallelism: 1,
+};
+
+async fn device(sync_settings: &str) -> DatabaseService {
+    let db = migrated_database(USER_SETTINGS_MIGRATIONS).await;
+    execute(
+        &db,
+        &format!("CREATE cloud_sync_settings:main SET enabled = true{sync_settings}"),
+    )
+    .await;
+    db
+}
+
+async fn hotkey(db: &DatabaseService, key: &str) -> Option<Value> {
+    db.query(&format!("SELECT * FROM hotkey_settings:{key}"))
+        .await
+        .expect("Query should run")
+        .take::<Option<Value>>(0)
+        .expect("Record should deserialize")
+}
+
+async fn engine(backend: &Arc<dyn SyncBackend>) -> SyncEngine {
+    SyncEngine::connect(backend.clone(), PASSPHRASE, TEST_COST)
+        .await
+        .expect("Engine should connect")
+}
+
+fn local_backend(dir: &TempDir) -> Arc<dyn SyncBackend> {
+    Arc::new(LocalDirectoryBackend::new(dir.path()))
+}
+
+const CREATE_LAUNCHER: &str = "CREATE hotkey_settings:launcher SET hotkey_id = 'launcher', \
+     modifiers = ['cmd'], key_code = 'space', description = 'Open launcher'";
+
+#[test]
+fn test_sealed_objects_round_trip_and_detect_tampering() {
+    let (key, params) = SyncKey::create(PASSPHRASE, TEST_COST).unwrap();
+    let sealed = key.seal("changes/a/1", b"secret settings").unwrap();
+    assert_eq!(
+        key.open("changes/a/1", &sealed).unwrap(),
+        b"secret settings"
+    );
+
+    let unlocked = SyncKey::unlock(PASSPHRASE, &params).unwrap();
+    assert_eq!(
+        unlocked.open("changes/a/1", &sealed).unwrap(),
+        b"secret settings"
+    );
+    assert!(matches!(
+        SyncKey::unlock("wrong passphrase", &params),
+        Err(SyncError::WrongPassphrase)
+    ));
+
+    let mut tampered = sealed.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        key.open("changes/a/1", &tampered),
+        Err(SyncError::Tampered(_))
+    ));
+    // Sealed objects cannot be swapped for one another
+    assert!(matches!(
+        key.open("changes/a/2", &sealed),
+        Err(SyncError::Tampered(_))
+    ));
+}
+
+#[test]
+fn test_vector_clocks_tell_concurrent_edits_apart() {
+    let mut a = VectorClock::default();
+    a.increment("a");
+    let mut b = a.clone();
+    assert_eq!(a.compare(&b), Causality::Equal);
+
+    b.increment("b");
+    assert_eq!(b.compare(&a), Causality::After);
+    assert_eq!(a.compare(&b), Causality::Before);
+
+    a.increment("a");
+    assert_eq!(a.compare(&b), Causality::Concurrent);
+    a.merge(&b);
+    assert_eq!(a.compare(&b), Causality::After);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wrong_passphrase_is_rejected_by_backend() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    engine(&backend).await;
+
+    let result = SyncEngine::connect(backend, "not the passphrase", TEST_COST).await;
+    assert!(matches!(result, Err(SyncError::WrongPassphrase)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_hotkey_propagates_between_devices() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let (first, second) = (device("").await, device("").await);
+
+    execute(&first, CREATE_LAUNCHER).await;
+    let report = engine(&backend).await.sync(&first).await.unwrap();
+    assert_eq!(report.pushed, 1);
+
+    let report = engine(&backend).await.sync(&second).await.unwrap();
+    assert_eq!(report.pulled, 1);
+    assert_eq!(report.applied, 1);
+    let synced = hotkey(&second, "launcher")
+        .await
+        .expect("Hotkey should sync");
+    assert_eq!(synced["key_code"], "space");
+    assert_eq!(synced["modifiers"], serde_json::json!(["cmd"]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_edits_merge_per_field() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let (first, second) = (device("").await, device("").await);
+    let (first_engine, second_engine) = (engine(&backend).await, engine(&backend).await);
+
+    execute(&first, CREATE_LAUNCHER).await;
+    first_engine.sync(&first).await.unwrap();
+    second_engine.sync(&second).await.unwrap();
+
+    execute(&first, "UPDATE hotkey_settings:launcher SET key_code = 'k'").await;
+    execute(
+        &second,
+        "UPDATE hotkey_settings:launcher SET description = 'Search'",
+    )
+    .await;
+    first_engine.sync(&first).await.unwrap();
+    let report = second_engine.sync(&second).await.unwrap();
+    assert_eq!(report.conflicts, 1);
+    first_engine.sync(&first).await.unwrap();
+
+    for db in [&first, &second] {
+        let merged = hotkey(db, "launcher").await.unwrap();
+        assert_eq!(merged["key_code"], "k");
+        assert_eq!(merged["description"], "Search");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_later_write_wins_the_same_field() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let (first, second) = (device("").await, device("").await);
+    let (first_engine, second_engine) = (engine(&backend).await, engine(&backend).await);
+
+    execute(&first, CREATE_LAUNCHER).await;
+    first_engine.sync(&first).await.unwrap();
+    second_engine.sync(&second).await.unwrap();
+
+    execute(&first, "UPDATE hotkey_settings:launcher SET key_code = 'k'").await;
+    first_engine.sync(&first).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    execute(
+        &second,
+        "UPDATE hotkey_settings:launcher SET key_code = 'j'",
+    )
+    .await;
+    second_engine.sync(&second).await.unwrap();
+    first_engine.sync(&first).await.unwrap();
+
+    for db in [&first, &second] {
+        assert_eq!(hotkey(db, "launcher").await.unwrap()["key_code"], "j");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_delete_propagates() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let (first, second) = (device("").await, device("").await);
+    let (first_engine, second_engine) = (engine(&backend).await, engine(&backend).await);
+
+    execute(&first, CREATE_LAUNCHER).await;
+    first_engine.sync(&first).await.unwrap();
+    second_engine.sync(&second).await.unwrap();
+    assert!(hotkey(&second, "launcher").await.is_some());
+
+    execute(&second, "DELETE hotkey_settings:launcher").await;
+    second_engine.sync(&second).await.unwrap();
+    first_engine.sync(&first).await.unwrap();
+    assert!(hotkey(&first, "launcher").await.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_disabled_categories_stay_local() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let first = device(", sync_hotkeys = false").await;
+    let second = device("").await;
+
+    execute(&first, CREATE_LAUNCHER).await;
+    let report = engine(&backend).await.sync(&first).await.unwrap();
+    assert_eq!(report.pushed, 0);
+
+    engine(&backend).await.sync(&second).await.unwrap();
+    assert!(hotkey(&second, "launcher").await.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_requires_enabled_setting() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let db = device("").await;
+    execute(&db, "UPDATE cloud_sync_settings SET enabled = false").await;
+
+    let result = engine(&backend).await.sync(&db).await;
+    assert_eq!(result, Err(SyncError::Disabled));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_backend_never_sees_plaintext() {
+    let dir = TempDir::new().unwrap();
+    let backend = local_backend(&dir);
+    let db = device("").await;
+
+    execute(&db, CREATE_LAUNCHER).await;
+    engine(&backend).await.sync(&db).await.unwrap();
+
+    let names = backend.list("changes/").await.unwrap();
+    assert_eq!(names.len(), 1);
+    let stored = backend.get(&names[0]).await.unwrap().unwrap();
+    let stored = String::from_utf8_lossy(&stored);
+    for plaintext in ["launcher", "Open launcher", "space", "hotkey_settings"] {
+        assert!(
+            !stored.contains(plaintext),
+            "Backend object contains '{plaintext}'"
+        );
+    }
+}
@@ -30,7 +30,7 @@ pub use events::{
 pub use http::HeaderMap;
 pub use plugin::HttpPlugin;
 // Re-export HTTP types for convenience
-pub use reqwest::{Method, StatusCode, Version};
+pub use reqwest::{Client, Method, StatusCode, Version};
 pub use resources::{HttpClientPool, HttpConfig, RateLimitManager, RequestMetrics};
 /// HTTP client error types
 pub use security::HttpError;
@@ -0,0 +1,71 @@
+//! Preference values set for installed extensions
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use action_items_common::AppDirectories;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Values users set for extension preferences, keyed by extension id and preference name
+///
+/// Only values the user changed are stored; defaults stay in each extension's manifest.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionPreferences {
+    extensions: HashMap<String, Map<String, Value>>,
+}
+
+impl ExtensionPreferences {
+    /// Where extension preferences are kept in the user's config directory
+    pub fn default_path() -> PathBuf {
+        AppDirectories::new()
+            .config_dir()
+            .join("extension-preferences.json")
+    }
+
+    /// Read stored preferences, starting empty if the file is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!(
+                    "Ignoring invalid extension preferences {}: {}",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write the preferences, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, content)
+    }
+
+    /// Values the user set for an extension
+    pub fn values(&self, extension_id: &str) -> Map<String, Value> {
+        self.extensions
+            .get(extension_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Set one preference of an extension
+    pub fn set(&mut self, extension_id: &str, name: &str, value: Value) {
+        self.extensions
+            .entry(extension_id.to_string())
+            .or_default()
+            .insert(name.to_string(), value);
+    }
+
+    /// Go back to an extension's defaults
+    pub fn reset(&mut self, extension_id: &str) {
+        self.extensions.remove(extension_id);
+    }
+}
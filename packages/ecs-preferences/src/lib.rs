@@ -16,6 +16,7 @@
 //! ```
 
 pub mod events;
+pub mod extensions;
 pub mod plugin;
 pub mod resources;
 pub mod systems;
@@ -29,4 +30,5 @@ pub use ui::PreferencesUIPlugin;
 
 // Re-export key types
 pub use events::*;
+pub use extensions::ExtensionPreferences;
 pub use resources::*;
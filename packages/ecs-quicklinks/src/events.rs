@@ -0,0 +1,58 @@
+//! Quicklink request and notification events
+//!
+//! CRUD requests carry an `operation_id` that is echoed in [`QuicklinkOperationCompleted`], so
+//! callers can match results to their requests.
+
+use std::collections::HashMap;
+
+use action_items_common::plugin_interface::action_item::ActionType;
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::types::{Quicklink, QuicklinkDraft, QuicklinkError};
+
+/// Create a quicklink
+#[derive(Event, Debug, Clone)]
+pub struct QuicklinkCreateRequested {
+    pub operation_id: Uuid,
+    pub draft: QuicklinkDraft,
+}
+
+/// Replace the fields of a quicklink
+#[derive(Event, Debug, Clone)]
+pub struct QuicklinkUpdateRequested {
+    pub operation_id: Uuid,
+    pub quicklink_id: String,
+    pub draft: QuicklinkDraft,
+}
+
+/// Delete a quicklink
+#[derive(Event, Debug, Clone)]
+pub struct QuicklinkDeleteRequested {
+    pub operation_id: Uuid,
+    pub quicklink_id: String,
+}
+
+/// Result of a create, update or delete request
+#[derive(Event, Debug, Clone)]
+pub struct QuicklinkOperationCompleted {
+    pub operation_id: Uuid,
+    /// The created or updated quicklink, or the deleted one
+    pub result: Result<Quicklink, QuicklinkError>,
+}
+
+/// Fill in a quicklink's arguments and open the result
+#[derive(Event, Debug, Clone)]
+pub struct QuicklinkOpenRequested {
+    pub quicklink_id: String,
+    /// Values for the template's placeholders, by argument name
+    pub arguments: HashMap<String, String>,
+}
+
+/// Result of an open request
+#[derive(Event, Debug, Clone)]
+pub struct QuicklinkOpened {
+    pub quicklink_id: String,
+    /// The action that was performed
+    pub result: Result<ActionType, QuicklinkError>,
+}
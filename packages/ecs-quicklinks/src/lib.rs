@@ -0,0 +1,30 @@
+//! Action Items ECS Quicklinks
+//!
+//! Bevy ECS plugin for user-defined links with arguments.
+//!
+//! - Quicklinks are stored in SurrealDB and managed through request events
+//! - Templates are URLs or file paths with `{name}` placeholders, each backed by a typed
+//!   `ArgumentDefinition`
+//! - Keywords select a quicklink directly from the launcher, with the rest of the query used
+//!   as its arguments
+//! - Resolved quicklinks become `ActionType::OpenUrl` or `ActionType::OpenFile` and are opened
+//!   with the system default handler
+
+pub mod events;
+pub mod library;
+pub mod open;
+pub mod persistence;
+pub mod plugin;
+pub mod systems;
+pub mod template;
+pub mod types;
+
+pub use events::{
+    QuicklinkCreateRequested, QuicklinkDeleteRequested, QuicklinkOpenRequested, QuicklinkOpened,
+    QuicklinkOperationCompleted, QuicklinkUpdateRequested,
+};
+pub use library::{QuicklinkLibrary, QuicklinkMatch};
+pub use plugin::QuicklinksPlugin;
+pub use systems::{QUICKLINK_ACTION_PREFIX, QUICKLINKS_PROVIDER_ID};
+pub use template::{TargetKind, Template};
+pub use types::{Quicklink, QuicklinkDraft, QuicklinkError};
@@ -0,0 +1,284 @@
+//! In-memory quicklink library with validation, search and resolution
+//!
+//! The resource mirrors the `quicklink` table; systems write every change through to the
+//! database, so this type itself stays free of I/O and is easy to test.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use action_items_common::plugin_interface::action_item::ActionType;
+use action_items_common::plugin_interface::commands::ArgumentDefinition;
+use action_items_ecs_surrealdb::LoadState;
+use bevy::prelude::*;
+
+use crate::template::Template;
+use crate::types::{Quicklink, QuicklinkDraft, QuicklinkError, text_argument};
+
+/// A quicklink matching a launcher query
+#[derive(Debug, Clone)]
+pub struct QuicklinkMatch<'a> {
+    pub quicklink: &'a Quicklink,
+    pub score: f32,
+    /// Text typed after the quicklink's keyword, used as positional arguments
+    pub argument_input: Option<String>,
+}
+
+/// All quicklinks, ordered by name
+#[derive(Resource, Debug, Default)]
+pub struct QuicklinkLibrary {
+    pub load_state: LoadState,
+    quicklinks: Vec<Quicklink>,
+}
+
+impl QuicklinkLibrary {
+    /// Merge quicklinks loaded from the database into the library
+    ///
+    /// Returns quicklinks created before loading finished, which still need to be written to
+    /// the database.
+    pub fn load(&mut self, stored: Vec<Quicklink>) -> Vec<Quicklink> {
+        let unsaved: Vec<Quicklink> = std::mem::take(&mut self.quicklinks)
+            .into_iter()
+            .filter(|quicklink| !stored.iter().any(|q| q.id == quicklink.id))
+            .collect();
+        self.quicklinks = stored;
+        self.quicklinks.extend(unsaved.iter().cloned());
+        self.sort();
+        self.load_state = LoadState::Loaded;
+        unsaved
+    }
+
+    /// Create a quicklink from a draft
+    pub fn create(&mut self, draft: QuicklinkDraft, now: i64) -> Result<Quicklink, QuicklinkError> {
+        let draft = self.validate(draft, None)?;
+        let quicklink = Quicklink {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            name: draft.name,
+            keyword: draft.keyword,
+            template: draft.template,
+            arguments: draft.arguments,
+            created_at: now,
+            updated_at: now,
+        };
+        self.quicklinks.push(quicklink.clone());
+        self.sort();
+        Ok(quicklink)
+    }
+
+    /// Replace the fields of an existing quicklink
+    pub fn update(
+        &mut self,
+        id: &str,
+        draft: QuicklinkDraft,
+        now: i64,
+    ) -> Result<Quicklink, QuicklinkError> {
+        let draft = self.validate(draft, Some(id))?;
+        let quicklink = self
+            .quicklinks
+            .iter_mut()
+            .find(|q| q.id == id)
+            .ok_or_else(|| QuicklinkError::NotFound(id.to_string()))?;
+        quicklink.name = draft.name;
+        quicklink.keyword = draft.keyword;
+        quicklink.template = draft.template;
+        quicklink.arguments = draft.arguments;
+        quicklink.updated_at = now;
+        let quicklink = quicklink.clone();
+        self.sort();
+        Ok(quicklink)
+    }
+
+    pub fn remove(&mut self, id: &str) -> Result<Quicklink, QuicklinkError> {
+        let index = self
+            .quicklinks
+            .iter()
+            .position(|q| q.id == id)
+            .ok_or_else(|| QuicklinkError::NotFound(id.to_string()))?;
+        Ok(self.quicklinks.remove(index))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Quicklink> {
+        self.quicklinks.iter().find(|q| q.id == id)
+    }
+
+    pub fn quicklinks(&self) -> &[Quicklink] {
+        &self.quicklinks
+    }
+
+    pub fn len(&self) -> usize {
+        self.quicklinks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quicklinks.is_empty()
+    }
+
+    /// Validate argument values and build the action that opens a quicklink
+    pub fn resolve(
+        &self,
+        id: &str,
+        values: &HashMap<String, String>,
+    ) -> Result<ActionType, QuicklinkError> {
+        let quicklink = self
+            .get(id)
+            .ok_or_else(|| QuicklinkError::NotFound(id.to_string()))?;
+        Template::parse(&quicklink.template)?.resolve(&quicklink.arguments, values)
+    }
+
+    /// Search quicklinks by keyword, name and template, best matches first
+    ///
+    /// A query equal to a keyword selects that quicklink; a keyword followed by more text
+    /// selects it with the rest of the query as [`QuicklinkMatch::argument_input`].
+    pub fn search(&self, query: &str, limit: usize) -> Vec<QuicklinkMatch<'_>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let lowered = query.to_lowercase();
+        let terms: Vec<&str> = lowered.split_whitespace().collect();
+
+        let mut matches: Vec<QuicklinkMatch<'_>> = self
+            .quicklinks
+            .iter()
+            .filter_map(|quicklink| {
+                if let Some(keyword) = &quicklink.keyword {
+                    if keyword == query {
+                        return Some((quicklink, 1.0, None));
+                    }
+                    if let Some(rest) = query.strip_prefix(keyword.as_str())
+                        && rest.starts_with(char::is_whitespace)
+                    {
+                        return Some((quicklink, 0.98, Some(rest.trim().to_string())));
+                    }
+                }
+
+                let name = quicklink.name.to_lowercase();
+                if name.starts_with(&lowered) {
+                    return Some((quicklink, 0.85, None));
+                }
+                if terms.iter().all(|term| name.contains(term)) {
+                    return Some((quicklink, 0.75, None));
+                }
+                let template = quicklink.template.to_lowercase();
+                if terms
+                    .iter()
+                    .all(|term| name.contains(term) || template.contains(term))
+                {
+                    return Some((quicklink, 0.6, None));
+                }
+                None
+            })
+            .map(|(quicklink, score, argument_input)| QuicklinkMatch {
+                quicklink,
+                score,
+                argument_input,
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Normalise a draft and check it against the template and the other quicklinks
+    ///
+    /// Arguments keep their given order, followed by text arguments for placeholders that
+    /// have no definition.
+    fn validate(
+        &self,
+        mut draft: QuicklinkDraft,
+        except_id: Option<&str>,
+    ) -> Result<QuicklinkDraft, QuicklinkError> {
+        draft.name = draft.name.trim().to_string();
+        if draft.name.is_empty() {
+            return Err(QuicklinkError::EmptyName);
+        }
+        draft.keyword = draft
+            .keyword
+            .map(|keyword| keyword.trim().to_string())
+            .filter(|keyword| !keyword.is_empty());
+        if let Some(keyword) = &draft.keyword
+            && keyword.contains(char::is_whitespace)
+        {
+            return Err(QuicklinkError::InvalidKeyword(keyword.clone()));
+        }
+        draft.template = draft.template.trim().to_string();
+        let template = Template::parse(&draft.template)?;
+        let placeholders = template.placeholders();
+
+        let mut arguments: Vec<ArgumentDefinition> = Vec::new();
+        for argument in draft.arguments {
+            if arguments.iter().any(|a| a.name == argument.name) {
+                return Err(QuicklinkError::InvalidTemplate(format!(
+                    "argument '{}' is defined twice",
+                    argument.name
+                )));
+            }
+            if !placeholders.contains(&argument.name.as_str()) {
+                return Err(QuicklinkError::UnusedArgument(argument.name));
+            }
+            arguments.push(argument);
+        }
+        for name in placeholders {
+            if !arguments.iter().any(|a| a.name == name) {
+                arguments.push(text_argument(name));
+            }
+        }
+        draft.arguments = arguments;
+
+        for other in self
+            .quicklinks
+            .iter()
+            .filter(|q| Some(q.id.as_str()) != except_id)
+        {
+            if other.name.eq_ignore_ascii_case(&draft.name) {
+                return Err(QuicklinkError::DuplicateName(draft.name));
+            }
+            if draft.keyword.is_some() && other.keyword == draft.keyword {
+                return Err(QuicklinkError::DuplicateKeyword(
+                    draft.keyword.unwrap_or_default(),
+                ));
+            }
+        }
+        Ok(draft)
+    }
+
+    fn sort(&mut self) {
+        self.quicklinks
+            .sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    }
+}
+
+/// Assign text typed after a keyword to arguments in order
+///
+/// Words fill the arguments one by one and the last argument takes the remaining text, so a
+/// search quicklink with a single `{query}` receives everything typed after its keyword.
+pub fn bind_positional(arguments: &[ArgumentDefinition], input: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut rest = input.trim();
+    for (index, argument) in arguments.iter().enumerate() {
+        if rest.is_empty() {
+            break;
+        }
+        let value = if index + 1 == arguments.len() {
+            std::mem::take(&mut rest)
+        } else {
+            let (word, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            rest = remainder.trim_start();
+            word
+        };
+        values.insert(argument.name.clone(), value.to_string());
+    }
+    values
+}
+
+/// Current time as Unix milliseconds
+pub fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,42 @@
+//! Opening resolved quicklinks with the system default handler
+
+use action_items_common::plugin_interface::action_item::ActionType;
+
+use crate::types::QuicklinkError;
+
+/// Open an [`ActionType::OpenUrl`] or [`ActionType::OpenFile`] action
+///
+/// Blocks until the handler has been launched, so call it from a task.
+pub fn open_action(action: &ActionType) -> Result<(), QuicklinkError> {
+    let result = match action {
+        ActionType::OpenUrl(url) => opener::open(url),
+        ActionType::OpenFile(path) => {
+            if !path.exists() {
+                return Err(QuicklinkError::Open {
+                    target: path.display().to_string(),
+                    reason: "no such file or directory".to_string(),
+                });
+            }
+            opener::open(path)
+        },
+        other => {
+            return Err(QuicklinkError::Open {
+                target: format!("{other:?}"),
+                reason: "not an open action".to_string(),
+            });
+        },
+    };
+    result.map_err(|e| QuicklinkError::Open {
+        target: describe_action(action),
+        reason: e.to_string(),
+    })
+}
+
+/// The URL or path an action opens
+pub fn describe_action(action: &ActionType) -> String {
+    match action {
+        ActionType::OpenUrl(url) => url.clone(),
+        ActionType::OpenFile(path) => path.display().to_string(),
+        other => format!("{other:?}"),
+    }
+}
@@ -0,0 +1,81 @@
+//! SurrealDB persistence for quicklinks
+
+use action_items_ecs_surrealdb::{DatabaseError, DatabaseService, LoadState, Persisted};
+use surrealdb::RecordId;
+
+use crate::library::QuicklinkLibrary;
+use crate::types::Quicklink;
+
+/// Table holding quicklinks
+pub const QUICKLINK_TABLE: &str = "quicklink";
+
+/// Definition of the quicklink table
+///
+/// The table is schemaless so argument definitions are stored as they serialize.
+pub const QUICKLINK_SCHEMA: &str = r#"
+DEFINE TABLE IF NOT EXISTS quicklink SCHEMALESS;
+DEFINE FIELD IF NOT EXISTS name ON quicklink TYPE string;
+DEFINE FIELD IF NOT EXISTS keyword ON quicklink TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS template ON quicklink TYPE string;
+DEFINE FIELD IF NOT EXISTS arguments ON quicklink TYPE array<object> DEFAULT [];
+DEFINE FIELD IF NOT EXISTS created_at ON quicklink TYPE int;
+DEFINE FIELD IF NOT EXISTS updated_at ON quicklink TYPE int;
+DEFINE INDEX IF NOT EXISTS quicklink_keyword_idx ON quicklink COLUMNS keyword;
+"#;
+
+fn record_id(id: &str) -> RecordId {
+    RecordId::from((QUICKLINK_TABLE, id))
+}
+
+fn query_failed(error: impl std::fmt::Display) -> DatabaseError {
+    DatabaseError::QueryFailed(error.to_string())
+}
+
+impl Persisted for QuicklinkLibrary {
+    const NAME: &'static str = "quicklinks";
+    const SCHEMA: &'static str = QUICKLINK_SCHEMA;
+
+    fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    fn set_load_state(&mut self, state: LoadState) {
+        self.load_state = state;
+    }
+}
+
+/// Load all quicklinks
+pub async fn load_all(db: &DatabaseService) -> Result<Vec<Quicklink>, DatabaseError> {
+    // Record ids come back as `quicklink:⟨key⟩`; the key alone is the quicklink id
+    let mut response = db
+        .query("SELECT *, record::id(id) AS id FROM quicklink")
+        .await?;
+    response.take::<Vec<Quicklink>>(0).map_err(query_failed)
+}
+
+/// Upsert a quicklink
+pub async fn save_quicklink(
+    db: &DatabaseService,
+    quicklink: &Quicklink,
+) -> Result<(), DatabaseError> {
+    // The record key carries the id, so it is not repeated in the content
+    let mut content = serde_json::to_value(quicklink).map_err(query_failed)?;
+    if let Some(fields) = content.as_object_mut() {
+        fields.remove("id");
+        if quicklink.keyword.is_none() {
+            fields.remove("keyword");
+        }
+    }
+    let sql = format!("UPSERT {} CONTENT {}", record_id(&quicklink.id), content);
+    db.query(&sql).await?.check().map_err(query_failed)?;
+    Ok(())
+}
+
+/// Delete a quicklink
+pub async fn delete_quicklink(db: &DatabaseService, id: &str) -> Result<(), DatabaseError> {
+    db.query(&format!("DELETE {}", record_id(id)))
+        .await?
+        .check()
+        .map_err(query_failed)?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Quicklinks plugin
+
+use action_items_core::LauncherEvent;
+use action_items_ecs_search_aggregator::{
+    CurrentQuery, LocalSearchProviders, SearchRequested, SearchResultReceived,
+};
+use action_items_ecs_surrealdb::poll_persistence_tasks;
+use bevy::prelude::*;
+
+use crate::events::*;
+use crate::library::QuicklinkLibrary;
+use crate::systems::*;
+
+/// Plugin providing quicklink storage, opening and launcher search
+///
+/// Persistence starts once `DatabasePlugin` has inserted the `DatabaseService`; without it,
+/// quicklinks are kept in memory for the session only.
+pub struct QuicklinksPlugin;
+
+impl Plugin for QuicklinksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<QuicklinkLibrary>()
+            .init_resource::<LocalSearchProviders>()
+            .init_resource::<CurrentQuery>()
+            .add_event::<SearchRequested>()
+            .add_event::<SearchResultReceived>()
+            .add_event::<LauncherEvent>()
+            .add_event::<QuicklinkCreateRequested>()
+            .add_event::<QuicklinkUpdateRequested>()
+            .add_event::<QuicklinkDeleteRequested>()
+            .add_event::<QuicklinkOperationCompleted>()
+            .add_event::<QuicklinkOpenRequested>()
+            .add_event::<QuicklinkOpened>()
+            .add_systems(Startup, register_search_provider)
+            .add_systems(
+                Update,
+                (
+                    load_quicklink_library,
+                    handle_quicklink_crud_requests,
+                    handle_quicklink_open_requests,
+                    search_quicklinks,
+                    handle_quicklink_tasks,
+                    poll_persistence_tasks::<QuicklinkLibrary>,
+                )
+                    .chain(),
+            );
+
+        tracing::info!("QuicklinksPlugin initialized");
+    }
+}
@@ -0,0 +1,291 @@
+//! Bevy systems for quicklink persistence, opening and search
+
+use std::collections::HashMap;
+
+use action_items_core::{LauncherEvent, LauncherEventType};
+use action_items_ecs_search_aggregator::{
+    CurrentQuery, LocalSearchProviders, SearchRequested, SearchResult, SearchResultReceived,
+};
+use action_items_ecs_surrealdb::{DatabaseService, spawn_write, start_loading};
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use tracing::{info, warn};
+
+use crate::events::*;
+use crate::library::{QuicklinkLibrary, bind_positional, unix_millis};
+use crate::open::{describe_action, open_action};
+use crate::persistence;
+use crate::template::{TargetKind, Template};
+use crate::types::{Quicklink, QuicklinkError};
+
+/// Provider id attached to quicklink search results
+pub const QUICKLINKS_PROVIDER_ID: &str = "quicklinks";
+/// Prefix of launcher action ids that open a quicklink
+pub const QUICKLINK_ACTION_PREFIX: &str = "quicklink_";
+/// Quicklinks listed for one query at most
+const MAX_SEARCH_RESULTS: usize = 20;
+
+/// Component for quicklink open tasks
+#[derive(Component)]
+pub struct QuicklinkTask(pub Task<CommandQueue>);
+
+/// Register quicklinks as a local search provider
+pub fn register_search_provider(mut providers: ResMut<LocalSearchProviders>) {
+    providers.register(QUICKLINKS_PROVIDER_ID);
+}
+
+/// Apply the schema and load persisted quicklinks once the database is available
+pub fn load_quicklink_library(
+    mut commands: Commands,
+    mut library: ResMut<QuicklinkLibrary>,
+    db_service: Option<Res<DatabaseService>>,
+) {
+    start_loading(
+        &mut commands,
+        library.as_mut(),
+        db_service.as_deref(),
+        |db| async move { persistence::load_all(&db).await },
+        |world, quicklinks: Vec<Quicklink>| {
+            info!("Loaded {} quicklinks", quicklinks.len());
+            let Some(mut library) = world.get_resource_mut::<QuicklinkLibrary>() else {
+                return;
+            };
+            let unsaved = library.load(quicklinks);
+            for quicklink in unsaved {
+                spawn_save(world, quicklink);
+            }
+        },
+    );
+}
+
+/// Handle create, update and delete requests
+pub fn handle_quicklink_crud_requests(
+    mut commands: Commands,
+    mut create_events: EventReader<QuicklinkCreateRequested>,
+    mut update_events: EventReader<QuicklinkUpdateRequested>,
+    mut delete_events: EventReader<QuicklinkDeleteRequested>,
+    mut library: ResMut<QuicklinkLibrary>,
+    mut completed_events: EventWriter<QuicklinkOperationCompleted>,
+) {
+    let now = unix_millis();
+
+    for event in create_events.read() {
+        let result = library.create(event.draft.clone(), now);
+        if let Ok(quicklink) = &result {
+            let quicklink = quicklink.clone();
+            commands.queue(move |world: &mut World| spawn_save(world, quicklink));
+        }
+        completed_events.write(QuicklinkOperationCompleted {
+            operation_id: event.operation_id,
+            result,
+        });
+    }
+
+    for event in update_events.read() {
+        let result = library.update(&event.quicklink_id, event.draft.clone(), now);
+        if let Ok(quicklink) = &result {
+            let quicklink = quicklink.clone();
+            commands.queue(move |world: &mut World| spawn_save(world, quicklink));
+        }
+        completed_events.write(QuicklinkOperationCompleted {
+            operation_id: event.operation_id,
+            result,
+        });
+    }
+
+    for event in delete_events.read() {
+        let result = library.remove(&event.quicklink_id);
+        if let Ok(quicklink) = &result {
+            let id = quicklink.id.clone();
+            commands.queue(move |world: &mut World| spawn_delete(world, id));
+        }
+        completed_events.write(QuicklinkOperationCompleted {
+            operation_id: event.operation_id,
+            result,
+        });
+    }
+}
+
+/// Open quicklinks requested directly or chosen from launcher results
+///
+/// Launcher executions use any text typed after the quicklink's keyword as positional
+/// arguments. [`QuicklinkOpened`] is sent once the system handler has been launched.
+pub fn handle_quicklink_open_requests(
+    mut commands: Commands,
+    mut open_events: EventReader<QuicklinkOpenRequested>,
+    mut launcher_events: EventReader<LauncherEvent>,
+    library: Res<QuicklinkLibrary>,
+    current_query: Option<Res<CurrentQuery>>,
+    mut opened_events: EventWriter<QuicklinkOpened>,
+) {
+    let launched = launcher_events
+        .read()
+        .filter_map(|event| match &event.event_type {
+            LauncherEventType::Execute(action_id) => {
+                let quicklink_id = action_id.strip_prefix(QUICKLINK_ACTION_PREFIX)?;
+                let query = current_query.as_ref().map_or("", |query| query.0.as_str());
+                Some(QuicklinkOpenRequested {
+                    quicklink_id: quicklink_id.to_string(),
+                    arguments: keyword_arguments(&library, quicklink_id, query),
+                })
+            },
+            _ => None,
+        });
+    let requests: Vec<QuicklinkOpenRequested> =
+        open_events.read().cloned().chain(launched).collect();
+
+    for request in requests {
+        let action = match library.resolve(&request.quicklink_id, &request.arguments) {
+            Ok(action) => action,
+            Err(e) => {
+                warn!("Failed to open quicklink {}: {}", request.quicklink_id, e);
+                opened_events.write(QuicklinkOpened {
+                    quicklink_id: request.quicklink_id,
+                    result: Err(e),
+                });
+                continue;
+            },
+        };
+
+        let quicklink_id = request.quicklink_id;
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let mut command_queue = CommandQueue::default();
+            let result = open_action(&action).map(|()| action);
+            match &result {
+                Ok(action) => info!(
+                    "Opened quicklink {}: {}",
+                    quicklink_id,
+                    describe_action(action)
+                ),
+                Err(e) => warn!("Failed to open quicklink {}: {}", quicklink_id, e),
+            }
+            command_queue.push(move |world: &mut World| {
+                world.send_event(QuicklinkOpened {
+                    quicklink_id,
+                    result,
+                });
+            });
+            command_queue
+        });
+        commands.spawn(QuicklinkTask(task));
+    }
+}
+
+/// Answer aggregated launcher searches from the quicklink library
+pub fn search_quicklinks(
+    mut requests: EventReader<SearchRequested>,
+    mut results: EventWriter<SearchResultReceived>,
+    library: Res<QuicklinkLibrary>,
+) {
+    for request in requests.read() {
+        if !request
+            .requesting_plugins
+            .iter()
+            .any(|id| id == QUICKLINKS_PROVIDER_ID)
+        {
+            continue;
+        }
+
+        let started = std::time::Instant::now();
+        let matches = library
+            .search(&request.query, MAX_SEARCH_RESULTS)
+            .into_iter()
+            .map(|found| SearchResult {
+                title: found.quicklink.name.clone(),
+                description: describe_quicklink(found.quicklink),
+                action: format!("{QUICKLINK_ACTION_PREFIX}{}", found.quicklink.id),
+                icon: Some(quicklink_icon(found.quicklink).to_string()),
+                score: found.score,
+                plugin_id: QUICKLINKS_PROVIDER_ID.to_string(),
+                title_matches: Vec::new(),
+            })
+            .collect();
+
+        results.write(SearchResultReceived {
+            search_id: request.search_id,
+            plugin_id: QUICKLINKS_PROVIDER_ID.to_string(),
+            results: matches,
+            execution_time_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+}
+
+/// Poll quicklink tasks
+pub fn handle_quicklink_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut QuicklinkTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.0)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Arguments typed after the quicklink's keyword in the launcher query
+fn keyword_arguments(
+    library: &QuicklinkLibrary,
+    quicklink_id: &str,
+    query: &str,
+) -> HashMap<String, String> {
+    let input = library
+        .search(query, usize::MAX)
+        .into_iter()
+        .find(|found| found.quicklink.id == quicklink_id)
+        .and_then(|found| found.argument_input);
+    match (input, library.get(quicklink_id)) {
+        (Some(input), Some(quicklink)) => bind_positional(&quicklink.arguments, &input),
+        _ => HashMap::new(),
+    }
+}
+
+fn describe_quicklink(quicklink: &Quicklink) -> String {
+    match (&quicklink.keyword, quicklink.arguments.is_empty()) {
+        (Some(keyword), false) => {
+            let names: Vec<&str> = quicklink
+                .arguments
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect();
+            format!("{keyword} <{}> · {}", names.join("> <"), quicklink.template)
+        },
+        (Some(keyword), true) => format!("{keyword} · {}", quicklink.template),
+        (None, _) => quicklink.template.clone(),
+    }
+}
+
+fn quicklink_icon(quicklink: &Quicklink) -> &'static str {
+    match Template::parse(&quicklink.template).map(|t| t.kind()) {
+        Ok(TargetKind::File) => "📁",
+        _ => "🔗",
+    }
+}
+
+/// Persist a quicklink in the background once the library is backed by the database
+fn spawn_save(world: &mut World, quicklink: Quicklink) {
+    spawn_write::<QuicklinkLibrary, _>(world, move |db| async move {
+        if let Err(e) = persistence::save_quicklink(&db, &quicklink).await {
+            warn!(
+                "Failed to persist quicklink {}: {}",
+                quicklink.id,
+                QuicklinkError::from(e)
+            );
+        }
+    });
+}
+
+/// Delete a quicklink in the background once the library is backed by the database
+fn spawn_delete(world: &mut World, id: String) {
+    spawn_write::<QuicklinkLibrary, _>(world, move |db| async move {
+        if let Err(e) = persistence::delete_quicklink(&db, &id).await {
+            warn!(
+                "Failed to delete quicklink {}: {}",
+                id,
+                QuicklinkError::from(e)
+            );
+        }
+    });
+}
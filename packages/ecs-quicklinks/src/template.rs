@@ -0,0 +1,258 @@
+//! Quicklink templates
+//!
+//! A template is a URL or file path with `{name}` placeholders, for example
+//! `https://github.com/search?q={query}` or `file:///~/projects/{name}`. Placeholder values are
+//! checked against their [`ArgumentDefinition`] and then substituted:
+//!
+//! - URL templates percent-encode values; `{name|raw}` inserts a value unencoded, e.g. to fill
+//!   in a whole path
+//! - File templates (`file://` URLs, absolute paths and paths starting with `~`) insert values
+//!   as typed and expand a leading `~` to the home directory; only the literal text of `file://`
+//!   URLs is percent-decoded
+//!
+//! Braces that do not form a placeholder are kept as literal text.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use action_items_common::plugin_interface::action_item::ActionType;
+use action_items_common::plugin_interface::commands::{ArgumentDefinition, ArgumentType};
+
+use crate::types::QuicklinkError;
+
+/// What a template opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Url,
+    File,
+}
+
+/// A parsed template segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Literal(String),
+    Placeholder {
+        name: String,
+        /// Insert the value without percent-encoding
+        raw: bool,
+    },
+}
+
+/// A parsed quicklink template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    kind: TargetKind,
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse template text
+    pub fn parse(text: &str) -> Result<Self, QuicklinkError> {
+        let text = text.trim();
+        let kind = target_kind(text).ok_or_else(|| {
+            QuicklinkError::InvalidTemplate(format!(
+                "'{text}' is neither a URL nor an absolute file path"
+            ))
+        })?;
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = text;
+
+        while let Some(open) = rest.find('{') {
+            literal.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            match after.find('}').and_then(|close| {
+                parse_placeholder(&after[..close]).map(|segment| (close, segment))
+            }) {
+                Some((close, segment)) => {
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(segment);
+                    rest = &after[close + 1..];
+                },
+                None => {
+                    literal.push('{');
+                    rest = after;
+                },
+            }
+        }
+
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { kind, segments })
+    }
+
+    pub fn kind(&self) -> TargetKind {
+        self.kind
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Placeholder names in order of first use
+    pub fn placeholders(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for segment in &self.segments {
+            if let Segment::Placeholder { name, .. } = segment
+                && !names.contains(&name.as_str())
+            {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Validate argument values and build the action that opens the result
+    ///
+    /// Placeholders without a definition are treated as required text arguments. Missing
+    /// optional arguments are replaced by nothing.
+    pub fn resolve(
+        &self,
+        arguments: &[ArgumentDefinition],
+        values: &HashMap<String, String>,
+    ) -> Result<ActionType, QuicklinkError> {
+        // Literal parts of `file://` URLs may be percent-encoded; plain paths are taken as typed
+        let file_url = matches!(
+            self.segments.first(),
+            Some(Segment::Literal(text)) if text.starts_with("file://")
+        );
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) if file_url => {
+                    out.push_str(&urlencoding::decode(text).map_err(|e| {
+                        QuicklinkError::InvalidTemplate(format!("invalid escape in '{text}': {e}"))
+                    })?)
+                },
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Placeholder { name, raw } => {
+                    let value = match arguments.iter().find(|a| &a.name == name) {
+                        Some(definition) => validate_argument(definition, values.get(name))?,
+                        None => {
+                            validate_argument(&crate::types::text_argument(name), values.get(name))?
+                        },
+                    };
+                    if self.kind == TargetKind::Url && !raw {
+                        out.push_str(&urlencoding::encode(&value));
+                    } else {
+                        out.push_str(&value);
+                    }
+                },
+            }
+        }
+
+        Ok(match self.kind {
+            TargetKind::Url => ActionType::OpenUrl(out),
+            TargetKind::File => {
+                let path = out.strip_prefix("file://").unwrap_or(&out);
+                // `file:///~/x` names a path relative to the home directory
+                let path = path
+                    .strip_prefix("/~")
+                    .map_or(path.to_string(), |p| format!("~{p}"));
+                ActionType::OpenFile(expand_home(&path))
+            },
+        })
+    }
+}
+
+/// Check a value against its argument definition and normalise it
+///
+/// Numbers are trimmed, booleans become `true` or `false`, and file and directory arguments
+/// are expanded to existing absolute paths.
+pub fn validate_argument(
+    definition: &ArgumentDefinition,
+    value: Option<&String>,
+) -> Result<String, QuicklinkError> {
+    let invalid = |reason: String| QuicklinkError::InvalidArgument {
+        name: definition.name.clone(),
+        reason,
+    };
+
+    let value = value.map(|v| v.trim()).filter(|v| !v.is_empty());
+    let Some(value) = value else {
+        return if definition.required {
+            Err(QuicklinkError::MissingArgument(definition.name.clone()))
+        } else {
+            Ok(String::new())
+        };
+    };
+
+    match definition.arg_type {
+        ArgumentType::Text => Ok(value.to_string()),
+        ArgumentType::Number => match value.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(value.to_string()),
+            _ => Err(invalid(format!("'{value}' is not a number"))),
+        },
+        ArgumentType::Boolean => match value.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Ok("true".to_string()),
+            "false" | "no" | "off" | "0" => Ok("false".to_string()),
+            _ => Err(invalid(format!("'{value}' is not true or false"))),
+        },
+        ArgumentType::File | ArgumentType::Directory => {
+            let path = expand_home(value);
+            let expected_dir = matches!(definition.arg_type, ArgumentType::Directory);
+            match std::fs::metadata(&path) {
+                Ok(metadata) if metadata.is_dir() == expected_dir => {
+                    Ok(path.to_string_lossy().into_owned())
+                },
+                Ok(_) if expected_dir => Err(invalid(format!("'{value}' is not a directory"))),
+                Ok(_) => Err(invalid(format!("'{value}' is not a file"))),
+                Err(e) => Err(invalid(format!("'{value}': {e}"))),
+            }
+        },
+    }
+}
+
+/// Replace a leading `~` with the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    let home = || dirs::home_dir().unwrap_or_default();
+    if path == "~" {
+        home()
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        home().join(rest)
+    } else {
+        Path::new(path).to_path_buf()
+    }
+}
+
+/// Classify template text by its prefix; `None` if it is neither a URL nor a path
+fn target_kind(text: &str) -> Option<TargetKind> {
+    if text.starts_with("file://") || text.starts_with('/') || text.starts_with('~') {
+        return Some(TargetKind::File);
+    }
+    // RFC 3986 scheme: a letter followed by letters, digits, `+`, `-` or `.`, then `:`
+    let (scheme, rest) = text.split_once(':')?;
+    // Windows drive paths such as `C:\Users` look like a one-letter scheme
+    if scheme.len() == 1 && rest.starts_with(['\\', '/']) {
+        return Some(TargetKind::File);
+    }
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    valid.then_some(TargetKind::Url)
+}
+
+/// Parse the text between braces; `None` means it is not a placeholder
+fn parse_placeholder(inner: &str) -> Option<Segment> {
+    let (name, modifier) = match inner.split_once('|') {
+        Some((name, modifier)) => (name.trim(), Some(modifier.trim())),
+        None => (inner.trim(), None),
+    };
+    let raw = match modifier {
+        None => false,
+        Some("raw") => true,
+        Some(_) => return None,
+    };
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    valid.then(|| Segment::Placeholder {
+        name: name.to_string(),
+        raw,
+    })
+}
@@ -0,0 +1,97 @@
+//! Quicklink types and errors
+
+use action_items_common::plugin_interface::commands::{ArgumentDefinition, ArgumentType};
+use serde::{Deserialize, Serialize};
+
+/// A saved quicklink
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quicklink {
+    /// Unique id, also used as the database record key
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Short text that selects the quicklink directly from the launcher
+    pub keyword: Option<String>,
+    /// URL or file template with `{argument}` placeholders, see [`crate::template`]
+    pub template: String,
+    /// Arguments referenced by the template, in the order they are asked for
+    pub arguments: Vec<ArgumentDefinition>,
+    /// Unix timestamp (milliseconds) of creation
+    pub created_at: i64,
+    /// Unix timestamp (milliseconds) of the last change
+    pub updated_at: i64,
+}
+
+/// Fields of a quicklink supplied when creating or updating it
+///
+/// Placeholders without a matching entry in `arguments` become required text arguments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuicklinkDraft {
+    pub name: String,
+    pub keyword: Option<String>,
+    pub template: String,
+    pub arguments: Vec<ArgumentDefinition>,
+}
+
+impl QuicklinkDraft {
+    pub fn new(name: impl Into<String>, template: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            template: template.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keyword = Some(keyword.into());
+        self
+    }
+
+    pub fn with_argument(mut self, argument: ArgumentDefinition) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+}
+
+/// Required text argument used for placeholders without an explicit definition
+pub fn text_argument(name: &str) -> ArgumentDefinition {
+    ArgumentDefinition {
+        name: name.to_string(),
+        placeholder: name.to_string(),
+        arg_type: ArgumentType::Text,
+        required: true,
+    }
+}
+
+/// Errors from quicklink operations
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+pub enum QuicklinkError {
+    #[error("Quicklink not found: {0}")]
+    NotFound(String),
+    #[error("Quicklink name must not be empty")]
+    EmptyName,
+    #[error("A quicklink named '{0}' already exists")]
+    DuplicateName(String),
+    #[error("Keyword '{0}' is already used by another quicklink")]
+    DuplicateKeyword(String),
+    #[error("Keywords must be a single word: '{0}'")]
+    InvalidKeyword(String),
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+    #[error("Argument '{0}' is defined but not used by the template")]
+    UnusedArgument(String),
+    #[error("No value for required argument '{0}'")]
+    MissingArgument(String),
+    #[error("Invalid value for argument '{name}': {reason}")]
+    InvalidArgument { name: String, reason: String },
+    #[error("Failed to open {target}: {reason}")]
+    Open { target: String, reason: String },
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl From<action_items_ecs_surrealdb::DatabaseError> for QuicklinkError {
+    fn from(error: action_items_ecs_surrealdb::DatabaseError) -> Self {
+        QuicklinkError::Database(error.to_string())
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use action_items_common::plugin_interface::action_item::ActionType;
+use action_items_common::plugin_interface::commands::{ArgumentDefinition, ArgumentType};
+use action_items_ecs_quicklinks::library::bind_positional;
+use action_items_ecs_quicklinks::template::{Segment, expand_home};
+use action_items_ecs_quicklinks::{
+    QuicklinkDraft, QuicklinkError, QuicklinkLibrary, TargetKind, Template,
+};
+
+fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn argument(name: &str, arg_type: ArgumentType, required: bool) -> ArgumentDefinition {
+    ArgumentDefinition {
+        name: name.to_string(),
+        placeholder: name.to_string(),
+        arg_type,
+        required,
+    }
+}
+
+fn resolve(template: &str, pairs: &[(&str, &str)]) -> Result<ActionType, QuicklinkError> {
+    Template::parse(template)?.resolve(&[], &values(pairs))
+}
+
+fn url(action: ActionType) -> String {
+    match action {
+        ActionType::OpenUrl(url) => url,
+        other => panic!("expected OpenUrl, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_url_values_are_percent_encoded() {
+    let action = resolve(
+        "https://github.com/search?q={query}",
+        &[("query", "rust & bevy/ecs?")],
+    )
+    .unwrap();
+    assert_eq!(
+        url(action),
+        "https://github.com/search?q=rust%20%26%20bevy%2Fecs%3F"
+    );
+
+    let action = resolve("https://example.com/{path|raw}", &[("path", "a/b c")]).unwrap();
+    assert_eq!(url(action), "https://example.com/a/b c");
+}
+
+#[test]
+fn test_unknown_braces_are_literal() {
+    let template = Template::parse("https://example.com/{a b}/{q}?x={}").unwrap();
+    assert_eq!(template.placeholders(), vec!["q"]);
+    assert_eq!(
+        template.segments()[0],
+        Segment::Literal("https://example.com/{a b}/".to_string())
+    );
+}
+
+#[test]
+fn test_template_kinds() {
+    assert_eq!(
+        Template::parse("https://x.dev/{q}").unwrap().kind(),
+        TargetKind::Url
+    );
+    assert_eq!(
+        Template::parse("raycast://extensions/{q}").unwrap().kind(),
+        TargetKind::Url
+    );
+    assert_eq!(
+        Template::parse("file:///~/proj/{name}").unwrap().kind(),
+        TargetKind::File
+    );
+    assert_eq!(
+        Template::parse("~/proj/{name}").unwrap().kind(),
+        TargetKind::File
+    );
+    assert!(matches!(
+        Template::parse("github search {q}"),
+        Err(QuicklinkError::InvalidTemplate(_))
+    ));
+}
+
+#[test]
+fn test_file_templates_expand_home() {
+    let expected = expand_home("~/proj/my app");
+    assert_eq!(
+        resolve("file:///~/proj/{name}", &[("name", "my app")]).unwrap(),
+        ActionType::OpenFile(expected.clone())
+    );
+    assert_eq!(
+        resolve("file:///~/proj/my%20{name}", &[("name", "app")]).unwrap(),
+        ActionType::OpenFile(expected)
+    );
+    assert_eq!(
+        resolve("/var/log/{name}", &[("name", "system.log")]).unwrap(),
+        ActionType::OpenFile(PathBuf::from("/var/log/system.log"))
+    );
+    // Plain paths are not percent-encoded
+    assert_eq!(
+        resolve("~/100%/{name}", &[("name", "notes")]).unwrap(),
+        ActionType::OpenFile(expand_home("~/100%/notes"))
+    );
+    assert_eq!(
+        resolve("/srv/a%20b/{name}", &[("name", "c")]).unwrap(),
+        ActionType::OpenFile(PathBuf::from("/srv/a%20b/c"))
+    );
+}
+
+#[test]
+fn test_typed_arguments() {
+    let template = Template::parse("https://x.dev/?n={n}&flag={flag}&q={q}").unwrap();
+    let arguments = [
+        argument("n", ArgumentType::Number, true),
+        argument("flag", ArgumentType::Boolean, false),
+        argument("q", ArgumentType::Text, false),
+    ];
+
+    let action = template
+        .resolve(&arguments, &values(&[("n", " 42 "), ("flag", "Yes")]))
+        .unwrap();
+    assert_eq!(url(action), "https://x.dev/?n=42&flag=true&q=");
+
+    assert_eq!(
+        template.resolve(&arguments, &values(&[])),
+        Err(QuicklinkError::MissingArgument("n".to_string()))
+    );
+    assert!(matches!(
+        template.resolve(&arguments, &values(&[("n", "many")])),
+        Err(QuicklinkError::InvalidArgument { name, .. }) if name == "n"
+    ));
+    assert!(matches!(
+        template.resolve(&arguments, &values(&[("n", "1"), ("flag", "maybe")])),
+        Err(QuicklinkError::InvalidArgument { name, .. }) if name == "flag"
+    ));
+}
+
+#[test]
+fn test_path_arguments_must_exist() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("notes.md");
+    std::fs::write(&file, "notes").unwrap();
+    let dir_path = dir.path().to_string_lossy().into_owned();
+    let file_path = file.to_string_lossy().into_owned();
+
+    let template = Template::parse("file://{dir}").unwrap();
+    let arguments = [argument("dir", ArgumentType::Directory, true)];
+    assert_eq!(
+        template
+            .resolve(&arguments, &values(&[("dir", &dir_path)]))
+            .unwrap(),
+        ActionType::OpenFile(dir.path().to_path_buf())
+    );
+    assert!(matches!(
+        template.resolve(&arguments, &values(&[("dir", &file_path)])),
+        Err(QuicklinkError::InvalidArgument { .. })
+    ));
+
+    let arguments = [argument("dir", ArgumentType::File, true)];
+    assert!(matches!(
+        template.resolve(&arguments, &values(&[("dir", "/definitely/not/here")])),
+        Err(QuicklinkError::InvalidArgument { .. })
+    ));
+}
+
+#[test]
+fn test_library_fills_in_placeholder_arguments() {
+    let mut library = QuicklinkLibrary::default();
+    let draft = QuicklinkDraft::new("Crate Docs", "https://docs.rs/{crate}/{version}")
+        .with_keyword("docs")
+        .with_argument(argument("version", ArgumentType::Text, false));
+    let quicklink = library.create(draft, 0).unwrap();
+
+    let names: Vec<&str> = quicklink
+        .arguments
+        .iter()
+        .map(|a| a.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["version", "crate"]);
+    assert!(quicklink.arguments[1].required);
+
+    let unused = QuicklinkDraft::new("Unused", "https://x.dev").with_argument(argument(
+        "q",
+        ArgumentType::Text,
+        true,
+    ));
+    assert_eq!(
+        library.create(unused, 0).map(|q| q.id),
+        Err(QuicklinkError::UnusedArgument("q".to_string()))
+    );
+    assert_eq!(
+        library
+            .create(QuicklinkDraft::new("crate docs", "https://x.dev"), 0)
+            .map(|q| q.id),
+        Err(QuicklinkError::DuplicateName("crate docs".to_string()))
+    );
+}
+
+#[test]
+fn test_search_by_keyword_with_arguments() {
+    let mut library = QuicklinkLibrary::default();
+    let github = library
+        .create(
+            QuicklinkDraft::new("GitHub Search", "https://github.com/search?q={query}")
+                .with_keyword("gh"),
+            0,
+        )
+        .unwrap();
+    library
+        .create(
+            QuicklinkDraft::new("Project Folder", "file:///~/proj/{name}"),
+            0,
+        )
+        .unwrap();
+
+    let found = library.search("gh bevy ecs", 10);
+    assert_eq!(found[0].quicklink.id, github.id);
+    assert_eq!(found[0].argument_input.as_deref(), Some("bevy ecs"));
+
+    let bound = bind_positional(&github.arguments, "bevy ecs");
+    assert_eq!(
+        url(library.resolve(&github.id, &bound).unwrap()),
+        "https://github.com/search?q=bevy%20ecs"
+    );
+
+    let found = library.search("proj", 10);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].quicklink.name, "Project Folder");
+}
+
+#[test]
+fn test_load_keeps_unsaved_quicklinks() {
+    let mut stored = QuicklinkLibrary::default();
+    let saved = stored
+        .create(QuicklinkDraft::new("Stored", "https://a.dev"), 0)
+        .unwrap();
+
+    let mut library = QuicklinkLibrary::default();
+    let early = library
+        .create(QuicklinkDraft::new("Early", "https://b.dev"), 0)
+        .unwrap();
+    let unsaved = library.load(vec![saved]);
+
+    assert_eq!(unsaved.len(), 1);
+    assert_eq!(unsaved[0].id, early.id);
+    assert_eq!(library.len(), 2);
+}
@@ -0,0 +1,41 @@
+//! Script command request and notification events
+
+use bevy::prelude::*;
+
+use crate::output::ScriptPresentation;
+use crate::types::ScriptCommandError;
+
+/// Run a script command
+#[derive(Event, Debug, Clone)]
+pub struct ScriptCommandRunRequested {
+    pub command_id: String,
+    /// Positional argument values; missing optional arguments may be omitted
+    pub arguments: Vec<String>,
+    /// The user confirmed a command marked `@raycast.needsConfirmation`
+    pub confirmed: bool,
+}
+
+/// A command marked `@raycast.needsConfirmation` was requested without confirmation
+///
+/// Re-send the request with `confirmed` set once the user agrees.
+#[derive(Event, Debug, Clone)]
+pub struct ScriptCommandConfirmationRequired {
+    pub request: ScriptCommandRunRequested,
+    pub title: String,
+}
+
+/// A script command finished running
+#[derive(Event, Debug, Clone)]
+pub struct ScriptCommandFinished {
+    pub command_id: String,
+    pub result: Result<ScriptPresentation, ScriptCommandError>,
+}
+
+/// The set of script commands changed after a scan or a file change
+#[derive(Event, Debug, Clone)]
+pub struct ScriptCommandsReloaded {
+    /// Number of loaded commands
+    pub command_count: usize,
+    /// Number of scripts whose metadata is invalid
+    pub error_count: usize,
+}
@@ -0,0 +1,34 @@
+//! Action Items ECS Script Commands
+//!
+//! Bevy ECS plugin that turns local scripts into launcher commands.
+//!
+//! - Scripts in the configured directories are described by Raycast-compatible
+//!   `@raycast.*` comment headers and become `CommandDefinition`s
+//! - Scripts run with their arguments in a sandboxed process with a cleared environment,
+//!   timeout and output limits, writing only to their working directory and granted paths
+//! - Output is presented according to the script's mode: silent, compact, fullOutput or
+//!   inline
+//! - Changes in the script directories are picked up through `ecs_filesystem`'s watcher
+
+pub mod events;
+pub mod metadata;
+pub mod output;
+pub mod plugin;
+pub mod registry;
+pub mod runner;
+pub mod sandbox;
+pub mod systems;
+pub mod types;
+
+pub use events::{
+    ScriptCommandConfirmationRequired, ScriptCommandFinished, ScriptCommandRunRequested,
+    ScriptCommandsReloaded,
+};
+pub use metadata::{ScriptArgument, ScriptArgumentKind, ScriptMetadata};
+pub use output::ScriptPresentation;
+pub use plugin::ScriptCommandsPlugin;
+pub use registry::{ScriptCommand, ScriptCommandRegistry};
+pub use runner::{ScriptLimits, ScriptOutput};
+pub use sandbox::ScriptSandbox;
+pub use systems::{SCRIPT_COMMAND_ACTION_PREFIX, SCRIPT_COMMANDS_PROVIDER_ID};
+pub use types::{ScriptCommandError, ScriptCommandsConfig, ScriptMode};
@@ -0,0 +1,295 @@
+//! Raycast-compatible script metadata
+//!
+//! Script commands describe themselves with comment lines at the top of the file:
+//!
+//! ```text
+//! #!/bin/bash
+//! # @raycast.schemaVersion 1
+//! # @raycast.title Search Issues
+//! # @raycast.mode silent
+//! # @raycast.argument1 { "type": "text", "placeholder": "Query", "percentEncoded": true }
+//! ```
+//!
+//! `#`, `//` and `--` comments are recognised, so the same headers work in shell, Python,
+//! Ruby, JavaScript, Swift and AppleScript files. Unknown keys are ignored.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use action_items_common::plugin_interface::commands::{
+    ArgumentDefinition, ArgumentType, CommandDefinition,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ScriptCommandError, ScriptMode};
+
+/// Highest `@raycast.argumentN` position
+pub const MAX_ARGUMENTS: usize = 3;
+/// Shortest allowed `@raycast.refreshTime`
+pub const MIN_REFRESH_TIME: Duration = Duration::from_secs(10);
+/// Number of leading lines searched for metadata
+const HEADER_LINES: usize = 100;
+
+/// Kind of value an argument takes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptArgumentKind {
+    Text,
+    /// Text entered without echo
+    Password,
+    /// One of a fixed set of values
+    Dropdown(Vec<DropdownOption>),
+}
+
+/// An entry of a dropdown argument
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DropdownOption {
+    pub title: String,
+    pub value: String,
+}
+
+/// An argument declared with `@raycast.argumentN`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptArgument {
+    /// 1-based position on the command line
+    pub position: usize,
+    pub kind: ScriptArgumentKind,
+    pub placeholder: String,
+    pub optional: bool,
+    /// Percent-encode the value before passing it to the script
+    pub percent_encoded: bool,
+}
+
+/// Metadata parsed from a script's header
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptMetadata {
+    pub title: String,
+    pub mode: ScriptMode,
+    pub package_name: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub author: Option<String>,
+    /// Working directory; relative paths are resolved against the script's directory
+    pub current_directory_path: Option<PathBuf>,
+    /// Ask before running
+    pub needs_confirmation: bool,
+    /// Re-run interval of inline commands
+    pub refresh_time: Option<Duration>,
+    /// Arguments ordered by position
+    pub arguments: Vec<ScriptArgument>,
+}
+
+/// JSON form of `@raycast.argumentN`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawArgument {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    placeholder: String,
+    #[serde(default)]
+    optional: bool,
+    #[serde(default)]
+    percent_encoded: bool,
+    #[serde(default)]
+    secure: bool,
+    #[serde(default)]
+    data: Vec<DropdownOption>,
+}
+
+impl ScriptMetadata {
+    /// Parse the metadata header of a script
+    pub fn parse(source: &str) -> Result<Self, ScriptCommandError> {
+        let mut fields = Vec::new();
+        for line in source.lines().take(HEADER_LINES) {
+            let line = line.trim_start();
+            let Some(comment) = ["#", "//", "--"]
+                .iter()
+                .find_map(|prefix| line.strip_prefix(prefix))
+            else {
+                continue;
+            };
+            if let Some(field) = comment.trim().strip_prefix("@raycast.") {
+                let (key, value) = field.split_once(char::is_whitespace).unwrap_or((field, ""));
+                fields.push((key, value.trim()));
+            }
+        }
+        if fields.is_empty() {
+            return Err(ScriptCommandError::NoMetadata);
+        }
+
+        let field = |key: &str| {
+            fields
+                .iter()
+                .rev()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| *v)
+                .filter(|v| !v.is_empty())
+        };
+        let required =
+            |key: &str| field(key).ok_or_else(|| ScriptCommandError::MissingField(key.to_string()));
+
+        let version = required("schemaVersion")?;
+        if version != "1" {
+            return Err(ScriptCommandError::UnsupportedSchemaVersion(
+                version.to_string(),
+            ));
+        }
+        let mode = required("mode")?;
+        let mode = ScriptMode::parse(mode)
+            .ok_or_else(|| ScriptCommandError::InvalidMode(mode.to_string()))?;
+
+        let mut arguments = Vec::new();
+        for position in 1..=MAX_ARGUMENTS {
+            if let Some(json) = field(&format!("argument{position}")) {
+                arguments.push(parse_argument(position, json)?);
+            }
+        }
+        if let Some(gap) = arguments
+            .iter()
+            .enumerate()
+            .find(|(i, a)| a.position != i + 1)
+        {
+            return Err(ScriptCommandError::InvalidArgument {
+                position: gap.1.position,
+                reason: format!("argument{} is missing", gap.0 + 1),
+            });
+        }
+
+        Ok(Self {
+            title: required("title")?.to_string(),
+            mode,
+            package_name: field("packageName").map(String::from),
+            description: field("description").map(String::from),
+            icon: field("icon").map(String::from),
+            author: field("author").map(String::from),
+            current_directory_path: field("currentDirectoryPath").map(PathBuf::from),
+            needs_confirmation: field("needsConfirmation") == Some("true"),
+            refresh_time: field("refreshTime").map(parse_refresh_time).transpose()?,
+            arguments,
+        })
+    }
+
+    /// Launcher command for a script, identified by `id`
+    ///
+    /// Arguments are named `argument1` to `argument3`. Only inline commands have a refresh
+    /// interval.
+    pub fn to_command_definition(&self, id: &str) -> CommandDefinition {
+        CommandDefinition {
+            id: id.to_string(),
+            title: self.title.clone(),
+            subtitle: self.package_name.clone(),
+            description: self.description.clone().unwrap_or_default(),
+            icon: self.icon.clone(),
+            mode: self.mode.command_mode(),
+            keywords: self
+                .package_name
+                .iter()
+                .flat_map(|name| name.split_whitespace())
+                .map(str::to_lowercase)
+                .collect(),
+            arguments: self
+                .arguments
+                .iter()
+                .map(|argument| ArgumentDefinition {
+                    name: format!("argument{}", argument.position),
+                    placeholder: argument.placeholder.clone(),
+                    arg_type: ArgumentType::Text,
+                    required: !argument.optional,
+                })
+                .collect(),
+            hotkey: None,
+            interval: match self.mode {
+                ScriptMode::Inline => self.refresh_time.map(|time| time.as_secs()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Check argument values and turn them into the script's command line arguments
+    ///
+    /// Missing optional arguments are passed as empty strings so later arguments keep their
+    /// positions. Dropdown values must be one of the options, and `percentEncoded` arguments
+    /// are encoded.
+    pub fn command_arguments(&self, values: &[String]) -> Result<Vec<String>, ScriptCommandError> {
+        self.arguments
+            .iter()
+            .map(|argument| {
+                let value = values
+                    .get(argument.position - 1)
+                    .map(|value| value.trim())
+                    .unwrap_or_default();
+                if value.is_empty() {
+                    if argument.optional {
+                        return Ok(String::new());
+                    }
+                    return Err(ScriptCommandError::MissingArgument(argument.position));
+                }
+                if let ScriptArgumentKind::Dropdown(options) = &argument.kind
+                    && !options.iter().any(|option| option.value == value)
+                {
+                    return Err(ScriptCommandError::InvalidArgument {
+                        position: argument.position,
+                        reason: format!("'{value}' is not one of the options"),
+                    });
+                }
+                Ok(if argument.percent_encoded {
+                    urlencoding::encode(value).into_owned()
+                } else {
+                    value.to_string()
+                })
+            })
+            .collect()
+    }
+
+    /// Directory the script runs in
+    pub fn working_directory(&self, script: &Path) -> PathBuf {
+        let script_dir = script.parent().unwrap_or(Path::new("/")).to_path_buf();
+        match &self.current_directory_path {
+            Some(path) => match path.to_str().and_then(|p| p.strip_prefix("~/")) {
+                Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                None => script_dir.join(path),
+            },
+            None => script_dir,
+        }
+    }
+}
+
+fn parse_argument(position: usize, json: &str) -> Result<ScriptArgument, ScriptCommandError> {
+    let invalid = |reason: String| ScriptCommandError::InvalidArgument { position, reason };
+    let raw: RawArgument = serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+
+    let kind = match raw.kind.as_str() {
+        "text" if raw.secure => ScriptArgumentKind::Password,
+        "text" => ScriptArgumentKind::Text,
+        "password" => ScriptArgumentKind::Password,
+        "dropdown" if raw.data.is_empty() => {
+            return Err(invalid("dropdown has no data".to_string()));
+        },
+        "dropdown" => ScriptArgumentKind::Dropdown(raw.data),
+        other => return Err(invalid(format!("unknown type '{other}'"))),
+    };
+    Ok(ScriptArgument {
+        position,
+        kind,
+        placeholder: raw.placeholder,
+        optional: raw.optional,
+        percent_encoded: raw.percent_encoded,
+    })
+}
+
+/// Parse durations like `30s`, `10m`, `1h` or `1d`, raising them to [`MIN_REFRESH_TIME`]
+fn parse_refresh_time(value: &str) -> Result<Duration, ScriptCommandError> {
+    let invalid = || ScriptCommandError::InvalidRefreshTime(value.to_string());
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let amount: u64 = value[..split].parse().map_err(|_| invalid())?;
+    let unit = match &value[split..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    Ok(Duration::from_secs(amount * unit).max(MIN_REFRESH_TIME))
+}
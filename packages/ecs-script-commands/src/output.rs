@@ -0,0 +1,111 @@
+//! Presenting script output according to the script's mode
+//!
+//! Follows Raycast's conventions: silent scripts show their last line as a HUD, compact
+//! scripts as a toast, full-output scripts show everything and inline scripts show their
+//! first line in the command's search result. A non-zero exit shows the last line as a
+//! failure toast in every mode.
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::ScriptOutput;
+use crate::types::ScriptMode;
+
+/// How a finished script's output should be shown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptPresentation {
+    /// Nothing to show
+    None,
+    /// Short message shown after the launcher closes
+    Hud(String),
+    /// Message shown in the launcher
+    Toast { message: String, success: bool },
+    /// Full output in a detail view
+    Detail(String),
+    /// Text shown in the command's search result
+    Inline(String),
+}
+
+/// Present a script's output for its mode
+pub fn present(mode: ScriptMode, output: &ScriptOutput) -> ScriptPresentation {
+    let stdout = strip_ansi(&output.stdout);
+
+    if !output.success() {
+        let stderr = strip_ansi(&output.stderr);
+        let message = last_line(&stdout)
+            .or_else(|| last_line(&stderr))
+            .map(String::from)
+            .unwrap_or_else(|| match output.exit_code {
+                Some(code) => format!("Script failed with exit code {code}"),
+                None => "Script was terminated".to_string(),
+            });
+        return ScriptPresentation::Toast {
+            message,
+            success: false,
+        };
+    }
+
+    match mode {
+        ScriptMode::Silent => last_line(&stdout).map_or(ScriptPresentation::None, |line| {
+            ScriptPresentation::Hud(line.to_string())
+        }),
+        ScriptMode::Compact => {
+            last_line(&stdout).map_or(ScriptPresentation::None, |line| ScriptPresentation::Toast {
+                message: line.to_string(),
+                success: true,
+            })
+        },
+        ScriptMode::FullOutput => {
+            let mut text = stdout.trim_end().to_string();
+            if output.truncated {
+                text.push_str("\n…");
+            }
+            ScriptPresentation::Detail(text)
+        },
+        ScriptMode::Inline => stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map_or(ScriptPresentation::None, |line| {
+                ScriptPresentation::Inline(line.to_string())
+            }),
+    }
+}
+
+/// Remove ANSI escape sequences such as colour codes
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters and intermediates up to a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            },
+            // OSC: up to BEL or ST
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' || (c == '\u{1b}' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+    out
+}
+
+fn last_line(text: &str) -> Option<&str> {
+    text.lines()
+        .map(str::trim)
+        .rev()
+        .find(|line| !line.is_empty())
+}
@@ -0,0 +1,61 @@
+//! Script commands plugin
+
+use action_items_core::LauncherEvent;
+use action_items_ecs_search_aggregator::{
+    CurrentQuery, LocalSearchProviders, SearchRequested, SearchResultReceived,
+};
+use bevy::prelude::*;
+use ecs_filesystem::{FileSystemChanged, FileSystemRequest};
+
+use crate::events::*;
+use crate::registry::ScriptCommandRegistry;
+use crate::systems::*;
+use crate::types::ScriptCommandsConfig;
+
+/// Plugin turning script directories into launcher commands
+///
+/// Hot reloading relies on `FileSystemPlugin` watching the script directories; without it,
+/// scripts are only loaded at startup.
+#[derive(Default)]
+pub struct ScriptCommandsPlugin {
+    pub config: ScriptCommandsConfig,
+}
+
+impl ScriptCommandsPlugin {
+    pub fn new(config: ScriptCommandsConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for ScriptCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone())
+            .init_resource::<ScriptCommandRegistry>()
+            .init_resource::<ScriptDirectories>()
+            .init_resource::<LocalSearchProviders>()
+            .init_resource::<CurrentQuery>()
+            .add_event::<SearchRequested>()
+            .add_event::<SearchResultReceived>()
+            .add_event::<LauncherEvent>()
+            .add_event::<FileSystemRequest>()
+            .add_event::<FileSystemChanged>()
+            .add_event::<ScriptCommandRunRequested>()
+            .add_event::<ScriptCommandConfirmationRequired>()
+            .add_event::<ScriptCommandFinished>()
+            .add_event::<ScriptCommandsReloaded>()
+            .add_systems(Startup, (register_search_provider, start_script_commands))
+            .add_systems(
+                Update,
+                (
+                    handle_script_directory_changes,
+                    handle_script_run_requests,
+                    refresh_inline_commands,
+                    search_script_commands,
+                    handle_script_command_tasks,
+                )
+                    .chain(),
+            );
+
+        tracing::info!("ScriptCommandsPlugin initialized");
+    }
+}
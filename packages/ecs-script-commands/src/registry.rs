@@ -0,0 +1,280 @@
+//! Registry of script commands found in the configured directories
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use action_items_common::plugin_interface::commands::CommandDefinition;
+use bevy::prelude::*;
+
+use crate::metadata::ScriptMetadata;
+use crate::types::{ScriptCommandError, ScriptMode};
+
+/// Largest file read when looking for metadata
+const MAX_SCRIPT_BYTES: u64 = 1024 * 1024;
+/// Deepest subdirectory scanned below a script directory
+const MAX_SCAN_DEPTH: usize = 4;
+
+/// A script turned into a launcher command
+#[derive(Debug, Clone)]
+pub struct ScriptCommand {
+    /// Stable id derived from the script path
+    pub id: String,
+    pub path: PathBuf,
+    pub metadata: ScriptMetadata,
+    pub definition: CommandDefinition,
+    /// First output line of an inline command
+    pub inline_output: Option<String>,
+    /// When an inline command last finished running
+    pub last_refreshed: Option<Instant>,
+}
+
+impl ScriptCommand {
+    pub fn new(path: PathBuf, metadata: ScriptMetadata) -> Self {
+        let id = command_id(&path);
+        let definition = metadata.to_command_definition(&id);
+        Self {
+            id,
+            path,
+            metadata,
+            definition,
+            inline_output: None,
+            last_refreshed: None,
+        }
+    }
+}
+
+/// A script command matching a launcher query
+#[derive(Debug, Clone)]
+pub struct ScriptCommandMatch<'a> {
+    pub command: &'a ScriptCommand,
+    pub score: f32,
+    /// Text typed after the command's title, used as positional arguments
+    pub argument_input: Option<String>,
+}
+
+/// All loaded script commands, ordered by title
+#[derive(Resource, Debug, Default)]
+pub struct ScriptCommandRegistry {
+    commands: Vec<ScriptCommand>,
+    /// Scripts whose metadata could not be parsed
+    errors: HashMap<PathBuf, ScriptCommandError>,
+    /// Commands with a run in progress
+    running: HashSet<String>,
+}
+
+impl ScriptCommandRegistry {
+    /// Add, replace or drop the command for a script after (re)loading it
+    ///
+    /// Files without metadata are not script commands and are dropped silently.
+    pub fn apply(&mut self, path: PathBuf, loaded: Result<ScriptMetadata, ScriptCommandError>) {
+        self.errors.remove(&path);
+        match loaded {
+            Ok(metadata) => {
+                let mut command = ScriptCommand::new(path, metadata);
+                if let Some(existing) = self.commands.iter_mut().find(|c| c.path == command.path) {
+                    // Keep the last inline output until the command refreshes
+                    if command.metadata.mode == ScriptMode::Inline {
+                        command.inline_output = existing.inline_output.take();
+                        command.last_refreshed = existing.last_refreshed;
+                    }
+                    *existing = command;
+                } else {
+                    self.commands.push(command);
+                }
+                self.sort();
+            },
+            Err(error) => {
+                self.commands.retain(|c| c.path != path);
+                if error != ScriptCommandError::NoMetadata {
+                    self.errors.insert(path, error);
+                }
+            },
+        }
+    }
+
+    /// Remove the commands for a path, or for everything below it if it is a directory
+    pub fn remove(&mut self, path: &Path) {
+        self.commands.retain(|c| !c.path.starts_with(path));
+        self.errors.retain(|p, _| !p.starts_with(path));
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ScriptCommand> {
+        self.commands.iter().find(|c| c.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut ScriptCommand> {
+        self.commands.iter_mut().find(|c| c.id == id)
+    }
+
+    pub fn commands(&self) -> &[ScriptCommand] {
+        &self.commands
+    }
+
+    pub fn errors(&self) -> &HashMap<PathBuf, ScriptCommandError> {
+        &self.errors
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Mark a command as running; returns `false` if it already is
+    pub fn start_run(&mut self, id: &str) -> bool {
+        self.running.insert(id.to_string())
+    }
+
+    pub fn finish_run(&mut self, id: &str) {
+        self.running.remove(id);
+    }
+
+    pub fn is_running(&self, id: &str) -> bool {
+        self.running.contains(id)
+    }
+
+    /// Search commands by title, package and description, best matches first
+    ///
+    /// A query starting with a command's title followed by more text selects it with the rest
+    /// of the query as [`ScriptCommandMatch::argument_input`].
+    pub fn search(&self, query: &str, limit: usize) -> Vec<ScriptCommandMatch<'_>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let lowered = query.to_lowercase();
+        let terms: Vec<&str> = lowered.split_whitespace().collect();
+
+        let mut matches: Vec<ScriptCommandMatch<'_>> = self
+            .commands
+            .iter()
+            .filter_map(|command| {
+                let title = command.metadata.title.to_lowercase();
+                if title == lowered {
+                    return Some((command, 1.0, None));
+                }
+                if !command.metadata.arguments.is_empty()
+                    && let Some(rest) = lowered.strip_prefix(title.as_str())
+                    && rest.starts_with(char::is_whitespace)
+                {
+                    // Slice the original query to keep the arguments' case
+                    let input = query
+                        .get(query.len().saturating_sub(rest.len())..)
+                        .unwrap_or(rest)
+                        .trim()
+                        .to_string();
+                    return Some((command, 0.95, Some(input)));
+                }
+                if title.starts_with(&lowered) {
+                    return Some((command, 0.85, None));
+                }
+                if terms.iter().all(|term| title.contains(term)) {
+                    return Some((command, 0.75, None));
+                }
+                let context = [
+                    &command.metadata.package_name,
+                    &command.metadata.description,
+                ]
+                .into_iter()
+                .flatten()
+                .map(|text| text.to_lowercase())
+                .collect::<Vec<_>>()
+                .join(" ");
+                if terms
+                    .iter()
+                    .all(|term| title.contains(term) || context.contains(term))
+                {
+                    return Some((command, 0.6, None));
+                }
+                None
+            })
+            .map(|(command, score, argument_input)| ScriptCommandMatch {
+                command,
+                score,
+                argument_input,
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    fn sort(&mut self) {
+        self.commands.sort_by(|a, b| {
+            a.metadata
+                .title
+                .to_lowercase()
+                .cmp(&b.metadata.title.to_lowercase())
+        });
+    }
+}
+
+/// Stable command id for a script path
+pub fn command_id(path: &Path) -> String {
+    uuid::Uuid::new_v5(
+        &uuid::Uuid::NAMESPACE_URL,
+        path.to_string_lossy().as_bytes(),
+    )
+    .simple()
+    .to_string()
+}
+
+/// Whether a path may be a script command
+///
+/// Hidden files and Raycast's `*.template.*` files are skipped.
+pub fn is_script_candidate(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| !name.starts_with('.') && !name.contains(".template."))
+}
+
+/// Read and parse a script's metadata
+pub fn load_script(path: &Path) -> Result<ScriptMetadata, ScriptCommandError> {
+    let metadata = std::fs::metadata(path)?;
+    if !metadata.is_file() || metadata.len() > MAX_SCRIPT_BYTES {
+        return Err(ScriptCommandError::NoMetadata);
+    }
+    let bytes = std::fs::read(path)?;
+    // Binary files cannot carry metadata
+    let source = std::str::from_utf8(&bytes).map_err(|_| ScriptCommandError::NoMetadata)?;
+    ScriptMetadata::parse(source)
+}
+
+/// Load every script below a directory
+///
+/// Returns one entry per candidate file, including files without metadata, so callers can
+/// tell which paths were looked at.
+pub fn scan_directory(dir: &Path) -> Vec<(PathBuf, Result<ScriptMetadata, ScriptCommandError>)> {
+    let mut found = Vec::new();
+    let mut pending = vec![(dir.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !is_script_candidate(&path) {
+                continue;
+            }
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() && depth < MAX_SCAN_DEPTH => {
+                    pending.push((path, depth + 1));
+                },
+                Ok(kind) if kind.is_file() => {
+                    let loaded = load_script(&path);
+                    found.push((path, loaded));
+                },
+                _ => {},
+            }
+        }
+    }
+    found
+}
@@ -0,0 +1,265 @@
+//! Sandboxed process runner for script commands
+//!
+//! Scripts run with a cleared environment (only [`ScriptLimits::inherited_env`] is passed
+//! through), no stdin, capped output and a wall-clock timeout, confined by
+//! [`ScriptLimits::sandbox`] to writing their working directory and the directories it
+//! grants. On Unix each script gets its own process group, so a timeout also kills anything it
+//! started, and an optional CPU time limit is applied with `setrlimit`.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use wait_timeout::ChildExt;
+
+use crate::sandbox::{self, ScriptSandbox};
+use crate::types::ScriptCommandError;
+
+/// Limits applied to every script run
+#[derive(Debug, Clone)]
+pub struct ScriptLimits {
+    /// Wall-clock limit after which the script is killed
+    pub timeout: Duration,
+    /// Bytes kept from each of stdout and stderr; the rest is discarded
+    pub max_output_bytes: usize,
+    /// Environment variables passed through from the launcher
+    pub inherited_env: Vec<String>,
+    /// CPU time limit (Unix only)
+    pub cpu_time_limit: Option<Duration>,
+    /// Where scripts may write and whether they may use the network
+    pub sandbox: ScriptSandbox,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_output_bytes: 1024 * 1024,
+            inherited_env: [
+                "PATH", "HOME", "USER", "LOGNAME", "SHELL", "LANG", "LC_ALL", "TMPDIR",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            cpu_time_limit: Some(Duration::from_secs(60)),
+            sandbox: ScriptSandbox::default(),
+        }
+    }
+}
+
+/// How long reader threads may keep draining pipes after a script exits or is killed
+///
+/// Only a process the script left running in the background, or one that left its process
+/// group, can hold the pipes open that long; it keeps running, but its later output is lost.
+const OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// Captured result of a finished script
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptOutput {
+    pub stdout: String,
+    pub stderr: String,
+    /// Exit code, `None` if the script was killed by a signal or timed out
+    pub exit_code: Option<i32>,
+    /// Output exceeded [`ScriptLimits::max_output_bytes`]
+    pub truncated: bool,
+}
+
+impl ScriptOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Run a script with positional arguments
+///
+/// Blocks until the script exits or times out, so call it from a task. A timed-out script is
+/// reported as [`ScriptCommandError::TimedOut`] with the output it produced before it was
+/// killed.
+pub fn run_script(
+    script: &Path,
+    arguments: &[String],
+    working_dir: &Path,
+    config: &ScriptLimits,
+) -> Result<ScriptOutput, ScriptCommandError> {
+    let (mut command, _confinement) =
+        sandbox::confine(interpreter_command(script)?, working_dir, &config.sandbox)?;
+    command
+        .args(arguments)
+        .current_dir(working_dir)
+        .env_clear()
+        .envs(
+            config
+                .inherited_env
+                .iter()
+                .filter_map(|name| std::env::var_os(name).map(|value| (name, value))),
+        )
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    confine(&mut command, config);
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| ScriptCommandError::Spawn(format!("{}: {e}", script.display())))?;
+    let stdout = Capture::start(child.stdout.take(), config.max_output_bytes);
+    let stderr = Capture::start(child.stderr.take(), config.max_output_bytes);
+
+    let Some(status) = child.wait_timeout(config.timeout)? else {
+        kill(&mut child);
+        let output = script_output(
+            stdout.finish(OUTPUT_GRACE),
+            stderr.finish(OUTPUT_GRACE),
+            None,
+        );
+        return Err(ScriptCommandError::TimedOut {
+            after: config.timeout,
+            output,
+        });
+    };
+
+    Ok(script_output(
+        stdout.finish(OUTPUT_GRACE),
+        stderr.finish(OUTPUT_GRACE),
+        status.code(),
+    ))
+}
+
+fn script_output(stdout: Captured, stderr: Captured, exit_code: Option<i32>) -> ScriptOutput {
+    ScriptOutput {
+        stdout: String::from_utf8_lossy(&stdout.bytes).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr.bytes).into_owned(),
+        exit_code,
+        truncated: stdout.truncated || stderr.truncated,
+    }
+}
+
+/// Build the command that runs a script, using its `#!` line when present
+///
+/// `#!/usr/bin/env NAME` runs `NAME` directly, so scripts do not depend on `env` existing or
+/// on their executable bit.
+pub fn interpreter_command(script: &Path) -> Result<Command, ScriptCommandError> {
+    let source = std::fs::read(script)?;
+    let first_line = source.split(|&b| b == b'\n').next().unwrap_or_default();
+    let shebang = std::str::from_utf8(first_line)
+        .ok()
+        .and_then(|line| line.strip_prefix("#!"));
+
+    let Some(shebang) = shebang else {
+        if cfg!(unix) {
+            return Ok(Command::new(script));
+        }
+        return Err(ScriptCommandError::NoInterpreter(script.to_path_buf()));
+    };
+
+    let mut words = shebang.split_whitespace();
+    let interpreter = words
+        .next()
+        .ok_or_else(|| ScriptCommandError::NoInterpreter(script.to_path_buf()))?;
+    let mut words: Vec<&str> = words.collect();
+    let program = if Path::new(interpreter)
+        .file_name()
+        .is_some_and(|name| name == "env")
+    {
+        words.retain(|word| *word != "-S");
+        if words.is_empty() {
+            return Err(ScriptCommandError::NoInterpreter(script.to_path_buf()));
+        }
+        PathBuf::from(words.remove(0))
+    } else {
+        PathBuf::from(interpreter)
+    };
+
+    let mut command = Command::new(program);
+    command.args(words).arg(script);
+    Ok(command)
+}
+
+/// Bytes read from one pipe so far
+#[derive(Debug, Default)]
+struct Captured {
+    bytes: Vec<u8>,
+    truncated: bool,
+}
+
+/// A pipe drained on a reader thread, keeping at most a fixed number of bytes
+struct Capture {
+    captured: Arc<Mutex<Captured>>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl Capture {
+    fn start<R: Read + Send + 'static>(pipe: Option<R>, limit: usize) -> Self {
+        let captured = Arc::new(Mutex::new(Captured::default()));
+        let reader = pipe.map(|mut pipe| {
+            let captured = Arc::clone(&captured);
+            std::thread::spawn(move || {
+                let mut buffer = [0u8; 8192];
+                // Keep draining past the limit so the script never blocks on a full pipe
+                while let Ok(read) = pipe.read(&mut buffer) {
+                    if read == 0 {
+                        break;
+                    }
+                    let mut captured = captured.lock().unwrap_or_else(PoisonError::into_inner);
+                    let room = limit.saturating_sub(captured.bytes.len());
+                    captured.bytes.extend_from_slice(&buffer[..read.min(room)]);
+                    captured.truncated |= read > room;
+                }
+            })
+        });
+        Self { captured, reader }
+    }
+
+    /// Take what was read once the pipe closes, or after `grace`
+    ///
+    /// A reader still running then exits whenever the pipe finally closes.
+    fn finish(mut self, grace: Duration) -> Captured {
+        if let Some(reader) = self.reader.take() {
+            let deadline = Instant::now() + grace;
+            while Instant::now() < deadline && !reader.is_finished() {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+        std::mem::take(&mut *self.captured.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+#[cfg(unix)]
+fn confine(command: &mut Command, config: &ScriptLimits) {
+    use std::os::unix::process::CommandExt;
+
+    command.process_group(0);
+    if let Some(limit) = config.cpu_time_limit {
+        let seconds = limit.as_secs().max(1) as libc::rlim_t;
+        // SAFETY: setrlimit is async-signal-safe and only touches the forked child
+        unsafe {
+            command.pre_exec(move || {
+                let rlimit = libc::rlimit {
+                    rlim_cur: seconds,
+                    rlim_max: seconds,
+                };
+                if libc::setrlimit(libc::RLIMIT_CPU, &rlimit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: signals the process group created for this child by `confine`
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
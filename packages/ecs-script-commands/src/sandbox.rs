@@ -0,0 +1,329 @@
+//! File system and network confinement of script runs
+//!
+//! A confined script can read what the user can, but writes only inside its working directory
+//! and [`ScriptSandbox::writable`], and opens network connections only if
+//! [`ScriptSandbox::network`] allows it.
+//!
+//! - Linux: a Landlock ruleset applied to the script process (Linux 5.13; network rules need
+//!   6.7)
+//! - macOS: the script runs under `sandbox-exec` with a generated profile
+//!
+//! Elsewhere, or on kernels without Landlock, scripts run with [`ScriptLimits`] only and a
+//! warning is logged once.
+//!
+//! [`ScriptLimits`]: crate::runner::ScriptLimits
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+use std::sync::Once;
+
+use crate::types::ScriptCommandError;
+
+/// What a confined script may change
+#[derive(Debug, Clone)]
+pub struct ScriptSandbox {
+    /// Confine scripts; when false they run with the other limits only
+    pub enabled: bool,
+    /// Directories scripts may write to besides their working directory
+    pub writable: Vec<PathBuf>,
+    /// Whether scripts may open network connections
+    pub network: bool,
+}
+
+impl Default for ScriptSandbox {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            writable: vec![std::env::temp_dir()],
+            network: true,
+        }
+    }
+}
+
+/// Whether scripts can be confined on this system
+pub fn available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        landlock::abi() > 0
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Path::new(macos::SANDBOX_EXEC).exists()
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// State that must outlive spawning a confined command
+#[derive(Default)]
+pub(crate) struct Confinement {
+    #[cfg(target_os = "linux")]
+    _ruleset: Option<std::os::fd::OwnedFd>,
+}
+
+/// Confine `command`, which runs in `working_dir`, before its arguments are added
+///
+/// Keep the returned [`Confinement`] until the command has been spawned.
+pub(crate) fn confine(
+    command: Command,
+    working_dir: &Path,
+    sandbox: &ScriptSandbox,
+) -> Result<(Command, Confinement), ScriptCommandError> {
+    if !sandbox.enabled {
+        return Ok((command, Confinement::default()));
+    }
+    // Sandboxes compare real paths, so links such as macOS's /tmp would never match
+    let writable: Vec<PathBuf> = std::iter::once(working_dir)
+        .chain(sandbox.writable.iter().map(PathBuf::as_path))
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+
+    #[cfg(target_os = "linux")]
+    {
+        landlock::confine(command, &writable, sandbox.network)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Ok((
+            macos::confine(command, &writable, sandbox.network),
+            Confinement::default(),
+        ))
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = writable;
+        static WARNED: Once = Once::new();
+        WARNED.call_once(|| {
+            tracing::warn!("Script commands cannot be confined on this platform");
+        });
+        Ok((command, Confinement::default()))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod landlock {
+    //! Raw Landlock system calls; see `linux/landlock.h`
+
+    use std::fs::File;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::process::CommandExt;
+    use std::path::PathBuf;
+    use std::process::Command;
+    use std::sync::Once;
+
+    use super::Confinement;
+    use crate::types::ScriptCommandError;
+
+    const CREATE_RULESET_VERSION: libc::c_uint = 1 << 0;
+    const RULE_PATH_BENEATH: libc::c_int = 1;
+
+    const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+    const ACCESS_FS_REMOVE_DIR: u64 = 1 << 4;
+    const ACCESS_FS_REMOVE_FILE: u64 = 1 << 5;
+    const ACCESS_FS_MAKE_CHAR: u64 = 1 << 6;
+    const ACCESS_FS_MAKE_DIR: u64 = 1 << 7;
+    const ACCESS_FS_MAKE_REG: u64 = 1 << 8;
+    const ACCESS_FS_MAKE_SOCK: u64 = 1 << 9;
+    const ACCESS_FS_MAKE_FIFO: u64 = 1 << 10;
+    const ACCESS_FS_MAKE_BLOCK: u64 = 1 << 11;
+    const ACCESS_FS_MAKE_SYM: u64 = 1 << 12;
+    /// ABI 2
+    const ACCESS_FS_REFER: u64 = 1 << 13;
+    /// ABI 3
+    const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+    /// ABI 4
+    const ACCESS_NET_BIND_TCP: u64 = 1 << 0;
+    const ACCESS_NET_CONNECT_TCP: u64 = 1 << 1;
+
+    const ACCESS_FS_WRITE: u64 = ACCESS_FS_WRITE_FILE
+        | ACCESS_FS_REMOVE_DIR
+        | ACCESS_FS_REMOVE_FILE
+        | ACCESS_FS_MAKE_CHAR
+        | ACCESS_FS_MAKE_DIR
+        | ACCESS_FS_MAKE_REG
+        | ACCESS_FS_MAKE_SOCK
+        | ACCESS_FS_MAKE_FIFO
+        | ACCESS_FS_MAKE_BLOCK
+        | ACCESS_FS_MAKE_SYM;
+
+    /// Files scripts may always write, such as the target of `>/dev/null`
+    const WRITABLE_FILES: &[&str] = &["/dev/null", "/dev/tty"];
+
+    #[repr(C)]
+    struct RulesetAttr {
+        handled_access_fs: u64,
+        handled_access_net: u64,
+    }
+
+    #[repr(C, packed)]
+    struct PathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: libc::c_int,
+    }
+
+    /// Landlock ABI version of the running kernel, or zero or less without Landlock
+    pub(super) fn abi() -> libc::c_long {
+        // SAFETY: querying the version reads no attribute
+        unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                std::ptr::null::<RulesetAttr>(),
+                0usize,
+                CREATE_RULESET_VERSION,
+            )
+        }
+    }
+
+    pub(super) fn confine(
+        mut command: Command,
+        writable: &[PathBuf],
+        network: bool,
+    ) -> Result<(Command, Confinement), ScriptCommandError> {
+        let abi = abi();
+        if abi <= 0 {
+            static WARNED: Once = Once::new();
+            WARNED.call_once(|| {
+                tracing::warn!("This kernel lacks Landlock; script commands are not confined");
+            });
+            return Ok((command, Confinement::default()));
+        }
+        if !network && abi < 4 {
+            static WARNED: Once = Once::new();
+            WARNED.call_once(|| {
+                tracing::warn!("Landlock ABI {abi} cannot deny script commands the network");
+            });
+        }
+
+        let ruleset = ruleset(abi, writable, network).map_err(|e| {
+            ScriptCommandError::Spawn(format!("Failed to create the script sandbox: {e}"))
+        })?;
+        let fd = ruleset.as_raw_fd();
+        // SAFETY: prctl and landlock_restrict_self are plain system calls, safe between fork and
+        // exec; the ruleset descriptor stays open until the command has spawned
+        unsafe {
+            command.pre_exec(move || {
+                if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::syscall(libc::SYS_landlock_restrict_self, fd, 0) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        Ok((
+            command,
+            Confinement {
+                _ruleset: Some(ruleset),
+            },
+        ))
+    }
+
+    fn ruleset(abi: libc::c_long, writable: &[PathBuf], network: bool) -> io::Result<OwnedFd> {
+        let mut handled_fs = ACCESS_FS_WRITE;
+        if abi >= 2 {
+            handled_fs |= ACCESS_FS_REFER;
+        }
+        if abi >= 3 {
+            handled_fs |= ACCESS_FS_TRUNCATE;
+        }
+        let attr = RulesetAttr {
+            handled_access_fs: handled_fs,
+            handled_access_net: if !network && abi >= 4 {
+                ACCESS_NET_BIND_TCP | ACCESS_NET_CONNECT_TCP
+            } else {
+                0
+            },
+        };
+        // SAFETY: `attr` is a valid ruleset attribute of the size passed
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                &attr as *const RulesetAttr,
+                std::mem::size_of::<RulesetAttr>(),
+                0,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the kernel returned a new descriptor that nothing else owns
+        let ruleset = unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) };
+
+        let file_access = handled_fs & (ACCESS_FS_WRITE_FILE | ACCESS_FS_TRUNCATE);
+        let directories = writable.iter().map(|dir| (dir.as_path(), handled_fs));
+        let files = WRITABLE_FILES
+            .iter()
+            .map(|file| (std::path::Path::new(*file), file_access));
+        for (path, access) in directories.chain(files) {
+            // Paths that do not exist cannot be written through anyway
+            let Ok(parent) = File::options()
+                .read(true)
+                .custom_flags(libc::O_PATH | libc::O_CLOEXEC)
+                .open(path)
+            else {
+                continue;
+            };
+            let rule = PathBeneathAttr {
+                allowed_access: access,
+                parent_fd: parent.as_raw_fd(),
+            };
+            // SAFETY: `rule` is a valid path-beneath rule for the open ruleset
+            let added = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_add_rule,
+                    ruleset.as_raw_fd(),
+                    RULE_PATH_BENEATH,
+                    &rule as *const PathBeneathAttr,
+                    0,
+                )
+            };
+            if added != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(ruleset)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::fmt::Write;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    pub(super) const SANDBOX_EXEC: &str = "/usr/bin/sandbox-exec";
+
+    /// Run `command` under `sandbox-exec`; paths are passed as parameters, never quoted into
+    /// the profile
+    pub(super) fn confine(command: Command, writable: &[PathBuf], network: bool) -> Command {
+        let mut profile = String::from(
+            "(version 1)\n(allow default)\n(deny file-write*)\n(allow file-write*\n    \
+             (literal \"/dev/null\")\n    (literal \"/dev/tty\")\n    \
+             (literal \"/dev/dtracehelper\")",
+        );
+        for index in 0..writable.len() {
+            let _ = write!(profile, "\n    (subpath (param \"WRITABLE_{index}\"))");
+        }
+        profile.push_str(")\n");
+        if !network {
+            profile.push_str("(deny network-outbound (remote ip))\n");
+            profile.push_str("(deny network-bind (local ip))\n");
+        }
+
+        let mut wrapped = Command::new(SANDBOX_EXEC);
+        wrapped.arg("-p").arg(profile);
+        for (index, path) in writable.iter().enumerate() {
+            let mut define = std::ffi::OsString::from(format!("WRITABLE_{index}="));
+            define.push(path);
+            wrapped.arg("-D").arg(define);
+        }
+        wrapped.arg(command.get_program()).args(command.get_args());
+        wrapped
+    }
+}
@@ -0,0 +1,411 @@
+//! Bevy systems for loading, watching, running and searching script commands
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use action_items_core::{LauncherEvent, LauncherEventType};
+use action_items_ecs_search_aggregator::{
+    CurrentQuery, LocalSearchProviders, SearchRequested, SearchResult, SearchResultReceived,
+};
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use ecs_filesystem::types::ChangeType;
+use ecs_filesystem::{FileOperationId, FileSystemChanged, FileSystemRequest, WatchConfig};
+use tracing::{info, warn};
+
+use crate::events::*;
+use crate::metadata::ScriptMetadata;
+use crate::output::{ScriptPresentation, present};
+use crate::registry::{ScriptCommand, ScriptCommandRegistry, is_script_candidate, scan_directory};
+use crate::runner::run_script;
+use crate::types::{ScriptCommandError, ScriptCommandsConfig, ScriptMode};
+
+/// Provider id used for search results and the aggregator registration
+pub const SCRIPT_COMMANDS_PROVIDER_ID: &str = "script_commands";
+/// Prefix of launcher action ids that run a script command
+pub const SCRIPT_COMMAND_ACTION_PREFIX: &str = "script_command_";
+/// Maximum results returned to a single search
+const MAX_SEARCH_RESULTS: usize = 20;
+
+/// Canonical paths of the watched script directories
+#[derive(Resource, Debug, Default)]
+pub struct ScriptDirectories(pub Vec<PathBuf>);
+
+/// Marker for the entity that requests directory watches
+#[derive(Component)]
+pub struct ScriptDirectoryWatcher;
+
+/// Component for script scan and run tasks
+#[derive(Component)]
+pub struct ScriptCommandTask(pub Task<CommandQueue>);
+
+/// Register script commands as a local search provider
+pub fn register_search_provider(mut providers: ResMut<LocalSearchProviders>) {
+    providers.register(SCRIPT_COMMANDS_PROVIDER_ID);
+}
+
+/// Create the script directories, watch them for changes and run the initial scan
+pub fn start_script_commands(
+    mut commands: Commands,
+    config: Res<ScriptCommandsConfig>,
+    mut watch_requests: EventWriter<FileSystemRequest>,
+) {
+    let mut directories = Vec::new();
+    for dir in &config.directories {
+        let canonical = std::fs::create_dir_all(dir).and_then(|()| dir.canonicalize());
+        match canonical {
+            Ok(canonical) => directories.push(canonical),
+            Err(e) => warn!("Skipping script directory {}: {}", dir.display(), e),
+        }
+    }
+
+    let requester = commands.spawn(ScriptDirectoryWatcher).id();
+    for dir in &directories {
+        watch_requests.write(FileSystemRequest::WatchDirectory {
+            operation_id: FileOperationId::new(),
+            requester,
+            path: dir.clone(),
+            config: Box::new(WatchConfig::default()),
+        });
+    }
+
+    spawn_reload(&mut commands, Vec::new(), directories.clone());
+    commands.insert_resource(ScriptDirectories(directories));
+}
+
+/// Reload scripts that changed inside the watched directories
+pub fn handle_script_directory_changes(
+    mut commands: Commands,
+    mut changes: EventReader<FileSystemChanged>,
+    directories: Res<ScriptDirectories>,
+) {
+    let mut removed = Vec::new();
+    let mut reload = HashSet::new();
+    let watched = |path: &PathBuf| directories.0.iter().any(|dir| path.starts_with(dir));
+
+    for change in changes.read().flat_map(|event| &event.changes) {
+        if !watched(&change.path) {
+            continue;
+        }
+        match &change.change_type {
+            ChangeType::Deleted => removed.push(change.path.clone()),
+            ChangeType::Renamed { from } | ChangeType::Moved { from } => {
+                removed.push(from.clone());
+                reload.insert(change.path.clone());
+            },
+            ChangeType::Created | ChangeType::Modified | ChangeType::AttributesChanged => {
+                reload.insert(change.path.clone());
+            },
+        }
+    }
+
+    let reload: Vec<PathBuf> = reload
+        .into_iter()
+        .filter(|path| is_script_candidate(path))
+        .collect();
+    if !removed.is_empty() || !reload.is_empty() {
+        spawn_reload(&mut commands, removed, reload);
+    }
+}
+
+/// Run script commands requested directly or chosen from launcher results
+///
+/// Launcher executions use any text typed after the command's title as positional
+/// arguments. Commands marked `@raycast.needsConfirmation` only run once confirmed.
+pub fn handle_script_run_requests(
+    mut commands: Commands,
+    mut run_events: EventReader<ScriptCommandRunRequested>,
+    mut launcher_events: EventReader<LauncherEvent>,
+    mut registry: ResMut<ScriptCommandRegistry>,
+    config: Res<ScriptCommandsConfig>,
+    current_query: Option<Res<CurrentQuery>>,
+    mut confirmation_events: EventWriter<ScriptCommandConfirmationRequired>,
+    mut finished_events: EventWriter<ScriptCommandFinished>,
+) {
+    let launched: Vec<ScriptCommandRunRequested> = launcher_events
+        .read()
+        .filter_map(|event| match &event.event_type {
+            LauncherEventType::Execute(action_id) => {
+                let command_id = action_id.strip_prefix(SCRIPT_COMMAND_ACTION_PREFIX)?;
+                let query = current_query.as_ref().map_or("", |query| query.0.as_str());
+                Some(ScriptCommandRunRequested {
+                    command_id: command_id.to_string(),
+                    arguments: title_arguments(&registry, command_id, query),
+                    confirmed: false,
+                })
+            },
+            _ => None,
+        })
+        .collect();
+    let requests: Vec<ScriptCommandRunRequested> =
+        run_events.read().cloned().chain(launched).collect();
+
+    for request in requests {
+        let Some(command) = registry.get(&request.command_id) else {
+            finished_events.write(ScriptCommandFinished {
+                result: Err(ScriptCommandError::NotFound(request.command_id.clone())),
+                command_id: request.command_id,
+            });
+            continue;
+        };
+        if command.metadata.needs_confirmation && !request.confirmed {
+            confirmation_events.write(ScriptCommandConfirmationRequired {
+                title: command.metadata.title.clone(),
+                request,
+            });
+            continue;
+        }
+        let arguments = match command.metadata.command_arguments(&request.arguments) {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                finished_events.write(ScriptCommandFinished {
+                    command_id: request.command_id,
+                    result: Err(e),
+                });
+                continue;
+            },
+        };
+
+        let command = command.clone();
+        if !registry.start_run(&command.id) {
+            warn!(
+                "Script command {} is already running",
+                command.metadata.title
+            );
+            continue;
+        }
+        spawn_run(&mut commands, command, arguments, &config);
+    }
+}
+
+/// Re-run inline commands whose refresh time has passed
+///
+/// Inline commands without a refresh time run once, so their result shows up in search.
+pub fn refresh_inline_commands(
+    mut commands: Commands,
+    mut registry: ResMut<ScriptCommandRegistry>,
+    config: Res<ScriptCommandsConfig>,
+) {
+    let now = Instant::now();
+    let due: Vec<ScriptCommand> = registry
+        .commands()
+        .iter()
+        .filter(|command| {
+            command.metadata.mode == ScriptMode::Inline
+                && !registry.is_running(&command.id)
+                && command.metadata.arguments.iter().all(|a| a.optional)
+                && match (command.last_refreshed, command.metadata.refresh_time) {
+                    (None, _) => true,
+                    (Some(last), Some(interval)) => now.duration_since(last) >= interval,
+                    (Some(_), None) => false,
+                }
+        })
+        .cloned()
+        .collect();
+
+    for command in due {
+        registry.start_run(&command.id);
+        spawn_run(&mut commands, command, Vec::new(), &config);
+    }
+}
+
+/// Answer aggregated launcher searches from the script command registry
+pub fn search_script_commands(
+    mut requests: EventReader<SearchRequested>,
+    mut results: EventWriter<SearchResultReceived>,
+    registry: Res<ScriptCommandRegistry>,
+) {
+    for request in requests.read() {
+        if !request
+            .requesting_plugins
+            .iter()
+            .any(|id| id == SCRIPT_COMMANDS_PROVIDER_ID)
+        {
+            continue;
+        }
+
+        let started = std::time::Instant::now();
+        let matches = registry
+            .search(&request.query, MAX_SEARCH_RESULTS)
+            .into_iter()
+            .map(|found| SearchResult {
+                title: found.command.metadata.title.clone(),
+                description: describe_command(found.command),
+                action: format!("{SCRIPT_COMMAND_ACTION_PREFIX}{}", found.command.id),
+                icon: Some(command_icon(&found.command.metadata).to_string()),
+                score: found.score,
+                plugin_id: SCRIPT_COMMANDS_PROVIDER_ID.to_string(),
+                title_matches: Vec::new(),
+            })
+            .collect();
+
+        results.write(SearchResultReceived {
+            search_id: request.search_id,
+            plugin_id: SCRIPT_COMMANDS_PROVIDER_ID.to_string(),
+            results: matches,
+            execution_time_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+}
+
+/// Poll script command tasks
+pub fn handle_script_command_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut ScriptCommandTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.0)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Drop `removed` paths and (re)load everything at or below `reload` in the background
+fn spawn_reload(commands: &mut Commands, removed: Vec<PathBuf>, reload: Vec<PathBuf>) {
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let mut command_queue = CommandQueue::default();
+
+        let mut loaded: Vec<(PathBuf, Result<ScriptMetadata, ScriptCommandError>)> = Vec::new();
+        for path in &reload {
+            if path.is_dir() {
+                loaded.extend(scan_directory(path));
+            } else {
+                loaded.push((path.clone(), crate::registry::load_script(path)));
+            }
+        }
+
+        command_queue.push(move |world: &mut World| {
+            let Some(mut registry) = world.get_resource_mut::<ScriptCommandRegistry>() else {
+                return;
+            };
+            for path in &removed {
+                registry.remove(path);
+            }
+            for (path, result) in loaded {
+                if let Err(e) = &result
+                    && *e != ScriptCommandError::NoMetadata
+                {
+                    warn!("Invalid script command {}: {}", path.display(), e);
+                }
+                registry.apply(path, result);
+            }
+            let reloaded = ScriptCommandsReloaded {
+                command_count: registry.len(),
+                error_count: registry.errors().len(),
+            };
+            info!("Loaded {} script commands", reloaded.command_count);
+            world.send_event(reloaded);
+        });
+
+        command_queue
+    });
+    commands.spawn(ScriptCommandTask(task));
+}
+
+/// Run a script within the configured limits and report the presented result
+fn spawn_run(
+    commands: &mut Commands,
+    command: ScriptCommand,
+    arguments: Vec<String>,
+    config: &ScriptCommandsConfig,
+) {
+    let limits = config.limits.clone();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let mut command_queue = CommandQueue::default();
+
+        let working_dir = command.metadata.working_directory(&command.path);
+        let result = run_script(&command.path, &arguments, &working_dir, &limits)
+            .map(|output| present(command.metadata.mode, &output));
+        if let Err(e) = &result {
+            warn!("Script command {} failed: {}", command.metadata.title, e);
+        }
+
+        command_queue.push(move |world: &mut World| {
+            if let Some(mut registry) = world.get_resource_mut::<ScriptCommandRegistry>() {
+                registry.finish_run(&command.id);
+                if command.metadata.mode == ScriptMode::Inline
+                    && let Some(stored) = registry.get_mut(&command.id)
+                {
+                    stored.last_refreshed = Some(Instant::now());
+                    if let Ok(ScriptPresentation::Inline(line)) = &result {
+                        stored.inline_output = Some(line.clone());
+                    }
+                }
+            }
+            world.send_event(ScriptCommandFinished {
+                command_id: command.id,
+                result,
+            });
+        });
+
+        command_queue
+    });
+    commands.spawn(ScriptCommandTask(task));
+}
+
+/// Arguments typed after the command's title in the launcher query
+///
+/// Words fill the arguments one by one and the last argument takes the remaining text.
+fn title_arguments(registry: &ScriptCommandRegistry, command_id: &str, query: &str) -> Vec<String> {
+    let Some(input) = registry
+        .search(query, usize::MAX)
+        .into_iter()
+        .find(|found| found.command.id == command_id)
+        .and_then(|found| found.argument_input)
+    else {
+        return Vec::new();
+    };
+    let count = registry
+        .get(command_id)
+        .map_or(0, |command| command.metadata.arguments.len());
+
+    let mut values = Vec::new();
+    let mut rest = input.trim();
+    while !rest.is_empty() && values.len() < count {
+        if values.len() + 1 == count {
+            values.push(std::mem::take(&mut rest).to_string());
+        } else {
+            let (word, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            values.push(word.to_string());
+            rest = remainder.trim_start();
+        }
+    }
+    values
+}
+
+fn describe_command(command: &ScriptCommand) -> String {
+    let metadata = &command.metadata;
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(output) = &command.inline_output {
+        parts.push(output.clone());
+    }
+    if !metadata.arguments.is_empty() {
+        let names: Vec<&str> = metadata
+            .arguments
+            .iter()
+            .map(|a| a.placeholder.as_str())
+            .collect();
+        parts.push(format!("<{}>", names.join("> <")));
+    }
+    if let Some(package) = &metadata.package_name {
+        parts.push(package.clone());
+    }
+    if command.inline_output.is_none()
+        && let Some(description) = &metadata.description
+    {
+        parts.push(description.clone());
+    }
+    parts.join(" · ")
+}
+
+/// Emoji icons are shown as is; file and URL icons fall back to a generic script icon
+fn command_icon(metadata: &ScriptMetadata) -> &str {
+    match metadata.icon.as_deref() {
+        Some(icon) if !icon.chars().any(|c| c.is_ascii()) => icon,
+        _ => "📜",
+    }
+}
@@ -0,0 +1,100 @@
+//! Script command types and errors
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use action_items_common::directories::AppDirectories;
+use action_items_common::plugin_interface::commands::CommandMode;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{ScriptLimits, ScriptOutput};
+
+/// Script command settings
+#[derive(Resource, Debug, Clone)]
+pub struct ScriptCommandsConfig {
+    /// Directories scanned for scripts, including subdirectories
+    pub directories: Vec<PathBuf>,
+    /// Limits applied when running scripts
+    pub limits: ScriptLimits,
+}
+
+impl Default for ScriptCommandsConfig {
+    fn default() -> Self {
+        Self {
+            directories: vec![AppDirectories::new().config_dir().join("script-commands")],
+            limits: ScriptLimits::default(),
+        }
+    }
+}
+
+/// How a script's output is presented, from `@raycast.mode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptMode {
+    /// Runs in the background; the last output line is shown as a HUD
+    Silent,
+    /// Runs in the background; the last output line is shown as a toast
+    Compact,
+    /// Shows the whole output in a detail view
+    FullOutput,
+    /// Shows the first output line in the command's search result, optionally refreshed
+    Inline,
+}
+
+impl ScriptMode {
+    /// Parse a Raycast mode name
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "silent" => Some(Self::Silent),
+            "compact" => Some(Self::Compact),
+            "fullOutput" => Some(Self::FullOutput),
+            "inline" => Some(Self::Inline),
+            _ => None,
+        }
+    }
+
+    /// The launcher command mode used for this script mode
+    pub fn command_mode(self) -> CommandMode {
+        match self {
+            Self::Silent | Self::Compact => CommandMode::NoView,
+            Self::FullOutput => CommandMode::Detail,
+            Self::Inline => CommandMode::List,
+        }
+    }
+}
+
+/// Errors from loading and running script commands
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+pub enum ScriptCommandError {
+    #[error("File has no @raycast metadata")]
+    NoMetadata,
+    #[error("Missing required metadata @raycast.{0}")]
+    MissingField(String),
+    #[error("Unsupported schema version '{0}'")]
+    UnsupportedSchemaVersion(String),
+    #[error("Unknown mode '{0}'")]
+    InvalidMode(String),
+    #[error("Invalid refresh time '{0}'")]
+    InvalidRefreshTime(String),
+    #[error("Invalid @raycast.argument{position}: {reason}")]
+    InvalidArgument { position: usize, reason: String },
+    #[error("No value for required argument {0}")]
+    MissingArgument(usize),
+    #[error("Script command not found: {0}")]
+    NotFound(String),
+    #[error("No interpreter for {0}; add a #! line")]
+    NoInterpreter(PathBuf),
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("Failed to start script: {0}")]
+    Spawn(String),
+    /// The script was killed at the time limit; `output` holds what it wrote until then
+    #[error("Script timed out after {after:?}")]
+    TimedOut { after: Duration, output: ScriptOutput },
+}
+
+impl From<std::io::Error> for ScriptCommandError {
+    fn from(error: std::io::Error) -> Self {
+        ScriptCommandError::Io(error.to_string())
+    }
+}
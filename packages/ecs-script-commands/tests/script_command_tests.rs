@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+use action_items_common::plugin_interface::commands::CommandMode;
+use action_items_ecs_script_commands::output::{present, strip_ansi};
+use action_items_ecs_script_commands::registry::scan_directory;
+use action_items_ecs_script_commands::{
+    ScriptArgumentKind, ScriptCommandError, ScriptCommandRegistry, ScriptMetadata, ScriptMode,
+    ScriptOutput, ScriptPresentation,
+};
+
+const SEARCH_SCRIPT: &str = r#"#!/bin/bash
+
+# Required parameters:
+# @raycast.schemaVersion 1
+# @raycast.title Search Issues
+# @raycast.mode silent
+
+# Optional parameters:
+# @raycast.packageName Developer Utils
+# @raycast.icon 🐛
+# @raycast.argument1 { "type": "text", "placeholder": "Query", "percentEncoded": true }
+# @raycast.argument2 { "type": "dropdown", "placeholder": "State", "optional": true, "data": [{"title": "Open", "value": "open"}, {"title": "Closed", "value": "closed"}] }
+
+open "https://github.com/issues?q=$1&state=$2"
+"#;
+
+fn output(stdout: &str, exit_code: i32) -> ScriptOutput {
+    ScriptOutput {
+        stdout: stdout.to_string(),
+        exit_code: Some(exit_code),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_raycast_headers() {
+    let metadata = ScriptMetadata::parse(SEARCH_SCRIPT).unwrap();
+    assert_eq!(metadata.title, "Search Issues");
+    assert_eq!(metadata.mode, ScriptMode::Silent);
+    assert_eq!(metadata.package_name.as_deref(), Some("Developer Utils"));
+    assert_eq!(metadata.arguments.len(), 2);
+    assert!(metadata.arguments[0].percent_encoded);
+    assert!(matches!(
+        &metadata.arguments[1].kind,
+        ScriptArgumentKind::Dropdown(options) if options.len() == 2
+    ));
+
+    let definition = metadata.to_command_definition("id");
+    assert!(matches!(definition.mode, CommandMode::NoView));
+    assert_eq!(definition.arguments[0].name, "argument1");
+    assert!(definition.arguments[0].required);
+    assert!(!definition.arguments[1].required);
+}
+
+#[test]
+fn test_other_comment_styles_and_refresh_time() {
+    let source = "// @raycast.schemaVersion 1\n// @raycast.title Battery\n\
+                  // @raycast.mode inline\n// @raycast.refreshTime 1s\n";
+    let metadata = ScriptMetadata::parse(source).unwrap();
+    assert_eq!(metadata.refresh_time, Some(Duration::from_secs(10)));
+    assert_eq!(metadata.to_command_definition("id").interval, Some(10));
+
+    let source =
+        "-- @raycast.schemaVersion 1\n-- @raycast.title Notes\n-- @raycast.mode fullOutput";
+    assert_eq!(
+        ScriptMetadata::parse(source).unwrap().mode,
+        ScriptMode::FullOutput
+    );
+}
+
+#[test]
+fn test_invalid_headers() {
+    assert_eq!(
+        ScriptMetadata::parse("#!/bin/sh\necho hi\n"),
+        Err(ScriptCommandError::NoMetadata)
+    );
+    assert_eq!(
+        ScriptMetadata::parse("# @raycast.schemaVersion 1\n# @raycast.mode silent"),
+        Err(ScriptCommandError::MissingField("title".to_string()))
+    );
+    assert_eq!(
+        ScriptMetadata::parse(
+            "# @raycast.schemaVersion 1\n# @raycast.title X\n# @raycast.mode loud"
+        ),
+        Err(ScriptCommandError::InvalidMode("loud".to_string()))
+    );
+    assert!(matches!(
+        ScriptMetadata::parse(
+            "# @raycast.schemaVersion 1\n# @raycast.title X\n# @raycast.mode silent\n\
+             # @raycast.argument2 { \"type\": \"text\" }"
+        ),
+        Err(ScriptCommandError::InvalidArgument { position: 2, .. })
+    ));
+}
+
+#[test]
+fn test_command_arguments() {
+    let metadata = ScriptMetadata::parse(SEARCH_SCRIPT).unwrap();
+    assert_eq!(
+        metadata
+            .command_arguments(&["is:open bug".to_string()])
+            .unwrap(),
+        vec!["is%3Aopen%20bug".to_string(), String::new()]
+    );
+    assert_eq!(
+        metadata.command_arguments(&[]),
+        Err(ScriptCommandError::MissingArgument(1))
+    );
+    assert!(matches!(
+        metadata.command_arguments(&["bug".to_string(), "stale".to_string()]),
+        Err(ScriptCommandError::InvalidArgument { position: 2, .. })
+    ));
+}
+
+#[test]
+fn test_presentation_by_mode() {
+    let out = output("step 1\n\u{1b}[32mDone\u{1b}[0m\n", 0);
+    assert_eq!(
+        present(ScriptMode::Silent, &out),
+        ScriptPresentation::Hud("Done".to_string())
+    );
+    assert_eq!(
+        present(ScriptMode::Compact, &out),
+        ScriptPresentation::Toast {
+            message: "Done".to_string(),
+            success: true
+        }
+    );
+    assert_eq!(
+        present(ScriptMode::FullOutput, &out),
+        ScriptPresentation::Detail("step 1\nDone".to_string())
+    );
+    assert_eq!(
+        present(ScriptMode::Inline, &out),
+        ScriptPresentation::Inline("step 1".to_string())
+    );
+    assert_eq!(
+        present(ScriptMode::FullOutput, &output("partial\nno network\n", 2)),
+        ScriptPresentation::Toast {
+            message: "no network".to_string(),
+            success: false
+        }
+    );
+    assert_eq!(strip_ansi("\u{1b}]0;title\u{7}plain"), "plain");
+}
+
+#[test]
+fn test_scan_and_search() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("search-issues.sh"), SEARCH_SCRIPT).unwrap();
+    std::fs::write(dir.path().join("helper.sh"), "#!/bin/sh\necho helper\n").unwrap();
+    std::fs::write(dir.path().join("x.template.sh"), SEARCH_SCRIPT).unwrap();
+    std::fs::create_dir(dir.path().join("nested")).unwrap();
+    std::fs::write(
+        dir.path().join("nested/broken.py"),
+        "# @raycast.schemaVersion 2\n",
+    )
+    .unwrap();
+
+    let mut registry = ScriptCommandRegistry::default();
+    for (path, loaded) in scan_directory(dir.path()) {
+        registry.apply(path, loaded);
+    }
+    assert_eq!(registry.len(), 1);
+    assert_eq!(registry.errors().len(), 1);
+
+    let found = registry.search("search issues crash on start", 10);
+    assert_eq!(found[0].argument_input.as_deref(), Some("crash on start"));
+    assert_eq!(registry.search("developer", 10).len(), 1);
+
+    registry.remove(dir.path());
+    assert!(registry.is_empty());
+    assert!(registry.errors().is_empty());
+}
+
+#[cfg(unix)]
+mod runner {
+    use action_items_ecs_script_commands::runner::run_script;
+    use action_items_ecs_script_commands::{ScriptLimits, ScriptSandbox};
+
+    use super::*;
+
+    fn script(dir: &tempfile::TempDir, source: &str) -> std::path::PathBuf {
+        let path = dir.path().join("script.sh");
+        std::fs::write(&path, source).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_runs_with_arguments_in_cleared_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = script(
+            &dir,
+            "#!/bin/sh\necho \"$1|$2|${SECRET_TOKEN:-unset}\"\npwd\n",
+        );
+        // SAFETY: no other test in this binary reads the variable
+        unsafe { std::env::set_var("SECRET_TOKEN", "leaked") };
+
+        let output = run_script(
+            &path,
+            &["a b".to_string(), "c".to_string()],
+            dir.path(),
+            &ScriptLimits::default(),
+        )
+        .unwrap();
+        assert!(output.success());
+        let lines: Vec<&str> = output.stdout.lines().collect();
+        assert_eq!(lines[0], "a b|c|unset");
+        assert_eq!(
+            std::path::Path::new(lines[1]).canonicalize().unwrap(),
+            dir.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_timeout_and_output_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ScriptLimits {
+            timeout: Duration::from_millis(200),
+            max_output_bytes: 16,
+            ..Default::default()
+        };
+
+        let path = script(&dir, "#!/bin/sh\necho started\necho oops >&2\nsleep 5\n");
+        match run_script(&path, &[], dir.path(), &config) {
+            Err(ScriptCommandError::TimedOut { after, output }) => {
+                assert_eq!(after, config.timeout);
+                assert_eq!(output.stdout, "started\n");
+                assert_eq!(output.stderr, "oops\n");
+                assert_eq!(output.exit_code, None);
+            },
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+
+        let path = script(&dir, "#!/usr/bin/env sh\nseq 1 1000\nexit 3\n");
+        let output = run_script(&path, &[], dir.path(), &config).unwrap();
+        assert_eq!(output.exit_code, Some(3));
+        assert!(output.truncated);
+        assert_eq!(output.stdout.len(), 16);
+    }
+
+    #[test]
+    fn test_background_children_do_not_hold_up_the_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = script(&dir, "#!/bin/sh\necho done\nsleep 100 &\n");
+
+        let started = std::time::Instant::now();
+        let output = run_script(&path, &[], dir.path(), &ScriptLimits::default()).unwrap();
+        assert!(output.success());
+        assert_eq!(output.stdout, "done\n");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_sandbox_limits_writes_to_granted_directories() {
+        if !action_items_ecs_script_commands::sandbox::available() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let config = ScriptLimits {
+            sandbox: ScriptSandbox {
+                writable: Vec::new(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let path = script(
+            &dir,
+            &format!(
+                "#!/bin/sh\necho kept > inside.txt\necho lost > {}/outside.txt\n",
+                outside.path().display()
+            ),
+        );
+
+        let output = run_script(&path, &[], dir.path(), &config).unwrap();
+        assert!(!output.success());
+        assert!(dir.path().join("inside.txt").exists());
+        assert!(!outside.path().join("outside.txt").exists());
+    }
+}
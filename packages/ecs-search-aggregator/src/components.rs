@@ -3,6 +3,7 @@ use std::time::Instant;
 use bevy::prelude::*;
 use bevy::tasks::Task;
 
+use crate::provider::CancellationToken;
 use crate::types::{SearchError, SearchId, SearchResult};
 
 /// Component for tracking plugin search tasks - following async_compute.rs pattern
@@ -33,6 +34,36 @@ impl PluginSearchTask {
     }
 }
 
+/// Component for a [`SearchProvider`](crate::provider::SearchProvider) streaming results
+///
+/// The task forwards each result through `results`; the task finishing means the stream ended.
+#[derive(Component)]
+pub struct ProviderSearchTask {
+    pub search_id: SearchId,
+    pub provider_id: String,
+    pub task: Task<()>,
+    pub results: crossbeam_channel::Receiver<SearchResult>,
+    pub cancellation: CancellationToken,
+    pub started_at: Instant,
+    /// When the provider's budget runs out
+    pub deadline: Instant,
+}
+
+impl ProviderSearchTask {
+    pub fn execution_time_ms(&self) -> u64 {
+        self.started_at.elapsed().as_millis() as u64
+    }
+
+    pub fn is_over_budget(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    /// Results received since the last call
+    pub fn drain(&self) -> Vec<SearchResult> {
+        self.results.try_iter().collect()
+    }
+}
+
 /// Component for search timeout tracking
 #[derive(Component)]
 pub struct SearchTimeout {
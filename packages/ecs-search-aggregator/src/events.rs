@@ -29,6 +29,17 @@ pub struct SearchResultReceived {
     pub execution_time_ms: u64,
 }
 
+/// Event carrying results a provider streamed before finishing
+///
+/// Results are shown right away; the provider still answers with [`SearchResultReceived`]
+/// (possibly with no further results) or [`SearchFailed`] when it is done.
+#[derive(Event, Debug, Clone)]
+pub struct SearchResultsStreamed {
+    pub search_id: SearchId,
+    pub plugin_id: String,
+    pub results: Vec<SearchResult>,
+}
+
 /// Event when a plugin search fails or times out
 #[derive(Event, Debug, Clone)]
 pub struct SearchFailed {
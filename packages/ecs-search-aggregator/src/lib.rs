@@ -10,6 +10,7 @@ pub mod components;
 pub mod events;
 pub mod manager;
 pub mod plugin;
+pub mod provider;
 pub mod types;
 
 // Re-export core types
@@ -17,6 +18,10 @@ pub use components::*;
 pub use events::*;
 pub use manager::SearchAggregatorManager;
 pub use plugin::SearchAggregatorPlugin;
+pub use provider::{
+    CancellationToken, DEFAULT_PROVIDER_BUDGET, SearchContext, SearchProvider, SearchProviders,
+    SearchResultStream,
+};
 pub use types::*;
 
 /// Convenience function to add the search aggregator system to a Bevy app
@@ -15,6 +15,7 @@ use tracing::{debug, info, warn};
 use crate::components::*;
 use crate::events::*;
 use crate::manager::SearchAggregatorManager;
+use crate::provider::{CancellationToken, SearchContext, SearchProviders, provider_query};
 use crate::types::*;
 
 /// Bevy plugin for search aggregation - coordinates searches across multiple plugins
@@ -26,8 +27,11 @@ impl Plugin for SearchAggregatorPlugin {
             .init_resource::<AggregatedSearchResults>()
             .init_resource::<SearchConfig>()
             .init_resource::<CurrentQuery>()
+            .init_resource::<LocalSearchProviders>()
+            .init_resource::<SearchProviders>()
             .add_event::<SearchRequested>()
             .add_event::<SearchResultReceived>()
+            .add_event::<SearchResultsStreamed>()
             .add_event::<SearchFailed>()
             .add_event::<SearchCompleted>()
             .add_event::<SearchCancelled>()
@@ -37,6 +41,7 @@ impl Plugin for SearchAggregatorPlugin {
                     query_change_detection_system,
                     spawn_plugin_search_tasks_system,
                     handle_plugin_search_tasks_system,
+                    poll_provider_search_tasks_system,
                     aggregate_search_results_system,
                     search_timeout_system,
                     search_cancellation_system,
@@ -55,6 +60,8 @@ fn query_change_detection_system(
     mut aggregated_results: ResMut<AggregatedSearchResults>,
     search_config: Res<SearchConfig>,
     capability_index: Res<PluginCapabilityIndex>,
+    local_providers: Res<LocalSearchProviders>,
+    search_providers: Res<SearchProviders>,
 ) {
     // Only trigger on actual query changes
     if !current_query.is_changed() {
@@ -66,13 +73,7 @@ fn query_change_detection_system(
     // Clear results immediately when query changes
     aggregated_results.clear();
 
-    // Validate query
-    if let Err(e) = SearchAggregatorManager::validate_query(&query, &search_config) {
-        debug!("Invalid search query '{}': {}", query, e);
-        return;
-    }
-
-    // Cancel any active searches with proper event emission
+    // Cancel searches for the previous query, including when the new one is empty
     for (search_id, active_search) in search_aggregator.active_searches.iter() {
         let cancellation_event = SearchCancelled {
             search_id: *search_id,
@@ -86,8 +87,26 @@ fn query_change_detection_system(
     }
     search_aggregator.active_searches.clear();
 
-    // Find plugins with search capability via service bridge integration
-    let search_capable_plugins: Vec<String> = discover_search_capable_plugins(&capability_index);
+    // Validate query
+    if let Err(e) = SearchAggregatorManager::validate_query(&query, &search_config) {
+        debug!("Invalid search query '{}': {}", query, e);
+        return;
+    }
+
+    // Find plugins with search capability via service bridge integration, plus in-process
+    // providers that answer the request event directly
+    let mut search_capable_plugins: Vec<String> =
+        discover_search_capable_plugins(&capability_index);
+    for provider_id in &local_providers.provider_ids {
+        if !search_capable_plugins.contains(provider_id) {
+            search_capable_plugins.push(provider_id.clone());
+        }
+    }
+    for (provider, _) in search_providers.matching(&query) {
+        if !search_capable_plugins.iter().any(|id| id == provider.id()) {
+            search_capable_plugins.push(provider.id().to_string());
+        }
+    }
 
     if search_capable_plugins.is_empty() {
         debug!("No search-capable plugins found");
@@ -121,6 +140,8 @@ fn spawn_plugin_search_tasks_system(
     search_config: Res<SearchConfig>,
     plugin_registry: Res<PluginRegistryResource>,
     message_infrastructure: Res<MessageInfrastructure>,
+    local_providers: Res<LocalSearchProviders>,
+    search_providers: Res<SearchProviders>,
 ) {
     for search_event in search_events.read() {
         let search_id = search_event.search_id;
@@ -136,8 +157,21 @@ fn spawn_plugin_search_tasks_system(
         let timeout_duration = std::time::Duration::from_millis(search_config.timeout_ms);
         commands.spawn(SearchTimeout::new(search_id, timeout_duration));
 
-        // Spawn search task for each plugin
+        // Spawn search task for each plugin; local providers answer the event themselves
         for plugin_id in &search_event.requesting_plugins {
+            if local_providers.contains(plugin_id) {
+                continue;
+            }
+            if let Some(provider) = search_providers.get(plugin_id) {
+                spawn_provider_search(
+                    &mut commands,
+                    provider,
+                    search_event,
+                    &search_config,
+                    timeout_duration,
+                );
+                continue;
+            }
             let plugin_id_owned = plugin_id.clone();
             let query_owned = query.clone();
             let _max_results = search_config.max_results_per_plugin;
@@ -243,8 +277,61 @@ fn handle_plugin_search_tasks_system(
         }
     }
 }
+/// System to poll search provider streams, forwarding results as they arrive
+fn poll_provider_search_tasks_system(
+    mut commands: Commands,
+    provider_tasks: Query<(Entity, &ProviderSearchTask)>,
+    mut streamed_events: EventWriter<SearchResultsStreamed>,
+    mut result_events: EventWriter<SearchResultReceived>,
+    mut failure_events: EventWriter<SearchFailed>,
+) {
+    for (entity, task) in provider_tasks.iter() {
+        // The stream has ended once its task finished; everything it sent is in the channel
+        let finished = task.task.is_finished();
+        let results = task.drain();
+
+        if finished {
+            result_events.write(SearchResultReceived {
+                search_id: task.search_id,
+                plugin_id: task.provider_id.clone(),
+                results,
+                execution_time_ms: task.execution_time_ms(),
+            });
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if !results.is_empty() {
+            streamed_events.write(SearchResultsStreamed {
+                search_id: task.search_id,
+                plugin_id: task.provider_id.clone(),
+                results,
+            });
+        }
+
+        if task.is_over_budget() {
+            // Results streamed so far stay in the list
+            debug!(
+                "Provider '{}' exceeded its budget after {}ms",
+                task.provider_id,
+                task.execution_time_ms()
+            );
+            task.cancellation.cancel();
+            failure_events.write(SearchFailed {
+                search_id: task.search_id,
+                plugin_id: task.provider_id.clone(),
+                error: SearchError::Timeout,
+            });
+            commands.entity(entity).despawn();
+        }
+    }
+}
 /// System to aggregate results from multiple plugins
+///
+/// The aggregated list is rebuilt whenever results arrive, so fast providers show up before
+/// slow ones have answered.
 fn aggregate_search_results_system(
+    mut streamed_events: EventReader<SearchResultsStreamed>,
     mut result_events: EventReader<SearchResultReceived>,
     mut failure_events: EventReader<SearchFailed>,
     mut search_aggregator: ResMut<SearchAggregator>,
@@ -252,11 +339,32 @@ fn aggregate_search_results_system(
     mut completion_events: EventWriter<SearchCompleted>,
     search_config: Res<SearchConfig>,
 ) {
+    // Handle results from providers that are still running
+    for streamed_event in streamed_events.read() {
+        let search_id = streamed_event.search_id;
+
+        if let Some(active_search) = search_aggregator.active_searches.get_mut(&search_id) {
+            if active_search.is_settled(&streamed_event.plugin_id) {
+                continue;
+            }
+            active_search.stream_results(streamed_event.results.clone());
+
+            if aggregated_results.search_id == Some(search_id) {
+                refresh_aggregated_results(active_search, &mut aggregated_results, &search_config);
+            }
+        }
+    }
+
     // Handle successful results
     for result_event in result_events.read() {
         let search_id = result_event.search_id;
 
         if let Some(active_search) = search_aggregator.active_searches.get_mut(&search_id) {
+            // Late answers from plugins that already timed out are dropped
+            if active_search.is_settled(&result_event.plugin_id) {
+                continue;
+            }
+
             // Add results to the active search
             active_search.add_results(result_event.plugin_id.clone(), result_event.results.clone());
 
@@ -265,44 +373,12 @@ fn aggregate_search_results_system(
                 aggregated_results
                     .completed_plugins
                     .insert(result_event.plugin_id.clone());
-
-                // Re-process all results with scoring and deduplication
-                let all_plugin_results: Vec<(String, Vec<SearchResult>)> = active_search
-                    .expected_plugins
-                    .iter()
-                    .filter_map(|pid| {
-                        if active_search.completed_plugins.contains(pid) {
-                            let plugin_results: Vec<SearchResult> = active_search
-                                .results
-                                .iter()
-                                .filter(|r| r.plugin_id == *pid)
-                                .cloned()
-                                .collect();
-                            Some((pid.clone(), plugin_results))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-
-                aggregated_results.results = SearchAggregatorManager::merge_plugin_results(
-                    all_plugin_results,
-                    &active_search.query,
-                    &search_config,
-                );
+                refresh_aggregated_results(active_search, &mut aggregated_results, &search_config);
             }
 
             // Check if search is complete
             if active_search.is_complete() {
-                let execution_time = active_search.started_at.elapsed().as_millis() as u64;
-
-                completion_events.write(SearchCompleted {
-                    search_id,
-                    total_results: active_search.results.len(),
-                    responding_plugins: active_search.completed_plugins.iter().cloned().collect(),
-                    failed_plugins: active_search.failed_plugins.keys().cloned().collect(),
-                    execution_time_ms: execution_time,
-                });
+                completion_events.write(completion_event(active_search));
             }
         }
     }
@@ -312,6 +388,10 @@ fn aggregate_search_results_system(
         let search_id = failure_event.search_id;
 
         if let Some(active_search) = search_aggregator.active_searches.get_mut(&search_id) {
+            if active_search.is_settled(&failure_event.plugin_id) {
+                continue;
+            }
+
             active_search.mark_failed(
                 failure_event.plugin_id.clone(),
                 format!("{:?}", failure_event.error),
@@ -327,19 +407,49 @@ fn aggregate_search_results_system(
 
             // Check if search is complete
             if active_search.is_complete() {
-                let execution_time = active_search.started_at.elapsed().as_millis() as u64;
-
-                completion_events.write(SearchCompleted {
-                    search_id,
-                    total_results: active_search.results.len(),
-                    responding_plugins: active_search.completed_plugins.iter().cloned().collect(),
-                    failed_plugins: active_search.failed_plugins.keys().cloned().collect(),
-                    execution_time_ms: execution_time,
-                });
+                completion_events.write(completion_event(active_search));
             }
         }
     }
 }
+
+/// Re-process all results received so far with scoring and deduplication
+fn refresh_aggregated_results(
+    active_search: &ActiveSearch,
+    aggregated_results: &mut AggregatedSearchResults,
+    search_config: &SearchConfig,
+) {
+    let all_plugin_results: Vec<(String, Vec<SearchResult>)> = active_search
+        .expected_plugins
+        .iter()
+        .map(|pid| {
+            let plugin_results: Vec<SearchResult> = active_search
+                .results
+                .iter()
+                .filter(|r| r.plugin_id == *pid)
+                .cloned()
+                .collect();
+            (pid.clone(), plugin_results)
+        })
+        .filter(|(_, results)| !results.is_empty())
+        .collect();
+
+    aggregated_results.results = SearchAggregatorManager::merge_plugin_results(
+        all_plugin_results,
+        &active_search.query,
+        search_config,
+    );
+}
+
+fn completion_event(active_search: &ActiveSearch) -> SearchCompleted {
+    SearchCompleted {
+        search_id: active_search.search_id,
+        total_results: active_search.results.len(),
+        responding_plugins: active_search.completed_plugins.iter().cloned().collect(),
+        failed_plugins: active_search.failed_plugins.keys().cloned().collect(),
+        execution_time_ms: active_search.started_at.elapsed().as_millis() as u64,
+    }
+}
 /// System to handle search timeouts
 fn search_timeout_system(
     mut commands: Commands,
@@ -383,6 +493,7 @@ fn search_cancellation_system(
     mut cancel_events: EventReader<SearchCancelled>,
     mut commands: Commands,
     active_tasks: Query<(Entity, &PluginSearchTask)>,
+    provider_tasks: Query<(Entity, &ProviderSearchTask)>,
     timeout_entities: Query<(Entity, &SearchTimeout)>,
 ) {
     for cancel_event in cancel_events.read() {
@@ -395,6 +506,14 @@ fn search_cancellation_system(
             }
         }
 
+        // Dropping a provider's task stops its stream at the next await point
+        for (entity, task) in provider_tasks.iter() {
+            if task.search_id == search_id {
+                task.cancellation.cancel();
+                commands.entity(entity).despawn();
+            }
+        }
+
         // Cancel timeout entities for this search
         for (entity, timeout) in timeout_entities.iter() {
             if timeout.search_id == search_id {
@@ -436,6 +555,62 @@ fn search_cleanup_system(
     }
 }
 
+/// Run a search provider's stream in a task that forwards each result over a channel
+///
+/// The provider's budget is capped by the overall search timeout.
+fn spawn_provider_search(
+    commands: &mut Commands,
+    provider: &std::sync::Arc<dyn crate::provider::SearchProvider>,
+    search_event: &SearchRequested,
+    search_config: &SearchConfig,
+    search_timeout: std::time::Duration,
+) {
+    let Some(query) = provider_query(provider.trigger(), &search_event.query) else {
+        return;
+    };
+    let cancellation = CancellationToken::default();
+    let context = SearchContext {
+        search_id: search_event.search_id,
+        raw_query: search_event.query.clone(),
+        max_results: search_config.max_results_per_plugin,
+        cancellation: cancellation.clone(),
+    };
+    let query = query.to_string();
+    let max_results = search_config.max_results_per_plugin;
+    let provider_id = provider.id().to_string();
+
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let result_plugin_id = provider_id.clone();
+    let search_provider = provider.clone();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        use bevy::tasks::futures_lite::StreamExt;
+
+        // Started in the task so a provider doing work up front never stalls the frame
+        let mut stream = search_provider.search(&query, context);
+        let mut sent = 0;
+        while sent < max_results
+            && let Some(mut result) = stream.next().await
+        {
+            result.plugin_id = result_plugin_id.clone();
+            if sender.send(result).is_err() {
+                break;
+            }
+            sent += 1;
+        }
+    });
+
+    let started_at = std::time::Instant::now();
+    commands.spawn(ProviderSearchTask {
+        search_id: search_event.search_id,
+        provider_id,
+        task,
+        results: receiver,
+        cancellation,
+        started_at,
+        deadline: started_at + provider.budget().min(search_timeout),
+    });
+}
+
 /// Discover search-capable plugins from the service bridge registry
 fn discover_search_capable_plugins(capability_index: &PluginCapabilityIndex) -> Vec<String> {
     let search_plugins = capability_index
@@ -612,6 +787,7 @@ fn parse_search_response(
             icon,
             score,
             plugin_id: plugin_id.to_string(),
+            title_matches: Vec::new(),
         });
     }
 
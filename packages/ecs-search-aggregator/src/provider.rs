@@ -0,0 +1,141 @@
+//! Search provider abstraction
+//!
+//! A [`SearchProvider`] answers a query with a stream of results. The aggregator runs each
+//! matching provider in its own task, merges results into [`AggregatedSearchResults`] as they
+//! arrive and stops a provider once its latency budget is spent or the query changes, keeping
+//! whatever it produced so far.
+//!
+//! Providers that need ECS resources to answer can still register in
+//! [`LocalSearchProviders`] and reply to [`SearchRequested`] events instead.
+//!
+//! [`AggregatedSearchResults`]: crate::components::AggregatedSearchResults
+//! [`LocalSearchProviders`]: crate::types::LocalSearchProviders
+//! [`SearchRequested`]: crate::events::SearchRequested
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::Stream;
+
+use crate::types::{SearchId, SearchResult};
+
+/// Budget of providers that do not choose their own
+pub const DEFAULT_PROVIDER_BUDGET: Duration = Duration::from_millis(500);
+
+/// Results produced by a provider, in the order they become available
+pub type SearchResultStream = Pin<Box<dyn Stream<Item = SearchResult> + Send>>;
+
+/// A source of launcher search results
+pub trait SearchProvider: Send + Sync + 'static {
+    /// Unique provider id, used as the results' `plugin_id`
+    fn id(&self) -> &str;
+
+    /// Prefix that selects this provider, such as `gh `
+    ///
+    /// Providers with a trigger only run for queries starting with it and receive the query
+    /// without the prefix. Providers without one run for every query.
+    fn trigger(&self) -> Option<&str> {
+        None
+    }
+
+    /// How long the provider may take before it is cut off
+    fn budget(&self) -> Duration {
+        DEFAULT_PROVIDER_BUDGET
+    }
+
+    /// Start searching for `query`
+    ///
+    /// Must return without doing the search itself: the work belongs inside the stream, for
+    /// example with `stream::once_future`, where the budget can cut it off. The stream is
+    /// polled on the async compute pool. It is dropped when the budget runs out
+    /// or the query changes, and [`SearchContext::is_cancelled`] turns true, so long-running
+    /// work outside the stream can stop as well.
+    fn search(&self, query: &str, ctx: SearchContext) -> SearchResultStream;
+}
+
+/// Information passed to a provider for one search
+#[derive(Debug, Clone)]
+pub struct SearchContext {
+    pub search_id: SearchId,
+    /// The full query, including any trigger prefix
+    pub raw_query: String,
+    pub max_results: usize,
+    pub cancellation: CancellationToken,
+}
+
+impl SearchContext {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
+
+/// Shared flag telling a provider its search is no longer wanted
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Registered search providers
+#[derive(Resource, Default)]
+pub struct SearchProviders {
+    providers: Vec<Arc<dyn SearchProvider>>,
+}
+
+impl SearchProviders {
+    /// Register a provider, replacing any provider with the same id
+    pub fn register(&mut self, provider: impl SearchProvider) {
+        self.providers.retain(|p| p.id() != provider.id());
+        self.providers.push(Arc::new(provider));
+    }
+
+    pub fn get(&self, provider_id: &str) -> Option<&Arc<dyn SearchProvider>> {
+        self.providers.iter().find(|p| p.id() == provider_id)
+    }
+
+    pub fn contains(&self, provider_id: &str) -> bool {
+        self.get(provider_id).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn SearchProvider>> {
+        self.providers.iter()
+    }
+
+    /// Providers that should run for `query`, with the query each of them receives
+    pub fn matching<'a>(
+        &'a self,
+        query: &'a str,
+    ) -> impl Iterator<Item = (&'a Arc<dyn SearchProvider>, &'a str)> {
+        self.providers.iter().filter_map(move |provider| {
+            provider_query(provider.trigger(), query).map(|query| (provider, query))
+        })
+    }
+}
+
+/// The query a provider with `trigger` receives, or `None` if it should not run
+///
+/// Triggers match case-insensitively; a query consisting only of the trigger yields an empty
+/// query so the provider can show suggestions.
+pub fn provider_query<'a>(trigger: Option<&str>, query: &'a str) -> Option<&'a str> {
+    let Some(trigger) = trigger else {
+        return Some(query);
+    };
+    if let Some(head) = query.get(..trigger.len())
+        && head.eq_ignore_ascii_case(trigger)
+    {
+        return Some(query[trigger.len()..].trim_start());
+    }
+    // Queries are trimmed, so `gh ` has to match a bare `gh` as well
+    let bare = trigger.trim_end();
+    (bare != trigger && query.eq_ignore_ascii_case(bare)).then_some("")
+}
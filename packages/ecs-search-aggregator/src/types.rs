@@ -16,6 +16,30 @@ pub struct SearchResult {
     pub icon: Option<String>,
     pub score: f32,
     pub plugin_id: String,
+    /// Title characters matched by the query, for providers that match fuzzily
+    #[serde(default)]
+    pub title_matches: Vec<MatchRange>,
+}
+
+/// Half-open range of matched characters, in `char` indices of the candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl MatchRange {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
 }
 
 /// Main resource for managing search aggregation
@@ -70,6 +94,16 @@ impl ActiveSearch {
         self.results.extend(results);
     }
 
+    /// Add results from a plugin that has not finished yet
+    pub fn stream_results(&mut self, results: Vec<SearchResult>) {
+        self.results.extend(results);
+    }
+
+    /// Whether a plugin has finished or failed
+    pub fn is_settled(&self, plugin_id: &str) -> bool {
+        self.completed_plugins.contains(plugin_id) || self.failed_plugins.contains_key(plugin_id)
+    }
+
     pub fn mark_failed(&mut self, plugin_id: String, error: String) {
         self.failed_plugins.insert(plugin_id, error);
     }
@@ -144,6 +178,27 @@ pub enum SearchError {
     PluginSearchFailed(String, String), // plugin_id, error_message
 }
 
+/// In-process search providers that answer searches themselves
+///
+/// Providers registered here are included in every search alongside service-bridge plugins.
+/// Instead of receiving a message, they read [`SearchRequested`](crate::events::SearchRequested)
+/// and reply with [`SearchResultReceived`](crate::events::SearchResultReceived) (or
+/// [`SearchFailed`](crate::events::SearchFailed)) under their provider id.
+#[derive(Resource, Debug, Default)]
+pub struct LocalSearchProviders {
+    pub provider_ids: HashSet<String>,
+}
+
+impl LocalSearchProviders {
+    pub fn register(&mut self, provider_id: impl Into<String>) {
+        self.provider_ids.insert(provider_id.into());
+    }
+
+    pub fn contains(&self, provider_id: &str) -> bool {
+        self.provider_ids.contains(provider_id)
+    }
+}
+
 /// Resource to track the current search query
 #[derive(Resource, Debug, Clone, Default)]
 pub struct CurrentQuery(pub String);
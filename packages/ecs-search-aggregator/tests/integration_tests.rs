@@ -29,6 +29,7 @@ mod tests {
             icon: Some("test_icon".to_string()),
             score: 0.9,
             plugin_id: "test_plugin".to_string(),
+            title_matches: Vec::new(),
         };
 
         assert_eq!(result.title, "Test Result");
@@ -51,4 +52,147 @@ mod tests {
             assert!(SearchAggregatorManager::validate_query("a", &config).is_err());
         }
     }
+
+    struct StaticProvider {
+        id: &'static str,
+        trigger: Option<&'static str>,
+        titles: Vec<&'static str>,
+    }
+
+    impl SearchProvider for StaticProvider {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn trigger(&self) -> Option<&str> {
+            self.trigger
+        }
+
+        fn search(&self, _query: &str, _ctx: SearchContext) -> SearchResultStream {
+            let results: Vec<SearchResult> = self
+                .titles
+                .iter()
+                .map(|title| SearchResult {
+                    title: title.to_string(),
+                    description: String::new(),
+                    action: format!("static_{title}"),
+                    icon: None,
+                    score: 0.5,
+                    plugin_id: self.id.to_string(),
+                    title_matches: Vec::new(),
+                })
+                .collect();
+            Box::pin(bevy::tasks::futures_lite::stream::iter(results))
+        }
+    }
+
+    #[test]
+    fn test_provider_trigger_matching() {
+        use action_items_ecs_search_aggregator::provider::provider_query;
+
+        assert_eq!(provider_query(None, "safari"), Some("safari"));
+        assert_eq!(provider_query(Some("gh "), "gh bevy"), Some("bevy"));
+        assert_eq!(provider_query(Some("gh "), "GH  bevy"), Some("bevy"));
+        assert_eq!(provider_query(Some("gh "), "gh"), Some(""));
+        assert_eq!(provider_query(Some("gh "), "ghost"), None);
+        assert_eq!(provider_query(Some("gh "), "g"), None);
+    }
+
+    #[test]
+    fn test_search_providers_registry() {
+        let mut providers = SearchProviders::default();
+        providers.register(StaticProvider {
+            id: "everywhere",
+            trigger: None,
+            titles: vec!["a"],
+        });
+        providers.register(StaticProvider {
+            id: "github",
+            trigger: Some("gh "),
+            titles: vec!["b"],
+        });
+        // Registering the same id again replaces the provider
+        providers.register(StaticProvider {
+            id: "everywhere",
+            trigger: None,
+            titles: vec!["c", "d"],
+        });
+
+        assert_eq!(providers.iter().count(), 2);
+        assert!(providers.contains("github"));
+        assert!(!providers.contains("missing"));
+
+        let matching: Vec<(&str, &str)> = providers
+            .matching("gh issues")
+            .map(|(provider, query)| (provider.id(), query))
+            .collect();
+        assert_eq!(matching, vec![("github", "issues"), ("everywhere", "gh issues")]);
+
+        let matching: Vec<&str> = providers
+            .matching("notes")
+            .map(|(provider, _)| provider.id())
+            .collect();
+        assert_eq!(matching, vec!["everywhere"]);
+    }
+
+    #[test]
+    fn test_provider_stream_yields_results() {
+        use bevy::tasks::block_on;
+        use bevy::tasks::futures_lite::StreamExt;
+
+        let provider = StaticProvider {
+            id: "static",
+            trigger: None,
+            titles: vec!["first", "second"],
+        };
+        let context = SearchContext {
+            search_id: SearchId::new_v4(),
+            raw_query: "query".to_string(),
+            max_results: 10,
+            cancellation: CancellationToken::default(),
+        };
+        assert!(!context.is_cancelled());
+
+        let results: Vec<SearchResult> = block_on(provider.search("query", context).collect());
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_streamed_results_do_not_settle_plugin() {
+        let mut search = ActiveSearch::new(
+            "query".to_string(),
+            SearchId::new_v4(),
+            ["fast".to_string(), "slow".to_string()].into(),
+        );
+        let result = SearchResult {
+            title: "Partial".to_string(),
+            description: String::new(),
+            action: "partial".to_string(),
+            icon: None,
+            score: 0.5,
+            plugin_id: "slow".to_string(),
+            title_matches: Vec::new(),
+        };
+
+        search.stream_results(vec![result]);
+        assert!(!search.is_settled("slow"));
+        assert_eq!(search.results.len(), 1);
+
+        search.add_results("fast".to_string(), Vec::new());
+        search.mark_failed("slow".to_string(), "Timeout".to_string());
+        assert!(search.is_settled("fast"));
+        assert!(search.is_settled("slow"));
+        assert!(search.is_complete());
+        // Partial results survive the provider timing out
+        assert_eq!(search.results.len(), 1);
+    }
+
+    #[test]
+    fn test_cancellation_token_is_shared() {
+        let token = CancellationToken::default();
+        let clone = token.clone();
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
 }
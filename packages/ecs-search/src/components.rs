@@ -6,6 +6,7 @@ use bevy::prelude::*;
 use bevy::tasks::Task;
 use serde::{Deserialize, Serialize};
 
+use crate::fuzzy::MatchRange;
 use crate::scoring::SearchScore;
 use crate::systems::filtering::FilterCategory;
 
@@ -27,6 +28,9 @@ pub struct SearchResult {
     pub score_details: SearchScore,
     /// Filter categories this result belongs to
     pub categories: Vec<FilterCategory>,
+    /// Character ranges of the title matched by the query, for highlighting
+    #[serde(default)]
+    pub title_matches: Vec<MatchRange>,
 }
 
 impl SearchResult {
@@ -46,6 +50,7 @@ impl SearchResult {
             score,
             score_details: SearchScore::default(),
             categories: Vec::new(),
+            title_matches: Vec::new(),
         }
     }
 
@@ -55,6 +60,12 @@ impl SearchResult {
         self
     }
 
+    /// Attach the title character ranges matched by the query
+    pub fn with_title_matches(mut self, title_matches: Vec<MatchRange>) -> Self {
+        self.title_matches = title_matches;
+        self
+    }
+
     /// Create a new search result with full score details
     pub fn with_score_details(mut self, score_details: SearchScore) -> Self {
         self.score_details = score_details;
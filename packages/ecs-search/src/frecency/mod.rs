@@ -0,0 +1,59 @@
+//! Frecency ranking from launcher executions
+//!
+//! Every successful [`ActionExecuteCompleted`](ecs_launcher::ActionExecuteCompleted) bumps the
+//! launched item's score, and the score of the item for each prefix of the query that was typed
+//! when the matching [`ActionExecuteRequested`](ecs_launcher::ActionExecuteRequested) was sent.
+//! Scores decay exponentially with a configurable half-life and are persisted
+//! in SurrealDB through [`DatabaseService`](action_items_ecs_surrealdb::DatabaseService), so
+//! frequently and recently used items rise in search results across restarts.
+
+pub mod persistence;
+pub mod store;
+pub mod systems;
+
+use action_items_ecs_surrealdb::poll_persistence_tasks;
+use bevy::prelude::*;
+
+pub use store::{DeferredChange, FrecencyConfig, FrecencyEntry, FrecencyStore};
+pub use systems::{FrecencyForgetRequested, FrecencyResetRequested, LaunchQueries};
+
+use self::systems::*;
+
+/// Plugin recording launches and feeding frecency into search ranking
+///
+/// Persistence starts once `DatabasePlugin` has inserted the `DatabaseService`; without it,
+/// history is kept in memory for the session only.
+#[derive(Default)]
+pub struct FrecencyPlugin {
+    pub config: FrecencyConfig,
+}
+
+impl FrecencyPlugin {
+    pub fn new(config: FrecencyConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for FrecencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FrecencyStore::new(self.config.clone()))
+            .init_resource::<LaunchQueries>()
+            .add_event::<ecs_launcher::ActionExecuteRequested>()
+            .add_event::<ecs_launcher::ActionExecuteCompleted>()
+            .add_event::<FrecencyForgetRequested>()
+            .add_event::<FrecencyResetRequested>()
+            .add_systems(
+                Update,
+                (
+                    load_frecency_store,
+                    snapshot_launch_queries,
+                    record_action_launches,
+                    handle_frecency_forget_requests,
+                    poll_persistence_tasks::<FrecencyStore>,
+                )
+                    .chain(),
+            );
+
+        tracing::info!("FrecencyPlugin initialized");
+    }
+}
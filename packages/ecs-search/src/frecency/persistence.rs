@@ -0,0 +1,131 @@
+//! SurrealDB persistence for frecency records
+//!
+//! Item history lives in `frecency_item` keyed by item id, and query affinity in
+//! `frecency_query` keyed by `[prefix, item_id]`. Writes are upserts of the full record, so a
+//! lost write only costs the increments of that single launch.
+
+use action_items_ecs_surrealdb::{DatabaseError, DatabaseService, LoadState, Persisted};
+use surrealdb::{RecordId, RecordIdKey, Value};
+
+use super::store::{FrecencyEntry, FrecencyStore, QueryFrecencyEntry, RecordedLaunch};
+
+/// Table holding per-item launch history
+pub const FRECENCY_ITEM_TABLE: &str = "frecency_item";
+/// Table holding per-query-prefix launch history
+pub const FRECENCY_QUERY_TABLE: &str = "frecency_query";
+
+/// Definitions of the item and query-prefix frecency tables
+pub const FRECENCY_SCHEMA: &str = r#"
+DEFINE TABLE IF NOT EXISTS frecency_item SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS item_id ON frecency_item TYPE string;
+DEFINE FIELD IF NOT EXISTS score ON frecency_item TYPE float;
+DEFINE FIELD IF NOT EXISTS count ON frecency_item TYPE int;
+DEFINE FIELD IF NOT EXISTS last_used ON frecency_item TYPE float;
+
+DEFINE TABLE IF NOT EXISTS frecency_query SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS prefix ON frecency_query TYPE string;
+DEFINE FIELD IF NOT EXISTS item_id ON frecency_query TYPE string;
+DEFINE FIELD IF NOT EXISTS score ON frecency_query TYPE float;
+DEFINE FIELD IF NOT EXISTS count ON frecency_query TYPE int;
+DEFINE FIELD IF NOT EXISTS last_used ON frecency_query TYPE float;
+DEFINE INDEX IF NOT EXISTS frecency_query_item_idx ON frecency_query COLUMNS item_id;
+"#;
+
+fn item_record_id(item_id: &str) -> RecordId {
+    RecordId::from((FRECENCY_ITEM_TABLE, item_id))
+}
+
+fn query_record_id(prefix: &str, item_id: &str) -> RecordId {
+    // Array keys keep prefixes containing ':' or other separators unambiguous
+    RecordId::from((
+        FRECENCY_QUERY_TABLE,
+        vec![
+            Value::from(RecordIdKey::from(prefix)),
+            Value::from(RecordIdKey::from(item_id)),
+        ],
+    ))
+}
+
+impl Persisted for FrecencyStore {
+    const NAME: &'static str = "frecency history";
+    const SCHEMA: &'static str = FRECENCY_SCHEMA;
+
+    fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    fn set_load_state(&mut self, state: LoadState) {
+        self.load_state = state;
+    }
+}
+
+/// Load all persisted frecency records
+pub async fn load_all(
+    db: &DatabaseService,
+) -> Result<(Vec<FrecencyEntry>, Vec<QueryFrecencyEntry>), DatabaseError> {
+    let items = db.select::<FrecencyEntry>(FRECENCY_ITEM_TABLE).await?;
+    let queries = db
+        .select::<QueryFrecencyEntry>(FRECENCY_QUERY_TABLE)
+        .await?;
+    Ok((items, queries))
+}
+
+/// Upsert every record touched by a launch
+pub async fn save_launch(
+    db: &DatabaseService,
+    launch: &RecordedLaunch,
+) -> Result<(), DatabaseError> {
+    let mut statements = vec![upsert_statement(
+        item_record_id(&launch.item.item_id),
+        &launch.item,
+    )?];
+    for entry in &launch.queries {
+        statements.push(upsert_statement(
+            query_record_id(&entry.prefix, &entry.item_id),
+            entry,
+        )?);
+    }
+    db.query(&statements.join(";\n"))
+        .await?
+        .check()
+        .map_err(query_failed)?;
+    Ok(())
+}
+
+/// Delete an item's history and the query records it was chosen under
+pub async fn delete_item(
+    db: &DatabaseService,
+    item_id: &str,
+    prefixes: &[String],
+) -> Result<(), DatabaseError> {
+    let mut statements = vec![format!("DELETE {}", item_record_id(item_id))];
+    statements.extend(
+        prefixes
+            .iter()
+            .map(|prefix| format!("DELETE {}", query_record_id(prefix, item_id))),
+    );
+    db.query(&statements.join(";\n"))
+        .await?
+        .check()
+        .map_err(query_failed)?;
+    Ok(())
+}
+
+/// Delete all frecency history
+pub async fn delete_all(db: &DatabaseService) -> Result<(), DatabaseError> {
+    let sql = format!("DELETE {FRECENCY_ITEM_TABLE}; DELETE {FRECENCY_QUERY_TABLE};");
+    db.query(&sql).await?.check().map_err(query_failed)?;
+    Ok(())
+}
+
+fn upsert_statement(
+    record_id: RecordId,
+    data: &impl serde::Serialize,
+) -> Result<String, DatabaseError> {
+    let content = serde_json::to_string(data).map_err(query_failed)?;
+    Ok(format!("UPSERT {} CONTENT {}", record_id, content))
+}
+
+fn query_failed(error: impl std::fmt::Display) -> DatabaseError {
+    DatabaseError::QueryFailed(error.to_string())
+}
@@ -0,0 +1,293 @@
+//! In-memory frecency model with exponential decay
+//!
+//! Every launch adds `1.0` to an item's score after decaying the previous score by
+//! `0.5^(elapsed / half_life)`. Storing the score at the time of the last use is enough to
+//! evaluate it at any later time, so records never need to be rewritten just because time passes.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use action_items_ecs_surrealdb::LoadState;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Frecency tuning parameters
+#[derive(Debug, Clone)]
+pub struct FrecencyConfig {
+    /// Time for an item's accumulated score to halve without further use
+    pub half_life: Duration,
+    /// Longest query prefix tracked for query → item affinity
+    pub max_prefix_len: usize,
+    /// Decayed score at which frequency reaches 0.5 on the normalized scale
+    pub frequency_midpoint: f64,
+}
+
+impl Default for FrecencyConfig {
+    fn default() -> Self {
+        Self {
+            half_life: Duration::from_secs(7 * 24 * 60 * 60),
+            max_prefix_len: 8,
+            frequency_midpoint: 5.0,
+        }
+    }
+}
+
+/// Usage history of one item, as persisted in `frecency_item`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrecencyEntry {
+    /// Search item id the history belongs to
+    pub item_id: String,
+    /// Decayed score as of `last_used`
+    pub score: f64,
+    /// Total number of launches
+    pub count: u64,
+    /// Unix timestamp (seconds) of the most recent launch
+    pub last_used: f64,
+}
+
+/// Usage of one item after a particular query prefix, as persisted in `frecency_query`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryFrecencyEntry {
+    /// Lowercased query prefix that was typed before launching
+    pub prefix: String,
+    /// Search item id chosen for the prefix
+    pub item_id: String,
+    /// Decayed score as of `last_used`
+    pub score: f64,
+    /// Total number of launches after this prefix
+    pub count: u64,
+    /// Unix timestamp (seconds) of the most recent launch
+    pub last_used: f64,
+}
+
+/// Entries touched by a single recorded launch, for persistence
+#[derive(Debug, Clone)]
+pub struct RecordedLaunch {
+    pub item: FrecencyEntry,
+    pub queries: Vec<QueryFrecencyEntry>,
+}
+
+/// Change to the history requested before persisted history finished loading
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeferredChange {
+    /// Launch of `item_id` after typing `query`, at Unix time `at`
+    Launch {
+        item_id: String,
+        query: String,
+        at: f64,
+    },
+    /// Forget the history of one item
+    Forget(String),
+    /// Forget all history
+    Reset,
+}
+
+/// Frecency data used to rank search results
+#[derive(Resource, Debug, Default)]
+pub struct FrecencyStore {
+    pub config: FrecencyConfig,
+    pub load_state: LoadState,
+    items: HashMap<String, FrecencyEntry>,
+    queries: HashMap<(String, String), QueryFrecencyEntry>,
+    /// Changes requested before persisted history finished loading, in order
+    deferred: Vec<DeferredChange>,
+}
+
+impl FrecencyStore {
+    pub fn new(config: FrecencyConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Replace the in-memory state with records loaded from the database
+    pub fn load(&mut self, items: Vec<FrecencyEntry>, queries: Vec<QueryFrecencyEntry>) {
+        self.items = items.into_iter().map(|e| (e.item_id.clone(), e)).collect();
+        self.queries = queries
+            .into_iter()
+            .map(|e| ((e.prefix.clone(), e.item_id.clone()), e))
+            .collect();
+        self.load_state = LoadState::Loaded;
+    }
+
+    /// Queue a launch until persisted history is loaded
+    ///
+    /// Recording it right away would upsert scores computed without the stored history and
+    /// overwrite it.
+    pub fn defer(&mut self, item_id: &str, query: &str, now: f64) {
+        self.deferred.push(DeferredChange::Launch {
+            item_id: item_id.to_string(),
+            query: query.to_string(),
+            at: now,
+        });
+    }
+
+    /// Queue forgetting an item until persisted history is loaded
+    ///
+    /// Forgetting it right away would not last: [`FrecencyStore::load`] brings back whatever
+    /// the database still held when it was read.
+    pub fn defer_forget(&mut self, item_id: &str) {
+        self.deferred
+            .push(DeferredChange::Forget(item_id.to_string()));
+    }
+
+    /// Queue a reset until persisted history is loaded; earlier queued changes are dropped
+    pub fn defer_reset(&mut self) {
+        self.deferred.clear();
+        self.deferred.push(DeferredChange::Reset);
+    }
+
+    /// Take changes queued with [`FrecencyStore::defer`], [`FrecencyStore::defer_forget`] and
+    /// [`FrecencyStore::defer_reset`], in the order they were requested
+    pub fn take_deferred(&mut self) -> Vec<DeferredChange> {
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Record a launch of `item_id` after typing `query`
+    pub fn record(&mut self, item_id: &str, query: &str, now: f64) -> RecordedLaunch {
+        let half_life = self.config.half_life.as_secs_f64();
+
+        let item = self
+            .items
+            .entry(item_id.to_string())
+            .or_insert_with(|| FrecencyEntry {
+                item_id: item_id.to_string(),
+                score: 0.0,
+                count: 0,
+                last_used: now,
+            });
+        item.score = decay(item.score, item.last_used, now, half_life) + 1.0;
+        item.count += 1;
+        item.last_used = now;
+        let item = item.clone();
+
+        let mut touched = Vec::new();
+        for prefix in query_prefixes(query, self.config.max_prefix_len) {
+            let entry = self
+                .queries
+                .entry((prefix.clone(), item_id.to_string()))
+                .or_insert_with(|| QueryFrecencyEntry {
+                    prefix,
+                    item_id: item_id.to_string(),
+                    score: 0.0,
+                    count: 0,
+                    last_used: now,
+                });
+            entry.score = decay(entry.score, entry.last_used, now, half_life) + 1.0;
+            entry.count += 1;
+            entry.last_used = now;
+            touched.push(entry.clone());
+        }
+
+        RecordedLaunch {
+            item,
+            queries: touched,
+        }
+    }
+
+    /// Normalized usage frequency (0.0 to 1.0) of an item at `now`
+    pub fn frequency(&self, item_id: &str, now: f64) -> f32 {
+        self.items.get(item_id).map_or(0.0, |e| {
+            let score = decay(
+                e.score,
+                e.last_used,
+                now,
+                self.config.half_life.as_secs_f64(),
+            );
+            saturate(score, self.config.frequency_midpoint)
+        })
+    }
+
+    /// Normalized recency (0.0 to 1.0) of an item at `now`; 1.0 means just launched
+    pub fn recency(&self, item_id: &str, now: f64) -> f32 {
+        self.items.get(item_id).map_or(0.0, |e| {
+            decay(1.0, e.last_used, now, self.config.half_life.as_secs_f64()) as f32
+        })
+    }
+
+    /// How strongly `item_id` has been chosen after typing `query` (0.0 to 1.0)
+    ///
+    /// Uses the longest tracked prefix of the query that has history for the item, so typing
+    /// `fir` benefits from past launches that happened after `fi` or `fire`.
+    pub fn query_affinity(&self, item_id: &str, query: &str, now: f64) -> f32 {
+        let half_life = self.config.half_life.as_secs_f64();
+        query_prefixes(query, self.config.max_prefix_len)
+            .into_iter()
+            .rev()
+            .find_map(|prefix| self.queries.get(&(prefix, item_id.to_string())))
+            .map_or(0.0, |e| {
+                let score = decay(e.score, e.last_used, now, half_life);
+                saturate(score, self.config.frequency_midpoint / 2.0)
+            })
+    }
+
+    /// Drop all history for an item, returning the query prefixes it was recorded under
+    pub fn forget(&mut self, item_id: &str) -> Vec<String> {
+        self.items.remove(item_id);
+        self.deferred.retain(
+            |change| !matches!(change, DeferredChange::Launch { item_id: id, .. } if id == item_id),
+        );
+        let prefixes: Vec<String> = self
+            .queries
+            .keys()
+            .filter(|(_, id)| id == item_id)
+            .map(|(prefix, _)| prefix.clone())
+            .collect();
+        for prefix in &prefixes {
+            self.queries.remove(&(prefix.clone(), item_id.to_string()));
+        }
+        prefixes
+    }
+
+    /// Drop all history
+    pub fn reset(&mut self) {
+        self.items.clear();
+        self.deferred.clear();
+        self.queries.clear();
+    }
+
+    /// History of a single item, if any
+    pub fn entry(&self, item_id: &str) -> Option<&FrecencyEntry> {
+        self.items.get(item_id)
+    }
+
+    /// Number of items with recorded history
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Current time as fractional Unix seconds
+pub fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+/// Decay `score` recorded at `recorded_at` to `now` with the given half-life in seconds
+fn decay(score: f64, recorded_at: f64, now: f64, half_life: f64) -> f64 {
+    let elapsed = (now - recorded_at).max(0.0);
+    if half_life <= 0.0 {
+        return score;
+    }
+    score * 0.5_f64.powf(elapsed / half_life)
+}
+
+/// Map an unbounded score onto 0.0..1.0, reaching 0.5 at `midpoint`
+fn saturate(score: f64, midpoint: f64) -> f32 {
+    (score / (score + midpoint.max(f64::EPSILON))) as f32
+}
+
+/// Lowercased, trimmed prefixes of `query` from one character up to `max_len` characters
+fn query_prefixes(query: &str, max_len: usize) -> Vec<String> {
+    let normalized: Vec<char> = query.trim().to_lowercase().chars().take(max_len).collect();
+    (1..=normalized.len())
+        .map(|len| normalized[..len].iter().collect())
+        .collect()
+}
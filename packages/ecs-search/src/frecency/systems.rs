@@ -0,0 +1,205 @@
+//! Frecency systems: loading, recording launches, and forgetting history
+
+use std::collections::HashMap;
+
+use action_items_ecs_surrealdb::{DatabaseService, LoadState, spawn_write, start_loading};
+use bevy::prelude::*;
+use ecs_launcher::{ActionExecuteCompleted, ActionExecuteRequested};
+
+use super::persistence;
+use super::store::{DeferredChange, FrecencyStore, RecordedLaunch, unix_now};
+use crate::resources::SearchResource;
+
+/// Forget all frecency history of a single item
+#[derive(Event, Debug, Clone)]
+pub struct FrecencyForgetRequested {
+    pub item_id: String,
+}
+
+impl FrecencyForgetRequested {
+    pub fn new(item_id: impl Into<String>) -> Self {
+        Self {
+            item_id: item_id.into(),
+        }
+    }
+}
+
+/// Forget all frecency history
+#[derive(Event, Debug, Clone, Default)]
+pub struct FrecencyResetRequested;
+
+/// Query that was typed when each in-flight action was started, keyed by action id
+///
+/// Launches are credited to this query rather than the one current at completion, which may
+/// already have been edited or cleared by then.
+#[derive(Resource, Debug, Default)]
+pub struct LaunchQueries(HashMap<String, String>);
+
+/// Apply the schema and load persisted history once the database is available
+pub fn load_frecency_store(
+    mut commands: Commands,
+    mut store: ResMut<FrecencyStore>,
+    db_service: Option<Res<DatabaseService>>,
+) {
+    start_loading(
+        &mut commands,
+        store.as_mut(),
+        db_service.as_deref(),
+        |db| async move { persistence::load_all(&db).await },
+        |world, (items, queries)| {
+            let Some(mut store) = world.get_resource_mut::<FrecencyStore>() else {
+                return;
+            };
+            tracing::info!(
+                "Loaded frecency history for {} items ({} query prefixes)",
+                items.len(),
+                queries.len()
+            );
+            store.load(items, queries);
+
+            for change in store.take_deferred() {
+                let mut store = world.resource_mut::<FrecencyStore>();
+                match change {
+                    DeferredChange::Launch { item_id, query, at } => {
+                        let launch = store.record(&item_id, &query, at);
+                        spawn_save_launch(world, launch);
+                    },
+                    DeferredChange::Forget(item_id) => {
+                        let prefixes = store.forget(&item_id);
+                        spawn_delete_item(world, item_id, prefixes);
+                    },
+                    DeferredChange::Reset => {
+                        store.reset();
+                        spawn_delete_all(world);
+                    },
+                }
+            }
+        },
+    );
+}
+
+/// Remember the query each action was started from
+pub fn snapshot_launch_queries(
+    mut events: EventReader<ActionExecuteRequested>,
+    mut launch_queries: ResMut<LaunchQueries>,
+    search: Res<SearchResource>,
+) {
+    for event in events.read() {
+        launch_queries.0.insert(event.action_id.clone(), search.current_query.clone());
+    }
+}
+
+/// Record successful launches and persist the updated scores
+pub fn record_action_launches(
+    mut commands: Commands,
+    mut events: EventReader<ActionExecuteCompleted>,
+    mut store: ResMut<FrecencyStore>,
+    mut launch_queries: ResMut<LaunchQueries>,
+    mut search: ResMut<SearchResource>,
+    db_service: Option<Res<DatabaseService>>,
+) {
+    let mut recorded = false;
+    for event in events.read() {
+        // Launches that were never requested through the launcher carry no query
+        let query = launch_queries.0.remove(&event.action_id).unwrap_or_default();
+        if !event.success {
+            continue;
+        }
+
+        // Without a database the history is kept in memory only; with one, launches wait
+        // until the stored history is loaded so the upserts build on it.
+        let now = unix_now();
+        match (db_service.is_some(), store.load_state) {
+            (true, LoadState::NotLoaded | LoadState::Loading) => {
+                store.defer(&event.action_id, &query, now);
+                continue;
+            },
+            (true, LoadState::Loaded) => {
+                let launch = store.record(&event.action_id, &query, now);
+                commands.queue(move |world: &mut World| spawn_save_launch(world, launch));
+            },
+            _ => {
+                store.record(&event.action_id, &query, now);
+            },
+        }
+        tracing::debug!("Recorded launch of '{}'", event.action_id);
+        recorded = true;
+    }
+
+    // Cached rankings predate the new boosts
+    if recorded {
+        search.clear_cache();
+    }
+}
+
+/// Persist a recorded launch in the background
+fn spawn_save_launch(world: &mut World, launch: RecordedLaunch) {
+    spawn_write::<FrecencyStore, _>(world, |db| async move {
+        if let Err(e) = persistence::save_launch(&db, &launch).await {
+            tracing::warn!(
+                "Failed to persist frecency for '{}': {}",
+                launch.item.item_id,
+                e
+            );
+        }
+    });
+}
+
+/// Delete the stored history of one item in the background
+fn spawn_delete_item(world: &mut World, item_id: String, prefixes: Vec<String>) {
+    spawn_write::<FrecencyStore, _>(world, |db| async move {
+        if let Err(e) = persistence::delete_item(&db, &item_id, &prefixes).await {
+            tracing::warn!("Failed to delete frecency history of '{}': {}", item_id, e);
+        }
+    });
+}
+
+/// Delete all stored history in the background
+fn spawn_delete_all(world: &mut World) {
+    spawn_write::<FrecencyStore, _>(world, |db| async move {
+        if let Err(e) = persistence::delete_all(&db).await {
+            tracing::warn!("Failed to delete frecency history: {}", e);
+        }
+    });
+}
+
+/// Handle requests to forget one item or reset all history
+pub fn handle_frecency_forget_requests(
+    mut commands: Commands,
+    mut forget_events: EventReader<FrecencyForgetRequested>,
+    mut reset_events: EventReader<FrecencyResetRequested>,
+    mut store: ResMut<FrecencyStore>,
+    mut search: ResMut<SearchResource>,
+    db_service: Option<Res<DatabaseService>>,
+) {
+    let reset = reset_events.read().count() > 0;
+    let forgotten: Vec<String> = forget_events
+        .read()
+        .map(|event| event.item_id.clone())
+        .collect();
+    if !reset && forgotten.is_empty() {
+        return;
+    }
+
+    // With a database, changes wait until the stored history is loaded; loading would
+    // otherwise bring back what was forgotten in the meantime.
+    let loading = db_service.is_some() && store.load_state != LoadState::Loaded;
+    for item_id in forgotten {
+        if loading {
+            store.defer_forget(&item_id);
+            continue;
+        }
+        let prefixes = store.forget(&item_id);
+        commands.queue(move |world: &mut World| spawn_delete_item(world, item_id, prefixes));
+    }
+    if reset {
+        if loading {
+            store.defer_reset();
+        } else {
+            store.reset();
+            commands.queue(spawn_delete_all);
+        }
+    }
+    // Cached rankings include the forgotten boosts
+    search.clear_cache();
+}
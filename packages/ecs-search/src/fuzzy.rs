@@ -0,0 +1,268 @@
+//! Fuzzy matching with match-highlight ranges
+//!
+//! Implements a Smith-Waterman style local alignment of the query against a candidate string,
+//! in the spirit of fzf's v2 algorithm. Query characters must appear in order, but may be
+//! separated by gaps; the scorer rewards matches at word starts, camelCase humps, and runs of
+//! consecutive characters so that `vsc` ranks "Visual Studio Code" above "Preview Script".
+
+pub use action_items_ecs_search_aggregator::MatchRange;
+
+/// Score for every matched character
+const SCORE_MATCH: i32 = 16;
+/// Penalty for opening a gap between matched characters
+const SCORE_GAP_START: i32 = -3;
+/// Penalty for each additional character inside a gap
+const SCORE_GAP_EXTENSION: i32 = -1;
+/// Bonus for matching the first character of a word (after whitespace or punctuation)
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+/// Bonus for matching a camelCase hump or a letter-to-digit transition
+const BONUS_CAMEL: i32 = BONUS_BOUNDARY - 1;
+/// Minimum bonus for a character directly following the previous match
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+/// Multiplier applied to the bonus of the first query character
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// Result of a successful fuzzy match
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Normalized score (0.0 to 1.0), comparable across candidates and query lengths
+    pub score: f32,
+    /// Raw alignment score before normalization
+    pub raw_score: i32,
+    /// Matched characters collapsed into contiguous ranges
+    pub ranges: Vec<MatchRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Delimiter,
+    Lower,
+    Upper,
+    Digit,
+    Other,
+}
+
+impl CharClass {
+    fn of(ch: char) -> Self {
+        if ch.is_lowercase() {
+            CharClass::Lower
+        } else if ch.is_uppercase() {
+            CharClass::Upper
+        } else if ch.is_numeric() {
+            CharClass::Digit
+        } else if ch.is_whitespace() || matches!(ch, '/' | '\\' | '-' | '_' | '.' | ':' | ',') {
+            CharClass::Delimiter
+        } else if ch.is_alphabetic() {
+            CharClass::Lower
+        } else {
+            CharClass::Other
+        }
+    }
+
+    fn is_word(self) -> bool {
+        matches!(self, CharClass::Lower | CharClass::Upper | CharClass::Digit)
+    }
+}
+
+/// Positional bonus for matching a character of class `current` that follows `previous`
+fn position_bonus(previous: CharClass, current: CharClass) -> i32 {
+    if !current.is_word() {
+        return 0;
+    }
+    match (previous, current) {
+        (CharClass::Delimiter | CharClass::Other, _) => BONUS_BOUNDARY,
+        (CharClass::Lower, CharClass::Upper) => BONUS_CAMEL,
+        (CharClass::Lower | CharClass::Upper, CharClass::Digit) => BONUS_CAMEL,
+        _ => 0,
+    }
+}
+
+/// Source of the best score in a DP cell, used for backtracking
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    None,
+    Start,
+    Consecutive,
+    Gap(usize),
+}
+
+/// Match `query` against `candidate`, returning `None` when the query is not a subsequence
+///
+/// Matching is case-insensitive. Whitespace in the query is ignored so that `vs code` behaves
+/// like `vscode`.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return None;
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let lower: Vec<char> =
+        chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+
+    if !is_subsequence(&query, &lower) {
+        return None;
+    }
+
+    let n = query.len();
+    let m = lower.len();
+
+    let mut bonus = Vec::with_capacity(m);
+    let mut previous = CharClass::Delimiter;
+    for ch in &chars {
+        let current = CharClass::of(*ch);
+        bonus.push(position_bonus(previous, current));
+        previous = current;
+    }
+
+    // scores[i][j]: best score aligning query[..=i] with query[i] matched at candidate[j]
+    let mut scores = vec![vec![i32::MIN; m]; n];
+    let mut steps = vec![vec![Step::None; m]; n];
+
+    for j in 0..m {
+        if lower[j] == query[0] {
+            scores[0][j] = SCORE_MATCH + bonus[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+            steps[0][j] = Step::Start;
+        }
+    }
+
+    for i in 1..n {
+        // Best gapped predecessor seen so far: (score including gap penalty, column)
+        let mut gap_best: Option<(i32, usize)> = None;
+
+        for j in i..m {
+            if j >= 2 {
+                let opened = scores[i - 1][j - 2];
+                let extended = gap_best.map(|(s, k)| (s + SCORE_GAP_EXTENSION, k));
+                let opened = (opened != i32::MIN).then_some((opened + SCORE_GAP_START, j - 2));
+                gap_best = match (extended, opened) {
+                    (Some(e), Some(o)) => Some(if o.0 >= e.0 { o } else { e }),
+                    (e, o) => e.or(o),
+                };
+            }
+
+            if lower[j] != query[i] {
+                continue;
+            }
+
+            let mut best = i32::MIN;
+            let mut step = Step::None;
+
+            let diagonal = scores[i - 1][j - 1];
+            if diagonal != i32::MIN {
+                best = diagonal + SCORE_MATCH + bonus[j].max(BONUS_CONSECUTIVE);
+                step = Step::Consecutive;
+            }
+
+            if let Some((gap_score, k)) = gap_best {
+                let gapped = gap_score + SCORE_MATCH + bonus[j];
+                if gapped > best {
+                    best = gapped;
+                    step = Step::Gap(k);
+                }
+            }
+
+            scores[i][j] = best;
+            steps[i][j] = step;
+        }
+    }
+
+    let (end, raw_score) = scores[n - 1]
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| **s != i32::MIN)
+        .max_by_key(|(j, s)| (**s, std::cmp::Reverse(*j)))
+        .map(|(j, s)| (j, *s))?;
+
+    let positions = backtrack(&steps, n, end)?;
+
+    Some(FuzzyMatch {
+        score: normalize(raw_score, n, m),
+        raw_score,
+        ranges: positions_to_ranges(&positions),
+    })
+}
+
+/// Best raw score attainable for a query of `query_len` characters
+fn perfect_score(query_len: usize) -> i32 {
+    let first = SCORE_MATCH + BONUS_BOUNDARY * BONUS_FIRST_CHAR_MULTIPLIER;
+    let rest = (SCORE_MATCH + BONUS_BOUNDARY.max(BONUS_CONSECUTIVE)) * (query_len as i32 - 1);
+    first + rest
+}
+
+/// Map a raw score onto 0.0..=1.0, slightly favouring shorter candidates
+fn normalize(raw_score: i32, query_len: usize, candidate_len: usize) -> f32 {
+    let base = (raw_score as f32 / perfect_score(query_len) as f32).clamp(0.0, 1.0);
+    let coverage = query_len as f32 / candidate_len.max(1) as f32;
+    (base * 0.9 + coverage.min(1.0) * 0.1).clamp(0.0, 1.0)
+}
+
+fn is_subsequence(query: &[char], candidate: &[char]) -> bool {
+    let mut remaining = query.iter().peekable();
+    for ch in candidate {
+        if remaining.peek() == Some(&ch) {
+            remaining.next();
+        }
+    }
+    remaining.peek().is_none()
+}
+
+fn backtrack(steps: &[Vec<Step>], query_len: usize, end: usize) -> Option<Vec<usize>> {
+    let mut positions = vec![0; query_len];
+    let mut j = end;
+
+    for i in (0..query_len).rev() {
+        positions[i] = j;
+        match steps[i][j] {
+            Step::Start if i == 0 => {},
+            Step::Consecutive => j -= 1,
+            Step::Gap(k) => j = k,
+            _ => return None,
+        }
+    }
+
+    Some(positions)
+}
+
+fn positions_to_ranges(positions: &[usize]) -> Vec<MatchRange> {
+    let mut ranges: Vec<MatchRange> = Vec::new();
+    for &pos in positions {
+        match ranges.last_mut() {
+            Some(range) if range.end == pos => range.end += 1,
+            _ => ranges.push(MatchRange::new(pos, pos + 1)),
+        }
+    }
+    ranges
+}
+
+/// Split `text` into `(segment, highlighted)` pairs according to `ranges`
+///
+/// Ranges are in `char` indices as produced by [`fuzzy_match`]; out-of-bounds ranges are
+/// clamped so stale highlights never panic while rendering.
+pub fn highlight_segments(text: &str, ranges: &[MatchRange]) -> Vec<(String, bool)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut segments = Vec::new();
+    let mut cursor = 0;
+
+    for range in ranges {
+        let start = range.start.min(chars.len()).max(cursor);
+        let end = range.end.min(chars.len());
+        if start >= end {
+            continue;
+        }
+        if start > cursor {
+            segments.push((chars[cursor..start].iter().collect(), false));
+        }
+        segments.push((chars[start..end].iter().collect(), true));
+        cursor = end;
+    }
+
+    if cursor < chars.len() {
+        segments.push((chars[cursor..].iter().collect(), false));
+    }
+    segments
+}
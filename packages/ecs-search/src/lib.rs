@@ -8,6 +8,7 @@
 //! - Event-driven search requests and responses
 //! - Async search task management
 //! - Result scoring and filtering
+//! - Fuzzy matching with highlight ranges
 //! - Search result caching with TTL
 //! - Configurable search parameters
 //!
@@ -35,6 +36,8 @@
 
 pub mod components;
 pub mod events;
+pub mod frecency;
+pub mod fuzzy;
 pub mod plugin;
 pub mod resources;
 pub mod scoring;
@@ -51,5 +54,9 @@ pub use ui::SearchUIPlugin;
 pub use events::*;
 pub use resources::*;
 pub use components::*;
+pub use frecency::{
+    FrecencyConfig, FrecencyForgetRequested, FrecencyPlugin, FrecencyResetRequested, FrecencyStore,
+};
+pub use fuzzy::{FuzzyMatch, MatchRange, fuzzy_match, highlight_segments};
 pub use scoring::{SearchScore, ScoreTier, ConfidenceLevel};
 pub use systems::filtering::{FilterCategory, FilterState};
@@ -4,9 +4,14 @@
 
 pub mod filtering;
 
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 
 use action_items_core::search::{SearchIndex, SearchItem, SearchItemType};
+use crate::frecency::FrecencyStore;
+use crate::frecency::store::unix_now;
+use crate::fuzzy::{FuzzyMatch, fuzzy_match};
 use crate::{components::*, events::*, resources::*, scoring::SearchScore};
 
 /// Process search requests using SearchIndex
@@ -15,6 +20,7 @@ pub fn process_search_requests(
     mut resource: ResMut<SearchResource>,
     mut completed_events: EventWriter<SearchCompleted>,
     search_index: Res<SearchIndex>,
+    frecency: Option<Res<FrecencyStore>>,
 ) {
     for event in events.read() {
         tracing::debug!("Processing search request: {}", event.query);
@@ -36,16 +42,26 @@ pub fn process_search_requests(
         // Update current query
         resource.current_query = event.query.clone();
 
-        // Perform REAL search using SearchIndex
-        let search_items = search_index.search(&event.query);
-        
-        // Convert SearchItem -> SearchResult  
-        let results: Vec<SearchResult> = search_items
+        // Perform REAL search using SearchIndex, merged with fuzzy matches over all items
+        let search_items = rank_search_items(&search_index, &event.query);
+
+        // Convert SearchItem -> SearchResult, then rerank the best text matches by their
+        // composite score so usage history can lift a slightly weaker match
+        let now = unix_now();
+        let mut results: Vec<SearchResult> = search_items
             .into_iter()
-            .take(event.max_results)
-            .filter(|item| item.score >= resource.config.score_threshold)
-            .map(convert_search_item_to_result)
+            .filter(|(item, _)| item.score / 100.0 >= resource.config.score_threshold)
+            .take(event.max_results.saturating_mul(RERANK_CANDIDATE_FACTOR))
+            .map(|(item, fuzzy)| {
+                let usage =
+                    UsageSignals::for_item(frecency.as_deref(), &item.id, &event.query, now);
+                convert_search_item_to_result(item, fuzzy, usage)
+            })
             .collect();
+        results.sort_by(|a, b| {
+            b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(event.max_results);
 
         let duration_ms = 0; // Synchronous search, instant
 
@@ -102,8 +118,117 @@ pub fn handle_query_changes(
     }
 }
 
+/// How many text-ranked candidates per requested result are reranked with usage signals
+const RERANK_CANDIDATE_FACTOR: usize = 4;
+
+/// Usage-derived score components for a single result
+#[derive(Debug, Clone, Copy)]
+struct UsageSignals {
+    frequency: f32,
+    recency: f32,
+    relevance: f32,
+}
+
+impl UsageSignals {
+    /// Neutral signals used when no frecency store is installed
+    const NEUTRAL: Self = Self {
+        frequency: 0.5,
+        recency: 0.5,
+        relevance: 0.7,
+    };
+
+    fn for_item(store: Option<&FrecencyStore>, item_id: &str, query: &str, now: f64) -> Self {
+        let Some(store) = store else {
+            return Self::NEUTRAL;
+        };
+        Self {
+            frequency: store.frequency(item_id, now),
+            recency: store.recency(item_id, now),
+            // Items previously chosen for this query prefix are more relevant to it
+            relevance: 0.7 + 0.3 * store.query_affinity(item_id, query, now),
+        }
+    }
+}
+
+/// Keyword fuzzy matches count for less than title matches
+const KEYWORD_FUZZY_WEIGHT: f32 = 0.6;
+
+/// Minimum normalized fuzzy score for items the inverted index did not return, so scattered
+/// subsequence hits do not flood the result list
+const MIN_FUZZY_SCORE: f32 = 0.35;
+
+/// Most items fuzzy matched per query beyond the inverted-index hits
+const FUZZY_CANDIDATE_LIMIT: usize = 512;
+
+/// Rank index items for a query by combining inverted-index and fuzzy title scores
+///
+/// The inverted index covers whole-word, prefix and infix hits on every field; the fuzzy pass
+/// adds subsequence and acronym matches on titles and keywords (e.g. `vsc` for
+/// "Visual Studio Code") and provides the ranges used for highlighting. The fuzzy pass only
+/// looks at [`SearchIndex::fuzzy_candidates`], never the whole index. Scores are on the
+/// index's 0-100 scale.
+fn rank_search_items(index: &SearchIndex, query: &str) -> Vec<(SearchItem, Option<FuzzyMatch>)> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranked: HashMap<String, (SearchItem, Option<FuzzyMatch>)> = index
+        .search(query)
+        .into_iter()
+        .map(|item| {
+            let fuzzy = fuzzy_match(query, &item.title);
+            (item.id.clone(), (item, fuzzy))
+        })
+        .collect();
+
+    for item in index.fuzzy_candidates(query, FUZZY_CANDIDATE_LIMIT) {
+        if ranked.contains_key(&item.id) {
+            continue;
+        }
+
+        let title_match = fuzzy_match(query, &item.title);
+        let keyword_score = item
+            .keywords
+            .iter()
+            .filter_map(|keyword| fuzzy_match(query, keyword))
+            .map(|m| m.score * KEYWORD_FUZZY_WEIGHT)
+            .fold(0.0_f32, f32::max);
+        let title_score = title_match.as_ref().map_or(0.0, |m| m.score);
+
+        let best = title_score.max(keyword_score);
+        if best < MIN_FUZZY_SCORE {
+            continue;
+        }
+        let score = best * 100.0;
+
+        let mut result = item.clone();
+        result.score = score;
+        ranked.insert(result.id.clone(), (result, title_match));
+    }
+
+    // Items found by the index keep their score unless the fuzzy title match is stronger
+    for (item, fuzzy) in ranked.values_mut() {
+        if let Some(fuzzy) = fuzzy {
+            item.score = item.score.max(fuzzy.score * 100.0);
+        }
+    }
+
+    let mut results: Vec<(SearchItem, Option<FuzzyMatch>)> = ranked.into_values().collect();
+    results.sort_by(|(a, _), (b, _)| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    results
+}
+
 /// Convert SearchItem to SearchResult
-fn convert_search_item_to_result(item: SearchItem) -> SearchResult {
+fn convert_search_item_to_result(
+    item: SearchItem,
+    fuzzy: Option<FuzzyMatch>,
+    usage: UsageSignals,
+) -> SearchResult {
     use crate::systems::filtering::FilterCategory;
     
     // Map icon path to string, or use emoji fallback
@@ -132,10 +257,10 @@ fn convert_search_item_to_result(item: SearchItem) -> SearchResult {
     
     // Create SearchScore with proper weighting
     let score_details = SearchScore::new(
-        score / 100.0,      // text_match (SearchIndex uses 0-100, normalize to 0-1)
-        0.5,                 // frequency (default)
-        0.5,                 // recency (default)
-        0.7,                 // relevance (based on match quality)
+        score / 100.0,       // text_match (SearchIndex uses 0-100, normalize to 0-1)
+        usage.frequency,     // frequency (decayed launch count)
+        usage.recency,       // recency (time since last launch)
+        usage.relevance,     // relevance (launches after this query prefix)
     );
     
     SearchResult::new(
@@ -147,4 +272,5 @@ fn convert_search_item_to_result(item: SearchItem) -> SearchResult {
     )
     .with_categories(categories)
     .with_score_details(score_details)
+    .with_title_matches(fuzzy.map(|m| m.ranges).unwrap_or_default())
 }
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use action_items_ecs_search::{FrecencyConfig, FrecencyStore};
+
+const DAY: f64 = 24.0 * 60.0 * 60.0;
+
+fn store() -> FrecencyStore {
+    FrecencyStore::new(FrecencyConfig {
+        half_life: Duration::from_secs_f64(DAY),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_score_halves_after_half_life() {
+    let mut store = store();
+    let launch = store.record("app_firefox", "fire", 0.0);
+    assert_eq!(launch.item.count, 1);
+    assert_eq!(launch.queries.len(), 4);
+
+    assert!((store.recency("app_firefox", 0.0) - 1.0).abs() < 1e-6);
+    assert!((store.recency("app_firefox", DAY) - 0.5).abs() < 1e-6);
+
+    let second = store.record("app_firefox", "", DAY);
+    assert!((second.item.score - 1.5).abs() < 1e-9);
+    assert_eq!(second.item.count, 2);
+}
+
+#[test]
+fn test_frequent_item_outranks_rare_item() {
+    let mut store = store();
+    for i in 0..5 {
+        store.record("app_terminal", "", i as f64 * 60.0);
+    }
+    store.record("app_textedit", "", 300.0);
+
+    assert!(store.frequency("app_terminal", 400.0) > store.frequency("app_textedit", 400.0));
+    assert_eq!(store.frequency("app_unknown", 400.0), 0.0);
+}
+
+#[test]
+fn test_query_affinity_uses_longest_prefix() {
+    let mut store = store();
+    store.record("app_firefox", "Fi", 0.0);
+    store.record("app_finder", "fin", 0.0);
+
+    assert!(store.query_affinity("app_firefox", "fi", 0.0) > 0.0);
+    assert!(store.query_affinity("app_firefox", "fire", 0.0) > 0.0);
+    assert_eq!(store.query_affinity("app_firefox", "safari", 0.0), 0.0);
+    assert!(store.query_affinity("app_finder", "find", 0.0) > 0.0);
+}
+
+#[test]
+fn test_forget_and_reset() {
+    let mut store = store();
+    store.record("app_firefox", "fire", 0.0);
+    store.record("app_safari", "saf", 0.0);
+
+    let mut prefixes = store.forget("app_firefox");
+    prefixes.sort();
+    assert_eq!(prefixes, vec!["f", "fi", "fir", "fire"]);
+    assert!(store.entry("app_firefox").is_none());
+    assert_eq!(store.query_affinity("app_firefox", "fire", 0.0), 0.0);
+
+    store.reset();
+    assert!(store.is_empty());
+}
+
+#[test]
+fn test_changes_before_loading_are_replayed_in_order() {
+    use action_items_ecs_search::frecency::DeferredChange;
+
+    let mut store = store();
+    store.defer("app_firefox", "fire", 0.0);
+    store.defer_forget("app_firefox");
+    store.defer("app_safari", "saf", 1.0);
+    assert_eq!(
+        store.take_deferred(),
+        vec![
+            DeferredChange::Launch {
+                item_id: "app_firefox".into(),
+                query: "fire".into(),
+                at: 0.0,
+            },
+            DeferredChange::Forget("app_firefox".into()),
+            DeferredChange::Launch {
+                item_id: "app_safari".into(),
+                query: "saf".into(),
+                at: 1.0,
+            },
+        ]
+    );
+
+    store.defer("app_firefox", "fire", 2.0);
+    store.defer_reset();
+    assert_eq!(store.take_deferred(), vec![DeferredChange::Reset]);
+    assert!(store.take_deferred().is_empty());
+}
+
+#[test]
+fn test_launch_is_credited_to_query_at_request_time() {
+    use action_items_ecs_search::frecency::store::unix_now;
+    use action_items_ecs_search::{FrecencyPlugin, SearchResource};
+    use bevy::prelude::*;
+    use ecs_launcher::{ActionExecuteCompleted, ActionExecuteRequested, ExecutionContext};
+
+    let mut app = App::new();
+    app.insert_resource(SearchResource::default()).add_plugins(FrecencyPlugin::default());
+
+    app.world_mut().resource_mut::<SearchResource>().current_query = "fire".to_string();
+    app.world_mut().send_event(ActionExecuteRequested {
+        action_id: "app_firefox".to_string(),
+        requester: "test".to_string(),
+        parameters: serde_json::Value::Null,
+        execution_context: ExecutionContext::default(),
+    });
+    app.update();
+
+    // The query is cleared once the launcher hides, before execution completes
+    app.world_mut().resource_mut::<SearchResource>().current_query.clear();
+    app.world_mut().send_event(ActionExecuteCompleted {
+        action_id: "app_firefox".to_string(),
+        requester: "test".to_string(),
+        success: true,
+        result: None,
+        error_message: None,
+        execution_time: Duration::ZERO,
+    });
+    app.update();
+
+    let store = app.world().resource::<FrecencyStore>();
+    assert!(store.query_affinity("app_firefox", "fire", unix_now()) > 0.0);
+}
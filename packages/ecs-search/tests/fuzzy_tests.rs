@@ -0,0 +1,45 @@
+use action_items_ecs_search::{MatchRange, fuzzy_match, highlight_segments};
+
+#[test]
+fn test_acronym_matches_word_starts() {
+    let m = fuzzy_match("vsc", "Visual Studio Code").expect("acronym should match");
+    assert_eq!(
+        m.ranges,
+        vec![MatchRange::new(0, 1), MatchRange::new(7, 8), MatchRange::new(14, 15)]
+    );
+}
+
+#[test]
+fn test_non_subsequence_does_not_match() {
+    assert!(fuzzy_match("xyz", "Visual Studio Code").is_none());
+    assert!(fuzzy_match("", "Visual Studio Code").is_none());
+}
+
+#[test]
+fn test_word_start_outranks_scattered_match() {
+    let acronym = fuzzy_match("vsc", "Visual Studio Code").expect("match");
+    let scattered = fuzzy_match("vsc", "Previous Script").expect("match");
+    assert!(acronym.score > scattered.score);
+}
+
+#[test]
+fn test_consecutive_and_camel_case_bonuses() {
+    let prefix = fuzzy_match("fire", "Firefox").expect("match");
+    assert_eq!(prefix.ranges, vec![MatchRange::new(0, 4)]);
+
+    let camel = fuzzy_match("gh", "openGitHub").expect("match");
+    assert_eq!(camel.ranges, vec![MatchRange::new(4, 5), MatchRange::new(7, 8)]);
+}
+
+#[test]
+fn test_highlight_segments_split_title() {
+    let segments = highlight_segments("Firefox", &[MatchRange::new(0, 4)]);
+    assert_eq!(
+        segments,
+        vec![("Fire".to_string(), true), ("fox".to_string(), false)]
+    );
+
+    // Stale ranges beyond the text are clamped rather than panicking
+    let segments = highlight_segments("Fox", &[MatchRange::new(2, 10)]);
+    assert_eq!(segments, vec![("Fo".to_string(), false), ("x".to_string(), true)]);
+}
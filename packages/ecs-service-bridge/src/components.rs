@@ -51,6 +51,8 @@ pub enum PluginStatus {
     Error(String) = 3,
     /// Plugin is shutting down
     Terminating = 4,
+    /// Plugin is loaded but tripped a resource limit and no longer receives messages
+    Degraded(String) = 5,
 }
 
 /// Component representing a plugin's capabilities
@@ -0,0 +1,139 @@
+//! The bundle file format
+//!
+//! A bundle is one JSON or TOML document holding the records of every exported table by
+//! record key, without the fields each machine maintains itself, and the exported plugin
+//! configs by plugin id. `format_version` is read before anything else so bundles from newer
+//! versions of the app are refused with a clear error instead of a parse failure.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use action_items_core::config::PluginConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::category::BundleCategory;
+use crate::error::BundleError;
+
+/// Version of the bundle format written by this build
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Records of one table: fields by record key
+pub type BundleRecords = BTreeMap<String, Map<String, Value>>;
+
+/// File format of a bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+impl BundleFormat {
+    /// TOML for `.toml` files, JSON otherwise
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Exported settings, ready to be written to a file or imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Categories exported, including those that were empty
+    pub categories: BTreeSet<BundleCategory>,
+    /// Records by table
+    #[serde(default)]
+    pub tables: BTreeMap<String, BundleRecords>,
+    /// Plugin configs by plugin id
+    #[serde(default)]
+    pub plugin_configs: BTreeMap<String, PluginConfig>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    format_version: u32,
+}
+
+impl SettingsBundle {
+    pub fn new(categories: BTreeSet<BundleCategory>) -> Self {
+        Self {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            categories,
+            tables: BTreeMap::new(),
+            plugin_configs: BTreeMap::new(),
+        }
+    }
+
+    /// Records of the given category's tables, by table
+    pub fn records(
+        &self,
+        category: BundleCategory,
+    ) -> impl Iterator<Item = (&str, &BundleRecords)> + '_ {
+        self.tables
+            .iter()
+            .filter(move |(table, _)| BundleCategory::of_table(table) == Some(category))
+            .map(|(table, records)| (table.as_str(), records))
+    }
+
+    /// Serialize the bundle
+    ///
+    /// TOML has no null, so a plugin config holding one can only be written as JSON.
+    pub fn to_string(&self, format: BundleFormat) -> Result<String, BundleError> {
+        match format {
+            BundleFormat::Json => serde_json::to_string_pretty(self).map_err(malformed),
+            BundleFormat::Toml => toml::to_string_pretty(self).map_err(malformed),
+        }
+    }
+
+    /// Parse a bundle, refusing versions this build does not know
+    pub fn parse(content: &str, format: BundleFormat) -> Result<Self, BundleError> {
+        let header: Header = match format {
+            BundleFormat::Json => serde_json::from_str(content).map_err(malformed)?,
+            BundleFormat::Toml => toml::from_str(content).map_err(malformed)?,
+        };
+        if header.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(BundleError::UnsupportedVersion {
+                found: header.format_version,
+                supported: BUNDLE_FORMAT_VERSION,
+            });
+        }
+        if header.format_version == 0 {
+            return Err(BundleError::Malformed("Format version 0".to_string()));
+        }
+
+        match format {
+            BundleFormat::Json => serde_json::from_str(content).map_err(malformed),
+            BundleFormat::Toml => toml::from_str(content).map_err(malformed),
+        }
+    }
+
+    /// Write the bundle to a file in the format its extension names
+    pub fn write(&self, path: &Path) -> Result<(), BundleError> {
+        let content = self.to_string(BundleFormat::from_path(path))?;
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Read a bundle from a file in the format its extension names
+    pub fn read(path: &Path) -> Result<Self, BundleError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content, BundleFormat::from_path(path))
+    }
+}
+
+fn malformed(error: impl std::fmt::Display) -> BundleError {
+    BundleError::Malformed(error.to_string())
+}
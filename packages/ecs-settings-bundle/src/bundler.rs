@@ -0,0 +1,359 @@
+//! Exporting and importing bundles
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use action_items_core::config::PluginConfig;
+use action_items_ecs_surrealdb::{DatabaseError, DatabaseService};
+use futures::FutureExt;
+use serde_json::{Map, Value};
+use surrealdb::Value as SurrealValue;
+use tracing::{debug, info, warn};
+
+use crate::bundle::{BundleRecords, SettingsBundle};
+use crate::category::{BundleCategory, BundledTable};
+use crate::error::{BundleError, BundleIssue};
+use crate::plugin_configs::PluginConfigSource;
+use crate::preview::{CategoryPreview, ImportPreview, ImportReport, RecordDiff, diff_fields};
+
+/// Exports settings into bundles and restores them
+///
+/// Without a [`PluginConfigSource`], plugin configs are left out of exports and cannot be
+/// imported.
+#[derive(Clone)]
+pub struct SettingsBundler {
+    db: DatabaseService,
+    plugin_configs: Option<PluginConfigSource>,
+}
+
+/// A write planned by an import
+enum PlannedWrite {
+    Record {
+        table: String,
+        key: String,
+        fields: Map<String, Value>,
+    },
+    PluginConfig(PluginConfig),
+}
+
+impl SettingsBundler {
+    pub fn new(db: DatabaseService) -> Self {
+        Self {
+            db,
+            plugin_configs: None,
+        }
+    }
+
+    pub fn with_plugin_configs(mut self, source: PluginConfigSource) -> Self {
+        self.plugin_configs = Some(source);
+        self
+    }
+
+    /// Bundle the current settings of the given categories
+    pub async fn export(
+        &self,
+        categories: &BTreeSet<BundleCategory>,
+    ) -> Result<SettingsBundle, BundleError> {
+        let mut bundle = SettingsBundle::new(categories.clone());
+        for category in categories {
+            for table in category.tables() {
+                let records = read_table(&self.db, table).await?;
+                bundle.tables.insert(table.name.to_string(), records);
+            }
+        }
+
+        if categories.contains(&BundleCategory::PluginConfigs) {
+            match &self.plugin_configs {
+                Some(source) => {
+                    bundle.plugin_configs = source
+                        .store()
+                        .load_all_configs()
+                        .await?
+                        .into_iter()
+                        .collect();
+                },
+                None => {
+                    warn!("No plugin config store; leaving plugin configs out of the bundle");
+                    bundle.categories.remove(&BundleCategory::PluginConfigs);
+                },
+            }
+        }
+
+        info!(
+            tables = bundle.tables.len(),
+            plugin_configs = bundle.plugin_configs.len(),
+            "Exported settings bundle"
+        );
+        Ok(bundle)
+    }
+
+    /// Compare a bundle with the current settings and validate it
+    pub async fn preview(&self, bundle: &SettingsBundle) -> Result<ImportPreview, BundleError> {
+        let (preview, _) = self.plan(bundle).await?;
+        Ok(preview)
+    }
+
+    /// Import the selected categories of a bundle
+    ///
+    /// Nothing is written if any selected category has issues. Records are written in one
+    /// transaction, so a record the database schema rejects rolls back the others; plugin
+    /// configs are written once the records are in.
+    pub async fn import(
+        &self,
+        bundle: &SettingsBundle,
+        selection: &BTreeSet<BundleCategory>,
+    ) -> Result<ImportReport, BundleError> {
+        let (preview, mut writes) = self.plan(bundle).await?;
+        let issues = preview.issues(selection);
+        if !issues.is_empty() {
+            return Err(BundleError::Invalid(issues));
+        }
+        writes.retain(|category, _| selection.contains(category));
+
+        let mut report = ImportReport {
+            unchanged: preview
+                .categories
+                .iter()
+                .filter(|(category, _)| selection.contains(category))
+                .map(|(_, category)| category.unchanged)
+                .sum(),
+            ..Default::default()
+        };
+        let mut statements = Vec::new();
+        let mut plugin_configs = Vec::new();
+        for (category, category_writes) in writes {
+            report.written.insert(category, category_writes.len());
+            for write in category_writes {
+                match write {
+                    PlannedWrite::Record { table, key, fields } => {
+                        let params = HashMap::from([
+                            ("table".to_string(), to_value(table)?),
+                            ("key".to_string(), to_value(key)?),
+                            ("fields".to_string(), to_value(Value::Object(fields))?),
+                        ]);
+                        statements.push(params);
+                    },
+                    PlannedWrite::PluginConfig(config) => plugin_configs.push(config),
+                }
+            }
+        }
+
+        if !statements.is_empty() {
+            self.db
+                .with_transaction(|ctx| {
+                    async move {
+                        for params in statements {
+                            ctx.query_with_params(
+                                "UPSERT type::thing($table, $key) MERGE $fields",
+                                params,
+                            )
+                            .await?
+                            .check()
+                            .map_err(query_failed)?;
+                        }
+                        Ok(())
+                    }
+                    .boxed()
+                })
+                .await?;
+        }
+        if let Some(source) = &self.plugin_configs {
+            for config in plugin_configs {
+                source.store().save_config(&config).await?;
+            }
+        }
+
+        info!(written = ?report.written, unchanged = report.unchanged, "Imported settings bundle");
+        Ok(report)
+    }
+
+    /// Diff and validate every category in the bundle, with the writes importing it needs
+    async fn plan(
+        &self,
+        bundle: &SettingsBundle,
+    ) -> Result<(ImportPreview, BTreeMap<BundleCategory, Vec<PlannedWrite>>), BundleError> {
+        let mut preview = ImportPreview {
+            format_version: bundle.format_version,
+            exported_at: bundle.exported_at,
+            categories: BTreeMap::new(),
+            ignored_tables: bundle
+                .tables
+                .keys()
+                .filter(|table| BundledTable::find(table).is_none())
+                .cloned()
+                .collect(),
+        };
+        let mut writes = BTreeMap::new();
+
+        for category in &bundle.categories {
+            let (category_preview, category_writes) = match category {
+                BundleCategory::PluginConfigs => self.plan_plugin_configs(bundle).await?,
+                _ => self.plan_records(bundle, *category).await?,
+            };
+            preview.categories.insert(*category, category_preview);
+            writes.insert(*category, category_writes);
+        }
+        Ok((preview, writes))
+    }
+
+    async fn plan_records(
+        &self,
+        bundle: &SettingsBundle,
+        category: BundleCategory,
+    ) -> Result<(CategoryPreview, Vec<PlannedWrite>), BundleError> {
+        let mut preview = CategoryPreview::default();
+        let mut writes = Vec::new();
+
+        for (table_name, records) in bundle.records(category) {
+            let Some(table) = BundledTable::find(table_name) else {
+                continue;
+            };
+            let current = read_table(&self.db, table).await?;
+            for (key, fields) in records {
+                let target = format!("{table_name}:{key}");
+                let mut fields = fields.clone();
+                fields.retain(|name, _| !table.machine_fields.contains(&name.as_str()));
+
+                if let Some(message) = record_problem(key, &fields) {
+                    preview.issues.push(BundleIssue {
+                        category,
+                        target,
+                        message,
+                    });
+                    continue;
+                }
+                match diff_fields(current.get(key), &fields) {
+                    Some(kind) => {
+                        preview.diffs.push(RecordDiff { target, kind });
+                        writes.push(PlannedWrite::Record {
+                            table: table_name.to_string(),
+                            key: key.clone(),
+                            fields,
+                        });
+                    },
+                    None => preview.unchanged += 1,
+                }
+            }
+        }
+        Ok((preview, writes))
+    }
+
+    async fn plan_plugin_configs(
+        &self,
+        bundle: &SettingsBundle,
+    ) -> Result<(CategoryPreview, Vec<PlannedWrite>), BundleError> {
+        let mut preview = CategoryPreview::default();
+        let mut writes = Vec::new();
+        let Some(source) = &self.plugin_configs else {
+            if !bundle.plugin_configs.is_empty() {
+                preview.issues.push(BundleIssue {
+                    category: BundleCategory::PluginConfigs,
+                    target: "plugin configs".to_string(),
+                    message: "No plugin config store is available to import into".to_string(),
+                });
+            }
+            return Ok((preview, writes));
+        };
+
+        for (plugin_id, imported) in &bundle.plugin_configs {
+            let issues = source.validate(plugin_id, imported);
+            if !issues.is_empty() {
+                preview.issues.extend(issues);
+                continue;
+            }
+            let current = source.store().load_config(plugin_id).await?;
+            let current_fields = current.as_ref().map(config_fields);
+            match diff_fields(current_fields.as_ref(), &config_fields(imported)) {
+                Some(kind) => {
+                    preview.diffs.push(RecordDiff {
+                        target: plugin_id.clone(),
+                        kind,
+                    });
+                    writes.push(PlannedWrite::PluginConfig(merge_config(current, imported)));
+                },
+                None => preview.unchanged += 1,
+            }
+        }
+        Ok((preview, writes))
+    }
+}
+
+/// Why a bundled record cannot be written, if it cannot
+fn record_problem(key: &str, fields: &Map<String, Value>) -> Option<String> {
+    if key.is_empty() {
+        return Some("Record key is empty".to_string());
+    }
+    if fields.contains_key("id") {
+        return Some("Records cannot set 'id'; the key names the record".to_string());
+    }
+    None
+}
+
+/// A plugin config's values as fields, with configuration and preference keys prefixed
+fn config_fields(config: &PluginConfig) -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert("version".to_string(), Value::String(config.version.clone()));
+    fields.insert("enabled".to_string(), Value::Bool(config.enabled));
+    for (name, value) in &config.configuration {
+        fields.insert(format!("configuration.{name}"), value.clone());
+    }
+    for (name, value) in &config.preferences {
+        fields.insert(format!("preferences.{name}"), value.clone());
+    }
+    fields
+}
+
+/// Merge an imported plugin config into the current one, keeping values only set locally
+fn merge_config(current: Option<PluginConfig>, imported: &PluginConfig) -> PluginConfig {
+    let mut config = current.unwrap_or_else(|| PluginConfig {
+        configuration: HashMap::new(),
+        preferences: HashMap::new(),
+        ..imported.clone()
+    });
+    config.version = imported.version.clone();
+    config.enabled = imported.enabled;
+    config.configuration.extend(imported.configuration.clone());
+    config.preferences.extend(imported.preferences.clone());
+    config.last_modified = chrono::Utc::now();
+    config
+}
+
+fn query_failed(error: impl std::fmt::Display) -> DatabaseError {
+    DatabaseError::QueryFailed(error.to_string())
+}
+
+fn to_value(value: impl serde::Serialize + 'static) -> Result<SurrealValue, BundleError> {
+    surrealdb::value::to_value(value).map_err(|e| BundleError::Malformed(e.to_string()))
+}
+
+/// Bundled fields of every record in a table, by record key
+async fn read_table(
+    db: &DatabaseService,
+    table: &BundledTable,
+) -> Result<BundleRecords, BundleError> {
+    let params = HashMap::from([("table".to_string(), to_value(table.name.to_string())?)]);
+    let mut response = db
+        .query_with_params(
+            "SELECT *, record::id(id) AS id FROM type::table($table)",
+            params,
+        )
+        .await?;
+    let rows = response.take::<Vec<Value>>(0).map_err(query_failed)?;
+
+    let mut records = BundleRecords::new();
+    for row in rows {
+        let Value::Object(mut fields) = row else {
+            continue;
+        };
+        // Only string keys survive the round trip through `type::thing`
+        let Some(Value::String(key)) = fields.remove("id") else {
+            debug!("Skipping {} record without a string key", table.name);
+            continue;
+        };
+        // TOML has no null, and an unset field is the same as a missing one
+        fields.retain(|name, value| {
+            !value.is_null() && !table.machine_fields.contains(&name.as_str())
+        });
+        records.insert(key, fields);
+    }
+    Ok(records)
+}
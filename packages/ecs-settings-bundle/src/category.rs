@@ -0,0 +1,115 @@
+//! What a bundle can carry
+
+use action_items_ecs_user_settings::table_names::*;
+use serde::{Deserialize, Serialize};
+
+/// A part of the settings that is exported and restored as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleCategory {
+    /// Every user settings table but hotkeys, such as appearance, AI and advanced settings
+    Settings,
+    Hotkeys,
+    /// Configuration and preferences of each plugin, from the plugin config store
+    PluginConfigs,
+    Snippets,
+    Quicklinks,
+}
+
+impl BundleCategory {
+    pub const ALL: [Self; 5] = [
+        Self::Settings,
+        Self::Hotkeys,
+        Self::PluginConfigs,
+        Self::Snippets,
+        Self::Quicklinks,
+    ];
+
+    /// Exported unless chosen otherwise; snippets and quicklinks are user content and opt-in
+    pub const DEFAULT: [Self; 3] = [Self::Settings, Self::Hotkeys, Self::PluginConfigs];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Settings => "Settings",
+            Self::Hotkeys => "Hotkeys",
+            Self::PluginConfigs => "Plugin configs",
+            Self::Snippets => "Snippets",
+            Self::Quicklinks => "Quicklinks",
+        }
+    }
+
+    /// SurrealDB tables holding the category's records
+    pub fn tables(self) -> &'static [BundledTable] {
+        match self {
+            Self::Settings => SETTINGS_TABLES,
+            Self::Hotkeys => HOTKEY_TABLES,
+            // Bundled from the config store; the `plugin_configs` table only holds the copy
+            // made when config files were migrated to the database
+            Self::PluginConfigs => &[],
+            Self::Snippets => SNIPPET_TABLES,
+            Self::Quicklinks => QUICKLINK_TABLES,
+        }
+    }
+
+    /// The category a bundled table belongs to
+    pub fn of_table(table: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.tables().iter().any(|t| t.name == table))
+    }
+}
+
+/// A table whose records are bundled, and the fields left out of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundledTable {
+    pub name: &'static str,
+    /// Fields each machine maintains itself, such as timestamps filled in by
+    /// `DEFAULT time::now()`
+    pub machine_fields: &'static [&'static str],
+}
+
+impl BundledTable {
+    pub fn find(name: &str) -> Option<&'static BundledTable> {
+        BundleCategory::ALL
+            .into_iter()
+            .flat_map(|category| category.tables())
+            .find(|table| table.name == name)
+    }
+}
+
+const SETTINGS_TIMESTAMPS: &[&str] = &["created_at", "updated_at"];
+
+const fn settings_table(name: &'static str) -> BundledTable {
+    BundledTable {
+        name,
+        machine_fields: SETTINGS_TIMESTAMPS,
+    }
+}
+
+const SETTINGS_TABLES: &[BundledTable] = &[
+    settings_table(USER_PREFERENCES),
+    settings_table(AI_SETTINGS),
+    BundledTable {
+        name: CLOUD_SYNC_SETTINGS,
+        machine_fields: &["created_at", "updated_at", "last_synced"],
+    },
+    settings_table(ACCOUNT_SETTINGS),
+    settings_table(ORGANIZATION_SETTINGS),
+    settings_table(ADVANCED_SETTINGS),
+    settings_table(APPEARANCE_SETTINGS),
+    settings_table(STARTUP_SETTINGS),
+    BundledTable {
+        name: UI_STATE,
+        machine_fields: &["last_updated"],
+    },
+];
+const HOTKEY_TABLES: &[BundledTable] = &[settings_table(HOTKEY_SETTINGS)];
+// Owned by ecs-snippets and ecs-quicklinks, whose timestamps are plain integers
+const SNIPPET_TABLES: &[BundledTable] = &[BundledTable {
+    name: "snippet",
+    machine_fields: &[],
+}];
+const QUICKLINK_TABLES: &[BundledTable] = &[BundledTable {
+    name: "quicklink",
+    machine_fields: &[],
+}];
@@ -0,0 +1,63 @@
+//! Settings bundle error types
+
+use action_items_ecs_surrealdb::DatabaseError;
+use thiserror::Error;
+
+use crate::category::BundleCategory;
+
+/// A reason part of a bundle cannot be imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleIssue {
+    pub category: BundleCategory,
+    /// The record, plugin or field concerned, such as `hotkey_settings:launcher`
+    pub target: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for BundleIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.target, self.message)
+    }
+}
+
+/// Errors raised while exporting or importing settings bundles
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BundleError {
+    /// The bundle was written by a newer version of the app
+    #[error("Settings bundle version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Malformed settings bundle: {0}")]
+    Malformed(String),
+
+    /// The selected categories failed validation; nothing was written
+    #[error("Settings bundle failed validation with {} issue(s)", .0.len())]
+    Invalid(Vec<BundleIssue>),
+
+    #[error("File error: {0}")]
+    Io(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+
+    #[error("Plugin config error: {0}")]
+    Config(String),
+}
+
+impl From<DatabaseError> for BundleError {
+    fn from(error: DatabaseError) -> Self {
+        BundleError::Database(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for BundleError {
+    fn from(error: std::io::Error) -> Self {
+        BundleError::Io(error.to_string())
+    }
+}
+
+impl From<action_items_core::Error> for BundleError {
+    fn from(error: action_items_core::Error) -> Self {
+        BundleError::Config(error.to_string())
+    }
+}
@@ -0,0 +1,59 @@
+//! Settings bundle request and result events
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::category::BundleCategory;
+use crate::error::BundleError;
+use crate::preview::{ImportPreview, ImportReport};
+
+/// Export the given categories to a bundle file, as TOML if its extension is `.toml` and as
+/// JSON otherwise
+#[derive(Event, Debug, Clone)]
+pub struct SettingsExportRequested {
+    pub path: PathBuf,
+    pub categories: BTreeSet<BundleCategory>,
+}
+
+impl SettingsExportRequested {
+    /// Export the default categories
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            categories: BundleCategory::DEFAULT.into(),
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct SettingsExportCompleted {
+    pub path: PathBuf,
+    pub result: Result<(), BundleError>,
+}
+
+/// Read a bundle file and compare it with the current settings
+#[derive(Event, Debug, Clone)]
+pub struct SettingsImportPreviewRequested {
+    pub path: PathBuf,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct SettingsImportPreviewed {
+    pub path: PathBuf,
+    pub result: Result<ImportPreview, BundleError>,
+}
+
+/// Import the selected categories of a bundle file
+#[derive(Event, Debug, Clone)]
+pub struct SettingsImportRequested {
+    pub path: PathBuf,
+    pub categories: BTreeSet<BundleCategory>,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct SettingsImportCompleted {
+    pub path: PathBuf,
+    pub result: Result<ImportReport, BundleError>,
+}
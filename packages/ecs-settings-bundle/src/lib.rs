@@ -0,0 +1,35 @@
+//! Action Items ECS Settings Bundle
+//!
+//! Bevy ECS plugin moving settings between machines in a single file.
+//!
+//! - Exports the user settings tables, hotkeys and plugin configs, and optionally snippets and
+//!   quicklinks, into a versioned JSON or TOML bundle
+//! - Previews an import as a per-record diff against the current settings
+//! - Imports only the selected categories, after validating plugin configs against their
+//!   manifests with `action_items_core::config::validation`
+//!
+//! [`SettingsBundler`] does the work directly; [`SettingsBundlePlugin`] runs it from events.
+
+pub mod bundle;
+pub mod bundler;
+pub mod category;
+pub mod error;
+pub mod events;
+pub mod plugin;
+pub mod plugin_configs;
+pub mod preview;
+pub mod systems;
+
+pub use bundle::{BUNDLE_FORMAT_VERSION, BundleFormat, BundleRecords, SettingsBundle};
+pub use bundler::SettingsBundler;
+pub use category::{BundleCategory, BundledTable};
+pub use error::{BundleError, BundleIssue};
+pub use events::{
+    SettingsExportCompleted, SettingsExportRequested, SettingsImportCompleted,
+    SettingsImportPreviewRequested, SettingsImportPreviewed, SettingsImportRequested,
+};
+pub use plugin::SettingsBundlePlugin;
+pub use plugin_configs::PluginConfigSource;
+pub use preview::{
+    CategoryPreview, DiffKind, FieldChange, ImportPreview, ImportReport, RecordDiff,
+};
@@ -0,0 +1,35 @@
+//! Settings bundle plugin
+
+use bevy::prelude::*;
+
+use crate::events::*;
+use crate::systems::*;
+
+/// Plugin exporting settings to bundle files and importing them
+///
+/// Needs the `DatabaseService` from `DatabasePlugin`. Plugin configs are included once a
+/// [`PluginConfigSource`](crate::PluginConfigSource) resource is inserted.
+pub struct SettingsBundlePlugin;
+
+impl Plugin for SettingsBundlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SettingsExportRequested>()
+            .add_event::<SettingsExportCompleted>()
+            .add_event::<SettingsImportPreviewRequested>()
+            .add_event::<SettingsImportPreviewed>()
+            .add_event::<SettingsImportRequested>()
+            .add_event::<SettingsImportCompleted>()
+            .add_systems(
+                Update,
+                (
+                    handle_export_requests,
+                    handle_import_preview_requests,
+                    handle_import_requests,
+                    handle_settings_bundle_tasks,
+                )
+                    .chain(),
+            );
+
+        tracing::info!("SettingsBundlePlugin initialized");
+    }
+}
@@ -0,0 +1,87 @@
+//! Plugin configs as bundled from a config store
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use action_items_common::plugin_interface::ConfigurationField;
+use action_items_core::PluginManifest;
+use action_items_core::config::{ConfigStore, PluginConfig, ValidationEngine};
+use bevy::prelude::Resource;
+
+use crate::category::BundleCategory;
+use crate::error::BundleIssue;
+
+/// The store plugin configs are exported from and restored to, and the manifest fields they
+/// are validated against
+///
+/// Insert it as a resource to include plugin configs in bundles from Bevy events. Configs of
+/// plugins without registered fields are checked for consistency only.
+#[derive(Resource, Clone)]
+pub struct PluginConfigSource {
+    store: Arc<dyn ConfigStore>,
+    fields: HashMap<String, Vec<ConfigurationField>>,
+}
+
+impl PluginConfigSource {
+    pub fn new(store: Arc<dyn ConfigStore>) -> Self {
+        Self {
+            store,
+            fields: HashMap::new(),
+        }
+    }
+
+    /// Validate this plugin's imported configuration against its manifest
+    pub fn with_manifest(self, manifest: &PluginManifest) -> Self {
+        self.with_fields(&manifest.id, manifest.configuration.clone())
+    }
+
+    /// Validate a plugin's imported configuration against these fields
+    pub fn with_fields(mut self, plugin_id: &str, fields: Vec<ConfigurationField>) -> Self {
+        self.fields.insert(plugin_id.to_string(), fields);
+        self
+    }
+
+    pub fn register_manifest(&mut self, manifest: &PluginManifest) {
+        self.fields
+            .insert(manifest.id.clone(), manifest.configuration.clone());
+    }
+
+    pub fn store(&self) -> &Arc<dyn ConfigStore> {
+        &self.store
+    }
+
+    /// Problems that keep a bundled config from being restored
+    pub fn validate(&self, plugin_id: &str, config: &PluginConfig) -> Vec<BundleIssue> {
+        let issue = |target: String, message: String| BundleIssue {
+            category: BundleCategory::PluginConfigs,
+            target,
+            message,
+        };
+        let mut issues = Vec::new();
+
+        if config.plugin_id != plugin_id {
+            issues.push(issue(
+                plugin_id.to_string(),
+                format!(
+                    "Bundled under '{plugin_id}' but names '{}'",
+                    config.plugin_id
+                ),
+            ));
+        }
+
+        let Some(fields) = self.fields.get(plugin_id) else {
+            return issues;
+        };
+        let mut engine = ValidationEngine::new();
+        let results = engine.validate_configuration(fields, &config.configuration);
+        let mut errors = engine.get_all_errors(&results);
+        errors.sort_by(|a, b| a.field_name.cmp(&b.field_name));
+        for error in errors {
+            issues.push(issue(
+                format!("{plugin_id}.{}", error.field_name),
+                error.message,
+            ));
+        }
+        issues
+    }
+}
@@ -0,0 +1,111 @@
+//! What importing a bundle would change
+//!
+//! Importing merges: every bundled field replaces the local one and fields only present
+//! locally are kept, so the preview lists records and plugin configs that would be added and
+//! the fields that would change in the others.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::category::BundleCategory;
+use crate::error::BundleIssue;
+
+/// A field whose value would change
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    /// `None` if the field is not set locally
+    pub current: Option<Value>,
+    pub imported: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffKind {
+    /// No local record or plugin config yet
+    Added,
+    Changed(Vec<FieldChange>),
+}
+
+/// A record or plugin config the import would write
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordDiff {
+    /// `table:key` for records, the plugin id for plugin configs
+    pub target: String,
+    pub kind: DiffKind,
+}
+
+/// Preview of one category
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CategoryPreview {
+    pub diffs: Vec<RecordDiff>,
+    /// Records or plugin configs already matching the bundle
+    pub unchanged: usize,
+    /// Problems that keep the category from being imported
+    pub issues: Vec<BundleIssue>,
+}
+
+/// Preview of importing a bundle, by category
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportPreview {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub categories: BTreeMap<BundleCategory, CategoryPreview>,
+    /// Bundled tables this build does not know, which are never imported
+    pub ignored_tables: Vec<String>,
+}
+
+impl ImportPreview {
+    /// Issues of the selected categories; importing them fails unless this is empty
+    pub fn issues(&self, selection: &BTreeSet<BundleCategory>) -> Vec<BundleIssue> {
+        self.selected(selection)
+            .flat_map(|(_, preview)| preview.issues.iter().cloned())
+            .collect()
+    }
+
+    /// Number of records and plugin configs importing the selected categories would write
+    pub fn change_count(&self, selection: &BTreeSet<BundleCategory>) -> usize {
+        self.selected(selection)
+            .map(|(_, preview)| preview.diffs.len())
+            .sum()
+    }
+
+    fn selected<'a>(
+        &'a self,
+        selection: &'a BTreeSet<BundleCategory>,
+    ) -> impl Iterator<Item = (&'a BundleCategory, &'a CategoryPreview)> {
+        self.categories
+            .iter()
+            .filter(|(category, _)| selection.contains(category))
+    }
+}
+
+/// What an import wrote
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Records and plugin configs written, by category
+    pub written: BTreeMap<BundleCategory, usize>,
+    /// Records and plugin configs already matching the bundle
+    pub unchanged: usize,
+}
+
+/// How merging `imported` into `current` would change it, or `None` if it would not
+pub fn diff_fields(
+    current: Option<&Map<String, Value>>,
+    imported: &Map<String, Value>,
+) -> Option<DiffKind> {
+    let Some(current) = current else {
+        return Some(DiffKind::Added);
+    };
+    let changes: Vec<FieldChange> = imported
+        .iter()
+        .filter(|(field, value)| current.get(*field) != Some(*value))
+        .map(|(field, value)| FieldChange {
+            field: field.clone(),
+            current: current.get(field).cloned(),
+            imported: value.clone(),
+        })
+        .collect();
+    (!changes.is_empty()).then_some(DiffKind::Changed(changes))
+}
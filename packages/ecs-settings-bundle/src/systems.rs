@@ -0,0 +1,148 @@
+//! Bevy systems running bundle exports and imports
+
+use std::future::Future;
+
+use action_items_ecs_surrealdb::DatabaseService;
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use tracing::warn;
+
+use crate::bundle::SettingsBundle;
+use crate::bundler::SettingsBundler;
+use crate::error::BundleError;
+use crate::events::*;
+use crate::plugin_configs::PluginConfigSource;
+
+/// Component for settings bundle tasks
+#[derive(Component)]
+pub struct SettingsBundleTask(pub Task<CommandQueue>);
+
+fn bundler(
+    db: Option<Res<DatabaseService>>,
+    plugin_configs: Option<Res<PluginConfigSource>>,
+) -> Result<SettingsBundler, BundleError> {
+    let db = db.ok_or_else(|| BundleError::Database("Database is not available yet".into()))?;
+    let bundler = SettingsBundler::new((*db).clone());
+    Ok(match plugin_configs {
+        Some(source) => bundler.with_plugin_configs((*source).clone()),
+        None => bundler,
+    })
+}
+
+/// Spawn a task whose result is sent as an event
+fn spawn_bundle_task<E: Event>(
+    commands: &mut Commands,
+    work: impl Future<Output = E> + Send + 'static,
+) {
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let event = work.await;
+        let mut command_queue = CommandQueue::default();
+        command_queue.push(move |world: &mut World| {
+            world.send_event(event);
+        });
+        command_queue
+    });
+    commands.spawn(SettingsBundleTask(task));
+}
+
+/// Write requested exports to their files
+pub fn handle_export_requests(
+    mut commands: Commands,
+    mut requests: EventReader<SettingsExportRequested>,
+    db: Option<Res<DatabaseService>>,
+    plugin_configs: Option<Res<PluginConfigSource>>,
+) {
+    if requests.is_empty() {
+        return;
+    }
+    let bundler = bundler(db, plugin_configs);
+    for request in requests.read() {
+        let request = request.clone();
+        let bundler = bundler.clone();
+        spawn_bundle_task(&mut commands, async move {
+            let result = async {
+                let bundle = bundler?.export(&request.categories).await?;
+                bundle.write(&request.path)
+            }
+            .await;
+            if let Err(e) = &result {
+                warn!("Settings export to {:?} failed: {}", request.path, e);
+            }
+            SettingsExportCompleted {
+                path: request.path,
+                result,
+            }
+        });
+    }
+}
+
+/// Read requested bundles and compare them with the current settings
+pub fn handle_import_preview_requests(
+    mut commands: Commands,
+    mut requests: EventReader<SettingsImportPreviewRequested>,
+    db: Option<Res<DatabaseService>>,
+    plugin_configs: Option<Res<PluginConfigSource>>,
+) {
+    if requests.is_empty() {
+        return;
+    }
+    let bundler = bundler(db, plugin_configs);
+    for request in requests.read() {
+        let path = request.path.clone();
+        let bundler = bundler.clone();
+        spawn_bundle_task(&mut commands, async move {
+            let result = async {
+                let bundle = SettingsBundle::read(&path)?;
+                bundler?.preview(&bundle).await
+            }
+            .await;
+            SettingsImportPreviewed { path, result }
+        });
+    }
+}
+
+/// Import the selected categories of requested bundles
+pub fn handle_import_requests(
+    mut commands: Commands,
+    mut requests: EventReader<SettingsImportRequested>,
+    db: Option<Res<DatabaseService>>,
+    plugin_configs: Option<Res<PluginConfigSource>>,
+) {
+    if requests.is_empty() {
+        return;
+    }
+    let bundler = bundler(db, plugin_configs);
+    for request in requests.read() {
+        let request = request.clone();
+        let bundler = bundler.clone();
+        spawn_bundle_task(&mut commands, async move {
+            let result = async {
+                let bundle = SettingsBundle::read(&request.path)?;
+                bundler?.import(&bundle, &request.categories).await
+            }
+            .await;
+            if let Err(e) = &result {
+                warn!("Settings import from {:?} failed: {}", request.path, e);
+            }
+            SettingsImportCompleted {
+                path: request.path,
+                result,
+            }
+        });
+    }
+}
+
+/// Poll settings bundle tasks and send their results
+pub fn handle_settings_bundle_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut SettingsBundleTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.0)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
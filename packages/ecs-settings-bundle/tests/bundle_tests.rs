@@ -0,0 +1,249 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use action_items_common::plugin_interface::{ConfigFieldType, ConfigurationField};
+use action_items_core::config::{ConfigStore, MemoryConfigStore, PluginConfig};
+use action_items_ecs_settings_bundle::{
+    BundleCategory, BundleError, BundleFormat, DiffKind, PluginConfigSource, SettingsBundle,
+    SettingsBundler,
+};
+use action_items_ecs_surrealdb::DatabaseService;
+use action_items_ecs_surrealdb::testing::{execute, migrated_database};
+use action_items_ecs_user_settings::USER_SETTINGS_MIGRATIONS;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+async fn record(db: &DatabaseService, thing: &str) -> Option<Value> {
+    db.query(&format!("SELECT * FROM {thing}"))
+        .await
+        .expect("Query should run")
+        .take::<Option<Value>>(0)
+        .expect("Record should deserialize")
+}
+
+/// A machine with a hotkey, an appearance setting and a snippet
+async fn configured_database() -> DatabaseService {
+    let db = migrated_database(USER_SETTINGS_MIGRATIONS).await;
+    execute(
+        &db,
+        "CREATE hotkey_settings:launcher SET hotkey_id = 'launcher', modifiers = ['cmd'], \
+         key_code = 'space', description = 'Open launcher'",
+    )
+    .await;
+    execute(
+        &db,
+        "CREATE appearance_settings:main SET theme_dark = 'midnight'",
+    )
+    .await;
+    execute(
+        &db,
+        "CREATE snippet:greeting SET name = 'Greeting', text = 'Hello'",
+    )
+    .await;
+    db
+}
+
+fn categories(categories: &[BundleCategory]) -> BTreeSet<BundleCategory> {
+    categories.iter().copied().collect()
+}
+
+fn weather_config(units: &str) -> PluginConfig {
+    PluginConfig {
+        plugin_id: "weather".to_string(),
+        version: "1.0.0".to_string(),
+        configuration: HashMap::from([("units".to_string(), json!(units))]),
+        preferences: HashMap::new(),
+        enabled: true,
+        last_modified: chrono::Utc::now(),
+    }
+}
+
+fn weather_fields() -> Vec<ConfigurationField> {
+    vec![ConfigurationField {
+        name: "units".to_string(),
+        title: "Units".to_string(),
+        description: None,
+        field_type: ConfigFieldType::Text,
+        required: true,
+        default: None,
+        placeholder: None,
+        validation: None,
+    }]
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_export_round_trips_through_toml() {
+    let db = configured_database().await;
+    let bundle = SettingsBundler::new(db)
+        .export(&BundleCategory::DEFAULT.into())
+        .await
+        .unwrap();
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("settings.toml");
+    bundle.write(&path).unwrap();
+    let read = SettingsBundle::read(&path).unwrap();
+
+    // Plugin configs are left out without a config store
+    assert_eq!(
+        read.categories,
+        categories(&[BundleCategory::Settings, BundleCategory::Hotkeys])
+    );
+    let hotkey = &read.tables["hotkey_settings"]["launcher"];
+    assert_eq!(hotkey["key_code"], "space");
+    assert!(!hotkey.contains_key("created_at"));
+    assert_eq!(
+        read.tables["appearance_settings"]["main"]["theme_dark"],
+        "midnight"
+    );
+    // Snippets are only bundled on request
+    assert!(!read.tables.contains_key("snippet"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snippets_are_opt_in() {
+    let db = configured_database().await;
+    let bundle = SettingsBundler::new(db)
+        .export(&categories(&[BundleCategory::Snippets]))
+        .await
+        .unwrap();
+
+    assert_eq!(bundle.tables.len(), 1);
+    assert_eq!(bundle.tables["snippet"]["greeting"]["text"], "Hello");
+}
+
+#[test]
+fn test_newer_bundle_versions_are_refused() {
+    let content = r#"{"format_version": 99, "future": "layout"}"#;
+    let result = SettingsBundle::parse(content, BundleFormat::Json);
+    assert_eq!(
+        result.unwrap_err(),
+        BundleError::UnsupportedVersion {
+            found: 99,
+            supported: 1
+        }
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_preview_diffs_and_selective_import() {
+    let source = configured_database().await;
+    let bundle = SettingsBundler::new(source)
+        .export(&BundleCategory::DEFAULT.into())
+        .await
+        .unwrap();
+
+    let target = migrated_database(USER_SETTINGS_MIGRATIONS).await;
+    execute(
+        &target,
+        "CREATE appearance_settings:main SET theme_dark = 'dawn'",
+    )
+    .await;
+    let bundler = SettingsBundler::new(target.clone());
+
+    let preview = bundler.preview(&bundle).await.unwrap();
+    let hotkeys = &preview.categories[&BundleCategory::Hotkeys];
+    assert_eq!(hotkeys.diffs.len(), 1);
+    assert_eq!(hotkeys.diffs[0].target, "hotkey_settings:launcher");
+    assert_eq!(hotkeys.diffs[0].kind, DiffKind::Added);
+
+    let settings = &preview.categories[&BundleCategory::Settings];
+    assert_eq!(settings.diffs.len(), 1);
+    let DiffKind::Changed(changes) = &settings.diffs[0].kind else {
+        panic!("Appearance settings should change");
+    };
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].field, "theme_dark");
+    assert_eq!(changes[0].current, Some(json!("dawn")));
+    assert_eq!(changes[0].imported, json!("midnight"));
+
+    let report = bundler
+        .import(&bundle, &categories(&[BundleCategory::Hotkeys]))
+        .await
+        .unwrap();
+    assert_eq!(report.written[&BundleCategory::Hotkeys], 1);
+    assert!(!report.written.contains_key(&BundleCategory::Settings));
+
+    let hotkey = record(&target, "hotkey_settings:launcher").await.unwrap();
+    assert_eq!(hotkey["modifiers"], json!(["cmd"]));
+    let appearance = record(&target, "appearance_settings:main").await.unwrap();
+    assert_eq!(appearance["theme_dark"], "dawn");
+
+    // Importing again changes nothing
+    let preview = bundler.preview(&bundle).await.unwrap();
+    assert_eq!(
+        preview.change_count(&categories(&[BundleCategory::Hotkeys])),
+        0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_plugin_configs_merge_into_store() {
+    let source_store = Arc::new(MemoryConfigStore::default());
+    source_store
+        .save_config(&weather_config("metric"))
+        .await
+        .unwrap();
+    let source = migrated_database(USER_SETTINGS_MIGRATIONS).await;
+    let bundle = SettingsBundler::new(source)
+        .with_plugin_configs(PluginConfigSource::new(source_store))
+        .export(&categories(&[BundleCategory::PluginConfigs]))
+        .await
+        .unwrap();
+
+    let store = Arc::new(MemoryConfigStore::default());
+    let mut local = weather_config("imperial");
+    local
+        .configuration
+        .insert("city".to_string(), json!("Oslo"));
+    store.save_config(&local).await.unwrap();
+    let target = migrated_database(USER_SETTINGS_MIGRATIONS).await;
+    let bundler = SettingsBundler::new(target).with_plugin_configs(
+        PluginConfigSource::new(store.clone()).with_fields("weather", weather_fields()),
+    );
+
+    bundler
+        .import(&bundle, &categories(&[BundleCategory::PluginConfigs]))
+        .await
+        .unwrap();
+    let restored = store.load_config("weather").await.unwrap().unwrap();
+    assert_eq!(restored.configuration["units"], "metric");
+    assert_eq!(restored.configuration["city"], "Oslo");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invalid_plugin_config_blocks_the_whole_import() {
+    let mut bundle = SettingsBundler::new(configured_database().await)
+        .export(&BundleCategory::DEFAULT.into())
+        .await
+        .unwrap();
+    let mut invalid = weather_config("metric");
+    invalid.configuration.insert("units".to_string(), json!(42));
+    bundle.plugin_configs.insert("weather".to_string(), invalid);
+    bundle.categories.insert(BundleCategory::PluginConfigs);
+
+    let target = migrated_database(USER_SETTINGS_MIGRATIONS).await;
+    let store = Arc::new(MemoryConfigStore::default());
+    let bundler = SettingsBundler::new(target.clone()).with_plugin_configs(
+        PluginConfigSource::new(store.clone()).with_fields("weather", weather_fields()),
+    );
+
+    let preview = bundler.preview(&bundle).await.unwrap();
+    let issues = preview.issues(&BundleCategory::DEFAULT.into());
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].target, "weather.units");
+
+    let result = bundler
+        .import(&bundle, &BundleCategory::DEFAULT.into())
+        .await;
+    assert!(matches!(result, Err(BundleError::Invalid(_))));
+    assert!(record(&target, "hotkey_settings:launcher").await.is_none());
+    assert!(store.load_config("weather").await.unwrap().is_none());
+
+    // The valid categories can still be imported on their own
+    bundler
+        .import(&bundle, &categories(&[BundleCategory::Hotkeys]))
+        .await
+        .unwrap();
+    assert!(record(&target, "hotkey_settings:launcher").await.is_some());
+}
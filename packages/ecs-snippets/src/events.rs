@@ -0,0 +1,59 @@
+//! Snippet request and notification events
+//!
+//! CRUD requests carry an `operation_id` that is echoed in [`SnippetOperationCompleted`], so
+//! callers can match results to their requests.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::template::Expansion;
+use crate::types::{Snippet, SnippetDraft, SnippetError};
+
+/// Create a snippet
+#[derive(Event, Debug, Clone)]
+pub struct SnippetCreateRequested {
+    pub operation_id: Uuid,
+    pub draft: SnippetDraft,
+}
+
+/// Replace the fields of a snippet
+#[derive(Event, Debug, Clone)]
+pub struct SnippetUpdateRequested {
+    pub operation_id: Uuid,
+    pub snippet_id: String,
+    pub draft: SnippetDraft,
+}
+
+/// Delete a snippet
+#[derive(Event, Debug, Clone)]
+pub struct SnippetDeleteRequested {
+    pub operation_id: Uuid,
+    pub snippet_id: String,
+}
+
+/// Result of a create, update or delete request
+#[derive(Event, Debug, Clone)]
+pub struct SnippetOperationCompleted {
+    pub operation_id: Uuid,
+    /// The created or updated snippet, or the deleted one
+    pub result: Result<Snippet, SnippetError>,
+}
+
+/// Expand a snippet and write the result to the clipboard
+#[derive(Event, Debug, Clone)]
+pub struct SnippetExpandRequested {
+    pub snippet_id: String,
+    /// Values for `{argument}` placeholders
+    pub arguments: HashMap<String, String>,
+    /// Also paste the expansion into the focused application
+    pub paste: bool,
+}
+
+/// Result of an expansion request
+#[derive(Event, Debug, Clone)]
+pub struct SnippetExpanded {
+    pub snippet_id: String,
+    pub result: Result<Expansion, SnippetError>,
+}
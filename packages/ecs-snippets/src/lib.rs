@@ -0,0 +1,28 @@
+//! Action Items ECS Snippets
+//!
+//! Bevy ECS plugin for reusable text snippets.
+//!
+//! - Snippets are stored in SurrealDB and managed through request events
+//! - Keywords select a snippet directly from the launcher, with the rest of the query used as
+//!   its arguments
+//! - Templates support `{clipboard}`, `{date}`, `{date:FORMAT}`, `{cursor}`,
+//!   `{argument name="..."}` and nested `{snippet name="..."}` placeholders
+//! - Expansions are written to the clipboard and optionally pasted into the focused app
+
+pub mod events;
+pub mod library;
+pub mod persistence;
+pub mod plugin;
+pub mod systems;
+pub mod template;
+pub mod types;
+
+pub use events::{
+    SnippetCreateRequested, SnippetDeleteRequested, SnippetExpandRequested, SnippetExpanded,
+    SnippetOperationCompleted, SnippetUpdateRequested,
+};
+pub use library::{SnippetLibrary, SnippetMatch};
+pub use plugin::SnippetsPlugin;
+pub use systems::{SNIPPET_ACTION_PREFIX, SNIPPETS_PROVIDER_ID};
+pub use template::{ArgumentSpec, Expansion, Template};
+pub use types::{Snippet, SnippetDraft, SnippetError};
@@ -0,0 +1,306 @@
+//! In-memory snippet library with validation, search and expansion
+//!
+//! The resource mirrors the `snippet` table; systems write every change through to the
+//! database, so this type itself stays free of I/O and is easy to test.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use action_items_ecs_surrealdb::LoadState;
+use bevy::prelude::*;
+use chrono::{DateTime, Local};
+
+use crate::template::{ArgumentSpec, Expansion, ExpansionContext, Template};
+use crate::types::{Snippet, SnippetDraft, SnippetError};
+
+/// A snippet matching a launcher query
+#[derive(Debug, Clone)]
+pub struct SnippetMatch<'a> {
+    pub snippet: &'a Snippet,
+    pub score: f32,
+    /// Text typed after the snippet's keyword, used as positional arguments
+    pub argument_input: Option<String>,
+}
+
+/// All snippets, ordered by name
+#[derive(Resource, Debug, Default)]
+pub struct SnippetLibrary {
+    pub load_state: LoadState,
+    snippets: Vec<Snippet>,
+}
+
+impl SnippetLibrary {
+    /// Merge snippets loaded from the database into the library
+    ///
+    /// Returns snippets created before loading finished, which still need to be written to the
+    /// database.
+    pub fn load(&mut self, stored: Vec<Snippet>) -> Vec<Snippet> {
+        let unsaved: Vec<Snippet> = std::mem::take(&mut self.snippets)
+            .into_iter()
+            .filter(|snippet| !stored.iter().any(|s| s.id == snippet.id))
+            .collect();
+        self.snippets = stored;
+        self.snippets.extend(unsaved.iter().cloned());
+        self.sort();
+        self.load_state = LoadState::Loaded;
+        unsaved
+    }
+
+    /// Create a snippet from a draft
+    pub fn create(&mut self, draft: SnippetDraft, now: i64) -> Result<Snippet, SnippetError> {
+        let draft = self.validate(draft, None)?;
+        let snippet = Snippet {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            name: draft.name,
+            keyword: draft.keyword,
+            text: draft.text,
+            created_at: now,
+            updated_at: now,
+        };
+        self.snippets.push(snippet.clone());
+        self.sort();
+        Ok(snippet)
+    }
+
+    /// Replace the fields of an existing snippet
+    pub fn update(
+        &mut self,
+        id: &str,
+        draft: SnippetDraft,
+        now: i64,
+    ) -> Result<Snippet, SnippetError> {
+        let draft = self.validate(draft, Some(id))?;
+        let snippet = self
+            .snippets
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| SnippetError::NotFound(id.to_string()))?;
+        snippet.name = draft.name;
+        snippet.keyword = draft.keyword;
+        snippet.text = draft.text;
+        snippet.updated_at = now;
+        let snippet = snippet.clone();
+        self.sort();
+        Ok(snippet)
+    }
+
+    pub fn remove(&mut self, id: &str) -> Result<Snippet, SnippetError> {
+        let index = self
+            .snippets
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or_else(|| SnippetError::NotFound(id.to_string()))?;
+        Ok(self.snippets.remove(index))
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Snippet> {
+        self.snippets.iter().find(|s| s.id == id)
+    }
+
+    /// Find a snippet by keyword, or by name ignoring case
+    pub fn find(&self, name_or_keyword: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|s| s.keyword.as_deref() == Some(name_or_keyword))
+            .or_else(|| {
+                self.snippets
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(name_or_keyword))
+            })
+    }
+
+    pub fn snippets(&self) -> &[Snippet] {
+        &self.snippets
+    }
+
+    pub fn len(&self) -> usize {
+        self.snippets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snippets.is_empty()
+    }
+
+    /// Arguments needed to expand a snippet, including those of nested snippets
+    pub fn arguments(&self, id: &str) -> Result<Vec<ArgumentSpec>, SnippetError> {
+        let snippet = self
+            .get(id)
+            .ok_or_else(|| SnippetError::NotFound(id.to_string()))?;
+        let mut arguments: Vec<ArgumentSpec> = Vec::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![snippet.text.clone()];
+
+        while let Some(text) = pending.pop() {
+            let template = Template::parse(&text)?;
+            for argument in template.arguments() {
+                if !arguments.iter().any(|a| a.name == argument.name) {
+                    arguments.push(argument);
+                }
+            }
+            for name in template.nested_snippets() {
+                if visited.insert(name.to_string())
+                    && let Some(nested) = self.find(name)
+                {
+                    pending.push(nested.text.clone());
+                }
+            }
+        }
+        Ok(arguments)
+    }
+
+    /// Expand a snippet with the given argument values
+    pub fn expand(
+        &self,
+        id: &str,
+        arguments: &HashMap<String, String>,
+        now: DateTime<Local>,
+        clipboard: &dyn Fn() -> Option<String>,
+    ) -> Result<Expansion, SnippetError> {
+        let snippet = self
+            .get(id)
+            .ok_or_else(|| SnippetError::NotFound(id.to_string()))?;
+        let resolve = |name: &str| {
+            self.find(name)
+                .and_then(|nested| Template::parse(&nested.text).ok())
+        };
+        let context = ExpansionContext {
+            arguments,
+            now,
+            clipboard,
+            resolve: &resolve,
+        };
+        Template::parse(&snippet.text)?.expand(&context)
+    }
+
+    /// Search snippets by keyword, name and text, best matches first
+    ///
+    /// A query equal to a keyword selects that snippet; a keyword followed by more text selects
+    /// it with the rest of the query as [`SnippetMatch::argument_input`].
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SnippetMatch<'_>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let lowered = query.to_lowercase();
+        let terms: Vec<&str> = lowered.split_whitespace().collect();
+
+        let mut matches: Vec<SnippetMatch<'_>> = self
+            .snippets
+            .iter()
+            .filter_map(|snippet| {
+                if let Some(keyword) = &snippet.keyword {
+                    if keyword == query {
+                        return Some((snippet, 1.0, None));
+                    }
+                    if let Some(rest) = query.strip_prefix(keyword.as_str())
+                        && rest.starts_with(char::is_whitespace)
+                    {
+                        return Some((snippet, 0.98, Some(rest.trim().to_string())));
+                    }
+                }
+
+                let name = snippet.name.to_lowercase();
+                if name.starts_with(&lowered) {
+                    return Some((snippet, 0.85, None));
+                }
+                if terms.iter().all(|term| name.contains(term)) {
+                    return Some((snippet, 0.75, None));
+                }
+                let text = snippet.text.to_lowercase();
+                if terms
+                    .iter()
+                    .all(|term| name.contains(term) || text.contains(term))
+                {
+                    return Some((snippet, 0.6, None));
+                }
+                None
+            })
+            .map(|(snippet, score, argument_input)| SnippetMatch {
+                snippet,
+                score,
+                argument_input,
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    fn validate(
+        &self,
+        mut draft: SnippetDraft,
+        except_id: Option<&str>,
+    ) -> Result<SnippetDraft, SnippetError> {
+        draft.name = draft.name.trim().to_string();
+        if draft.name.is_empty() {
+            return Err(SnippetError::EmptyName);
+        }
+        draft.keyword = draft
+            .keyword
+            .map(|keyword| keyword.trim().to_string())
+            .filter(|keyword| !keyword.is_empty());
+        if let Some(keyword) = &draft.keyword
+            && keyword.contains(char::is_whitespace)
+        {
+            return Err(SnippetError::InvalidKeyword(keyword.clone()));
+        }
+        Template::parse(&draft.text)?;
+
+        for other in self
+            .snippets
+            .iter()
+            .filter(|s| Some(s.id.as_str()) != except_id)
+        {
+            if other.name.eq_ignore_ascii_case(&draft.name) {
+                return Err(SnippetError::DuplicateName(draft.name));
+            }
+            if draft.keyword.is_some() && other.keyword == draft.keyword {
+                return Err(SnippetError::DuplicateKeyword(
+                    draft.keyword.unwrap_or_default(),
+                ));
+            }
+        }
+        Ok(draft)
+    }
+
+    fn sort(&mut self) {
+        self.snippets
+            .sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    }
+}
+
+/// Assign text typed after a keyword to arguments in order
+///
+/// Words fill the arguments one by one and the last argument takes the remaining text, so a
+/// snippet with a single argument receives everything typed after its keyword.
+pub fn bind_positional(arguments: &[ArgumentSpec], input: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut rest = input.trim();
+    for (index, argument) in arguments.iter().enumerate() {
+        if rest.is_empty() {
+            break;
+        }
+        let value = if index + 1 == arguments.len() {
+            std::mem::take(&mut rest)
+        } else {
+            let (word, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            rest = remainder.trim_start();
+            word
+        };
+        values.insert(argument.name.clone(), value.to_string());
+    }
+    values
+}
+
+/// Current time as Unix milliseconds
+pub fn unix_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
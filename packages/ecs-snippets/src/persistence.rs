@@ -0,0 +1,75 @@
+//! SurrealDB persistence for snippets
+
+use action_items_ecs_surrealdb::{DatabaseError, DatabaseService, LoadState, Persisted};
+use surrealdb::RecordId;
+
+use crate::library::SnippetLibrary;
+use crate::types::Snippet;
+
+/// Table holding snippets
+pub const SNIPPET_TABLE: &str = "snippet";
+
+/// Definition of the snippet table; keywords are indexed for launcher lookups
+pub const SNIPPET_SCHEMA: &str = r#"
+DEFINE TABLE IF NOT EXISTS snippet SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS name ON snippet TYPE string;
+DEFINE FIELD IF NOT EXISTS keyword ON snippet TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS text ON snippet TYPE string;
+DEFINE FIELD IF NOT EXISTS created_at ON snippet TYPE int;
+DEFINE FIELD IF NOT EXISTS updated_at ON snippet TYPE int;
+DEFINE INDEX IF NOT EXISTS snippet_keyword_idx ON snippet COLUMNS keyword;
+"#;
+
+fn record_id(id: &str) -> RecordId {
+    RecordId::from((SNIPPET_TABLE, id))
+}
+
+fn query_failed(error: impl std::fmt::Display) -> DatabaseError {
+    DatabaseError::QueryFailed(error.to_string())
+}
+
+impl Persisted for SnippetLibrary {
+    const NAME: &'static str = "snippets";
+    const SCHEMA: &'static str = SNIPPET_SCHEMA;
+
+    fn load_state(&self) -> LoadState {
+        self.load_state
+    }
+
+    fn set_load_state(&mut self, state: LoadState) {
+        self.load_state = state;
+    }
+}
+
+/// Load all snippets
+pub async fn load_all(db: &DatabaseService) -> Result<Vec<Snippet>, DatabaseError> {
+    // Record ids come back as `snippet:⟨key⟩`; the key alone is the snippet id
+    let mut response = db
+        .query("SELECT *, record::id(id) AS id FROM snippet")
+        .await?;
+    response.take::<Vec<Snippet>>(0).map_err(query_failed)
+}
+
+/// Upsert a snippet
+pub async fn save_snippet(db: &DatabaseService, snippet: &Snippet) -> Result<(), DatabaseError> {
+    // The record key carries the id, so it is not repeated in the content
+    let mut content = serde_json::to_value(snippet).map_err(query_failed)?;
+    if let Some(fields) = content.as_object_mut() {
+        fields.remove("id");
+        if snippet.keyword.is_none() {
+            fields.remove("keyword");
+        }
+    }
+    let sql = format!("UPSERT {} CONTENT {}", record_id(&snippet.id), content);
+    db.query(&sql).await?.check().map_err(query_failed)?;
+    Ok(())
+}
+
+/// Delete a snippet
+pub async fn delete_snippet(db: &DatabaseService, id: &str) -> Result<(), DatabaseError> {
+    db.query(&format!("DELETE {}", record_id(id)))
+        .await?
+        .check()
+        .map_err(query_failed)?;
+    Ok(())
+}
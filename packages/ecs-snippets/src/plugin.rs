@@ -0,0 +1,51 @@
+//! Snippets plugin
+
+use action_items_core::LauncherEvent;
+use action_items_ecs_search_aggregator::{
+    CurrentQuery, LocalSearchProviders, SearchRequested, SearchResultReceived,
+};
+use action_items_ecs_surrealdb::poll_persistence_tasks;
+use bevy::prelude::*;
+
+use crate::events::*;
+use crate::library::SnippetLibrary;
+use crate::systems::*;
+
+/// Plugin providing snippet storage, expansion and launcher search
+///
+/// Expansion writes to the clipboard through `ClipboardResource`, so `ClipboardPlugin` must be
+/// added as well. Persistence starts once `DatabasePlugin` has inserted the `DatabaseService`;
+/// without it, snippets are kept in memory for the session only.
+pub struct SnippetsPlugin;
+
+impl Plugin for SnippetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SnippetLibrary>()
+            .init_resource::<LocalSearchProviders>()
+            .init_resource::<CurrentQuery>()
+            .add_event::<SearchRequested>()
+            .add_event::<SearchResultReceived>()
+            .add_event::<LauncherEvent>()
+            .add_event::<SnippetCreateRequested>()
+            .add_event::<SnippetUpdateRequested>()
+            .add_event::<SnippetDeleteRequested>()
+            .add_event::<SnippetOperationCompleted>()
+            .add_event::<SnippetExpandRequested>()
+            .add_event::<SnippetExpanded>()
+            .add_systems(Startup, register_search_provider)
+            .add_systems(
+                Update,
+                (
+                    load_snippet_library,
+                    handle_snippet_crud_requests,
+                    handle_snippet_expand_requests,
+                    search_snippets,
+                    handle_snippet_tasks,
+                    poll_persistence_tasks::<SnippetLibrary>,
+                )
+                    .chain(),
+            );
+
+        tracing::info!("SnippetsPlugin initialized");
+    }
+}
@@ -0,0 +1,293 @@
+//! Bevy systems for snippet persistence, expansion and search
+
+use std::collections::HashMap;
+
+use action_items_core::{LauncherEvent, LauncherEventType};
+use action_items_ecs_clipboard::{
+    ArboardManager, ClipboardData, ClipboardFormat, ClipboardResource,
+};
+use action_items_ecs_search_aggregator::{
+    CurrentQuery, LocalSearchProviders, SearchRequested, SearchResult, SearchResultReceived,
+};
+use action_items_ecs_surrealdb::{DatabaseService, spawn_write, start_loading};
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use tracing::{info, warn};
+
+use crate::events::*;
+use crate::library::{SnippetLibrary, bind_positional, unix_millis};
+use crate::persistence;
+use crate::template::Expansion;
+use crate::types::{Snippet, SnippetError};
+
+/// Id the snippet library answers launcher searches under
+pub const SNIPPETS_PROVIDER_ID: &str = "snippets";
+/// Prefix of launcher action ids that expand and paste a snippet
+pub const SNIPPET_ACTION_PREFIX: &str = "snippet_";
+/// Snippets listed for one query at most
+const MAX_SEARCH_RESULTS: usize = 20;
+/// Maximum characters of snippet text shown in a result description
+const PREVIEW_CHARS: usize = 80;
+
+/// Component for snippet paste tasks
+#[derive(Component)]
+pub struct SnippetTask(pub Task<CommandQueue>);
+
+/// Register snippets as a local search provider
+pub fn register_search_provider(mut providers: ResMut<LocalSearchProviders>) {
+    providers.register(SNIPPETS_PROVIDER_ID);
+}
+
+/// Apply the schema and load persisted snippets once the database is available
+pub fn load_snippet_library(
+    mut commands: Commands,
+    mut library: ResMut<SnippetLibrary>,
+    db_service: Option<Res<DatabaseService>>,
+) {
+    start_loading(
+        &mut commands,
+        library.as_mut(),
+        db_service.as_deref(),
+        |db| async move { persistence::load_all(&db).await },
+        |world, snippets: Vec<Snippet>| {
+            info!("Loaded {} snippets", snippets.len());
+            let Some(mut library) = world.get_resource_mut::<SnippetLibrary>() else {
+                return;
+            };
+            let unsaved = library.load(snippets);
+            for snippet in unsaved {
+                spawn_save(world, snippet);
+            }
+        },
+    );
+}
+
+/// Handle create, update and delete requests
+pub fn handle_snippet_crud_requests(
+    mut commands: Commands,
+    mut create_events: EventReader<SnippetCreateRequested>,
+    mut update_events: EventReader<SnippetUpdateRequested>,
+    mut delete_events: EventReader<SnippetDeleteRequested>,
+    mut library: ResMut<SnippetLibrary>,
+    mut completed_events: EventWriter<SnippetOperationCompleted>,
+) {
+    let now = unix_millis();
+
+    for event in create_events.read() {
+        let result = library.create(event.draft.clone(), now);
+        if let Ok(snippet) = &result {
+            let snippet = snippet.clone();
+            commands.queue(move |world: &mut World| spawn_save(world, snippet));
+        }
+        completed_events.write(SnippetOperationCompleted {
+            operation_id: event.operation_id,
+            result,
+        });
+    }
+
+    for event in update_events.read() {
+        let result = library.update(&event.snippet_id, event.draft.clone(), now);
+        if let Ok(snippet) = &result {
+            let snippet = snippet.clone();
+            commands.queue(move |world: &mut World| spawn_save(world, snippet));
+        }
+        completed_events.write(SnippetOperationCompleted {
+            operation_id: event.operation_id,
+            result,
+        });
+    }
+
+    for event in delete_events.read() {
+        let result = library.remove(&event.snippet_id);
+        if let Ok(snippet) = &result {
+            let id = snippet.id.clone();
+            commands.queue(move |world: &mut World| spawn_delete(world, id));
+        }
+        completed_events.write(SnippetOperationCompleted {
+            operation_id: event.operation_id,
+            result,
+        });
+    }
+}
+
+/// Expand snippets requested directly or chosen from launcher results
+///
+/// The expansion is written to the clipboard. Launcher executions also paste it, using any
+/// text typed after the snippet's keyword as positional arguments.
+pub fn handle_snippet_expand_requests(
+    mut commands: Commands,
+    mut expand_events: EventReader<SnippetExpandRequested>,
+    mut launcher_events: EventReader<LauncherEvent>,
+    library: Res<SnippetLibrary>,
+    clipboard: Res<ClipboardResource>,
+    current_query: Option<Res<CurrentQuery>>,
+    mut expanded_events: EventWriter<SnippetExpanded>,
+) {
+    let launched = launcher_events
+        .read()
+        .filter_map(|event| match &event.event_type {
+            LauncherEventType::Execute(action_id) => {
+                let snippet_id = action_id.strip_prefix(SNIPPET_ACTION_PREFIX)?;
+                let query = current_query.as_ref().map_or("", |query| query.0.as_str());
+                Some(SnippetExpandRequested {
+                    snippet_id: snippet_id.to_string(),
+                    arguments: keyword_arguments(&library, snippet_id, query),
+                    paste: true,
+                })
+            },
+            _ => None,
+        });
+    let requests: Vec<SnippetExpandRequested> =
+        expand_events.read().cloned().chain(launched).collect();
+
+    for request in requests {
+        let read_clipboard = || match clipboard.get_sync(ClipboardFormat::Text) {
+            Ok(ClipboardData::Text(text)) => Some(text),
+            _ => None,
+        };
+        let result = library
+            .expand(
+                &request.snippet_id,
+                &request.arguments,
+                chrono::Local::now(),
+                &read_clipboard,
+            )
+            .and_then(|expansion| {
+                clipboard.set_sync(ClipboardData::Text(expansion.text.clone()))?;
+                Ok(expansion)
+            });
+
+        match &result {
+            Ok(expansion) if request.paste => spawn_paste(&mut commands, expansion),
+            Ok(_) => {},
+            Err(e) => warn!("Failed to expand snippet {}: {}", request.snippet_id, e),
+        }
+        expanded_events.write(SnippetExpanded {
+            snippet_id: request.snippet_id,
+            result,
+        });
+    }
+}
+
+/// Answer aggregated launcher searches from the snippet library
+pub fn search_snippets(
+    mut requests: EventReader<SearchRequested>,
+    mut results: EventWriter<SearchResultReceived>,
+    library: Res<SnippetLibrary>,
+) {
+    for request in requests.read() {
+        if !request
+            .requesting_plugins
+            .iter()
+            .any(|id| id == SNIPPETS_PROVIDER_ID)
+        {
+            continue;
+        }
+
+        let started = std::time::Instant::now();
+        let matches = library
+            .search(&request.query, MAX_SEARCH_RESULTS)
+            .into_iter()
+            .map(|found| SearchResult {
+                title: found.snippet.name.clone(),
+                description: describe_snippet(&library, found.snippet),
+                action: format!("{SNIPPET_ACTION_PREFIX}{}", found.snippet.id),
+                icon: Some("📝".to_string()),
+                score: found.score,
+                plugin_id: SNIPPETS_PROVIDER_ID.to_string(),
+                title_matches: Vec::new(),
+            })
+            .collect();
+
+        results.write(SearchResultReceived {
+            search_id: request.search_id,
+            plugin_id: SNIPPETS_PROVIDER_ID.to_string(),
+            results: matches,
+            execution_time_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+}
+
+/// Poll snippet tasks
+pub fn handle_snippet_tasks(mut commands: Commands, mut tasks: Query<(Entity, &mut SnippetTask)>) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.0)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Arguments typed after the snippet's keyword in the launcher query
+fn keyword_arguments(
+    library: &SnippetLibrary,
+    snippet_id: &str,
+    query: &str,
+) -> HashMap<String, String> {
+    let input = library
+        .search(query, usize::MAX)
+        .into_iter()
+        .find(|found| found.snippet.id == snippet_id)
+        .and_then(|found| found.argument_input);
+    match (input, library.arguments(snippet_id)) {
+        (Some(input), Ok(arguments)) => bind_positional(&arguments, &input),
+        _ => HashMap::new(),
+    }
+}
+
+fn describe_snippet(library: &SnippetLibrary, snippet: &Snippet) -> String {
+    let mut preview: String = snippet
+        .text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if preview.chars().count() > PREVIEW_CHARS {
+        preview = preview.chars().take(PREVIEW_CHARS - 1).collect();
+        preview.push('…');
+    }
+
+    let arguments = library.arguments(&snippet.id).unwrap_or_default();
+    match (&snippet.keyword, arguments.is_empty()) {
+        (Some(keyword), false) => {
+            let names: Vec<&str> = arguments.iter().map(|a| a.name.as_str()).collect();
+            format!("{keyword} <{}> · {preview}", names.join("> <"))
+        },
+        (Some(keyword), true) => format!("{keyword} · {preview}"),
+        (None, _) => preview,
+    }
+}
+
+fn spawn_paste(commands: &mut Commands, expansion: &Expansion) {
+    let move_left = expansion.chars_after_cursor();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        if let Err(e) = ArboardManager::paste(move_left).await {
+            warn!("Failed to paste snippet: {}", e);
+        }
+        CommandQueue::default()
+    });
+    commands.spawn(SnippetTask(task));
+}
+
+/// Persist a snippet in the background once the library is backed by the database
+fn spawn_save(world: &mut World, snippet: Snippet) {
+    spawn_write::<SnippetLibrary, _>(world, move |db| async move {
+        if let Err(e) = persistence::save_snippet(&db, &snippet).await {
+            warn!(
+                "Failed to persist snippet {}: {}",
+                snippet.id,
+                SnippetError::from(e)
+            );
+        }
+    });
+}
+
+/// Delete a snippet in the background once the library is backed by the database
+fn spawn_delete(world: &mut World, id: String) {
+    spawn_write::<SnippetLibrary, _>(world, move |db| async move {
+        if let Err(e) = persistence::delete_snippet(&db, &id).await {
+            warn!("Failed to delete snippet {}: {}", id, SnippetError::from(e));
+        }
+    });
+}
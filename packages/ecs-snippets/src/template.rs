@@ -0,0 +1,282 @@
+//! Snippet template language
+//!
+//! Templates are plain text with placeholders in braces:
+//!
+//! - `{clipboard}` - current clipboard text
+//! - `{date}` / `{date:FORMAT}` - current local date, with an optional `strftime` format
+//! - `{cursor}` - where the cursor is placed after pasting
+//! - `{argument name="NAME"}` / `{argument name="NAME" default="VALUE"}` - a value supplied
+//!   when the snippet is expanded
+//! - `{snippet name="NAME"}` - the expansion of another snippet, by name or keyword
+//!
+//! Braces that do not form one of these placeholders are kept as literal text, so code and
+//! JSON snippets need no escaping.
+
+use std::cell::OnceCell;
+use std::collections::HashMap;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+
+use crate::types::SnippetError;
+
+/// Default format of `{date}`
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// Maximum depth of nested snippet placeholders
+pub const MAX_NESTING_DEPTH: usize = 8;
+
+/// A parsed template segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Literal(String),
+    Clipboard,
+    Date {
+        format: Option<String>,
+    },
+    Cursor,
+    Argument {
+        name: String,
+        default: Option<String>,
+    },
+    Snippet {
+        name: String,
+    },
+}
+
+/// An argument a template asks for when expanded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentSpec {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+/// Result of expanding a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion {
+    pub text: String,
+    /// Character offset of the first `{cursor}` placeholder, if any
+    pub cursor: Option<usize>,
+}
+
+impl Expansion {
+    /// Number of characters after the cursor position, i.e. how far to move left after pasting
+    pub fn chars_after_cursor(&self) -> usize {
+        self.cursor
+            .map_or(0, |cursor| self.text.chars().count().saturating_sub(cursor))
+    }
+}
+
+/// Inputs available while expanding a template
+pub struct ExpansionContext<'a> {
+    /// Values for `{argument}` placeholders
+    pub arguments: &'a HashMap<String, String>,
+    /// Time used for `{date}` placeholders
+    pub now: DateTime<Local>,
+    /// Reads the clipboard; only called if a `{clipboard}` placeholder is expanded
+    pub clipboard: &'a dyn Fn() -> Option<String>,
+    /// Looks up the template of a nested snippet by name or keyword
+    pub resolve: &'a dyn Fn(&str) -> Option<Template>,
+}
+
+/// A parsed snippet template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse template text
+    pub fn parse(text: &str) -> Result<Self, SnippetError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = text;
+
+        while let Some(open) = rest.find('{') {
+            literal.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            // A nested `{` means this brace does not start a placeholder
+            let close = after
+                .find('}')
+                .filter(|&close| !after[..close].contains('{'));
+            let Some(close) = close else {
+                literal.push('{');
+                rest = after;
+                continue;
+            };
+
+            match parse_placeholder(&after[..close])? {
+                Some(segment) => {
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(segment);
+                },
+                None => literal.push_str(&rest[open..open + close + 2]),
+            }
+            rest = &after[close + 1..];
+        }
+
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Arguments used directly by this template, in order of first use
+    pub fn arguments(&self) -> Vec<ArgumentSpec> {
+        let mut arguments: Vec<ArgumentSpec> = Vec::new();
+        for segment in &self.segments {
+            if let Segment::Argument { name, default } = segment {
+                match arguments.iter_mut().find(|a| &a.name == name) {
+                    Some(existing) => {
+                        if existing.default.is_none() {
+                            existing.default = default.clone();
+                        }
+                    },
+                    None => arguments.push(ArgumentSpec {
+                        name: name.clone(),
+                        default: default.clone(),
+                    }),
+                }
+            }
+        }
+        arguments
+    }
+
+    /// Names of snippets referenced by `{snippet}` placeholders
+    pub fn nested_snippets(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Snippet { name } => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Expand the template
+    ///
+    /// Nested snippets share the argument values and clipboard read of the outer snippet.
+    pub fn expand(&self, context: &ExpansionContext<'_>) -> Result<Expansion, SnippetError> {
+        let clipboard = OnceCell::new();
+        let mut expansion = Expansion {
+            text: String::new(),
+            cursor: None,
+        };
+        let mut stack = Vec::new();
+        self.expand_into(context, &clipboard, &mut stack, &mut expansion)?;
+        Ok(expansion)
+    }
+
+    fn expand_into(
+        &self,
+        context: &ExpansionContext<'_>,
+        clipboard: &OnceCell<Option<String>>,
+        stack: &mut Vec<String>,
+        out: &mut Expansion,
+    ) -> Result<(), SnippetError> {
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.text.push_str(text),
+                Segment::Clipboard => {
+                    let text = clipboard.get_or_init(|| (context.clipboard)());
+                    out.text.push_str(text.as_deref().unwrap_or_default());
+                },
+                Segment::Date { format } => {
+                    let format = format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT);
+                    out.text.push_str(&context.now.format(format).to_string());
+                },
+                Segment::Cursor => {
+                    if out.cursor.is_none() {
+                        out.cursor = Some(out.text.chars().count());
+                    }
+                },
+                Segment::Argument { name, default } => {
+                    let value = context
+                        .arguments
+                        .get(name)
+                        .or(default.as_ref())
+                        .ok_or_else(|| SnippetError::MissingArgument(name.clone()))?;
+                    out.text.push_str(value);
+                },
+                Segment::Snippet { name } => {
+                    if stack.iter().any(|entered| entered == name) {
+                        return Err(SnippetError::RecursiveSnippet(name.clone()));
+                    }
+                    if stack.len() >= MAX_NESTING_DEPTH {
+                        return Err(SnippetError::NestingTooDeep(MAX_NESTING_DEPTH));
+                    }
+                    let nested = (context.resolve)(name)
+                        .ok_or_else(|| SnippetError::UnknownSnippet(name.clone()))?;
+                    stack.push(name.clone());
+                    nested.expand_into(context, clipboard, stack, out)?;
+                    stack.pop();
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the text between braces; `None` means it is not a placeholder
+fn parse_placeholder(inner: &str) -> Result<Option<Segment>, SnippetError> {
+    let inner = inner.trim();
+    if inner == "clipboard" {
+        return Ok(Some(Segment::Clipboard));
+    }
+    if inner == "cursor" {
+        return Ok(Some(Segment::Cursor));
+    }
+    if inner == "date" {
+        return Ok(Some(Segment::Date { format: None }));
+    }
+    if let Some(format) = inner.strip_prefix("date:") {
+        if format.is_empty() || StrftimeItems::new(format).any(|item| item == Item::Error) {
+            return Err(SnippetError::InvalidTemplate(format!(
+                "invalid date format '{format}'"
+            )));
+        }
+        return Ok(Some(Segment::Date {
+            format: Some(format.to_string()),
+        }));
+    }
+
+    let (kind, attributes) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+    if kind != "argument" && kind != "snippet" {
+        return Ok(None);
+    }
+    let Some(mut attributes) = parse_attributes(attributes) else {
+        return Ok(None);
+    };
+    let name = attributes
+        .remove("name")
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| SnippetError::InvalidTemplate(format!("{{{kind}}} needs a name")))?;
+
+    Ok(Some(if kind == "argument" {
+        Segment::Argument {
+            name,
+            default: attributes.remove("default"),
+        }
+    } else {
+        Segment::Snippet { name }
+    }))
+}
+
+/// Parse `key="value"` pairs separated by whitespace
+fn parse_attributes(mut text: &str) -> Option<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            return Some(attributes);
+        }
+        let (key, rest) = text.split_once('=')?;
+        let rest = rest.strip_prefix('"')?;
+        let (value, rest) = rest.split_once('"')?;
+        attributes.insert(key.trim().to_string(), value.to_string());
+        text = rest;
+    }
+}
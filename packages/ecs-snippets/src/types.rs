@@ -0,0 +1,84 @@
+//! Snippet types and errors
+
+use serde::{Deserialize, Serialize};
+
+/// A saved snippet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Unique id, also used as the database record key
+    pub id: String,
+    /// Display name, also used to reference the snippet from other templates
+    pub name: String,
+    /// Short text that selects the snippet directly from the launcher
+    pub keyword: Option<String>,
+    /// Template text, see [`crate::template`]
+    pub text: String,
+    /// Unix timestamp (milliseconds) of creation
+    pub created_at: i64,
+    /// Unix timestamp (milliseconds) of the last change
+    pub updated_at: i64,
+}
+
+/// Fields of a snippet supplied when creating or updating it
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SnippetDraft {
+    pub name: String,
+    pub keyword: Option<String>,
+    pub text: String,
+}
+
+impl SnippetDraft {
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            keyword: None,
+            text: text.into(),
+        }
+    }
+
+    pub fn with_keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keyword = Some(keyword.into());
+        self
+    }
+}
+
+/// Errors from snippet operations
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+pub enum SnippetError {
+    #[error("Snippet not found: {0}")]
+    NotFound(String),
+    #[error("Snippet name must not be empty")]
+    EmptyName,
+    #[error("A snippet named '{0}' already exists")]
+    DuplicateName(String),
+    #[error("Keyword '{0}' is already used by another snippet")]
+    DuplicateKeyword(String),
+    #[error("Keywords must be a single word: '{0}'")]
+    InvalidKeyword(String),
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+    #[error("No value for argument '{0}'")]
+    MissingArgument(String),
+    #[error("Template references unknown snippet '{0}'")]
+    UnknownSnippet(String),
+    #[error("Snippet '{0}' includes itself")]
+    RecursiveSnippet(String),
+    #[error("Snippets are nested more than {0} levels deep")]
+    NestingTooDeep(usize),
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl From<action_items_ecs_surrealdb::DatabaseError> for SnippetError {
+    fn from(error: action_items_ecs_surrealdb::DatabaseError) -> Self {
+        SnippetError::Database(error.to_string())
+    }
+}
+
+impl From<action_items_ecs_clipboard::ClipboardError> for SnippetError {
+    fn from(error: action_items_ecs_clipboard::ClipboardError) -> Self {
+        SnippetError::Clipboard(error.to_string())
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use action_items_ecs_snippets::library::bind_positional;
+use action_items_ecs_snippets::template::{ExpansionContext, Segment};
+use action_items_ecs_snippets::{SnippetDraft, SnippetError, SnippetLibrary, Template};
+use chrono::{Local, TimeZone};
+
+fn expand(text: &str, arguments: &[(&str, &str)]) -> Result<String, SnippetError> {
+    let arguments: HashMap<String, String> = arguments
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let context = ExpansionContext {
+        arguments: &arguments,
+        now: Local.with_ymd_and_hms(2025, 3, 9, 14, 30, 0).unwrap(),
+        clipboard: &|| Some("copied".to_string()),
+        resolve: &|_| None,
+    };
+    Template::parse(text)?.expand(&context).map(|e| e.text)
+}
+
+#[test]
+fn test_placeholders() {
+    assert_eq!(expand("{clipboard}!", &[]).unwrap(), "copied!");
+    assert_eq!(expand("{date}", &[]).unwrap(), "2025-03-09");
+    assert_eq!(expand("{date:%d/%m %H:%M}", &[]).unwrap(), "09/03 14:30");
+    assert_eq!(
+        expand("Hi {argument name=\"who\"}", &[("who", "Sam")]).unwrap(),
+        "Hi Sam"
+    );
+    assert_eq!(
+        expand("Hi {argument name=\"who\" default=\"there\"}", &[]).unwrap(),
+        "Hi there"
+    );
+    assert_eq!(
+        expand("Hi {argument name=\"who\"}", &[]),
+        Err(SnippetError::MissingArgument("who".to_string()))
+    );
+}
+
+#[test]
+fn test_unknown_braces_are_literal() {
+    assert_eq!(expand("{\"a\": {b}}", &[]).unwrap(), "{\"a\": {b}}");
+    assert_eq!(expand("fn main() {}", &[]).unwrap(), "fn main() {}");
+    assert_eq!(expand("open { brace", &[]).unwrap(), "open { brace");
+    assert!(matches!(
+        Template::parse("{date:%Q}"),
+        Err(SnippetError::InvalidTemplate(_))
+    ));
+}
+
+#[test]
+fn test_cursor_position() {
+    let template = Template::parse("<div>{cursor}</div>").unwrap();
+    assert_eq!(template.segments()[1], Segment::Cursor);
+
+    let context = ExpansionContext {
+        arguments: &HashMap::new(),
+        now: Local::now(),
+        clipboard: &|| None,
+        resolve: &|_| None,
+    };
+    let expansion = template.expand(&context).unwrap();
+    assert_eq!(expansion.text, "<div></div>");
+    assert_eq!(expansion.cursor, Some(5));
+    assert_eq!(expansion.chars_after_cursor(), 6);
+}
+
+#[test]
+fn test_nested_snippets() {
+    let mut library = SnippetLibrary::default();
+    library
+        .create(
+            SnippetDraft::new("Signature", "-- {argument name=\"name\"}"),
+            0,
+        )
+        .unwrap();
+    let mail = library
+        .create(
+            SnippetDraft::new("Mail", "Thanks!\n{snippet name=\"signature\"}").with_keyword("ty"),
+            0,
+        )
+        .unwrap();
+
+    let arguments = library.arguments(&mail.id).unwrap();
+    assert_eq!(arguments.len(), 1);
+    assert_eq!(arguments[0].name, "name");
+
+    let values = HashMap::from([("name".to_string(), "Sam".to_string())]);
+    let expansion = library
+        .expand(&mail.id, &values, Local::now(), &|| None)
+        .unwrap();
+    assert_eq!(expansion.text, "Thanks!\n-- Sam");
+
+    let looped = library
+        .create(SnippetDraft::new("Loop", "{snippet name=\"loop\"}"), 0)
+        .unwrap();
+    assert_eq!(
+        library.expand(&looped.id, &HashMap::new(), Local::now(), &|| None),
+        Err(SnippetError::RecursiveSnippet("loop".to_string()))
+    );
+}
+
+#[test]
+fn test_validation() {
+    let mut library = SnippetLibrary::default();
+    library
+        .create(
+            SnippetDraft::new("Address", "1 Main St").with_keyword("addr"),
+            0,
+        )
+        .unwrap();
+
+    assert_eq!(
+        library.create(SnippetDraft::new("address", "x"), 0),
+        Err(SnippetError::DuplicateName("address".to_string()))
+    );
+    assert_eq!(
+        library.create(SnippetDraft::new("Other", "x").with_keyword("addr"), 0),
+        Err(SnippetError::DuplicateKeyword("addr".to_string()))
+    );
+    assert_eq!(
+        library.create(SnippetDraft::new("Other", "x").with_keyword("a b"), 0),
+        Err(SnippetError::InvalidKeyword("a b".to_string()))
+    );
+    assert_eq!(
+        library.create(SnippetDraft::new("  ", "x"), 0),
+        Err(SnippetError::EmptyName)
+    );
+}
+
+#[test]
+fn test_keyword_search_and_positional_arguments() {
+    let mut library = SnippetLibrary::default();
+    let greet = library
+        .create(
+            SnippetDraft::new(
+                "Greeting",
+                "Hello {argument name=\"who\"}, {argument name=\"msg\"}",
+            )
+            .with_keyword("hi"),
+            0,
+        )
+        .unwrap();
+    library
+        .create(SnippetDraft::new("History", "hi there"), 0)
+        .unwrap();
+
+    let exact = library.search("hi", 10);
+    assert_eq!(exact[0].snippet.id, greet.id);
+    assert_eq!(exact[0].score, 1.0);
+
+    let with_arguments = library.search("hi Sam how are you", 10);
+    assert_eq!(with_arguments[0].snippet.id, greet.id);
+    let input = with_arguments[0].argument_input.clone().unwrap();
+
+    let values = bind_positional(&library.arguments(&greet.id).unwrap(), &input);
+    assert_eq!(values["who"], "Sam");
+    assert_eq!(values["msg"], "how are you");
+}
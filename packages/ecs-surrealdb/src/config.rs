@@ -1,6 +1,6 @@
 //! Database configuration, error types, and validation
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use action_items_common::AppDirectories;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -25,6 +25,8 @@ pub enum DatabaseError {
 pub enum DatabaseEngine {
     /// SurrealKV persistent storage with path
     SurrealKv(std::path::PathBuf),
+    /// In-memory storage, discarded when the service is dropped
+    Memory,
 }
 
 /// Database root credentials
@@ -36,6 +38,7 @@ pub struct DatabaseCredentials {
     pub password: String,
 }
 
+/// The `root`/`root` user of databases created before credentials were stored
 impl Default for DatabaseCredentials {
     fn default() -> Self {
         Self {
@@ -45,6 +48,86 @@ impl Default for DatabaseCredentials {
     }
 }
 
+impl DatabaseCredentials {
+    /// Root user with a random password
+    pub fn generate() -> Self {
+        Self {
+            username: "root".to_string(),
+            password: format!(
+                "{}{}",
+                uuid::Uuid::new_v4().simple(),
+                uuid::Uuid::new_v4().simple()
+            ),
+        }
+    }
+
+    /// Credentials stored beside a SurrealKV database, created on first use
+    ///
+    /// A database created before credentials were stored keeps the `root`/`root` user it was
+    /// created with; a new one gets a random password, readable only by the current user.
+    pub fn load_or_create(storage_path: &Path) -> Result<Self, DatabaseError> {
+        let credentials_path = Self::path_for(storage_path);
+        if let Ok(content) = std::fs::read_to_string(&credentials_path) {
+            return serde_json::from_str(&content).map_err(|e| {
+                DatabaseError::InvalidConfiguration(format!(
+                    "Invalid database credentials in {credentials_path:?}: {e}"
+                ))
+            });
+        }
+
+        let existing_database = std::fs::read_dir(storage_path)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false);
+        let credentials = if existing_database {
+            Self::default()
+        } else {
+            Self::generate()
+        };
+        credentials.save(&credentials_path)?;
+        Ok(credentials)
+    }
+
+    /// Credentials to open `engine` with: stored ones for SurrealKV, random ones in memory
+    pub fn for_engine(engine: &DatabaseEngine) -> Result<Self, DatabaseError> {
+        match engine {
+            DatabaseEngine::SurrealKv(storage_path) => Self::load_or_create(storage_path),
+            DatabaseEngine::Memory => Ok(Self::generate()),
+        }
+    }
+
+    fn path_for(storage_path: &Path) -> PathBuf {
+        let mut file_name = storage_path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_else(|| "database".into());
+        file_name.push(".credentials.json");
+        storage_path.with_file_name(file_name)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), DatabaseError> {
+        let write_error = |e: std::io::Error| {
+            DatabaseError::InvalidConfiguration(format!(
+                "Failed to store database credentials in {path:?}: {e}"
+            ))
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        let content = serde_json::to_string(self).map_err(|e| {
+            DatabaseError::InvalidConfiguration(format!("Unserializable credentials: {e}"))
+        })?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, content.as_bytes()))
+            .map_err(write_error)
+    }
+}
+
 /// Database configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -59,7 +142,9 @@ pub struct DatabaseConfig {
     /// Enable query logging
     pub enable_query_logging: bool,
     /// Root credentials for embedded database
-    pub credentials: DatabaseCredentials,
+    ///
+    /// When unset, [`DatabaseCredentials::for_engine`] picks them while connecting.
+    pub credentials: Option<DatabaseCredentials>,
     /// Enable all capabilities (scripting, live queries, etc.)
     pub enable_all_capabilities: bool,
 }
@@ -67,25 +152,36 @@ pub struct DatabaseConfig {
 impl Default for DatabaseConfig {
     fn default() -> Self {
         let app_dirs = AppDirectories::new();
-        let db_path = app_dirs.config_dir().join("database");
+        Self::surreal_kv(app_dirs.config_dir().join("database"))
+    }
+}
+
+impl DatabaseConfig {
+    /// Create configuration for persistent SurrealKV storage
+    pub fn surreal_kv<P: Into<std::path::PathBuf>>(storage_path: P) -> Self {
         Self {
             namespace: "action_items".to_string(),
             database: "main".to_string(),
-            engine: DatabaseEngine::SurrealKv(db_path),
+            engine: DatabaseEngine::SurrealKv(storage_path.into()),
             query_timeout_ms: 10000,
             enable_query_logging: true,
-            credentials: DatabaseCredentials::default(),
+            credentials: None,
             enable_all_capabilities: true,
         }
     }
-}
 
-impl DatabaseConfig {
-    /// Create configuration for persistent SurrealKV storage
-    pub fn surreal_kv<P: Into<std::path::PathBuf>>(storage_path: P) -> Self {
+    /// Create configuration for in-memory storage in a namespace of its own
+    ///
+    /// Nothing is written to disk, so this suits tests and throwaway sessions.
+    pub fn in_memory() -> Self {
         Self {
-            engine: DatabaseEngine::SurrealKv(storage_path.into()),
-            ..Default::default()
+            namespace: format!("test_{}", uuid::Uuid::new_v4().simple()),
+            database: "main".to_string(),
+            engine: DatabaseEngine::Memory,
+            query_timeout_ms: 10000,
+            enable_query_logging: true,
+            credentials: None,
+            enable_all_capabilities: true,
         }
     }
 
@@ -114,8 +210,17 @@ impl DatabaseConfig {
         }
 
         // Validate storage path security
-        let DatabaseEngine::SurrealKv(path) = &self.engine;
-        Self::validate_storage_path(path)?;
+        if let DatabaseEngine::SurrealKv(path) = &self.engine {
+            Self::validate_storage_path(path)?;
+        }
+
+        if let Some(credentials) = &self.credentials
+            && (credentials.username.is_empty() || credentials.password.is_empty())
+        {
+            return Err(DatabaseError::InvalidConfiguration(
+                "Database credentials cannot be empty".into(),
+            ));
+        }
 
         Ok(())
     }
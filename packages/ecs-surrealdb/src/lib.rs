@@ -6,12 +6,23 @@
 //! Uses SurrealDB v3.0's recommended LazyLock singleton pattern
 
 pub mod config;
+pub mod live;
+pub mod migrations;
+pub mod persisted;
 pub mod service;
 pub mod transactions;
 pub mod plugin;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Re-export public API
-pub use config::{DatabaseConfig, DatabaseEngine, DatabaseError};
+pub use config::{DatabaseConfig, DatabaseCredentials, DatabaseEngine, DatabaseError};
+pub use live::{LiveAction, LiveQuery, LiveQueryAppExt, LiveQueryEvent};
+pub use migrations::{Migration, MigrationError, MigrationFailure, MigrationReport};
+pub use persisted::{
+    LoadState, Persisted, PersistenceTask, persistent_database, poll_persistence_tasks,
+    spawn_write, start_loading,
+};
 pub use service::{DatabaseService, DatabaseServiceError, DatabaseShutdown};
 pub use transactions::TransactionContext;
 pub use plugin::DatabasePlugin;
\ No newline at end of file
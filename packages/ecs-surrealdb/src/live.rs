@@ -0,0 +1,286 @@
+//! Live queries delivered as Bevy events
+//!
+//! [`LiveQueryAppExt::add_live_query`] subscribes to every create, update and delete on a table
+//! once [`DatabaseService`] is available, and sends each change as a [`LiveQueryEvent`] in
+//! `PreUpdate`, so systems in `Update` react to writes from anywhere in the app the same frame.
+//!
+//! ```rust,ignore
+//! #[derive(Deserialize, Clone)]
+//! struct Appearance { theme: String }
+//!
+//! app.add_live_query::<Appearance>("appearance_settings")
+//!     .add_systems(Update, apply_theme);
+//!
+//! fn apply_theme(mut changes: EventReader<LiveQueryEvent<Appearance>>) {
+//!     for change in changes.read() {
+//!         if change.action != LiveAction::Delete {
+//!             set_theme(&change.record.theme);
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use surrealdb::Action;
+use tokio::sync::mpsc::{UnboundedReceiver, error::TryRecvError};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::config::{DatabaseError, validate_table_name};
+use crate::service::DatabaseService;
+
+/// What happened to a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiveAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// A change to a record in a table with a live query
+#[derive(Event, Debug, Clone)]
+pub struct LiveQueryEvent<T: Send + Sync + 'static> {
+    pub table: String,
+    pub action: LiveAction,
+    /// The record after a create or update, or as it was before a delete
+    pub record: T,
+}
+
+/// An open live query; dropping it kills the query
+pub struct LiveQuery<T: Send + Sync + 'static> {
+    table: String,
+    receiver: UnboundedReceiver<Result<LiveQueryEvent<T>, DatabaseError>>,
+    task: JoinHandle<()>,
+}
+
+impl<T: Send + Sync + 'static> LiveQuery<T> {
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Next change received, without waiting
+    ///
+    /// Returns `None` when no change is waiting. Once the query has ended, for example because
+    /// the table was removed, this returns `Some(Err(..))`.
+    pub fn try_next(&mut self) -> Option<Result<LiveQueryEvent<T>, DatabaseError>> {
+        match self.receiver.try_recv() {
+            Ok(change) => Some(change),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(DatabaseError::QueryFailed(format!(
+                "Live query on {} ended",
+                self.table
+            )))),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for LiveQuery<T> {
+    fn drop(&mut self) {
+        // Dropping the stream inside the task kills the query on the database
+        self.task.abort();
+    }
+}
+
+impl DatabaseService {
+    /// Subscribe to every create, update and delete on a table
+    ///
+    /// Records that do not deserialize as `T` are reported as errors by
+    /// [`LiveQuery::try_next`] without ending the query.
+    pub async fn live_table<T>(&self, table: &str) -> Result<LiveQuery<T>, DatabaseError>
+    where
+        T: DeserializeOwned + Unpin + Send + Sync + 'static,
+    {
+        validate_table_name(table)?;
+        let db = self.db().clone();
+        let table = table.to_string();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (opened_sender, opened) = oneshot::channel();
+
+        // The stream lives on the database's runtime, which it needs to kill the query when
+        // dropped
+        let task_table = table.clone();
+        let task = self.runtime().spawn(async move {
+            let mut stream = match db.select::<Vec<T>>(task_table.as_str()).live().await {
+                Ok(stream) => {
+                    let _ = opened_sender.send(Ok(()));
+                    stream
+                },
+                Err(e) => {
+                    let _ = opened_sender.send(Err(DatabaseError::QueryFailed(e.to_string())));
+                    return;
+                },
+            };
+
+            while let Some(notification) = stream.next().await {
+                let change = match notification {
+                    Ok(notification) => {
+                        let action = match notification.action {
+                            Action::Create => LiveAction::Create,
+                            Action::Update => LiveAction::Update,
+                            Action::Delete => LiveAction::Delete,
+                            _ => continue,
+                        };
+                        Ok(LiveQueryEvent {
+                            table: task_table.clone(),
+                            action,
+                            record: notification.data,
+                        })
+                    },
+                    Err(e) => Err(DatabaseError::QueryFailed(e.to_string())),
+                };
+                if sender.send(change).is_err() {
+                    break;
+                }
+            }
+            debug!("Live query on {} stopped", task_table);
+        });
+
+        match opened.await {
+            Ok(Ok(())) => Ok(LiveQuery {
+                table,
+                receiver,
+                task,
+            }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(DatabaseError::QueryFailed(format!(
+                "Live query on {table} stopped before it started"
+            ))),
+        }
+    }
+}
+
+/// Registers live queries whose changes arrive as [`LiveQueryEvent`]s
+pub trait LiveQueryAppExt {
+    /// Send a [`LiveQueryEvent<T>`] for every change to `table`
+    ///
+    /// The query opens once [`DatabaseService`] is available. If it fails to open or ends, it is
+    /// reopened after a delay that grows with each failure in a row, up to 30 seconds. Several
+    /// tables can share a record type; [`LiveQueryEvent::table`] tells them apart.
+    fn add_live_query<T>(&mut self, table: &str) -> &mut Self
+    where
+        T: DeserializeOwned + Unpin + Send + Sync + 'static;
+}
+
+impl LiveQueryAppExt for App {
+    fn add_live_query<T>(&mut self, table: &str) -> &mut Self
+    where
+        T: DeserializeOwned + Unpin + Send + Sync + 'static,
+    {
+        if !self.world().contains_resource::<LiveQueries<T>>() {
+            self.add_event::<LiveQueryEvent<T>>()
+                .insert_resource(LiveQueries::<T> { tables: Vec::new() })
+                .add_systems(PreUpdate, drive_live_queries::<T>);
+        }
+
+        let mut queries = self.world_mut().resource_mut::<LiveQueries<T>>();
+        if !queries.tables.iter().any(|query| query.table == table) {
+            queries.tables.push(TableQuery {
+                table: table.to_string(),
+                state: QueryState::Closed,
+                failures: 0,
+            });
+        }
+        self
+    }
+}
+
+/// Live queries for one record type
+#[derive(Resource)]
+struct LiveQueries<T: Send + Sync + 'static> {
+    tables: Vec<TableQuery<T>>,
+}
+
+struct TableQuery<T: Send + Sync + 'static> {
+    table: String,
+    state: QueryState<T>,
+    /// Failures in a row, reset once the query delivers a change
+    failures: u32,
+}
+
+impl<T: Send + Sync + 'static> TableQuery<T> {
+    /// Try again after a delay that doubles with each failure in a row
+    fn retry_later(&mut self) {
+        let delay = RETRY_DELAY
+            .saturating_mul(1 << self.failures.min(16))
+            .min(MAX_RETRY_DELAY);
+        self.failures += 1;
+        self.state = QueryState::Waiting(Instant::now() + delay);
+    }
+}
+
+enum QueryState<T: Send + Sync + 'static> {
+    Closed,
+    /// Reopen once the instant has passed
+    Waiting(Instant),
+    Opening(Task<Result<LiveQuery<T>, DatabaseError>>),
+    Open(LiveQuery<T>),
+}
+
+/// Delay before reopening a live query after its first failure
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Longest delay between attempts to reopen a live query
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Open live queries once the database is available and turn their changes into events
+fn drive_live_queries<T>(
+    database: Option<Res<DatabaseService>>,
+    mut queries: ResMut<LiveQueries<T>>,
+    mut events: EventWriter<LiveQueryEvent<T>>,
+) where
+    T: DeserializeOwned + Unpin + Send + Sync + 'static,
+{
+    let Some(database) = database else {
+        return;
+    };
+
+    for query in &mut queries.tables {
+        match &mut query.state {
+            QueryState::Waiting(retry_at) => {
+                if Instant::now() >= *retry_at {
+                    query.state = QueryState::Closed;
+                }
+            },
+            QueryState::Closed => {
+                let database = database.clone();
+                let table = query.table.clone();
+                query.state = QueryState::Opening(
+                    AsyncComputeTaskPool::get()
+                        .spawn(async move { database.live_table::<T>(&table).await }),
+                );
+            },
+            QueryState::Opening(task) => match block_on(future::poll_once(task)) {
+                Some(Ok(live_query)) => {
+                    debug!("Live query on {} opened", query.table);
+                    query.state = QueryState::Open(live_query);
+                },
+                Some(Err(e)) => {
+                    warn!("Failed to open live query on {}: {}", query.table, e);
+                    query.retry_later();
+                },
+                None => {},
+            },
+            QueryState::Open(live_query) => loop {
+                match live_query.receiver.try_recv() {
+                    Ok(Ok(event)) => {
+                        query.failures = 0;
+                        events.write(event);
+                    },
+                    Ok(Err(e)) => warn!("Live query on {}: {}", query.table, e),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        warn!("Live query on {} ended; reopening", query.table);
+                        query.retry_later();
+                        break;
+                    },
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Versioned schema migrations recorded in a `schema_migrations` table
+//!
+//! Each migration runs in its own transaction together with the record marking it applied, so a
+//! failing migration leaves the database exactly as the previous one left it. Migrations that
+//! are already recorded are skipped, and statements should use `IF NOT EXISTS` or `OVERWRITE`
+//! so that re-running one against a database that predates version tracking is harmless.
+
+use std::fmt;
+
+use futures::FutureExt;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::config::DatabaseError;
+use crate::service::DatabaseService;
+
+/// Table recording applied migrations, one record per version
+pub const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+const MIGRATIONS_SCHEMA: &str = r#"
+DEFINE TABLE IF NOT EXISTS schema_migrations SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS version ON schema_migrations TYPE int;
+DEFINE FIELD IF NOT EXISTS name ON schema_migrations TYPE string;
+DEFINE FIELD IF NOT EXISTS applied_at ON schema_migrations TYPE datetime DEFAULT time::now();
+"#;
+
+/// A numbered up-migration
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Position in the migration sequence; versions must be unique and ascending
+    pub version: u32,
+    pub name: &'static str,
+    /// SurrealQL statements that apply the migration
+    pub up: &'static str,
+}
+
+/// What a migration run did, or would do in a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    /// Versions recorded before this run
+    pub already_applied: Vec<u32>,
+    /// Versions applied by this run, or that a dry run would apply
+    pub applied: Vec<u32>,
+    pub failed: Option<MigrationFailure>,
+    /// Versions not attempted because an earlier one failed
+    pub not_attempted: Vec<u32>,
+}
+
+/// The migration that stopped a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationFailure {
+    pub version: u32,
+    pub name: String,
+    pub reason: String,
+}
+
+/// Migration run errors
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("Invalid migration list: {0}")]
+    InvalidMigrations(String),
+    #[error("Could not read applied migrations: {0}")]
+    Database(#[from] DatabaseError),
+    /// A migration failed and was rolled back; the report says what was applied before it
+    #[error("{0}")]
+    Failed(MigrationReport),
+}
+
+impl MigrationReport {
+    /// Whether every pending migration was applied, or would be in a dry run
+    pub fn is_success(&self) -> bool {
+        self.failed.is_none()
+    }
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let applied = if self.dry_run { "would apply" } else { "applied" };
+        match &self.failed {
+            Some(failure) => write!(
+                f,
+                "Migration {} ({}) failed and was rolled back: {}; {applied} {:?} before it, \
+                 not attempted {:?}",
+                failure.version, failure.name, failure.reason, self.applied, self.not_attempted
+            ),
+            None => write!(
+                f,
+                "{applied} migrations {:?}, {} already applied",
+                self.applied,
+                self.already_applied.len()
+            ),
+        }
+    }
+}
+
+impl DatabaseService {
+    /// Apply pending migrations in version order, each in its own transaction
+    ///
+    /// A dry run reports the pending migrations without changing the database. On failure the
+    /// failing migration is rolled back, later ones are not attempted and migrations applied
+    /// before it stay applied.
+    pub async fn migrate(
+        &self,
+        migrations: &[Migration],
+        dry_run: bool,
+    ) -> Result<MigrationReport, MigrationError> {
+        validate_migrations(migrations)?;
+        if !dry_run {
+            self.execute_schema(MIGRATIONS_SCHEMA).await?;
+        }
+
+        let already_applied = self.applied_migrations().await?;
+        let mut report = MigrationReport {
+            dry_run,
+            already_applied: already_applied.clone(),
+            ..Default::default()
+        };
+
+        let mut pending = migrations
+            .iter()
+            .filter(|migration| !already_applied.contains(&migration.version));
+        for migration in pending.by_ref() {
+            if dry_run {
+                debug!("Dry run: would apply migration {}", migration.version);
+                report.applied.push(migration.version);
+                continue;
+            }
+
+            match self.apply_migration(migration).await {
+                Ok(()) => {
+                    info!(
+                        "Applied migration {} ({})",
+                        migration.version, migration.name
+                    );
+                    report.applied.push(migration.version);
+                },
+                Err(e) => {
+                    report.failed = Some(MigrationFailure {
+                        version: migration.version,
+                        name: migration.name.to_string(),
+                        reason: e.to_string(),
+                    });
+                    break;
+                },
+            }
+        }
+        report.not_attempted = pending.map(|migration| migration.version).collect();
+
+        if report.is_success() {
+            Ok(report)
+        } else {
+            warn!("{}", report);
+            Err(MigrationError::Failed(report))
+        }
+    }
+
+    /// Versions recorded in `schema_migrations`, empty if the table does not exist yet
+    pub async fn applied_migrations(&self) -> Result<Vec<u32>, DatabaseError> {
+        let mut response = self
+            .query(&format!(
+                "SELECT VALUE version FROM {MIGRATIONS_TABLE} ORDER BY version"
+            ))
+            .await?;
+        response
+            .take::<Vec<u32>>(0)
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn apply_migration(&self, migration: &Migration) -> Result<(), DatabaseError> {
+        let Migration { version, up, .. } = *migration;
+        let name = serde_json::to_string(migration.name)
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        let record = format!(
+            "CREATE {MIGRATIONS_TABLE}:{version} SET version = {version}, name = {name}"
+        );
+
+        self.with_transaction(|ctx| async move {
+            for statements in [up, record.as_str()] {
+                ctx.query(statements)
+                    .await?
+                    .check()
+                    .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+            }
+            Ok(())
+        }
+        .boxed())
+        .await
+    }
+}
+
+fn validate_migrations(migrations: &[Migration]) -> Result<(), MigrationError> {
+    for pair in migrations.windows(2) {
+        if pair[0].version >= pair[1].version {
+            return Err(MigrationError::InvalidMigrations(format!(
+                "version {} follows version {}; versions must be unique and ascending",
+                pair[1].version, pair[0].version
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! Resources mirrored in database tables
+//!
+//! Libraries such as snippets or clipboard history keep their contents in a Bevy resource and
+//! write every change through to the database. [`start_loading`] applies the resource's schema
+//! and reads the stored records once [`DatabaseService`] is available. Changes made before
+//! loading finishes stay in memory; the resource merges them with the stored records, and
+//! [`spawn_write`] only reaches the database after that, so an early write never clobbers a
+//! stored record.
+//!
+//! ```rust,ignore
+//! impl Persisted for SnippetLibrary {
+//!     const NAME: &'static str = "snippets";
+//!     const SCHEMA: &'static str = SNIPPET_SCHEMA;
+//!
+//!     fn load_state(&self) -> LoadState { self.load_state }
+//!     fn set_load_state(&mut self, state: LoadState) { self.load_state = state; }
+//! }
+//!
+//! app.add_systems(Update, (load_snippet_library, poll_persistence_tasks::<SnippetLibrary>));
+//! ```
+
+use std::future::Future;
+use std::marker::PhantomData;
+
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use tracing::error;
+
+use crate::config::DatabaseError;
+use crate::service::DatabaseService;
+
+/// Loading state of a persisted resource relative to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadState {
+    #[default]
+    NotLoaded,
+    Loading,
+    Loaded,
+    /// Loading failed; the resource stays in memory for the session
+    Failed,
+}
+
+/// A resource loaded from and written through to the database
+pub trait Persisted: Resource {
+    /// What the resource holds, for log messages, such as `"snippets"`
+    const NAME: &'static str;
+    /// Table definitions applied before the first load
+    const SCHEMA: &'static str;
+
+    fn load_state(&self) -> LoadState;
+    fn set_load_state(&mut self, state: LoadState);
+}
+
+/// Background database operation of the persisted resource `R`
+#[derive(Component)]
+pub struct PersistenceTask<R: Persisted> {
+    task: Task<CommandQueue>,
+    _resource: PhantomData<fn() -> R>,
+}
+
+impl<R: Persisted> PersistenceTask<R> {
+    /// Run `work` on the async compute pool, then hand its output to `then` on the main world
+    pub fn new<T: Send + 'static>(
+        work: impl Future<Output = T> + Send + 'static,
+        then: impl FnOnce(&mut World, T) + Send + 'static,
+    ) -> Self {
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let output = work.await;
+            let mut command_queue = CommandQueue::default();
+            command_queue.push(move |world: &mut World| then(world, output));
+            command_queue
+        });
+        Self {
+            task,
+            _resource: PhantomData,
+        }
+    }
+}
+
+/// Apply the schema of `R` and load its stored records once the database is available
+///
+/// Does nothing unless `resource` is [`LoadState::NotLoaded`]. `load` reads the records in the
+/// background after the schema is applied; `loaded` then merges them into the resource, which
+/// must report [`LoadState::Loaded`] afterwards. If either step fails, the resource is marked
+/// [`LoadState::Failed`] and is never written through.
+pub fn start_loading<R, T, Fut>(
+    commands: &mut Commands,
+    resource: &mut R,
+    db: Option<&DatabaseService>,
+    load: impl FnOnce(DatabaseService) -> Fut,
+    loaded: impl FnOnce(&mut World, T) + Send + 'static,
+) where
+    R: Persisted,
+    T: Send + 'static,
+    Fut: Future<Output = Result<T, DatabaseError>> + Send + 'static,
+{
+    if resource.load_state() != LoadState::NotLoaded {
+        return;
+    }
+    let Some(db) = db else {
+        return;
+    };
+
+    resource.set_load_state(LoadState::Loading);
+    let schema_db = db.clone();
+    let records = load(db.clone());
+    let work = async move {
+        schema_db.execute_schema(R::SCHEMA).await?;
+        records.await
+    };
+
+    commands.spawn(PersistenceTask::<R>::new(work, move |world, result| match result {
+        Ok(records) => loaded(world, records),
+        Err(e) => {
+            error!("Failed to load {}: {}", R::NAME, e);
+            if let Some(mut resource) = world.get_resource_mut::<R>() {
+                resource.set_load_state(LoadState::Failed);
+            }
+        },
+    }));
+}
+
+/// Database to write through to, once the stored records of `R` have been loaded
+pub fn persistent_database<R: Persisted>(world: &World) -> Option<DatabaseService> {
+    let resource = world.get_resource::<R>()?;
+    if resource.load_state() != LoadState::Loaded {
+        return None;
+    }
+    world.get_resource::<DatabaseService>().cloned()
+}
+
+/// Run `write` against the database in the background once `R` has been loaded
+///
+/// Writes before then are skipped; the resource's merge on load picks those changes up.
+pub fn spawn_write<R, Fut>(world: &mut World, write: impl FnOnce(DatabaseService) -> Fut)
+where
+    R: Persisted,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let Some(db) = persistent_database::<R>(world) else {
+        return;
+    };
+    world.spawn(PersistenceTask::<R>::new(write(db), |_, ()| {}));
+}
+
+/// Poll the background database operations of `R` and apply their results
+pub fn poll_persistence_tasks<R: Persisted>(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut PersistenceTask<R>)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(mut command_queue) = block_on(future::poll_once(&mut task.task)) {
+            commands.append(&mut command_queue);
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -3,14 +3,17 @@
 use std::time::Duration;
 use bevy::prelude::*;
 use serde::Serialize;
-use surrealdb::engine::local::{Db, SurrealKv};
+use surrealdb::engine::local::{Db, Mem, SurrealKv};
 use surrealdb::opt::Config;
 use surrealdb::opt::capabilities::Capabilities;
 use surrealdb::opt::auth::Root;
 use surrealdb::{RecordId, Response, Surreal, Value};
+use tokio::runtime::Handle;
 use tracing::{debug, info, warn};
 
-use crate::config::{DatabaseConfig, DatabaseEngine, DatabaseError, validate_table_name};
+use crate::config::{
+    DatabaseConfig, DatabaseCredentials, DatabaseEngine, DatabaseError, validate_table_name,
+};
 
 /// Resource indicating database service is unavailable
 #[derive(Resource)]
@@ -25,6 +28,8 @@ pub struct DatabaseShutdown;
 pub struct DatabaseService {
     config: DatabaseConfig,
     db: Surreal<Db>,
+    /// Runtime driving the embedded database, which live query streams must run on
+    runtime: Handle,
 }
 
 impl DatabaseService {
@@ -76,56 +81,57 @@ impl DatabaseService {
     async fn try_connect(config: &DatabaseConfig) -> Result<Self, DatabaseError> {
         config.validate()?;
 
-        // Ensure storage directory exists for SurrealKv - create the full storage path
-        let DatabaseEngine::SurrealKv(storage_path) = &config.engine;
-        debug!("Creating storage directory: {:?}", storage_path);
-        std::fs::create_dir_all(storage_path).map_err(|e| {
-            DatabaseError::InvalidConfiguration(
-                format!("Failed to create storage directory {:?}: {}", storage_path, e)
-            )
+        // The embedded engine spawns its router and live query cleanup on tokio
+        let runtime = Handle::try_current().map_err(|_| {
+            DatabaseError::ConnectionFailed("Embedded database needs a tokio runtime".into())
         })?;
 
-        // Initialize SurrealDB v3.0 using proper SDK authentication pattern from tests
+        let credentials = match &config.credentials {
+            Some(credentials) => credentials.clone(),
+            None => DatabaseCredentials::for_engine(&config.engine)?,
+        };
+        let root = Root {
+            username: &credentials.username,
+            password: &credentials.password,
+        };
+        // The root user is created from these credentials the first time the datastore opens
+        let sdb_config = Config::new()
+            .user(root)
+            .capabilities(if config.enable_all_capabilities {
+                Capabilities::all()
+            } else {
+                Capabilities::none()
+            });
+
         let db = match &config.engine {
             DatabaseEngine::SurrealKv(storage_path) => {
-                debug!("Creating SurrealKV connection to: {:?}", storage_path);
-                
-                // Create root credentials using pattern from SDK tests (lines 470-481)
-                // Use static strings to avoid lifetime issues - matching SDK test pattern
-                let root = Root {
-                    username: "root",
-                    password: "root",
-                };
-                
-                // Use proper Config pattern with root user and all capabilities
-                let sdb_config = Config::new()
-                    .user(root)
-                    .capabilities(if config.enable_all_capabilities {
-                        Capabilities::all()
-                    } else {
-                        Capabilities::none()
-                    });
-                
-                debug!("Initializing SurrealKV with root credentials and proper config");
-                // Use the correct SurrealDB v3.0 initialization pattern
-                let result = Surreal::new::<SurrealKv>((storage_path.clone(), sdb_config)).await;
-                debug!("SurrealKV initialization result: {:?}", result.is_ok());
-                let db = result.map_err(|e| {
-                    warn!("SurrealKV initialization failed: {}", e);
-                    DatabaseError::ConnectionFailed(e.to_string())
-                })?;
-                
-                // Sign in using root credentials (required for embedded databases)
-                debug!("Signing in with root credentials");
-                db.signin(root).await.map_err(|e| {
-                    warn!("SurrealKV signin failed: {}", e);
-                    DatabaseError::ConnectionFailed(format!("Authentication failed: {}", e))
+                // Ensure storage directory exists for SurrealKv - create the full storage path
+                debug!("Creating storage directory: {:?}", storage_path);
+                std::fs::create_dir_all(storage_path).map_err(|e| {
+                    DatabaseError::InvalidConfiguration(
+                        format!("Failed to create storage directory {:?}: {}", storage_path, e)
+                    )
                 })?;
-                debug!("SurrealKV signin successful");
-                
-                db
+
+                debug!("Creating SurrealKV connection to: {:?}", storage_path);
+                Surreal::new::<SurrealKv>((storage_path.clone(), sdb_config)).await
             },
-        };
+            DatabaseEngine::Memory => {
+                debug!("Creating in-memory database");
+                Surreal::new::<Mem>(sdb_config).await
+            },
+        }
+        .map_err(|e| {
+            warn!("Database initialization failed: {}", e);
+            DatabaseError::ConnectionFailed(e.to_string())
+        })?;
+
+        // Sign in using root credentials (required for embedded databases)
+        debug!("Signing in as {}", credentials.username);
+        db.signin(root).await.map_err(|e| {
+            warn!("Database signin failed: {}", e);
+            DatabaseError::ConnectionFailed(format!("Authentication failed: {}", e))
+        })?;
 
         // Use namespace and database
         db.use_ns(&config.namespace)
@@ -140,9 +146,26 @@ impl DatabaseService {
         Ok(Self {
             config: config.clone(),
             db,
+            runtime,
         })
     }
 
+    /// Create a service backed by a fresh in-memory database
+    pub async fn new_in_memory() -> Result<Self, DatabaseError> {
+        Self::new_with_retries(DatabaseConfig::in_memory(), 1).await
+    }
+
+    /// Execute schema statements, failing if any statement fails
+    ///
+    /// Unlike [`Self::query`], errors from individual statements are not left in the response.
+    pub async fn execute_schema(&self, schema: &str) -> Result<(), DatabaseError> {
+        self.query(schema)
+            .await?
+            .check()
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
     /// Execute a raw SurrealQL query
     pub async fn query(&self, sql: &str) -> Result<Response, DatabaseError> {
         if self.config.enable_query_logging {
@@ -251,4 +274,9 @@ impl DatabaseService {
     pub(crate) fn db(&self) -> &Surreal<Db> {
         &self.db
     }
+
+    /// Runtime the database was connected on
+    pub(crate) fn runtime(&self) -> &Handle {
+        &self.runtime
+    }
 }
\ No newline at end of file
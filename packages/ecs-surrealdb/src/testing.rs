@@ -0,0 +1,165 @@
+//! Test harness for crates built on DatabaseService
+//!
+//! Each harness owns a headless Bevy `App` with a ready [`DatabaseService`] backed by a fresh
+//! in-memory database in a namespace of its own, so tests never share state or touch disk.
+//!
+//! SurrealDB drives embedded databases from tokio tasks, so run harness tests on a
+//! multi-threaded tokio runtime:
+//!
+//! ```rust,ignore
+//! #[tokio::test(flavor = "multi_thread")]
+//! async fn test_write() {
+//!     let mut harness = DatabaseTestHarness::new(&[USER_SETTINGS_SCHEMA]).await.unwrap();
+//!     harness.app.add_plugins(UserSettingsPlugin);
+//!     harness.collect::<SettingsWriteCompleted>();
+//!
+//!     harness.send(SettingsWriteRequested { .. });
+//!     let completed = harness.wait_for::<SettingsWriteCompleted>(1).await;
+//!     assert!(completed[0].result.is_ok());
+//! }
+//! ```
+//!
+//! Tests that call into a crate directly, without an app, can start from [`migrated_database`]
+//! and set up records with [`execute`].
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+
+use crate::config::{DatabaseConfig, DatabaseError};
+use crate::migrations::Migration;
+use crate::service::DatabaseService;
+
+/// How long [`DatabaseTestHarness::wait_for`] runs the app before giving up
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Headless app with an in-memory database
+pub struct DatabaseTestHarness {
+    pub app: App,
+    pub database: DatabaseService,
+}
+
+/// Events of one type seen since [`DatabaseTestHarness::collect`]
+#[derive(Resource)]
+struct CollectedEvents<E: Event>(Vec<E>);
+
+impl DatabaseTestHarness {
+    /// Connect a fresh in-memory database, apply `schemas` in order and insert the service
+    ///
+    /// The app's startup schedules run here, before plugins added by the test, so plugins that
+    /// set up their tables at startup need their schema passed in `schemas`.
+    pub async fn new(schemas: &[&str]) -> Result<Self, DatabaseError> {
+        Self::with_config(DatabaseConfig::in_memory(), schemas).await
+    }
+
+    /// Like [`Self::new`] with a custom configuration
+    pub async fn with_config(
+        config: DatabaseConfig,
+        schemas: &[&str],
+    ) -> Result<Self, DatabaseError> {
+        let database = DatabaseService::new_with_retries(config, 1).await?;
+        for schema in schemas {
+            database.execute_schema(schema).await?;
+        }
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(database.clone());
+        app.update();
+
+        Ok(Self { app, database })
+    }
+
+    /// Start recording events of type `E` for [`Self::wait_for`] and [`Self::take`]
+    pub fn collect<E: Event + Clone>(&mut self) -> &mut Self {
+        if self.app.world().contains_resource::<CollectedEvents<E>>() {
+            return self;
+        }
+        self.app
+            .add_event::<E>()
+            .insert_resource(CollectedEvents::<E>(Vec::new()))
+            .add_systems(Last, collect_events::<E>);
+        self
+    }
+
+    /// Send an event into the app
+    pub fn send<E: Event>(&mut self, event: E) {
+        self.app.world_mut().send_event(event);
+    }
+
+    /// Run the app until `count` events of type `E` have been collected, then take them
+    ///
+    /// Panics if they have not arrived within ten seconds.
+    pub async fn wait_for<E: Event + Clone>(&mut self, count: usize) -> Vec<E> {
+        self.collect::<E>();
+        let started = Instant::now();
+        loop {
+            self.app.update();
+            if self.collected::<E>().len() >= count {
+                return self.take::<E>();
+            }
+            if started.elapsed() > WAIT_TIMEOUT {
+                panic!(
+                    "Expected {count} {} events, got {} within {WAIT_TIMEOUT:?}",
+                    std::any::type_name::<E>(),
+                    self.collected::<E>().len()
+                );
+            }
+            async_std::task::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    /// Run the app for a number of frames, giving async tasks time to finish
+    pub async fn run_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.app.update();
+            async_std::task::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    /// Take the events of type `E` collected so far
+    pub fn take<E: Event + Clone>(&mut self) -> Vec<E> {
+        self.app
+            .world_mut()
+            .get_resource_mut::<CollectedEvents<E>>()
+            .map(|mut collected| std::mem::take(&mut collected.0))
+            .unwrap_or_default()
+    }
+
+    fn collected<E: Event + Clone>(&self) -> &[E] {
+        self.app
+            .world()
+            .get_resource::<CollectedEvents<E>>()
+            .map(|collected| collected.0.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+fn collect_events<E: Event + Clone>(
+    mut events: EventReader<E>,
+    mut collected: ResMut<CollectedEvents<E>>,
+) {
+    collected.0.extend(events.read().cloned());
+}
+
+/// A fresh in-memory database with `migrations` applied
+///
+/// Panics if the database does not start or a migration fails.
+pub async fn migrated_database(migrations: &[Migration]) -> DatabaseService {
+    let database = DatabaseService::new_in_memory()
+        .await
+        .expect("In-memory database should start");
+    database
+        .migrate(migrations, false)
+        .await
+        .expect("Migrations should apply");
+    database
+}
+
+/// Run `sql`, panicking unless every statement succeeds
+pub async fn execute(database: &DatabaseService, sql: &str) {
+    database
+        .execute_schema(sql)
+        .await
+        .unwrap_or_else(|e| panic!("{sql} should succeed: {e}"));
+}
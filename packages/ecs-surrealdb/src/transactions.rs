@@ -1,9 +1,9 @@
 //! Transaction support with automatic commit/rollback and panic safety
 
 use std::collections::HashMap;
-use std::future::Future;
 use std::panic::AssertUnwindSafe;
 use futures::FutureExt;
+use futures::future::BoxFuture;
 use serde::Serialize;
 use surrealdb::engine::local::Db;
 use surrealdb::{RecordId, Response, Surreal, Value};
@@ -17,10 +17,13 @@ impl DatabaseService {
     ///
     /// The transaction is automatically committed if the closure returns Ok,
     /// or rolled back if the closure returns Err or panics.
-    pub async fn with_transaction<T, F, Fut>(&self, f: F) -> Result<T, DatabaseError>
+    ///
+    /// The closure's future borrows the context, so it is boxed:
+    /// `db.with_transaction(|ctx| async move { ctx.query("...").await }.boxed())`.
+    pub async fn with_transaction<T, F>(&self, f: F) -> Result<T, DatabaseError>
     where
-        F: for<'a> FnOnce(&'a TransactionContext<'a>) -> Fut + Send,
-        Fut: Future<Output = Result<T, DatabaseError>> + Send,
+        F: for<'a> FnOnce(&'a TransactionContext<'a>) -> BoxFuture<'a, Result<T, DatabaseError>>
+            + Send,
     {
         let db = self.db();
         
@@ -7,7 +7,6 @@
 pub(crate) mod certificate;
 pub(crate) mod crl_cache;
 pub(crate) mod errors;
-pub(crate) mod key_encryption;
 pub(crate) mod ocsp;
 pub(crate) mod tls_config;
 pub(crate) mod types;
@@ -15,6 +14,9 @@ pub(crate) mod types;
 // Bevy systems for TLS cleanup
 pub mod cleanup_systems;
 
+// Passphrase-based AES-256-GCM encryption, also used for other secrets at rest
+pub mod key_encryption;
+
 // Public builder interface - the only public API
 pub mod builder;
 pub use builder::{CertificateAuthority, Tls};
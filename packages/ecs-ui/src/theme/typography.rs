@@ -41,6 +41,22 @@ impl Default for TypographyTheme {
     }
 }
 
+impl TypographyTheme {
+    /// Default typography with every font size multiplied by `factor`
+    pub fn scaled(factor: f32) -> Self {
+        let default = Self::default();
+        Self {
+            font_size_xs: default.font_size_xs * factor,
+            font_size_sm: default.font_size_sm * factor,
+            font_size_base: default.font_size_base * factor,
+            font_size_lg: default.font_size_lg * factor,
+            font_size_xl: default.font_size_xl * factor,
+            font_size_2xl: default.font_size_2xl * factor,
+            ..default
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum FontScale {
     XS,
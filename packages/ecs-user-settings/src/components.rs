@@ -10,6 +10,7 @@
 use bevy::ecs::world::CommandQueue;
 use bevy::prelude::*;
 use bevy::tasks::Task;
+use action_items_ecs_surrealdb::MigrationReport;
 use uuid::Uuid;
 
 /// Component for schema initialization task
@@ -80,6 +81,17 @@ pub struct SchemaInitialized;
 #[derive(Resource)]
 pub struct SchemaInitError(pub String);
 
+/// How schema migrations run at startup; insert before startup to change it
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SchemaMigrationSettings {
+    /// Report pending migrations without applying them
+    pub dry_run: bool,
+}
+
+/// Outcome of the startup schema migration run, including dry runs and failures
+#[derive(Resource, Debug, Clone)]
+pub struct SchemaMigrationReport(pub MigrationReport);
+
 /// Marker resource indicating migration completed
 #[derive(Resource)]
 pub struct MigrationCompleted;
@@ -27,13 +27,20 @@
 //! - Audit trail recording (see [`systems::write_audit_trail`])
 //! - Live update notifications to interested systems
 //! - Complete change history with old_value and new_value
+//!
+//! Those only cover changes made through these events. [`HotkeySettingsChanged`] and
+//! [`AppearanceSettingsChanged`] come from live queries on their tables, so they also report
+//! writes made directly through `DatabaseService`, by a migration or by another process sharing
+//! the database.
 
+use action_items_ecs_surrealdb::LiveQueryEvent;
 use bevy::prelude::*;
 use surrealdb::Value;
 use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::error::SettingsError;
+use crate::records::{AppearanceSettings, HotkeySetting};
 
 // ============================================================================
 // Request Events
@@ -151,3 +158,13 @@ pub struct SettingChanged {
     pub new_value: Value,
     pub changed_at: chrono::DateTime<chrono::Utc>,
 }
+
+/// A record created, updated or deleted in `hotkey_settings`, however it was written
+///
+/// Delivered in `PreUpdate` from a live query on the table.
+pub type HotkeySettingsChanged = LiveQueryEvent<HotkeySetting>;
+
+/// A record created, updated or deleted in `appearance_settings`, however it was written
+///
+/// Delivered in `PreUpdate` from a live query on the table.
+pub type AppearanceSettingsChanged = LiveQueryEvent<AppearanceSettings>;
@@ -6,7 +6,8 @@
 //! - **SQL Injection Prevention**: Table name validation and RecordId type safety
 //! - **Complete Audit Trail**: Full change history with old/new values in settings_history
 //! - **Event-Driven Architecture**: Request/response pattern for async database operations
-//! - **Automatic Migration**: JSON to database migration on first startup
+//! - **Automatic Migration**: Versioned schema migrations and JSON to database
+//!   migration on first startup
 //!
 //! # Security
 //!
@@ -52,7 +53,8 @@
 //! - [`events`] - Request and response events for all operations
 //! - [`systems`] - Request processors and task handlers
 //! - [`types`] - Table validation and RecordId construction
-//! - [`schema`] - SurrealDB schema definition
+//! - [`records`] - Typed records of the tables with live queries
+//! - [`schema`] - SurrealDB schema definition and its versioned migrations
 //! - [`migration`] - JSON to database migration logic
 
 mod components;
@@ -60,6 +62,7 @@ mod error;
 mod events;
 mod migration;
 mod plugin;
+mod records;
 mod schema;
 mod systems;
 pub mod table_names;
@@ -72,4 +75,5 @@ pub use components::*;
 pub use error::*;
 pub use events::*;
 pub use plugin::UserSettingsPlugin;
-pub use schema::USER_SETTINGS_SCHEMA;
+pub use records::{AppearanceSettings, HotkeySetting};
+pub use schema::{USER_SETTINGS_MIGRATIONS, USER_SETTINGS_SCHEMA};
@@ -1,10 +1,13 @@
 //! Bevy plugin for user settings service
 
+use action_items_ecs_surrealdb::LiveQueryAppExt;
 use bevy::prelude::*;
 use tracing::info;
 
 use crate::events::*;
+use crate::records::{AppearanceSettings, HotkeySetting};
 use crate::systems::*;
+use crate::table_names::{APPEARANCE_SETTINGS, HOTKEY_SETTINGS};
 
 /// User settings plugin - provides centralized database-backed settings storage
 pub struct UserSettingsPlugin;
@@ -40,6 +43,12 @@ impl Plugin for UserSettingsPlugin {
         // Add change notification event
         app.add_event::<SettingChanged>();
 
+        // Live queries report every change to these tables as HotkeySettingsChanged and
+        // AppearanceSettingsChanged; the app rebinds the launcher hotkey and rescales text
+        // from them
+        app.add_live_query::<HotkeySetting>(HOTKEY_SETTINGS)
+            .add_live_query::<AppearanceSettings>(APPEARANCE_SETTINGS);
+
         // Add processing systems (Update schedule)
         app.add_systems(Update, (
             // Request processors
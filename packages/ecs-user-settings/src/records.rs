@@ -0,0 +1,57 @@
+//! Typed records of the settings tables with live queries
+//!
+//! Fields mirror the table definitions in [`crate::schema`]. Missing fields take the schema's
+//! defaults, so records written before a field existed still deserialize; timestamps and record
+//! ids are left out.
+
+use serde::{Deserialize, Serialize};
+
+/// A record in `hotkey_settings`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeySetting {
+    pub hotkey_id: String,
+    pub modifiers: Vec<String>,
+    pub key_code: String,
+    pub description: String,
+    pub enabled: bool,
+    pub priority: i64,
+}
+
+impl Default for HotkeySetting {
+    fn default() -> Self {
+        Self {
+            hotkey_id: String::new(),
+            modifiers: Vec::new(),
+            key_code: String::new(),
+            description: String::new(),
+            enabled: true,
+            priority: 0,
+        }
+    }
+}
+
+/// A record in `appearance_settings`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    pub text_size: String,
+    pub theme_dark: String,
+    pub theme_light: String,
+    pub follow_system_appearance: bool,
+    pub window_mode: String,
+    pub show_favorites_compact: bool,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            text_size: "medium".to_string(),
+            theme_dark: "raycast_dark".to_string(),
+            theme_light: "raycast_light".to_string(),
+            follow_system_appearance: true,
+            window_mode: "default".to_string(),
+            show_favorites_compact: true,
+        }
+    }
+}
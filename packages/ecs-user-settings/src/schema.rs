@@ -19,211 +19,229 @@
 //! - Automatic timestamps (created_at, updated_at)
 //! - Indexes for performance
 //! - SCHEMAFULL enforcement for data integrity
+//!
+//! # Migrations
+//!
+//! The schema is versioned by [`USER_SETTINGS_MIGRATIONS`], applied at startup
+//! through `DatabaseService::migrate`. Never edit an applied migration: append a
+//! new one with the next version number, e.g. a `DEFINE FIELD OVERWRITE` to change
+//! a field's type, or `DEFINE FIELD IF NOT EXISTS` plus an `UPDATE` to add and
+//! backfill one.
+
+use action_items_ecs_surrealdb::Migration;
 
 /// Complete schema as const string for initialization
 ///
-/// This schema is executed once during service initialization to create
-/// all required tables, fields, and indexes. The settings_history table
-/// provides a complete audit trail of all setting changes.
+/// This is migration 1 of [`USER_SETTINGS_MIGRATIONS`]: it creates all required
+/// tables, fields, and indexes. Every statement is `IF NOT EXISTS` so it also
+/// applies cleanly to databases created before migrations were tracked. The
+/// settings_history table provides a complete audit trail of all setting changes.
 pub const USER_SETTINGS_SCHEMA: &str = r#"
 -- ============================================================================
 -- USER PREFERENCES TABLE
 -- ============================================================================
-DEFINE TABLE user_preferences SCHEMAFULL;
-DEFINE FIELD key ON user_preferences TYPE string
+DEFINE TABLE IF NOT EXISTS user_preferences SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS key ON user_preferences TYPE string
     ASSERT $value != NONE AND string::len($value) > 0;
-DEFINE FIELD value ON user_preferences TYPE object;
-DEFINE FIELD category ON user_preferences TYPE string DEFAULT "general";
-DEFINE FIELD description ON user_preferences TYPE option<string>;
-DEFINE FIELD created_at ON user_preferences TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON user_preferences TYPE datetime DEFAULT time::now();
-DEFINE INDEX key_idx ON user_preferences COLUMNS key UNIQUE;
-DEFINE INDEX category_idx ON user_preferences COLUMNS category;
+DEFINE FIELD IF NOT EXISTS value ON user_preferences TYPE object;
+DEFINE FIELD IF NOT EXISTS category ON user_preferences TYPE string DEFAULT "general";
+DEFINE FIELD IF NOT EXISTS description ON user_preferences TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS created_at ON user_preferences TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON user_preferences TYPE datetime DEFAULT time::now();
+DEFINE INDEX IF NOT EXISTS key_idx ON user_preferences COLUMNS key UNIQUE;
+DEFINE INDEX IF NOT EXISTS category_idx ON user_preferences COLUMNS category;
 
 -- ============================================================================
 -- HOTKEY SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE hotkey_settings SCHEMAFULL;
-DEFINE FIELD hotkey_id ON hotkey_settings TYPE string
+DEFINE TABLE IF NOT EXISTS hotkey_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS hotkey_id ON hotkey_settings TYPE string
     ASSERT $value != NONE AND string::len($value) > 0;
-DEFINE FIELD modifiers ON hotkey_settings TYPE array<string>;
-DEFINE FIELD key_code ON hotkey_settings TYPE string;
-DEFINE FIELD description ON hotkey_settings TYPE string;
-DEFINE FIELD enabled ON hotkey_settings TYPE bool DEFAULT true;
-DEFINE FIELD priority ON hotkey_settings TYPE number DEFAULT 0;
-DEFINE FIELD created_at ON hotkey_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON hotkey_settings TYPE datetime DEFAULT time::now();
-DEFINE INDEX hotkey_id_idx ON hotkey_settings COLUMNS hotkey_id UNIQUE;
-DEFINE INDEX enabled_idx ON hotkey_settings COLUMNS enabled;
+DEFINE FIELD IF NOT EXISTS modifiers ON hotkey_settings TYPE array<string>;
+DEFINE FIELD IF NOT EXISTS key_code ON hotkey_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS description ON hotkey_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS enabled ON hotkey_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS priority ON hotkey_settings TYPE number DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS created_at ON hotkey_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON hotkey_settings TYPE datetime DEFAULT time::now();
+DEFINE INDEX IF NOT EXISTS hotkey_id_idx ON hotkey_settings COLUMNS hotkey_id UNIQUE;
+DEFINE INDEX IF NOT EXISTS enabled_idx ON hotkey_settings COLUMNS enabled;
 
 -- ============================================================================
 -- PLUGIN CONFIGURATIONS TABLE
 -- ============================================================================
-DEFINE TABLE plugin_configs SCHEMAFULL;
-DEFINE FIELD plugin_id ON plugin_configs TYPE string
+DEFINE TABLE IF NOT EXISTS plugin_configs SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS plugin_id ON plugin_configs TYPE string
     ASSERT $value != NONE AND string::len($value) > 0;
-DEFINE FIELD version ON plugin_configs TYPE string;
-DEFINE FIELD configuration ON plugin_configs TYPE object;
-DEFINE FIELD preferences ON plugin_configs TYPE object;
-DEFINE FIELD enabled ON plugin_configs TYPE bool DEFAULT true;
-DEFINE FIELD last_modified ON plugin_configs TYPE datetime DEFAULT time::now();
-DEFINE INDEX plugin_id_idx ON plugin_configs COLUMNS plugin_id UNIQUE;
-DEFINE INDEX enabled_idx ON plugin_configs COLUMNS enabled;
+DEFINE FIELD IF NOT EXISTS version ON plugin_configs TYPE string;
+DEFINE FIELD IF NOT EXISTS configuration ON plugin_configs TYPE object;
+DEFINE FIELD IF NOT EXISTS preferences ON plugin_configs TYPE object;
+DEFINE FIELD IF NOT EXISTS enabled ON plugin_configs TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS last_modified ON plugin_configs TYPE datetime DEFAULT time::now();
+DEFINE INDEX IF NOT EXISTS plugin_id_idx ON plugin_configs COLUMNS plugin_id UNIQUE;
+DEFINE INDEX IF NOT EXISTS enabled_idx ON plugin_configs COLUMNS enabled;
 
 -- ============================================================================
 -- UI STATE TABLE
 -- ============================================================================
-DEFINE TABLE ui_state SCHEMAFULL;
-DEFINE FIELD window_id ON ui_state TYPE string;
-DEFINE FIELD state ON ui_state TYPE object;
-DEFINE FIELD last_updated ON ui_state TYPE datetime DEFAULT time::now();
-DEFINE INDEX window_id_idx ON ui_state COLUMNS window_id UNIQUE;
+DEFINE TABLE IF NOT EXISTS ui_state SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS window_id ON ui_state TYPE string;
+DEFINE FIELD IF NOT EXISTS state ON ui_state TYPE object;
+DEFINE FIELD IF NOT EXISTS last_updated ON ui_state TYPE datetime DEFAULT time::now();
+DEFINE INDEX IF NOT EXISTS window_id_idx ON ui_state COLUMNS window_id UNIQUE;
 
 -- ============================================================================
 -- AI SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE ai_settings SCHEMAFULL;
-DEFINE FIELD enabled ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD quick_ai_trigger ON ai_settings TYPE string DEFAULT "Tab";
-DEFINE FIELD show_hint_in_root_search ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD quick_ai_model ON ai_settings TYPE string DEFAULT "sonar-reasoning-pro";
-DEFINE FIELD web_search_enabled ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD default_primary_action ON ai_settings TYPE string DEFAULT "paste_response";
-DEFINE FIELD chat_hotkey ON ai_settings TYPE string DEFAULT "^ ⌘ L";
-DEFINE FIELD start_new_chat_after ON ai_settings TYPE string DEFAULT "30_minutes";
-DEFINE FIELD ai_commands_model ON ai_settings TYPE string DEFAULT "gemini-2.5-pro";
-DEFINE FIELD show_tool_call_info ON ai_settings TYPE bool DEFAULT false;
-DEFINE FIELD auto_confirm_tool_calls ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD text_size ON ai_settings TYPE string DEFAULT "medium";
-DEFINE FIELD ollama_host ON ai_settings TYPE string DEFAULT "127.0.0.1:11434";
-DEFINE FIELD ollama_models ON ai_settings TYPE array<string>;
-DEFINE FIELD browser_extension_enabled ON ai_settings TYPE bool DEFAULT false;
-DEFINE FIELD experiments_auto_models ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD experiments_chat_branching ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD experiments_custom_providers ON ai_settings TYPE bool DEFAULT false;
-DEFINE FIELD experiments_mcp_servers ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD experiments_ollama_extensions ON ai_settings TYPE bool DEFAULT true;
-DEFINE FIELD created_at ON ai_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON ai_settings TYPE datetime DEFAULT time::now();
-DEFINE INDEX ai_enabled_idx ON ai_settings COLUMNS enabled;
+DEFINE TABLE IF NOT EXISTS ai_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS enabled ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS quick_ai_trigger ON ai_settings TYPE string DEFAULT "Tab";
+DEFINE FIELD IF NOT EXISTS show_hint_in_root_search ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS quick_ai_model ON ai_settings TYPE string DEFAULT "sonar-reasoning-pro";
+DEFINE FIELD IF NOT EXISTS web_search_enabled ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS default_primary_action ON ai_settings TYPE string DEFAULT "paste_response";
+DEFINE FIELD IF NOT EXISTS chat_hotkey ON ai_settings TYPE string DEFAULT "^ ⌘ L";
+DEFINE FIELD IF NOT EXISTS start_new_chat_after ON ai_settings TYPE string DEFAULT "30_minutes";
+DEFINE FIELD IF NOT EXISTS ai_commands_model ON ai_settings TYPE string DEFAULT "gemini-2.5-pro";
+DEFINE FIELD IF NOT EXISTS show_tool_call_info ON ai_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS auto_confirm_tool_calls ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS text_size ON ai_settings TYPE string DEFAULT "medium";
+DEFINE FIELD IF NOT EXISTS ollama_host ON ai_settings TYPE string DEFAULT "127.0.0.1:11434";
+DEFINE FIELD IF NOT EXISTS ollama_models ON ai_settings TYPE array<string>;
+DEFINE FIELD IF NOT EXISTS browser_extension_enabled ON ai_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS experiments_auto_models ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS experiments_chat_branching ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS experiments_custom_providers ON ai_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS experiments_mcp_servers ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS experiments_ollama_extensions ON ai_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS created_at ON ai_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON ai_settings TYPE datetime DEFAULT time::now();
+DEFINE INDEX IF NOT EXISTS ai_enabled_idx ON ai_settings COLUMNS enabled;
 
 -- ============================================================================
 -- CLOUD SYNC SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE cloud_sync_settings SCHEMAFULL;
-DEFINE FIELD enabled ON cloud_sync_settings TYPE bool DEFAULT false;
-DEFINE FIELD last_synced ON cloud_sync_settings TYPE option<datetime>;
-DEFINE FIELD sync_search_history ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_aliases ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_hotkeys ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_quicklinks ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_snippets ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_notes ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_extensions_settings ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_ai_chats ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_themes ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD sync_window_management ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD not_synced_clipboard_history ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD not_synced_script_commands ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD not_synced_credentials ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD not_synced_general_advanced ON cloud_sync_settings TYPE bool DEFAULT true;
-DEFINE FIELD created_at ON cloud_sync_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON cloud_sync_settings TYPE datetime DEFAULT time::now();
+DEFINE TABLE IF NOT EXISTS cloud_sync_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS enabled ON cloud_sync_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS last_synced ON cloud_sync_settings TYPE option<datetime>;
+DEFINE FIELD IF NOT EXISTS sync_search_history ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_aliases ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_hotkeys ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_quicklinks ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_snippets ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_notes ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_extensions_settings ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_ai_chats ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_themes ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS sync_window_management ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS not_synced_clipboard_history ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS not_synced_script_commands ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS not_synced_credentials ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS not_synced_general_advanced ON cloud_sync_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS created_at ON cloud_sync_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON cloud_sync_settings TYPE datetime DEFAULT time::now();
 
 -- ============================================================================
 -- ACCOUNT SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE account_settings SCHEMAFULL;
-DEFINE FIELD user_name ON account_settings TYPE string;
-DEFINE FIELD user_email ON account_settings TYPE string;
-DEFINE FIELD user_avatar ON account_settings TYPE option<string>;
-DEFINE FIELD subscription_type ON account_settings TYPE string;
-DEFINE FIELD subscription_status ON account_settings TYPE string;
-DEFINE FIELD pro_features ON account_settings TYPE object;
-DEFINE FIELD organization_id ON account_settings TYPE option<string>;
-DEFINE FIELD created_at ON account_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON account_settings TYPE datetime DEFAULT time::now();
+DEFINE TABLE IF NOT EXISTS account_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS user_name ON account_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS user_email ON account_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS user_avatar ON account_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS subscription_type ON account_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS subscription_status ON account_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS pro_features ON account_settings TYPE object;
+DEFINE FIELD IF NOT EXISTS organization_id ON account_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS created_at ON account_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON account_settings TYPE datetime DEFAULT time::now();
 
 -- ============================================================================
 -- ORGANIZATION SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE organization_settings SCHEMAFULL;
-DEFINE FIELD organization_id ON organization_settings TYPE string;
-DEFINE FIELD organization_name ON organization_settings TYPE string;
-DEFINE FIELD subscription_plan ON organization_settings TYPE string;
-DEFINE FIELD private_extensions ON organization_settings TYPE bool DEFAULT false;
-DEFINE FIELD shared_quicklinks ON organization_settings TYPE bool DEFAULT false;
-DEFINE FIELD shared_snippets ON organization_settings TYPE bool DEFAULT false;
-DEFINE FIELD pro_features_for_all ON organization_settings TYPE bool DEFAULT false;
-DEFINE FIELD store_url ON organization_settings TYPE option<string>;
-DEFINE FIELD created_at ON organization_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON organization_settings TYPE datetime DEFAULT time::now();
-DEFINE INDEX org_id_idx ON organization_settings COLUMNS organization_id UNIQUE;
+DEFINE TABLE IF NOT EXISTS organization_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS organization_id ON organization_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS organization_name ON organization_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS subscription_plan ON organization_settings TYPE string;
+DEFINE FIELD IF NOT EXISTS private_extensions ON organization_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS shared_quicklinks ON organization_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS shared_snippets ON organization_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS pro_features_for_all ON organization_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS store_url ON organization_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS created_at ON organization_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON organization_settings TYPE datetime DEFAULT time::now();
+DEFINE INDEX IF NOT EXISTS org_id_idx ON organization_settings COLUMNS organization_id UNIQUE;
 
 -- ============================================================================
 -- ADVANCED SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE advanced_settings SCHEMAFULL;
-DEFINE FIELD show_raycast_on ON advanced_settings TYPE string DEFAULT "screen_with_mouse";
-DEFINE FIELD pop_to_root_after ON advanced_settings TYPE string DEFAULT "90_seconds";
-DEFINE FIELD escape_key_behavior ON advanced_settings TYPE string DEFAULT "navigate_back";
-DEFINE FIELD auto_switch_input_source ON advanced_settings TYPE string DEFAULT "us";
-DEFINE FIELD navigation_bindings ON advanced_settings TYPE string DEFAULT "macos_standard";
-DEFINE FIELD page_navigation_keys ON advanced_settings TYPE string DEFAULT "square_brackets";
-DEFINE FIELD root_search_sensitivity ON advanced_settings TYPE string DEFAULT "medium";
-DEFINE FIELD hyper_key ON advanced_settings TYPE option<string>;
-DEFINE FIELD hyper_key_replacement ON advanced_settings TYPE option<string>;
-DEFINE FIELD favicon_provider ON advanced_settings TYPE string DEFAULT "raycast";
-DEFINE FIELD emoji_skin_tone ON advanced_settings TYPE number DEFAULT 0;
-DEFINE FIELD import_export_data ON advanced_settings TYPE object;
-DEFINE FIELD window_capture_hotkey ON advanced_settings TYPE option<string>;
-DEFINE FIELD window_capture_clipboard ON advanced_settings TYPE bool DEFAULT true;
-DEFINE FIELD window_capture_finder ON advanced_settings TYPE bool DEFAULT false;
-DEFINE FIELD custom_wallpaper ON advanced_settings TYPE option<string>;
-DEFINE FIELD use_node_production ON advanced_settings TYPE bool DEFAULT true;
-DEFINE FIELD use_file_logging ON advanced_settings TYPE bool DEFAULT true;
-DEFINE FIELD auto_reload_on_save ON advanced_settings TYPE bool DEFAULT true;
-DEFINE FIELD disable_pop_to_root ON advanced_settings TYPE bool DEFAULT false;
-DEFINE FIELD open_in_dev_mode ON advanced_settings TYPE bool DEFAULT false;
-DEFINE FIELD keep_visible_in_dev ON advanced_settings TYPE bool DEFAULT false;
-DEFINE FIELD use_system_network ON advanced_settings TYPE bool DEFAULT true;
-DEFINE FIELD certificates_keychain ON advanced_settings TYPE string DEFAULT "keychain";
-DEFINE FIELD created_at ON advanced_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON advanced_settings TYPE datetime DEFAULT time::now();
+DEFINE TABLE IF NOT EXISTS advanced_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS show_raycast_on ON advanced_settings TYPE string DEFAULT "screen_with_mouse";
+DEFINE FIELD IF NOT EXISTS pop_to_root_after ON advanced_settings TYPE string DEFAULT "90_seconds";
+DEFINE FIELD IF NOT EXISTS escape_key_behavior ON advanced_settings TYPE string DEFAULT "navigate_back";
+DEFINE FIELD IF NOT EXISTS auto_switch_input_source ON advanced_settings TYPE string DEFAULT "us";
+DEFINE FIELD IF NOT EXISTS navigation_bindings ON advanced_settings TYPE string DEFAULT "macos_standard";
+DEFINE FIELD IF NOT EXISTS page_navigation_keys ON advanced_settings TYPE string DEFAULT "square_brackets";
+DEFINE FIELD IF NOT EXISTS root_search_sensitivity ON advanced_settings TYPE string DEFAULT "medium";
+DEFINE FIELD IF NOT EXISTS hyper_key ON advanced_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS hyper_key_replacement ON advanced_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS favicon_provider ON advanced_settings TYPE string DEFAULT "raycast";
+DEFINE FIELD IF NOT EXISTS emoji_skin_tone ON advanced_settings TYPE number DEFAULT 0;
+DEFINE FIELD IF NOT EXISTS import_export_data ON advanced_settings TYPE object;
+DEFINE FIELD IF NOT EXISTS window_capture_hotkey ON advanced_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS window_capture_clipboard ON advanced_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS window_capture_finder ON advanced_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS custom_wallpaper ON advanced_settings TYPE option<string>;
+DEFINE FIELD IF NOT EXISTS use_node_production ON advanced_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS use_file_logging ON advanced_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS auto_reload_on_save ON advanced_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS disable_pop_to_root ON advanced_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS open_in_dev_mode ON advanced_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS keep_visible_in_dev ON advanced_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS use_system_network ON advanced_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS certificates_keychain ON advanced_settings TYPE string DEFAULT "keychain";
+DEFINE FIELD IF NOT EXISTS created_at ON advanced_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON advanced_settings TYPE datetime DEFAULT time::now();
 
 -- ============================================================================
 -- APPEARANCE SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE appearance_settings SCHEMAFULL;
-DEFINE FIELD text_size ON appearance_settings TYPE string DEFAULT "medium";
-DEFINE FIELD theme_dark ON appearance_settings TYPE string DEFAULT "raycast_dark";
-DEFINE FIELD theme_light ON appearance_settings TYPE string DEFAULT "raycast_light";
-DEFINE FIELD follow_system_appearance ON appearance_settings TYPE bool DEFAULT true;
-DEFINE FIELD window_mode ON appearance_settings TYPE string DEFAULT "default";
-DEFINE FIELD show_favorites_compact ON appearance_settings TYPE bool DEFAULT true;
-DEFINE FIELD created_at ON appearance_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON appearance_settings TYPE datetime DEFAULT time::now();
+DEFINE TABLE IF NOT EXISTS appearance_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS text_size ON appearance_settings TYPE string DEFAULT "medium";
+DEFINE FIELD IF NOT EXISTS theme_dark ON appearance_settings TYPE string DEFAULT "raycast_dark";
+DEFINE FIELD IF NOT EXISTS theme_light ON appearance_settings TYPE string DEFAULT "raycast_light";
+DEFINE FIELD IF NOT EXISTS follow_system_appearance ON appearance_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS window_mode ON appearance_settings TYPE string DEFAULT "default";
+DEFINE FIELD IF NOT EXISTS show_favorites_compact ON appearance_settings TYPE bool DEFAULT true;
+DEFINE FIELD IF NOT EXISTS created_at ON appearance_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON appearance_settings TYPE datetime DEFAULT time::now();
 
 -- ============================================================================
 -- STARTUP SETTINGS TABLE
 -- ============================================================================
-DEFINE TABLE startup_settings SCHEMAFULL;
-DEFINE FIELD launch_at_login ON startup_settings TYPE bool DEFAULT false;
-DEFINE FIELD show_menu_bar_icon ON startup_settings TYPE bool DEFAULT false;
-DEFINE FIELD created_at ON startup_settings TYPE datetime DEFAULT time::now();
-DEFINE FIELD updated_at ON startup_settings TYPE datetime DEFAULT time::now();
+DEFINE TABLE IF NOT EXISTS startup_settings SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS launch_at_login ON startup_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS show_menu_bar_icon ON startup_settings TYPE bool DEFAULT false;
+DEFINE FIELD IF NOT EXISTS created_at ON startup_settings TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS updated_at ON startup_settings TYPE datetime DEFAULT time::now();
 
 -- ============================================================================
 -- SETTINGS HISTORY TABLE (Audit Trail)
 -- ============================================================================
-DEFINE TABLE settings_history SCHEMAFULL;
-DEFINE FIELD table_name ON settings_history TYPE string;
-DEFINE FIELD record_id ON settings_history TYPE string;
-DEFINE FIELD field_name ON settings_history TYPE string;
-DEFINE FIELD old_value ON settings_history TYPE option<object>;
-DEFINE FIELD new_value ON settings_history TYPE object;
-DEFINE FIELD changed_at ON settings_history TYPE datetime DEFAULT time::now();
-DEFINE FIELD change_type ON settings_history TYPE string;
-DEFINE INDEX changed_at_idx ON settings_history COLUMNS changed_at;
-DEFINE INDEX table_record_idx ON settings_history COLUMNS table_name, record_id;
+DEFINE TABLE IF NOT EXISTS settings_history SCHEMAFULL;
+DEFINE FIELD IF NOT EXISTS table_name ON settings_history TYPE string;
+DEFINE FIELD IF NOT EXISTS record_id ON settings_history TYPE string;
+DEFINE FIELD IF NOT EXISTS field_name ON settings_history TYPE string;
+DEFINE FIELD IF NOT EXISTS old_value ON settings_history TYPE option<object>;
+DEFINE FIELD IF NOT EXISTS new_value ON settings_history TYPE object;
+DEFINE FIELD IF NOT EXISTS changed_at ON settings_history TYPE datetime DEFAULT time::now();
+DEFINE FIELD IF NOT EXISTS change_type ON settings_history TYPE string;
+DEFINE INDEX IF NOT EXISTS changed_at_idx ON settings_history COLUMNS changed_at;
+DEFINE INDEX IF NOT EXISTS table_record_idx ON settings_history COLUMNS table_name, record_id;
 "#;
+
+/// Schema migrations for the settings store, in version order
+pub const USER_SETTINGS_MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "initial_settings_schema",
+    up: USER_SETTINGS_SCHEMA,
+}];
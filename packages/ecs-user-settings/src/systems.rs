@@ -6,7 +6,7 @@ use bevy::tasks::futures_lite::future;
 use bevy::tasks::{AsyncComputeTaskPool, block_on};
 use surrealdb::{RecordId, Value};
 use tracing::{debug, error, info};
-use action_items_ecs_surrealdb::DatabaseService;
+use action_items_ecs_surrealdb::{DatabaseService, MigrationError};
 use action_items_common::AppDirectories;
 
 use crate::components::*;
@@ -14,23 +14,28 @@ use crate::error::SettingsError;
 use crate::events::*;
 use crate::types::parse_record_id;
 use crate::migration;
-use crate::schema::USER_SETTINGS_SCHEMA;
+use crate::schema::USER_SETTINGS_MIGRATIONS;
 
 // ============================================================================
 // Schema Initialization
 // ============================================================================
 
-/// Initialize user settings schema in database
+/// Bring the user settings schema up to date by running pending migrations
+///
+/// With [`SchemaMigrationSettings::dry_run`] set, pending migrations are only
+/// reported and the schema is not marked initialized, so JSON migration waits.
 pub fn initialize_user_settings_schema(
     mut commands: Commands,
     db_service: Option<Res<DatabaseService>>,
+    settings: Option<Res<SchemaMigrationSettings>>,
 ) {
     let Some(db) = db_service else {
         error!("DatabaseService not available - cannot initialize user settings schema");
         return;
     };
 
-    info!("Initializing user settings schema");
+    let dry_run = settings.is_some_and(|settings| settings.dry_run);
+    info!("Migrating user settings schema (dry run: {})", dry_run);
 
     // Clone for async task
     let db = (*db).clone();
@@ -38,19 +43,22 @@ pub fn initialize_user_settings_schema(
     let task = AsyncComputeTaskPool::get().spawn(async move {
         let mut command_queue = CommandQueue::default();
 
-        debug!("Applying user settings schema to database");
-
-        // Execute schema using DatabaseService.query()
-        match db.query(USER_SETTINGS_SCHEMA).await {
-            Ok(_) => {
-                info!("User settings schema applied successfully");
-                command_queue.push(|world: &mut World| {
-                    world.insert_resource(SchemaInitialized);
+        match db.migrate(USER_SETTINGS_MIGRATIONS, dry_run).await {
+            Ok(report) => {
+                info!("User settings schema: {}", report);
+                command_queue.push(move |world: &mut World| {
+                    if !report.dry_run {
+                        world.insert_resource(SchemaInitialized);
+                    }
+                    world.insert_resource(SchemaMigrationReport(report));
                 });
             },
             Err(e) => {
-                error!("Failed to apply user settings schema: {}", e);
+                error!("Failed to migrate user settings schema: {}", e);
                 command_queue.push(move |world: &mut World| {
+                    if let MigrationError::Failed(report) = &e {
+                        world.insert_resource(SchemaMigrationReport(report.clone()));
+                    }
                     world.insert_resource(SchemaInitError(e.to_string()));
                 });
             },
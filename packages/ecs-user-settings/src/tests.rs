@@ -6,6 +6,9 @@
 //! - Audit trail and change tracking
 //! - Migration from JSON files
 //! - Error handling and edge cases
+//!
+//! Database tests run against an in-memory database through
+//! `action_items_ecs_surrealdb::testing::DatabaseTestHarness`.
 
 #[cfg(test)]
 mod validation_tests {
@@ -124,260 +127,164 @@ mod validation_tests {
     }
 }
 
-// Integration tests are disabled pending DatabaseService test API
-// These tests require a running SurrealDB instance and proper test harness setup
-#[cfg(all(test, feature = "integration-tests"))]
+#[cfg(test)]
 mod integration_tests {
     use bevy::prelude::*;
     use uuid::Uuid;
     use surrealdb::Value;
     use std::collections::HashMap;
-    use action_items_ecs_surrealdb::DatabaseService;
-    
+    use action_items_ecs_surrealdb::{DatabaseError, LiveAction};
+    use action_items_ecs_surrealdb::testing::DatabaseTestHarness;
+
     use crate::events::*;
     use crate::plugin::UserSettingsPlugin;
     use crate::schema::USER_SETTINGS_SCHEMA;
 
-    /// Helper to create test app with database
-    async fn create_test_app() -> App {
-        let mut app = App::new();
-        
-        // Create in-memory database for testing
-        let db = DatabaseService::new_in_memory()
+    /// Harness with the settings schema and plugin, returning a requester entity
+    async fn create_test_app() -> (DatabaseTestHarness, Entity) {
+        let mut harness = DatabaseTestHarness::new(&[USER_SETTINGS_SCHEMA])
             .await
             .expect("Failed to create in-memory database");
-        
-        // Initialize schema
-        db.execute_schema(USER_SETTINGS_SCHEMA)
-            .await
-            .expect("Failed to initialize schema");
-        
-        app.insert_resource(db);
-        app.add_plugins(UserSettingsPlugin);
-        
-        app
+        harness.app.add_plugins(UserSettingsPlugin);
+        harness
+            .collect::<SettingsReadCompleted>()
+            .collect::<SettingsWriteCompleted>()
+            .collect::<SettingsUpdateCompleted>()
+            .collect::<SettingsDeleteCompleted>()
+            .collect::<SettingsQueryCompleted>()
+            .collect::<SettingChanged>();
+
+        let requester = harness.app.world_mut().spawn_empty().id();
+        (harness, requester)
     }
 
-    /// Helper to create test entity
-    fn create_test_requester(app: &mut App) -> Entity {
-        app.world_mut().spawn_empty().id()
+    /// SurrealQL value from JSON
+    fn value(json: serde_json::Value) -> Value {
+        json.to_string().parse().expect("JSON is valid SurrealQL")
     }
 
-    #[tokio::test]
-    async fn test_read_operation() {
-        let mut app = create_test_app().await;
-        let requester = create_test_requester(&mut app);
+    /// A user_preferences record as the schema expects it
+    fn preference(key: &str, settings: serde_json::Value) -> Value {
+        value(serde_json::json!({ "key": key, "value": settings }))
+    }
 
-        // First write a value
-        let operation_id = Uuid::new_v4();
-        let test_value = serde_json::json!({"theme": "dark", "language": "en"});
-        
-        app.world_mut().send_event(SettingsWriteRequested {
-            operation_id,
+    async fn write(
+        harness: &mut DatabaseTestHarness,
+        requester: Entity,
+        key: &str,
+        record: Value,
+    ) {
+        harness.send(SettingsWriteRequested {
+            operation_id: Uuid::new_v4(),
             table: "user_preferences".to_string(),
-            key: "test_user".to_string(),
-            value: Value::from(test_value.clone()),
+            key: key.to_string(),
+            value: record,
             requester,
         });
+        let completed = harness.wait_for::<SettingsWriteCompleted>(1).await;
+        assert!(completed[0].result.is_ok(), "Write should succeed");
+    }
 
-        // Run systems to process write
-        app.update();
-        app.update();
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_read_operation() {
+        let (mut harness, requester) = create_test_app().await;
+        let settings = serde_json::json!({"theme": "dark", "language": "en"});
+        write(&mut harness, requester, "test_user", preference("test_user", settings.clone()))
+            .await;
 
-        // Now read it back
         let read_id = Uuid::new_v4();
-        app.world_mut().send_event(SettingsReadRequested {
+        harness.send(SettingsReadRequested {
             operation_id: read_id,
             table: "user_preferences".to_string(),
             key: "test_user".to_string(),
             requester,
         });
 
-        // Run systems to process read
-        app.update();
-        app.update();
-
-        // Check for completion event
-        let mut completed_events = app.world_mut()
-            .resource_mut::<Events<SettingsReadCompleted>>()
-            .expect("SettingsReadCompleted events should exist");
-        
-        let events: Vec<_> = completed_events.drain().collect();
+        let events = harness.wait_for::<SettingsReadCompleted>(1).await;
         assert_eq!(events.len(), 1, "Should have one read completion event");
 
         let event = &events[0];
         assert_eq!(event.operation_id, read_id);
-        assert!(event.result.is_ok(), "Read should succeed");
-        
-        if let Ok(Some(value)) = &event.result {
-            // Verify the value matches what we wrote
-            assert_eq!(value, &Value::from(test_value));
-        } else {
-            panic!("Expected Some(value) from read");
+        assert_eq!(event.requester, requester);
+        match &event.result {
+            Ok(Some(record)) => assert_eq!(record.get("value"), &value(settings)),
+            other => panic!("Expected Some(value) from read, got {:?}", other),
         }
     }
 
-    #[tokio::test]
+    #[tokio::test(flavor = "multi_thread")]
     async fn test_write_operation() {
-        let mut app = create_test_app().await;
-        let requester = create_test_requester(&mut app);
-
-        let operation_id = Uuid::new_v4();
-        let test_value = serde_json::json!({
+        let (mut harness, requester) = create_test_app().await;
+        let settings = serde_json::json!({
             "theme": "dark",
             "font_size": 14,
             "auto_save": true
         });
-        
-        app.world_mut().send_event(SettingsWriteRequested {
-            operation_id,
-            table: "user_preferences".to_string(),
-            key: "write_test".to_string(),
-            value: Value::from(test_value.clone()),
-            requester,
-        });
-
-        // Run systems
-        app.update();
-        app.update();
-
-        // Check completion event
-        let mut completed_events = app.world_mut()
-            .resource_mut::<Events<SettingsWriteCompleted>>()
-            .expect("SettingsWriteCompleted events should exist");
-        
-        let events: Vec<_> = completed_events.drain().collect();
-        assert_eq!(events.len(), 1);
-        assert!(events[0].result.is_ok(), "Write should succeed");
+        write(&mut harness, requester, "write_test", preference("write_test", settings)).await;
 
         // Verify SettingChanged event was emitted
-        let mut change_events = app.world_mut()
-            .resource_mut::<Events<SettingChanged>>()
-            .expect("SettingChanged events should exist");
-        
-        let changes: Vec<_> = change_events.drain().collect();
+        let changes = harness.wait_for::<SettingChanged>(1).await;
         assert_eq!(changes.len(), 1, "Should emit one SettingChanged event");
         assert_eq!(changes[0].table, "user_preferences");
         assert_eq!(changes[0].key, "write_test");
+        assert!(changes[0].old_value.is_none(), "New record has no old value");
     }
 
-    #[tokio::test]
+    #[tokio::test(flavor = "multi_thread")]
     async fn test_update_operation() {
-        let mut app = create_test_app().await;
-        let requester = create_test_requester(&mut app);
-
-        // First write initial value
-        let write_id = Uuid::new_v4();
-        let initial_value = serde_json::json!({
-            "theme": "light",
-            "font_size": 12,
-        });
-        
-        app.world_mut().send_event(SettingsWriteRequested {
-            operation_id: write_id,
-            table: "user_preferences".to_string(),
-            key: "update_test".to_string(),
-            value: Value::from(initial_value),
-            requester,
-        });
-
-        app.update();
-        app.update();
+        let (mut harness, requester) = create_test_app().await;
+        let initial = serde_json::json!({"theme": "light", "font_size": 12});
+        write(&mut harness, requester, "update_test", preference("update_test", initial)).await;
 
         // Now update with partial fields
-        let update_id = Uuid::new_v4();
         let mut update_fields = HashMap::new();
-        update_fields.insert("theme".to_string(), Value::from("dark"));
-        update_fields.insert("font_size".to_string(), Value::from(16));
-        
-        app.world_mut().send_event(SettingsUpdateRequested {
-            operation_id: update_id,
+        update_fields.insert("category".to_string(), value(serde_json::json!("appearance")));
+
+        harness.send(SettingsUpdateRequested {
+            operation_id: Uuid::new_v4(),
             table: "user_preferences".to_string(),
             key: "update_test".to_string(),
             fields: update_fields,
             requester,
         });
 
-        app.update();
-        app.update();
-
-        // Check completion
-        let mut completed_events = app.world_mut()
-            .resource_mut::<Events<SettingsUpdateCompleted>>()
-            .expect("SettingsUpdateCompleted events should exist");
-        
-        let events: Vec<_> = completed_events.drain().collect();
-        assert_eq!(events.len(), 1);
+        let events = harness.wait_for::<SettingsUpdateCompleted>(1).await;
         assert!(events[0].result.is_ok(), "Update should succeed");
 
-        // Verify SettingChanged event has old_value populated (task 2.2)
-        let mut change_events = app.world_mut()
-            .resource_mut::<Events<SettingChanged>>()
-            .expect("SettingChanged events should exist");
-        
-        let changes: Vec<_> = change_events.drain().collect();
         // Should have 2 events: write + update
-        assert!(changes.len() >= 1, "Should have SettingChanged events");
-        
+        let changes = harness.wait_for::<SettingChanged>(2).await;
         let update_change = changes.last().expect("Should have update change");
         assert!(
             update_change.old_value.is_some(),
-            "Update should have old_value for audit trail (task 2.2)"
+            "Update should have old_value for audit trail"
+        );
+        assert_eq!(
+            update_change.new_value.get("category"),
+            &value(serde_json::json!("appearance"))
         );
     }
 
-    #[tokio::test]
+    #[tokio::test(flavor = "multi_thread")]
     async fn test_delete_operation() {
-        let mut app = create_test_app().await;
-        let requester = create_test_requester(&mut app);
+        let (mut harness, requester) = create_test_app().await;
+        let record = preference("delete_test", serde_json::json!({"data": "to_delete"}));
+        write(&mut harness, requester, "delete_test", record).await;
 
-        // First write a value
-        let write_id = Uuid::new_v4();
-        let test_value = serde_json::json!({"data": "to_delete"});
-        
-        app.world_mut().send_event(SettingsWriteRequested {
-            operation_id: write_id,
+        harness.send(SettingsDeleteRequested {
+            operation_id: Uuid::new_v4(),
             table: "user_preferences".to_string(),
             key: "delete_test".to_string(),
-            value: Value::from(test_value),
             requester,
         });
 
-        app.update();
-        app.update();
-
-        // Now delete it
-        let delete_id = Uuid::new_v4();
-        app.world_mut().send_event(SettingsDeleteRequested {
-            operation_id: delete_id,
-            table: "user_preferences".to_string(),
-            key: "delete_test".to_string(),
-            requester,
-        });
-
-        app.update();
-        app.update();
-
-        // Check completion
-        let mut completed_events = app.world_mut()
-            .resource_mut::<Events<SettingsDeleteCompleted>>()
-            .expect("SettingsDeleteCompleted events should exist");
-        
-        let events: Vec<_> = completed_events.drain().collect();
-        assert_eq!(events.len(), 1);
-        
+        let events = harness.wait_for::<SettingsDeleteCompleted>(1).await;
         let result = &events[0].result;
         assert!(result.is_ok(), "Delete should succeed");
         assert_eq!(result.as_ref().unwrap(), &true, "Should return true (existed)");
 
-        // Verify SettingChanged event with old_value and Value::default() (task 2.3)
-        let mut change_events = app.world_mut()
-            .resource_mut::<Events<SettingChanged>>()
-            .expect("SettingChanged events should exist");
-        
-        let changes: Vec<_> = change_events.drain().collect();
+        // Verify SettingChanged event with old_value and Value::default()
+        let changes = harness.wait_for::<SettingChanged>(2).await;
         let delete_change = changes.last().expect("Should have delete change");
-        
         assert!(
             delete_change.old_value.is_some(),
             "Delete should have old_value for audit"
@@ -389,89 +296,117 @@ mod integration_tests {
         );
     }
 
-    #[tokio::test]
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "write_audit_trail does not write the settings_history schema's fields yet"]
     async fn test_audit_trail_write() {
-        let mut app = create_test_app().await;
-        let requester = create_test_requester(&mut app);
-
-        // Write a value - should trigger audit trail
-        let operation_id = Uuid::new_v4();
-        let test_value = serde_json::json!({"audit_test": "value"});
-        
-        app.world_mut().send_event(SettingsWriteRequested {
-            operation_id,
-            table: "user_preferences".to_string(),
-            key: "audit_write".to_string(),
-            value: Value::from(test_value),
-            requester,
-        });
-
-        app.update();
-        app.update();
-
-        // Audit trail system should write to settings_history
-        // We'll query settings_history to verify
-        let query_id = Uuid::new_v4();
-        app.world_mut().send_event(SettingsQueryRequested {
-            operation_id: query_id,
-            query: "SELECT * FROM settings_history WHERE table = 'user_preferences' AND key = 'audit_write'".to_string(),
+        let (mut harness, requester) = create_test_app().await;
+        let record = preference("audit_write", serde_json::json!({"audit_test": "value"}));
+        write(&mut harness, requester, "audit_write", record).await;
+        // Give the detached audit task time to finish
+        harness.run_frames(10).await;
+
+        harness.send(SettingsQueryRequested {
+            operation_id: Uuid::new_v4(),
+            query: "SELECT * FROM settings_history WHERE table_name = 'user_preferences' \
+                    AND record_id = 'audit_write'"
+                .to_string(),
             params: None,
             requester,
         });
 
-        app.update();
-        app.update();
-
-        // Check audit entries exist
-        let mut query_events = app.world_mut()
-            .resource_mut::<Events<SettingsQueryCompleted>>()
-            .expect("SettingsQueryCompleted events should exist");
-        
-        let events: Vec<_> = query_events.drain().collect();
-        assert_eq!(events.len(), 1);
-        
-        if let Ok(results) = &events[0].result {
-            assert!(
+        let events = harness.wait_for::<SettingsQueryCompleted>(1).await;
+        match &events[0].result {
+            Ok(results) => assert!(
                 !results.is_empty(),
                 "Audit trail should have recorded the write operation"
-            );
-        } else {
-            panic!("Query failed: {:?}", events[0].result);
+            ),
+            Err(e) => panic!("Query failed: {:?}", e),
         }
     }
 
-    #[tokio::test]
+    #[tokio::test(flavor = "multi_thread")]
     async fn test_invalid_table_rejected() {
-        let mut app = create_test_app().await;
-        let requester = create_test_requester(&mut app);
+        let (mut harness, requester) = create_test_app().await;
 
-        let operation_id = Uuid::new_v4();
-        
-        app.world_mut().send_event(SettingsReadRequested {
-            operation_id,
+        harness.send(SettingsReadRequested {
+            operation_id: Uuid::new_v4(),
             table: "invalid_table; DROP TABLE users".to_string(),
             key: "test".to_string(),
             requester,
         });
 
-        app.update();
-        app.update();
-
         // Should get error event
-        let mut completed_events = app.world_mut()
-            .resource_mut::<Events<SettingsReadCompleted>>()
-            .expect("SettingsReadCompleted events should exist");
-        
-        let events: Vec<_> = completed_events.drain().collect();
+        let events = harness.wait_for::<SettingsReadCompleted>(1).await;
         assert_eq!(events.len(), 1);
         assert!(events[0].result.is_err(), "Invalid table should be rejected");
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tests_use_separate_namespaces() {
+        let (mut first, requester) = create_test_app().await;
+        let record = preference("isolated", serde_json::json!({"theme": "dark"}));
+        write(&mut first, requester, "isolated", record).await;
+
+        let (mut second, requester) = create_test_app().await;
+        second.send(SettingsReadRequested {
+            operation_id: Uuid::new_v4(),
+            table: "user_preferences".to_string(),
+            key: "isolated".to_string(),
+            requester,
+        });
+        let events = second.wait_for::<SettingsReadCompleted>(1).await;
+        assert!(matches!(events[0].result, Ok(None)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_direct_writes_arrive_as_table_changes() {
+        let (mut harness, _) = create_test_app().await;
+        // Let the live queries open before writing
+        harness.run_frames(20).await;
+
+        harness
+            .database
+            .query(
+                "CREATE hotkey_settings:launcher SET hotkey_id = 'launcher', \
+                 modifiers = ['cmd'], key_code = 'space', description = 'Open launcher'",
+            )
+            .await
+            .and_then(|response| {
+                response
+                    .check()
+                    .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+            })
+            .expect("Create should succeed");
+        let changes = harness.wait_for::<HotkeySettingsChanged>(1).await;
+        assert_eq!(changes[0].table, "hotkey_settings");
+        assert_eq!(changes[0].action, LiveAction::Create);
+        assert_eq!(changes[0].record.key_code, "space");
+        assert_eq!(changes[0].record.modifiers, vec!["cmd".to_string()]);
+        assert!(changes[0].record.enabled, "Schema default should be filled in");
+
+        harness
+            .database
+            .query("DELETE hotkey_settings:launcher")
+            .await
+            .expect("Delete should succeed");
+        let changes = harness.wait_for::<HotkeySettingsChanged>(1).await;
+        assert_eq!(changes[0].action, LiveAction::Delete);
+        assert_eq!(changes[0].record.hotkey_id, "launcher");
+
+        harness
+            .database
+            .query("UPSERT appearance_settings:main SET theme_dark = 'midnight'")
+            .await
+            .expect("Upsert should succeed");
+        let changes = harness.wait_for::<AppearanceSettingsChanged>(1).await;
+        assert_eq!(changes[0].table, "appearance_settings");
+        assert_eq!(changes[0].record.theme_dark, "midnight");
+        assert_eq!(changes[0].record.text_size, "medium");
+    }
 }
 
-// Migration tests are disabled pending DatabaseService test API
-#[cfg(all(test, feature = "integration-tests"))]
+#[cfg(test)]
 mod migration_tests {
-    use std::path::PathBuf;
     use tempfile::TempDir;
     use std::fs;
     use serde_json::json;
@@ -549,3 +484,113 @@ mod migration_tests {
         assert!(result.is_ok(), "Migration should succeed with no files");
     }
 }
+
+#[cfg(test)]
+mod schema_migration_tests {
+    use action_items_ecs_surrealdb::{DatabaseService, Migration, MigrationError};
+
+    use crate::schema::{USER_SETTINGS_MIGRATIONS, USER_SETTINGS_SCHEMA};
+
+    async fn create_test_database() -> DatabaseService {
+        DatabaseService::new_in_memory()
+            .await
+            .expect("Failed to create test database")
+    }
+
+    async fn record_count(db: &DatabaseService, query: &str) -> usize {
+        let mut response = db.query(query).await.expect("Query should succeed");
+        response
+            .take::<Vec<surrealdb::Value>>(0)
+            .expect("Query should return records")
+            .len()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_migrations_are_recorded_once() {
+        let db = create_test_database().await;
+
+        let report = db.migrate(USER_SETTINGS_MIGRATIONS, false).await.expect("Should migrate");
+        assert_eq!(report.applied, [1]);
+        assert_eq!(db.applied_migrations().await.unwrap(), [1]);
+
+        let report = db.migrate(USER_SETTINGS_MIGRATIONS, false).await.expect("Should migrate");
+        assert!(report.applied.is_empty(), "Applied migrations should be skipped");
+        assert_eq!(report.already_applied, [1]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_schema_from_before_version_tracking_migrates() {
+        let db = create_test_database().await;
+        db.execute_schema(USER_SETTINGS_SCHEMA).await.expect("Schema should apply");
+
+        let report = db.migrate(USER_SETTINGS_MIGRATIONS, false).await.expect("Should migrate");
+        assert_eq!(report.applied, [1]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_dry_run_changes_nothing() {
+        let db = create_test_database().await;
+
+        let report = db.migrate(USER_SETTINGS_MIGRATIONS, true).await.expect("Dry run should work");
+        assert!(report.dry_run);
+        assert_eq!(report.applied, [1]);
+        assert!(db.applied_migrations().await.unwrap().is_empty());
+        assert_eq!(
+            record_count(&db, "SELECT * FROM user_preferences").await,
+            0,
+            "Dry run should not define tables"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_failed_migration_leaves_data_untouched() {
+        const MIGRATIONS: &[Migration] = &[
+            USER_SETTINGS_MIGRATIONS[0],
+            Migration {
+                version: 2,
+                name: "broken",
+                up: "DELETE user_preferences; THROW 'broken migration';",
+            },
+            Migration {
+                version: 3,
+                name: "after_broken",
+                up: "DEFINE FIELD IF NOT EXISTS locale ON user_preferences TYPE string;",
+            },
+        ];
+        let db = create_test_database().await;
+        db.migrate(&MIGRATIONS[..1], false).await.expect("Should migrate");
+        db.query("CREATE user_preferences:main SET key = 'main', value = { theme: 'dark' }")
+            .await
+            .expect("Should create record");
+
+        let Err(MigrationError::Failed(report)) = db.migrate(MIGRATIONS, false).await else {
+            panic!("Migration 2 should fail");
+        };
+        let failure = report.failed.as_ref().expect("Report should name the failure");
+        assert_eq!(failure.version, 2);
+        assert!(failure.reason.contains("broken migration"), "{}", failure.reason);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.not_attempted, [3]);
+
+        assert_eq!(db.applied_migrations().await.unwrap(), [1]);
+        assert_eq!(
+            record_count(&db, "SELECT * FROM user_preferences").await,
+            1,
+            "Rolled back migration should not delete data"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unordered_migrations_rejected() {
+        const MIGRATIONS: &[Migration] = &[
+            Migration { version: 2, name: "second", up: "" },
+            Migration { version: 1, name: "first", up: "" },
+        ];
+        let db = create_test_database().await;
+
+        assert!(matches!(
+            db.migrate(MIGRATIONS, false).await,
+            Err(MigrationError::InvalidMigrations(_))
+        ));
+    }
+}
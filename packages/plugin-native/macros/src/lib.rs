@@ -2,7 +2,7 @@ use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{ItemFn, parse_macro_input};
 
-/// Attribute macro that generates the FFI export for a plugin
+/// Attribute macro that generates the C ABI exports for a plugin
 ///
 /// Example:
 /// ```rust
@@ -23,12 +23,17 @@ pub fn plugin(_attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         #func
 
-        #vis fn #wrapper() -> Box<dyn ::action_items_plugin_native::ffi::LauncherPlugin> {
-            #ident().build()
+        #vis fn #wrapper() -> ::action_items_native::ffi::PluginVTable {
+            #ident().build_ffi()
         }
 
         #[unsafe(no_mangle)]
-        pub extern "C" fn _action_items_create_plugin() -> Box<dyn ::action_items_plugin_native::ffi::LauncherPlugin> {
+        pub extern "C" fn _action_items_plugin_abi_version() -> u32 {
+            ::action_items_native::ffi::PLUGIN_ABI_VERSION
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn _action_items_create_plugin() -> ::action_items_native::ffi::PluginVTable {
             #wrapper()
         }
     })
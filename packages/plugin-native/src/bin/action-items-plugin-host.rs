@@ -0,0 +1,85 @@
+//! Host process running one native plugin outside the launcher
+//!
+//! Usage: `action-items-plugin-host <library> (--socket <path> | --stdio)`
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use action_items_native::Error;
+use action_items_native::ffi::FfiPlugin;
+use action_items_native::ipc;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("action-items-plugin-host: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn run() -> Result<(), Error> {
+    let mut args = std::env::args_os().skip(1);
+    let usage = || Error::HostError("usage: <library> (--socket <path> | --stdio)".to_string());
+    let library = PathBuf::from(args.next().ok_or_else(usage)?);
+    let transport = args.next().ok_or_else(usage)?;
+
+    // Frames go to a copy of stdout taken before the plugin is loaded, so nothing the plugin
+    // prints can end up between them
+    let stdio_output = match transport.to_str() {
+        Some("--stdio") => Some(redirect_stdout()?),
+        #[cfg(unix)]
+        Some("--socket") => None,
+        _ => return Err(usage()),
+    };
+
+    // Load before connecting so a broken library shows up as an exit during startup
+    let plugin = FfiPlugin::load(&library)?;
+
+    match stdio_output {
+        Some(output) => ipc::serve(&plugin, std::io::stdin().lock(), output),
+        #[cfg(unix)]
+        None => {
+            let socket = args.next().ok_or_else(usage)?;
+            let stream = std::os::unix::net::UnixStream::connect(socket)?;
+            ipc::serve(&plugin, stream.try_clone()?, stream)
+        },
+        #[cfg(not(unix))]
+        None => Err(usage()),
+    }
+}
+
+/// Duplicate stdout for the IPC frames and point stdout itself at stderr
+#[cfg(unix)]
+fn redirect_stdout() -> Result<File, Error> {
+    use std::os::fd::AsFd;
+
+    let output = std::io::stdout().as_fd().try_clone_to_owned()?;
+    // SAFETY: dup2 replaces descriptor 1 atomically; the copy above keeps the original open
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(File::from(output))
+}
+
+/// Duplicate stdout for the IPC frames and point stdout itself at stderr
+///
+/// Rust's stdout looks up the standard handle on every write and follows the switch; a C
+/// runtime that captured the handle at startup keeps writing to the original one.
+#[cfg(windows)]
+fn redirect_stdout() -> Result<File, Error> {
+    use std::os::windows::io::AsHandle;
+
+    use windows_sys::Win32::System::Console::{
+        GetStdHandle, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE, SetStdHandle,
+    };
+
+    let output = std::io::stdout().as_handle().try_clone_to_owned()?;
+    // SAFETY: both calls only read or replace the process's standard handle table
+    if unsafe { SetStdHandle(STD_OUTPUT_HANDLE, GetStdHandle(STD_ERROR_HANDLE)) } == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(File::from(output))
+}
@@ -79,6 +79,7 @@ impl PluginBuilder {
                 max_launcher_version: None,
                 update_url: None,
                 changelog_url: None,
+                resource_limits: Default::default(),
             },
             search_handler: None,
             action_handler: None,
@@ -197,9 +198,8 @@ impl PluginBuilder {
         })
     }
 
-    /// Build the plugin and return an FFI-safe pointer (for use in extern functions)
-    #[allow(dead_code)]
-    pub fn build_ffi(self) -> crate::ffi::LauncherPluginFFI {
+    /// Build the plugin and wrap it in its C ABI vtable (for use in extern functions)
+    pub fn build_ffi(self) -> crate::ffi::PluginVTable {
         let plugin = self.build();
         crate::ffi::ffi_helpers::plugin_to_ffi(plugin)
     }
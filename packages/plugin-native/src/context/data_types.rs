@@ -42,3 +42,16 @@ pub struct HttpResponseData {
     pub headers: HashMap<String, String>,
     pub body: Vec<u8>,
 }
+
+/// How a toast is presented
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToastStyle {
+    #[default]
+    Success,
+    Failure,
+    /// Ongoing work, replaced by a later toast
+    Animated,
+    /// Title-only heads-up display shown after the launcher window closes
+    Hud,
+}
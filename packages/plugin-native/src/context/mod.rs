@@ -16,14 +16,15 @@ pub use commands::{
     ClipboardAction, ClipboardCommand, HttpCommand, NotificationCommand, NotificationUrgency,
     StorageAction, StorageCommand,
 };
-pub use data_types::{HttpMethod, HttpRequest, HttpResponseData};
+pub use data_types::{HttpMethod, HttpRequest, HttpResponseData, ToastStyle};
 pub use requests::{
-    ClipboardReadRequest, ClipboardWriteRequest, NotificationRequest, StorageReadRequest,
-    StorageWriteRequest,
+    ClipboardReadRequest, ClipboardWriteRequest, FileReadRequest, FileWriteRequest,
+    NotificationRequest, OpenUrlRequest, PreferencesReadRequest, SearchIngestRequest,
+    ShellExecuteRequest, StorageReadRequest, StorageWriteRequest, ToastRequest,
 };
 pub use responses::{
-    ClipboardReadResponse, ClipboardWriteResponse, HttpResponse, NotificationResponse,
-    StorageReadResponse, StorageWriteResponse,
+    ClipboardReadResponse, ClipboardWriteResponse, HostCallResponse, HttpResponse,
+    NotificationResponse, StorageReadResponse, StorageWriteResponse,
 };
 pub use services::{
     CacheService, ClipboardAccess, HttpClient, NotificationService, StorageService,
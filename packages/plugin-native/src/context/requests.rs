@@ -1,5 +1,10 @@
+use std::path::PathBuf;
+
+use action_items_common::plugin_interface::ActionItem;
 use bevy::prelude::*;
 
+use super::data_types::ToastStyle;
+
 // Service request events - plugins send these to request services
 
 /// Request to read from clipboard
@@ -43,3 +48,74 @@ pub struct StorageWriteRequest {
     pub key: String,
     pub value: String,
 }
+
+/// Request to read a file as UTF-8 text
+#[derive(Event, Debug, Clone)]
+pub struct FileReadRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    /// Plugin export called with the result
+    pub callback: String,
+    pub path: PathBuf,
+}
+
+/// Request to write UTF-8 text to a file, replacing its contents
+#[derive(Event, Debug, Clone)]
+pub struct FileWriteRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    pub callback: String,
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Request to run a program and collect its output
+#[derive(Event, Debug, Clone)]
+pub struct ShellExecuteRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    pub callback: String,
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    /// The whole environment of the program: the launcher variables the plugin was granted
+    pub env: Vec<(String, String)>,
+}
+
+/// Request to read the plugin's preferences, or a single one by key
+#[derive(Event, Debug, Clone)]
+pub struct PreferencesReadRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    pub callback: String,
+    pub key: Option<String>,
+}
+
+/// Request to show a toast or HUD message
+#[derive(Event, Debug, Clone)]
+pub struct ToastRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    pub callback: String,
+    pub style: ToastStyle,
+    pub title: String,
+    pub message: Option<String>,
+}
+
+/// Request to open a URL in the default application
+#[derive(Event, Debug, Clone)]
+pub struct OpenUrlRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    pub callback: String,
+    pub url: String,
+}
+
+/// Request to add items to the launcher's search index
+#[derive(Event, Debug, Clone)]
+pub struct SearchIngestRequest {
+    pub plugin_id: String,
+    pub request_id: String,
+    pub callback: String,
+    pub items: Vec<ActionItem>,
+}
@@ -51,3 +51,13 @@ pub struct HttpResponse {
     pub request_id: String,
     pub result: Result<HttpResponseData, String>,
 }
+
+/// Result of an Extism host call that answers through a plugin callback
+#[derive(Event, Clone, Debug)]
+pub struct HostCallResponse {
+    pub plugin_id: String,
+    pub request_id: String,
+    /// Plugin export called with the result
+    pub callback: String,
+    pub result: Result<serde_json::Value, String>,
+}
@@ -21,6 +21,10 @@ pub enum Error {
     PluginLoadError(String),
     #[error("System error: {0}")]
     SystemError(String),
+    #[error("Plugin host error: {0}")]
+    HostError(String),
+    #[error("Incompatible plugin ABI: plugin uses version {found}, launcher supports {expected}")]
+    IncompatibleAbi { found: u32, expected: u32 },
 }
 
 impl From<extism::Error> for Error {
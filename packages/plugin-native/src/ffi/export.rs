@@ -0,0 +1,159 @@
+//! Plugin side of the C ABI
+
+use std::any::Any;
+use std::ffi::c_void;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use bevy::tasks::block_on;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::{
+    FFI_STATUS_ERROR, FFI_STATUS_OK, FFI_STATUS_PANIC, FfiActionRequest, FfiBuffer, FfiContext,
+    FfiResult, FfiSearchRequest, FfiSlice, LauncherPlugin, PLUGIN_ABI_VERSION, PluginVTable,
+};
+use crate::{Error, PluginContext};
+
+/// Helper functions for exposing a plugin through the C ABI
+pub mod ffi_helpers {
+    use super::*;
+
+    /// Wrap a plugin in a [`PluginVTable`]
+    ///
+    /// The plugin is boxed once more so the instance pointer is a thin pointer. Futures returned
+    /// by the plugin are driven to completion on the calling thread.
+    pub fn plugin_to_ffi(plugin: Box<dyn LauncherPlugin>) -> PluginVTable {
+        let instance = Box::into_raw(Box::new(plugin)) as *mut c_void;
+        PluginVTable {
+            abi_version: PLUGIN_ABI_VERSION,
+            instance,
+            manifest: manifest_export,
+            search: search_export,
+            execute_action: execute_action_export,
+            background_refresh: background_refresh_export,
+            free_buffer: free_buffer_export,
+            destroy: destroy_export,
+        }
+    }
+}
+
+/// # Safety
+/// `instance` must be the pointer created by `plugin_to_ffi` and not yet destroyed.
+unsafe fn plugin<'a>(instance: *const c_void) -> &'a dyn LauncherPlugin {
+    unsafe { &**(instance as *const Box<dyn LauncherPlugin>) }
+}
+
+unsafe extern "C" fn manifest_export(instance: *const c_void) -> FfiResult {
+    guarded(|| Ok(unsafe { plugin(instance) }.manifest()))
+}
+
+unsafe extern "C" fn search_export(instance: *const c_void, request: FfiSlice) -> FfiResult {
+    guarded(|| {
+        let request: FfiSearchRequest = unsafe { decode(request) }?;
+        let context = plugin_context(request.context);
+        block_on(unsafe { plugin(instance) }.search(request.query, context))
+    })
+}
+
+unsafe extern "C" fn execute_action_export(
+    instance: *const c_void,
+    request: FfiSlice,
+) -> FfiResult {
+    guarded(|| {
+        let request: FfiActionRequest = unsafe { decode(request) }?;
+        let context = plugin_context(request.context);
+        block_on(unsafe { plugin(instance) }.execute_action(
+            request.action_id,
+            context,
+            request.metadata,
+        ))
+    })
+}
+
+unsafe extern "C" fn background_refresh_export(
+    instance: *const c_void,
+    context: FfiSlice,
+) -> FfiResult {
+    guarded(|| {
+        let context: FfiContext = unsafe { decode(context) }?;
+        block_on(unsafe { plugin(instance) }.background_refresh(plugin_context(context)))
+    })
+}
+
+unsafe extern "C" fn free_buffer_export(buffer: FfiBuffer) {
+    if !buffer.ptr.is_null() {
+        drop(unsafe { Vec::from_raw_parts(buffer.ptr, buffer.len, buffer.capacity) });
+    }
+}
+
+unsafe extern "C" fn destroy_export(instance: *mut c_void) {
+    if !instance.is_null() {
+        // A panicking destructor must not unwind into the host
+        let _ = catch_unwind(AssertUnwindSafe(|| {
+            drop(unsafe { Box::from_raw(instance as *mut Box<dyn LauncherPlugin>) });
+        }));
+    }
+}
+
+/// Run a call, encoding its outcome and catching panics so they never unwind into the host
+fn guarded<T: Serialize>(call: impl FnOnce() -> Result<T, Error>) -> FfiResult {
+    match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(value)) => match serde_json::to_vec(&value) {
+            Ok(json) => ffi_result(FFI_STATUS_OK, json),
+            Err(e) => ffi_result(FFI_STATUS_ERROR, e.to_string().into_bytes()),
+        },
+        Ok(Err(e)) => ffi_result(FFI_STATUS_ERROR, e.to_string().into_bytes()),
+        Err(panic) => ffi_result(FFI_STATUS_PANIC, panic_message(panic).into_bytes()),
+    }
+}
+
+fn ffi_result(status: u32, payload: Vec<u8>) -> FfiResult {
+    let mut payload = std::mem::ManuallyDrop::new(payload);
+    FfiResult {
+        status,
+        payload: FfiBuffer {
+            ptr: payload.as_mut_ptr(),
+            len: payload.len(),
+            capacity: payload.capacity(),
+        },
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// # Safety
+/// `slice` must point to `len` readable bytes for the duration of the call.
+unsafe fn decode<T: DeserializeOwned>(slice: FfiSlice) -> Result<T, Error> {
+    let bytes = if slice.ptr.is_null() {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(slice.ptr, slice.len) }
+    };
+    Ok(serde_json::from_slice(bytes)?)
+}
+
+/// Build the plugin's context from what the host sent
+///
+/// Storage requests are served by the plugin's own [`StorageService`](crate::StorageService);
+/// the event channels have no host-side receiver across the C ABI.
+fn plugin_context(context: FfiContext) -> PluginContext {
+    let (storage_read_sender, _storage_read_receiver) = crossbeam_channel::unbounded();
+    let (storage_write_sender, _storage_write_receiver) = crossbeam_channel::unbounded();
+    PluginContext {
+        config: context.config,
+        preferences: context.preferences,
+        environment: context.environment,
+        ..PluginContext::with_service_bridge(
+            context.plugin_id,
+            storage_read_sender,
+            storage_write_sender,
+            context.data_dir,
+        )
+    }
+}
@@ -0,0 +1,167 @@
+//! Launcher side of the C ABI
+
+use std::path::Path;
+
+use action_items_common::plugin_interface::{ActionItem, PluginManifest};
+use libloading::Library;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{
+    ABI_VERSION_SYMBOL, AbiVersionFn, CREATE_PLUGIN_SYMBOL, CreatePluginFn, FFI_STATUS_ERROR,
+    FFI_STATUS_OK, FFI_STATUS_PANIC, FfiActionRequest, FfiContext, FfiResult, FfiSearchRequest,
+    FfiSlice, PLUGIN_ABI_VERSION, PluginVTable,
+};
+use crate::Error;
+
+/// A native plugin instance reached through its [`PluginVTable`]
+///
+/// Calls block until the plugin returns, so they belong on a task pool rather than the main
+/// thread.
+pub struct FfiPlugin {
+    vtable: PluginVTable,
+    // Dropped after the instance is destroyed, keeping the plugin's code mapped until then
+    _library: Option<Library>,
+}
+
+// SAFETY: plugins are `LauncherPlugin: Send + Sync`, and the vtable only passes owned buffers
+unsafe impl Send for FfiPlugin {}
+unsafe impl Sync for FfiPlugin {}
+
+impl FfiPlugin {
+    /// Load a plugin library, refusing it unless it was built for [`PLUGIN_ABI_VERSION`]
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        // SAFETY: loading runs the library's initializers; plugins are trusted to that extent
+        let library = unsafe { Library::new(path) }.map_err(|e| {
+            Error::PluginLoadError(format!("Failed to load library {}: {e}", path.display()))
+        })?;
+
+        // Check the version before calling anything else the library exports
+        let vtable = {
+            let abi_version =
+                unsafe { library.get::<AbiVersionFn>(ABI_VERSION_SYMBOL) }.map_err(|_| {
+                    Error::PluginLoadError(format!(
+                        "{} does not export a plugin ABI version; rebuild it with the current \
+                         action_items_native",
+                        path.display()
+                    ))
+                })?;
+            check_abi_version(abi_version())?;
+
+            let create =
+                unsafe { library.get::<CreatePluginFn>(CREATE_PLUGIN_SYMBOL) }.map_err(|e| {
+                    Error::PluginLoadError(format!(
+                        "{} is missing its plugin constructor: {e}",
+                        path.display()
+                    ))
+                })?;
+            create()
+        };
+
+        // SAFETY: the vtable was produced by the library's constructor, which stays loaded
+        let mut plugin = unsafe { Self::from_vtable(vtable) }?;
+        plugin._library = Some(library);
+        Ok(plugin)
+    }
+
+    /// Take ownership of a plugin instance
+    ///
+    /// # Safety
+    /// The vtable must come from `ffi_helpers::plugin_to_ffi` and its code must stay loaded for
+    /// the lifetime of the returned value.
+    pub unsafe fn from_vtable(vtable: PluginVTable) -> Result<Self, Error> {
+        let plugin = Self {
+            vtable,
+            _library: None,
+        };
+        // Destroying a vtable of another version could itself be unsound, so it is leaked
+        if let Err(e) = check_abi_version(plugin.vtable.abi_version) {
+            std::mem::forget(plugin);
+            return Err(e);
+        }
+        Ok(plugin)
+    }
+
+    pub fn manifest(&self) -> Result<PluginManifest, Error> {
+        let result = unsafe { (self.vtable.manifest)(self.vtable.instance) };
+        self.decode(result)
+    }
+
+    pub fn search(&self, query: String, context: FfiContext) -> Result<Vec<ActionItem>, Error> {
+        let request = FfiSearchRequest { query, context };
+        self.call(self.vtable.search, &request)
+    }
+
+    pub fn execute_action(
+        &self,
+        action_id: String,
+        context: FfiContext,
+        metadata: Option<Value>,
+    ) -> Result<(), Error> {
+        let request = FfiActionRequest {
+            action_id,
+            context,
+            metadata,
+        };
+        self.call(self.vtable.execute_action, &request)
+    }
+
+    pub fn background_refresh(&self, context: FfiContext) -> Result<(), Error> {
+        self.call(self.vtable.background_refresh, &context)
+    }
+
+    fn call<T: DeserializeOwned>(
+        &self,
+        function: unsafe extern "C" fn(*const std::ffi::c_void, FfiSlice) -> FfiResult,
+        request: &impl Serialize,
+    ) -> Result<T, Error> {
+        let request = serde_json::to_vec(request)?;
+        let slice = FfiSlice {
+            ptr: request.as_ptr(),
+            len: request.len(),
+        };
+        // SAFETY: the request outlives the call and the instance is alive until drop
+        let result = unsafe { function(self.vtable.instance, slice) };
+        self.decode(result)
+    }
+
+    /// Decode a call's outcome and hand its buffer back to the plugin's allocator
+    fn decode<T: DeserializeOwned>(&self, result: FfiResult) -> Result<T, Error> {
+        let payload = if result.payload.ptr.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(result.payload.ptr, result.payload.len) }.to_vec()
+        };
+        unsafe { (self.vtable.free_buffer)(result.payload) };
+
+        match result.status {
+            FFI_STATUS_OK => Ok(serde_json::from_slice(&payload)?),
+            FFI_STATUS_ERROR => Err(Error::PluginError(String::from_utf8_lossy(&payload).into())),
+            FFI_STATUS_PANIC => Err(Error::PluginError(format!(
+                "Plugin panicked: {}",
+                String::from_utf8_lossy(&payload)
+            ))),
+            status => Err(Error::PluginError(format!(
+                "Unknown plugin call status {status}"
+            ))),
+        }
+    }
+}
+
+impl Drop for FfiPlugin {
+    fn drop(&mut self) {
+        unsafe { (self.vtable.destroy)(self.vtable.instance) };
+    }
+}
+
+fn check_abi_version(found: u32) -> Result<(), Error> {
+    if found == PLUGIN_ABI_VERSION {
+        Ok(())
+    } else {
+        Err(Error::IncompatibleAbi {
+            found,
+            expected: PLUGIN_ABI_VERSION,
+        })
+    }
+}
@@ -0,0 +1,147 @@
+//! Stable C ABI between the launcher and native plugin libraries
+//!
+//! A plugin library exports two symbols, both generated by the [`plugin`](crate::plugin) macro:
+//!
+//! - [`ABI_VERSION_SYMBOL`], an [`AbiVersionFn`] returning the [`PLUGIN_ABI_VERSION`] the
+//!   plugin was built against
+//! - [`CREATE_PLUGIN_SYMBOL`], a [`CreatePluginFn`] returning a [`PluginVTable`]
+//!
+//! The vtable holds only `extern "C"` function pointers and plain data. Manifests, action
+//! items and requests cross the boundary as JSON, so host and plugin may be built with
+//! different compilers and crate versions as long as their ABI versions match.
+
+mod export;
+mod host;
+
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use action_items_common::plugin_interface::{ActionItem, PluginManifest};
+pub use export::ffi_helpers;
+pub use host::FfiPlugin;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Error, PluginContext};
+
+/// FFI-safe boxed future type
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send>>;
+
+/// Main plugin trait - this is what plugins implement
+pub trait LauncherPlugin: Send + Sync {
+    /// Get plugin metadata
+    fn manifest(&self) -> PluginManifest;
+
+    /// Search for items
+    fn search(&self, query: String, context: PluginContext) -> BoxFuture<Vec<ActionItem>>;
+
+    /// Execute an action
+    fn execute_action(
+        &self,
+        action_id: String,
+        context: PluginContext,
+        metadata: Option<Value>,
+    ) -> BoxFuture<()>;
+
+    /// Optional: background refresh
+    fn background_refresh(&self, _context: PluginContext) -> BoxFuture<()> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Version of the plugin ABI; bumped whenever [`PluginVTable`] or a request type changes
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Symbol of the plugin's [`AbiVersionFn`]
+pub const ABI_VERSION_SYMBOL: &[u8] = b"_action_items_plugin_abi_version";
+
+/// Symbol of the plugin's [`CreatePluginFn`]
+pub const CREATE_PLUGIN_SYMBOL: &[u8] = b"_action_items_create_plugin";
+
+/// Export reporting the ABI version a plugin was built against
+pub type AbiVersionFn = extern "C" fn() -> u32;
+
+/// Export creating the plugin instance
+pub type CreatePluginFn = extern "C" fn() -> PluginVTable;
+
+/// Call succeeded; the payload is the JSON-encoded result
+pub const FFI_STATUS_OK: u32 = 0;
+/// Call failed; the payload is the UTF-8 error message
+pub const FFI_STATUS_ERROR: u32 = 1;
+/// Plugin panicked; the payload is the UTF-8 panic message
+pub const FFI_STATUS_PANIC: u32 = 2;
+
+/// Borrowed bytes passed from the host to the plugin
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct FfiSlice {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+/// Bytes allocated by the plugin and released with [`PluginVTable::free_buffer`]
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+/// Outcome of a call into the plugin
+#[repr(C)]
+#[derive(Debug)]
+pub struct FfiResult {
+    /// One of [`FFI_STATUS_OK`], [`FFI_STATUS_ERROR`] or [`FFI_STATUS_PANIC`]
+    pub status: u32,
+    pub payload: FfiBuffer,
+}
+
+/// Function table describing a plugin instance
+///
+/// Every function takes `instance` as its first argument. Request slices hold JSON encoded
+/// [`FfiSearchRequest`], [`FfiActionRequest`] or [`FfiContext`] values.
+#[repr(C)]
+pub struct PluginVTable {
+    pub abi_version: u32,
+    pub instance: *mut c_void,
+    pub manifest: unsafe extern "C" fn(instance: *const c_void) -> FfiResult,
+    pub search: unsafe extern "C" fn(instance: *const c_void, request: FfiSlice) -> FfiResult,
+    pub execute_action:
+        unsafe extern "C" fn(instance: *const c_void, request: FfiSlice) -> FfiResult,
+    pub background_refresh:
+        unsafe extern "C" fn(instance: *const c_void, context: FfiSlice) -> FfiResult,
+    pub free_buffer: unsafe extern "C" fn(buffer: FfiBuffer),
+    pub destroy: unsafe extern "C" fn(instance: *mut c_void),
+}
+
+/// Serializable part of [`PluginContext`] sent with every call
+///
+/// The plugin builds its own services around it on its side of the boundary.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FfiContext {
+    pub plugin_id: String,
+    pub config: HashMap<String, Value>,
+    pub preferences: HashMap<String, Value>,
+    pub environment: HashMap<String, String>,
+    /// Directory holding the per-plugin storage directories
+    pub data_dir: PathBuf,
+}
+
+/// Request passed to [`PluginVTable::search`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FfiSearchRequest {
+    pub query: String,
+    pub context: FfiContext,
+}
+
+/// Request passed to [`PluginVTable::execute_action`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FfiActionRequest {
+    pub action_id: String,
+    pub context: FfiContext,
+    pub metadata: Option<Value>,
+}
@@ -0,0 +1,18 @@
+//! Out-of-process plugin hosting
+//!
+//! The `action-items-plugin-host` binary loads one native plugin through the C ABI and serves
+//! it with [`serve`]. The launcher talks to it through an [`IsolatedPlugin`], so a plugin that
+//! crashes or hangs takes down only its host. On Unix the host connects back over a socket;
+//! elsewhere it uses its stdin and stdout.
+
+mod protocol;
+mod server;
+mod supervisor;
+
+pub use protocol::{
+    HostRequest, MAX_FRAME_LEN, RequestFrame, ResponseFrame, read_frame, write_frame,
+};
+pub use server::serve;
+pub use supervisor::{
+    HostExit, IsolatedPlugin, PLUGIN_HOST_BINARY, SupervisorConfig, default_host_path,
+};
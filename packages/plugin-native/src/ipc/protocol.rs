@@ -0,0 +1,78 @@
+//! Framed messages exchanged between the launcher and a plugin host process
+//!
+//! Every frame is a little-endian `u32` length followed by that many bytes of JSON.
+
+use std::io::{self, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ffi::{FfiActionRequest, FfiContext, FfiSearchRequest};
+
+/// Largest frame either side accepts, guarding against a corrupted length prefix
+pub const MAX_FRAME_LEN: u32 = 16 * 1024 * 1024;
+
+/// A call into the plugin, mirroring [`LauncherPlugin`](crate::ffi::LauncherPlugin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HostRequest {
+    Manifest,
+    Search(FfiSearchRequest),
+    ExecuteAction(FfiActionRequest),
+    BackgroundRefresh(FfiContext),
+    /// Ask the host to destroy the plugin and exit
+    Shutdown,
+}
+
+/// Frame sent from the launcher to the host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestFrame {
+    pub id: u64,
+    pub request: HostRequest,
+}
+
+/// Frame sent from the host in answer to the request with the same id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseFrame {
+    pub id: u64,
+    /// JSON-encoded return value, or the plugin's error message
+    pub result: Result<Value, String>,
+}
+
+/// Write one frame and flush it
+pub fn write_frame(writer: &mut impl Write, message: &impl Serialize) -> io::Result<()> {
+    let payload = serde_json::to_vec(message)?;
+    let len = u32::try_from(payload.len())
+        .ok()
+        .filter(|len| *len <= MAX_FRAME_LEN)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame of {} bytes exceeds the limit", payload.len()),
+            )
+        })?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+/// Read one frame, returning `None` when the peer closed the stream between frames
+pub fn read_frame<T: DeserializeOwned>(reader: &mut impl Read) -> io::Result<Option<T>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {},
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_le_bytes(len);
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes exceeds the limit"),
+        ));
+    }
+
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    Ok(Some(serde_json::from_slice(&payload)?))
+}
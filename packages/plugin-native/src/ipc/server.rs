@@ -0,0 +1,52 @@
+//! Host process side: serve one plugin over a framed stream
+
+use std::io::{Read, Write};
+
+use serde_json::Value;
+
+use super::protocol::{HostRequest, RequestFrame, ResponseFrame, read_frame, write_frame};
+use crate::Error;
+use crate::ffi::FfiPlugin;
+
+/// Answer requests until the launcher sends [`HostRequest::Shutdown`] or closes the stream
+///
+/// Requests are handled one at a time in the order they arrive.
+pub fn serve(
+    plugin: &FfiPlugin,
+    mut reader: impl Read,
+    mut writer: impl Write,
+) -> Result<(), Error> {
+    while let Some(frame) = read_frame::<RequestFrame>(&mut reader)? {
+        let shutdown = matches!(frame.request, HostRequest::Shutdown);
+        let result = handle(plugin, frame.request).map_err(|e| e.to_string());
+        write_frame(
+            &mut writer,
+            &ResponseFrame {
+                id: frame.id,
+                result,
+            },
+        )?;
+        if shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn handle(plugin: &FfiPlugin, request: HostRequest) -> Result<Value, Error> {
+    match request {
+        HostRequest::Manifest => Ok(serde_json::to_value(plugin.manifest()?)?),
+        HostRequest::Search(request) => Ok(serde_json::to_value(
+            plugin.search(request.query, request.context)?,
+        )?),
+        HostRequest::ExecuteAction(request) => {
+            plugin.execute_action(request.action_id, request.context, request.metadata)?;
+            Ok(Value::Null)
+        },
+        HostRequest::BackgroundRefresh(context) => {
+            plugin.background_refresh(context)?;
+            Ok(Value::Null)
+        },
+        HostRequest::Shutdown => Ok(Value::Null),
+    }
+}
@@ -0,0 +1,543 @@
+//! Launcher side: run a plugin in a supervised host process
+
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+use std::time::{Duration, Instant};
+
+use action_items_common::plugin_interface::{ActionItem, PluginManifest};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::protocol::{HostRequest, RequestFrame, ResponseFrame, read_frame, write_frame};
+use crate::Error;
+use crate::ffi::{FfiActionRequest, FfiContext, FfiSearchRequest};
+
+/// File name of the plugin host executable shipped next to the launcher
+pub const PLUGIN_HOST_BINARY: &str = "action-items-plugin-host";
+
+/// Time a host gets to exit after being asked to shut down
+const SHUTDOWN_GRACE: Duration = Duration::from_millis(500);
+
+/// Time a host that closed its connection gets to exit before it is killed
+const EXIT_GRACE: Duration = Duration::from_millis(100);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Limits for a supervised plugin host
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Plugin host executable
+    pub host_path: PathBuf,
+    /// Time a call may take before the host is killed
+    pub call_timeout: Duration,
+    /// Time the host has to load the plugin and connect
+    pub startup_timeout: Duration,
+    /// Delay before the first restart, doubled after each consecutive failure
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Consecutive failures after which the host is no longer restarted
+    pub max_restarts: u32,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            host_path: default_host_path(),
+            call_timeout: Duration::from_secs(10),
+            startup_timeout: Duration::from_secs(5),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: 5,
+        }
+    }
+}
+
+/// [`PLUGIN_HOST_BINARY`] in the directory of the running executable
+pub fn default_host_path() -> PathBuf {
+    let name = format!("{PLUGIN_HOST_BINARY}{}", std::env::consts::EXE_SUFFIX);
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&name)))
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
+/// Notice that a plugin host died or was killed
+#[derive(Debug, Clone)]
+pub struct HostExit {
+    pub plugin_id: String,
+    pub reason: String,
+    /// False once the supervisor has given up restarting the host
+    pub recoverable: bool,
+}
+
+/// A native plugin running in its own host process
+///
+/// A crash or hang only takes down the host. The supervisor kills hosts that exceed the call
+/// timeout and watches idle hosts, so a host that dies between calls is reported right away.
+/// A new host is started once the restart backoff has passed, or by the next call if that
+/// comes first. Calls to one plugin are serialized.
+pub struct IsolatedPlugin {
+    supervised: Arc<Supervised>,
+}
+
+/// State shared between an [`IsolatedPlugin`] and its supervisor thread
+struct Supervised {
+    manifest: PluginManifest,
+    library: PathBuf,
+    config: SupervisorConfig,
+    exits: Option<Sender<HostExit>>,
+    notices: Sender<Notice>,
+    state: Mutex<HostState>,
+}
+
+#[derive(Default)]
+struct HostState {
+    connection: Option<Connection>,
+    next_id: u64,
+    /// Connections started so far, identifying which one a [`Notice::Closed`] is about
+    generation: u64,
+    /// Host failures since the last successful call
+    failures: u32,
+    restart_at: Option<Instant>,
+}
+
+/// Wake-up of the supervisor thread
+enum Notice {
+    /// The reader of the connection with this generation reached the end of its stream
+    Closed(u64),
+    /// A restart was scheduled
+    Rescheduled,
+}
+
+impl IsolatedPlugin {
+    /// Start a host for the plugin library and read its manifest
+    ///
+    /// Every later death of the host is sent to `exits`.
+    pub fn spawn(
+        library: &Path,
+        config: SupervisorConfig,
+        exits: Option<Sender<HostExit>>,
+    ) -> Result<Self, Error> {
+        let (notices, notice_receiver) = crossbeam_channel::unbounded();
+        let mut connection = Connection::spawn(library, &config, 0, notices.clone())?;
+        let manifest = match connection.call(0, HostRequest::Manifest, config.call_timeout) {
+            Ok(Ok(manifest)) => serde_json::from_value(manifest)?,
+            Ok(Err(e)) => {
+                connection.shutdown();
+                return Err(Error::PluginLoadError(e));
+            },
+            Err(reason) => {
+                let status = connection.terminate(Duration::ZERO);
+                return Err(Error::PluginLoadError(format!("{reason} ({status})")));
+            },
+        };
+
+        let supervised = Arc::new(Supervised {
+            manifest,
+            library: library.to_path_buf(),
+            config,
+            exits,
+            notices,
+            state: Mutex::new(HostState {
+                connection: Some(connection),
+                next_id: 1,
+                ..Default::default()
+            }),
+        });
+        let watched = Arc::downgrade(&supervised);
+        std::thread::Builder::new()
+            .name("plugin-host-supervisor".to_string())
+            .spawn(move || supervise(watched, notice_receiver))?;
+        Ok(Self { supervised })
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.supervised.manifest
+    }
+
+    pub fn search(&self, query: String, context: FfiContext) -> Result<Vec<ActionItem>, Error> {
+        self.supervised
+            .call(HostRequest::Search(FfiSearchRequest { query, context }))
+    }
+
+    pub fn execute_action(
+        &self,
+        action_id: String,
+        context: FfiContext,
+        metadata: Option<Value>,
+    ) -> Result<(), Error> {
+        self.supervised
+            .call(HostRequest::ExecuteAction(FfiActionRequest {
+                action_id,
+                context,
+                metadata,
+            }))
+    }
+
+    pub fn background_refresh(&self, context: FfiContext) -> Result<(), Error> {
+        self.supervised
+            .call(HostRequest::BackgroundRefresh(context))
+    }
+}
+
+/// Supervisor thread: report hosts that die while idle and restart them after the backoff
+///
+/// Holds the plugin only weakly and exits once it is dropped.
+fn supervise(plugin: Weak<Supervised>, notices: Receiver<Notice>) {
+    loop {
+        let restart_at = match plugin.upgrade() {
+            Some(supervised) => supervised.lock_state().restart_at,
+            None => return,
+        };
+        let notice = match restart_at {
+            Some(restart_at) => notices.recv_deadline(restart_at),
+            None => notices.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        let Some(supervised) = plugin.upgrade() else {
+            return;
+        };
+        match notice {
+            Ok(Notice::Closed(generation)) => supervised.host_closed(generation),
+            Ok(Notice::Rescheduled) => {},
+            Err(RecvTimeoutError::Timeout) => supervised.restart_due(),
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+impl Supervised {
+    fn lock_state(&self) -> MutexGuard<'_, HostState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn call<T: DeserializeOwned>(&self, request: HostRequest) -> Result<T, Error> {
+        let mut state = self.lock_state();
+        if state.connection.is_none() {
+            self.restart(&mut state)?;
+        }
+        let id = state.next_id;
+        state.next_id = id.wrapping_add(1);
+        let Some(connection) = state.connection.as_mut() else {
+            return Err(Error::HostError("plugin host is not running".to_string()));
+        };
+
+        match connection.call(id, request, self.config.call_timeout) {
+            Ok(result) => {
+                state.failures = 0;
+                let value = result.map_err(Error::PluginError)?;
+                Ok(serde_json::from_value(value)?)
+            },
+            Err(reason) => Err(Error::HostError(self.fail(&mut state, reason))),
+        }
+    }
+
+    /// Handle the end of a connection's stream outside of a call
+    ///
+    /// Calls notice a dead host themselves, so only a connection that is still current here
+    /// died while idle.
+    fn host_closed(&self, generation: u64) {
+        let mut state = self.lock_state();
+        if state
+            .connection
+            .as_ref()
+            .is_some_and(|connection| connection.generation == generation)
+        {
+            self.fail(&mut state, "plugin host closed the connection".to_string());
+        }
+    }
+
+    /// Start a new host if the scheduled restart is due and no call has started one yet
+    fn restart_due(&self) {
+        let mut state = self.lock_state();
+        let due = state
+            .restart_at
+            .is_some_and(|restart_at| restart_at <= Instant::now());
+        if state.connection.is_none() && due {
+            // Failures are reported by `fail`
+            let _ = self.restart(&mut state);
+        }
+    }
+
+    fn restart(&self, state: &mut HostState) -> Result<(), Error> {
+        if state.failures > self.config.max_restarts {
+            return Err(Error::HostError(format!(
+                "plugin host for '{}' failed {} times in a row and is not restarted",
+                self.manifest.id, state.failures
+            )));
+        }
+        if let Some(restart_at) = state.restart_at {
+            let now = Instant::now();
+            if now < restart_at {
+                return Err(Error::HostError(format!(
+                    "plugin host for '{}' restarts in {:?}",
+                    self.manifest.id,
+                    restart_at - now
+                )));
+            }
+        }
+
+        state.generation += 1;
+        let spawned = Connection::spawn(
+            &self.library,
+            &self.config,
+            state.generation,
+            self.notices.clone(),
+        );
+        match spawned {
+            Ok(connection) => {
+                state.connection = Some(connection);
+                state.restart_at = None;
+                Ok(())
+            },
+            Err(e) => Err(Error::HostError(self.fail(state, e.to_string()))),
+        }
+    }
+
+    /// Tear down the host after a failure, schedule its restart and report it
+    fn fail(&self, state: &mut HostState, reason: String) -> String {
+        let reason = match state.connection.take() {
+            Some(connection) => format!("{reason} ({})", connection.terminate(EXIT_GRACE)),
+            None => reason,
+        };
+        state.failures += 1;
+        let recoverable = state.failures <= self.config.max_restarts;
+        state.restart_at = recoverable.then(|| Instant::now() + self.backoff(state.failures));
+        let _ = self.notices.send(Notice::Rescheduled);
+
+        log::warn!("Plugin host for '{}' failed: {reason}", self.manifest.id);
+        if let Some(exits) = &self.exits {
+            let _ = exits.send(HostExit {
+                plugin_id: self.manifest.id.clone(),
+                reason: reason.clone(),
+                recoverable,
+            });
+        }
+        reason
+    }
+
+    fn backoff(&self, failures: u32) -> Duration {
+        let doublings = failures.saturating_sub(1).min(16);
+        self.config
+            .initial_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.config.max_backoff)
+    }
+}
+
+impl Drop for Supervised {
+    fn drop(&mut self) {
+        let state = self.state.get_mut().unwrap_or_else(PoisonError::into_inner);
+        if let Some(connection) = state.connection.take() {
+            connection.shutdown();
+        }
+    }
+}
+
+/// A running host process and its framed stream
+struct Connection {
+    generation: u64,
+    child: Child,
+    writer: Box<dyn Write + Send>,
+    responses: Receiver<io::Result<ResponseFrame>>,
+}
+
+impl Connection {
+    /// Start a host that connects back over a Unix socket in the user's runtime directory
+    #[cfg(unix)]
+    fn spawn(
+        library: &Path,
+        config: &SupervisorConfig,
+        generation: u64,
+        notices: Sender<Notice>,
+    ) -> Result<Self, Error> {
+        use std::os::unix::net::UnixListener;
+
+        let socket_path = dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(format!(
+                "action-items-plugin-{}.sock",
+                uuid::Uuid::new_v4().simple()
+            ));
+        let listener = UnixListener::bind(&socket_path)?;
+
+        let child = host_command(config, library)
+            .arg("--socket")
+            .arg(&socket_path)
+            .stdin(Stdio::null())
+            .spawn();
+        let stream = child.map_err(Error::from).and_then(|mut child| {
+            match accept_host(&listener, &mut child, config.startup_timeout) {
+                Ok(stream) => Ok((child, stream)),
+                Err(e) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    Err(e)
+                },
+            }
+        });
+        // The socket file is only needed until the host has connected
+        let _ = std::fs::remove_file(&socket_path);
+
+        let (child, stream) = stream?;
+        let reader = stream.try_clone()?;
+        Self::start(generation, notices, child, reader, stream)
+    }
+
+    /// Start a host that talks over its stdin and stdout
+    ///
+    /// The host's first answer is bounded by the call timeout rather than the startup timeout.
+    #[cfg(not(unix))]
+    fn spawn(
+        library: &Path,
+        config: &SupervisorConfig,
+        generation: u64,
+        notices: Sender<Notice>,
+    ) -> Result<Self, Error> {
+        let mut child = host_command(config, library)
+            .arg("--stdio")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let (Some(writer), Some(reader)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(Error::HostError(
+                "plugin host pipes are unavailable".to_string(),
+            ));
+        };
+        Self::start(generation, notices, child, reader, writer)
+    }
+
+    /// Read responses on a thread, which tells the supervisor when the stream ends
+    fn start(
+        generation: u64,
+        notices: Sender<Notice>,
+        child: Child,
+        mut reader: impl Read + Send + 'static,
+        writer: impl Write + Send + 'static,
+    ) -> Result<Self, Error> {
+        let (sender, responses) = crossbeam_channel::unbounded();
+        std::thread::Builder::new()
+            .name("plugin-host-reader".to_string())
+            .spawn(move || {
+                loop {
+                    match read_frame::<ResponseFrame>(&mut reader) {
+                        Ok(Some(frame)) => {
+                            if sender.send(Ok(frame)).is_err() {
+                                break;
+                            }
+                        },
+                        Ok(None) => break,
+                        Err(e) => {
+                            let _ = sender.send(Err(e));
+                            break;
+                        },
+                    }
+                }
+                // Disconnect a waiting call before the supervisor takes the state lock
+                drop(sender);
+                let _ = notices.send(Notice::Closed(generation));
+            })?;
+
+        Ok(Self {
+            generation,
+            child,
+            writer: Box::new(writer),
+            responses,
+        })
+    }
+
+    /// Send a request and wait for its answer; `Err` means the host itself failed
+    fn call(
+        &mut self,
+        id: u64,
+        request: HostRequest,
+        timeout: Duration,
+    ) -> Result<Result<Value, String>, String> {
+        write_frame(&mut self.writer, &RequestFrame { id, request })
+            .map_err(|e| format!("failed to send request to plugin host: {e}"))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.responses.recv_deadline(deadline) {
+                Ok(Ok(frame)) if frame.id == id => return Ok(frame.result),
+                // Answer to a request whose caller already gave up
+                Ok(Ok(_)) => {},
+                Ok(Err(e)) => return Err(format!("lost connection to plugin host: {e}")),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("plugin call timed out after {timeout:?}"));
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("plugin host closed the connection".to_string());
+                },
+            }
+        }
+    }
+
+    /// Ask the host to exit, killing it if it does not
+    fn shutdown(mut self) {
+        let frame = RequestFrame {
+            id: u64::MAX,
+            request: HostRequest::Shutdown,
+        };
+        let _ = write_frame(&mut self.writer, &frame);
+        self.terminate(SHUTDOWN_GRACE);
+    }
+
+    /// Give the host `grace` to exit, then kill it; describes how it ended
+    fn terminate(mut self, grace: Duration) -> String {
+        let deadline = Instant::now() + grace;
+        loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) => return format!("host exited with {status}"),
+                Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+                _ => break,
+            }
+        }
+        let _ = self.child.kill();
+        match self.child.wait() {
+            Ok(status) => format!("host killed, {status}"),
+            Err(e) => format!("host killed, exit status unknown: {e}"),
+        }
+    }
+}
+
+fn host_command(config: &SupervisorConfig, library: &Path) -> Command {
+    let mut command = Command::new(&config.host_path);
+    command.arg(library);
+    command
+}
+
+#[cfg(unix)]
+fn accept_host(
+    listener: &std::os::unix::net::UnixListener,
+    child: &mut Child,
+    timeout: Duration,
+) -> Result<std::os::unix::net::UnixStream, Error> {
+    listener.set_nonblocking(true)?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                return Ok(stream);
+            },
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                if let Some(status) = child.try_wait()? {
+                    return Err(Error::HostError(format!(
+                        "plugin host exited during startup with {status}"
+                    )));
+                }
+                if Instant::now() >= deadline {
+                    return Err(Error::HostError(format!(
+                        "plugin host did not connect within {timeout:?}"
+                    )));
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            },
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
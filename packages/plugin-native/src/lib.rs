@@ -3,6 +3,7 @@ pub mod context;
 pub mod error;
 pub mod events;
 pub mod ffi;
+pub mod ipc;
 pub mod native;
 pub mod traits;
 pub mod views;
@@ -25,6 +26,9 @@ pub use context::{
     ClipboardWriteRequest,
     ClipboardWriteResponse,
     CommandResult as ContextCommandResult,
+    FileReadRequest,
+    FileWriteRequest,
+    HostCallResponse,
     HttpClient,
     HttpMethod,
     HttpRequest,
@@ -33,12 +37,18 @@ pub use context::{
     NotificationRequest,
     NotificationResponse,
     NotificationService,
+    OpenUrlRequest,
     PluginContext,
+    PreferencesReadRequest,
+    SearchIngestRequest,
+    ShellExecuteRequest,
     StorageReadRequest,
     StorageReadResponse,
     StorageService,
     StorageWriteRequest,
     StorageWriteResponse,
+    ToastRequest,
+    ToastStyle,
 };
 pub use error::{Error, Result};
 pub use events::*;
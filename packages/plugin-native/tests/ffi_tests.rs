@@ -0,0 +1,104 @@
+//! Round trips through the plugin C ABI without loading a library
+
+use action_items_native::ffi::{FfiContext, FfiPlugin, PLUGIN_ABI_VERSION, ffi_helpers};
+use action_items_native::{ActionItem, Error, PluginBuilder};
+
+fn item(id: &str, title: String) -> ActionItem {
+    ActionItem {
+        id: id.to_string(),
+        title,
+        subtitle: None,
+        description: None,
+        icon: None,
+        actions: Vec::new(),
+        item_badges: Vec::new(),
+        tags: Vec::new(),
+        metadata: None,
+        score: 1.0,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+fn context() -> FfiContext {
+    FfiContext {
+        plugin_id: "ffi-test".to_string(),
+        data_dir: std::env::temp_dir().join("action-items-ffi-tests"),
+        ..Default::default()
+    }
+}
+
+fn test_plugin() -> FfiPlugin {
+    let plugin = PluginBuilder::new("ffi-test", "FFI Test")
+        .on_search(|query, _context| async move { Ok(vec![item("echo", query)]) })
+        .on_action(|action_id, _context, _metadata| async move {
+            match action_id.as_str() {
+                "fail" => Err(Error::PluginError("action failed".to_string())),
+                "panic" => panic!("action panicked"),
+                _ => Ok(()),
+            }
+        })
+        .build();
+    let vtable = ffi_helpers::plugin_to_ffi(plugin);
+    unsafe { FfiPlugin::from_vtable(vtable) }.expect("current ABI version")
+}
+
+#[test]
+fn manifest_crosses_the_abi() {
+    let manifest = test_plugin().manifest().expect("manifest");
+    assert_eq!(manifest.id, "ffi-test");
+    assert_eq!(manifest.name, "FFI Test");
+    assert!(manifest.capabilities.search);
+}
+
+#[test]
+fn search_results_cross_the_abi() {
+    let results = test_plugin()
+        .search("hello".to_string(), context())
+        .expect("search");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, "echo");
+    assert_eq!(results[0].title, "hello");
+}
+
+#[test]
+fn plugin_errors_are_returned() {
+    let plugin = test_plugin();
+    assert!(
+        plugin
+            .execute_action("ok".to_string(), context(), None)
+            .is_ok()
+    );
+
+    let error = plugin
+        .execute_action("fail".to_string(), context(), None)
+        .expect_err("action should fail");
+    assert!(error.to_string().contains("action failed"));
+}
+
+#[test]
+fn plugin_panics_do_not_unwind_into_the_host() {
+    let plugin = test_plugin();
+    let error = plugin
+        .execute_action("panic".to_string(), context(), None)
+        .expect_err("panic should become an error");
+    assert!(error.to_string().contains("action panicked"));
+
+    // The instance stays usable after a caught panic
+    assert!(plugin.search("again".to_string(), context()).is_ok());
+}
+
+#[test]
+fn mismatched_abi_version_is_rejected() {
+    let mut vtable = PluginBuilder::new("old-plugin", "Old Plugin").build_ffi();
+    vtable.abi_version = PLUGIN_ABI_VERSION + 1;
+
+    match unsafe { FfiPlugin::from_vtable(vtable) } {
+        Err(Error::IncompatibleAbi { found, expected }) => {
+            assert_eq!(found, PLUGIN_ABI_VERSION + 1);
+            assert_eq!(expected, PLUGIN_ABI_VERSION);
+        },
+        Err(e) => panic!("unexpected error: {e}"),
+        Ok(_) => panic!("plugin with a different ABI version was accepted"),
+    }
+}
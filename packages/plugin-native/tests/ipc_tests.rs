@@ -0,0 +1,135 @@
+//! Plugin host protocol and supervision
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use action_items_native::Error;
+use action_items_native::ffi::{FfiContext, FfiSearchRequest};
+use action_items_native::ipc::{
+    HostRequest, IsolatedPlugin, MAX_FRAME_LEN, RequestFrame, SupervisorConfig, read_frame,
+    write_frame,
+};
+
+#[test]
+fn frames_round_trip() {
+    let mut buffer = Vec::new();
+    let frame = RequestFrame {
+        id: 7,
+        request: HostRequest::Search(FfiSearchRequest {
+            query: "hello".to_string(),
+            context: FfiContext::default(),
+        }),
+    };
+    write_frame(&mut buffer, &frame).expect("write");
+    write_frame(
+        &mut buffer,
+        &RequestFrame {
+            id: 8,
+            request: HostRequest::Shutdown,
+        },
+    )
+    .expect("write");
+
+    let mut reader = Cursor::new(buffer);
+    let first: RequestFrame = read_frame(&mut reader).expect("read").expect("frame");
+    assert_eq!(first.id, 7);
+    assert!(matches!(first.request, HostRequest::Search(ref r) if r.query == "hello"));
+    let second: RequestFrame = read_frame(&mut reader).expect("read").expect("frame");
+    assert!(matches!(second.request, HostRequest::Shutdown));
+    assert!(
+        read_frame::<RequestFrame>(&mut reader)
+            .expect("read")
+            .is_none()
+    );
+}
+
+#[test]
+fn oversized_frames_are_rejected() {
+    let mut reader = Cursor::new((MAX_FRAME_LEN + 1).to_le_bytes().to_vec());
+    assert!(read_frame::<RequestFrame>(&mut reader).is_err());
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::os::unix::net::UnixStream;
+
+    use action_items_native::PluginBuilder;
+    use action_items_native::ffi::{FfiPlugin, ffi_helpers};
+    use action_items_native::ipc::{ResponseFrame, serve};
+
+    use super::*;
+
+    fn test_plugin() -> FfiPlugin {
+        let plugin = PluginBuilder::new("ipc-test", "IPC Test")
+            .on_search(|query, _context| async move {
+                Err(Error::PluginError(format!("no results for {query}")))
+            })
+            .build();
+        unsafe { FfiPlugin::from_vtable(ffi_helpers::plugin_to_ffi(plugin)) }.expect("plugin")
+    }
+
+    #[test]
+    fn host_serves_requests_until_shutdown() {
+        let (mut launcher, host) = UnixStream::pair().expect("socket pair");
+        let server = std::thread::spawn(move || {
+            let plugin = test_plugin();
+            serve(&plugin, host.try_clone().expect("clone"), host)
+        });
+
+        write_frame(
+            &mut launcher,
+            &RequestFrame {
+                id: 1,
+                request: HostRequest::Manifest,
+            },
+        )
+        .expect("write");
+        let response: ResponseFrame = read_frame(&mut launcher).expect("read").expect("frame");
+        assert_eq!(response.id, 1);
+        let manifest = response.result.expect("manifest");
+        assert_eq!(manifest["id"], "ipc-test");
+
+        write_frame(
+            &mut launcher,
+            &RequestFrame {
+                id: 2,
+                request: HostRequest::Search(FfiSearchRequest {
+                    query: "nothing".to_string(),
+                    context: FfiContext::default(),
+                }),
+            },
+        )
+        .expect("write");
+        let response: ResponseFrame = read_frame(&mut launcher).expect("read").expect("frame");
+        assert_eq!(response.id, 2);
+        let error = response.result.expect_err("search should fail");
+        assert!(error.contains("no results for nothing"));
+
+        write_frame(
+            &mut launcher,
+            &RequestFrame {
+                id: 3,
+                request: HostRequest::Shutdown,
+            },
+        )
+        .expect("write");
+        let response: ResponseFrame = read_frame(&mut launcher).expect("read").expect("frame");
+        assert_eq!(response.id, 3);
+        server.join().expect("server thread").expect("serve");
+    }
+
+    #[test]
+    fn host_that_exits_during_startup_fails_to_load() {
+        let config = SupervisorConfig {
+            host_path: "/bin/false".into(),
+            startup_timeout: Duration::from_secs(5),
+            ..Default::default()
+        };
+        let result = IsolatedPlugin::spawn("/nonexistent/plugin.so".as_ref(), config, None);
+        match result {
+            Err(Error::HostError(message)) => assert!(message.contains("exited during startup")),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("host that never connects was accepted"),
+        }
+    }
+}
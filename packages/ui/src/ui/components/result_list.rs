@@ -1,3 +1,4 @@
+use action_items_ecs_search::MatchRange;
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -41,6 +42,10 @@ pub struct ActionItemsSearchResultIcon {
 #[derive(Component, Debug)]
 pub struct ActionItemsSearchResultTitle;
 
+/// Component marker for the emphasized query-match spans inside a result title
+#[derive(Component, Debug)]
+pub struct ActionItemsSearchResultTitleHighlight;
+
 /// Component marker for search result item subtitle/description with secondary styling
 #[derive(Component, Debug)]
 pub struct ActionItemsSearchResultSubtitle;
@@ -64,6 +69,7 @@ pub struct ActionItemsSearchResultData {
     pub category: Option<String>,
     pub score: f32,     // Search relevance score for sorting
     pub ranking: usize, // Display ranking position (0-based index)
+    pub title_highlights: Vec<MatchRange>, // Title characters matched by the query
 }
 
 impl ActionItemsSearchResultData {
@@ -78,6 +84,7 @@ impl ActionItemsSearchResultData {
             category: None,
             score: 1.0,
             ranking: 0,
+            title_highlights: Vec::new(),
         }
     }
 
@@ -115,6 +122,12 @@ impl ActionItemsSearchResultData {
         self.ranking = ranking;
         self
     }
+
+    /// Set title character ranges to emphasize as query matches
+    pub fn with_title_highlights(mut self, title_highlights: Vec<MatchRange>) -> Self {
+        self.title_highlights = title_highlights;
+        self
+    }
 }
 
 /// Animation components
@@ -4,13 +4,15 @@
 //! interaction states
 
 use action_items_core::CurrentSearchResults;
+use action_items_ecs_search::highlight_segments;
 use bevy::prelude::*;
 use tracing::{info, warn};
 
 use crate::ui::components::{
     ActionItemsSearchResultBackground, ActionItemsSearchResultData, ActionItemsSearchResultIcon,
     ActionItemsSearchResultItem, ActionItemsSearchResultShortcut, ActionItemsSearchResultSubtitle,
-    ActionItemsSearchResultTitle, ResultsContainer, UiFonts,
+    ActionItemsSearchResultTitle, ActionItemsSearchResultTitleHighlight, ResultsContainer,
+    UiFonts,
 };
 use crate::ui::icons::{LauncherIconCache, get_icon_for_search_result};
 use action_items_ecs_ui::gradients::GradientComponent;
@@ -57,6 +59,7 @@ pub fn render_professional_results(
                 .with_category("General".to_string()) // Default category since ActionItem doesn't have category field
                 .with_score(item.score)
                 .with_ranking(index) // Use index for result ranking display
+                .with_title_highlights(item.title_matches.clone())
         })
         .collect();
 
@@ -135,17 +138,46 @@ pub fn render_professional_results(
                             ..default()
                         },))
                         .with_children(|content_parent| {
-                            // Title
-                            content_parent.spawn((
-                                Text::new(result_data.title.clone()),
-                                TextFont {
-                                    font: ui_fonts.ubuntu_medium.clone(),
-                                    font_size: typography.text_styles.body.font_size,
-                                    ..default()
-                                },
-                                TextColor(theme.colors.text_primary),
-                                ActionItemsSearchResultTitle,
-                            ));
+                            // Title, split into spans so query matches are emphasized
+                            let title_font = TextFont {
+                                font: ui_fonts.ubuntu_medium.clone(),
+                                font_size: typography.text_styles.body.font_size,
+                                ..default()
+                            };
+                            let highlight_font = TextFont {
+                                font: ui_fonts.ubuntu_bold.clone(),
+                                ..title_font.clone()
+                            };
+                            let segments = highlight_segments(
+                                &result_data.title,
+                                &result_data.title_highlights,
+                            );
+
+                            content_parent
+                                .spawn((
+                                    Text::default(),
+                                    title_font.clone(),
+                                    TextColor(theme.colors.text_primary),
+                                    ActionItemsSearchResultTitle,
+                                ))
+                                .with_children(|title_parent| {
+                                    for (segment, highlighted) in segments {
+                                        if highlighted {
+                                            title_parent.spawn((
+                                                TextSpan::new(segment),
+                                                highlight_font.clone(),
+                                                TextColor(theme.colors.accent_blue),
+                                                ActionItemsSearchResultTitleHighlight,
+                                            ));
+                                        } else {
+                                            title_parent.spawn((
+                                                TextSpan::new(segment),
+                                                title_font.clone(),
+                                                TextColor(theme.colors.text_primary),
+                                            ));
+                                        }
+                                    }
+                                });
 
                             // Subtitle (if available)
                             if let Some(subtitle) = &result_data.subtitle